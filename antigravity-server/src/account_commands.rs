@@ -3,6 +3,8 @@ use colored::Colorize;
 use comfy_table::{presets::UTF8_FULL, Cell, Color, Table};
 use std::io::Write;
 
+use antigravity_core::modules::account_pg::PostgresAccountRepository;
+use antigravity_core::modules::repository::AccountRepository;
 use antigravity_core::modules::{account, oauth};
 use antigravity_types::models::TokenData;
 
@@ -21,7 +23,7 @@ pub async fn list_accounts(json: bool) -> Result<()> {
 
     let mut table = Table::new();
     table.load_preset(UTF8_FULL);
-    table.set_header(vec!["Email", "Name", "Gemini", "Claude", "Status"]);
+    table.set_header(vec!["Email", "Name", "Groups", "Gemini", "Claude", "Status"]);
 
     for acc in &accounts {
         let gemini = get_quota(acc, "gemini");
@@ -35,6 +37,7 @@ pub async fn list_accounts(json: bool) -> Result<()> {
         table.add_row(vec![
             Cell::new(&acc.email),
             Cell::new(acc.name.as_deref().unwrap_or("-")),
+            Cell::new(if acc.groups.is_empty() { "-".to_string() } else { acc.groups.join(", ") }),
             Cell::new(&gemini),
             Cell::new(&claude),
             status,
@@ -151,6 +154,43 @@ pub async fn refresh_quota(identifier: &str) -> Result<()> {
     Ok(())
 }
 
+/// Stored in PostgreSQL when DATABASE_URL is set, and in the account's JSON file if present.
+pub async fn set_groups(identifier: &str, groups: &[String]) -> Result<()> {
+    let groups = account::normalize_groups(groups).map_err(|e| anyhow::anyhow!(e))?;
+
+    let email = if let Ok(url) = std::env::var("DATABASE_URL") {
+        let repo = PostgresAccountRepository::connect(&url).await?;
+        let acc = repo
+            .list_accounts()
+            .await?
+            .into_iter()
+            .find(|a| a.email == identifier || a.id == identifier)
+            .context("Account not found")?;
+        repo.update_groups(&acc.id, &groups).await?;
+        if let Ok(mut local) = account::load_account(&acc.id) {
+            local.groups = groups.clone();
+            account::save_account(&local).map_err(|e| anyhow::anyhow!(e))?;
+        }
+        acc.email
+    } else {
+        let accounts = account::list_accounts().map_err(|e| anyhow::anyhow!(e))?;
+        let mut acc = accounts
+            .into_iter()
+            .find(|a| a.email == identifier || a.id == identifier)
+            .context("Account not found")?;
+        acc.groups = groups.clone();
+        account::save_account(&acc).map_err(|e| anyhow::anyhow!(e))?;
+        acc.email
+    };
+
+    if groups.is_empty() {
+        println!("{} Account {} removed from all groups", "✓".green(), email);
+    } else {
+        println!("{} Account {} groups: {}", "✓".green(), email, groups.join(", "));
+    }
+    Ok(())
+}
+
 async fn refresh_all_quotas() -> Result<()> {
     let accounts = account::list_accounts().map_err(|e| anyhow::anyhow!(e))?;
     let enabled: Vec<_> =
@@ -0,0 +1,101 @@
+//! Account group management API handlers.
+
+use axum::{extract::State, http::StatusCode, response::Json};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use antigravity_core::modules::account;
+use antigravity_core::modules::repository::RepositoryError;
+
+use crate::state::AppState;
+
+#[derive(Deserialize)]
+pub struct SetGroupsRequest {
+    pub account_id: String,
+    pub groups: Vec<String>,
+}
+
+#[derive(Serialize)]
+pub struct SetGroupsResponse {
+    pub account_id: String,
+    pub groups: Vec<String>,
+}
+
+pub async fn set_groups_handler(
+    State(state): State<AppState>,
+    Json(payload): Json<SetGroupsRequest>,
+) -> Result<Json<SetGroupsResponse>, (StatusCode, String)> {
+    let groups =
+        account::normalize_groups(&payload.groups).map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+
+    persist_groups(&state, &payload.account_id, &groups).await?;
+    drop(state.reload_accounts().await);
+
+    tracing::info!("Account {} groups set to {:?}", payload.account_id, groups);
+    Ok(Json(SetGroupsResponse { account_id: payload.account_id, groups }))
+}
+
+#[derive(Serialize)]
+pub struct AccountGroup {
+    pub name: String,
+    /// Emails of member accounts
+    pub accounts: Vec<String>,
+}
+
+pub async fn list_groups_handler(
+    State(state): State<AppState>,
+) -> Result<Json<Vec<AccountGroup>>, (StatusCode, String)> {
+    let accounts =
+        state.list_accounts().await.map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+
+    let mut groups: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for acc in accounts {
+        for group in acc.groups {
+            groups.entry(group).or_default().push(acc.email.clone());
+        }
+    }
+    Ok(Json(groups.into_iter().map(|(name, accounts)| AccountGroup { name, accounts }).collect()))
+}
+
+async fn persist_groups(
+    state: &AppState,
+    account_id: &str,
+    groups: &[String],
+) -> Result<(), (StatusCode, String)> {
+    if let Some(repo) = state.repository() {
+        repo.update_groups(account_id, groups).await.map_err(|e| match e {
+            RepositoryError::NotFound(_) => {
+                (StatusCode::NOT_FOUND, format!("Account not found: {account_id}"))
+            },
+            e => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()),
+        })?;
+    }
+
+    // JSON file dual-write; without a repository the file is the only copy
+    let has_repo = state.repository().is_some();
+    let aid = account_id.to_string();
+    let groups = groups.to_vec();
+    let json_result = tokio::task::spawn_blocking(move || match account::load_account(&aid) {
+        Ok(mut acc) => {
+            acc.groups = groups;
+            account::save_account(&acc)
+        },
+        Err(e) if has_repo => {
+            tracing::debug!("No JSON file for account {aid}: {e}");
+            Ok(())
+        },
+        Err(e) => Err(e),
+    })
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, format!("spawn_blocking panicked: {e}")))?;
+
+    match json_result {
+        Ok(()) => Ok(()),
+        Err(e) if has_repo => {
+            tracing::warn!("JSON dual-write failed: {e}");
+            Ok(())
+        },
+        Err(e) if e.starts_with("Account not found") => Err((StatusCode::NOT_FOUND, e)),
+        Err(e) => Err((StatusCode::INTERNAL_SERVER_ERROR, e)),
+    }
+}
@@ -28,7 +28,8 @@ pub async fn set_proxy_handler(
     // Validate account exists BEFORE any network I/O (prevents SSRF port scanning via non-existent accounts)
     let email = get_account_email(&state, &payload.account_id).await?;
 
-    let exit_ip = check_proxy_health(&payload.proxy_url)
+    let health_check_urls = state.inner.proxy_config.read().await.health_check_urls.clone();
+    let exit_ip = check_proxy_health(&payload.proxy_url, &health_check_urls)
         .await
         .map_err(|e| (StatusCode::BAD_REQUEST, format!("Proxy health check failed: {e}")))?;
 
@@ -131,11 +132,15 @@ pub async fn set_proxy_bulk_handler(
 
     const MAX_CONCURRENT_HEALTH_CHECKS: usize = 10;
 
+    let health_check_urls = state.inner.proxy_config.read().await.health_check_urls.clone();
     let health_results: std::collections::HashMap<String, Result<String, String>> =
         futures::stream::iter(valid_urls)
-            .map(|url| async move {
-                let result = check_proxy_health(&url).await;
-                (url, result)
+            .map(|url| {
+                let health_check_urls = &health_check_urls;
+                async move {
+                    let result = check_proxy_health(&url, health_check_urls).await;
+                    (url, result)
+                }
             })
             .buffer_unordered(MAX_CONCURRENT_HEALTH_CHECKS)
             .collect()
@@ -5,24 +5,10 @@ use serde::{Deserialize, Serialize};
 use tokio::task::JoinSet;
 
 use antigravity_core::modules::{account, oauth as core_oauth};
-use antigravity_types::models::TokenData;
+use antigravity_types::models::{AccountInfo, TokenData};
 
 use crate::state::AppState;
 
-#[derive(Serialize)]
-pub struct AccountInfo {
-    pub id: String,
-    pub email: String,
-    pub name: Option<String>,
-    pub disabled: bool,
-    pub proxy_disabled: bool,
-    pub is_current: bool,
-    pub gemini_quota: Option<i32>,
-    pub claude_quota: Option<i32>,
-    pub subscription_tier: Option<String>,
-    pub quota: Option<antigravity_types::models::QuotaData>,
-}
-
 pub async fn list_accounts(
     State(state): State<AppState>,
 ) -> Result<Json<Vec<AccountInfo>>, (StatusCode, String)> {
@@ -52,6 +38,7 @@ pub async fn list_accounts(
                 .map(|m| m.percentage),
             subscription_tier: a.quota.as_ref().and_then(|q| q.subscription_tier.clone()),
             quota: a.quota.clone(),
+            groups: a.groups.clone(),
         })
         .collect();
     Ok(Json(infos))
@@ -80,6 +67,7 @@ pub async fn get_current_account(
                 .map(|m| m.percentage),
             subscription_tier: a.quota.as_ref().and_then(|q| q.subscription_tier.clone()),
             quota: a.quota.clone(),
+            groups: a.groups.clone(),
         }))),
         Ok(None) => Ok(Json(None)),
         Err(e) => Err((StatusCode::INTERNAL_SERVER_ERROR, e)),
@@ -299,6 +287,7 @@ pub async fn add_account_by_token(
                             name: acc.name.clone(),
                             disabled: acc.disabled,
                             proxy_disabled: acc.proxy_disabled,
+                            groups: acc.groups.clone(),
                             is_current: false,
                             gemini_quota: acc
                                 .quota
@@ -18,12 +18,13 @@ pub async fn get_config(
     }
 }
 
+/// Save the GUI's edits of the config returned by [`get_config`].
 pub async fn save_config(
     State(state): State<AppState>,
     Json(payload): Json<AppConfig>,
 ) -> Result<Json<bool>, (StatusCode, String)> {
-    match tokio::task::spawn_blocking(move || core_config::save_config(&payload)).await {
-        Ok(Ok(())) => {
+    match tokio::task::spawn_blocking(move || core_config::save_config_changes(&payload)).await {
+        Ok(Ok(_)) => {
             state.hot_reload_proxy_config().await;
             Ok(Json(true))
         },
@@ -0,0 +1,95 @@
+//! Client API key management handlers.
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::Json,
+};
+
+use antigravity_core::modules::account;
+use antigravity_types::models::{ApiKey, CreateApiKeyRequest, CreatedApiKey, UpdateApiKeyRequest};
+
+use crate::state::AppState;
+
+pub async fn list_keys(State(state): State<AppState>) -> Json<Vec<ApiKey>> {
+    Json(state.api_keys().list())
+}
+
+pub async fn get_key(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<ApiKey>, (StatusCode, String)> {
+    state.api_keys().get(&id).map(Json).ok_or_else(|| not_found(&id))
+}
+
+pub async fn create_key(
+    State(state): State<AppState>,
+    Json(payload): Json<CreateApiKeyRequest>,
+) -> Result<(StatusCode, Json<CreatedApiKey>), (StatusCode, String)> {
+    if payload.label.trim().is_empty() {
+        return Err((StatusCode::BAD_REQUEST, "label must not be empty".to_string()));
+    }
+    validate_group(payload.account_group.as_ref())?;
+    let created = state
+        .api_keys()
+        .create(payload)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+    tracing::info!("Created client API key '{}' ({})", created.key.label, created.key.id);
+    Ok((StatusCode::CREATED, Json(created)))
+}
+
+pub async fn update_key(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Json(payload): Json<UpdateApiKeyRequest>,
+) -> Result<Json<ApiKey>, (StatusCode, String)> {
+    validate_group(payload.account_group.as_ref().and_then(Option::as_ref))?;
+    state
+        .api_keys()
+        .update(&id, payload)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?
+        .map(Json)
+        .ok_or_else(|| not_found(&id))
+}
+
+pub async fn reset_key_usage(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<ApiKey>, (StatusCode, String)> {
+    state
+        .api_keys()
+        .reset_usage(&id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?
+        .map(Json)
+        .ok_or_else(|| not_found(&id))
+}
+
+pub async fn delete_key(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<StatusCode, (StatusCode, String)> {
+    let existed =
+        state.api_keys().delete(&id).await.map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+    if existed {
+        tracing::info!("Deleted client API key {}", id);
+        Ok(StatusCode::NO_CONTENT)
+    } else {
+        Err(not_found(&id))
+    }
+}
+
+fn not_found(id: &str) -> (StatusCode, String) {
+    (StatusCode::NOT_FOUND, format!("API key not found: {}", id))
+}
+
+fn validate_group(group: Option<&String>) -> Result<(), (StatusCode, String)> {
+    match group {
+        Some(group) => account::normalize_groups(std::slice::from_ref(group))
+            .map(drop)
+            .map_err(|e| (StatusCode::BAD_REQUEST, e)),
+        None => Ok(()),
+    }
+}
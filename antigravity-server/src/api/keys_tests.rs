@@ -0,0 +1,78 @@
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::response::Json;
+
+use antigravity_types::models::{CreateApiKeyRequest, UpdateApiKeyRequest};
+
+use super::keys::{create_key, delete_key, get_key, list_keys, reset_key_usage, update_key};
+use crate::test_helpers::test_app_state;
+
+fn create_request(label: &str) -> CreateApiKeyRequest {
+    CreateApiKeyRequest {
+        label: label.to_string(),
+        allowed_models: vec!["gemini-*".to_string()],
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn test_list_keys_empty() {
+    let (state, _tmp) = test_app_state().await;
+    let Json(keys) = list_keys(State(state)).await;
+    assert!(keys.is_empty());
+}
+
+#[tokio::test]
+async fn test_create_key_returns_secret_once() {
+    let (state, _tmp) = test_app_state().await;
+    let (status, Json(created)) =
+        create_key(State(state.clone()), Json(create_request("ci"))).await.unwrap();
+    assert_eq!(status, StatusCode::CREATED);
+    assert!(created.secret.starts_with(&created.key.key_prefix));
+
+    let Json(keys) = list_keys(State(state)).await;
+    assert_eq!(keys.len(), 1);
+    assert_eq!(keys[0].allowed_models, vec!["gemini-*".to_string()]);
+    assert!(keys[0].key_hash.is_empty());
+}
+
+#[tokio::test]
+async fn test_create_key_rejects_empty_label() {
+    let (state, _tmp) = test_app_state().await;
+    let err = create_key(State(state), Json(create_request("  "))).await.unwrap_err();
+    assert_eq!(err.0, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_update_and_reset_key() {
+    let (state, _tmp) = test_app_state().await;
+    let (_, Json(created)) =
+        create_key(State(state.clone()), Json(create_request("alice"))).await.unwrap();
+    let id = created.key.id;
+
+    let update = UpdateApiKeyRequest { enabled: Some(false), ..Default::default() };
+    let Json(updated) =
+        update_key(State(state.clone()), Path(id.clone()), Json(update)).await.unwrap();
+    assert!(!updated.enabled);
+
+    state.api_keys().record_usage(&id, 100);
+    let Json(reset) = reset_key_usage(State(state.clone()), Path(id.clone())).await.unwrap();
+    assert_eq!(reset.usage.requests, 0);
+    assert_eq!(reset.usage.tokens, 0);
+
+    let Json(fetched) = get_key(State(state), Path(id)).await.unwrap();
+    assert!(!fetched.enabled);
+}
+
+#[tokio::test]
+async fn test_delete_key() {
+    let (state, _tmp) = test_app_state().await;
+    let (_, Json(created)) =
+        create_key(State(state.clone()), Json(create_request("bob"))).await.unwrap();
+
+    let status = delete_key(State(state.clone()), Path(created.key.id.clone())).await.unwrap();
+    assert_eq!(status, StatusCode::NO_CONTENT);
+
+    let err = delete_key(State(state), Path(created.key.id)).await.unwrap_err();
+    assert_eq!(err.0, StatusCode::NOT_FOUND);
+}
@@ -2,24 +2,35 @@
 //!
 //! REST API endpoints that mirror the Tauri IPC commands.
 
+mod account_groups;
 mod account_proxy;
 mod accounts;
 mod config;
 mod device;
+mod keys;
 mod monitor;
+mod notifications;
 pub mod oauth;
 mod proxy;
 pub(crate) mod proxy_health;
 mod quota;
 mod resilience;
+mod usage;
 
+#[cfg(test)]
+mod keys_tests;
 #[cfg(test)]
 mod monitor_tests;
 #[cfg(test)]
+mod notifications_tests;
+#[cfg(test)]
 mod proxy_tests;
 #[cfg(test)]
 mod resilience_tests;
+#[cfg(test)]
+mod usage_tests;
 
+use antigravity_types::models::ServerStatus;
 use axum::{
     extract::State,
     http::StatusCode,
@@ -27,7 +38,6 @@ use axum::{
     routing::{delete, get, post},
     Router,
 };
-use serde::Serialize;
 
 use crate::state::AppState;
 
@@ -59,6 +69,9 @@ pub fn router() -> Router<AppState> {
         .route("/accounts/toggle-proxy", post(quota::toggle_proxy_status))
         .route("/accounts/warmup", post(quota::warmup_account))
         .route("/accounts/warmup-all", post(quota::warmup_all_accounts))
+        // Account groups
+        .route("/accounts/groups", get(account_groups::list_groups_handler))
+        .route("/accounts/set-groups", post(account_groups::set_groups_handler))
         // Per-account proxy management
         .route("/accounts/set-proxy", post(account_proxy::set_proxy_handler))
         .route(
@@ -87,11 +100,29 @@ pub fn router() -> Router<AppState> {
             delete(proxy::clear_rate_limit),
         )
         .route("/accounts/reload", post(proxy::reload_accounts))
+        // Client API keys
+        .route("/keys", get(keys::list_keys).post(keys::create_key))
+        .route(
+            "/keys/:id",
+            get(keys::get_key).patch(keys::update_key).delete(keys::delete_key),
+        )
+        .route(
+            "/keys/:id/reset-usage",
+            post(keys::reset_key_usage),
+        )
         // Monitor
         .route("/monitor/requests", get(monitor::get_monitor_requests))
+        .route("/monitor/search", get(monitor::search_monitor_requests))
+        .route("/monitor/stream", get(monitor::stream_monitor_events))
         .route("/monitor/stats", get(monitor::get_monitor_stats))
         .route("/monitor/clear", post(monitor::clear_monitor_logs))
         .route("/monitor/token-stats", get(monitor::get_token_usage_stats))
+        // Spend accounting
+        .route("/usage", get(usage::get_usage))
+        .route("/usage/budgets", get(usage::get_budgets))
+        // Webhook notifications
+        .route("/notifications/deliveries", get(notifications::get_deliveries))
+        .route("/notifications/test", post(notifications::send_test_notification))
         // Config
         .route("/config", get(config::get_config))
         .route("/config", post(config::save_config))
@@ -126,18 +157,10 @@ async fn api_not_found() -> impl IntoResponse {
     (StatusCode::NOT_FOUND, Json(serde_json::json!({"error": "Not found"})))
 }
 
-#[derive(Serialize)]
-struct StatusResponse {
-    version: String,
-    proxy_running: bool,
-    accounts_count: usize,
-    current_account: Option<String>,
-}
-
-async fn get_status(State(state): State<AppState>) -> Json<StatusResponse> {
+async fn get_status(State(state): State<AppState>) -> Json<ServerStatus> {
     let current = state.get_current_account().await.ok().flatten();
 
-    Json(StatusResponse {
+    Json(ServerStatus {
         version: env!("CARGO_PKG_VERSION").to_string(),
         proxy_running: true,
         accounts_count: state.get_account_count().await.unwrap_or(0),
@@ -1,9 +1,21 @@
 //! Request monitoring handlers
 
-use axum::{extract::State, response::Json};
+use std::convert::Infallible;
+
+use axum::{
+    extract::State,
+    http::{HeaderMap, StatusCode},
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        Json,
+    },
+};
+use futures::{Stream, StreamExt};
 use serde::Deserialize;
+use tokio::sync::broadcast::error::RecvError;
 
 use crate::state::AppState;
+use antigravity_types::models::MonitorEvent;
 
 #[derive(Deserialize)]
 pub struct MonitorQuery {
@@ -18,6 +30,74 @@ pub async fn get_monitor_requests(
     Json(logs)
 }
 
+/// Search captured request/response bodies persisted in SQLite.
+pub async fn search_monitor_requests(
+    State(state): State<AppState>,
+    axum::extract::Query(query): axum::extract::Query<antigravity_types::models::ProxyLogQuery>,
+) -> Result<Json<Vec<antigravity_types::models::ProxyRequestLog>>, (StatusCode, String)> {
+    state
+        .search_proxy_logs(query)
+        .await
+        .map(Json)
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))
+}
+
+#[derive(Deserialize)]
+pub struct StreamQuery {
+    /// Resume point for clients that cannot set `Last-Event-ID`
+    pub last_event_id: Option<u64>,
+}
+
+fn sse_event(event: &MonitorEvent) -> Event {
+    Event::default()
+        .id(event.id.to_string())
+        .event(event.payload.kind())
+        .data(serde_json::to_string(event).unwrap_or_default())
+}
+
+/// Tells the client to reload state: events were missed and cannot be replayed.
+fn resync_event() -> Event {
+    Event::default().event("resync").data("{}")
+}
+
+/// Live request logs, account state changes and quota updates over SSE.
+///
+/// Reconnecting clients send `Last-Event-ID` (or `?last_event_id=`) to replay
+/// what they missed from the server's buffer.
+pub async fn stream_monitor_events(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    axum::extract::Query(query): axum::extract::Query<StreamQuery>,
+) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    let last_id = headers
+        .get("last-event-id")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.trim().parse().ok())
+        .or(query.last_event_id);
+    let subscription = state.events().subscribe(last_id);
+
+    let replay = subscription
+        .gap
+        .then(resync_event)
+        .into_iter()
+        .chain(subscription.backlog.iter().map(sse_event))
+        .map(Ok)
+        .collect::<Vec<_>>();
+    let live = futures::stream::unfold(subscription.receiver, |mut receiver| async move {
+        let event = match receiver.recv().await {
+            Ok(event) => sse_event(&event),
+            Err(RecvError::Lagged(skipped)) => {
+                tracing::warn!("[Monitor] Stream subscriber lagged, {} events skipped", skipped);
+                resync_event()
+            },
+            Err(RecvError::Closed) => return None,
+        };
+        Some((Ok(event), receiver))
+    });
+
+    Sse::new(futures::stream::iter(replay).chain(live)).keep_alive(KeepAlive::default())
+}
+
 #[allow(clippy::similar_names, reason = "state/stats naming is clear in context")]
 pub async fn get_monitor_stats(
     State(state): State<AppState>,
@@ -0,0 +1,69 @@
+//! Webhook notifier handlers
+
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    response::Json,
+};
+use serde::Deserialize;
+
+use crate::state::AppState;
+use antigravity_types::models::{Notification, NotificationSeverity, WebhookDelivery};
+
+const DEFAULT_DELIVERY_LIMIT: usize = 50;
+
+#[derive(Deserialize)]
+pub struct DeliveriesQuery {
+    pub limit: Option<usize>,
+}
+
+/// Recent webhook deliveries, newest first.
+pub async fn get_deliveries(
+    State(state): State<AppState>,
+    Query(query): Query<DeliveriesQuery>,
+) -> Json<Vec<WebhookDelivery>> {
+    Json(state.notifier().deliveries(query.limit.unwrap_or(DEFAULT_DELIVERY_LIMIT)))
+}
+
+#[derive(Deserialize, Default)]
+pub struct TestNotificationRequest {
+    /// Target name; all enabled targets when omitted
+    pub target: Option<String>,
+}
+
+/// Send a test notification and wait for the delivery results.
+pub async fn send_test_notification(
+    State(state): State<AppState>,
+    payload: Option<Json<TestNotificationRequest>>,
+) -> Result<Json<Vec<WebhookDelivery>>, (StatusCode, String)> {
+    let request = payload.map(|Json(r)| r).unwrap_or_default();
+    let config = state.notifier().config();
+    let targets: Vec<_> = match &request.target {
+        Some(name) => {
+            let target = config.targets.into_iter().find(|t| &t.name == name).ok_or_else(|| {
+                (StatusCode::NOT_FOUND, format!("Webhook target '{}' not found", name))
+            })?;
+            vec![target]
+        },
+        None => config.targets.into_iter().filter(|t| t.enabled).collect(),
+    };
+    if targets.is_empty() {
+        return Err((StatusCode::BAD_REQUEST, "No enabled webhook targets".to_string()));
+    }
+
+    let notification = Notification {
+        kind: "test".to_string(),
+        severity: NotificationSeverity::Info,
+        title: "Test notification from Antigravity Manager".to_string(),
+        account_id: None,
+        account_email: None,
+        model: None,
+        data: serde_json::Value::Null,
+        timestamp: chrono::Utc::now().timestamp_millis(),
+    };
+    let deliveries = futures::future::join_all(
+        targets.iter().map(|target| state.notifier().deliver_to(target, &notification)),
+    )
+    .await;
+    Ok(Json(deliveries))
+}
@@ -0,0 +1,44 @@
+use axum::extract::{Query, State};
+use axum::http::StatusCode;
+use axum::response::Json;
+
+use antigravity_types::models::{ProxyConfig, WebhookFormat, WebhookTarget};
+
+use super::notifications::{
+    get_deliveries, send_test_notification, DeliveriesQuery, TestNotificationRequest,
+};
+use crate::test_helpers::{test_app_state, test_app_state_with_config};
+
+#[tokio::test]
+async fn test_get_deliveries_empty() {
+    let (state, _tmp) = test_app_state().await;
+    let Json(deliveries) =
+        get_deliveries(State(state), Query(DeliveriesQuery { limit: None })).await;
+    assert!(deliveries.is_empty());
+}
+
+#[tokio::test]
+async fn test_send_test_notification_without_targets() {
+    let (state, _tmp) = test_app_state().await;
+    let err = send_test_notification(State(state), None).await.unwrap_err();
+    assert_eq!(err.0, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_send_test_notification_unknown_target() {
+    let mut config = ProxyConfig::default();
+    config.notifier.targets.push(WebhookTarget {
+        name: "ops".to_string(),
+        url: "https://example.com/hook".to_string(),
+        format: WebhookFormat::Slack,
+        enabled: true,
+        events: Vec::new(),
+        secret: None,
+        chat_id: None,
+    });
+    let (state, _tmp) = test_app_state_with_config(config).await;
+
+    let request = TestNotificationRequest { target: Some("missing".to_string()) };
+    let err = send_test_notification(State(state), Some(Json(request))).await.unwrap_err();
+    assert_eq!(err.0, StatusCode::NOT_FOUND);
+}
@@ -8,22 +8,15 @@ use axum::{
 use serde::Serialize;
 
 use antigravity_core::modules::config as core_config;
+use antigravity_types::models::ProxyStatus;
 
 use crate::state::AppState;
 
-#[derive(Serialize)]
-pub struct ProxyStatusResponse {
-    pub running: bool,
-    pub port: u16,
-    pub base_url: String,
-    pub active_accounts: usize,
-}
-
-pub async fn get_proxy_status(State(state): State<AppState>) -> Json<ProxyStatusResponse> {
+pub async fn get_proxy_status(State(state): State<AppState>) -> Json<ProxyStatus> {
     let port = state.get_bound_port();
     let bind_addr = state.get_proxy_bind_address().await;
 
-    Json(ProxyStatusResponse {
+    Json(ProxyStatus {
         running: true,
         port,
         base_url: format!("http://{}:{}", bind_addr, port),
@@ -9,16 +9,15 @@ const MAX_HEALTH_RESPONSE_BYTES: usize = 8192;
 const DEFAULT_HEALTH_CHECK_URL: &str = "https://ifconfig.co";
 const FALLBACK_HEALTH_CHECK_URL: &str = "https://api.ipify.org";
 
-fn health_check_urls() -> Vec<&'static str> {
-    static URLS: std::sync::OnceLock<Vec<String>> = std::sync::OnceLock::new();
-    let urls = URLS.get_or_init(|| {
-        if let Ok(custom) = std::env::var("ANTIGRAVITY_HEALTH_CHECK_URL") {
-            vec![custom]
-        } else {
-            vec![DEFAULT_HEALTH_CHECK_URL.to_owned(), FALLBACK_HEALTH_CHECK_URL.to_owned()]
-        }
-    });
-    urls.iter().map(String::as_str).collect()
+/// Endpoints: `ANTIGRAVITY_HEALTH_CHECK_URL` > configured `health_check_urls` > defaults.
+fn health_check_urls(configured: &[String]) -> Vec<String> {
+    if let Ok(custom) = std::env::var("ANTIGRAVITY_HEALTH_CHECK_URL") {
+        vec![custom]
+    } else if configured.is_empty() {
+        vec![DEFAULT_HEALTH_CHECK_URL.to_owned(), FALLBACK_HEALTH_CHECK_URL.to_owned()]
+    } else {
+        configured.to_vec()
+    }
 }
 
 pub fn validate_proxy_url(raw: &str) -> Result<(), String> {
@@ -129,7 +128,10 @@ fn is_private_ip(ip: IpAddr) -> bool {
     }
 }
 
-pub async fn check_proxy_health(proxy_url: &str) -> Result<String, String> {
+pub async fn check_proxy_health(
+    proxy_url: &str,
+    configured_urls: &[String],
+) -> Result<String, String> {
     validate_proxy_url_async(proxy_url).await?;
 
     let proxy = reqwest::Proxy::all(proxy_url).map_err(|e| format!("Invalid proxy URL: {e}"))?;
@@ -142,8 +144,8 @@ pub async fn check_proxy_health(proxy_url: &str) -> Result<String, String> {
         .map_err(|e| format!("Failed to build health check client: {e}"))?;
 
     let mut last_err = String::new();
-    for url in health_check_urls() {
-        match try_health_check(&client, url).await {
+    for url in health_check_urls(configured_urls) {
+        match try_health_check(&client, &url).await {
             Ok(ip) => return Ok(ip),
             Err(e) => {
                 tracing::debug!("Health check via {url} failed: {e}");
@@ -1,26 +1,16 @@
 //! Quota refresh handlers
 
 use axum::{extract::State, http::StatusCode, response::Json};
-use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use tokio::sync::Semaphore;
 use tokio::task::JoinSet;
 
 use antigravity_core::modules::account;
+use antigravity_core::proxy::monitor::ProxyEventBus;
+use antigravity_types::models::{QuotaResponse, RefreshQuotaRequest};
 
 use crate::state::AppState;
 
-#[derive(Deserialize)]
-pub struct RefreshQuotaRequest {
-    pub account_id: String,
-}
-
-#[derive(Serialize)]
-pub struct QuotaResponse {
-    pub account_id: String,
-    pub quota: Option<antigravity_types::models::QuotaData>,
-}
-
 pub async fn refresh_account_quota(
     State(state): State<AppState>,
     Json(payload): Json<RefreshQuotaRequest>,
@@ -43,6 +33,7 @@ pub async fn refresh_account_quota(
     match account::fetch_quota_with_retry(&acc, state.repository(), enforce_proxy).await {
         Ok(result) => {
             let quota = result.quota;
+            state.events().emit_quota_update(&payload.account_id, &quota);
             let updated_account = match account::update_account_quota_async(
                 payload.account_id.clone(),
                 quota.clone(),
@@ -116,6 +107,7 @@ pub async fn refresh_all_quotas(
     while let Some(result) = join_set.join_next().await {
         match result {
             Ok(Ok((account_id, email, quota))) => {
+                state.events().emit_quota_update(&account_id, &quota);
                 let protected_models =
                     match account::update_account_quota_async(account_id.clone(), quota.clone())
                         .await
@@ -7,23 +7,10 @@ use tokio::sync::Semaphore;
 use tokio::task::JoinSet;
 
 use antigravity_core::modules::account;
+use antigravity_types::models::{ToggleProxyRequest, ToggleProxyResponse};
 
 use crate::state::AppState;
 
-#[derive(Deserialize)]
-pub struct ToggleProxyRequest {
-    pub account_id: String,
-    pub enable: bool,
-    pub reason: Option<String>,
-}
-
-#[derive(Serialize)]
-pub struct ToggleProxyResponse {
-    pub success: bool,
-    pub account_id: String,
-    pub proxy_disabled: bool,
-}
-
 pub async fn toggle_proxy_status(
     State(state): State<AppState>,
     Json(payload): Json<ToggleProxyRequest>,
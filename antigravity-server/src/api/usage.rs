@@ -0,0 +1,23 @@
+//! Spend accounting handlers
+
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    response::Json,
+};
+
+use crate::state::AppState;
+use antigravity_types::models::{BudgetStatus, UsageQuery, UsageReport};
+
+/// Usage rollups by key, model and account plus budget status.
+pub async fn get_usage(
+    State(state): State<AppState>,
+    Query(query): Query<UsageQuery>,
+) -> Result<Json<UsageReport>, (StatusCode, String)> {
+    state.usage_report(query).await.map(Json).map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))
+}
+
+/// Current-period spend of every configured budget (in-memory, no database read).
+pub async fn get_budgets(State(state): State<AppState>) -> Json<Vec<BudgetStatus>> {
+    Json(state.budget_statuses())
+}
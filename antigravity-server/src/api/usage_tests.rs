@@ -0,0 +1,34 @@
+use axum::extract::State;
+use axum::response::Json;
+
+use antigravity_types::models::{BudgetAction, BudgetScope, ProxyConfig, SpendBudget, SpendPeriod};
+
+use super::usage::get_budgets;
+use crate::test_helpers::{test_app_state, test_app_state_with_config};
+
+#[tokio::test]
+async fn test_get_budgets_empty() {
+    let (state, _tmp) = test_app_state().await;
+    let Json(budgets) = get_budgets(State(state)).await;
+    assert!(budgets.is_empty());
+}
+
+#[tokio::test]
+async fn test_get_budgets_reports_configured_budget() {
+    let mut config = ProxyConfig::default();
+    config.spend.budgets.push(SpendBudget {
+        name: "team".to_string(),
+        scope: BudgetScope::ApiKey,
+        target: Some("key-1".to_string()),
+        period: SpendPeriod::Monthly,
+        limit: 5_000_000,
+        action: BudgetAction::Block,
+    });
+    let (state, _tmp) = test_app_state_with_config(config).await;
+
+    let Json(budgets) = get_budgets(State(state)).await;
+    assert_eq!(budgets.len(), 1);
+    assert_eq!(budgets[0].budget.name, "team");
+    assert_eq!(budgets[0].spent, 0);
+    assert!(!budgets[0].exceeded);
+}
@@ -48,6 +48,9 @@ pub enum Commands {
 
     #[command(about = "Generate a new API key")]
     GenerateKey,
+
+    #[command(subcommand, about = "Manage client API keys")]
+    Key(KeyCommands),
 }
 
 #[derive(Subcommand)]
@@ -90,6 +93,15 @@ pub enum AccountCommands {
         #[arg(help = "Email or account ID (or 'all' for all accounts)")]
         identifier: String,
     },
+
+    #[command(about = "Set the account groups of an account (none clears them)")]
+    Groups {
+        #[arg(help = "Email or account ID")]
+        identifier: String,
+
+        #[arg(help = "Group names (e.g. team-a batch)")]
+        groups: Vec<String>,
+    },
 }
 
 #[derive(Subcommand)]
@@ -114,4 +126,103 @@ pub enum ConfigCommands {
         #[arg(help = "New value")]
         value: String,
     },
+
+    #[command(
+        about = "Validate a declarative config file (TOML) against the current configuration"
+    )]
+    Validate {
+        #[arg(help = "Config file (default: the active config file)")]
+        file: Option<PathBuf>,
+    },
+
+    #[command(about = "Show the settings a config file would change")]
+    Diff {
+        #[arg(help = "Candidate config file")]
+        file: PathBuf,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum KeyCommands {
+    #[command(about = "List client API keys with usage")]
+    List {
+        #[arg(short, long, help = "Output as JSON")]
+        json: bool,
+    },
+
+    #[command(about = "Create a client API key (the secret is shown once)")]
+    Create {
+        #[arg(help = "Label (owner, team member, CI job, ...)")]
+        label: String,
+
+        #[arg(long = "model", help = "Allowed model, supports '*' (repeatable; default: all)")]
+        models: Vec<String>,
+
+        #[arg(long, help = "Expire after this many days")]
+        expires_in_days: Option<u32>,
+
+        #[arg(long, help = "Maximum requests per budget period")]
+        max_requests: Option<u64>,
+
+        #[arg(long, help = "Maximum tokens per budget period")]
+        max_tokens: Option<u64>,
+
+        #[arg(long, value_enum, default_value = "total", help = "Budget period")]
+        period: BudgetPeriodArg,
+
+        #[arg(long, help = "Serve this key's requests only from accounts in this group")]
+        group: Option<String>,
+
+        #[arg(long, value_enum, default_value = "interactive", help = "Admission queue class")]
+        priority: PriorityArg,
+    },
+
+    #[command(about = "Set the admission queue class of a client API key")]
+    Priority {
+        #[arg(help = "Key ID, label or prefix")]
+        identifier: String,
+
+        #[arg(value_enum)]
+        priority: PriorityArg,
+    },
+
+    #[command(about = "Restrict a client API key to an account group")]
+    Group {
+        #[arg(help = "Key ID, label or prefix")]
+        identifier: String,
+
+        #[arg(help = "Account group (omit to remove the restriction)")]
+        group: Option<String>,
+    },
+
+    #[command(about = "Disable a client API key")]
+    Revoke {
+        #[arg(help = "Key ID, label or prefix")]
+        identifier: String,
+    },
+
+    #[command(about = "Re-enable a client API key")]
+    Enable {
+        #[arg(help = "Key ID, label or prefix")]
+        identifier: String,
+    },
+
+    #[command(about = "Permanently delete a client API key")]
+    Delete {
+        #[arg(help = "Key ID, label or prefix")]
+        identifier: String,
+    },
+}
+
+#[derive(Clone, Copy, clap::ValueEnum)]
+pub enum BudgetPeriodArg {
+    Total,
+    Daily,
+    Monthly,
+}
+
+#[derive(Clone, Copy, clap::ValueEnum)]
+pub enum PriorityArg {
+    Interactive,
+    Batch,
 }
@@ -3,7 +3,7 @@ use colored::Colorize;
 
 use antigravity_core::modules::{account, config as core_config};
 
-use crate::cli::{AccountCommands, ConfigCommands};
+use crate::cli::{AccountCommands, ConfigCommands, KeyCommands};
 
 mod account_commands_impl {
     pub use crate::account_commands::*;
@@ -14,6 +14,9 @@ mod warmup_commands_impl {
 mod config_commands_impl {
     pub use crate::config_commands::*;
 }
+mod key_commands_impl {
+    pub use crate::key_commands::*;
+}
 
 pub async fn handle_account_command(cmd: AccountCommands) -> Result<()> {
     match cmd {
@@ -30,6 +33,9 @@ pub async fn handle_account_command(cmd: AccountCommands) -> Result<()> {
         AccountCommands::Refresh { identifier } => {
             account_commands_impl::refresh_quota(&identifier).await
         },
+        AccountCommands::Groups { identifier, groups } => {
+            account_commands_impl::set_groups(&identifier, &groups).await
+        },
     }
 }
 
@@ -38,6 +44,43 @@ pub async fn handle_config_command(cmd: ConfigCommands) -> Result<()> {
         ConfigCommands::Show { json } => config_commands_impl::show_config(json),
         ConfigCommands::Get { key } => config_commands_impl::get_config_value(&key),
         ConfigCommands::Set { key, value } => config_commands_impl::set_config_value(&key, &value),
+        ConfigCommands::Validate { file } => config_commands_impl::validate_config_file(file),
+        ConfigCommands::Diff { file } => config_commands_impl::diff_config_file(&file),
+    }
+}
+
+pub async fn handle_key_command(cmd: KeyCommands) -> Result<()> {
+    match cmd {
+        KeyCommands::List { json } => key_commands_impl::list_keys(json).await,
+        KeyCommands::Create {
+            label,
+            models,
+            expires_in_days,
+            max_requests,
+            max_tokens,
+            period,
+            group,
+            priority,
+        } => {
+            let budget = key_commands_impl::budget(period, max_requests, max_tokens);
+            let priority = key_commands_impl::priority(priority);
+            key_commands_impl::create_key(label, models, expires_in_days, budget, group, priority)
+                .await
+        },
+        KeyCommands::Priority { identifier, priority } => {
+            key_commands_impl::set_key_priority(&identifier, key_commands_impl::priority(priority))
+                .await
+        },
+        KeyCommands::Group { identifier, group } => {
+            key_commands_impl::set_key_group(&identifier, group).await
+        },
+        KeyCommands::Revoke { identifier } => {
+            key_commands_impl::set_key_enabled(&identifier, false).await
+        },
+        KeyCommands::Enable { identifier } => {
+            key_commands_impl::set_key_enabled(&identifier, true).await
+        },
+        KeyCommands::Delete { identifier } => key_commands_impl::delete_key(&identifier).await,
     }
 }
 
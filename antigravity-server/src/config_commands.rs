@@ -1,5 +1,7 @@
 use anyhow::Result;
 use colored::Colorize;
+use serde_json::Value;
+use std::path::{Path, PathBuf};
 
 use antigravity_core::modules::config as core_config;
 
@@ -60,9 +62,80 @@ pub fn set_config_value(key: &str, value: &str) -> Result<()> {
     }
 
     println!("{} Config updated: {} = {}", "✓".green(), key, value);
+    if let Some(path) = core_config::config_file_path() {
+        let pinned = core_config::read_config_file(&path)
+            .is_ok_and(|file| file.pointer(&format!("/{}", key.replace('.', "/"))).is_some());
+        if pinned {
+            println!(
+                "{} {} also sets {}; the value from that file stays in effect",
+                "!".yellow(),
+                path.display(),
+                key
+            );
+        }
+    }
     Ok(())
 }
 
+/// Apply `file` on top of `gui_config.json`, exactly as the server would.
+fn layer_file(file: &Path) -> Result<antigravity_core::models::AppConfig> {
+    let base = core_config::load_base_config().map_err(|e| anyhow::anyhow!(e))?;
+    let overlay = core_config::read_config_file(file).map_err(|e| anyhow::anyhow!(e))?;
+    core_config::layer_config(&base, overlay)
+        .map_err(|e| anyhow::anyhow!("{}: {}", file.display(), e))
+}
+
+pub fn validate_config_file(file: Option<PathBuf>) -> Result<()> {
+    let file = file.or_else(core_config::config_file_path).ok_or_else(|| {
+        anyhow::anyhow!("No config file given and none active (config.toml or $ANTIGRAVITY_CONFIG)")
+    })?;
+    layer_file(&file)?;
+    println!("{} {} is valid", "✓".green(), file.display());
+    Ok(())
+}
+
+pub fn diff_config_file(file: &Path) -> Result<()> {
+    let current = core_config::load_config().map_err(|e| anyhow::anyhow!(e))?;
+    let candidate = layer_file(file)?;
+    let changes =
+        core_config::diff_configs(&current, &candidate).map_err(|e| anyhow::anyhow!(e))?;
+
+    if changes.is_empty() {
+        println!("No changes");
+        return Ok(());
+    }
+    for change in &changes {
+        let show = |value: &Value| mask_secrets(&change.key, value).to_string();
+        match (&change.old, &change.new) {
+            (Some(old), Some(new)) => {
+                println!("{} {}: {} -> {}", "~".yellow(), change.key, show(old), show(new));
+            },
+            (None, Some(new)) => println!("{} {} = {}", "+".green(), change.key, show(new)),
+            (Some(old), None) => println!("{} {} = {}", "-".red(), change.key, show(old)),
+            (None, None) => {},
+        }
+    }
+    println!("{} setting(s) would change", changes.len());
+    Ok(())
+}
+
+fn is_secret_key(key: &str) -> bool {
+    let name = key.rsplit('.').next().unwrap_or(key);
+    name.ends_with("api_key") || name == "secret" || name.contains("password")
+}
+
+/// Mask credentials so the diff can be pasted into a review.
+fn mask_secrets(key: &str, value: &Value) -> Value {
+    match value {
+        Value::String(s) if is_secret_key(key) => Value::String(mask_key(s)),
+        Value::Array(items) => Value::Array(items.iter().map(|v| mask_secrets(key, v)).collect()),
+        Value::Object(map) => Value::Object(
+            map.iter().map(|(k, v)| (k.clone(), mask_secrets(&format!("{key}.{k}"), v))).collect(),
+        ),
+        other => other.clone(),
+    }
+}
+
 fn mask_key(key: &str) -> String {
     if key.len() <= 8 {
         return "*".repeat(key.len());
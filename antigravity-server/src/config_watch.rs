@@ -0,0 +1,85 @@
+//! Hot reload of the declarative config file (`config.toml` / `$ANTIGRAVITY_CONFIG`).
+//!
+//! The file is polled for changes to its modification time or size. A changed file is
+//! validated and applied through [`AppState::hot_reload_proxy_config`]; an invalid one
+//! is logged and the running configuration stays in place.
+
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+use tokio::time::{interval, MissedTickBehavior};
+
+use antigravity_core::models::AppConfig;
+use antigravity_core::modules::config as core_config;
+
+use crate::state::AppState;
+
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Settings read once at startup
+const RESTART_REQUIRED: &[&str] =
+    &["proxy.port", "proxy.allow_lan_access", "proxy.cluster.", "proxy.telemetry."];
+
+fn fingerprint(path: &Path) -> Option<(SystemTime, u64)> {
+    let metadata = std::fs::metadata(path).ok()?;
+    Some((metadata.modified().ok()?, metadata.len()))
+}
+
+async fn load() -> Result<AppConfig, String> {
+    tokio::task::spawn_blocking(core_config::load_config)
+        .await
+        .map_err(|e| format!("spawn_blocking panicked: {e}"))?
+}
+
+pub fn start_config_watch(state: AppState, path: PathBuf) {
+    tracing::info!("✅ Config file watch started ({})", path.display());
+    tokio::spawn(async move {
+        let mut last_seen = fingerprint(&path);
+        let mut applied = match load().await {
+            Ok(config) => config,
+            Err(e) => {
+                tracing::error!("[ConfigWatch] Not watching {}: {}", path.display(), e);
+                return;
+            },
+        };
+
+        let mut ticker = interval(POLL_INTERVAL);
+        ticker.set_missed_tick_behavior(MissedTickBehavior::Skip);
+        loop {
+            ticker.tick().await;
+            let seen = fingerprint(&path);
+            if seen == last_seen {
+                continue;
+            }
+            last_seen = seen;
+
+            let config = match load().await {
+                Ok(config) => config,
+                Err(e) => {
+                    tracing::warn!(
+                        "[ConfigWatch] {} rejected, keeping the running configuration: {}",
+                        path.display(),
+                        e
+                    );
+                    continue;
+                },
+            };
+            let changes = core_config::diff_configs(&applied, &config).unwrap_or_default();
+            if changes.is_empty() {
+                continue;
+            }
+
+            let keys: Vec<&str> = changes.iter().map(|c| c.key.as_str()).collect();
+            tracing::info!("[ConfigWatch] {} changed: {}", path.display(), keys.join(", "));
+            for key in keys
+                .iter()
+                .filter(|key| RESTART_REQUIRED.iter().any(|prefix| key.starts_with(prefix)))
+            {
+                tracing::warn!("[ConfigWatch] {} takes effect after a restart", key);
+            }
+
+            core_config::invalidate_config_cache();
+            state.hot_reload_proxy_config().await;
+            applied = config;
+        }
+    });
+}
@@ -0,0 +1,192 @@
+use anyhow::Result;
+use colored::Colorize;
+use comfy_table::{presets::UTF8_FULL, Cell, Color, Table};
+
+use antigravity_core::modules::account_pg::PostgresAccountRepository;
+use antigravity_core::modules::api_key_store::ApiKeyStore;
+use antigravity_core::proxy::ApiKeyRegistry;
+use antigravity_types::models::{
+    ApiKey, ApiKeyBudget, BudgetPeriod, CreateApiKeyRequest, PriorityClass, UpdateApiKeyRequest,
+};
+
+use crate::cli::{BudgetPeriodArg, PriorityArg};
+
+/// Open the same key store the server uses (PostgreSQL if DATABASE_URL is set).
+async fn open_registry() -> Result<ApiKeyRegistry> {
+    let pool = match std::env::var("DATABASE_URL") {
+        Ok(url) => Some(PostgresAccountRepository::connect(&url).await?.pool().clone()),
+        Err(_) => None,
+    };
+    let registry = ApiKeyRegistry::new(ApiKeyStore::new(pool).map_err(|e| anyhow::anyhow!(e))?);
+    registry.reload().await.map_err(|e| anyhow::anyhow!(e))?;
+    Ok(registry)
+}
+
+fn find_key(registry: &ApiKeyRegistry, identifier: &str) -> Result<ApiKey> {
+    let keys = registry.list();
+    keys.iter()
+        .find(|k| k.id == identifier || k.key_prefix == identifier)
+        .or_else(|| keys.iter().find(|k| k.label == identifier))
+        .cloned()
+        .ok_or_else(|| anyhow::anyhow!("API key not found: {}", identifier))
+}
+
+pub fn budget(
+    period: BudgetPeriodArg,
+    max_requests: Option<u64>,
+    max_tokens: Option<u64>,
+) -> ApiKeyBudget {
+    let period = match period {
+        BudgetPeriodArg::Total => BudgetPeriod::Total,
+        BudgetPeriodArg::Daily => BudgetPeriod::Daily,
+        BudgetPeriodArg::Monthly => BudgetPeriod::Monthly,
+    };
+    ApiKeyBudget { period, max_requests, max_tokens }
+}
+
+pub fn priority(priority: PriorityArg) -> PriorityClass {
+    match priority {
+        PriorityArg::Interactive => PriorityClass::Interactive,
+        PriorityArg::Batch => PriorityClass::Batch,
+    }
+}
+
+pub async fn list_keys(json: bool) -> Result<()> {
+    let keys = open_registry().await?.list();
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&keys)?);
+        return Ok(());
+    }
+
+    if keys.is_empty() {
+        println!("{}", "No client API keys found.".yellow());
+        return Ok(());
+    }
+
+    let now = chrono::Utc::now().timestamp();
+    let mut table = Table::new();
+    table.load_preset(UTF8_FULL);
+    table.set_header(vec![
+        "Label", "Prefix", "Models", "Group", "Requests", "Tokens", "Expires", "Status",
+    ]);
+
+    for key in &keys {
+        let status = if !key.enabled {
+            Cell::new("Revoked").fg(Color::Red)
+        } else if key.is_expired(now) {
+            Cell::new("Expired").fg(Color::Yellow)
+        } else {
+            Cell::new("Active").fg(Color::Green)
+        };
+        let models = if key.allowed_models.is_empty() {
+            "*".to_string()
+        } else {
+            key.allowed_models.join(", ")
+        };
+
+        table.add_row(vec![
+            Cell::new(&key.label),
+            Cell::new(&key.key_prefix),
+            Cell::new(models),
+            Cell::new(key.account_group.as_deref().unwrap_or("-")),
+            Cell::new(usage_cell(key.usage.requests, key.budget.max_requests)),
+            Cell::new(usage_cell(key.usage.tokens, key.budget.max_tokens)),
+            Cell::new(format_timestamp(key.expires_at)),
+            status,
+        ]);
+    }
+
+    println!("{table}");
+    println!("\n{} keys total", keys.len());
+    Ok(())
+}
+
+fn usage_cell(used: u64, limit: Option<u64>) -> String {
+    match limit {
+        Some(limit) => format!("{}/{}", used, limit),
+        None => used.to_string(),
+    }
+}
+
+fn format_timestamp(ts: Option<i64>) -> String {
+    ts.and_then(|ts| chrono::DateTime::from_timestamp(ts, 0))
+        .map_or_else(|| "never".to_string(), |dt| dt.format("%Y-%m-%d").to_string())
+}
+
+pub async fn create_key(
+    label: String,
+    allowed_models: Vec<String>,
+    expires_in_days: Option<u32>,
+    budget: ApiKeyBudget,
+    account_group: Option<String>,
+    priority: PriorityClass,
+) -> Result<()> {
+    let registry = open_registry().await?;
+    let expires_at =
+        expires_in_days.map(|days| chrono::Utc::now().timestamp() + i64::from(days) * 86_400);
+
+    let created = registry
+        .create(CreateApiKeyRequest {
+            label,
+            expires_at,
+            allowed_models,
+            budget,
+            account_group,
+            priority,
+        })
+        .await
+        .map_err(|e| anyhow::anyhow!(e))?;
+
+    println!("{} Created API key '{}' ({})", "✓".green(), created.key.label, created.key.id);
+    println!("  {}", created.secret.bold());
+    println!("{}", "Store this secret now; it cannot be shown again.".yellow());
+    Ok(())
+}
+
+pub async fn set_key_enabled(identifier: &str, enabled: bool) -> Result<()> {
+    let registry = open_registry().await?;
+    let key = find_key(&registry, identifier)?;
+
+    let update = UpdateApiKeyRequest { enabled: Some(enabled), ..Default::default() };
+    registry.update(&key.id, update).await.map_err(|e| anyhow::anyhow!(e))?;
+
+    let action = if enabled { "enabled" } else { "revoked" };
+    println!("{} API key '{}' {}", "✓".green(), key.label, action);
+    Ok(())
+}
+
+pub async fn set_key_group(identifier: &str, group: Option<String>) -> Result<()> {
+    let registry = open_registry().await?;
+    let key = find_key(&registry, identifier)?;
+
+    let update = UpdateApiKeyRequest { account_group: Some(group), ..Default::default() };
+    let updated = registry.update(&key.id, update).await.map_err(|e| anyhow::anyhow!(e))?;
+
+    match updated.and_then(|k| k.account_group) {
+        Some(group) => {
+            println!("{} API key '{}' restricted to group '{}'", "✓".green(), key.label, group)
+        },
+        None => println!("{} API key '{}' may use any account", "✓".green(), key.label),
+    }
+    Ok(())
+}
+
+pub async fn set_key_priority(identifier: &str, priority: PriorityClass) -> Result<()> {
+    let registry = open_registry().await?;
+    let key = find_key(&registry, identifier)?;
+
+    let update = UpdateApiKeyRequest { priority: Some(priority), ..Default::default() };
+    registry.update(&key.id, update).await.map_err(|e| anyhow::anyhow!(e))?;
+    println!("{} API key '{}' queued as {}", "✓".green(), key.label, priority.as_str());
+    Ok(())
+}
+
+pub async fn delete_key(identifier: &str) -> Result<()> {
+    let registry = open_registry().await?;
+    let key = find_key(&registry, identifier)?;
+
+    registry.delete(&key.id).await.map_err(|e| anyhow::anyhow!(e))?;
+    println!("{} API key '{}' deleted", "✓".green(), key.label);
+    Ok(())
+}
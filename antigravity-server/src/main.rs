@@ -33,12 +33,15 @@ use anyhow::Result;
 use clap::Parser;
 use std::sync::Arc;
 use tracing::{info, Level};
-use tracing_subscriber::FmtSubscriber;
+use tracing_subscriber::filter::LevelFilter;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::Layer;
 
 mod api;
 mod cli;
 mod commands;
 mod config_sync;
+mod config_watch;
 mod router;
 mod scheduler;
 mod server_utils;
@@ -49,11 +52,16 @@ mod test_helpers;
 
 mod account_commands;
 mod config_commands;
+mod key_commands;
 mod warmup_commands;
 
 use antigravity_core::modules::account_pg::PostgresAccountRepository;
+use antigravity_core::modules::api_key_store::ApiKeyStore;
+use antigravity_core::modules::batch_store::BatchStore;
 use antigravity_core::modules::repository::AccountRepository;
-use antigravity_core::proxy::SignatureCache;
+use antigravity_core::proxy::cluster::{Cluster, PostgresClusterBackend};
+use antigravity_core::proxy::{ApiKeyRegistry, BatchManager, SignatureCache};
+use antigravity_types::models::ClusterBackendKind;
 use cli::{Cli, Commands};
 use state::AppState;
 
@@ -68,17 +76,47 @@ async fn main() -> Result<()> {
         _ => Level::INFO,
     };
 
-    let subscriber = FmtSubscriber::builder().with_max_level(log_level).finish();
+    // OTLP export only applies to the long-running server
+    let telemetry = if matches!(cli.command, None | Some(Commands::Serve { .. })) {
+        init_telemetry()
+    } else {
+        None
+    };
+    let otel_layer =
+        telemetry.as_ref().map(|t| tracing_opentelemetry::layer().with_tracer(t.tracer()));
+    let subscriber = tracing_subscriber::registry()
+        .with(tracing_subscriber::fmt::layer().with_filter(LevelFilter::from_level(log_level)))
+        .with(otel_layer.with_filter(LevelFilter::INFO));
     tracing::subscriber::set_global_default(subscriber)?;
 
-    match cli.command {
+    let result = match cli.command {
         Some(Commands::Account(cmd)) => commands::handle_account_command(cmd).await,
         Some(Commands::Config(cmd)) => commands::handle_config_command(cmd).await,
         Some(Commands::Warmup { all, email }) => commands::handle_warmup(all, email).await,
         Some(Commands::Status) => commands::handle_status().await,
         Some(Commands::GenerateKey) => commands::handle_generate_key().await,
+        Some(Commands::Key(cmd)) => commands::handle_key_command(cmd).await,
         Some(Commands::Serve { port }) => run_server(port).await,
         None => run_server(cli.port).await,
+    };
+
+    if let Some(telemetry) = telemetry {
+        let _ = tokio::task::spawn_blocking(move || telemetry.shutdown()).await;
+    }
+    result
+}
+
+/// Build the OTLP exporter from the persisted config (runs before logging is set up).
+fn init_telemetry() -> Option<antigravity_core::proxy::telemetry::Telemetry> {
+    let config = antigravity_core::modules::config::load_config()
+        .map(|c| c.proxy.telemetry)
+        .unwrap_or_default();
+    match antigravity_core::proxy::telemetry::Telemetry::init(&config) {
+        Ok(telemetry) => telemetry,
+        Err(e) => {
+            eprintln!("⚠️ OpenTelemetry export disabled: {}", e);
+            None
+        },
     }
 }
 
@@ -90,8 +128,11 @@ async fn run_server(port: u16) -> Result<()> {
 
     let data_dir = antigravity_core::modules::account::get_data_dir()
         .map_err(|e| anyhow::anyhow!("Failed to get data directory: {}", e))?;
+    let config_file = antigravity_core::modules::config::config_file_path();
     let initial_app_config = match antigravity_core::modules::config::load_config() {
         Ok(config) => config,
+        // Defaults would silently drop everything the config file pins (API key, mappings, ...)
+        Err(e) if config_file.is_some() => anyhow::bail!("Invalid configuration: {}", e),
         Err(e) => {
             tracing::warn!("⚠️ Failed to load config, using defaults: {}", e);
             Default::default()
@@ -103,9 +144,13 @@ async fn run_server(port: u16) -> Result<()> {
     antigravity_core::proxy::common::thinking_config::update_thinking_budget_config(
         initial_proxy_config.thinking_budget.clone(),
     );
+    antigravity_core::proxy::common::image_retention::set_retention_turns(
+        initial_proxy_config.image_retention_turns,
+    );
 
     let token_manager = Arc::new(antigravity_core::proxy::TokenManager::new(data_dir.clone()));
 
+    let mut pg_pool = None;
     let repository: Option<Arc<dyn AccountRepository>> = match std::env::var("DATABASE_URL") {
         Ok(database_url) => {
             info!("🗄️ Connecting to PostgreSQL...");
@@ -127,6 +172,7 @@ async fn run_server(port: u16) -> Result<()> {
                         tracing::warn!("⚠️ JSON migration skipped or failed: {}", e);
                     }
                     SignatureCache::global().set_db_pool(repo.pool().clone());
+                    pg_pool = Some(repo.pool().clone());
                     Some(Arc::new(repo) as Arc<dyn AccountRepository>)
                 },
                 Err(e) => {
@@ -160,21 +206,43 @@ async fn run_server(port: u16) -> Result<()> {
     token_manager.start_auto_cleanup();
     token_manager.start_auto_account_sync();
 
+    let api_key_store = ApiKeyStore::new(pg_pool.clone())
+        .map_err(|e| anyhow::anyhow!("Failed to open API key store: {}", e))?;
+    let api_keys = Arc::new(ApiKeyRegistry::new(api_key_store));
+    match api_keys.reload().await {
+        Ok(count) => info!("🔑 Loaded {} client API keys", count),
+        Err(e) => tracing::warn!("⚠️ Could not load client API keys: {}", e),
+    }
+    api_keys.start_usage_flush();
+
+    let batch_store = BatchStore::new(pg_pool.clone())
+        .map_err(|e| anyhow::anyhow!("Failed to open batch store: {}", e))?;
+    let batches = Arc::new(BatchManager::new(batch_store));
+
+    let events = Arc::new(state::BroadcastEventBus::new());
     let monitor = if let Some(ref repo) = repository {
-        Arc::new(antigravity_core::proxy::ProxyMonitor::with_db(
-            Arc::new(antigravity_core::proxy::monitor::NoopEventBus),
+        antigravity_core::proxy::ProxyMonitor::with_db(
+            events.clone(),
             Arc::clone(repo),
             token_manager.tokens_ref().clone(),
-        ))
+        )
     } else {
-        Arc::new(antigravity_core::proxy::ProxyMonitor::new())
+        antigravity_core::proxy::ProxyMonitor::with_event_bus(events.clone())
     };
+    let monitor = Arc::new(monitor.with_api_keys(Arc::clone(&api_keys)));
+    if let Err(e) = monitor.spend().hydrate().await {
+        tracing::warn!("⚠️ Could not load usage rollups: {}", e);
+    }
+    monitor.spend().start_flush();
 
     let state = AppState::new_with_components(
         token_manager.clone(),
         monitor.clone(),
         initial_proxy_config.clone(),
         repository,
+        api_keys,
+        batches,
+        events,
     )
     .await?;
 
@@ -182,11 +250,32 @@ async fn run_server(port: u16) -> Result<()> {
     info!("📊 {} accounts loaded", state.get_account_count().await.unwrap_or(0));
 
     state.hydrate_proxy_assignments().await;
+    if initial_proxy_config.cluster.enabled {
+        match (&pg_pool, initial_proxy_config.cluster.backend) {
+            (Some(pool), ClusterBackendKind::Postgres) => {
+                let backend = Arc::new(PostgresClusterBackend::new(
+                    pool.clone(),
+                    initial_proxy_config.cluster.channel.clone(),
+                ));
+                let instance_id = state::get_instance_id();
+                info!("🔗 Cluster mode enabled (instance {})", instance_id);
+                state.start_cluster(Arc::new(Cluster::new(instance_id, backend)));
+            },
+            (None, ClusterBackendKind::Postgres) => {
+                tracing::warn!("⚠️ Cluster mode requires DATABASE_URL; running standalone");
+            },
+        }
+    }
+    state.start_notifier();
 
     scheduler::start(state.clone());
     scheduler::start_quota_refresh(state.clone());
     scheduler::start_oauth_cleanup(state.clone());
 
+    if let Some(path) = config_file {
+        config_watch::start_config_watch(state.clone(), path);
+    }
+
     if let Ok(remote_url) = std::env::var("ANTIGRAVITY_SYNC_REMOTE") {
         config_sync::start_auto_config_sync(Arc::new(state.clone()), remote_url);
     }
@@ -204,6 +293,10 @@ async fn run_server(port: u16) -> Result<()> {
 
     axum::serve(listener, app).with_graceful_shutdown(server_utils::shutdown_signal()).await?;
 
+    if let Err(e) = monitor.spend().flush().await {
+        tracing::warn!("⚠️ Could not save usage rollups: {}", e);
+    }
+
     info!("👋 Server shutdown complete");
     Ok(())
 }
@@ -9,7 +9,9 @@ use tower_http::{
 
 use crate::api;
 use crate::state::AppState;
-use antigravity_core::proxy::middleware::{admin_auth_middleware, cors::cors_layer};
+use antigravity_core::proxy::middleware::{
+    admin_auth_middleware, cors::cors_layer, trace_middleware,
+};
 
 pub async fn build_router(state: AppState) -> Router {
     let proxy_router = state.build_proxy_router().await;
@@ -46,6 +48,7 @@ pub async fn build_router(state: AppState) -> Router {
         .fallback_service(spa_service)
         .layer(DefaultBodyLimit::max(100 * 1024 * 1024))
         .layer(TraceLayer::new_for_http())
+        .layer(middleware::from_fn(trace_middleware))
         .layer(cors_layer())
 }
 
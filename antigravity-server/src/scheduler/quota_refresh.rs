@@ -5,10 +5,12 @@ use tokio::time::interval;
 
 use crate::state::AppState;
 use antigravity_core::modules::{account, config};
+use antigravity_core::proxy::monitor::ProxyEventBus;
 use antigravity_types::models::QuotaData;
 
 /// Persist quota to PostgreSQL (primary) and JSON (best-effort fallback).
 pub async fn persist_quota(state: &AppState, account_id: &str, email: &str, quota: QuotaData) {
+    state.events().emit_quota_update(account_id, &quota);
     // When PG is available, it's the source of truth — update PG first.
     // JSON update is best-effort (PG UUIDs don't match JSON filenames).
     if let Some(repo) = state.repository() {
@@ -49,6 +51,11 @@ pub fn start_quota_refresh(state: AppState) {
         loop {
             check_interval.tick().await;
 
+            // Followers would fetch the same quotas again
+            if !state.is_leader() {
+                continue;
+            }
+
             let app_config = match tokio::task::spawn_blocking(config::load_config).await {
                 Ok(res) => match res {
                     Ok(cfg) => cfg,
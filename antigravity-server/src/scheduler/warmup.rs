@@ -38,6 +38,11 @@ pub fn start(state: AppState) {
         loop {
             check_interval.tick().await;
 
+            // Leader-only in a cluster so accounts aren't warmed once per instance
+            if !state.is_leader() {
+                continue;
+            }
+
             let app_config = match tokio::task::spawn_blocking(config::load_config).await {
                 Ok(res) => match res {
                     Ok(cfg) => cfg,
@@ -7,8 +7,10 @@ use std::time::Instant;
 use antigravity_core::models::Account;
 use antigravity_core::modules::account;
 use antigravity_core::modules::repository::AccountRepository;
+use antigravity_core::proxy::cluster::{Cluster, ClusterMembers};
 use antigravity_core::proxy::{
-    AdaptiveLimitManager, CircuitBreakerManager, HealthMonitor, ProxySecurityConfig,
+    AdaptiveLimitManager, ApiKeyRegistry, CircuitBreakerManager, HealthMonitor, Notifier,
+    ProxySecurityConfig,
 };
 
 use super::AppState;
@@ -94,6 +96,60 @@ impl AppState {
         self.inner.monitor.get_logs(limit).await
     }
 
+    pub async fn search_proxy_logs(
+        &self,
+        query: antigravity_types::models::ProxyLogQuery,
+    ) -> Result<Vec<antigravity_types::models::ProxyRequestLog>, String> {
+        antigravity_core::modules::proxy_db::search_logs(query).await
+    }
+
+    pub async fn usage_report(
+        &self,
+        query: antigravity_types::models::UsageQuery,
+    ) -> Result<antigravity_types::models::UsageReport, String> {
+        self.inner.monitor.spend().report(query).await
+    }
+
+    pub fn budget_statuses(&self) -> Vec<antigravity_types::models::BudgetStatus> {
+        self.inner.monitor.spend().budget_statuses()
+    }
+
+    /// Start feeding the webhook notifier from the monitor bus, `account_events`
+    /// (PostgreSQL only) and pool availability checks.
+    pub fn start_notifier(&self) {
+        let notifier = &self.inner.notifier;
+        let token_manager = &self.inner.token_manager;
+        notifier.start_event_forwarding(
+            self.inner.events.subscribe(None).receiver,
+            Arc::clone(token_manager),
+        );
+        if let Some(repo) = &self.inner.repository {
+            notifier.start_account_event_watch(Arc::clone(repo), Arc::clone(token_manager));
+        }
+        notifier.start_pool_watch(Arc::clone(token_manager));
+    }
+
+    /// Join the cluster: share rate limits, sessions, circuit breakers and AIMD limits
+    /// with the other instances and take part in leader election.
+    pub fn start_cluster(&self, cluster: Arc<Cluster>) {
+        cluster.start(ClusterMembers {
+            token_manager: Arc::clone(&self.inner.token_manager),
+            circuit_breaker: Arc::clone(&self.inner.circuit_breaker),
+            adaptive_limits: Arc::clone(&self.inner.adaptive_limits),
+        });
+        self.inner.notifier.set_cluster(Arc::clone(&cluster));
+        self.inner.cluster.set(cluster);
+    }
+
+    /// Whether this instance runs the schedulers (always true when standalone).
+    pub fn is_leader(&self) -> bool {
+        self.inner.cluster.is_leader()
+    }
+
+    pub fn notifier(&self) -> &Arc<Notifier> {
+        &self.inner.notifier
+    }
+
     pub async fn clear_proxy_logs(&self) {
         self.inner.monitor.clear_logs().await;
     }
@@ -127,11 +183,23 @@ impl AppState {
         antigravity_core::proxy::common::thinking_config::update_thinking_budget_config(
             proxy_config.thinking_budget.clone(),
         );
+        antigravity_core::proxy::common::image_retention::set_retention_turns(
+            proxy_config.image_retention_turns,
+        );
+        self.inner.upstream_client.set_upstream_urls(&proxy_config.upstream_urls);
+        self.inner.monitor.set_capture_config(proxy_config.capture.clone());
+        self.inner.monitor.set_spend_config(proxy_config.spend.clone());
+        self.inner.notifier.set_config(proxy_config.notifier.clone());
+        self.inner.token_manager.set_admission_config(proxy_config.admission.clone());
+        self.inner.adaptive_limits.set_hedging_config(proxy_config.hedging.clone());
+        self.inner.batches.set_config(proxy_config.batch.clone());
 
         // Acquire ALL write guards atomically (alphabetical lock order to prevent deadlocks)
         let mut mapping = self.inner.custom_mapping.write().await;
         let mut experimental = self.inner.experimental_config.write().await;
         let mut inner_proxy_config = self.inner.proxy_config.write().await;
+        let mut providers = self.inner.providers.write().await;
+        let mut routing_rules = self.inner.routing_rules.write().await;
         let mut security = self.inner.security_config.write().await;
         let mut upstream = self.inner.upstream_proxy.write().await;
         let mut zai = self.inner.zai_config.write().await;
@@ -141,6 +209,8 @@ impl AppState {
         *security = ProxySecurityConfig::from_proxy_config(&proxy_config);
         *upstream = proxy_config.upstream_proxy.clone();
         *zai = proxy_config.zai.clone();
+        *providers = proxy_config.providers.clone();
+        *routing_rules = proxy_config.routing_rules.clone();
         *inner_proxy_config = proxy_config;
 
         // Sync enforce_proxy to TokenManager for side-channel leak prevention
@@ -186,10 +256,18 @@ impl AppState {
         &self.inner.health_monitor
     }
 
+    pub fn events(&self) -> &Arc<super::BroadcastEventBus> {
+        &self.inner.events
+    }
+
     pub fn circuit_breaker(&self) -> &Arc<CircuitBreakerManager> {
         &self.inner.circuit_breaker
     }
 
+    pub fn api_keys(&self) -> &Arc<ApiKeyRegistry> {
+        &self.inner.api_keys
+    }
+
     pub fn generate_oauth_state(&self, proxy_url: Option<String>) -> String {
         use rand::Rng;
 
@@ -0,0 +1,108 @@
+//! Broadcast event bus behind `/api/monitor/stream`.
+//!
+//! Every event gets an increasing ID and is kept in a bounded replay buffer, so a
+//! reconnecting client can send its last seen ID and receive what it missed.
+
+use std::collections::VecDeque;
+use std::sync::Mutex;
+
+use antigravity_core::proxy::monitor::ProxyEventBus;
+use antigravity_types::models::{
+    AccountStateChange, BudgetAlert, MonitorEvent, MonitorEventPayload, ProxyRequestLog, QuotaData,
+};
+use tokio::sync::broadcast;
+
+const REPLAY_BUFFER_SIZE: usize = 1000;
+const CHANNEL_CAPACITY: usize = 1024;
+
+#[derive(Default)]
+struct History {
+    last_id: u64,
+    events: VecDeque<MonitorEvent>,
+}
+
+/// Live subscription: missed events to replay first, then the live receiver.
+pub struct Subscription {
+    /// Events after the client's last seen ID still in the replay buffer
+    pub backlog: Vec<MonitorEvent>,
+    /// True when events were dropped from the buffer before the client resumed
+    pub gap: bool,
+    pub receiver: broadcast::Receiver<MonitorEvent>,
+}
+
+pub struct BroadcastEventBus {
+    sender: broadcast::Sender<MonitorEvent>,
+    history: Mutex<History>,
+}
+
+impl BroadcastEventBus {
+    pub fn new() -> Self {
+        let (sender, _) = broadcast::channel(CHANNEL_CAPACITY);
+        Self { sender, history: Mutex::new(History::default()) }
+    }
+
+    #[allow(clippy::arithmetic_side_effects, reason = "u64 event counter")]
+    pub fn publish(&self, payload: MonitorEventPayload) {
+        let mut history = self.history.lock().unwrap_or_else(std::sync::PoisonError::into_inner);
+        history.last_id += 1;
+        let event = MonitorEvent {
+            id: history.last_id,
+            timestamp: chrono::Utc::now().timestamp_millis(),
+            payload,
+        };
+        if history.events.len() >= REPLAY_BUFFER_SIZE {
+            history.events.pop_front();
+        }
+        history.events.push_back(event.clone());
+        // Sent under the lock so subscribers never see events out of order
+        let _ = self.sender.send(event);
+    }
+
+    /// Subscribe, replaying events newer than `last_id` (none when `None`).
+    pub fn subscribe(&self, last_id: Option<u64>) -> Subscription {
+        let history = self.history.lock().unwrap_or_else(std::sync::PoisonError::into_inner);
+        let receiver = self.sender.subscribe();
+        let Some(last_id) = last_id else {
+            return Subscription { backlog: Vec::new(), gap: false, receiver };
+        };
+
+        let oldest = history.events.front().map_or(history.last_id.saturating_add(1), |e| e.id);
+        // A last ID from before a restart is ahead of the counter: replay everything
+        let restarted = last_id > history.last_id;
+        let gap = restarted || oldest > last_id.saturating_add(1);
+        let backlog =
+            history.events.iter().filter(|e| restarted || e.id > last_id).cloned().collect();
+        Subscription { backlog, gap, receiver }
+    }
+}
+
+impl Default for BroadcastEventBus {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ProxyEventBus for BroadcastEventBus {
+    fn emit_request_log(&self, log: &ProxyRequestLog) {
+        self.publish(MonitorEventPayload::RequestLog(Box::new(log.clone())));
+    }
+
+    fn emit_account_state(&self, change: &AccountStateChange) {
+        self.publish(MonitorEventPayload::AccountState(change.clone()));
+    }
+
+    fn emit_quota_update(&self, account_id: &str, quota: &QuotaData) {
+        self.publish(MonitorEventPayload::QuotaUpdated {
+            account_id: account_id.to_string(),
+            quota: quota.clone(),
+        });
+    }
+
+    fn emit_budget_alert(&self, alert: &BudgetAlert) {
+        self.publish(MonitorEventPayload::BudgetAlert(alert.clone()));
+    }
+}
+
+#[cfg(test)]
+#[path = "events_tests.rs"]
+mod tests;
@@ -0,0 +1,50 @@
+use antigravity_core::proxy::monitor::ProxyEventBus;
+use antigravity_types::models::{MonitorEventPayload, QuotaData};
+
+use super::{BroadcastEventBus, REPLAY_BUFFER_SIZE};
+
+fn publish_quota(bus: &BroadcastEventBus, count: usize) {
+    for i in 0..count {
+        bus.emit_quota_update(&format!("acc-{i}"), &QuotaData::new());
+    }
+}
+
+#[test]
+fn fresh_subscription_has_no_backlog() {
+    let bus = BroadcastEventBus::new();
+    publish_quota(&bus, 3);
+    let sub = bus.subscribe(None);
+    assert!(sub.backlog.is_empty());
+    assert!(!sub.gap);
+}
+
+#[tokio::test]
+async fn resumes_after_last_seen_id() {
+    let bus = BroadcastEventBus::new();
+    publish_quota(&bus, 5);
+
+    let mut sub = bus.subscribe(Some(3));
+    let ids: Vec<u64> = sub.backlog.iter().map(|e| e.id).collect();
+    assert_eq!(ids, vec![4, 5]);
+    assert!(!sub.gap);
+
+    publish_quota(&bus, 1);
+    let live = sub.receiver.recv().await.unwrap();
+    assert_eq!(live.id, 6);
+    assert!(matches!(live.payload, MonitorEventPayload::QuotaUpdated { .. }));
+}
+
+#[test]
+fn reports_gap_when_buffer_overflowed() {
+    let bus = BroadcastEventBus::new();
+    publish_quota(&bus, REPLAY_BUFFER_SIZE + 10);
+
+    let sub = bus.subscribe(Some(2));
+    assert!(sub.gap);
+    assert_eq!(sub.backlog.len(), REPLAY_BUFFER_SIZE);
+
+    // An ID from a previous server process replays the whole buffer
+    let sub = bus.subscribe(Some(u64::MAX));
+    assert!(sub.gap);
+    assert_eq!(sub.backlog.len(), REPLAY_BUFFER_SIZE);
+}
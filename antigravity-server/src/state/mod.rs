@@ -4,8 +4,11 @@
 
 mod accessors;
 mod config_sync;
+mod events;
 mod proxy_sync;
 
+pub use events::BroadcastEventBus;
+
 use anyhow::Result;
 use axum::Router;
 use dashmap::DashMap;
@@ -15,9 +18,11 @@ use std::time::Instant;
 use tokio::sync::RwLock;
 
 use antigravity_core::modules::repository::AccountRepository;
+use antigravity_core::proxy::cluster::ClusterSlot;
 use antigravity_core::proxy::{
-    build_proxy_router_with_shared_state, AdaptiveLimitManager, CircuitBreakerManager,
-    HealthMonitor, ProxyMonitor, ProxyRouterConfig, ProxySecurityConfig, TokenManager,
+    build_proxy_router_with_shared_state, AdaptiveLimitManager, ApiKeyRegistry, BatchManager,
+    CircuitBreakerManager, HealthMonitor, Notifier, ProxyMonitor, ProxyRouterConfig,
+    ProxySecurityConfig, ResponseStore, TokenManager,
 };
 use antigravity_types::models::ProxyConfig;
 
@@ -38,6 +43,8 @@ pub struct AppStateInner {
     pub security_config: Arc<RwLock<ProxySecurityConfig>>,
     pub upstream_proxy: Arc<RwLock<antigravity_types::models::UpstreamProxyConfig>>,
     pub zai_config: Arc<RwLock<antigravity_types::models::ZaiConfig>>,
+    pub providers: Arc<RwLock<Vec<antigravity_types::models::ProviderConfig>>>,
+    pub routing_rules: Arc<RwLock<Vec<antigravity_types::models::RoutingRule>>>,
     pub experimental_config: Arc<RwLock<antigravity_types::models::ExperimentalConfig>>,
     pub adaptive_limits: Arc<AdaptiveLimitManager>,
     pub health_monitor: Arc<HealthMonitor>,
@@ -50,6 +57,12 @@ pub struct AppStateInner {
     pub zai_vision_mcp: Arc<antigravity_core::proxy::zai_vision_mcp::ZaiVisionMcpState>,
     pub upstream_client: Arc<antigravity_core::proxy::upstream::client::UpstreamClient>,
     pub proxy_assignments: Arc<RwLock<antigravity_types::SyncableProxyAssignments>>,
+    pub api_keys: Arc<ApiKeyRegistry>,
+    pub events: Arc<BroadcastEventBus>,
+    pub response_store: Arc<ResponseStore>,
+    pub batches: Arc<BatchManager>,
+    pub notifier: Arc<Notifier>,
+    pub cluster: ClusterSlot,
 }
 
 impl AppState {
@@ -59,12 +72,17 @@ impl AppState {
         monitor: Arc<ProxyMonitor>,
         proxy_config: ProxyConfig,
         repository: Option<Arc<dyn AccountRepository>>,
+        api_keys: Arc<ApiKeyRegistry>,
+        batches: Arc<BatchManager>,
+        events: Arc<BroadcastEventBus>,
     ) -> Result<Self> {
         let custom_mapping = Arc::new(RwLock::new(proxy_config.custom_mapping.clone()));
         let upstream_proxy = Arc::new(RwLock::new(proxy_config.upstream_proxy.clone()));
         let security_config =
             Arc::new(RwLock::new(ProxySecurityConfig::from_proxy_config(&proxy_config)));
         let zai_config = Arc::new(RwLock::new(proxy_config.zai.clone()));
+        let providers = Arc::new(RwLock::new(proxy_config.providers.clone()));
+        let routing_rules = Arc::new(RwLock::new(proxy_config.routing_rules.clone()));
         let experimental_config = Arc::new(RwLock::new(proxy_config.experimental));
 
         let adaptive_limits = Arc::new(AdaptiveLimitManager::new(
@@ -87,10 +105,18 @@ impl AppState {
         });
 
         health_monitor.start_recovery_task();
+        circuit_breaker.set_event_bus(events.clone());
+        token_manager.set_event_bus(events.clone());
 
         token_manager.set_adaptive_limits(adaptive_limits.clone()).await;
         token_manager.set_health_monitor(health_monitor.clone()).await;
         token_manager.set_enforce_proxy(proxy_config.upstream_proxy.enforce_proxy);
+        token_manager.set_admission_config(proxy_config.admission.clone());
+        adaptive_limits.set_hedging_config(proxy_config.hedging.clone());
+        batches.set_config(proxy_config.batch.clone());
+        monitor.set_capture_config(proxy_config.capture.clone());
+        monitor.set_spend_config(proxy_config.spend.clone());
+        let notifier = Arc::new(Notifier::new(proxy_config.notifier.clone()));
 
         tracing::info!("AIMD rate limiting system initialized");
 
@@ -103,6 +129,7 @@ impl AppState {
                 Arc::clone(&upstream_proxy),
                 None,
             ));
+        upstream_client.set_upstream_urls(&proxy_config.upstream_urls);
 
         Ok(Self {
             inner: Arc::new(AppStateInner {
@@ -114,6 +141,8 @@ impl AppState {
                 mapping_timestamps: Arc::new(RwLock::new(std::collections::HashMap::new())),
                 security_config,
                 zai_config,
+                providers,
+                routing_rules,
                 experimental_config,
                 adaptive_limits,
                 health_monitor,
@@ -128,6 +157,12 @@ impl AppState {
                 proxy_assignments: Arc::new(RwLock::new(
                     antigravity_types::SyncableProxyAssignments::new(),
                 )),
+                api_keys,
+                events,
+                response_store: Arc::new(ResponseStore::new()),
+                batches,
+                notifier,
+                cluster: ClusterSlot::default(),
             }),
         })
     }
@@ -139,6 +174,7 @@ impl AppState {
             upstream_proxy: Arc::clone(&self.inner.upstream_proxy),
             security_config: self.inner.security_config.clone(),
             zai: self.inner.zai_config.clone(),
+            providers: self.inner.providers.clone(),
             monitor: self.inner.monitor.clone(),
             experimental: self.inner.experimental_config.clone(),
             adaptive_limits: self.inner.adaptive_limits.clone(),
@@ -148,6 +184,10 @@ impl AppState {
             provider_rr: self.inner.provider_rr.clone(),
             zai_vision_mcp: self.inner.zai_vision_mcp.clone(),
             upstream_client: self.inner.upstream_client.clone(),
+            api_keys: self.inner.api_keys.clone(),
+            response_store: self.inner.response_store.clone(),
+            batches: self.inner.batches.clone(),
+            routing_rules: self.inner.routing_rules.clone(),
         })
     }
 }
@@ -4,23 +4,42 @@ use std::sync::Arc;
 
 use tempfile::TempDir;
 
-use antigravity_core::proxy::{ProxyMonitor, TokenManager};
+use antigravity_core::modules::api_key_store::ApiKeyStore;
+use antigravity_core::modules::batch_store::BatchStore;
+use antigravity_core::proxy::{ApiKeyRegistry, BatchManager, ProxyMonitor, TokenManager};
 use antigravity_types::models::ProxyConfig;
 
-use crate::state::AppState;
+use crate::state::{AppState, BroadcastEventBus};
 
 /// Create a minimal `AppState` for testing.
 ///
 /// Returns `(AppState, TempDir)` — keep `TempDir` alive for the test duration.
 pub async fn test_app_state() -> (AppState, TempDir) {
+    test_app_state_with_config(ProxyConfig::default()).await
+}
+
+/// Like [`test_app_state`], starting from `proxy_config`.
+pub async fn test_app_state_with_config(proxy_config: ProxyConfig) -> (AppState, TempDir) {
     let temp_dir = TempDir::new().expect("failed to create temp dir");
     let token_manager = Arc::new(TokenManager::new(temp_dir.path().to_path_buf()));
-    let monitor = Arc::new(ProxyMonitor::new());
-    let proxy_config = ProxyConfig::default();
+    let api_keys =
+        Arc::new(ApiKeyRegistry::new(ApiKeyStore::Json(temp_dir.path().join("api_keys.json"))));
+    let batches = Arc::new(BatchManager::new(BatchStore::Json(temp_dir.path().join("batches"))));
+    let events = Arc::new(BroadcastEventBus::new());
+    let monitor =
+        Arc::new(ProxyMonitor::with_event_bus(events.clone()).with_api_keys(Arc::clone(&api_keys)));
 
-    let state = AppState::new_with_components(token_manager, monitor, proxy_config, None)
-        .await
-        .expect("failed to create test AppState");
+    let state = AppState::new_with_components(
+        token_manager,
+        monitor,
+        proxy_config,
+        None,
+        api_keys,
+        batches,
+        events,
+    )
+    .await
+    .expect("failed to create test AppState");
 
     (state, temp_dir)
 }
@@ -0,0 +1,146 @@
+//! Admin API (`/api/...`): accounts, quotas, rate limits, monitoring and configuration.
+
+use antigravity_types::models::{
+    AccountInfo, AppConfig, ProxyRequestLog, ProxyStats, ProxyStatus, QuotaResponse,
+    RefreshQuotaRequest, RefreshStats, ServerStatus, ToggleProxyRequest, ToggleProxyResponse,
+};
+use reqwest::Method;
+
+use crate::client::{AntigravityClient, Auth};
+use crate::error::ClientError;
+
+impl AntigravityClient {
+    /// Server version, proxy state and account count.
+    ///
+    /// # Errors
+    /// Returns `ClientError` on connection failure, authentication failure, or server error.
+    pub async fn server_status(&self) -> Result<ServerStatus, ClientError> {
+        self.send_json(self.request(Method::GET, "/api/status", Auth::Admin)).await
+    }
+
+    /// Proxy port, base URL and active account count.
+    ///
+    /// # Errors
+    /// Returns `ClientError` on connection failure, authentication failure, or server error.
+    pub async fn proxy_status(&self) -> Result<ProxyStatus, ClientError> {
+        self.send_json(self.request(Method::GET, "/api/proxy/status", Auth::Admin)).await
+    }
+
+    /// All accounts with their quota and proxy state.
+    ///
+    /// # Errors
+    /// Returns `ClientError` on connection failure, authentication failure, or server error.
+    pub async fn list_accounts(&self) -> Result<Vec<AccountInfo>, ClientError> {
+        self.send_json(self.request(Method::GET, "/api/accounts", Auth::Admin)).await
+    }
+
+    /// Includes an account in the proxy pool or excludes it, with an optional reason.
+    ///
+    /// # Errors
+    /// Returns `ClientError::ServerError` with status 404 for an unknown account.
+    pub async fn set_account_proxy_enabled(
+        &self,
+        account_id: &str,
+        enable: bool,
+        reason: Option<&str>,
+    ) -> Result<ToggleProxyResponse, ClientError> {
+        let body = ToggleProxyRequest {
+            account_id: account_id.to_string(),
+            enable,
+            reason: reason.map(str::to_string),
+        };
+        let builder =
+            self.request(Method::POST, "/api/accounts/toggle-proxy", Auth::Admin).json(&body);
+        self.send_json(builder).await
+    }
+
+    /// Fetches one account's quota from upstream.
+    ///
+    /// # Errors
+    /// Returns `ClientError::ServerError` with status 404 for an unknown account.
+    pub async fn refresh_quota(&self, account_id: &str) -> Result<QuotaResponse, ClientError> {
+        let body = RefreshQuotaRequest { account_id: account_id.to_string() };
+        let builder =
+            self.request(Method::POST, "/api/accounts/refresh-quota", Auth::Admin).json(&body);
+        self.send_json(builder).await
+    }
+
+    /// Fetches every account's quota from upstream.
+    ///
+    /// # Errors
+    /// Returns `ClientError` on connection failure, authentication failure, or server error.
+    pub async fn refresh_all_quotas(&self) -> Result<RefreshStats, ClientError> {
+        let builder = self.request(Method::POST, "/api/accounts/refresh-all-quotas", Auth::Admin);
+        self.send_json(builder).await
+    }
+
+    /// Clears the rate-limit state of all accounts.
+    ///
+    /// # Errors
+    /// Returns `ClientError` on connection failure, authentication failure, or server error.
+    pub async fn clear_rate_limits(&self) -> Result<(), ClientError> {
+        self.send(self.request(Method::DELETE, "/api/proxy/rate-limits", Auth::Admin)).await?;
+        Ok(())
+    }
+
+    /// Clears one account's rate-limit state; `false` if it wasn't rate-limited.
+    ///
+    /// # Errors
+    /// Returns `ClientError` on connection failure, authentication failure, or server error.
+    pub async fn clear_rate_limit(&self, account_id: &str) -> Result<bool, ClientError> {
+        let path = format!("/api/proxy/rate-limits/{account_id}");
+        match self.send(self.request(Method::DELETE, &path, Auth::Admin)).await {
+            Ok(_) => Ok(true),
+            Err(ClientError::ServerError { status: 404, .. }) => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Most recent request logs, newest first.
+    ///
+    /// # Errors
+    /// Returns `ClientError` on connection failure, authentication failure, or server error.
+    pub async fn monitor_requests(
+        &self,
+        limit: Option<usize>,
+    ) -> Result<Vec<ProxyRequestLog>, ClientError> {
+        let mut builder = self.request(Method::GET, "/api/monitor/requests", Auth::Admin);
+        if let Some(limit) = limit {
+            builder = builder.query(&[("limit", limit)]);
+        }
+        self.send_json(builder).await
+    }
+
+    /// Aggregate request counters.
+    ///
+    /// # Errors
+    /// Returns `ClientError` on connection failure, authentication failure, or server error.
+    pub async fn monitor_stats(&self) -> Result<ProxyStats, ClientError> {
+        self.send_json(self.request(Method::GET, "/api/monitor/stats", Auth::Admin)).await
+    }
+
+    /// Deletes the stored request logs.
+    ///
+    /// # Errors
+    /// Returns `ClientError` on connection failure, authentication failure, or server error.
+    pub async fn clear_monitor_logs(&self) -> Result<bool, ClientError> {
+        self.send_json(self.request(Method::POST, "/api/monitor/clear", Auth::Admin)).await
+    }
+
+    /// Current server configuration.
+    ///
+    /// # Errors
+    /// Returns `ClientError` on connection failure, authentication failure, or server error.
+    pub async fn get_config(&self) -> Result<AppConfig, ClientError> {
+        self.send_json(self.request(Method::GET, "/api/config", Auth::Admin)).await
+    }
+
+    /// Saves and applies a new server configuration.
+    ///
+    /// # Errors
+    /// Returns `ClientError` on connection failure, authentication failure, or server error.
+    pub async fn save_config(&self, config: &AppConfig) -> Result<bool, ClientError> {
+        let builder = self.request(Method::POST, "/api/config", Auth::Admin).json(config);
+        self.send_json(builder).await
+    }
+}
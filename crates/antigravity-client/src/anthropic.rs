@@ -0,0 +1,44 @@
+//! Anthropic-compatible Messages API.
+
+use antigravity_types::protocol::claude::{ClaudeRequest, ClaudeResponse, ClaudeStreamEvent};
+use reqwest::{Method, RequestBuilder};
+
+use crate::client::{AntigravityClient, Auth, EventStream};
+use crate::error::ClientError;
+
+const ANTHROPIC_VERSION: &str = "2023-06-01";
+
+impl AntigravityClient {
+    /// Sends a Messages request, including extended thinking if configured.
+    ///
+    /// # Errors
+    /// Returns `ClientError` on connection failure, rate limiting, or server error.
+    pub async fn messages(
+        &self,
+        mut request: ClaudeRequest,
+    ) -> Result<ClaudeResponse, ClientError> {
+        request.stream = false;
+        self.send_json(self.messages_request(&request)).await
+    }
+
+    /// Sends a streaming Messages request.
+    ///
+    /// The request is modified to enable streaming; the returned stream yields the
+    /// Anthropic event sequence (`message_start`, block deltas, `message_stop`).
+    ///
+    /// # Errors
+    /// Returns `ClientError` on connection failure, rate limiting, or server error.
+    pub async fn messages_stream(
+        &self,
+        mut request: ClaudeRequest,
+    ) -> Result<EventStream<ClaudeStreamEvent>, ClientError> {
+        request.stream = true;
+        self.send_stream(self.messages_request(&request)).await
+    }
+
+    fn messages_request(&self, request: &ClaudeRequest) -> RequestBuilder {
+        self.request(Method::POST, "/v1/messages", Auth::Proxy)
+            .header("anthropic-version", ANTHROPIC_VERSION)
+            .json(request)
+    }
+}
@@ -1,17 +1,32 @@
 //! Antigravity client implementation with auto-discovery and retry logic.
 
-use crate::error::ClientError;
-use crate::messages::{ChatRequest, ChatResponse, ClientConfig, StreamChunk};
-use futures::Stream;
-use reqwest::Client;
 use std::pin::Pin;
 use std::time::Duration;
-use tokio_stream::StreamExt;
+
+use futures::Stream;
+use reqwest::{Client, Method, RequestBuilder, Response};
+use serde::de::DeserializeOwned;
+
+use crate::config::ClientConfig;
+use crate::error::ClientError;
+use crate::sse;
+
+/// Typed stream of server-sent events.
+pub type EventStream<T> = Pin<Box<dyn Stream<Item = Result<T, ClientError>> + Send>>;
+
+/// Which key a request authenticates with.
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum Auth {
+    /// Inference endpoints (`/v1/...`, `/v1beta/...`).
+    Proxy,
+    /// Admin API (`/api/...`).
+    Admin,
+}
 
 /// HTTP client for communicating with Antigravity server.
 ///
-/// Provides auto-discovery, automatic retry with exponential backoff,
-/// and both blocking and streaming chat completion APIs.
+/// Provides auto-discovery, automatic retry with exponential backoff, typed
+/// OpenAI, Anthropic and Gemini inference (blocking and streaming), and the admin API.
 #[derive(Debug)]
 pub struct AntigravityClient {
     client: Client,
@@ -50,38 +65,77 @@ impl AntigravityClient {
         let client = Self::new(config)?;
         let resp = client
             .client
-            .get(format!("{}/api/proxy/status", client.config.base_url))
+            .get(format!("{}/healthz", client.config.base_url))
             .timeout(Duration::from_secs(2))
             .send()
             .await
             .map_err(|e| ClientError::Connection(e.to_string()))?;
-        if resp.status().is_success() {
+        if !resp.status().is_success() {
+            return Err(ClientError::Connection(format!("Health check failed: {}", resp.status())));
+        }
+        // Anything else listening on the port won't answer with Antigravity's health body.
+        let body: serde_json::Value =
+            resp.json().await.map_err(|e| ClientError::Connection(e.to_string()))?;
+        if body.get("status").and_then(|s| s.as_str()) == Some("ok") {
             Ok(client)
         } else {
-            Err(ClientError::Connection(format!("Health check failed: {}", resp.status())))
+            Err(ClientError::Connection(format!("Unexpected health response: {body}")))
         }
     }
 
-    /// Sends a chat completion request with automatic retry on transient errors.
-    ///
-    /// # Errors
-    /// Returns `ClientError` on connection failure, rate limiting, or server error.
-    pub async fn chat(&self, request: ChatRequest) -> Result<ChatResponse, ClientError> {
-        self.chat_with_retry(request).await
+    /// Returns a reference to the client configuration.
+    pub const fn config(&self) -> &ClientConfig {
+        &self.config
+    }
+
+    /// Starts a request to `path` (relative to the base URL) with the given credentials.
+    pub(crate) fn request(&self, method: Method, path: &str, auth: Auth) -> RequestBuilder {
+        let key = match auth {
+            Auth::Proxy => self.config.api_key.as_str(),
+            Auth::Admin => self.config.admin_key(),
+        };
+        self.client.request(method, format!("{}{}", self.config.base_url, path)).bearer_auth(key)
     }
 
+    /// Sends a request with retry and decodes the JSON response.
+    pub(crate) async fn send_json<T: DeserializeOwned>(
+        &self,
+        request: RequestBuilder,
+    ) -> Result<T, ClientError> {
+        let resp = self.send(request).await?;
+        resp.json().await.map_err(|e| ClientError::InvalidResponse(e.to_string()))
+    }
+
+    /// Sends a request with retry and decodes the body as server-sent events.
+    pub(crate) async fn send_stream<T>(
+        &self,
+        request: RequestBuilder,
+    ) -> Result<EventStream<T>, ClientError>
+    where
+        T: DeserializeOwned + Send + 'static,
+    {
+        let resp = self.send(request).await?;
+        Ok(sse::decode(resp))
+    }
+
+    /// Sends a request, retrying on 429 and 5xx with exponential backoff.
     #[allow(
         clippy::arithmetic_side_effects,
         clippy::integer_division,
         reason = "Retry logic: attempts counter and exponential backoff are bounded by max_retries"
     )]
-    async fn chat_with_retry(&self, request: ChatRequest) -> Result<ChatResponse, ClientError> {
+    pub(crate) async fn send(&self, request: RequestBuilder) -> Result<Response, ClientError> {
         let mut attempts = 0_u32;
         let mut delay = self.config.retry.base_delay_ms;
 
         loop {
             attempts += 1;
-            match self.chat_once(&request).await {
+            // Bodies are always buffered JSON, so cloning only fails for streaming bodies,
+            // which get a single attempt.
+            let Some(attempt) = request.try_clone() else {
+                return send_once(request).await;
+            };
+            match send_once(attempt).await {
                 Ok(response) => return Ok(response),
                 Err(ClientError::RateLimited { retry_after }) => {
                     if attempts > self.config.retry.max_retries {
@@ -104,88 +158,27 @@ impl AntigravityClient {
             }
         }
     }
+}
 
-    async fn chat_once(&self, request: &ChatRequest) -> Result<ChatResponse, ClientError> {
-        let resp = self
-            .client
-            .post(format!("{}/v1/chat/completions", self.config.base_url))
-            .header("Authorization", format!("Bearer {}", self.config.api_key))
-            .header("Content-Type", "application/json")
-            .json(request)
-            .send()
-            .await?;
-
-        let status = resp.status();
-
-        if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
-            let retry_after = resp
-                .headers()
-                .get("retry-after")
-                .and_then(|v| v.to_str().ok())
-                .and_then(|s| s.parse().ok());
-            return Err(ClientError::RateLimited { retry_after });
-        }
-
-        if !status.is_success() {
-            let message = resp.text().await.unwrap_or_default();
-            return Err(ClientError::ServerError { status: status.as_u16(), message });
-        }
-
-        resp.json().await.map_err(|e| ClientError::InvalidResponse(e.to_string()))
+async fn send_once(request: RequestBuilder) -> Result<Response, ClientError> {
+    let resp = request.send().await?;
+    let status = resp.status();
+
+    if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+        let retry_after = resp
+            .headers()
+            .get("retry-after")
+            .and_then(|v| v.to_str().ok())
+            .and_then(|s| s.parse().ok());
+        return Err(ClientError::RateLimited { retry_after });
     }
 
-    /// Sends a streaming chat completion request.
-    ///
-    /// Returns a stream of SSE chunks. The request is automatically modified
-    /// to enable streaming.
-    ///
-    /// # Errors
-    /// Returns `ClientError` on connection failure, rate limiting, or server error.
-    pub async fn chat_stream(
-        &self,
-        mut request: ChatRequest,
-    ) -> Result<Pin<Box<dyn Stream<Item = Result<StreamChunk, ClientError>> + Send>>, ClientError>
-    {
-        request.stream = Some(true);
-
-        let resp = self
-            .client
-            .post(format!("{}/v1/chat/completions", self.config.base_url))
-            .header("Authorization", format!("Bearer {}", self.config.api_key))
-            .header("Content-Type", "application/json")
-            .json(&request)
-            .send()
-            .await?;
-
-        let status = resp.status();
-
-        if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
-            let retry_after = resp
-                .headers()
-                .get("retry-after")
-                .and_then(|v| v.to_str().ok())
-                .and_then(|s| s.parse().ok());
-            return Err(ClientError::RateLimited { retry_after });
-        }
-
-        if !status.is_success() {
-            let message = resp.text().await.unwrap_or_default();
-            return Err(ClientError::ServerError { status: status.as_u16(), message });
-        }
-
-        let byte_stream = resp.bytes_stream();
-        let stream = byte_stream.map(|chunk| {
-            let bytes = chunk.map_err(|e| ClientError::Stream(e.to_string()))?;
-            parse_sse_chunk(&bytes)
-        });
-
-        Ok(Box::pin(stream))
+    if !status.is_success() {
+        let message = resp.text().await.unwrap_or_default();
+        return Err(ClientError::ServerError { status: status.as_u16(), message });
     }
 
-    /// Returns a reference to the client configuration.
-    pub const fn config(&self) -> &ClientConfig {
-        &self.config
-    }
+    Ok(resp)
 }
 
 fn discovery_candidates() -> Vec<String> {
@@ -200,20 +193,3 @@ fn discovery_candidates() -> Vec<String> {
     candidates.push("http://127.0.0.1:8046".to_string());
     candidates
 }
-
-fn parse_sse_chunk(bytes: &[u8]) -> Result<StreamChunk, ClientError> {
-    let text = std::str::from_utf8(bytes).map_err(|e| ClientError::Stream(e.to_string()))?;
-
-    for line in text.lines() {
-        if let Some(data) = line.strip_prefix("data: ") {
-            let trimmed = data.trim();
-            if trimmed.is_empty() || trimmed.starts_with(':') {
-                continue;
-            }
-            return serde_json::from_str(trimmed)
-                .map_err(|e| ClientError::Stream(format!("JSON parse error: {e}")));
-        }
-    }
-
-    Err(ClientError::Stream("No data in SSE chunk".to_string()))
-}
@@ -0,0 +1,52 @@
+//! Client configuration.
+
+/// Configuration for retry behavior on transient errors.
+#[derive(Debug, Clone, Copy)]
+pub struct RetryConfig {
+    /// Maximum number of retry attempts.
+    pub max_retries: u32,
+    /// Initial delay between retries in milliseconds.
+    pub base_delay_ms: u64,
+    /// Maximum delay between retries in milliseconds.
+    pub max_delay_ms: u64,
+}
+
+impl Default for RetryConfig {
+    fn default() -> Self {
+        Self { max_retries: 3, base_delay_ms: 500, max_delay_ms: 30_000 }
+    }
+}
+
+/// Configuration for the Antigravity client.
+#[derive(Debug, Clone)]
+pub struct ClientConfig {
+    /// Base URL of the Antigravity server.
+    pub base_url: String,
+    /// API key for the inference endpoints.
+    pub api_key: String,
+    /// Key for the admin API (`/api/...`); falls back to `api_key` when unset.
+    pub admin_key: Option<String>,
+    /// Request timeout in seconds.
+    pub timeout_secs: u64,
+    /// Retry configuration for transient errors.
+    pub retry: RetryConfig,
+}
+
+impl ClientConfig {
+    /// Key sent to the admin API.
+    pub fn admin_key(&self) -> &str {
+        self.admin_key.as_deref().unwrap_or(&self.api_key)
+    }
+}
+
+impl Default for ClientConfig {
+    fn default() -> Self {
+        Self {
+            base_url: "http://127.0.0.1:8045".to_string(),
+            api_key: "sk-antigravity".to_string(),
+            admin_key: None,
+            timeout_secs: 120,
+            retry: RetryConfig::default(),
+        }
+    }
+}
@@ -0,0 +1,37 @@
+//! Gemini-native `generateContent`.
+
+use antigravity_types::protocol::gemini::{GeminiResponse, GenerateContentRequest};
+use reqwest::Method;
+
+use crate::client::{AntigravityClient, Auth, EventStream};
+use crate::error::ClientError;
+
+impl AntigravityClient {
+    /// Calls `models/{model}:generateContent`.
+    ///
+    /// # Errors
+    /// Returns `ClientError` on connection failure, rate limiting, or server error.
+    pub async fn generate_content(
+        &self,
+        model: &str,
+        request: &GenerateContentRequest,
+    ) -> Result<GeminiResponse, ClientError> {
+        let path = format!("/v1beta/models/{model}:generateContent");
+        self.send_json(self.request(Method::POST, &path, Auth::Proxy).json(request)).await
+    }
+
+    /// Calls `models/{model}:streamGenerateContent` with SSE framing.
+    ///
+    /// Each stream item is a partial response carrying the newly generated parts.
+    ///
+    /// # Errors
+    /// Returns `ClientError` on connection failure, rate limiting, or server error.
+    pub async fn stream_generate_content(
+        &self,
+        model: &str,
+        request: &GenerateContentRequest,
+    ) -> Result<EventStream<GeminiResponse>, ClientError> {
+        let path = format!("/v1beta/models/{model}:streamGenerateContent?alt=sse");
+        self.send_stream(self.request(Method::POST, &path, Auth::Proxy).json(request)).await
+    }
+}
@@ -1,9 +1,21 @@
 #![doc = include_str!("../README.md")]
 
+mod admin;
+mod anthropic;
 mod client;
+mod config;
 mod error;
+mod gemini;
 mod messages;
+mod openai;
+mod sse;
 
-pub use client::AntigravityClient;
+pub use antigravity_types::models::{
+    AccountInfo, AppConfig, ProxyRequestLog, ProxyStats, ProxyStatus, QuotaResponse, RefreshStats,
+    ServerStatus, ToggleProxyResponse,
+};
+pub use antigravity_types::protocol;
+pub use client::{AntigravityClient, EventStream};
+pub use config::{ClientConfig, RetryConfig};
 pub use error::ClientError;
 pub use messages::*;
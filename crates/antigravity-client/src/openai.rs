@@ -0,0 +1,74 @@
+//! OpenAI-compatible chat completions and image generation.
+
+use antigravity_types::protocol::openai::{
+    ChatCompletionChunk, ImageGenerationRequest, ImageResponse, OpenAIRequest, OpenAIResponse,
+    ResponseFormat,
+};
+use reqwest::Method;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+use crate::client::{AntigravityClient, Auth, EventStream};
+use crate::error::ClientError;
+
+impl AntigravityClient {
+    /// Sends a chat completion request with automatic retry on transient errors.
+    ///
+    /// # Errors
+    /// Returns `ClientError` on connection failure, rate limiting, or server error.
+    pub async fn chat(&self, mut request: OpenAIRequest) -> Result<OpenAIResponse, ClientError> {
+        request.stream = false;
+        let builder =
+            self.request(Method::POST, "/v1/chat/completions", Auth::Proxy).json(&request);
+        self.send_json(builder).await
+    }
+
+    /// Sends a streaming chat completion request.
+    ///
+    /// The request is modified to enable streaming; the returned stream yields one
+    /// chunk per SSE event, with text, reasoning and tool call deltas.
+    ///
+    /// # Errors
+    /// Returns `ClientError` on connection failure, rate limiting, or server error.
+    pub async fn chat_stream(
+        &self,
+        mut request: OpenAIRequest,
+    ) -> Result<EventStream<ChatCompletionChunk>, ClientError> {
+        request.stream = true;
+        let builder =
+            self.request(Method::POST, "/v1/chat/completions", Auth::Proxy).json(&request);
+        self.send_stream(builder).await
+    }
+
+    /// Requests output matching `schema` and deserializes it into `T`.
+    ///
+    /// Sets a strict `json_schema` response format named `name` on the request.
+    ///
+    /// # Errors
+    /// Returns `ClientError::InvalidResponse` if the answer does not deserialize into `T`,
+    /// and the errors of [`chat`](Self::chat) otherwise.
+    pub async fn chat_structured<T: DeserializeOwned>(
+        &self,
+        mut request: OpenAIRequest,
+        name: &str,
+        schema: Value,
+    ) -> Result<T, ClientError> {
+        request.response_format = Some(ResponseFormat::json_schema(name, schema, true));
+        let text = self.chat(request).await?.text();
+        serde_json::from_str(&text)
+            .map_err(|e| ClientError::InvalidResponse(format!("Structured output: {e}: {text}")))
+    }
+
+    /// Generates images with `/v1/images/generations`.
+    ///
+    /// # Errors
+    /// Returns `ClientError` on connection failure, rate limiting, or server error.
+    pub async fn generate_images(
+        &self,
+        request: &ImageGenerationRequest,
+    ) -> Result<ImageResponse, ClientError> {
+        let builder =
+            self.request(Method::POST, "/v1/images/generations", Auth::Proxy).json(request);
+        self.send_json(builder).await
+    }
+}
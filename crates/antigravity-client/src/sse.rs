@@ -0,0 +1,137 @@
+//! Server-sent events decoding into typed stream items.
+
+use std::collections::VecDeque;
+
+use futures::stream::{self, BoxStream};
+use futures::StreamExt;
+use serde::de::DeserializeOwned;
+
+use crate::client::EventStream;
+use crate::error::ClientError;
+
+/// Splits an SSE byte stream into the `data` payloads of its events.
+///
+/// Events may arrive split across chunks at any byte; multi-line `data` fields are
+/// joined with `\n`, and the OpenAI `[DONE]` terminator is dropped.
+#[derive(Debug, Default)]
+struct SseDecoder {
+    line: Vec<u8>,
+    data: Vec<String>,
+}
+
+impl SseDecoder {
+    fn feed(&mut self, bytes: &[u8]) -> Vec<String> {
+        let mut events = Vec::new();
+        for &byte in bytes {
+            if byte != b'\n' {
+                self.line.push(byte);
+                continue;
+            }
+            let line = std::mem::take(&mut self.line);
+            self.handle_line(&line, &mut events);
+        }
+        events
+    }
+
+    fn finish(&mut self) -> Vec<String> {
+        let mut events = Vec::new();
+        let line = std::mem::take(&mut self.line);
+        if !line.is_empty() {
+            self.handle_line(&line, &mut events);
+        }
+        self.dispatch(&mut events);
+        events
+    }
+
+    fn handle_line(&mut self, line: &[u8], events: &mut Vec<String>) {
+        let line = String::from_utf8_lossy(line);
+        let line = line.strip_suffix('\r').unwrap_or(&line);
+        if line.is_empty() {
+            self.dispatch(events);
+        } else if let Some(value) = line.strip_prefix("data:") {
+            self.data.push(value.strip_prefix(' ').unwrap_or(value).to_string());
+        }
+    }
+
+    fn dispatch(&mut self, events: &mut Vec<String>) {
+        if self.data.is_empty() {
+            return;
+        }
+        let data = std::mem::take(&mut self.data).join("\n");
+        if data.trim() != "[DONE]" {
+            events.push(data);
+        }
+    }
+}
+
+struct DecodeState {
+    body: BoxStream<'static, reqwest::Result<bytes::Bytes>>,
+    decoder: SseDecoder,
+    pending: VecDeque<String>,
+    done: bool,
+}
+
+/// Decodes a streaming response body into a stream of `T`, one per SSE event.
+pub(crate) fn decode<T>(response: reqwest::Response) -> EventStream<T>
+where
+    T: DeserializeOwned + Send + 'static,
+{
+    let state = DecodeState {
+        body: response.bytes_stream().boxed(),
+        decoder: SseDecoder::default(),
+        pending: VecDeque::new(),
+        done: false,
+    };
+    Box::pin(stream::unfold(state, |mut state| async move {
+        loop {
+            if let Some(data) = state.pending.pop_front() {
+                let item = serde_json::from_str(&data)
+                    .map_err(|e| ClientError::Stream(format!("JSON parse error: {e}: {data}")));
+                return Some((item, state));
+            }
+            if state.done {
+                return None;
+            }
+            match state.body.next().await {
+                Some(Ok(bytes)) => state.pending.extend(state.decoder.feed(&bytes)),
+                Some(Err(e)) => {
+                    state.done = true;
+                    return Some((Err(ClientError::Stream(e.to_string())), state));
+                },
+                None => {
+                    state.done = true;
+                    state.pending.extend(state.decoder.finish());
+                },
+            }
+        }
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SseDecoder;
+
+    #[test]
+    fn test_events_split_across_chunks() {
+        let mut decoder = SseDecoder::default();
+        assert!(decoder.feed(b"event: message_start\ndata: {\"a\"").is_empty());
+        assert_eq!(
+            decoder.feed(b":1}\n\ndata: {\"b\":2}\r\n\r\n"),
+            vec!["{\"a\":1}".to_string(), "{\"b\":2}".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_done_and_comments_are_skipped() {
+        let mut decoder = SseDecoder::default();
+        let events = decoder.feed(b": keep-alive\n\ndata: {\"x\":1}\n\ndata: [DONE]\n\n");
+        assert_eq!(events, vec!["{\"x\":1}".to_string()]);
+    }
+
+    #[test]
+    fn test_multiline_data_and_unterminated_tail() {
+        let mut decoder = SseDecoder::default();
+        assert!(decoder.feed(b"data: {\"x\":\ndata: 1}").is_empty());
+        assert_eq!(decoder.finish(), vec!["{\"x\":\n1}".to_string()]);
+    }
+}
@@ -0,0 +1,31 @@
+-- Multi-tenant client API keys.
+-- Each key has its own label, expiry, model allowlist, budget and usage counters.
+-- Only the SHA-256 hash of the secret is stored; the plaintext is shown once on creation.
+
+CREATE TABLE IF NOT EXISTS api_keys (
+    id UUID PRIMARY KEY,
+    label TEXT NOT NULL,
+    key_prefix TEXT NOT NULL,
+    key_hash TEXT NOT NULL UNIQUE,
+    enabled BOOLEAN NOT NULL DEFAULT TRUE,
+    expires_at BIGINT,
+    allowed_models JSONB NOT NULL DEFAULT '[]'::jsonb,
+    budget JSONB NOT NULL DEFAULT '{}'::jsonb,
+    used_requests BIGINT NOT NULL DEFAULT 0,
+    used_tokens BIGINT NOT NULL DEFAULT 0,
+    period_start BIGINT NOT NULL DEFAULT 0,
+    last_used_at BIGINT,
+    created_at BIGINT NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+DROP TRIGGER IF EXISTS update_api_keys_updated_at ON api_keys;
+CREATE TRIGGER update_api_keys_updated_at
+    BEFORE UPDATE ON api_keys
+    FOR EACH ROW
+    EXECUTE FUNCTION update_updated_at_column();
+
+-- Attribute request analytics to the client key (no FK: keys may be deleted, history is kept)
+ALTER TABLE requests ADD COLUMN IF NOT EXISTS api_key_id UUID;
+CREATE INDEX IF NOT EXISTS idx_requests_api_key_id ON requests(api_key_id, created_at DESC)
+    WHERE api_key_id IS NOT NULL;
@@ -0,0 +1,7 @@
+-- Named account groups ("team-a", "batch", ...).
+-- Routing rules and client keys can restrict account selection to one group.
+
+ALTER TABLE accounts ADD COLUMN IF NOT EXISTS account_groups JSONB NOT NULL DEFAULT '[]'::jsonb;
+
+-- Group a client key's requests are served from (NULL = any account)
+ALTER TABLE api_keys ADD COLUMN IF NOT EXISTS account_group TEXT;
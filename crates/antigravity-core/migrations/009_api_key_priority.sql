@@ -0,0 +1,3 @@
+-- Admission queue class of a client key's requests ('interactive' or 'batch')
+
+ALTER TABLE api_keys ADD COLUMN IF NOT EXISTS priority TEXT NOT NULL DEFAULT 'interactive';
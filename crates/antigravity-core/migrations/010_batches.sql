@@ -0,0 +1,48 @@
+-- Message batches (/v1/messages/batches and the OpenAI /v1/batches facade).
+-- A batch owns its requests; each request row receives its response body once executed.
+-- Request counts are derived from batch_requests rather than stored on the batch.
+
+CREATE TABLE IF NOT EXISTS batches (
+    id TEXT PRIMARY KEY,
+    api TEXT NOT NULL,
+    endpoint TEXT NOT NULL,
+    api_key_id UUID,
+    status TEXT NOT NULL,
+    metadata JSONB NOT NULL DEFAULT '{}'::jsonb,
+    input_file_id TEXT,
+    output_file_id TEXT,
+    error_file_id TEXT,
+    created_at BIGINT NOT NULL,
+    expires_at BIGINT NOT NULL,
+    cancel_initiated_at BIGINT,
+    ended_at BIGINT
+);
+
+CREATE INDEX IF NOT EXISTS idx_batches_api_created ON batches(api, created_at DESC, id DESC);
+CREATE INDEX IF NOT EXISTS idx_batches_unfinished ON batches(status) WHERE status <> 'ended';
+
+CREATE TABLE IF NOT EXISTS batch_requests (
+    batch_id TEXT NOT NULL REFERENCES batches(id) ON DELETE CASCADE,
+    position INTEGER NOT NULL,
+    custom_id TEXT NOT NULL,
+    params JSONB NOT NULL,
+    status TEXT NOT NULL DEFAULT 'pending',
+    status_code INTEGER,
+    result JSONB,
+    completed_at BIGINT,
+    PRIMARY KEY (batch_id, position)
+);
+
+CREATE INDEX IF NOT EXISTS idx_batch_requests_pending ON batch_requests(batch_id, position)
+    WHERE status = 'pending';
+
+-- Files of the OpenAI files API: batch input uploads and generated result files
+CREATE TABLE IF NOT EXISTS batch_files (
+    id TEXT PRIMARY KEY,
+    filename TEXT NOT NULL,
+    purpose TEXT NOT NULL,
+    api_key_id UUID,
+    bytes BIGINT NOT NULL,
+    content BYTEA NOT NULL,
+    created_at BIGINT NOT NULL
+);
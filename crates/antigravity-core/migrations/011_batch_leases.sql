@@ -0,0 +1,5 @@
+-- Lease on a batch held by the instance running it, so that instances sharing the
+-- database never run the same batch at once. An expired lease may be taken over.
+
+ALTER TABLE batches ADD COLUMN IF NOT EXISTS runner_id TEXT;
+ALTER TABLE batches ADD COLUMN IF NOT EXISTS lease_expires_at BIGINT;
@@ -0,0 +1,35 @@
+//! Named account groups.
+
+use std::collections::BTreeSet;
+
+const MAX_GROUP_NAME_LEN: usize = 64;
+
+/// Trim, validate and de-duplicate group names. The result is sorted.
+///
+/// Names may contain ASCII letters, digits, `-`, `_` and `.`; empty entries are dropped.
+pub fn normalize_groups(groups: &[String]) -> Result<Vec<String>, String> {
+    let mut normalized = BTreeSet::new();
+    for group in groups.iter().map(|g| g.trim()).filter(|g| !g.is_empty()) {
+        if group.len() > MAX_GROUP_NAME_LEN
+            || !group.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+        {
+            return Err(format!("Invalid account group name: '{}'", group));
+        }
+        normalized.insert(group.to_string());
+    }
+    Ok(normalized.into_iter().collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalizes_group_names() {
+        let groups =
+            vec![" batch ".to_string(), "team-a".to_string(), String::new(), "batch".into()];
+        assert_eq!(normalize_groups(&groups).unwrap(), vec!["batch", "team-a"]);
+        assert!(normalize_groups(&["ultra only".to_string()]).is_err());
+        assert!(normalize_groups(&["x".repeat(65)]).is_err());
+    }
+}
@@ -4,6 +4,7 @@ mod async_wrappers;
 mod crud;
 mod current;
 mod fetch;
+mod groups;
 mod index;
 mod paths;
 mod quota;
@@ -17,6 +18,7 @@ pub use async_wrappers::{
 pub use crud::{add_account, delete_account, delete_accounts, reorder_accounts, upsert_account};
 pub use current::{get_current_account, get_current_account_id, set_current_account_id};
 pub use fetch::{fetch_quota_with_retry, QuotaFetchResult};
+pub use groups::normalize_groups;
 pub use index::{load_account_index, save_account_index};
 pub use paths::{get_accounts_dir, get_data_dir};
 pub use quota::update_account_quota;
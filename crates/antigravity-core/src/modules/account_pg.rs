@@ -6,15 +6,16 @@ use crate::modules::account_pg_crud::{
     upsert_account_impl,
 };
 use crate::modules::account_pg_events::{
-    get_account_health_impl, get_current_account_id_impl, get_events_impl, log_event_impl,
-    log_request_impl, set_current_account_id_impl, update_quota_impl,
+    get_account_health_impl, get_current_account_id_impl, get_events_impl, latest_event_id_impl,
+    list_events_after_impl, log_event_impl, log_request_impl, set_current_account_id_impl,
+    update_quota_impl,
 };
 use crate::modules::account_pg_query::{
     get_account_by_email_impl, get_account_impl, list_accounts_impl,
 };
 use crate::modules::account_pg_targeted::{
-    set_account_disabled_impl, update_name_impl, update_project_id_impl, update_proxy_url_impl,
-    update_token_credentials_impl,
+    set_account_disabled_impl, update_groups_impl, update_name_impl, update_project_id_impl,
+    update_proxy_url_impl, update_token_credentials_impl,
 };
 use crate::modules::repository::{
     AccountEvent, AccountHealth, AccountRepository, RepoResult, RepositoryError, RequestLog,
@@ -138,6 +139,18 @@ impl AccountRepository for PostgresAccountRepository {
         get_events_impl(&self.pool, account_id, limit).await
     }
 
+    async fn latest_event_id(&self) -> RepoResult<i64> {
+        latest_event_id_impl(&self.pool).await
+    }
+
+    async fn list_events_after(
+        &self,
+        after_id: i64,
+        limit: i64,
+    ) -> RepoResult<Vec<(i64, AccountEvent)>> {
+        list_events_after_impl(&self.pool, after_id, limit).await
+    }
+
     async fn update_token_credentials(
         &self,
         account_id: &str,
@@ -161,6 +174,10 @@ impl AccountRepository for PostgresAccountRepository {
         update_proxy_url_impl(&self.pool, account_id, proxy_url).await
     }
 
+    async fn update_groups(&self, account_id: &str, groups: &[String]) -> RepoResult<()> {
+        update_groups_impl(&self.pool, account_id, groups).await
+    }
+
     async fn set_account_disabled(
         &self,
         account_id: &str,
@@ -111,9 +111,15 @@ pub(crate) async fn log_event_impl(pool: &PgPool, event: AccountEvent) -> RepoRe
 pub(crate) async fn log_request_impl(pool: &PgPool, request: RequestLog) -> RepoResult<()> {
     let uuid = Uuid::parse_str(&request.account_id)
         .map_err(|err| RepositoryError::InvalidInput(err.to_string()))?;
+    let api_key_id = request
+        .api_key_id
+        .as_deref()
+        .map(Uuid::parse_str)
+        .transpose()
+        .map_err(|err| RepositoryError::InvalidInput(err.to_string()))?;
     sqlx::query(
-        r#"INSERT INTO requests (account_id, model, tokens_in, tokens_out, cached_tokens, latency_ms, status_code, error_type)
-           VALUES ($1, $2, $3, $4, $5, $6, $7, $8)"#,
+        r#"INSERT INTO requests (account_id, model, tokens_in, tokens_out, cached_tokens, latency_ms, status_code, error_type, api_key_id)
+           VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)"#,
     )
     .bind(uuid)
     .bind(&request.model)
@@ -123,6 +129,7 @@ pub(crate) async fn log_request_impl(pool: &PgPool, request: RequestLog) -> Repo
     .bind(request.latency_ms)
     .bind(request.status_code)
     .bind(&request.error_type)
+    .bind(api_key_id)
     .execute(pool)
     .await
     .map_err(map_sqlx_err)?;
@@ -182,6 +189,44 @@ pub(crate) async fn get_events_impl(
         .collect())
 }
 
+/// ID of the newest account event.
+pub(crate) async fn latest_event_id_impl(pool: &PgPool) -> RepoResult<i64> {
+    sqlx::query_scalar("SELECT COALESCE(MAX(id), 0) FROM account_events")
+        .fetch_one(pool)
+        .await
+        .map_err(map_sqlx_err)
+}
+
+/// Events across all accounts after `after_id`, oldest first.
+pub(crate) async fn list_events_after_impl(
+    pool: &PgPool,
+    after_id: i64,
+    limit: i64,
+) -> RepoResult<Vec<(i64, AccountEvent)>> {
+    let rows = sqlx::query(
+        "SELECT id, account_id, event_type, metadata, created_at FROM account_events WHERE id > $1 ORDER BY id LIMIT $2",
+    )
+    .bind(after_id)
+    .bind(limit)
+    .fetch_all(pool)
+    .await
+    .map_err(map_sqlx_err)?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| {
+            let account_id: Uuid = row.get("account_id");
+            let event = AccountEvent {
+                account_id: account_id.to_string(),
+                event_type: parse_event_type(row.get("event_type")),
+                metadata: row.get("metadata"),
+                created_at: row.get("created_at"),
+            };
+            (row.get("id"), event)
+        })
+        .collect())
+}
+
 /// Log an event within a transaction.
 pub(crate) async fn log_event_internal_impl(
     transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
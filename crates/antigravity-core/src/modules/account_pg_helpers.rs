@@ -12,6 +12,8 @@ pub(crate) fn row_to_account(row: &sqlx::postgres::PgRow) -> RepoResult<Account>
     let protected_json: serde_json::Value = row.get("protected_models");
     let protected: HashSet<String> = serde_json::from_value(protected_json)
         .map_err(|err| RepositoryError::Serialization(err.to_string()))?;
+    let groups: Vec<String> = serde_json::from_value(row.get("account_groups"))
+        .map_err(|err| RepositoryError::Serialization(err.to_string()))?;
 
     let created_at: chrono::DateTime<chrono::Utc> = row.get("created_at");
     let last_used: chrono::DateTime<chrono::Utc> = row.get("last_used_at");
@@ -65,6 +67,7 @@ pub(crate) fn row_to_account(row: &sqlx::postgres::PgRow) -> RepoResult<Account>
         proxy_disabled_at: proxy_disabled_at.map(|dt| dt.timestamp()),
         protected_models: protected,
         proxy_url: row.get("proxy_url"),
+        groups,
         created_at: created_at.timestamp(),
         last_used: last_used.timestamp(),
     })
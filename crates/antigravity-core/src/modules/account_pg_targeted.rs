@@ -153,6 +153,39 @@ pub(crate) async fn update_proxy_url_impl(
     tx.commit().await.map_err(map_sqlx_err)
 }
 
+pub(crate) async fn update_groups_impl(
+    pool: &PgPool,
+    account_id: &str,
+    groups: &[String],
+) -> RepoResult<()> {
+    let uuid = Uuid::parse_str(account_id).map_err(|e| RepositoryError::NotFound(e.to_string()))?;
+    let groups_json =
+        serde_json::to_value(groups).map_err(|e| RepositoryError::Serialization(e.to_string()))?;
+
+    let mut tx = pool.begin().await.map_err(map_sqlx_err)?;
+
+    let result = sqlx::query("UPDATE accounts SET account_groups = $2 WHERE id = $1")
+        .bind(uuid)
+        .bind(groups_json)
+        .execute(&mut *tx)
+        .await
+        .map_err(map_sqlx_err)?;
+
+    if result.rows_affected() == 0 {
+        return Err(RepositoryError::NotFound(account_id.to_string()));
+    }
+
+    log_event_internal_impl(
+        &mut tx,
+        account_id.to_string(),
+        AccountEventType::Updated,
+        serde_json::json!({"groups": groups}),
+    )
+    .await?;
+
+    tx.commit().await.map_err(map_sqlx_err)
+}
+
 pub(crate) async fn set_account_disabled_impl(
     pool: &PgPool,
     account_id: &str,
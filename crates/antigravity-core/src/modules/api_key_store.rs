@@ -0,0 +1,299 @@
+//! Persistence for client API keys.
+//!
+//! PostgreSQL is used when available; otherwise keys live in `api_keys.json`
+//! next to `gui_config.json` in the data directory.
+
+use antigravity_types::models::{ApiKey, ApiKeyBudget, ApiKeyUsage, PriorityClass};
+use sqlx::postgres::PgPool;
+use sqlx::Row;
+use std::fs;
+use std::path::PathBuf;
+use uuid::Uuid;
+
+const API_KEYS_FILE: &str = "api_keys.json";
+
+/// Storage backend for the client API key registry.
+#[derive(Clone)]
+pub enum ApiKeyStore {
+    /// `api_keys` table in PostgreSQL.
+    Postgres(PgPool),
+    /// JSON file (used when DATABASE_URL is not set).
+    Json(PathBuf),
+}
+
+impl ApiKeyStore {
+    /// PostgreSQL store if a pool is available, JSON file in the data directory otherwise.
+    pub fn new(pool: Option<PgPool>) -> Result<Self, String> {
+        match pool {
+            Some(pool) => Ok(Self::Postgres(pool)),
+            None => Ok(Self::Json(crate::modules::account::get_data_dir()?.join(API_KEYS_FILE))),
+        }
+    }
+
+    /// Load all keys.
+    pub async fn list(&self) -> Result<Vec<ApiKey>, String> {
+        match self {
+            Self::Postgres(pool) => list_pg(pool).await,
+            Self::Json(path) => {
+                let path = path.clone();
+                tokio::task::spawn_blocking(move || read_json(&path))
+                    .await
+                    .map_err(|e| format!("spawn_blocking panicked: {e}"))?
+            },
+        }
+    }
+
+    /// Insert a key or update its settings. Usage counters of existing keys are preserved.
+    pub async fn upsert(&self, key: &ApiKey) -> Result<(), String> {
+        match self {
+            Self::Postgres(pool) => upsert_pg(pool, key).await,
+            Self::Json(path) => {
+                let (path, key) = (path.clone(), key.clone());
+                modify_json(path, move |keys| {
+                    match keys.iter_mut().find(|k| k.id == key.id) {
+                        Some(existing) => {
+                            *existing = ApiKey {
+                                usage: existing.usage,
+                                last_used_at: existing.last_used_at,
+                                ..key
+                            };
+                        },
+                        None => keys.push(key),
+                    }
+                    true
+                })
+                .await?;
+                Ok(())
+            },
+        }
+    }
+
+    /// Delete a key. Returns false if no key had this ID.
+    pub async fn delete(&self, id: &str) -> Result<bool, String> {
+        match self {
+            Self::Postgres(pool) => {
+                let uuid = Uuid::parse_str(id).map_err(|e| e.to_string())?;
+                let result = sqlx::query("DELETE FROM api_keys WHERE id = $1")
+                    .bind(uuid)
+                    .execute(pool)
+                    .await
+                    .map_err(|e| e.to_string())?;
+                Ok(result.rows_affected() > 0)
+            },
+            Self::Json(path) => {
+                let id = id.to_string();
+                modify_json(path.clone(), move |keys| {
+                    let before = keys.len();
+                    keys.retain(|k| k.id != id);
+                    keys.len() != before
+                })
+                .await
+            },
+        }
+    }
+
+    /// Add usage recorded by this instance to a key's stored counters (see
+    /// [`ApiKeyUsage::add`]), so that instances sharing the store don't overwrite each other.
+    pub async fn add_usage(
+        &self,
+        id: &str,
+        delta: ApiKeyUsage,
+        last_used_at: Option<i64>,
+    ) -> Result<(), String> {
+        match self {
+            Self::Postgres(pool) => {
+                let uuid = Uuid::parse_str(id).map_err(|e| e.to_string())?;
+                sqlx::query(
+                    r#"UPDATE api_keys
+                       SET used_requests = CASE
+                               WHEN period_start < $4 THEN $2
+                               WHEN period_start = $4 THEN used_requests + $2
+                               ELSE used_requests END,
+                           used_tokens = CASE
+                               WHEN period_start < $4 THEN $3
+                               WHEN period_start = $4 THEN used_tokens + $3
+                               ELSE used_tokens END,
+                           period_start = GREATEST(period_start, $4),
+                           last_used_at = GREATEST(last_used_at, $5)
+                       WHERE id = $1"#,
+                )
+                .bind(uuid)
+                .bind(i64::try_from(delta.requests).unwrap_or(i64::MAX))
+                .bind(i64::try_from(delta.tokens).unwrap_or(i64::MAX))
+                .bind(delta.period_start)
+                .bind(last_used_at)
+                .execute(pool)
+                .await
+                .map_err(|e| e.to_string())?;
+                Ok(())
+            },
+            Self::Json(path) => {
+                let id = id.to_string();
+                modify_json(path.clone(), move |keys| match keys.iter_mut().find(|k| k.id == id) {
+                    Some(key) => {
+                        key.usage.add(&delta);
+                        key.last_used_at = key.last_used_at.max(last_used_at);
+                        true
+                    },
+                    None => false,
+                })
+                .await?;
+                Ok(())
+            },
+        }
+    }
+
+    /// Overwrite the usage counters of a key without touching its settings.
+    pub async fn save_usage(
+        &self,
+        id: &str,
+        usage: ApiKeyUsage,
+        last_used_at: Option<i64>,
+    ) -> Result<(), String> {
+        match self {
+            Self::Postgres(pool) => {
+                let uuid = Uuid::parse_str(id).map_err(|e| e.to_string())?;
+                sqlx::query(
+                    r#"UPDATE api_keys
+                       SET used_requests = $2, used_tokens = $3, period_start = $4, last_used_at = $5
+                       WHERE id = $1"#,
+                )
+                .bind(uuid)
+                .bind(i64::try_from(usage.requests).unwrap_or(i64::MAX))
+                .bind(i64::try_from(usage.tokens).unwrap_or(i64::MAX))
+                .bind(usage.period_start)
+                .bind(last_used_at)
+                .execute(pool)
+                .await
+                .map_err(|e| e.to_string())?;
+                Ok(())
+            },
+            Self::Json(path) => {
+                let id = id.to_string();
+                modify_json(path.clone(), move |keys| match keys.iter_mut().find(|k| k.id == id) {
+                    Some(key) => {
+                        key.usage = usage;
+                        key.last_used_at = last_used_at;
+                        true
+                    },
+                    None => false,
+                })
+                .await?;
+                Ok(())
+            },
+        }
+    }
+}
+
+async fn list_pg(pool: &PgPool) -> Result<Vec<ApiKey>, String> {
+    let rows = sqlx::query(
+        r#"SELECT id, label, key_prefix, key_hash, enabled, expires_at, allowed_models, budget,
+                  account_group, priority, used_requests, used_tokens, period_start, last_used_at, created_at
+           FROM api_keys ORDER BY created_at"#,
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    rows.iter()
+        .map(|row| {
+            let id: Uuid = row.get("id");
+            let allowed_models: Vec<String> =
+                serde_json::from_value(row.get("allowed_models")).map_err(|e| e.to_string())?;
+            let budget: ApiKeyBudget =
+                serde_json::from_value(row.get("budget")).map_err(|e| e.to_string())?;
+            Ok(ApiKey {
+                id: id.to_string(),
+                label: row.get("label"),
+                key_prefix: row.get("key_prefix"),
+                key_hash: row.get("key_hash"),
+                enabled: row.get("enabled"),
+                expires_at: row.get("expires_at"),
+                allowed_models,
+                budget,
+                account_group: row.get("account_group"),
+                priority: PriorityClass::parse(row.get("priority")).unwrap_or_default(),
+                usage: ApiKeyUsage {
+                    requests: u64::try_from(row.get::<i64, _>("used_requests")).unwrap_or(0),
+                    tokens: u64::try_from(row.get::<i64, _>("used_tokens")).unwrap_or(0),
+                    period_start: row.get("period_start"),
+                },
+                last_used_at: row.get("last_used_at"),
+                created_at: row.get("created_at"),
+            })
+        })
+        .collect()
+}
+
+async fn upsert_pg(pool: &PgPool, key: &ApiKey) -> Result<(), String> {
+    let uuid = Uuid::parse_str(&key.id).map_err(|e| e.to_string())?;
+    let allowed_models = serde_json::to_value(&key.allowed_models).map_err(|e| e.to_string())?;
+    let budget = serde_json::to_value(key.budget).map_err(|e| e.to_string())?;
+    sqlx::query(
+        r#"INSERT INTO api_keys (id, label, key_prefix, key_hash, enabled, expires_at,
+                                 allowed_models, budget, period_start, created_at, account_group,
+                                 priority)
+           VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+           ON CONFLICT (id) DO UPDATE SET
+               label = EXCLUDED.label,
+               enabled = EXCLUDED.enabled,
+               expires_at = EXCLUDED.expires_at,
+               allowed_models = EXCLUDED.allowed_models,
+               budget = EXCLUDED.budget,
+               account_group = EXCLUDED.account_group,
+               priority = EXCLUDED.priority"#,
+    )
+    .bind(uuid)
+    .bind(&key.label)
+    .bind(&key.key_prefix)
+    .bind(&key.key_hash)
+    .bind(key.enabled)
+    .bind(key.expires_at)
+    .bind(allowed_models)
+    .bind(budget)
+    .bind(key.usage.period_start)
+    .bind(key.created_at)
+    .bind(&key.account_group)
+    .bind(key.priority.as_str())
+    .execute(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+fn read_json(path: &PathBuf) -> Result<Vec<ApiKey>, String> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let content =
+        fs::read_to_string(path).map_err(|e| format!("Failed to read API keys file: {}", e))?;
+    serde_json::from_str(&content).map_err(|e| format!("Failed to parse API keys file: {}", e))
+}
+
+fn write_json(path: &PathBuf, keys: &[ApiKey]) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create data directory: {}", e))?;
+    }
+    let temp_path = path.with_extension("json.tmp");
+    let content = serde_json::to_string_pretty(keys)
+        .map_err(|e| format!("Failed to serialize API keys: {}", e))?;
+    fs::write(&temp_path, content).map_err(|e| format!("Failed to write API keys: {}", e))?;
+    fs::rename(&temp_path, path).map_err(|e| format!("Failed to save API keys: {}", e))
+}
+
+/// Read-modify-write the JSON file. The closure returns whether anything changed.
+async fn modify_json<F>(path: PathBuf, f: F) -> Result<bool, String>
+where
+    F: FnOnce(&mut Vec<ApiKey>) -> bool + Send,
+{
+    static JSON_LOCK: tokio::sync::Mutex<()> = tokio::sync::Mutex::const_new(());
+    let _guard = JSON_LOCK.lock().await;
+
+    let mut keys = read_json(&path)?;
+    let changed = f(&mut keys);
+    if changed {
+        write_json(&path, &keys)?;
+    }
+    Ok(changed)
+}
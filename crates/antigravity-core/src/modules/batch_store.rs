@@ -0,0 +1,979 @@
+//! Persistence for message batches and the files of the OpenAI files API.
+//!
+//! PostgreSQL is used when available; otherwise each batch is one JSON file (batch and
+//! requests together) under `batches/` in the data directory, and each file is stored
+//! as a metadata JSON file plus its raw content.
+
+use antigravity_types::models::{
+    Batch, BatchApi, BatchFile, BatchItem, BatchItemStatus, BatchPage, BatchRequestCounts,
+    BatchStatus,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sqlx::postgres::{PgPool, PgRow};
+use sqlx::Row;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use uuid::Uuid;
+
+const BATCHES_DIR: &str = "batches";
+const FILES_DIR: &str = "files";
+
+/// Storage backend for message batches.
+#[derive(Clone)]
+pub enum BatchStore {
+    /// `batches`, `batch_requests` and `batch_files` tables in PostgreSQL.
+    Postgres(PgPool),
+    /// JSON files in this directory (used when DATABASE_URL is not set).
+    Json(PathBuf),
+}
+
+/// Batch and its requests as stored by the JSON backend.
+#[derive(Serialize, Deserialize)]
+struct StoredBatch {
+    batch: Batch,
+    items: Vec<BatchItem>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    lease: Option<Lease>,
+}
+
+/// Claim of the instance currently running a batch.
+#[derive(Serialize, Deserialize)]
+struct Lease {
+    runner_id: String,
+    expires_at: i64,
+}
+
+impl BatchStore {
+    /// PostgreSQL store if a pool is available, `batches/` in the data directory otherwise.
+    pub fn new(pool: Option<PgPool>) -> Result<Self, String> {
+        match pool {
+            Some(pool) => Ok(Self::Postgres(pool)),
+            None => Ok(Self::Json(crate::modules::account::get_data_dir()?.join(BATCHES_DIR))),
+        }
+    }
+
+    /// Insert a new batch with all of its requests.
+    pub async fn create(&self, batch: &Batch, items: &[BatchItem]) -> Result<(), String> {
+        match self {
+            Self::Postgres(pool) => create_pg(pool, batch, items).await,
+            Self::Json(dir) => {
+                let stored =
+                    StoredBatch { batch: batch.clone(), items: items.to_vec(), lease: None };
+                let path = batch_path(dir, &batch.id)?;
+                with_json_lock(move || write_json(&path, &stored)).await
+            },
+        }
+    }
+
+    /// Batch with its current request counts.
+    pub async fn get(&self, id: &str) -> Result<Option<Batch>, String> {
+        match self {
+            Self::Postgres(pool) => {
+                let row = sqlx::query("SELECT * FROM batches WHERE id = $1")
+                    .bind(id)
+                    .fetch_optional(pool)
+                    .await
+                    .map_err(|e| e.to_string())?;
+                let Some(row) = row else {
+                    return Ok(None);
+                };
+                let mut batch = batch_from_row(&row)?;
+                batch.request_counts = counts_pg(pool, std::slice::from_ref(&batch.id))
+                    .await?
+                    .remove(id)
+                    .unwrap_or_default();
+                Ok(Some(batch))
+            },
+            Self::Json(dir) => {
+                let path = batch_path(dir, id)?;
+                with_json_lock(move || {
+                    Ok(read_batch(&path)?.map(|s| with_counts(s.batch, &s.items)))
+                })
+                .await
+            },
+        }
+    }
+
+    /// Batches of `api` visible to `key_id`, newest first. Returns up to `page.limit + 1`
+    /// batches so the caller can tell whether more exist.
+    pub async fn list(
+        &self,
+        api: BatchApi,
+        key_id: Option<&str>,
+        page: &BatchPage,
+    ) -> Result<Vec<Batch>, String> {
+        match self {
+            Self::Postgres(pool) => list_pg(pool, api, key_id, page).await,
+            Self::Json(dir) => {
+                let (dir, key_id, page) = (dir.clone(), key_id.map(str::to_string), page.clone());
+                with_json_lock(move || {
+                    let mut batches: Vec<Batch> = read_all_batches(&dir)?
+                        .into_iter()
+                        .filter(|s| s.batch.api == api && s.batch.is_visible_to(key_id.as_deref()))
+                        .map(|s| with_counts(s.batch, &s.items))
+                        .collect();
+                    batches.sort_by(|a, b| (b.created_at, &b.id).cmp(&(a.created_at, &a.id)));
+                    Ok(paginate(batches, &page))
+                })
+                .await
+            },
+        }
+    }
+
+    /// Current status of a batch, without its request counts.
+    pub async fn status(&self, id: &str) -> Result<Option<BatchStatus>, String> {
+        match self {
+            Self::Postgres(pool) => {
+                let status: Option<String> =
+                    sqlx::query_scalar("SELECT status FROM batches WHERE id = $1")
+                        .bind(id)
+                        .fetch_optional(pool)
+                        .await
+                        .map_err(|e| e.to_string())?;
+                status
+                    .map(|status| {
+                        BatchStatus::parse(&status)
+                            .ok_or_else(|| format!("Unknown batch status '{}'", status))
+                    })
+                    .transpose()
+            },
+            Self::Json(dir) => {
+                let path = batch_path(dir, id)?;
+                with_json_lock(move || Ok(read_batch(&path)?.map(|s| s.batch.status))).await
+            },
+        }
+    }
+
+    /// IDs of batches that have not ended, for resuming after a restart.
+    pub async fn unfinished(&self) -> Result<Vec<String>, String> {
+        match self {
+            Self::Postgres(pool) => sqlx::query_scalar(
+                "SELECT id FROM batches WHERE status <> 'ended' ORDER BY created_at",
+            )
+            .fetch_all(pool)
+            .await
+            .map_err(|e| e.to_string()),
+            Self::Json(dir) => {
+                let dir = dir.clone();
+                with_json_lock(move || {
+                    let mut batches: Vec<Batch> = read_all_batches(&dir)?
+                        .into_iter()
+                        .map(|s| s.batch)
+                        .filter(|b| b.status != BatchStatus::Ended)
+                        .collect();
+                    batches.sort_by_key(|b| b.created_at);
+                    Ok(batches.into_iter().map(|b| b.id).collect())
+                })
+                .await
+            },
+        }
+    }
+
+    /// Save the status, timestamps and result file IDs of a batch.
+    pub async fn update(&self, batch: &Batch) -> Result<(), String> {
+        match self {
+            Self::Postgres(pool) => {
+                sqlx::query(
+                    r#"UPDATE batches
+                       SET status = $2, cancel_initiated_at = $3, ended_at = $4,
+                           output_file_id = $5, error_file_id = $6
+                       WHERE id = $1"#,
+                )
+                .bind(&batch.id)
+                .bind(batch.status.as_str())
+                .bind(batch.cancel_initiated_at)
+                .bind(batch.ended_at)
+                .bind(&batch.output_file_id)
+                .bind(&batch.error_file_id)
+                .execute(pool)
+                .await
+                .map_err(|e| e.to_string())?;
+                Ok(())
+            },
+            Self::Json(dir) => {
+                let batch = batch.clone();
+                modify_batch(dir, &batch.id.clone(), move |stored| {
+                    stored.batch =
+                        Batch { metadata: std::mem::take(&mut stored.batch.metadata), ..batch };
+                })
+                .await
+            },
+        }
+    }
+
+    /// Move an in-progress batch to `canceling`. Returns false if it was not in progress.
+    pub async fn begin_cancel(&self, id: &str, now: i64) -> Result<bool, String> {
+        match self {
+            Self::Postgres(pool) => {
+                let result = sqlx::query(
+                    r#"UPDATE batches SET status = 'canceling', cancel_initiated_at = $2
+                       WHERE id = $1 AND status = 'in_progress'"#,
+                )
+                .bind(id)
+                .bind(now)
+                .execute(pool)
+                .await
+                .map_err(|e| e.to_string())?;
+                Ok(result.rows_affected() > 0)
+            },
+            Self::Json(dir) => {
+                let path = batch_path(dir, id)?;
+                with_json_lock(move || {
+                    let Some(mut stored) = read_batch(&path)? else {
+                        return Ok(false);
+                    };
+                    if stored.batch.status != BatchStatus::InProgress {
+                        return Ok(false);
+                    }
+                    stored.batch.status = BatchStatus::Canceling;
+                    stored.batch.cancel_initiated_at = Some(now);
+                    write_json(&path, &stored)?;
+                    Ok(true)
+                })
+                .await
+            },
+        }
+    }
+
+    /// Take or renew the lease on an unfinished batch for `runner_id` until `now + ttl_secs`.
+    /// Returns false if another runner holds an unexpired lease or the batch has ended.
+    pub async fn claim(
+        &self,
+        id: &str,
+        runner_id: &str,
+        now: i64,
+        ttl_secs: i64,
+    ) -> Result<bool, String> {
+        match self {
+            Self::Postgres(pool) => {
+                let result = sqlx::query(
+                    r#"UPDATE batches SET runner_id = $2, lease_expires_at = $3
+                       WHERE id = $1 AND status <> 'ended'
+                         AND (runner_id IS NULL OR runner_id = $2 OR lease_expires_at <= $4)"#,
+                )
+                .bind(id)
+                .bind(runner_id)
+                .bind(now + ttl_secs)
+                .bind(now)
+                .execute(pool)
+                .await
+                .map_err(|e| e.to_string())?;
+                Ok(result.rows_affected() > 0)
+            },
+            Self::Json(dir) => {
+                let path = batch_path(dir, id)?;
+                let runner_id = runner_id.to_string();
+                with_json_lock(move || {
+                    let Some(mut stored) = read_batch(&path)? else {
+                        return Ok(false);
+                    };
+                    let taken = stored.lease.as_ref().is_some_and(|lease| {
+                        lease.runner_id != runner_id && lease.expires_at > now
+                    });
+                    if stored.batch.status == BatchStatus::Ended || taken {
+                        return Ok(false);
+                    }
+                    stored.lease = Some(Lease { runner_id, expires_at: now + ttl_secs });
+                    write_json(&path, &stored)?;
+                    Ok(true)
+                })
+                .await
+            },
+        }
+    }
+
+    /// Drop the lease on a batch if `runner_id` holds it.
+    pub async fn release(&self, id: &str, runner_id: &str) -> Result<(), String> {
+        match self {
+            Self::Postgres(pool) => {
+                sqlx::query(
+                    r#"UPDATE batches SET runner_id = NULL, lease_expires_at = NULL
+                       WHERE id = $1 AND runner_id = $2"#,
+                )
+                .bind(id)
+                .bind(runner_id)
+                .execute(pool)
+                .await
+                .map_err(|e| e.to_string())?;
+                Ok(())
+            },
+            Self::Json(dir) => {
+                let path = batch_path(dir, id)?;
+                let runner_id = runner_id.to_string();
+                with_json_lock(move || {
+                    let Some(mut stored) = read_batch(&path)? else {
+                        return Ok(());
+                    };
+                    if stored.lease.as_ref().is_some_and(|lease| lease.runner_id == runner_id) {
+                        stored.lease = None;
+                        write_json(&path, &stored)?;
+                    }
+                    Ok(())
+                })
+                .await
+            },
+        }
+    }
+
+    /// Pending requests after `after_position`, in submission order.
+    pub async fn pending_items(
+        &self,
+        id: &str,
+        after_position: Option<u32>,
+        limit: usize,
+    ) -> Result<Vec<BatchItem>, String> {
+        match self {
+            Self::Postgres(pool) => {
+                let rows = sqlx::query(
+                    r#"SELECT * FROM batch_requests
+                       WHERE batch_id = $1 AND status = 'pending' AND position > $2
+                       ORDER BY position LIMIT $3"#,
+                )
+                .bind(id)
+                .bind(after_position.map_or(-1, i64::from))
+                .bind(i64::try_from(limit).unwrap_or(i64::MAX))
+                .fetch_all(pool)
+                .await
+                .map_err(|e| e.to_string())?;
+                rows.iter().map(item_from_row).collect()
+            },
+            Self::Json(dir) => {
+                let path = batch_path(dir, id)?;
+                with_json_lock(move || {
+                    let items = read_batch(&path)?.map(|s| s.items).unwrap_or_default();
+                    Ok(items
+                        .into_iter()
+                        .filter(|item| item.status == BatchItemStatus::Pending)
+                        .filter(|item| after_position.is_none_or(|after| item.position > after))
+                        .take(limit)
+                        .collect())
+                })
+                .await
+            },
+        }
+    }
+
+    /// All requests of a batch, in submission order.
+    pub async fn items(&self, id: &str) -> Result<Vec<BatchItem>, String> {
+        match self {
+            Self::Postgres(pool) => {
+                let rows = sqlx::query(
+                    "SELECT * FROM batch_requests WHERE batch_id = $1 ORDER BY position",
+                )
+                .bind(id)
+                .fetch_all(pool)
+                .await
+                .map_err(|e| e.to_string())?;
+                rows.iter().map(item_from_row).collect()
+            },
+            Self::Json(dir) => {
+                let path = batch_path(dir, id)?;
+                with_json_lock(move || Ok(read_batch(&path)?.map(|s| s.items).unwrap_or_default()))
+                    .await
+            },
+        }
+    }
+
+    /// Record the outcome of one request.
+    pub async fn finish_item(&self, id: &str, item: &BatchItem) -> Result<(), String> {
+        match self {
+            Self::Postgres(pool) => {
+                sqlx::query(
+                    r#"UPDATE batch_requests
+                       SET status = $3, status_code = $4, result = $5, completed_at = $6
+                       WHERE batch_id = $1 AND position = $2"#,
+                )
+                .bind(id)
+                .bind(i32::try_from(item.position).map_err(|e| e.to_string())?)
+                .bind(item.status.as_str())
+                .bind(item.status_code.map(i32::from))
+                .bind(&item.result)
+                .bind(item.completed_at)
+                .execute(pool)
+                .await
+                .map_err(|e| e.to_string())?;
+                Ok(())
+            },
+            Self::Json(dir) => {
+                let item = item.clone();
+                modify_batch(dir, id, move |stored| {
+                    if let Some(existing) =
+                        stored.items.iter_mut().find(|i| i.position == item.position)
+                    {
+                        *existing =
+                            BatchItem { params: std::mem::take(&mut existing.params), ..item };
+                    }
+                })
+                .await
+            },
+        }
+    }
+
+    /// Give every still-pending request of a batch the final `status`.
+    pub async fn finish_pending(
+        &self,
+        id: &str,
+        status: BatchItemStatus,
+        completed_at: i64,
+    ) -> Result<(), String> {
+        match self {
+            Self::Postgres(pool) => {
+                sqlx::query(
+                    r#"UPDATE batch_requests SET status = $2, completed_at = $3
+                       WHERE batch_id = $1 AND status = 'pending'"#,
+                )
+                .bind(id)
+                .bind(status.as_str())
+                .bind(completed_at)
+                .execute(pool)
+                .await
+                .map_err(|e| e.to_string())?;
+                Ok(())
+            },
+            Self::Json(dir) => {
+                modify_batch(dir, id, move |stored| {
+                    for item in &mut stored.items {
+                        if item.status == BatchItemStatus::Pending {
+                            item.status = status;
+                            item.completed_at = Some(completed_at);
+                        }
+                    }
+                })
+                .await
+            },
+        }
+    }
+
+    /// Store a file and its content.
+    pub async fn put_file(&self, file: &BatchFile, content: Vec<u8>) -> Result<(), String> {
+        match self {
+            Self::Postgres(pool) => {
+                sqlx::query(
+                    r#"INSERT INTO batch_files (id, filename, purpose, api_key_id, bytes, content, created_at)
+                       VALUES ($1, $2, $3, $4, $5, $6, $7)"#,
+                )
+                .bind(&file.id)
+                .bind(&file.filename)
+                .bind(&file.purpose)
+                .bind(parse_key_id(file.api_key_id.as_deref())?)
+                .bind(i64::try_from(file.bytes).unwrap_or(i64::MAX))
+                .bind(content)
+                .bind(file.created_at)
+                .execute(pool)
+                .await
+                .map_err(|e| e.to_string())?;
+                Ok(())
+            },
+            Self::Json(dir) => {
+                let (meta, data) = file_paths(dir, &file.id)?;
+                let file = file.clone();
+                with_json_lock(move || {
+                    write_bytes(&data, &content)?;
+                    write_json(&meta, &file)
+                })
+                .await
+            },
+        }
+    }
+
+    pub async fn get_file(&self, id: &str) -> Result<Option<BatchFile>, String> {
+        match self {
+            Self::Postgres(pool) => {
+                let row = sqlx::query(
+                    r#"SELECT id, filename, purpose, api_key_id, bytes, created_at
+                       FROM batch_files WHERE id = $1"#,
+                )
+                .bind(id)
+                .fetch_optional(pool)
+                .await
+                .map_err(|e| e.to_string())?;
+                row.as_ref().map(file_from_row).transpose()
+            },
+            Self::Json(dir) => {
+                let (meta, _) = file_paths(dir, id)?;
+                with_json_lock(move || read_json(&meta)).await
+            },
+        }
+    }
+
+    pub async fn file_content(&self, id: &str) -> Result<Option<Vec<u8>>, String> {
+        match self {
+            Self::Postgres(pool) => {
+                sqlx::query_scalar("SELECT content FROM batch_files WHERE id = $1")
+                    .bind(id)
+                    .fetch_optional(pool)
+                    .await
+                    .map_err(|e| e.to_string())
+            },
+            Self::Json(dir) => {
+                let (_, data) = file_paths(dir, id)?;
+                with_json_lock(move || match fs::read(&data) {
+                    Ok(content) => Ok(Some(content)),
+                    Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+                    Err(e) => Err(format!("Failed to read file: {}", e)),
+                })
+                .await
+            },
+        }
+    }
+
+    /// Files visible to `key_id`, newest first.
+    pub async fn list_files(&self, key_id: Option<&str>) -> Result<Vec<BatchFile>, String> {
+        match self {
+            Self::Postgres(pool) => {
+                let rows = sqlx::query(
+                    r#"SELECT id, filename, purpose, api_key_id, bytes, created_at FROM batch_files
+                       WHERE $1::uuid IS NULL OR api_key_id = $1
+                       ORDER BY created_at DESC, id DESC"#,
+                )
+                .bind(parse_key_id(key_id)?)
+                .fetch_all(pool)
+                .await
+                .map_err(|e| e.to_string())?;
+                rows.iter().map(file_from_row).collect()
+            },
+            Self::Json(dir) => {
+                let (dir, key_id) = (dir.join(FILES_DIR), key_id.map(str::to_string));
+                with_json_lock(move || {
+                    let mut files: Vec<BatchFile> = read_dir_json::<BatchFile>(&dir)?
+                        .into_iter()
+                        .filter(|f| f.is_visible_to(key_id.as_deref()))
+                        .collect();
+                    files.sort_by(|a, b| (b.created_at, &b.id).cmp(&(a.created_at, &a.id)));
+                    Ok(files)
+                })
+                .await
+            },
+        }
+    }
+
+    /// Delete a file. Returns false if no file had this ID.
+    pub async fn delete_file(&self, id: &str) -> Result<bool, String> {
+        match self {
+            Self::Postgres(pool) => {
+                let result = sqlx::query("DELETE FROM batch_files WHERE id = $1")
+                    .bind(id)
+                    .execute(pool)
+                    .await
+                    .map_err(|e| e.to_string())?;
+                Ok(result.rows_affected() > 0)
+            },
+            Self::Json(dir) => {
+                let (meta, data) = file_paths(dir, id)?;
+                with_json_lock(move || {
+                    let existed = meta.exists();
+                    for path in [&meta, &data] {
+                        if path.exists() {
+                            fs::remove_file(path)
+                                .map_err(|e| format!("Failed to delete file: {}", e))?;
+                        }
+                    }
+                    Ok(existed)
+                })
+                .await
+            },
+        }
+    }
+}
+
+fn with_counts(mut batch: Batch, items: &[BatchItem]) -> Batch {
+    let mut counts = BatchRequestCounts::default();
+    for item in items {
+        counts.add(item.status, 1);
+    }
+    batch.request_counts = counts;
+    batch
+}
+
+/// Apply cursor pagination to batches sorted newest first; keeps one extra batch.
+fn paginate(batches: Vec<Batch>, page: &BatchPage) -> Vec<Batch> {
+    let position = |id: &str| batches.iter().position(|b| b.id == id);
+    let take = page.limit.saturating_add(1);
+    if let Some(before) = &page.before_id {
+        let end = position(before).unwrap_or(0);
+        let start = end.saturating_sub(take);
+        return batches[start..end].to_vec();
+    }
+    let start = match &page.after_id {
+        Some(after) => position(after).map_or(batches.len(), |i| i.saturating_add(1)),
+        None => 0,
+    };
+    batches.into_iter().skip(start).take(take).collect()
+}
+
+// ===== PostgreSQL =====
+
+fn parse_key_id(key_id: Option<&str>) -> Result<Option<Uuid>, String> {
+    key_id.map(|id| Uuid::parse_str(id).map_err(|e| e.to_string())).transpose()
+}
+
+async fn create_pg(pool: &PgPool, batch: &Batch, items: &[BatchItem]) -> Result<(), String> {
+    let mut tx = pool.begin().await.map_err(|e| e.to_string())?;
+    sqlx::query(
+        r#"INSERT INTO batches (id, api, endpoint, api_key_id, status, metadata, input_file_id,
+                                created_at, expires_at)
+           VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)"#,
+    )
+    .bind(&batch.id)
+    .bind(batch.api.as_str())
+    .bind(&batch.endpoint)
+    .bind(parse_key_id(batch.api_key_id.as_deref())?)
+    .bind(batch.status.as_str())
+    .bind(Value::Object(batch.metadata.clone()))
+    .bind(&batch.input_file_id)
+    .bind(batch.created_at)
+    .bind(batch.expires_at)
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    // Insert requests in chunks to stay under the bind parameter limit
+    for chunk in items.chunks(1000) {
+        let mut query = sqlx::QueryBuilder::new(
+            "INSERT INTO batch_requests (batch_id, position, custom_id, params) ",
+        );
+        let mut failed = None;
+        query.push_values(chunk, |mut row, item| {
+            let position = i32::try_from(item.position).unwrap_or_else(|e| {
+                failed = Some(e.to_string());
+                i32::MAX
+            });
+            row.push_bind(&batch.id)
+                .push_bind(position)
+                .push_bind(&item.custom_id)
+                .push_bind(&item.params);
+        });
+        if let Some(e) = failed {
+            return Err(e);
+        }
+        query.build().execute(&mut *tx).await.map_err(|e| e.to_string())?;
+    }
+
+    tx.commit().await.map_err(|e| e.to_string())
+}
+
+async fn list_pg(
+    pool: &PgPool,
+    api: BatchApi,
+    key_id: Option<&str>,
+    page: &BatchPage,
+) -> Result<Vec<Batch>, String> {
+    let take = i64::try_from(page.limit.saturating_add(1)).unwrap_or(i64::MAX);
+    let (cursor, newer) = match (&page.before_id, &page.after_id) {
+        (Some(before), _) => (Some(before), true),
+        (None, Some(after)) => (Some(after), false),
+        (None, None) => (None, false),
+    };
+    let sql = format!(
+        r#"SELECT * FROM batches
+           WHERE api = $1 AND ($2::uuid IS NULL OR api_key_id = $2)
+             AND ($3::text IS NULL OR (created_at, id) {} (SELECT created_at, id FROM batches WHERE id = $3))
+           ORDER BY created_at {order}, id {order} LIMIT $4"#,
+        if newer { ">" } else { "<" },
+        order = if newer { "ASC" } else { "DESC" },
+    );
+    let rows = sqlx::query(&sql)
+        .bind(api.as_str())
+        .bind(parse_key_id(key_id)?)
+        .bind(cursor)
+        .bind(take)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+    let mut batches = rows.iter().map(batch_from_row).collect::<Result<Vec<_>, _>>()?;
+    if newer {
+        batches.reverse();
+    }
+
+    let ids: Vec<String> = batches.iter().map(|b| b.id.clone()).collect();
+    let mut counts = counts_pg(pool, &ids).await?;
+    for batch in &mut batches {
+        batch.request_counts = counts.remove(&batch.id).unwrap_or_default();
+    }
+    Ok(batches)
+}
+
+async fn counts_pg(
+    pool: &PgPool,
+    ids: &[String],
+) -> Result<HashMap<String, BatchRequestCounts>, String> {
+    let rows = sqlx::query(
+        r#"SELECT batch_id, status, COUNT(*) AS n FROM batch_requests
+           WHERE batch_id = ANY($1) GROUP BY batch_id, status"#,
+    )
+    .bind(ids)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let mut counts: HashMap<String, BatchRequestCounts> = HashMap::new();
+    for row in rows {
+        let status = BatchItemStatus::parse(row.get("status")).unwrap_or_default();
+        let n = u64::try_from(row.get::<i64, _>("n")).unwrap_or(0);
+        counts.entry(row.get("batch_id")).or_default().add(status, n);
+    }
+    Ok(counts)
+}
+
+fn batch_from_row(row: &PgRow) -> Result<Batch, String> {
+    let api: String = row.get("api");
+    let status: String = row.get("status");
+    let api_key_id: Option<Uuid> = row.get("api_key_id");
+    let metadata: Value = row.get("metadata");
+    Ok(Batch {
+        id: row.get("id"),
+        api: BatchApi::parse(&api).ok_or_else(|| format!("Unknown batch API '{}'", api))?,
+        endpoint: row.get("endpoint"),
+        api_key_id: api_key_id.map(|id| id.to_string()),
+        status: BatchStatus::parse(&status)
+            .ok_or_else(|| format!("Unknown batch status '{}'", status))?,
+        metadata: match metadata {
+            Value::Object(map) => map,
+            _ => serde_json::Map::new(),
+        },
+        input_file_id: row.get("input_file_id"),
+        output_file_id: row.get("output_file_id"),
+        error_file_id: row.get("error_file_id"),
+        created_at: row.get("created_at"),
+        expires_at: row.get("expires_at"),
+        cancel_initiated_at: row.get("cancel_initiated_at"),
+        ended_at: row.get("ended_at"),
+        request_counts: BatchRequestCounts::default(),
+    })
+}
+
+fn item_from_row(row: &PgRow) -> Result<BatchItem, String> {
+    let status: String = row.get("status");
+    let status_code: Option<i32> = row.get("status_code");
+    Ok(BatchItem {
+        position: u32::try_from(row.get::<i32, _>("position")).map_err(|e| e.to_string())?,
+        custom_id: row.get("custom_id"),
+        params: row.get("params"),
+        status: BatchItemStatus::parse(&status)
+            .ok_or_else(|| format!("Unknown request status '{}'", status))?,
+        status_code: status_code.and_then(|code| u16::try_from(code).ok()),
+        result: row.get("result"),
+        completed_at: row.get("completed_at"),
+    })
+}
+
+fn file_from_row(row: &PgRow) -> Result<BatchFile, String> {
+    let api_key_id: Option<Uuid> = row.get("api_key_id");
+    Ok(BatchFile {
+        id: row.get("id"),
+        filename: row.get("filename"),
+        purpose: row.get("purpose"),
+        api_key_id: api_key_id.map(|id| id.to_string()),
+        bytes: u64::try_from(row.get::<i64, _>("bytes")).unwrap_or(0),
+        created_at: row.get("created_at"),
+    })
+}
+
+// ===== JSON files =====
+
+/// Whether `id` only uses the characters of generated batch and file IDs. IDs come from
+/// clients, so the JSON backend refuses anything else as a file name.
+pub fn is_valid_id(id: &str) -> bool {
+    !id.is_empty()
+        && id.len() <= 128
+        && id.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+fn checked_id(id: &str) -> Result<&str, String> {
+    if is_valid_id(id) {
+        Ok(id)
+    } else {
+        Err(format!("Invalid ID '{}'", id))
+    }
+}
+
+fn batch_path(dir: &Path, id: &str) -> Result<PathBuf, String> {
+    Ok(dir.join(format!("{}.json", checked_id(id)?)))
+}
+
+fn file_paths(dir: &Path, id: &str) -> Result<(PathBuf, PathBuf), String> {
+    let id = checked_id(id)?;
+    let files = dir.join(FILES_DIR);
+    Ok((files.join(format!("{}.json", id)), files.join(format!("{}.data", id))))
+}
+
+fn read_batch(path: &Path) -> Result<Option<StoredBatch>, String> {
+    read_json(path)
+}
+
+fn read_all_batches(dir: &Path) -> Result<Vec<StoredBatch>, String> {
+    read_dir_json(dir)
+}
+
+fn read_json<T: serde::de::DeserializeOwned>(path: &Path) -> Result<Option<T>, String> {
+    if !path.exists() {
+        return Ok(None);
+    }
+    let content = fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    serde_json::from_str(&content)
+        .map(Some)
+        .map_err(|e| format!("Failed to parse {}: {}", path.display(), e))
+}
+
+fn read_dir_json<T: serde::de::DeserializeOwned>(dir: &Path) -> Result<Vec<T>, String> {
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+    let entries =
+        fs::read_dir(dir).map_err(|e| format!("Failed to read {}: {}", dir.display(), e))?;
+    let mut values = Vec::new();
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.extension().is_some_and(|ext| ext == "json") {
+            values.extend(read_json(&path)?);
+        }
+    }
+    Ok(values)
+}
+
+fn write_json<T: Serialize>(path: &Path, value: &T) -> Result<(), String> {
+    let content =
+        serde_json::to_vec(value).map_err(|e| format!("Failed to serialize batch data: {}", e))?;
+    write_bytes(path, &content)
+}
+
+fn write_bytes(path: &Path, content: &[u8]) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create batch directory: {}", e))?;
+    }
+    let temp_path = path.with_extension("tmp");
+    fs::write(&temp_path, content).map_err(|e| format!("Failed to write batch data: {}", e))?;
+    fs::rename(&temp_path, path).map_err(|e| format!("Failed to save batch data: {}", e))
+}
+
+/// Run blocking file I/O under the store's lock.
+async fn with_json_lock<T, F>(f: F) -> Result<T, String>
+where
+    F: FnOnce() -> Result<T, String> + Send + 'static,
+    T: Send + 'static,
+{
+    static JSON_LOCK: tokio::sync::Mutex<()> = tokio::sync::Mutex::const_new(());
+    let _guard = JSON_LOCK.lock().await;
+    tokio::task::spawn_blocking(f).await.map_err(|e| format!("spawn_blocking panicked: {e}"))?
+}
+
+/// Read-modify-write a stored batch; a missing batch is an error.
+async fn modify_batch<F>(dir: &Path, id: &str, f: F) -> Result<(), String>
+where
+    F: FnOnce(&mut StoredBatch) + Send + 'static,
+{
+    let path = batch_path(dir, id)?;
+    let id = id.to_string();
+    with_json_lock(move || {
+        let mut stored = read_batch(&path)?.ok_or_else(|| format!("Batch '{}' not found", id))?;
+        f(&mut stored);
+        write_json(&path, &stored)
+    })
+    .await
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, reason = "test assertions")]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn batch(id: &str, created_at: i64) -> Batch {
+        Batch {
+            id: id.to_string(),
+            api: BatchApi::Anthropic,
+            endpoint: "/v1/messages".to_string(),
+            api_key_id: None,
+            status: BatchStatus::InProgress,
+            metadata: serde_json::Map::new(),
+            input_file_id: None,
+            output_file_id: None,
+            error_file_id: None,
+            created_at,
+            expires_at: created_at + 86_400,
+            cancel_initiated_at: None,
+            ended_at: None,
+            request_counts: BatchRequestCounts::default(),
+        }
+    }
+
+    #[tokio::test]
+    async fn json_store_tracks_request_outcomes() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = BatchStore::Json(dir.path().to_path_buf());
+        let items: Vec<BatchItem> =
+            (0..3).map(|i| BatchItem::new(i, format!("req-{i}"), json!({"n": i}))).collect();
+        store.create(&batch("msgbatch_a", 1), &items).await.unwrap();
+
+        let pending = store.pending_items("msgbatch_a", Some(0), 10).await.unwrap();
+        assert_eq!(pending.iter().map(|i| i.position).collect::<Vec<_>>(), vec![1, 2]);
+
+        let mut done = pending[0].clone();
+        done.status = BatchItemStatus::Succeeded;
+        done.status_code = Some(200);
+        done.result = Some(json!({"ok": true}));
+        store.finish_item("msgbatch_a", &done).await.unwrap();
+        store.finish_pending("msgbatch_a", BatchItemStatus::Canceled, 5).await.unwrap();
+
+        let stored = store.get("msgbatch_a").await.unwrap().unwrap();
+        assert_eq!(stored.request_counts.succeeded, 1);
+        assert_eq!(stored.request_counts.canceled, 2);
+        let items = store.items("msgbatch_a").await.unwrap();
+        assert_eq!(items[1].params, json!({"n": 1}));
+        assert_eq!(items[1].result, Some(json!({"ok": true})));
+    }
+
+    #[tokio::test]
+    async fn json_store_lists_newest_first_with_cursors() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = BatchStore::Json(dir.path().to_path_buf());
+        for (id, created_at) in [("b1", 1), ("b2", 2), ("b3", 3)] {
+            store.create(&batch(id, created_at), &[]).await.unwrap();
+        }
+        let ids = |batches: Vec<Batch>| batches.into_iter().map(|b| b.id).collect::<Vec<_>>();
+
+        let first = BatchPage { limit: 1, ..Default::default() };
+        assert_eq!(ids(store.list(BatchApi::Anthropic, None, &first).await.unwrap()), ["b3", "b2"]);
+        let after = BatchPage { after_id: Some("b3".to_string()), limit: 5, ..Default::default() };
+        assert_eq!(ids(store.list(BatchApi::Anthropic, None, &after).await.unwrap()), ["b2", "b1"]);
+        let before =
+            BatchPage { before_id: Some("b1".to_string()), limit: 1, ..Default::default() };
+        assert_eq!(
+            ids(store.list(BatchApi::Anthropic, None, &before).await.unwrap()),
+            ["b3", "b2"]
+        );
+        assert!(store.list(BatchApi::Openai, None, &first).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn json_store_leases_batches_to_one_runner() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = BatchStore::Json(dir.path().to_path_buf());
+        store.create(&batch("msgbatch_a", 1), &[]).await.unwrap();
+
+        assert!(store.claim("msgbatch_a", "one", 100, 60).await.unwrap());
+        assert!(!store.claim("msgbatch_a", "two", 150, 60).await.unwrap());
+        // Renewing keeps the lease; once it expires another runner may take over
+        assert!(store.claim("msgbatch_a", "one", 150, 60).await.unwrap());
+        assert!(!store.claim("msgbatch_a", "two", 200, 60).await.unwrap());
+        assert!(store.claim("msgbatch_a", "two", 210, 60).await.unwrap());
+
+        store.release("msgbatch_a", "one").await.unwrap();
+        assert!(!store.claim("msgbatch_a", "one", 220, 60).await.unwrap());
+        store.release("msgbatch_a", "two").await.unwrap();
+        assert!(store.claim("msgbatch_a", "one", 220, 60).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn json_store_rejects_path_ids() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = BatchStore::Json(dir.path().to_path_buf());
+        assert!(store.get("../secrets").await.is_err());
+        assert!(store.get_file("a/b").await.is_err());
+        assert!(is_valid_id("msgbatch_0123abc") && is_valid_id("file-9f"));
+    }
+}
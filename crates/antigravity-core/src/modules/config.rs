@@ -1,14 +1,20 @@
-use serde_json;
+use serde_json::{self, Value};
+use std::collections::BTreeMap;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::{OnceLock, RwLock};
 use std::time::{Duration, Instant};
+use validator::{Validate, ValidationErrors, ValidationErrorsKind};
 
 use crate::models::AppConfig;
 use crate::modules::account::get_data_dir;
 
 const CONFIG_FILE: &str = "gui_config.json";
 
+/// Declarative config file layered over `gui_config.json` (see [`config_file_path`]).
+const DECLARATIVE_CONFIG_FILE: &str = "config.toml";
+const DECLARATIVE_CONFIG_ENV: &str = "ANTIGRAVITY_CONFIG";
+
 /// In-memory config cache with TTL to avoid disk I/O on hot paths.
 /// The cache is invalidated on save/update operations.
 const CONFIG_CACHE_TTL: Duration = Duration::from_secs(30);
@@ -58,10 +64,22 @@ pub fn invalidate_config_cache() {
 
 /// Load application configuration from disk.
 ///
-/// Note: This function includes migration logic that automatically migrates
-/// legacy mapping fields to custom_mapping.
+/// When a declarative config file is present (see [`config_file_path`]), its values
+/// are layered over `gui_config.json` and the result is validated.
 /// For hot paths, prefer `load_config_cached()` which avoids repeated disk reads.
 pub fn load_config() -> Result<AppConfig, String> {
+    let config = load_base_config()?;
+    match config_file_path() {
+        Some(path) => layer_config(&config, read_config_file(&path)?),
+        None => Ok(config),
+    }
+}
+
+/// Load `gui_config.json` alone, without the declarative config file.
+///
+/// Note: This function includes migration logic that automatically migrates
+/// legacy mapping fields to custom_mapping.
+pub fn load_base_config() -> Result<AppConfig, String> {
     let data_dir = get_data_dir()?;
     let config_path = data_dir.join(CONFIG_FILE);
 
@@ -153,17 +171,368 @@ pub fn save_config(config: &AppConfig) -> Result<(), String> {
 
 /// Update specific fields in the config.
 /// Automatically invalidates the in-memory config cache.
+///
+/// Only `gui_config.json` is written; values pinned by the declarative config file
+/// keep taking precedence.
 pub fn update_config<F>(updater: F) -> Result<AppConfig, String>
 where
     F: FnOnce(&mut AppConfig),
 {
-    let mut config = load_config()?;
+    let mut config = load_base_config()?;
     updater(&mut config);
     save_config(&config)?;
     Ok(config)
 }
 
+/// Save the edits a client made to the effective config it was shown (see [`load_config`]).
+///
+/// Only values that differ from the current effective config are written to
+/// `gui_config.json`, so settings coming from the declarative config file are not copied
+/// into it and keep following the file.
+pub fn save_config_changes(submitted: &AppConfig) -> Result<AppConfig, String> {
+    let config = apply_changes(&load_base_config()?, &load_config()?, submitted)?;
+    save_config(&config)?;
+    Ok(config)
+}
+
+/// `base` with the values that differ between `effective` and `submitted` taken from
+/// `submitted`.
+fn apply_changes(
+    base: &AppConfig,
+    effective: &AppConfig,
+    submitted: &AppConfig,
+) -> Result<AppConfig, String> {
+    let to_value = |config| {
+        serde_json::to_value(config).map_err(|e| format!("Failed to serialize config: {}", e))
+    };
+    let mut merged = to_value(base)?;
+    copy_changes(&mut merged, &to_value(effective)?, to_value(submitted)?);
+    serde_json::from_value(merged).map_err(|e| format!("Invalid config: {}", e))
+}
+
+fn copy_changes(base: &mut Value, effective: &Value, submitted: Value) {
+    match (base, submitted) {
+        (Value::Object(base), Value::Object(submitted)) => {
+            let Value::Object(effective) = effective else {
+                *base = submitted;
+                return;
+            };
+            let removed: Vec<String> =
+                effective.keys().filter(|key| !submitted.contains_key(*key)).cloned().collect();
+            for key in removed {
+                base.remove(&key);
+            }
+            for (key, value) in submitted {
+                match (effective.get(&key), base.get_mut(&key)) {
+                    (Some(seen), _) if *seen == value => {},
+                    (Some(seen), Some(existing)) => copy_changes(existing, seen, value),
+                    _ => {
+                        base.insert(key, value);
+                    },
+                }
+            }
+        },
+        (base, submitted) => *base = submitted,
+    }
+}
+
 /// Get the data directory path.
 pub fn get_data_directory() -> Result<PathBuf, String> {
     get_data_dir()
 }
+
+/// Declarative config file in effect: `$ANTIGRAVITY_CONFIG` if set, otherwise
+/// `config.toml` in the data directory when it exists.
+pub fn config_file_path() -> Option<PathBuf> {
+    if let Ok(path) = std::env::var(DECLARATIVE_CONFIG_ENV) {
+        if !path.trim().is_empty() {
+            return Some(PathBuf::from(path));
+        }
+    }
+    let path = get_data_dir().ok()?.join(DECLARATIVE_CONFIG_FILE);
+    path.exists().then_some(path)
+}
+
+/// Read a declarative config file. Keys mirror `gui_config.json`
+/// (`[proxy]`, `[proxy.custom_mapping]`, ...).
+pub fn read_config_file(path: &Path) -> Result<Value, String> {
+    let content = fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    parse_config_file(&content).map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+pub fn parse_config_file(content: &str) -> Result<Value, String> {
+    let value: Value = toml::from_str(content).map_err(|e| e.to_string())?;
+    if !value.is_object() {
+        return Err("expected a table at the top level".to_string());
+    }
+    Ok(value)
+}
+
+/// Layer `overlay` (a parsed config file) over `base` and validate the result.
+///
+/// Tables are merged key by key, anything else (including arrays) replaces the base
+/// value. Keys that don't exist in [`AppConfig`] are rejected so typos don't go unnoticed.
+/// Validation covers the settings the file sets; values only in `base` are trusted as
+/// they are, since older `gui_config.json` files predate some of the constraints.
+pub fn layer_config(base: &AppConfig, overlay: Value) -> Result<AppConfig, String> {
+    let mut merged =
+        serde_json::to_value(base).map_err(|e| format!("Failed to serialize config: {}", e))?;
+    merge_values(&mut merged, overlay.clone());
+
+    let config: AppConfig =
+        serde_json::from_value(merged).map_err(|e| format!("Invalid config: {}", e))?;
+
+    let effective =
+        serde_json::to_value(&config).map_err(|e| format!("Failed to serialize config: {}", e))?;
+    let unknown = unknown_keys(&overlay, &effective, "");
+    if !unknown.is_empty() {
+        return Err(format!("Unknown config keys: {}", unknown.join(", ")));
+    }
+
+    if let Err(errors) = config.validate() {
+        let mut failures = Vec::new();
+        collect_validation_errors(&errors, "", &mut failures);
+        failures.sort();
+        let mut set_paths = BTreeMap::new();
+        flatten(&overlay, String::new(), &mut set_paths);
+        failures.retain(|(path, _)| {
+            set_paths.keys().any(|set| {
+                path == set
+                    || path.starts_with(&format!("{}.", set))
+                    || path.starts_with(&format!("{}[", set))
+                    || set.starts_with(&format!("{}.", path))
+            })
+        });
+        if !failures.is_empty() {
+            let failures: Vec<String> =
+                failures.into_iter().map(|(path, error)| format!("{}: {}", path, error)).collect();
+            return Err(format!("Invalid config: {}", failures.join("; ")));
+        }
+    }
+    Ok(config)
+}
+
+/// Flatten validator errors into `(dotted path, description)` pairs.
+fn collect_validation_errors(
+    errors: &ValidationErrors,
+    prefix: &str,
+    out: &mut Vec<(String, String)>,
+) {
+    for (field, kind) in errors.errors() {
+        // Struct-level (`schema`) errors are reported under "__all__"
+        let path = match (prefix.is_empty(), field.as_ref()) {
+            (_, "__all__") => prefix.to_string(),
+            (true, field) => field.to_string(),
+            (false, field) => format!("{}.{}", prefix, field),
+        };
+        match kind {
+            ValidationErrorsKind::Field(errors) => {
+                for error in errors {
+                    let mut params: Vec<String> = error
+                        .params
+                        .iter()
+                        .filter(|(name, _)| *name != "value")
+                        .map(|(name, value)| format!("{}={}", name, value))
+                        .collect();
+                    params.sort();
+                    let description = match &error.message {
+                        Some(message) => message.to_string(),
+                        None if params.is_empty() => error.code.to_string(),
+                        None => format!("{} ({})", error.code, params.join(", ")),
+                    };
+                    out.push((path.clone(), description));
+                }
+            },
+            ValidationErrorsKind::Struct(errors) => collect_validation_errors(errors, &path, out),
+            ValidationErrorsKind::List(items) => {
+                for (index, errors) in items {
+                    collect_validation_errors(errors, &format!("{}[{}]", path, index), out);
+                }
+            },
+        }
+    }
+}
+
+fn merge_values(base: &mut Value, overlay: Value) {
+    match (base, overlay) {
+        (Value::Object(base), Value::Object(overlay)) => {
+            for (key, value) in overlay {
+                match base.get_mut(&key) {
+                    Some(existing) => merge_values(existing, value),
+                    None => {
+                        base.insert(key, value);
+                    },
+                }
+            }
+        },
+        (base, overlay) => *base = overlay,
+    }
+}
+
+fn unknown_keys(overlay: &Value, effective: &Value, prefix: &str) -> Vec<String> {
+    let Value::Object(overlay) = overlay else {
+        return Vec::new();
+    };
+    let mut unknown = Vec::new();
+    for (key, value) in overlay {
+        let path = if prefix.is_empty() { key.clone() } else { format!("{}.{}", prefix, key) };
+        match effective.get(key) {
+            Some(existing) => unknown.extend(unknown_keys(value, existing, &path)),
+            None => unknown.push(path),
+        }
+    }
+    unknown
+}
+
+/// A setting that differs between two configs, keyed by dotted path.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfigChange {
+    pub key: String,
+    /// `None` if the key was added
+    pub old: Option<Value>,
+    /// `None` if the key was removed
+    pub new: Option<Value>,
+}
+
+/// Changed settings between `old` and `new`, sorted by key. Arrays compare as a whole.
+pub fn diff_configs(old: &AppConfig, new: &AppConfig) -> Result<Vec<ConfigChange>, String> {
+    let flatten_config = |config: &AppConfig| -> Result<BTreeMap<String, Value>, String> {
+        let value = serde_json::to_value(config)
+            .map_err(|e| format!("Failed to serialize config: {}", e))?;
+        let mut leaves = BTreeMap::new();
+        flatten(&value, String::new(), &mut leaves);
+        Ok(leaves)
+    };
+    let mut old = flatten_config(old)?;
+    let new = flatten_config(new)?;
+
+    let mut changes = Vec::new();
+    for (key, new_value) in new {
+        match old.remove(&key) {
+            Some(old_value) if old_value == new_value => {},
+            old_value => changes.push(ConfigChange { key, old: old_value, new: Some(new_value) }),
+        }
+    }
+    changes.extend(old.into_iter().map(|(key, value)| ConfigChange {
+        key,
+        old: Some(value),
+        new: None,
+    }));
+    changes.sort_by(|a, b| a.key.cmp(&b.key));
+    Ok(changes)
+}
+
+/// Leaf values by dotted path; empty tables have no leaves.
+fn flatten(value: &Value, prefix: String, leaves: &mut BTreeMap<String, Value>) {
+    match value {
+        Value::Object(map) => {
+            for (key, value) in map {
+                let path =
+                    if prefix.is_empty() { key.clone() } else { format!("{}.{}", prefix, key) };
+                flatten(value, path, leaves);
+            }
+        },
+        _ => {
+            leaves.insert(prefix, value.clone());
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn base() -> AppConfig {
+        let mut config = AppConfig::new();
+        config.proxy.api_key = "sk-test".to_string();
+        config.proxy.custom_mapping.insert("gpt-4o".to_string(), "gemini-3-pro".to_string());
+        config
+    }
+
+    #[test]
+    fn file_values_override_base() {
+        let overlay = parse_config_file(
+            r#"
+            [proxy]
+            port = 9000
+            image_retention_turns = 2
+            upstream_urls = ["https://gateway.internal/v1internal"]
+
+            [proxy.custom_mapping]
+            "claude-sonnet-4" = "gemini-3-flash"
+            "#,
+        )
+        .unwrap();
+        let config = layer_config(&base(), overlay).unwrap();
+
+        assert_eq!(config.proxy.port, 9000);
+        assert_eq!(config.proxy.image_retention_turns, 2);
+        assert_eq!(config.proxy.upstream_urls, vec!["https://gateway.internal/v1internal"]);
+        // Tables merge: the mapping from gui_config.json survives
+        assert_eq!(config.proxy.custom_mapping.len(), 2);
+        assert_eq!(config.proxy.api_key, "sk-test");
+    }
+
+    #[test]
+    fn rejects_unknown_keys() {
+        let overlay = json!({"proxy": {"prot": 9000, "zai": {"enabeld": true}}});
+        let err = layer_config(&base(), overlay).unwrap_err();
+        assert!(err.contains("proxy.prot"), "{err}");
+        assert!(err.contains("proxy.zai.enabeld"), "{err}");
+    }
+
+    #[test]
+    fn rejects_invalid_values() {
+        let overlay = json!({"proxy": {"port": 80}});
+        let err = layer_config(&base(), overlay).unwrap_err();
+        assert_eq!(err, "Invalid config: proxy.port: range (max=65535, min=1024)");
+
+        let overlay = json!({"proxy": {"health_check_urls": ["not a url"]}});
+        assert!(layer_config(&base(), overlay).is_err());
+
+        let overlay = json!({"proxy": {"port": "9000"}});
+        assert!(layer_config(&base(), overlay).is_err());
+
+        assert!(parse_config_file("proxy = [").is_err());
+    }
+
+    #[test]
+    fn diff_lists_changed_keys() {
+        let old = base();
+        let mut new = base();
+        new.proxy.port = 9000;
+        new.proxy.custom_mapping.remove("gpt-4o");
+        new.proxy.custom_mapping.insert("o3".to_string(), "gemini-3-pro".to_string());
+
+        let changes = diff_configs(&old, &new).unwrap();
+        let keys: Vec<&str> = changes.iter().map(|c| c.key.as_str()).collect();
+        assert_eq!(keys, ["proxy.custom_mapping.gpt-4o", "proxy.custom_mapping.o3", "proxy.port"]);
+        assert_eq!(changes[0].new, None);
+        assert_eq!(changes[1].old, None);
+        assert_eq!(changes[2].old, Some(json!(8045)));
+        assert_eq!(changes[2].new, Some(json!(9000)));
+
+        assert!(diff_configs(&old, &old).unwrap().is_empty());
+    }
+
+    #[test]
+    fn saving_keeps_file_values_out_of_the_base() {
+        let overlay = json!({"proxy": {"port": 9000, "custom_mapping": {"o3": "gemini-3-flash"}}});
+        let effective = layer_config(&base(), overlay).unwrap();
+
+        let mut submitted = effective.clone();
+        submitted.proxy.api_key = "sk-new".to_string();
+        submitted.proxy.custom_mapping.remove("gpt-4o");
+        let saved = apply_changes(&base(), &effective, &submitted).unwrap();
+
+        assert_eq!(saved.proxy.api_key, "sk-new");
+        assert!(saved.proxy.custom_mapping.is_empty());
+        // Untouched values from the file stay out of gui_config.json
+        assert_eq!(saved.proxy.port, base().proxy.port);
+
+        submitted.proxy.port = 9100;
+        assert_eq!(apply_changes(&base(), &effective, &submitted).unwrap().proxy.port, 9100);
+    }
+}
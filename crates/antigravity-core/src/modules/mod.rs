@@ -7,6 +7,8 @@ pub(crate) mod account_pg_events;
 pub(crate) mod account_pg_helpers;
 pub(crate) mod account_pg_query;
 pub(crate) mod account_pg_targeted;
+pub mod api_key_store;
+pub mod batch_store;
 pub mod config;
 pub mod device;
 pub mod json_migration;
@@ -19,6 +21,7 @@ pub mod proxy_pool;
 pub mod quota;
 pub mod repository;
 pub mod signature_storage;
+pub mod spend_db;
 pub(crate) mod token_extraction;
 mod token_usage_stats;
 pub(crate) mod vscode;
@@ -7,7 +7,7 @@
     reason = "timestamp conversions and statistics calculations"
 )]
 
-use antigravity_types::models::{ProxyRequestLog, ProxyStats};
+use antigravity_types::models::{ProxyLogQuery, ProxyRequestLog, ProxyStats};
 use rusqlite::{params, Connection, Error as SqliteError};
 use std::cell::{Cell, RefCell};
 use std::path::PathBuf;
@@ -23,7 +23,9 @@ pub fn get_proxy_db_path() -> Result<PathBuf, String> {
     Ok(data_dir.join("proxy_logs.db"))
 }
 
-fn with_connection<T>(f: impl FnOnce(&Connection) -> Result<T, String>) -> Result<T, String> {
+pub(crate) fn with_connection<T>(
+    f: impl FnOnce(&Connection) -> Result<T, String>,
+) -> Result<T, String> {
     let db_path = get_proxy_db_path()?;
     PROXY_DB_CONN.with(|cell| {
         let mut cell_borrow = cell.borrow_mut();
@@ -32,6 +34,7 @@ fn with_connection<T>(f: impl FnOnce(&Connection) -> Result<T, String>) -> Resul
             // Enable WAL mode and busy timeout for better concurrency
             let _ = conn.execute("PRAGMA journal_mode=WAL", []);
             let _ = conn.execute("PRAGMA busy_timeout=5000", []);
+            init_schema(&conn)?;
             *cell_borrow = Some(conn);
         }
         let conn = cell_borrow.as_ref().ok_or_else(|| "Proxy DB connection missing".to_string())?;
@@ -53,86 +56,129 @@ fn add_column_if_missing(conn: &Connection, statement: &str) -> Result<(), Strin
 
 // ============ Synchronous inner functions ============
 
-fn init_db_sync() -> Result<(), String> {
-    with_connection(|conn| {
+fn init_schema(conn: &Connection) -> Result<(), String> {
+    let _rows_affected: usize = conn
+        .execute(
+            "CREATE TABLE IF NOT EXISTS request_logs (
+            id TEXT PRIMARY KEY,
+            timestamp INTEGER,
+            method TEXT,
+            url TEXT,
+            status INTEGER,
+            duration INTEGER,
+            model TEXT,
+            error TEXT,
+            request_body TEXT,
+            response_body TEXT,
+            input_tokens INTEGER,
+            output_tokens INTEGER,
+            account_email TEXT,
+            mapped_model TEXT,
+            mapping_reason TEXT,
+            cached_tokens INTEGER,
+            api_key_id TEXT,
+            api_key_label TEXT,
+            upstream_request_body TEXT
+        )",
+            [],
+        )
+        .map_err(|err| err.to_string())?;
+
+    add_column_if_missing(conn, "ALTER TABLE request_logs ADD COLUMN request_body TEXT")?;
+    add_column_if_missing(conn, "ALTER TABLE request_logs ADD COLUMN response_body TEXT")?;
+    add_column_if_missing(conn, "ALTER TABLE request_logs ADD COLUMN input_tokens INTEGER")?;
+    add_column_if_missing(conn, "ALTER TABLE request_logs ADD COLUMN output_tokens INTEGER")?;
+    add_column_if_missing(conn, "ALTER TABLE request_logs ADD COLUMN account_email TEXT")?;
+    add_column_if_missing(conn, "ALTER TABLE request_logs ADD COLUMN mapped_model TEXT")?;
+    add_column_if_missing(conn, "ALTER TABLE request_logs ADD COLUMN mapping_reason TEXT")?;
+    add_column_if_missing(conn, "ALTER TABLE request_logs ADD COLUMN cached_tokens INTEGER")?;
+    add_column_if_missing(conn, "ALTER TABLE request_logs ADD COLUMN api_key_id TEXT")?;
+    add_column_if_missing(conn, "ALTER TABLE request_logs ADD COLUMN api_key_label TEXT")?;
+    add_column_if_missing(conn, "ALTER TABLE request_logs ADD COLUMN upstream_request_body TEXT")?;
+
+    let _rows_affected: usize = conn
+        .execute("CREATE INDEX IF NOT EXISTS idx_timestamp ON request_logs (timestamp DESC)", [])
+        .map_err(|err| err.to_string())?;
+
+    // Full-text index over bodies, kept in sync by triggers (external content table)
+    let fts_exists: bool = conn
+        .query_row(
+            "SELECT EXISTS(SELECT 1 FROM sqlite_master WHERE name = 'request_logs_fts')",
+            [],
+            |row| row.get(0),
+        )
+        .map_err(|err| err.to_string())?;
+    conn.execute_batch(
+        "CREATE VIRTUAL TABLE IF NOT EXISTS request_logs_fts USING fts5(
+            request_body, upstream_request_body, response_body,
+            content='request_logs', content_rowid='rowid'
+        );
+        CREATE TRIGGER IF NOT EXISTS request_logs_fts_insert AFTER INSERT ON request_logs BEGIN
+            INSERT INTO request_logs_fts(rowid, request_body, upstream_request_body, response_body)
+            VALUES (new.rowid, new.request_body, new.upstream_request_body, new.response_body);
+        END;
+        CREATE TRIGGER IF NOT EXISTS request_logs_fts_delete AFTER DELETE ON request_logs BEGIN
+            INSERT INTO request_logs_fts(request_logs_fts, rowid, request_body, upstream_request_body, response_body)
+            VALUES ('delete', old.rowid, old.request_body, old.upstream_request_body, old.response_body);
+        END;",
+    )
+    .map_err(|err| err.to_string())?;
+    if !fts_exists {
+        // Index rows written before full-text search existed
         let _rows_affected: usize = conn
-            .execute(
-                "CREATE TABLE IF NOT EXISTS request_logs (
-                id TEXT PRIMARY KEY,
-                timestamp INTEGER,
-                method TEXT,
-                url TEXT,
-                status INTEGER,
-                duration INTEGER,
-                model TEXT,
-                error TEXT,
-                request_body TEXT,
-                response_body TEXT,
-                input_tokens INTEGER,
-                output_tokens INTEGER,
-                account_email TEXT,
-                mapped_model TEXT,
-                mapping_reason TEXT,
-                cached_tokens INTEGER
-            )",
-                [],
-            )
+            .execute("INSERT INTO request_logs_fts(request_logs_fts) VALUES ('rebuild')", [])
             .map_err(|err| err.to_string())?;
+    }
 
-        add_column_if_missing(conn, "ALTER TABLE request_logs ADD COLUMN request_body TEXT")?;
-        add_column_if_missing(conn, "ALTER TABLE request_logs ADD COLUMN response_body TEXT")?;
-        add_column_if_missing(conn, "ALTER TABLE request_logs ADD COLUMN input_tokens INTEGER")?;
-        add_column_if_missing(conn, "ALTER TABLE request_logs ADD COLUMN output_tokens INTEGER")?;
-        add_column_if_missing(conn, "ALTER TABLE request_logs ADD COLUMN account_email TEXT")?;
-        add_column_if_missing(conn, "ALTER TABLE request_logs ADD COLUMN mapped_model TEXT")?;
-        add_column_if_missing(conn, "ALTER TABLE request_logs ADD COLUMN mapping_reason TEXT")?;
-        add_column_if_missing(conn, "ALTER TABLE request_logs ADD COLUMN cached_tokens INTEGER")?;
+    super::spend_db::init_schema(conn)
+}
 
-        let _rows_affected: usize = conn
-            .execute(
-                "CREATE INDEX IF NOT EXISTS idx_timestamp ON request_logs (timestamp DESC)",
-                [],
-            )
-            .map_err(|err| err.to_string())?;
+fn init_db_sync() -> Result<(), String> {
+    with_connection(init_schema)
+}
 
-        Ok(())
-    })
+fn insert_log(conn: &Connection, log: &ProxyRequestLog) -> Result<(), String> {
+    let _rows_affected: usize = conn
+        .execute(
+            "INSERT INTO request_logs (id, timestamp, method, url, status, duration, model, error, request_body, response_body, input_tokens, output_tokens, account_email, mapped_model, mapping_reason, cached_tokens, api_key_id, api_key_label, upstream_request_body)
+          VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19)",
+            params![
+                log.id,
+                log.timestamp,
+                log.method,
+                log.url,
+                log.status,
+                log.duration,
+                log.model,
+                log.error,
+                log.request_body,
+                log.response_body,
+                log.input_tokens,
+                log.output_tokens,
+                log.account_email,
+                log.mapped_model,
+                log.mapping_reason,
+                log.cached_tokens,
+                log.api_key_id,
+                log.api_key_label,
+                log.upstream_request_body,
+            ],
+        )
+        .map_err(|err| err.to_string())?;
+    Ok(())
 }
 
-fn save_log_sync(log: ProxyRequestLog) -> Result<(), String> {
+fn save_log_sync(log: ProxyRequestLog, retention_days: u32) -> Result<(), String> {
     with_connection(|conn| {
-        let _rows_affected: usize = conn
-            .execute(
-                "INSERT INTO request_logs (id, timestamp, method, url, status, duration, model, error, request_body, response_body, input_tokens, output_tokens, account_email, mapped_model, mapping_reason, cached_tokens)
-              VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16)",
-                params![
-                    log.id,
-                    log.timestamp,
-                    log.method,
-                    log.url,
-                    log.status,
-                    log.duration,
-                    log.model,
-                    log.error,
-                    log.request_body,
-                    log.response_body,
-                    log.input_tokens,
-                    log.output_tokens,
-                    log.account_email,
-                    log.mapped_model,
-                    log.mapping_reason,
-                    log.cached_tokens,
-                ],
-            )
-            .map_err(|err| err.to_string())?;
+        insert_log(conn, &log)?;
 
         // Periodic cleanup
         INSERT_COUNT.with(|count| {
             let current = count.get() + 1;
             if current >= 1000 {
                 count.set(0);
-                // Cutoff for cleanup
-                let cutoff = chrono::Utc::now().timestamp_millis() - 7 * 86_400_000;
+                let cutoff =
+                    chrono::Utc::now().timestamp_millis() - i64::from(retention_days) * 86_400_000;
                 let _ =
                     conn.execute("DELETE FROM request_logs WHERE timestamp < ?1", params![cutoff]);
             } else {
@@ -154,39 +200,42 @@ fn cleanup_old_logs_sync(retention_days: u32) -> Result<usize, String> {
     })
 }
 
+const LOG_COLUMNS: &str = "id, timestamp, method, url, status, duration, model, error, request_body, response_body, input_tokens, output_tokens, account_email, mapped_model, mapping_reason, cached_tokens, api_key_id, api_key_label, upstream_request_body";
+
+fn row_to_log(row: &rusqlite::Row<'_>) -> rusqlite::Result<ProxyRequestLog> {
+    Ok(ProxyRequestLog {
+        id: row.get(0)?,
+        timestamp: row.get(1)?,
+        method: row.get(2)?,
+        url: row.get(3)?,
+        status: row.get(4)?,
+        duration: row.get(5)?,
+        model: row.get(6)?,
+        mapped_model: row.get(13).unwrap_or(None),
+        mapping_reason: row.get(14).unwrap_or(None),
+        account_email: row.get(12).unwrap_or(None),
+        error: row.get(7)?,
+        request_body: row.get(8).unwrap_or(None),
+        upstream_request_body: row.get(18).unwrap_or(None),
+        response_body: row.get(9).unwrap_or(None),
+        input_tokens: row.get(10).unwrap_or(None),
+        output_tokens: row.get(11).unwrap_or(None),
+        cached_tokens: row.get(15).unwrap_or(None),
+        api_key_id: row.get(16).unwrap_or(None),
+        api_key_label: row.get(17).unwrap_or(None),
+    })
+}
+
 fn get_logs_sync(limit: usize) -> Result<Vec<ProxyRequestLog>, String> {
     with_connection(|conn| {
         let mut stmt = conn
-            .prepare(
-                "SELECT id, timestamp, method, url, status, duration, model, error, request_body, response_body, input_tokens, output_tokens, account_email, mapped_model, mapping_reason, cached_tokens
-             FROM request_logs
-             ORDER BY timestamp DESC
-             LIMIT ?1",
-            )
+            .prepare(&format!(
+                "SELECT {} FROM request_logs ORDER BY timestamp DESC LIMIT ?1",
+                LOG_COLUMNS
+            ))
             .map_err(|err| err.to_string())?;
 
-        let logs_iter = stmt
-            .query_map([limit], |row| {
-                Ok(ProxyRequestLog {
-                    id: row.get(0)?,
-                    timestamp: row.get(1)?,
-                    method: row.get(2)?,
-                    url: row.get(3)?,
-                    status: row.get(4)?,
-                    duration: row.get(5)?,
-                    model: row.get(6)?,
-                    mapped_model: row.get(13).unwrap_or(None),
-                    mapping_reason: row.get(14).unwrap_or(None),
-                    account_email: row.get(12).unwrap_or(None),
-                    error: row.get(7)?,
-                    request_body: row.get(8).unwrap_or(None),
-                    response_body: row.get(9).unwrap_or(None),
-                    input_tokens: row.get(10).unwrap_or(None),
-                    output_tokens: row.get(11).unwrap_or(None),
-                    cached_tokens: row.get(15).unwrap_or(None),
-                })
-            })
-            .map_err(|err| err.to_string())?;
+        let logs_iter = stmt.query_map([limit], row_to_log).map_err(|err| err.to_string())?;
 
         let mut logs = Vec::new();
         for log in logs_iter {
@@ -196,6 +245,80 @@ fn get_logs_sync(limit: usize) -> Result<Vec<ProxyRequestLog>, String> {
     })
 }
 
+const DEFAULT_SEARCH_LIMIT: usize = 100;
+const MAX_SEARCH_LIMIT: usize = 1000;
+
+/// Quote each word so user input is never parsed as FTS5 query syntax.
+fn fts_match_expr(q: &str) -> String {
+    q.split_whitespace()
+        .map(|term| format!("\"{}\"", term.replace('"', "\"\"")))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn search_logs_with(
+    conn: &Connection,
+    query: &ProxyLogQuery,
+) -> Result<Vec<ProxyRequestLog>, String> {
+    use rusqlite::types::Value as Sql;
+
+    let mut clauses: Vec<&str> = Vec::new();
+    let mut values: Vec<Sql> = Vec::new();
+
+    if let Some(model) = &query.model {
+        clauses.push("(model = ? OR mapped_model = ?)");
+        values.push(Sql::Text(model.clone()));
+        values.push(Sql::Text(model.clone()));
+    }
+    if let Some(account) = &query.account {
+        clauses.push("account_email = ?");
+        values.push(Sql::Text(account.clone()));
+    }
+    if let Some(status) = query.status {
+        clauses.push("status = ?");
+        values.push(Sql::Integer(i64::from(status)));
+    }
+    if query.errors_only {
+        clauses.push("status >= 400");
+    }
+    if let Some(key) = &query.api_key_id {
+        clauses.push("api_key_id = ?");
+        values.push(Sql::Text(key.clone()));
+    }
+    if let Some(from) = query.from {
+        clauses.push("timestamp >= ?");
+        values.push(Sql::Integer(from));
+    }
+    if let Some(to) = query.to {
+        clauses.push("timestamp <= ?");
+        values.push(Sql::Integer(to));
+    }
+    if let Some(q) = query.q.as_deref().map(fts_match_expr).filter(|q| !q.is_empty()) {
+        clauses
+            .push("rowid IN (SELECT rowid FROM request_logs_fts WHERE request_logs_fts MATCH ?)");
+        values.push(Sql::Text(q));
+    }
+    let limit = query.limit.unwrap_or(DEFAULT_SEARCH_LIMIT).min(MAX_SEARCH_LIMIT);
+    values.push(Sql::Integer(limit as i64));
+
+    let where_sql =
+        if clauses.is_empty() { String::new() } else { format!("WHERE {}", clauses.join(" AND ")) };
+    let sql = format!(
+        "SELECT {} FROM request_logs {} ORDER BY timestamp DESC LIMIT ?",
+        LOG_COLUMNS, where_sql
+    );
+
+    let mut stmt = conn.prepare(&sql).map_err(|err| err.to_string())?;
+    let rows = stmt
+        .query_map(rusqlite::params_from_iter(values), row_to_log)
+        .map_err(|err| err.to_string())?;
+    rows.collect::<Result<Vec<_>, _>>().map_err(|err| err.to_string())
+}
+
+fn search_logs_sync(query: ProxyLogQuery) -> Result<Vec<ProxyRequestLog>, String> {
+    with_connection(|conn| search_logs_with(conn, &query))
+}
+
 fn get_stats_sync() -> Result<ProxyStats, String> {
     with_connection(|conn| {
         let total_requests: u64 = conn
@@ -257,9 +380,9 @@ pub async fn init_db() -> Result<(), String> {
         .map_err(|e| format!("spawn_blocking panicked: {e}"))?
 }
 
-/// Save a request log entry to the database.
-pub async fn save_log(log: ProxyRequestLog) -> Result<(), String> {
-    tokio::task::spawn_blocking(move || save_log_sync(log))
+/// Save a request log entry, pruning entries older than `retention_days` periodically.
+pub async fn save_log(log: ProxyRequestLog, retention_days: u32) -> Result<(), String> {
+    tokio::task::spawn_blocking(move || save_log_sync(log, retention_days))
         .await
         .map_err(|e| format!("spawn_blocking panicked: {e}"))?
 }
@@ -278,6 +401,13 @@ pub async fn get_logs(limit: usize) -> Result<Vec<ProxyRequestLog>, String> {
         .map_err(|e| format!("spawn_blocking panicked: {e}"))?
 }
 
+/// Search stored request logs.
+pub async fn search_logs(query: ProxyLogQuery) -> Result<Vec<ProxyRequestLog>, String> {
+    tokio::task::spawn_blocking(move || search_logs_sync(query))
+        .await
+        .map_err(|e| format!("spawn_blocking panicked: {e}"))?
+}
+
 /// Get aggregate statistics from the proxy logs.
 pub async fn get_stats() -> Result<ProxyStats, String> {
     tokio::task::spawn_blocking(get_stats_sync)
@@ -293,3 +423,85 @@ pub async fn clear_proxy_logs() -> Result<(), String> {
 }
 
 pub use super::token_usage_stats::get_token_usage_stats;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn log(id: &str, timestamp: i64, status: u16, request_body: &str) -> ProxyRequestLog {
+        ProxyRequestLog {
+            id: id.to_string(),
+            timestamp,
+            method: "POST".to_string(),
+            url: "/v1/chat/completions".to_string(),
+            status,
+            duration: 10,
+            model: Some("gpt-4o".to_string()),
+            mapped_model: Some("gemini-3-flash".to_string()),
+            mapping_reason: None,
+            account_email: Some("a@example.com".to_string()),
+            error: None,
+            request_body: Some(request_body.to_string()),
+            upstream_request_body: Some("{}".to_string()),
+            response_body: Some("{\"answer\":\"forty-two\"}".to_string()),
+            input_tokens: None,
+            output_tokens: None,
+            cached_tokens: None,
+            api_key_id: Some("key-1".to_string()),
+            api_key_label: None,
+        }
+    }
+
+    fn seeded() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        init_schema(&conn).unwrap();
+        insert_log(&conn, &log("a", 1_000, 200, "tell me about the weather")).unwrap();
+        insert_log(&conn, &log("b", 2_000, 500, "write a haiku about \"rust\"")).unwrap();
+        insert_log(&conn, &log("c", 3_000, 200, "another haiku please")).unwrap();
+        conn
+    }
+
+    fn ids(conn: &Connection, query: ProxyLogQuery) -> Vec<String> {
+        search_logs_with(conn, &query).unwrap().into_iter().map(|l| l.id).collect()
+    }
+
+    #[test]
+    fn search_filters_combine() {
+        let conn = seeded();
+        assert_eq!(ids(&conn, ProxyLogQuery::default()), vec!["c", "b", "a"]);
+        assert_eq!(
+            ids(&conn, ProxyLogQuery { errors_only: true, ..Default::default() }),
+            vec!["b"]
+        );
+        assert_eq!(
+            ids(&conn, ProxyLogQuery { from: Some(1_500), to: Some(2_500), ..Default::default() }),
+            vec!["b"]
+        );
+        assert_eq!(
+            ids(
+                &conn,
+                ProxyLogQuery { model: Some("gemini-3-flash".to_string()), ..Default::default() }
+            )
+            .len(),
+            3
+        );
+        assert!(ids(
+            &conn,
+            ProxyLogQuery { api_key_id: Some("other".to_string()), ..Default::default() }
+        )
+        .is_empty());
+    }
+
+    #[test]
+    fn full_text_search_is_literal() {
+        let conn = seeded();
+        let q = |text: &str| ProxyLogQuery { q: Some(text.to_string()), ..Default::default() };
+        assert_eq!(ids(&conn, q("haiku")), vec!["c", "b"]);
+        assert_eq!(ids(&conn, q("\"rust")), vec!["b"]);
+        assert_eq!(ids(&conn, q("forty-two")).len(), 3);
+        assert!(ids(&conn, q("AND OR")).is_empty());
+
+        conn.execute("DELETE FROM request_logs WHERE id = 'c'", []).unwrap();
+        assert_eq!(ids(&conn, q("haiku")), vec!["b"]);
+    }
+}
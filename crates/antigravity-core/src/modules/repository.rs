@@ -85,6 +85,8 @@ pub struct RequestLog {
     pub status_code: i32,
     /// Error type if request failed.
     pub error_type: Option<String>,
+    /// Client API key that made the request (None = master key).
+    pub api_key_id: Option<String>,
 }
 
 /// Account health metrics aggregated from request logs.
@@ -179,6 +181,14 @@ pub trait AccountRepository: Send + Sync {
     async fn get_account_health(&self, account_id: &str) -> RepoResult<AccountHealth>;
     /// Get recent events for account.
     async fn get_events(&self, account_id: &str, limit: i64) -> RepoResult<Vec<AccountEvent>>;
+    /// ID of the newest event across all accounts (0 when there are none).
+    async fn latest_event_id(&self) -> RepoResult<i64>;
+    /// Events across all accounts with an ID above `after_id`, oldest first.
+    async fn list_events_after(
+        &self,
+        after_id: i64,
+        limit: i64,
+    ) -> RepoResult<Vec<(i64, AccountEvent)>>;
 
     /// Update only token credentials (atomic, no read-modify-write).
     async fn update_token_credentials(
@@ -198,6 +208,9 @@ pub trait AccountRepository: Send + Sync {
     /// Update per-account proxy URL. Pass None to clear.
     async fn update_proxy_url(&self, account_id: &str, proxy_url: Option<&str>) -> RepoResult<()>;
 
+    /// Replace the account's group memberships.
+    async fn update_groups(&self, account_id: &str, groups: &[String]) -> RepoResult<()>;
+
     /// Disable an account (atomic, no read-modify-write).
     async fn set_account_disabled(
         &self,
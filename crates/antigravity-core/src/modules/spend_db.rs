@@ -0,0 +1,183 @@
+//! Spend rollups in the proxy SQLite database.
+//!
+//! One row per (period, period start, dimension, key). Writes add deltas, so several
+//! flushes of the same period accumulate instead of overwriting each other.
+
+use antigravity_types::models::{
+    SpendPeriod, UsageDimension, UsageQuery, UsageRollup, UsageTotals,
+};
+use rusqlite::{params, Connection};
+
+/// Rows returned by one query.
+const MAX_ROLLUPS: usize = 5000;
+
+pub(crate) fn init_schema(conn: &Connection) -> Result<(), String> {
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS usage_rollups (
+            period TEXT NOT NULL,
+            period_start INTEGER NOT NULL,
+            dimension TEXT NOT NULL,
+            key TEXT NOT NULL,
+            requests INTEGER NOT NULL DEFAULT 0,
+            input_tokens INTEGER NOT NULL DEFAULT 0,
+            output_tokens INTEGER NOT NULL DEFAULT 0,
+            cached_tokens INTEGER NOT NULL DEFAULT 0,
+            cost INTEGER NOT NULL DEFAULT 0,
+            PRIMARY KEY (period, period_start, dimension, key)
+        );",
+    )
+    .map_err(|err| err.to_string())
+}
+
+fn add_rollups_with(conn: &Connection, rows: &[UsageRollup]) -> Result<(), String> {
+    let tx = conn.unchecked_transaction().map_err(|err| err.to_string())?;
+    {
+        let mut stmt = tx
+            .prepare_cached(
+                "INSERT INTO usage_rollups (period, period_start, dimension, key, requests,
+                    input_tokens, output_tokens, cached_tokens, cost)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)
+                 ON CONFLICT (period, period_start, dimension, key) DO UPDATE SET
+                    requests = requests + excluded.requests,
+                    input_tokens = input_tokens + excluded.input_tokens,
+                    output_tokens = output_tokens + excluded.output_tokens,
+                    cached_tokens = cached_tokens + excluded.cached_tokens,
+                    cost = cost + excluded.cost",
+            )
+            .map_err(|err| err.to_string())?;
+        for row in rows {
+            let t = &row.totals;
+            stmt.execute(params![
+                row.period.as_str(),
+                row.period_start,
+                row.dimension.as_str(),
+                row.key,
+                t.requests,
+                t.input_tokens,
+                t.output_tokens,
+                t.cached_tokens,
+                t.cost,
+            ])
+            .map_err(|err| err.to_string())?;
+        }
+    }
+    tx.commit().map_err(|err| err.to_string())
+}
+
+fn row_to_rollup(row: &rusqlite::Row<'_>) -> rusqlite::Result<Option<UsageRollup>> {
+    let period: String = row.get(0)?;
+    let dimension: String = row.get(2)?;
+    let (Some(period), Some(dimension)) =
+        (SpendPeriod::parse(&period), UsageDimension::parse(&dimension))
+    else {
+        return Ok(None);
+    };
+    Ok(Some(UsageRollup {
+        period,
+        period_start: row.get(1)?,
+        dimension,
+        key: row.get(3)?,
+        totals: UsageTotals {
+            requests: row.get(4)?,
+            input_tokens: row.get(5)?,
+            output_tokens: row.get(6)?,
+            cached_tokens: row.get(7)?,
+            cost: row.get(8)?,
+        },
+    }))
+}
+
+fn query_rollups_with(conn: &Connection, query: &UsageQuery) -> Result<Vec<UsageRollup>, String> {
+    let mut sql = "SELECT period, period_start, dimension, key, requests, input_tokens,
+                   output_tokens, cached_tokens, cost FROM usage_rollups WHERE period = ?"
+        .to_string();
+    let mut args: Vec<Box<dyn rusqlite::ToSql>> = vec![Box::new(query.period.as_str())];
+
+    if let Some(dimension) = query.dimension {
+        sql.push_str(" AND dimension = ?");
+        args.push(Box::new(dimension.as_str()));
+    }
+    if let Some(key) = &query.key {
+        sql.push_str(" AND key = ?");
+        args.push(Box::new(key.clone()));
+    }
+    if let Some(from) = query.from {
+        sql.push_str(" AND period_start >= ?");
+        args.push(Box::new(from));
+    }
+    if let Some(to) = query.to {
+        sql.push_str(" AND period_start <= ?");
+        args.push(Box::new(to));
+    }
+    sql.push_str(" ORDER BY period_start DESC, cost DESC, requests DESC LIMIT ?");
+    args.push(Box::new(MAX_ROLLUPS as i64));
+
+    let mut stmt = conn.prepare(&sql).map_err(|err| err.to_string())?;
+    let rows = stmt
+        .query_map(rusqlite::params_from_iter(args.iter().map(|a| a.as_ref())), row_to_rollup)
+        .map_err(|err| err.to_string())?;
+    let mut rollups = Vec::new();
+    for row in rows {
+        if let Some(rollup) = row.map_err(|err| err.to_string())? {
+            rollups.push(rollup);
+        }
+    }
+    Ok(rollups)
+}
+
+pub async fn add_rollups(rows: Vec<UsageRollup>) -> Result<(), String> {
+    tokio::task::spawn_blocking(move || {
+        super::proxy_db::with_connection(|conn| add_rollups_with(conn, &rows))
+    })
+    .await
+    .map_err(|e| format!("spawn_blocking panicked: {e}"))?
+}
+
+pub async fn query_rollups(query: UsageQuery) -> Result<Vec<UsageRollup>, String> {
+    tokio::task::spawn_blocking(move || {
+        super::proxy_db::with_connection(|conn| query_rollups_with(conn, &query))
+    })
+    .await
+    .map_err(|e| format!("spawn_blocking panicked: {e}"))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rollup(dimension: UsageDimension, key: &str, cost: u64) -> UsageRollup {
+        UsageRollup {
+            period: SpendPeriod::Daily,
+            period_start: 86_400,
+            dimension,
+            key: key.to_string(),
+            totals: UsageTotals { requests: 1, input_tokens: 10, cost, ..Default::default() },
+        }
+    }
+
+    #[test]
+    fn deltas_accumulate_per_row() {
+        let conn = Connection::open_in_memory().unwrap();
+        init_schema(&conn).unwrap();
+        add_rollups_with(
+            &conn,
+            &[rollup(UsageDimension::Model, "m1", 100), rollup(UsageDimension::ApiKey, "k1", 100)],
+        )
+        .unwrap();
+        add_rollups_with(&conn, &[rollup(UsageDimension::Model, "m1", 50)]).unwrap();
+
+        let query = UsageQuery {
+            period: SpendPeriod::Daily,
+            dimension: Some(UsageDimension::Model),
+            ..Default::default()
+        };
+        let rows = query_rollups_with(&conn, &query).unwrap();
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].totals.requests, 2);
+        assert_eq!(rows[0].totals.input_tokens, 20);
+        assert_eq!(rows[0].totals.cost, 150);
+
+        let monthly = UsageQuery { period: SpendPeriod::Monthly, ..Default::default() };
+        assert!(query_rollups_with(&conn, &monthly).unwrap().is_empty());
+    }
+}
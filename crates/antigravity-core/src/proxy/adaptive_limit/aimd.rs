@@ -64,3 +64,52 @@ impl ProbeStrategy {
         matches!(self, ProbeStrategy::CheapProbe)
     }
 }
+
+/// Why a request got a second attempt on another account.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HedgeReason {
+    /// The account is close to its AIMD limit
+    Usage,
+    /// No first byte within the model's p95 time to first byte
+    SlowFirstByte,
+}
+
+impl HedgeReason {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Usage => "usage",
+            Self::SlowFirstByte => "slow_first_byte",
+        }
+    }
+}
+
+/// When to fire a hedge if the first attempt has not answered yet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HedgePlan {
+    pub delay: std::time::Duration,
+    pub reason: HedgeReason,
+}
+
+/// How a hedged request ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HedgeOutcome {
+    /// The first attempt answered first; the hedge was cancelled
+    PrimaryWon,
+    /// The hedge answered first; the first attempt was cancelled
+    SecondaryWon,
+    /// Neither attempt succeeded
+    BothFailed,
+    /// No other account was free to take the hedge
+    NoAccount,
+}
+
+impl HedgeOutcome {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::PrimaryWon => "primary_won",
+            Self::SecondaryWon => "secondary_won",
+            Self::BothFailed => "both_failed",
+            Self::NoAccount => "no_account",
+        }
+    }
+}
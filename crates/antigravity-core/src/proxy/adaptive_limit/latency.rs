@@ -0,0 +1,48 @@
+// Percentile index math on a window of at most WINDOW_SIZE samples.
+#![allow(
+    clippy::cast_precision_loss,
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss,
+    clippy::as_conversions,
+    reason = "Percentile rank over a small bounded window"
+)]
+
+use std::collections::VecDeque;
+use std::time::Duration;
+
+/// Samples kept per model; older ones are dropped first.
+const WINDOW_SIZE: usize = 200;
+
+/// Recent times to first byte (response headers) for one model.
+#[derive(Debug, Default)]
+pub struct FirstByteWindow {
+    samples: VecDeque<Duration>,
+}
+
+impl FirstByteWindow {
+    pub fn record(&mut self, elapsed: Duration) {
+        if self.samples.len() == WINDOW_SIZE {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(elapsed);
+    }
+
+    pub fn len(&self) -> usize {
+        self.samples.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.samples.is_empty()
+    }
+
+    /// Nearest-rank percentile, `quantile` in `0.0..=1.0`.
+    pub fn percentile(&self, quantile: f64) -> Option<Duration> {
+        if self.samples.is_empty() {
+            return None;
+        }
+        let mut sorted: Vec<Duration> = self.samples.iter().copied().collect();
+        sorted.sort_unstable();
+        let rank = (quantile.clamp(0.0, 1.0) * sorted.len() as f64).ceil() as usize;
+        sorted.get(rank.saturating_sub(1)).copied()
+    }
+}
@@ -1,17 +1,60 @@
-use super::aimd::{AIMDController, ProbeStrategy};
+use super::aimd::{AIMDController, HedgeOutcome, HedgePlan, HedgeReason, ProbeStrategy};
+use super::latency::FirstByteWindow;
 use super::stats::AimdAccountStats;
 use super::tracker::AdaptiveLimitTracker;
+use crate::proxy::cluster::{Cluster, ClusterEvent, ClusterSlot};
+use antigravity_types::models::HedgingConfig;
 use dashmap::DashMap;
+use parking_lot::{Mutex, RwLock};
+use std::sync::Arc;
+use std::time::Duration;
 
 pub struct AdaptiveLimitManager {
     trackers: DashMap<String, AdaptiveLimitTracker>,
     safety_margin: f64,
     aimd: AIMDController,
+    cluster: ClusterSlot,
+    hedging: RwLock<HedgingConfig>,
+    /// Time to first byte per model, for the slow-first-byte hedge threshold
+    first_byte: DashMap<String, Mutex<FirstByteWindow>>,
 }
 
 impl AdaptiveLimitManager {
     pub fn new(safety_margin: f64, aimd: AIMDController) -> Self {
-        Self { trackers: DashMap::new(), safety_margin, aimd }
+        Self {
+            trackers: DashMap::new(),
+            safety_margin,
+            aimd,
+            cluster: ClusterSlot::default(),
+            hedging: RwLock::new(HedgingConfig::default()),
+            first_byte: DashMap::new(),
+        }
+    }
+
+    pub fn set_hedging_config(&self, config: HedgingConfig) {
+        *self.hedging.write() = config;
+    }
+
+    pub fn hedging_config(&self) -> HedgingConfig {
+        self.hedging.read().clone()
+    }
+
+    /// Share limit changes with the other instances.
+    pub fn set_cluster(&self, cluster: Arc<Cluster>) {
+        self.cluster.set(cluster);
+    }
+
+    fn publish_limit(&self, account_id: &str, tracker: &AdaptiveLimitTracker) {
+        self.cluster.publish(ClusterEvent::AimdLimit {
+            account_id: account_id.to_string(),
+            confirmed_limit: tracker.confirmed_limit(),
+            ceiling: tracker.ceiling(),
+        });
+    }
+
+    /// Apply a limit calibrated by another instance.
+    pub fn apply_remote_limit(&self, account_id: &str, confirmed_limit: u64, ceiling: u64) {
+        self.get_or_create(account_id).apply_limit(confirmed_limit, ceiling);
     }
 
     pub fn get_or_create(
@@ -59,20 +102,93 @@ impl AdaptiveLimitManager {
         self.get_or_create(account_id).probe_strategy()
     }
 
+    pub fn record_first_byte(&self, model: &str, elapsed: Duration) {
+        self.first_byte.entry(model.to_string()).or_default().lock().record(elapsed);
+    }
+
+    /// Time-to-first-byte percentile for `model`, once it has `min_samples` samples.
+    pub fn first_byte_percentile(
+        &self,
+        model: &str,
+        quantile: f64,
+        min_samples: usize,
+    ) -> Option<Duration> {
+        let window = self.first_byte.get(model)?;
+        let window = window.lock();
+        if window.len() < min_samples {
+            return None;
+        }
+        window.percentile(quantile)
+    }
+
+    /// When to hedge a request on `account_id` for `model`, if at all.
+    ///
+    /// Near-limit accounts are hedged immediately (`ImmediateHedge`) or after the median
+    /// first byte (`DelayedHedge`); any other request once the p95 first byte has passed.
+    pub fn hedge_plan(&self, account_id: &str, model: &str) -> Option<HedgePlan> {
+        let config = self.hedging.read().clone();
+        if !config.enabled {
+            return None;
+        }
+        let min_delay = Duration::from_millis(config.min_delay_ms);
+        let percentile = |quantile| {
+            self.first_byte_percentile(model, quantile, config.min_samples)
+                .map(|d| d.max(min_delay))
+        };
+        match self.probe_strategy(account_id) {
+            ProbeStrategy::ImmediateHedge => {
+                Some(HedgePlan { delay: Duration::ZERO, reason: HedgeReason::Usage })
+            },
+            ProbeStrategy::DelayedHedge => Some(HedgePlan {
+                delay: percentile(0.50).unwrap_or(min_delay),
+                reason: HedgeReason::Usage,
+            }),
+            ProbeStrategy::None | ProbeStrategy::CheapProbe => percentile(0.95)
+                .map(|delay| HedgePlan { delay, reason: HedgeReason::SlowFirstByte }),
+        }
+    }
+
+    /// Record a hedged request: `secondary` is the account the hedge went to, if one
+    /// was found. The cancelled leg still counts against its account's minute usage.
+    pub fn record_hedge(&self, primary: &str, secondary: Option<&str>, outcome: HedgeOutcome) {
+        let Some(secondary) = secondary else {
+            return;
+        };
+        self.get_or_create(primary).record_hedge(outcome == HedgeOutcome::SecondaryWon);
+        match outcome {
+            HedgeOutcome::PrimaryWon => self.get_or_create(secondary).record_cancelled(),
+            HedgeOutcome::SecondaryWon => self.get_or_create(primary).record_cancelled(),
+            HedgeOutcome::BothFailed | HedgeOutcome::NoAccount => {},
+        }
+    }
+
     pub fn record_success(&self, account_id: &str) {
-        self.get_or_create(account_id).record_success();
+        let tracker = self.get_or_create(account_id);
+        let before = tracker.confirmed_limit();
+        tracker.record_success();
+        if tracker.confirmed_limit() != before {
+            self.publish_limit(account_id, &tracker);
+        }
     }
 
     pub fn record_429(&self, account_id: &str) {
-        self.get_or_create(account_id).record_429();
+        let tracker = self.get_or_create(account_id);
+        tracker.record_429();
+        self.publish_limit(account_id, &tracker);
     }
 
     pub fn record_error(&self, account_id: &str, status_code: u16) {
-        self.get_or_create(account_id).record_error(status_code);
+        let tracker = self.get_or_create(account_id);
+        tracker.record_error(status_code);
+        if status_code == 429 {
+            self.publish_limit(account_id, &tracker);
+        }
     }
 
     pub fn force_expand(&self, account_id: &str) {
-        self.get_or_create(account_id).force_expand();
+        let tracker = self.get_or_create(account_id);
+        tracker.force_expand();
+        self.publish_limit(account_id, &tracker);
     }
 
     pub fn should_allow(&self, account_id: &str) -> bool {
@@ -99,6 +215,8 @@ impl AdaptiveLimitManager {
                 requests_this_minute: entry.value().requests_this_minute(),
                 working_threshold: entry.value().working_threshold(),
                 usage_ratio: entry.value().usage_ratio(),
+                hedges_fired: entry.value().hedges_fired(),
+                hedges_won: entry.value().hedges_won(),
             })
             .collect()
     }
@@ -1,9 +1,11 @@
 mod aimd;
+mod latency;
 mod manager;
 mod stats;
 mod tracker;
 
-pub use aimd::{AIMDController, ProbeStrategy};
+pub use aimd::{AIMDController, HedgeOutcome, HedgePlan, HedgeReason, ProbeStrategy};
+pub use latency::FirstByteWindow;
 pub use manager::AdaptiveLimitManager;
 pub use stats::AimdAccountStats;
 pub use tracker::AdaptiveLimitTracker;
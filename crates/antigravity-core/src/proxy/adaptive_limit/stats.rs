@@ -8,4 +8,8 @@ pub struct AimdAccountStats {
     pub requests_this_minute: u64,
     pub working_threshold: u64,
     pub usage_ratio: f64,
+    /// Requests on this account that got a second attempt elsewhere
+    pub hedges_fired: u64,
+    /// Hedges that answered before this account did
+    pub hedges_won: u64,
 }
@@ -1,9 +1,11 @@
 //! Tests for adaptive rate limiting
 
 use super::*;
+use antigravity_types::models::HedgingConfig;
 use std::sync::atomic::Ordering;
 use std::sync::Arc;
 use std::thread;
+use std::time::Duration;
 
 #[test]
 fn test_aimd_reward() {
@@ -210,3 +212,64 @@ fn test_concurrent_get_or_create_no_overwrite() {
 
     assert_eq!(manager.len(), 1);
 }
+
+#[test]
+fn test_first_byte_percentiles() {
+    let mut window = FirstByteWindow::default();
+    assert_eq!(window.percentile(0.95), None);
+    for ms in 1..=100 {
+        window.record(Duration::from_millis(ms));
+    }
+    assert_eq!(window.percentile(0.50), Some(Duration::from_millis(50)));
+    assert_eq!(window.percentile(0.95), Some(Duration::from_millis(95)));
+}
+
+fn hedging_manager() -> AdaptiveLimitManager {
+    let manager = AdaptiveLimitManager::default();
+    manager.set_hedging_config(HedgingConfig { enabled: true, min_samples: 5, min_delay_ms: 10 });
+    manager
+}
+
+#[test]
+fn test_hedge_plan_follows_latency_and_usage() {
+    let manager = hedging_manager();
+    assert_eq!(manager.hedge_plan("acc", "gemini-3-pro"), None);
+
+    for ms in 1..=10 {
+        manager.record_first_byte("gemini-3-pro", Duration::from_millis(ms * 100));
+    }
+    assert_eq!(
+        manager.hedge_plan("acc", "gemini-3-pro"),
+        Some(HedgePlan { delay: Duration::from_millis(1000), reason: HedgeReason::SlowFirstByte })
+    );
+
+    // Default threshold is 12 requests per minute: 11 is DelayedHedge, 12 ImmediateHedge
+    for _ in 0..11 {
+        manager.record_success("acc");
+    }
+    assert_eq!(
+        manager.hedge_plan("acc", "gemini-3-pro"),
+        Some(HedgePlan { delay: Duration::from_millis(500), reason: HedgeReason::Usage })
+    );
+    manager.record_success("acc");
+    assert_eq!(
+        manager.hedge_plan("acc", "gemini-3-pro").map(|plan| plan.delay),
+        Some(Duration::ZERO)
+    );
+
+    manager.set_hedging_config(HedgingConfig::default());
+    assert_eq!(manager.hedge_plan("acc", "gemini-3-pro"), None);
+}
+
+#[test]
+fn test_record_hedge_counts_outcome_and_cancelled_leg() {
+    let manager = hedging_manager();
+    manager.record_hedge("slow", Some("fast"), HedgeOutcome::SecondaryWon);
+    manager.record_hedge("slow", Some("other"), HedgeOutcome::PrimaryWon);
+    manager.record_hedge("slow", None, HedgeOutcome::NoAccount);
+
+    let slow = manager.get("slow").unwrap();
+    assert_eq!((slow.hedges_fired(), slow.hedges_won()), (2, 1));
+    assert_eq!(slow.requests_this_minute(), 1);
+    assert_eq!(manager.get("other").unwrap().requests_this_minute(), 1);
+}
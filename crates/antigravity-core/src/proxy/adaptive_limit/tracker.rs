@@ -25,6 +25,8 @@ pub struct AdaptiveLimitTracker {
     minute_started_at: RwLock<Instant>,
     last_calibration: RwLock<Instant>,
     consecutive_above_threshold: AtomicU64,
+    hedges_fired: AtomicU64,
+    hedges_won: AtomicU64,
     safety_margin: f64,
     aimd: AIMDController,
     limit_update_lock: Mutex<()>,
@@ -43,6 +45,8 @@ impl AdaptiveLimitTracker {
                 Instant::now().checked_sub(Duration::from_secs(3600)).unwrap_or_else(Instant::now),
             ),
             consecutive_above_threshold: AtomicU64::new(0),
+            hedges_fired: AtomicU64::new(0),
+            hedges_won: AtomicU64::new(0),
             safety_margin,
             aimd,
             limit_update_lock: Mutex::new(()),
@@ -177,6 +181,31 @@ impl AdaptiveLimitTracker {
         }
     }
 
+    /// Count a request that was cancelled after reaching upstream (a hedge loser).
+    ///
+    /// It still used quota, but says nothing about the limit, so it does not feed the
+    /// reward logic.
+    pub fn record_cancelled(&self) {
+        self.maybe_reset_minute();
+        self.requests_this_minute.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// A request on this account got a hedge; `won` when the hedge answered first.
+    pub fn record_hedge(&self, won: bool) {
+        self.hedges_fired.fetch_add(1, Ordering::Relaxed);
+        if won {
+            self.hedges_won.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    pub fn hedges_fired(&self) -> u64 {
+        self.hedges_fired.load(Ordering::Relaxed)
+    }
+
+    pub fn hedges_won(&self) -> u64 {
+        self.hedges_won.load(Ordering::Relaxed)
+    }
+
     pub fn record_error(&self, status_code: u16) {
         self.consecutive_above_threshold.store(0, Ordering::Relaxed);
 
@@ -250,6 +279,20 @@ impl AdaptiveLimitTracker {
         self.expand_limit();
     }
 
+    /// Adopt a limit calibrated elsewhere (another instance).
+    pub fn apply_limit(&self, confirmed_limit: u64, ceiling: u64) {
+        let _lock = self.limit_update_lock.lock().unwrap_or_else(|e| e.into_inner());
+        let confirmed_limit = confirmed_limit.max(self.aimd.min_limit).min(self.aimd.max_limit);
+        self.confirmed_limit.store(confirmed_limit, Ordering::Relaxed);
+        self.working_threshold
+            .store((confirmed_limit as f64 * self.safety_margin) as u64, Ordering::Relaxed);
+        self.ceiling.store(ceiling.max(confirmed_limit), Ordering::Relaxed);
+        match self.last_calibration.write() {
+            Ok(mut guard) => *guard = Instant::now(),
+            Err(poisoned) => *poisoned.into_inner() = Instant::now(),
+        }
+    }
+
     pub fn to_persisted(&self) -> (u64, u64, u64) {
         let elapsed = self.last_calibration.read().map(|t| t.elapsed().as_secs()).unwrap_or(3600);
         (
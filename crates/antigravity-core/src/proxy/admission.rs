@@ -0,0 +1,247 @@
+//! Admission queue in front of account selection.
+//!
+//! When no account can take a request (all rate-limited, quota-protected or over their
+//! AIMD limit), `TokenManager::get_token_with_exclusions` parks the request here instead
+//! of failing. Waiting requests are split into priority classes: a batch request is only
+//! retried while no interactive request is waiting. Each class has a bounded depth and a
+//! maximum wait, and a request whose predicted wait (from the accounts' rate limit reset
+//! times) exceeds its remaining deadline is rejected right away.
+//!
+//! The admission middleware runs every request inside a [`Ticket`] scope, which carries
+//! the priority class in and the queueing outcome back out.
+
+use antigravity_types::models::{AdmissionConfig, PriorityClass};
+use parking_lot::{Mutex, RwLock};
+use std::future::Future;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::watch;
+
+use crate::proxy::prometheus;
+
+/// How often a waiting request re-checks the pool when no reset time is known.
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Why a queued request was turned away.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rejection {
+    pub message: String,
+    /// Seconds until an account is expected to free up, if known
+    pub retry_after: Option<u64>,
+}
+
+struct TicketState {
+    priority: PriorityClass,
+    queued: watch::Sender<bool>,
+    rejection: Mutex<Option<Rejection>>,
+}
+
+/// Per-request admission state shared between the middleware and account selection.
+#[derive(Clone)]
+pub struct Ticket(Arc<TicketState>);
+
+impl Ticket {
+    pub fn new(priority: PriorityClass) -> Self {
+        let (queued, _) = watch::channel(false);
+        Self(Arc::new(TicketState { priority, queued, rejection: Mutex::new(None) }))
+    }
+
+    pub fn priority(&self) -> PriorityClass {
+        self.0.priority
+    }
+
+    /// Resolves once the request has entered the queue.
+    pub async fn queued(&self) {
+        let mut rx = self.0.queued.subscribe();
+        let _ = rx.wait_for(|queued| *queued).await;
+    }
+
+    pub fn rejection(&self) -> Option<Rejection> {
+        self.0.rejection.lock().clone()
+    }
+}
+
+tokio::task_local! {
+    static TICKET: Ticket;
+}
+
+/// Run `future` as the request `ticket` belongs to.
+pub async fn scope<F: Future>(ticket: Ticket, future: F) -> F::Output {
+    TICKET.scope(ticket, future).await
+}
+
+/// Priority class of the current request; interactive outside a ticket scope.
+pub fn current_priority() -> PriorityClass {
+    TICKET.try_with(Ticket::priority).unwrap_or_default()
+}
+
+fn with_ticket(f: impl FnOnce(&Ticket)) {
+    let _ = TICKET.try_with(f);
+}
+
+/// Bounded, per-class wait queue. Account selection decides when to enter it.
+pub struct AdmissionQueue {
+    config: RwLock<AdmissionConfig>,
+    waiting: [AtomicUsize; 2],
+}
+
+impl Default for AdmissionQueue {
+    fn default() -> Self {
+        Self { config: RwLock::new(AdmissionConfig::default()), waiting: Default::default() }
+    }
+}
+
+impl AdmissionQueue {
+    pub fn set_config(&self, config: AdmissionConfig) {
+        *self.config.write() = config;
+    }
+
+    pub fn config(&self) -> AdmissionConfig {
+        self.config.read().clone()
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.config.read().enabled
+    }
+
+    /// Requests currently waiting in `priority`'s class.
+    pub fn depth(&self, priority: PriorityClass) -> usize {
+        self.counter(priority).load(Ordering::Acquire)
+    }
+
+    fn counter(&self, priority: PriorityClass) -> &AtomicUsize {
+        match priority {
+            PriorityClass::Interactive => &self.waiting[0],
+            PriorityClass::Batch => &self.waiting[1],
+        }
+    }
+
+    /// Take a place in the current request's class, or reject it when the class is full.
+    pub fn enter(&self, retry_after: Option<u64>) -> Result<QueueSlot<'_>, String> {
+        let priority = current_priority();
+        let class = self.config.read().class(priority).clone();
+        let counter = self.counter(priority);
+        let admitted = counter
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |depth| {
+                (depth < class.max_depth).then_some(depth + 1)
+            })
+            .is_ok();
+        if !admitted {
+            prometheus::record_admission_outcome(priority.as_str(), "rejected_full");
+            return Err(self.reject(
+                format!(
+                    "Admission queue full ({} {} requests waiting)",
+                    class.max_depth,
+                    priority.as_str()
+                ),
+                retry_after,
+            ));
+        }
+
+        prometheus::record_admission_queued(priority.as_str());
+        prometheus::update_admission_depth(priority.as_str(), self.depth(priority));
+        with_ticket(|ticket| {
+            ticket.0.queued.send_replace(true);
+        });
+        tracing::debug!("[Admission] Queued {} request", priority.as_str());
+        Ok(QueueSlot {
+            queue: self,
+            priority,
+            entered: Instant::now(),
+            deadline: Instant::now() + Duration::from_secs(class.max_wait_secs),
+            outcome: "cancelled",
+        })
+    }
+
+    fn reject(&self, message: String, retry_after: Option<u64>) -> String {
+        tracing::warn!("[Admission] {}", message);
+        with_ticket(|ticket| {
+            *ticket.0.rejection.lock() = Some(Rejection { message: message.clone(), retry_after });
+        });
+        message
+    }
+}
+
+/// A request's place in the queue; leaving it (by drop) updates the depth gauge.
+pub struct QueueSlot<'a> {
+    queue: &'a AdmissionQueue,
+    priority: PriorityClass,
+    entered: Instant,
+    deadline: Instant,
+    outcome: &'static str,
+}
+
+impl QueueSlot<'_> {
+    /// Wait until the pool is worth checking again.
+    ///
+    /// `reset_in` is the predicted time until an account frees up; `None` when some
+    /// account is only busy (concurrency or AIMD) and could free up at any moment.
+    /// Errors when the deadline has passed or the predicted wait cannot fit in it.
+    pub async fn wait(&mut self, reset_in: Option<Duration>) -> Result<(), String> {
+        let remaining = self.deadline.saturating_duration_since(Instant::now());
+        let retry_after = reset_in.map(|d| d.as_secs().max(1));
+        if remaining.is_zero() {
+            self.outcome = "timeout";
+            return Err(self.queue.reject(
+                format!("No account became available within {}s", self.max_wait().as_secs()),
+                retry_after,
+            ));
+        }
+        if let Some(reset_in) = reset_in.filter(|d| *d > remaining) {
+            self.outcome = "deadline";
+            return Err(self.queue.reject(
+                format!(
+                    "Next account frees up in {}s, beyond the {}s queue deadline",
+                    reset_in.as_secs(),
+                    remaining.as_secs()
+                ),
+                retry_after,
+            ));
+        }
+
+        let mut sleep = reset_in.unwrap_or(POLL_INTERVAL).min(remaining);
+        loop {
+            tokio::time::sleep(sleep).await;
+            // Batch requests yield while interactive ones are waiting
+            let yield_to_interactive = self.priority == PriorityClass::Batch
+                && self.queue.depth(PriorityClass::Interactive) > 0;
+            if !yield_to_interactive || Instant::now() >= self.deadline {
+                return Ok(());
+            }
+            sleep = POLL_INTERVAL.min(self.deadline.saturating_duration_since(Instant::now()));
+        }
+    }
+
+    /// Record that the request got an account.
+    pub fn admitted(mut self) {
+        self.outcome = "admitted";
+        prometheus::record_admission_wait(self.priority.as_str(), self.entered.elapsed());
+        tracing::debug!(
+            "[Admission] {} request admitted after {}ms",
+            self.priority.as_str(),
+            self.entered.elapsed().as_millis()
+        );
+    }
+
+    /// Record that selection failed for a reason queueing cannot fix.
+    pub fn abandon(mut self) {
+        self.outcome = "failed";
+    }
+
+    fn max_wait(&self) -> Duration {
+        self.deadline.saturating_duration_since(self.entered)
+    }
+}
+
+impl Drop for QueueSlot<'_> {
+    fn drop(&mut self) {
+        let counter = self.queue.counter(self.priority);
+        let _ = counter.fetch_update(Ordering::AcqRel, Ordering::Acquire, |d| d.checked_sub(1));
+        prometheus::update_admission_depth(self.priority.as_str(), self.queue.depth(self.priority));
+        prometheus::record_admission_outcome(self.priority.as_str(), self.outcome);
+        with_ticket(|ticket| {
+            ticket.0.queued.send_replace(false);
+        });
+    }
+}
@@ -0,0 +1,313 @@
+//! Multi-tenant client API keys.
+//!
+//! Keys are held in memory (indexed by secret hash) for authentication on the hot path.
+//! Usage counters are updated in memory; the increments are periodically added to the
+//! [`ApiKeyStore`] and the totals reloaded, so instances sharing a store add up.
+
+use crate::modules::api_key_store::ApiKeyStore;
+use antigravity_types::models::{
+    ApiKey, ApiKeyUsage, CreateApiKeyRequest, CreatedApiKey, PriorityClass, UpdateApiKeyRequest,
+};
+use dashmap::DashMap;
+use rand::Rng;
+use sha2::{Digest, Sha256};
+use std::sync::Arc;
+use std::time::Duration;
+
+const SECRET_PREFIX: &str = "sk-ag-";
+const SECRET_RANDOM_LEN: usize = 40;
+const DISPLAY_PREFIX_LEN: usize = 12;
+const USAGE_FLUSH_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Identity of the client key that authenticated a request.
+///
+/// Inserted into request and response extensions by the auth middleware.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClientKey {
+    pub id: String,
+    pub label: String,
+    /// Account group the key is restricted to
+    pub account_group: Option<String>,
+    pub priority: PriorityClass,
+}
+
+/// Why a client key was not accepted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ApiKeyRejection {
+    Unknown,
+    Disabled,
+    Expired,
+    BudgetExhausted,
+    ModelNotAllowed(String),
+    /// The key has a model allowlist but the request names no model
+    ModelRequired,
+}
+
+impl ApiKeyRejection {
+    pub fn message(&self) -> String {
+        match self {
+            Self::Unknown => "Invalid API key".to_string(),
+            Self::Disabled => "API key is disabled".to_string(),
+            Self::Expired => "API key has expired".to_string(),
+            Self::BudgetExhausted => "API key budget exhausted for the current period".to_string(),
+            Self::ModelNotAllowed(model) => {
+                format!("Model '{}' is not allowed for this API key", model)
+            },
+            Self::ModelRequired => "This API key requires the request to name a model".to_string(),
+        }
+    }
+}
+
+/// In-memory registry of client API keys backed by an [`ApiKeyStore`].
+pub struct ApiKeyRegistry {
+    store: ApiKeyStore,
+    /// secret hash -> key
+    keys: DashMap<String, ApiKey>,
+    /// Usage recorded per key ID since the last flush
+    pending: DashMap<String, ApiKeyUsage>,
+}
+
+impl ApiKeyRegistry {
+    pub fn new(store: ApiKeyStore) -> Self {
+        Self { store, keys: DashMap::new(), pending: DashMap::new() }
+    }
+
+    /// Load keys from the store, replacing the in-memory set.
+    ///
+    /// Unflushed increments are added on top of the stored usage.
+    pub async fn reload(&self) -> Result<usize, String> {
+        let mut loaded = self.store.list().await?;
+        let count = loaded.len();
+        for key in &mut loaded {
+            if let Some(delta) = self.pending.get(&key.id) {
+                key.usage.add(&delta);
+            }
+            if let Some(current) = self.find_by_id(&key.id) {
+                key.last_used_at = key.last_used_at.max(current.last_used_at);
+            }
+        }
+        self.keys.retain(|hash, _| loaded.iter().any(|k| &k.key_hash == hash));
+        for key in loaded {
+            self.keys.insert(key.key_hash.clone(), key);
+        }
+        Ok(count)
+    }
+
+    /// Whether any client keys are configured.
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// All keys, secrets redacted, ordered by creation time.
+    pub fn list(&self) -> Vec<ApiKey> {
+        let mut keys: Vec<ApiKey> = self.keys.iter().map(|e| e.value().redacted()).collect();
+        keys.sort_by_key(|k| k.created_at);
+        keys
+    }
+
+    pub fn get(&self, id: &str) -> Option<ApiKey> {
+        self.find_by_id(id).map(|k| k.redacted())
+    }
+
+    /// Validate a presented secret. Does not check the model allowlist.
+    pub fn authenticate(&self, secret: &str) -> Result<ApiKey, ApiKeyRejection> {
+        let now = chrono::Utc::now().timestamp();
+        let mut entry = self.keys.get_mut(&hash_secret(secret)).ok_or(ApiKeyRejection::Unknown)?;
+        let key = entry.value_mut();
+        if !key.enabled {
+            return Err(ApiKeyRejection::Disabled);
+        }
+        if key.is_expired(now) {
+            return Err(ApiKeyRejection::Expired);
+        }
+        if key.usage.roll_period(key.budget.period, now) {
+            // Persist the reset even if the key sees no usage this period
+            self.pending.entry(key.id.clone()).or_default().roll_period(key.budget.period, now);
+        }
+        if key.budget_exhausted() {
+            return Err(ApiKeyRejection::BudgetExhausted);
+        }
+        Ok(key.clone())
+    }
+
+    /// Count a completed request and its tokens against the key's budget.
+    pub fn record_usage(&self, id: &str, tokens: u64) {
+        let now = chrono::Utc::now().timestamp();
+        for mut entry in self.keys.iter_mut() {
+            let key = entry.value_mut();
+            if key.id != id {
+                continue;
+            }
+            let delta = ApiKeyUsage {
+                requests: 1,
+                tokens,
+                period_start: key.budget.period.period_start(now),
+            };
+            key.usage.add(&delta);
+            key.last_used_at = Some(now);
+            self.pending.entry(key.id.clone()).or_default().add(&delta);
+            return;
+        }
+    }
+
+    /// Create a key. The returned secret is not stored and cannot be recovered.
+    pub async fn create(&self, request: CreateApiKeyRequest) -> Result<CreatedApiKey, String> {
+        let label = request.label.trim().to_string();
+        if label.is_empty() {
+            return Err("label must not be empty".to_string());
+        }
+        let account_group = normalize_group(request.account_group)?;
+        let secret = generate_secret();
+        let now = chrono::Utc::now().timestamp();
+        let mut key = ApiKey {
+            id: uuid::Uuid::new_v4().to_string(),
+            label,
+            key_prefix: secret.chars().take(DISPLAY_PREFIX_LEN).collect(),
+            key_hash: hash_secret(&secret),
+            enabled: true,
+            created_at: now,
+            expires_at: request.expires_at,
+            allowed_models: request.allowed_models,
+            budget: request.budget,
+            account_group,
+            priority: request.priority,
+            usage: Default::default(),
+            last_used_at: None,
+        };
+        key.usage.period_start = key.budget.period.period_start(now);
+
+        self.store.upsert(&key).await?;
+        self.keys.insert(key.key_hash.clone(), key.clone());
+        Ok(CreatedApiKey { key: key.redacted(), secret })
+    }
+
+    /// Apply a partial update. Returns None if the key does not exist.
+    pub async fn update(
+        &self,
+        id: &str,
+        update: UpdateApiKeyRequest,
+    ) -> Result<Option<ApiKey>, String> {
+        let Some(mut key) = self.find_by_id(id) else {
+            return Ok(None);
+        };
+        if let Some(label) = update.label {
+            key.label = label;
+        }
+        if let Some(enabled) = update.enabled {
+            key.enabled = enabled;
+        }
+        if let Some(expires_at) = update.expires_at {
+            key.expires_at = expires_at;
+        }
+        if let Some(allowed_models) = update.allowed_models {
+            key.allowed_models = allowed_models;
+        }
+        if let Some(budget) = update.budget {
+            key.budget = budget;
+        }
+        if let Some(account_group) = update.account_group {
+            key.account_group = normalize_group(account_group)?;
+        }
+        if let Some(priority) = update.priority {
+            key.priority = priority;
+        }
+
+        self.store.upsert(&key).await?;
+        if let Some(mut entry) = self.keys.get_mut(&key.key_hash) {
+            // Keep usage accumulated while the store write was in flight
+            key.usage = entry.usage;
+            key.last_used_at = entry.last_used_at;
+            *entry = key.clone();
+        }
+        Ok(Some(key.redacted()))
+    }
+
+    /// Zero the usage counters for the current period.
+    pub async fn reset_usage(&self, id: &str) -> Result<Option<ApiKey>, String> {
+        let Some(key) = self.find_by_id(id) else {
+            return Ok(None);
+        };
+        let now = chrono::Utc::now().timestamp();
+        let usage =
+            ApiKeyUsage { period_start: key.budget.period.period_start(now), ..Default::default() };
+
+        self.store.save_usage(id, usage, key.last_used_at).await?;
+        if let Some(mut entry) = self.keys.get_mut(&key.key_hash) {
+            entry.usage = usage;
+        }
+        self.pending.remove(id);
+        Ok(self.get(id))
+    }
+
+    /// Delete a key. Returns false if it did not exist.
+    pub async fn delete(&self, id: &str) -> Result<bool, String> {
+        let existed = self.store.delete(id).await?;
+        self.keys.retain(|_, k| k.id != id);
+        self.pending.remove(id);
+        Ok(existed)
+    }
+
+    /// Add the usage recorded since the last flush to the store.
+    pub async fn flush_usage(&self) {
+        let ids: Vec<String> = self.pending.iter().map(|e| e.key().clone()).collect();
+        for id in ids {
+            let Some((_, delta)) = self.pending.remove(&id) else {
+                continue;
+            };
+            let Some(key) = self.find_by_id(&id) else {
+                continue;
+            };
+            if let Err(e) = self.store.add_usage(&id, delta, key.last_used_at).await {
+                tracing::warn!("[ApiKeys] Failed to persist usage for {}: {}", key.label, e);
+                self.pending.entry(id).or_default().add(&delta);
+            }
+        }
+    }
+
+    /// Spawn a background task that flushes usage and picks up keys changed elsewhere
+    /// (CLI, other instances).
+    pub fn start_usage_flush(self: &Arc<Self>) -> tokio::task::JoinHandle<()> {
+        let registry = Arc::clone(self);
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(USAGE_FLUSH_INTERVAL);
+            interval.tick().await;
+            loop {
+                interval.tick().await;
+                registry.flush_usage().await;
+                if let Err(e) = registry.reload().await {
+                    tracing::warn!("[ApiKeys] Failed to reload keys: {}", e);
+                }
+            }
+        })
+    }
+
+    fn find_by_id(&self, id: &str) -> Option<ApiKey> {
+        self.keys.iter().find(|e| e.value().id == id).map(|e| e.value().clone())
+    }
+}
+
+/// SHA-256 hex digest of a client secret.
+pub fn hash_secret(secret: &str) -> String {
+    format!("{:x}", Sha256::digest(secret.as_bytes()))
+}
+
+/// Validated group name; an empty name means no restriction.
+fn normalize_group(group: Option<String>) -> Result<Option<String>, String> {
+    match group {
+        Some(group) => Ok(crate::modules::account::normalize_groups(&[group])?.into_iter().next()),
+        None => Ok(None),
+    }
+}
+
+fn generate_secret() -> String {
+    let random: String = rand::thread_rng()
+        .sample_iter(&rand::distributions::Alphanumeric)
+        .take(SECRET_RANDOM_LEN)
+        .map(char::from)
+        .collect();
+    format!("{}{}", SECRET_PREFIX, random)
+}
+
+#[cfg(test)]
+#[path = "api_keys_tests.rs"]
+mod api_keys_tests;
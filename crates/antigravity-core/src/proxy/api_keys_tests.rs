@@ -0,0 +1,120 @@
+use super::*;
+use antigravity_types::models::{ApiKeyBudget, BudgetPeriod};
+use tempfile::TempDir;
+
+fn registry() -> (ApiKeyRegistry, TempDir) {
+    let dir = TempDir::new().unwrap();
+    let store = ApiKeyStore::Json(dir.path().join("api_keys.json"));
+    (ApiKeyRegistry::new(store), dir)
+}
+
+fn request(label: &str) -> CreateApiKeyRequest {
+    CreateApiKeyRequest { label: label.to_string(), ..Default::default() }
+}
+
+#[tokio::test]
+async fn created_key_authenticates_and_secret_is_not_stored() {
+    let (registry, dir) = registry();
+    let created = registry.create(request("ci")).await.unwrap();
+
+    assert!(created.secret.starts_with(SECRET_PREFIX));
+    assert!(created.key.key_hash.is_empty());
+    assert_eq!(registry.authenticate(&created.secret).unwrap().id, created.key.id);
+    assert_eq!(registry.authenticate("sk-ag-wrong"), Err(ApiKeyRejection::Unknown));
+
+    let on_disk = std::fs::read_to_string(dir.path().join("api_keys.json")).unwrap();
+    assert!(!on_disk.contains(&created.secret));
+}
+
+#[tokio::test]
+async fn disabled_and_expired_keys_are_rejected() {
+    let (registry, _dir) = registry();
+    let created = registry.create(request("alice")).await.unwrap();
+
+    let update = UpdateApiKeyRequest { enabled: Some(false), ..Default::default() };
+    registry.update(&created.key.id, update).await.unwrap();
+    assert_eq!(registry.authenticate(&created.secret), Err(ApiKeyRejection::Disabled));
+
+    let update = UpdateApiKeyRequest {
+        enabled: Some(true),
+        expires_at: Some(Some(1)),
+        ..Default::default()
+    };
+    registry.update(&created.key.id, update).await.unwrap();
+    assert_eq!(registry.authenticate(&created.secret), Err(ApiKeyRejection::Expired));
+}
+
+#[tokio::test]
+async fn request_budget_is_enforced_and_resettable() {
+    let (registry, _dir) = registry();
+    let mut req = request("bob");
+    req.budget =
+        ApiKeyBudget { period: BudgetPeriod::Daily, max_requests: Some(2), max_tokens: None };
+    let created = registry.create(req).await.unwrap();
+
+    registry.record_usage(&created.key.id, 10);
+    assert!(registry.authenticate(&created.secret).is_ok());
+    registry.record_usage(&created.key.id, 10);
+    assert_eq!(registry.authenticate(&created.secret), Err(ApiKeyRejection::BudgetExhausted));
+
+    registry.reset_usage(&created.key.id).await.unwrap();
+    assert!(registry.authenticate(&created.secret).is_ok());
+}
+
+#[tokio::test]
+async fn usage_survives_flush_and_reload() {
+    let (registry, dir) = registry();
+    let created = registry.create(request("carol")).await.unwrap();
+    registry.record_usage(&created.key.id, 42);
+    registry.flush_usage().await;
+
+    let reloaded = ApiKeyRegistry::new(ApiKeyStore::Json(dir.path().join("api_keys.json")));
+    assert_eq!(reloaded.reload().await.unwrap(), 1);
+    let key = reloaded.get(&created.key.id).unwrap();
+    assert_eq!((key.usage.requests, key.usage.tokens), (1, 42));
+    assert!(reloaded.authenticate(&created.secret).is_ok());
+}
+
+#[tokio::test]
+async fn usage_from_registries_sharing_a_store_adds_up() {
+    let (first, dir) = registry();
+    let created = first.create(request("dave")).await.unwrap();
+    let second = ApiKeyRegistry::new(ApiKeyStore::Json(dir.path().join("api_keys.json")));
+    second.reload().await.unwrap();
+
+    first.record_usage(&created.key.id, 10);
+    second.record_usage(&created.key.id, 5);
+    second.record_usage(&created.key.id, 5);
+    first.flush_usage().await;
+    second.flush_usage().await;
+
+    first.reload().await.unwrap();
+    let key = first.get(&created.key.id).unwrap();
+    assert_eq!((key.usage.requests, key.usage.tokens), (3, 20));
+}
+
+#[tokio::test]
+async fn delete_removes_key() {
+    let (registry, _dir) = registry();
+    let created = registry.create(request("dave")).await.unwrap();
+    assert!(registry.delete(&created.key.id).await.unwrap());
+    assert!(!registry.delete(&created.key.id).await.unwrap());
+    assert_eq!(registry.authenticate(&created.secret), Err(ApiKeyRejection::Unknown));
+}
+
+#[tokio::test]
+async fn account_group_is_validated_and_clearable() {
+    let (registry, _dir) = registry();
+    let mut req = request("batch");
+    req.account_group = Some("bad group".into());
+    assert!(registry.create(req).await.is_err());
+
+    let mut req = request("batch");
+    req.account_group = Some(" batch ".into());
+    let created = registry.create(req).await.unwrap();
+    assert_eq!(created.key.account_group.as_deref(), Some("batch"));
+
+    let update = UpdateApiKeyRequest { account_group: Some(None), ..Default::default() };
+    let key = registry.update(&created.key.id, update).await.unwrap().unwrap();
+    assert_eq!(key.account_group, None);
+}
@@ -0,0 +1,257 @@
+//! Splitting long recordings on silence.
+//!
+//! The recording is decoded and downmixed to 16 kHz mono, then cut at the quietest
+//! stretch near each chunk's target length. Chunks are re-encoded as 16-bit PCM WAV,
+//! which keeps a five-minute chunk well under the inline data limit.
+
+use symphonia::core::audio::SampleBuffer;
+use symphonia::core::codecs::{DecoderOptions, CODEC_TYPE_NULL};
+use symphonia::core::errors::Error as SymphoniaError;
+use symphonia::core::formats::FormatOptions;
+use symphonia::core::io::MediaSourceStream;
+use symphonia::core::meta::MetadataOptions;
+use symphonia::core::probe::Hint;
+
+/// Sample rate chunks are encoded at; plenty for speech.
+const SAMPLE_RATE: u32 = 16_000;
+
+/// Loudness is compared over windows of this many samples (300 ms).
+const QUIET_WINDOW: usize = SAMPLE_RATE as usize * 3 / 10;
+
+/// How long chunks should be.
+#[derive(Debug, Clone, Copy)]
+pub struct ChunkLimits {
+    /// Preferred chunk length; cuts are searched from up to a minute before it
+    pub target_secs: u32,
+    /// Hard upper bound on a chunk's length
+    pub max_secs: u32,
+}
+
+impl Default for ChunkLimits {
+    fn default() -> Self {
+        Self { target_secs: 240, max_secs: 300 }
+    }
+}
+
+/// One piece of the recording.
+#[derive(Debug, Clone)]
+pub struct AudioChunk {
+    /// Start of the chunk in the original recording
+    pub offset_secs: f64,
+    /// 16 kHz mono 16-bit PCM WAV
+    pub wav: Vec<u8>,
+}
+
+/// Decode `data` and split it into chunks no longer than `limits.max_secs`.
+pub fn split_on_silence(
+    data: Vec<u8>,
+    extension: Option<&str>,
+    limits: ChunkLimits,
+) -> Result<Vec<AudioChunk>, String> {
+    let samples = decode_mono(data, extension)?;
+    let rate = SAMPLE_RATE as usize;
+    let target = limits.target_secs as usize * rate;
+    let max = (limits.max_secs as usize * rate).max(target).max(QUIET_WINDOW * 2);
+    let search_from = target.saturating_sub((60 * rate).min(target / 2)).max(QUIET_WINDOW);
+
+    let mut chunks = Vec::new();
+    let mut start = 0;
+    while start < samples.len() {
+        let end = if samples.len() - start <= max {
+            samples.len()
+        } else {
+            quietest_point(&samples, start + search_from, start + max)
+        };
+        chunks.push(AudioChunk {
+            offset_secs: start as f64 / f64::from(SAMPLE_RATE),
+            wav: encode_wav(&samples[start..end]),
+        });
+        start = end;
+    }
+    Ok(chunks)
+}
+
+/// Middle of the quietest `QUIET_WINDOW` between `from` and `to`, the latest on ties.
+fn quietest_point(samples: &[i16], from: usize, to: usize) -> usize {
+    let energy = |s: &i16| i64::from(*s) * i64::from(*s);
+    let from = from.min(to - QUIET_WINDOW);
+    let mut sum: i64 = samples[from..from + QUIET_WINDOW].iter().map(energy).sum();
+    let (mut best, mut best_sum) = (from, sum);
+    for start in from + 1..=to - QUIET_WINDOW {
+        sum += energy(&samples[start + QUIET_WINDOW - 1]) - energy(&samples[start - 1]);
+        if sum <= best_sum {
+            best = start;
+            best_sum = sum;
+        }
+    }
+    best + QUIET_WINDOW / 2
+}
+
+/// Decode any supported format to 16 kHz mono samples.
+fn decode_mono(data: Vec<u8>, extension: Option<&str>) -> Result<Vec<i16>, String> {
+    let mut hint = Hint::new();
+    if let Some(extension) = extension {
+        hint.with_extension(extension);
+    }
+    let stream = MediaSourceStream::new(Box::new(std::io::Cursor::new(data)), Default::default());
+    let probed = symphonia::default::get_probe()
+        .format(&hint, stream, &FormatOptions::default(), &MetadataOptions::default())
+        .map_err(|e| format!("Unrecognized audio container: {}", e))?;
+    let mut format = probed.format;
+    let track = format
+        .tracks()
+        .iter()
+        .find(|t| t.codec_params.codec != CODEC_TYPE_NULL)
+        .ok_or("No audio track found")?;
+    let track_id = track.id;
+    let source_rate = track.codec_params.sample_rate.ok_or("Unknown sample rate")?;
+    let mut decoder = symphonia::default::get_codecs()
+        .make(&track.codec_params, &DecoderOptions::default())
+        .map_err(|e| format!("Unsupported audio codec: {}", e))?;
+
+    let mut resampler = Resampler::new(source_rate);
+    loop {
+        let packet = match format.next_packet() {
+            Ok(packet) => packet,
+            Err(SymphoniaError::IoError(e)) if e.kind() == std::io::ErrorKind::UnexpectedEof => {
+                break
+            },
+            Err(SymphoniaError::ResetRequired) => break,
+            Err(e) => return Err(format!("Failed to read audio: {}", e)),
+        };
+        if packet.track_id() != track_id {
+            continue;
+        }
+        let decoded = match decoder.decode(&packet) {
+            Ok(decoded) => decoded,
+            Err(SymphoniaError::DecodeError(e)) => {
+                tracing::debug!("[Audio] Skipping undecodable packet: {}", e);
+                continue;
+            },
+            Err(e) => return Err(format!("Failed to decode audio: {}", e)),
+        };
+        let channels = decoded.spec().channels.count().max(1);
+        let mut buffer = SampleBuffer::<f32>::new(decoded.capacity() as u64, *decoded.spec());
+        buffer.copy_interleaved_ref(decoded);
+        for frame in buffer.samples().chunks(channels) {
+            resampler.push(frame.iter().sum::<f32>() / channels as f32);
+        }
+    }
+
+    if resampler.output.is_empty() {
+        return Err("Audio contains no samples".to_string());
+    }
+    Ok(resampler.output)
+}
+
+/// Streaming linear-interpolation resampler to `SAMPLE_RATE`.
+struct Resampler {
+    step: f64,
+    next: f64,
+    index: u64,
+    previous: f32,
+    output: Vec<i16>,
+}
+
+impl Resampler {
+    fn new(source_rate: u32) -> Self {
+        Self {
+            step: f64::from(source_rate) / f64::from(SAMPLE_RATE),
+            next: 0.0,
+            index: 0,
+            previous: 0.0,
+            output: Vec::new(),
+        }
+    }
+
+    fn push(&mut self, sample: f32) {
+        let position = self.index as f64;
+        while self.next <= position {
+            let fraction = (1.0 - (position - self.next)) as f32;
+            let value = self.previous + (sample - self.previous) * fraction;
+            self.output.push((value.clamp(-1.0, 1.0) * f32::from(i16::MAX)) as i16);
+            self.next += self.step;
+        }
+        self.previous = sample;
+        self.index += 1;
+    }
+}
+
+fn encode_wav(samples: &[i16]) -> Vec<u8> {
+    let data_len = (samples.len() * 2) as u32;
+    let mut wav = Vec::with_capacity(44 + samples.len() * 2);
+    wav.extend_from_slice(b"RIFF");
+    wav.extend_from_slice(&(36 + data_len).to_le_bytes());
+    wav.extend_from_slice(b"WAVEfmt ");
+    wav.extend_from_slice(&16u32.to_le_bytes());
+    wav.extend_from_slice(&1u16.to_le_bytes()); // PCM
+    wav.extend_from_slice(&1u16.to_le_bytes()); // mono
+    wav.extend_from_slice(&SAMPLE_RATE.to_le_bytes());
+    wav.extend_from_slice(&(SAMPLE_RATE * 2).to_le_bytes()); // byte rate
+    wav.extend_from_slice(&2u16.to_le_bytes()); // block align
+    wav.extend_from_slice(&16u16.to_le_bytes()); // bits per sample
+    wav.extend_from_slice(b"data");
+    wav.extend_from_slice(&data_len.to_le_bytes());
+    for sample in samples {
+        wav.extend_from_slice(&sample.to_le_bytes());
+    }
+    wav
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `seconds` of 8 kHz stereo tone where every `gap_every`-th second is silent.
+    fn speech_like(seconds: usize, gap_every: usize) -> Vec<i16> {
+        let mut samples = Vec::new();
+        for second in 0..seconds {
+            let silent = second % gap_every == gap_every - 1;
+            for i in 0..8000 {
+                let value = if silent { 0.0 } else { (i as f32 * 0.3).sin() * 12_000.0 };
+                samples.extend([value as i16, value as i16]);
+            }
+        }
+        samples
+    }
+
+    fn stereo_wav(samples: &[i16]) -> Vec<u8> {
+        let mut wav = encode_wav(samples);
+        wav[22..24].copy_from_slice(&2u16.to_le_bytes());
+        wav[24..28].copy_from_slice(&8000u32.to_le_bytes());
+        wav[28..32].copy_from_slice(&(8000u32 * 4).to_le_bytes());
+        wav[32..34].copy_from_slice(&4u16.to_le_bytes());
+        wav
+    }
+
+    #[test]
+    fn splits_in_silence_and_keeps_offsets() {
+        // Silence during seconds 2, 5, 8, ...
+        let wav = stereo_wav(&speech_like(12, 3));
+        let limits = ChunkLimits { target_secs: 4, max_secs: 7 };
+        let chunks = split_on_silence(wav, Some("wav"), limits).unwrap();
+
+        assert!(chunks.len() >= 2, "{} chunks", chunks.len());
+        assert_eq!(chunks[0].offset_secs, 0.0);
+        for pair in chunks.windows(2) {
+            let cut = pair[1].offset_secs;
+            // Every cut lands inside a silent second
+            assert_eq!(cut.floor() as usize % 3, 2, "cut at {cut}");
+            let chunk_secs = (pair[0].wav.len() - 44) as f64 / 2.0 / f64::from(SAMPLE_RATE);
+            assert!((pair[0].offset_secs + chunk_secs - cut).abs() < 1e-3);
+            assert!(chunk_secs <= 7.0);
+        }
+        let total: usize = chunks.iter().map(|c| (c.wav.len() - 44) / 2).sum();
+        assert!((total as i64 - 12 * SAMPLE_RATE as i64).abs() < 10, "{total} samples");
+    }
+
+    #[test]
+    fn short_audio_is_one_chunk() {
+        let wav = stereo_wav(&speech_like(3, 3));
+        let chunks = split_on_silence(wav, Some("wav"), ChunkLimits::default()).unwrap();
+        assert_eq!(chunks.len(), 1);
+        assert!(
+            split_on_silence(b"not audio at all".to_vec(), None, ChunkLimits::default()).is_err()
+        );
+    }
+}
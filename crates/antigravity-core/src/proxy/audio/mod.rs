@@ -1,9 +1,16 @@
 use base64::{engine::general_purpose, Engine as _};
 use std::path::Path;
 
+mod chunking;
+mod transcript;
+
+pub use chunking::{split_on_silence, AudioChunk, ChunkLimits};
+pub use transcript::{AudioTask, ResponseFormat, Segment, Transcript, Word};
+
 pub struct AudioProcessor;
 
 const MAX_SIZE: usize = 15 * 1024 * 1024; // 15MB
+const MAX_UPLOAD_SIZE: usize = 200 * 1024 * 1024; // longer files are chunked
 
 impl AudioProcessor {
     /// Detect MIME type from file magic bytes (signature)
@@ -77,6 +84,11 @@ impl AudioProcessor {
         MAX_SIZE
     }
 
+    /// Largest accepted upload; files above `max_size_bytes` are split into chunks
+    pub const fn max_upload_bytes() -> usize {
+        MAX_UPLOAD_SIZE
+    }
+
     /// Encode audio data to Base64
     pub fn encode_to_base64(audio_data: &[u8]) -> String {
         general_purpose::STANDARD.encode(audio_data)
@@ -0,0 +1,298 @@
+//! Timestamped transcripts and their Whisper-compatible renderings.
+
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::fmt::Write as _;
+
+/// `response_format` of the Whisper API.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResponseFormat {
+    Json,
+    Text,
+    Srt,
+    VerboseJson,
+    Vtt,
+}
+
+impl ResponseFormat {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim() {
+            "json" => Some(Self::Json),
+            "text" => Some(Self::Text),
+            "srt" => Some(Self::Srt),
+            "verbose_json" => Some(Self::VerboseJson),
+            "vtt" => Some(Self::Vtt),
+            _ => None,
+        }
+    }
+
+    pub fn content_type(self) -> &'static str {
+        match self {
+            Self::Json | Self::VerboseJson => "application/json",
+            Self::Text | Self::Srt => "text/plain; charset=utf-8",
+            Self::Vtt => "text/vtt; charset=utf-8",
+        }
+    }
+}
+
+/// Transcribe in the spoken language, or translate into English.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AudioTask {
+    Transcribe,
+    Translate,
+}
+
+impl AudioTask {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Transcribe => "transcribe",
+            Self::Translate => "translate",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Word {
+    pub word: String,
+    pub start: f64,
+    pub end: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Segment {
+    #[serde(default)]
+    pub id: usize,
+    pub start: f64,
+    pub end: f64,
+    pub text: String,
+    #[serde(default)]
+    pub words: Vec<Word>,
+}
+
+/// A transcript with times in seconds from the start of the recording.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+pub struct Transcript {
+    #[serde(default)]
+    pub language: Option<String>,
+    #[serde(default)]
+    pub segments: Vec<Segment>,
+}
+
+impl Transcript {
+    /// Parse the model's structured output; plain text becomes one untimed segment.
+    pub fn from_model_output(output: &str) -> Self {
+        let trimmed = output.trim();
+        let json = trimmed
+            .strip_prefix("```json")
+            .or_else(|| trimmed.strip_prefix("```"))
+            .and_then(|s| s.strip_suffix("```"))
+            .unwrap_or(trimmed);
+        match serde_json::from_str::<Self>(json) {
+            Ok(mut transcript) => {
+                transcript.segments.retain(|s| !s.text.trim().is_empty());
+                transcript
+            },
+            Err(_) if trimmed.is_empty() => Self::default(),
+            Err(_) => Self {
+                language: None,
+                segments: vec![Segment {
+                    id: 0,
+                    start: 0.0,
+                    end: 0.0,
+                    text: trimmed.to_string(),
+                    words: Vec::new(),
+                }],
+            },
+        }
+    }
+
+    /// Join chunk transcripts, moving each chunk's times by its offset in the recording.
+    pub fn stitch(parts: Vec<(f64, Self)>) -> Self {
+        let mut stitched = Self::default();
+        for (offset, part) in parts {
+            if stitched.language.is_none() {
+                stitched.language = part.language;
+            }
+            for mut segment in part.segments {
+                segment.start = (segment.start.max(0.0)) + offset;
+                segment.end = (segment.end.max(0.0) + offset).max(segment.start);
+                for word in &mut segment.words {
+                    word.start = word.start.max(0.0) + offset;
+                    word.end = (word.end.max(0.0) + offset).max(word.start);
+                }
+                stitched.segments.push(segment);
+            }
+        }
+        for (id, segment) in stitched.segments.iter_mut().enumerate() {
+            segment.id = id;
+        }
+        stitched
+    }
+
+    pub fn text(&self) -> String {
+        self.segments.iter().map(|s| s.text.trim()).collect::<Vec<_>>().join(" ")
+    }
+
+    pub fn duration(&self) -> f64 {
+        self.segments.iter().map(|s| s.end).fold(0.0, f64::max)
+    }
+
+    /// Render in `format`; `words` and `segments` select the verbose_json granularities.
+    pub fn render(
+        &self,
+        format: ResponseFormat,
+        task: AudioTask,
+        words: bool,
+        segments: bool,
+    ) -> String {
+        match format {
+            ResponseFormat::Json => json!({ "text": self.text() }).to_string(),
+            ResponseFormat::Text => self.text(),
+            ResponseFormat::Srt => self.srt(),
+            ResponseFormat::Vtt => self.vtt(),
+            ResponseFormat::VerboseJson => self.verbose_json(task, words, segments).to_string(),
+        }
+    }
+
+    fn verbose_json(&self, task: AudioTask, words: bool, segments: bool) -> Value {
+        let mut body = json!({
+            "task": task.as_str(),
+            "language": self.language.clone().unwrap_or_else(|| "english".to_string()),
+            "duration": round_ms(self.duration()),
+            "text": self.text(),
+        });
+        if segments {
+            body["segments"] = self
+                .segments
+                .iter()
+                .map(|s| {
+                    json!({
+                        "id": s.id,
+                        "seek": 0,
+                        "start": round_ms(s.start),
+                        "end": round_ms(s.end),
+                        "text": s.text.trim(),
+                        "tokens": [],
+                        "temperature": 0.0,
+                        "avg_logprob": 0.0,
+                        "compression_ratio": 0.0,
+                        "no_speech_prob": 0.0,
+                    })
+                })
+                .collect();
+        }
+        if words {
+            body["words"] = self
+                .segments
+                .iter()
+                .flat_map(|s| &s.words)
+                .map(|w| json!({ "word": w.word, "start": round_ms(w.start), "end": round_ms(w.end) }))
+                .collect();
+        }
+        body
+    }
+
+    fn srt(&self) -> String {
+        let mut out = String::new();
+        for (i, s) in self.segments.iter().enumerate() {
+            let _ = write!(
+                out,
+                "{}\n{} --> {}\n{}\n\n",
+                i + 1,
+                timestamp(s.start, ','),
+                timestamp(s.end, ','),
+                s.text.trim()
+            );
+        }
+        out
+    }
+
+    fn vtt(&self) -> String {
+        let mut out = String::from("WEBVTT\n\n");
+        for s in &self.segments {
+            let _ = write!(
+                out,
+                "{} --> {}\n{}\n\n",
+                timestamp(s.start, '.'),
+                timestamp(s.end, '.'),
+                s.text.trim()
+            );
+        }
+        out
+    }
+}
+
+fn round_ms(secs: f64) -> f64 {
+    (secs * 1000.0).round() / 1000.0
+}
+
+/// `HH:MM:SS,mmm` (SRT) or `HH:MM:SS.mmm` (WebVTT).
+fn timestamp(secs: f64, separator: char) -> String {
+    let total_ms = (secs.max(0.0) * 1000.0).round() as u64;
+    let (hours, rest) = (total_ms / 3_600_000, total_ms % 3_600_000);
+    let (minutes, rest) = (rest / 60_000, rest % 60_000);
+    format!("{:02}:{:02}:{:02}{}{:03}", hours, minutes, rest / 1000, separator, rest % 1000)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chunk(text: &str) -> Transcript {
+        Transcript::from_model_output(text)
+    }
+
+    #[test]
+    fn stitching_offsets_timestamps_and_renumbers() {
+        let first = chunk(
+            r#"{"language":"english","segments":[{"start":0.5,"end":2.0,"text":"Hello there.",
+                "words":[{"word":"Hello","start":0.5,"end":1.0},{"word":"there.","start":1.1,"end":2.0}]}]}"#,
+        );
+        let second = chunk(r#"{"segments":[{"start":1.0,"end":3.25,"text":" General Kenobi."}]}"#);
+        let transcript = Transcript::stitch(vec![(0.0, first), (240.0, second)]);
+
+        assert_eq!(transcript.text(), "Hello there. General Kenobi.");
+        assert_eq!(transcript.segments[1].id, 1);
+        assert!((transcript.segments[1].start - 241.0).abs() < f64::EPSILON);
+        assert!((transcript.duration() - 243.25).abs() < f64::EPSILON);
+
+        let srt = transcript.render(ResponseFormat::Srt, AudioTask::Transcribe, false, true);
+        assert!(srt.contains("2\n00:04:01,000 --> 00:04:03,250\nGeneral Kenobi.\n"), "{srt}");
+        let vtt = transcript.render(ResponseFormat::Vtt, AudioTask::Transcribe, false, true);
+        assert!(vtt.starts_with("WEBVTT\n\n00:00:00.500 --> 00:00:02.000\n"), "{vtt}");
+    }
+
+    #[test]
+    fn verbose_json_includes_requested_granularities() {
+        let transcript = Transcript::stitch(vec![(
+            10.0,
+            chunk(
+                r#"```json
+{"language":"german","segments":[{"start":0,"end":1,"text":"Hallo",
+ "words":[{"word":"Hallo","start":0.1,"end":0.9}]}]}
+```"#,
+            ),
+        )]);
+        let body: Value = serde_json::from_str(&transcript.render(
+            ResponseFormat::VerboseJson,
+            AudioTask::Translate,
+            true,
+            false,
+        ))
+        .unwrap();
+        assert_eq!(body["task"], "translate");
+        assert_eq!(body["language"], "german");
+        assert_eq!(body["words"][0]["start"], 10.1);
+        assert!(body.get("segments").is_none());
+    }
+
+    #[test]
+    fn plain_text_output_becomes_one_segment() {
+        let transcript = chunk("just words");
+        assert_eq!(
+            transcript.render(ResponseFormat::Json, AudioTask::Transcribe, false, true),
+            r#"{"text":"just words"}"#
+        );
+        assert!(chunk("  ").segments.is_empty());
+    }
+}
@@ -0,0 +1,246 @@
+//! Background execution of message batches (`/v1/messages/batches`, `/v1/batches`).
+//!
+//! A batch is persisted with all of its requests, then a runner task feeds the pending
+//! requests through the regular `/v1/messages` or `/v1/chat/completions` handler. All
+//! batches share one concurrency limit, every request runs at the admission queue's
+//! batch priority, and account selection only considers accounts with spare quota
+//! (see `TokenManager::has_spare_quota`). Unfinished batches resume after a restart.
+//!
+//! Instances sharing a database coordinate through a lease on the batch row: a runner
+//! only starts once it holds the lease and renews it while running, and every instance
+//! periodically picks up unfinished batches whose lease has expired.
+
+mod runner;
+#[cfg(test)]
+mod tests;
+
+use antigravity_types::models::{Batch, BatchApi, BatchConfig, BatchItem, BatchPage};
+use parking_lot::{Mutex, RwLock};
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Notify;
+
+use crate::modules::batch_store::{self, BatchStore};
+use crate::proxy::server::AppState;
+
+/// How long a runner's lease lasts without renewal.
+const LEASE_TTL_SECS: i64 = 60;
+/// How often a runner renews its lease.
+const LEASE_RENEW_INTERVAL: Duration = Duration::from_secs(20);
+/// How often unfinished batches without a live runner are looked for.
+const RESUME_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Owns batch persistence and the runner tasks.
+pub struct BatchManager {
+    store: BatchStore,
+    config: RwLock<BatchConfig>,
+    in_flight: AtomicUsize,
+    /// Signalled when a slot frees up, the limit changes or a batch is canceled
+    changed: Notify,
+    /// Batches with a live runner task
+    running: Mutex<HashSet<String>>,
+    /// Identifies this manager's leases in the store
+    runner_id: String,
+}
+
+/// One of the shared execution slots; released on drop.
+pub(crate) struct Slot(Arc<BatchManager>);
+
+impl Drop for Slot {
+    fn drop(&mut self) {
+        self.0.in_flight.fetch_sub(1, Ordering::AcqRel);
+        self.0.changed.notify_waiters();
+    }
+}
+
+impl BatchManager {
+    pub fn new(store: BatchStore) -> Self {
+        Self {
+            store,
+            config: RwLock::new(BatchConfig::default()),
+            in_flight: AtomicUsize::new(0),
+            changed: Notify::new(),
+            running: Mutex::new(HashSet::new()),
+            runner_id: uuid::Uuid::new_v4().to_string(),
+        }
+    }
+
+    pub fn set_config(&self, config: BatchConfig) {
+        *self.config.write() = config;
+        // A raised concurrency limit frees slots
+        self.changed.notify_waiters();
+    }
+
+    pub fn config(&self) -> BatchConfig {
+        self.config.read().clone()
+    }
+
+    pub fn store(&self) -> &BatchStore {
+        &self.store
+    }
+
+    /// Requests currently executing across all batches.
+    pub fn in_flight(&self) -> usize {
+        self.in_flight.load(Ordering::Acquire)
+    }
+
+    /// Persist a new batch and start executing it.
+    pub async fn submit(
+        self: &Arc<Self>,
+        state: &AppState,
+        batch: &Batch,
+        items: &[BatchItem],
+    ) -> Result<(), String> {
+        self.store.create(batch, items).await?;
+        tracing::info!(
+            "[Batch] Created {} ({} requests, {})",
+            batch.id,
+            items.len(),
+            batch.api.as_str()
+        );
+        self.spawn(state, &batch.id);
+        Ok(())
+    }
+
+    pub async fn get(&self, id: &str) -> Result<Option<Batch>, String> {
+        self.store.get(id).await
+    }
+
+    /// Batch `id` if it exists and the client key `key_id` may see it.
+    pub async fn get_visible(
+        &self,
+        id: &str,
+        key_id: Option<&str>,
+    ) -> Result<Option<Batch>, String> {
+        if !batch_store::is_valid_id(id) {
+            return Ok(None);
+        }
+        Ok(self.store.get(id).await?.filter(|batch| batch.is_visible_to(key_id)))
+    }
+
+    pub async fn list(
+        &self,
+        api: BatchApi,
+        key_id: Option<&str>,
+        page: &BatchPage,
+    ) -> Result<Vec<Batch>, String> {
+        self.store.list(api, key_id, page).await
+    }
+
+    pub async fn items(&self, id: &str) -> Result<Vec<BatchItem>, String> {
+        self.store.items(id).await
+    }
+
+    /// Stop dispatching requests of an in-progress batch. Requests already running finish;
+    /// the rest end as canceled. The runner may live on another instance, which notices
+    /// the status change within its poll interval. Returns the batch as it is after the call.
+    pub async fn cancel(
+        self: &Arc<Self>,
+        state: &AppState,
+        id: &str,
+    ) -> Result<Option<Batch>, String> {
+        if self.store.begin_cancel(id, chrono::Utc::now().timestamp()).await? {
+            tracing::info!("[Batch] Cancel requested for {}", id);
+            self.changed.notify_waiters();
+            self.spawn(state, id);
+        }
+        self.store.get(id).await
+    }
+
+    /// Restart runners for batches that had not ended when the server stopped, and keep
+    /// taking over batches whose runner (on any instance) has gone away.
+    pub fn resume(self: &Arc<Self>, state: &AppState) {
+        let manager = Arc::clone(self);
+        let state = state.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(RESUME_INTERVAL);
+            loop {
+                interval.tick().await;
+                match manager.store.unfinished().await {
+                    Ok(ids) => {
+                        for id in ids {
+                            manager.spawn(&state, &id);
+                        }
+                    },
+                    Err(e) => tracing::warn!("[Batch] Could not load unfinished batches: {}", e),
+                }
+            }
+        });
+    }
+
+    /// Start a runner for `id` unless one is already running here or on another instance.
+    fn spawn(self: &Arc<Self>, state: &AppState, id: &str) {
+        if !self.running.lock().insert(id.to_string()) {
+            return;
+        }
+        let manager = Arc::clone(self);
+        let state = state.clone();
+        let id = id.to_string();
+        tokio::spawn(async move {
+            match manager.claim(&id).await {
+                Ok(true) => {
+                    let result = tokio::select! {
+                        result = manager.run(&state, &id) => result,
+                        e = manager.hold_lease(&id) => Err(e),
+                    };
+                    if let Err(e) = result {
+                        tracing::warn!("[Batch] Runner for {} stopped: {}", id, e);
+                    }
+                    if let Err(e) = manager.store.release(&id, &manager.runner_id).await {
+                        tracing::warn!("[Batch] Could not release {}: {}", id, e);
+                    }
+                },
+                Ok(false) => tracing::debug!("[Batch] {} is running on another instance", id),
+                Err(e) => tracing::warn!("[Batch] Could not claim {}: {}", id, e),
+            }
+            manager.running.lock().remove(&id);
+        });
+    }
+
+    /// Take or renew this manager's lease on batch `id`.
+    async fn claim(&self, id: &str) -> Result<bool, String> {
+        let now = chrono::Utc::now().timestamp();
+        self.store.claim(id, &self.runner_id, now, LEASE_TTL_SECS).await
+    }
+
+    /// Renew the lease on `id`; returns once another instance has taken it over.
+    async fn hold_lease(&self, id: &str) -> String {
+        loop {
+            tokio::time::sleep(LEASE_RENEW_INTERVAL).await;
+            match self.claim(id).await {
+                Ok(true) => {},
+                Ok(false) => return "lease taken over by another instance".to_string(),
+                Err(e) => tracing::warn!("[Batch] Could not renew lease on {}: {}", id, e),
+            }
+        }
+    }
+
+    /// Why `items` may not be submitted by client key `key_id`: the first request whose model is
+    /// blocked by an exhausted spend budget.
+    pub fn budget_rejection(
+        &self,
+        state: &AppState,
+        key_id: Option<&str>,
+        items: &[BatchItem],
+    ) -> Option<String> {
+        let spend = state.monitor.spend();
+        items.iter().find_map(|item| {
+            let budget = spend.blocking_budget(key_id, item.params["model"].as_str())?;
+            Some(format!(
+                "Spend budget '{}' exhausted for the current period (request '{}')",
+                budget.name, item.custom_id
+            ))
+        })
+    }
+
+    /// Take a free slot, or None if the limit is reached.
+    pub(crate) fn try_acquire(self: &Arc<Self>) -> Option<Slot> {
+        let limit = self.config.read().concurrency;
+        self.in_flight
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |n| (n < limit).then(|| n + 1))
+            .ok()
+            .map(|_| Slot(Arc::clone(self)))
+    }
+}
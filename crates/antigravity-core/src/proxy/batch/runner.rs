@@ -0,0 +1,381 @@
+//! Batch runner: dispatches pending requests, records their results and closes the batch.
+
+use antigravity_types::models::{
+    Batch, BatchApi, BatchFile, BatchItem, BatchItemStatus, BatchStatus, PriorityClass,
+};
+use axum::{
+    extract::{Json, State},
+    http::{HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+};
+use serde_json::{json, Value};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::task::JoinSet;
+
+use super::{BatchManager, Slot};
+use crate::proxy::admission::{self, Ticket};
+use crate::proxy::api_keys::ClientKey;
+use crate::proxy::common::header_constants::{X_ACCOUNT_EMAIL, X_MAPPED_MODEL, X_MAPPING_REASON};
+use crate::proxy::handlers;
+use crate::proxy::middleware::monitor_usage::extract_usage_from_json;
+use crate::proxy::middleware::routing::route;
+use crate::proxy::middleware::spend::budget_exceeded_response;
+use crate::proxy::monitor::{truncate_body, ProxyRequestLog};
+use crate::proxy::routing_rules::{self, RouteDecision};
+use crate::proxy::server::AppState;
+
+/// Pending requests loaded from the store at a time.
+const PAGE_SIZE: usize = 100;
+/// How often a waiting runner re-checks quota, cancellation and expiry.
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+/// Attempts per request for overload and transient upstream errors.
+const MAX_ATTEMPTS: u32 = 3;
+const RETRY_DELAY: Duration = Duration::from_secs(2);
+/// Longest error body kept in the request log.
+const MAX_ERROR_LOG: usize = 16 * 1024;
+
+impl BatchManager {
+    /// Execute the pending requests of batch `id`, then end it.
+    pub(super) async fn run(self: &Arc<Self>, state: &AppState, id: &str) -> Result<(), String> {
+        let Some(batch) = self.store.get(id).await? else {
+            return Ok(());
+        };
+        if batch.status == BatchStatus::Ended {
+            return Ok(());
+        }
+        // Each request runs as if the owning client key had sent it
+        let owner =
+            batch.api_key_id.as_deref().and_then(|key_id| state.api_keys.get(key_id)).map(|key| {
+                ClientKey {
+                    id: key.id,
+                    label: key.label,
+                    account_group: key.account_group,
+                    priority: key.priority,
+                }
+            });
+
+        let mut tasks = JoinSet::new();
+        let mut after = None;
+        let stopped = 'dispatch: loop {
+            let page = self.store.pending_items(id, after, PAGE_SIZE).await?;
+            if page.is_empty() {
+                break None;
+            }
+            for item in page {
+                after = Some(item.position);
+                let slot = match self.wait_for_slot(state, &batch).await {
+                    Ok(slot) => slot,
+                    Err(status) => break 'dispatch Some(status),
+                };
+                tasks.spawn(execute(
+                    state.clone(),
+                    batch.id.clone(),
+                    batch.api,
+                    owner.clone(),
+                    item,
+                    slot,
+                ));
+                while tasks.try_join_next().is_some() {}
+            }
+        };
+        while tasks.join_next().await.is_some() {}
+
+        let now = chrono::Utc::now().timestamp();
+        if let Some(status) = stopped {
+            self.store.finish_pending(id, status, now).await?;
+        }
+        self.finish(&batch, now).await
+    }
+
+    /// Wait until an account has spare quota and a slot is free. Fails with the final
+    /// status of the remaining requests if the batch is canceled or expires meanwhile.
+    async fn wait_for_slot(
+        self: &Arc<Self>,
+        state: &AppState,
+        batch: &Batch,
+    ) -> Result<Slot, BatchItemStatus> {
+        loop {
+            let changed = self.changed.notified();
+            tokio::pin!(changed);
+            changed.as_mut().enable();
+            match self.stop_reason(batch).await {
+                Ok(Some(status)) => return Err(status),
+                Ok(None) => {},
+                Err(e) => tracing::warn!("[Batch] Could not check status of {}: {}", batch.id, e),
+            }
+            if state.token_manager.has_spare_quota() {
+                if let Some(slot) = self.try_acquire() {
+                    return Ok(slot);
+                }
+            }
+            let _ = tokio::time::timeout(POLL_INTERVAL, changed).await;
+        }
+    }
+
+    /// Why dispatching should stop, as the status the remaining requests end with. The status
+    /// is read from the store, since any instance may have canceled the batch.
+    pub(super) async fn stop_reason(
+        &self,
+        batch: &Batch,
+    ) -> Result<Option<BatchItemStatus>, String> {
+        let status = self.store.status(&batch.id).await?;
+        Ok(if status != Some(BatchStatus::InProgress) {
+            Some(BatchItemStatus::Canceled)
+        } else if chrono::Utc::now().timestamp() >= batch.expires_at {
+            Some(BatchItemStatus::Expired)
+        } else {
+            None
+        })
+    }
+
+    /// Write the OpenAI result files and mark the batch ended.
+    async fn finish(&self, batch: &Batch, now: i64) -> Result<(), String> {
+        let mut ended = self.store.get(&batch.id).await?.unwrap_or_else(|| batch.clone());
+        if ended.api == BatchApi::Openai {
+            let items = self.store.items(&batch.id).await?;
+            let (output, errors): (Vec<_>, Vec<_>) =
+                items.iter().partition(|item| item.status == BatchItemStatus::Succeeded);
+            ended.output_file_id = self.write_results(&ended, "output", &output, now).await?;
+            ended.error_file_id = self.write_results(&ended, "error", &errors, now).await?;
+        }
+        ended.status = BatchStatus::Ended;
+        ended.ended_at = Some(now);
+        self.store.update(&ended).await?;
+
+        let counts = ended.request_counts;
+        tracing::info!(
+            "[Batch] {} ended: {} succeeded, {} errored, {} canceled, {} expired",
+            batch.id,
+            counts.succeeded,
+            counts.errored,
+            counts.canceled,
+            counts.expired
+        );
+        Ok(())
+    }
+
+    /// Store `items` as an OpenAI results file; None when there is nothing to write.
+    async fn write_results(
+        &self,
+        batch: &Batch,
+        kind: &str,
+        items: &[&BatchItem],
+        now: i64,
+    ) -> Result<Option<String>, String> {
+        if items.is_empty() {
+            return Ok(None);
+        }
+        let mut content = Vec::new();
+        for item in items {
+            content.extend(openai_result_line(item).to_string().into_bytes());
+            content.push(b'\n');
+        }
+        let file = BatchFile {
+            id: format!("file-{}", uuid::Uuid::new_v4().simple()),
+            filename: format!("{}_{}.jsonl", batch.id, kind),
+            purpose: "batch_output".to_string(),
+            api_key_id: batch.api_key_id.clone(),
+            bytes: content.len() as u64,
+            created_at: now,
+        };
+        self.store.put_file(&file, content).await?;
+        Ok(Some(file.id))
+    }
+}
+
+/// Line of an OpenAI batch output or error file.
+pub(crate) fn openai_result_line(item: &BatchItem) -> Value {
+    let id = format!("batch_req_{}", uuid::Uuid::new_v4().simple());
+    let error = match item.status {
+        BatchItemStatus::Canceled => Some(("batch_cancelled", "Batch was cancelled")),
+        BatchItemStatus::Expired => Some(("batch_expired", "Batch expired before the request ran")),
+        _ => None,
+    };
+    match error {
+        Some((code, message)) => json!({
+            "id": id,
+            "custom_id": item.custom_id,
+            "response": null,
+            "error": { "code": code, "message": message },
+        }),
+        None => json!({
+            "id": id,
+            "custom_id": item.custom_id,
+            "response": {
+                "status_code": item.status_code.unwrap_or(500),
+                "request_id": format!("req_{}", uuid::Uuid::new_v4().simple()),
+                "body": item.result,
+            },
+            "error": null,
+        }),
+    }
+}
+
+/// Run one request and record its outcome. Holds `slot` until done.
+async fn execute(
+    state: AppState,
+    batch_id: String,
+    api: BatchApi,
+    owner: Option<ClientKey>,
+    mut item: BatchItem,
+    slot: Slot,
+) {
+    let start = Instant::now();
+    let (status, headers, body) = run_item(&state, api, owner.as_ref(), &item.params).await;
+    record(&state, api, owner.as_ref(), &item.params, status, &headers, &body, start).await;
+
+    item.status =
+        if status.is_success() { BatchItemStatus::Succeeded } else { BatchItemStatus::Errored };
+    item.status_code = Some(status.as_u16());
+    item.result = Some(body);
+    item.completed_at = Some(chrono::Utc::now().timestamp());
+
+    let Slot(manager) = &slot;
+    if let Err(e) = manager.store.finish_item(&batch_id, &item).await {
+        tracing::warn!("[Batch] Failed to store result of {}/{}: {}", batch_id, item.custom_id, e);
+    }
+}
+
+/// Apply the owner's spend budgets and the routing rules to `params`, then run it.
+async fn run_item(
+    state: &AppState,
+    api: BatchApi,
+    owner: Option<&ClientKey>,
+    params: &Value,
+) -> (StatusCode, HeaderMap, Value) {
+    let path = endpoint(api);
+    let key_id = owner.map(|key| key.id.as_str());
+    if let Some(budget) = state.monitor.spend().blocking_budget(key_id, params["model"].as_str()) {
+        tracing::warn!("[Batch] Request rejected by budget '{}'", budget.name);
+        return read_response(api, budget_exceeded_response(path, &budget.name)).await;
+    }
+
+    let mut params = params.clone();
+    match route(state, path, &HeaderMap::new(), owner.cloned(), &mut params).await {
+        Ok(decision) => call_with_retry(state, api, decision.unwrap_or_default(), &params).await,
+        Err(rejection) => read_response(api, rejection).await,
+    }
+}
+
+/// Send `params` through the API's handler, retrying overload and transient errors.
+async fn call_with_retry(
+    state: &AppState,
+    api: BatchApi,
+    decision: RouteDecision,
+    params: &Value,
+) -> (StatusCode, HeaderMap, Value) {
+    let mut attempt = 1;
+    loop {
+        let response = call(state, api, decision.clone(), params.clone()).await;
+        let status = response.status();
+        let retryable = matches!(status.as_u16(), 429 | 500 | 502 | 503 | 529);
+        if !retryable || attempt >= MAX_ATTEMPTS {
+            return read_response(api, response).await;
+        }
+        tracing::debug!("[Batch] Upstream returned {}, retrying (attempt {})", status, attempt);
+        tokio::time::sleep(RETRY_DELAY * attempt).await;
+        attempt += 1;
+    }
+}
+
+/// Invoke the handler of `api` as a batch-priority request under `decision`.
+async fn call(state: &AppState, api: BatchApi, decision: RouteDecision, params: Value) -> Response {
+    let request = async {
+        match api {
+            BatchApi::Anthropic => {
+                handlers::claude::handle_messages(
+                    State(state.clone()),
+                    HeaderMap::new(),
+                    Json(params),
+                )
+                .await
+            },
+            BatchApi::Openai => handlers::openai::handle_chat_completions(
+                State(state.clone()),
+                HeaderMap::new(),
+                Json(params),
+            )
+            .await
+            .into_response(),
+        }
+    };
+    admission::scope(Ticket::new(PriorityClass::Batch), routing_rules::scope(decision, request))
+        .await
+}
+
+/// Log a finished request to the monitor, which also charges the owner's key and budgets.
+async fn record(
+    state: &AppState,
+    api: BatchApi,
+    owner: Option<&ClientKey>,
+    params: &Value,
+    status: StatusCode,
+    headers: &HeaderMap,
+    body: &Value,
+    start: Instant,
+) {
+    let header = |name: &str| headers.get(name).and_then(|v| v.to_str().ok()).map(str::to_string);
+    let error = (!status.is_success()).then(|| truncate_body(&body.to_string(), MAX_ERROR_LOG));
+    let mut log = ProxyRequestLog {
+        id: uuid::Uuid::new_v4().to_string(),
+        timestamp: chrono::Utc::now().timestamp_millis(),
+        method: "POST".to_string(),
+        url: endpoint(api).to_string(),
+        status: status.as_u16(),
+        duration: start.elapsed().as_millis() as u64,
+        model: params["model"].as_str().map(str::to_string),
+        mapped_model: header(X_MAPPED_MODEL),
+        mapping_reason: header(X_MAPPING_REASON),
+        account_email: header(X_ACCOUNT_EMAIL),
+        response_body: error.clone(),
+        error,
+        request_body: None,
+        upstream_request_body: None,
+        input_tokens: None,
+        output_tokens: None,
+        cached_tokens: None,
+        api_key_id: owner.map(|key| key.id.clone()),
+        api_key_label: owner.map(|key| key.label.clone()),
+    };
+    if let Some(usage) = body.get("usage") {
+        extract_usage_from_json(usage, &mut log);
+    }
+    state.monitor.log_request(log).await;
+}
+
+/// Path of the endpoint that requests of `api` are sent to.
+fn endpoint(api: BatchApi) -> &'static str {
+    match api {
+        BatchApi::Anthropic => "/v1/messages",
+        BatchApi::Openai => "/v1/chat/completions",
+    }
+}
+
+async fn read_response(api: BatchApi, response: Response) -> (StatusCode, HeaderMap, Value) {
+    let status = response.status();
+    let headers = response.headers().clone();
+    (status, headers, read_body(api, response).await)
+}
+
+/// Response body as JSON; plain-text errors are wrapped in the API's error shape.
+async fn read_body(api: BatchApi, response: Response) -> Value {
+    let bytes = match axum::body::to_bytes(response.into_body(), usize::MAX).await {
+        Ok(bytes) => bytes,
+        Err(e) => return error_body(api, &format!("Failed to read response: {}", e)),
+    };
+    serde_json::from_slice(&bytes)
+        .unwrap_or_else(|_| error_body(api, String::from_utf8_lossy(&bytes).trim()))
+}
+
+fn error_body(api: BatchApi, message: &str) -> Value {
+    match api {
+        BatchApi::Anthropic => json!({
+            "type": "error",
+            "error": { "type": "api_error", "message": message },
+        }),
+        BatchApi::Openai => json!({
+            "error": { "message": message, "type": "api_error", "param": null, "code": null },
+        }),
+    }
+}
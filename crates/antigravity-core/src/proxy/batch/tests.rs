@@ -0,0 +1,103 @@
+use super::runner::openai_result_line;
+use super::BatchManager;
+use crate::modules::batch_store::BatchStore;
+use antigravity_types::models::{
+    Batch, BatchApi, BatchConfig, BatchItem, BatchItemStatus, BatchRequestCounts, BatchStatus,
+};
+use serde_json::json;
+use std::path::Path;
+use std::sync::Arc;
+
+fn manager(concurrency: usize) -> Arc<BatchManager> {
+    let dir = std::env::temp_dir().join(format!("antigravity-batch-{}", uuid::Uuid::new_v4()));
+    let manager = Arc::new(BatchManager::new(BatchStore::Json(dir)));
+    manager.set_config(BatchConfig { concurrency, ..Default::default() });
+    manager
+}
+
+fn manager_in(dir: &Path) -> Arc<BatchManager> {
+    Arc::new(BatchManager::new(BatchStore::Json(dir.to_path_buf())))
+}
+
+fn in_progress(id: &str) -> Batch {
+    let now = chrono::Utc::now().timestamp();
+    Batch {
+        id: id.to_string(),
+        api: BatchApi::Anthropic,
+        endpoint: "/v1/messages".to_string(),
+        api_key_id: None,
+        status: BatchStatus::InProgress,
+        metadata: serde_json::Map::new(),
+        input_file_id: None,
+        output_file_id: None,
+        error_file_id: None,
+        created_at: now,
+        expires_at: now + 86_400,
+        cancel_initiated_at: None,
+        ended_at: None,
+        request_counts: BatchRequestCounts::default(),
+    }
+}
+
+#[test]
+fn test_slots_are_limited_by_concurrency() {
+    let manager = manager(2);
+    let first = manager.try_acquire().unwrap();
+    let _second = manager.try_acquire().unwrap();
+    assert_eq!(manager.in_flight(), 2);
+    assert!(manager.try_acquire().is_none());
+
+    drop(first);
+    assert_eq!(manager.in_flight(), 1);
+    assert!(manager.try_acquire().is_some());
+}
+
+#[test]
+fn test_raising_concurrency_frees_slots() {
+    let manager = manager(1);
+    let _held = manager.try_acquire().unwrap();
+    assert!(manager.try_acquire().is_none());
+
+    manager.set_config(BatchConfig { concurrency: 2, ..Default::default() });
+    assert!(manager.try_acquire().is_some());
+}
+
+#[test]
+fn test_openai_result_lines() {
+    let mut item = BatchItem::new(0, "req-1".to_string(), json!({}));
+    item.status = BatchItemStatus::Succeeded;
+    item.status_code = Some(200);
+    item.result = Some(json!({"id": "chatcmpl-1"}));
+    let line = openai_result_line(&item);
+    assert_eq!(line["custom_id"], "req-1");
+    assert_eq!(line["response"]["status_code"], 200);
+    assert_eq!(line["response"]["body"]["id"], "chatcmpl-1");
+    assert!(line["error"].is_null());
+
+    item.status = BatchItemStatus::Expired;
+    let line = openai_result_line(&item);
+    assert!(line["response"].is_null());
+    assert_eq!(line["error"]["code"], "batch_expired");
+}
+
+#[tokio::test]
+async fn test_managers_sharing_a_store_coordinate() {
+    let dir = tempfile::tempdir().unwrap();
+    let (one, two) = (manager_in(dir.path()), manager_in(dir.path()));
+    let batch = in_progress("msgbatch_shared");
+    let items = [BatchItem::new(0, "req-1".to_string(), json!({}))];
+    one.store().create(&batch, &items).await.unwrap();
+
+    // Only one of them may run the batch
+    assert!(one.claim(&batch.id).await.unwrap());
+    assert!(!two.claim(&batch.id).await.unwrap());
+    assert!(one.claim(&batch.id).await.unwrap());
+
+    // A cancel through the other manager stops the runner that holds the batch
+    assert_eq!(one.stop_reason(&batch).await.unwrap(), None);
+    assert!(two.store().begin_cancel(&batch.id, chrono::Utc::now().timestamp()).await.unwrap());
+    assert_eq!(one.stop_reason(&batch).await.unwrap(), Some(BatchItemStatus::Canceled));
+
+    one.store().release(&batch.id, &one.runner_id).await.unwrap();
+    assert!(two.claim(&batch.id).await.unwrap());
+}
@@ -0,0 +1,48 @@
+//! Capture of transformed upstream request bodies for the monitor.
+//!
+//! The monitor middleware runs the handler inside [`scope`]; every upstream call made
+//! from that task records its body through [`record_upstream_request`]. Calls outside
+//! a scope (background jobs, warmups) are ignored.
+
+use parking_lot::Mutex;
+use serde::Serialize;
+use std::future::Future;
+use std::sync::Arc;
+
+tokio::task_local! {
+    static UPSTREAM_CAPTURE: Arc<Mutex<Option<String>>>;
+}
+
+/// Remember the body sent upstream; the last call of a retry loop wins.
+pub fn record_upstream_request(body: &impl Serialize) {
+    let _ = UPSTREAM_CAPTURE.try_with(|slot| {
+        if let Ok(json) = serde_json::to_string(body) {
+            *slot.lock() = Some(json);
+        }
+    });
+}
+
+/// Run `future` while capturing its upstream request body.
+pub async fn scope<F: Future>(future: F) -> (F::Output, Option<String>) {
+    let slot = Arc::new(Mutex::new(None));
+    let output = UPSTREAM_CAPTURE.scope(Arc::clone(&slot), future).await;
+    let captured = slot.lock().take();
+    (output, captured)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn captures_last_body_inside_scope_only() {
+        record_upstream_request(&json!({"outside": true}));
+        let ((), captured) = scope(async {
+            record_upstream_request(&json!({"attempt": 1}));
+            record_upstream_request(&json!({"attempt": 2}));
+        })
+        .await;
+        assert_eq!(captured.as_deref(), Some(r#"{"attempt":2}"#));
+    }
+}
@@ -0,0 +1,54 @@
+//! Applying state changes published by other instances.
+
+use super::{ClusterEvent, ClusterMembers};
+use crate::proxy::rate_limit::{RateLimitInfo, RateLimitKey};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+fn system_time_from_ms(ms: i64) -> SystemTime {
+    UNIX_EPOCH + Duration::from_millis(u64::try_from(ms).unwrap_or(0))
+}
+
+pub(super) fn apply(members: &ClusterMembers, event: ClusterEvent) {
+    tracing::debug!("[Cluster] Applying remote update: {:?}", event);
+    match event {
+        ClusterEvent::Lockout { account, model, reset_at_ms, retry_after_sec, reason } => {
+            let key = RateLimitKey::from_optional_model(&account, model.as_deref());
+            let info = RateLimitInfo {
+                reset_time: system_time_from_ms(reset_at_ms),
+                retry_after_sec,
+                detected_at: SystemTime::now(),
+                reason,
+                model,
+            };
+            members.token_manager.rate_limit_tracker().apply_remote_lockout(key, info);
+        },
+        ClusterEvent::LockoutCleared { account, model } => {
+            let key = RateLimitKey::from_optional_model(&account, model.as_deref());
+            members.token_manager.rate_limit_tracker().apply_remote_clear(&key);
+        },
+        ClusterEvent::LockoutsCleared => {
+            members.token_manager.rate_limit_tracker().apply_remote_clear_all();
+        },
+        ClusterEvent::SessionBound { session_id, email } => {
+            members.token_manager.apply_remote_session_binding(&session_id, Some(email));
+        },
+        ClusterEvent::SessionUnbound { session_id } => {
+            members.token_manager.apply_remote_session_binding(&session_id, None);
+        },
+        ClusterEvent::SessionsCleared => {
+            members.token_manager.apply_remote_sessions_cleared();
+        },
+        ClusterEvent::CircuitOpened { account_id, opened_at_ms, reason } => {
+            let age = SystemTime::now()
+                .duration_since(system_time_from_ms(opened_at_ms))
+                .unwrap_or_default();
+            members.circuit_breaker.apply_remote_open(&account_id, age, reason);
+        },
+        ClusterEvent::CircuitClosed { account_id } => {
+            members.circuit_breaker.apply_remote_close(&account_id);
+        },
+        ClusterEvent::AimdLimit { account_id, confirmed_limit, ceiling } => {
+            members.adaptive_limits.apply_remote_limit(&account_id, confirmed_limit, ceiling);
+        },
+    }
+}
@@ -0,0 +1,224 @@
+//! Shared state between gateway instances behind a load balancer.
+//!
+//! Each instance keeps its own rate-limit lockouts, sticky session bindings, circuit
+//! breakers and AIMD limits. With clustering enabled, changes to that state are
+//! published through a [`ClusterBackend`] and applied by every other instance, and one
+//! instance at a time is elected leader to run the background schedulers.
+//!
+//! Remote changes are applied without being re-published or re-emitted to the live
+//! monitor, so each event is reported once, by the instance that observed it.
+
+mod apply;
+mod postgres;
+
+pub use postgres::PostgresClusterBackend;
+
+use crate::proxy::adaptive_limit::AdaptiveLimitManager;
+use crate::proxy::common::circuit_breaker::CircuitBreakerManager;
+use crate::proxy::rate_limit::RateLimitReason;
+use crate::proxy::token_manager::TokenManager;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc;
+
+const OUTBOUND_QUEUE_SIZE: usize = 1024;
+const LEADER_CHECK_INTERVAL: Duration = Duration::from_secs(10);
+
+/// State change shared with the other instances.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ClusterEvent {
+    /// An account (or one of its models) was locked out by the rate limiter
+    Lockout {
+        account: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        model: Option<String>,
+        /// Unix milliseconds
+        reset_at_ms: i64,
+        retry_after_sec: u64,
+        reason: RateLimitReason,
+    },
+    /// A lockout ended early (request succeeded)
+    LockoutCleared {
+        account: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        model: Option<String>,
+    },
+    /// Every lockout was cleared (admin reset or optimistic reset)
+    LockoutsCleared,
+    SessionBound {
+        session_id: String,
+        email: String,
+    },
+    SessionUnbound {
+        session_id: String,
+    },
+    /// Every session binding was dropped
+    SessionsCleared,
+    CircuitOpened {
+        account_id: String,
+        /// Unix milliseconds
+        opened_at_ms: i64,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        reason: Option<String>,
+    },
+    CircuitClosed {
+        account_id: String,
+    },
+    /// AIMD limit after a penalty or reward
+    AimdLimit {
+        account_id: String,
+        confirmed_limit: u64,
+        ceiling: u64,
+    },
+}
+
+/// [`ClusterEvent`] tagged with the publishing instance.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ClusterMessage {
+    pub instance: String,
+    #[serde(flatten)]
+    pub event: ClusterEvent,
+}
+
+/// Transport between instances.
+#[async_trait]
+pub trait ClusterBackend: Send + Sync {
+    /// Deliver `message` to every instance (including, possibly, this one).
+    async fn publish(&self, message: &ClusterMessage) -> Result<(), String>;
+
+    /// Messages from all instances; the backend reconnects on its own.
+    fn subscribe(&self) -> mpsc::Receiver<ClusterMessage>;
+
+    /// Try to become (or confirm being) leader. Leadership must be released
+    /// automatically if this instance dies.
+    async fn try_acquire_leadership(&self) -> Result<bool, String>;
+}
+
+/// Components whose state is shared.
+pub struct ClusterMembers {
+    pub token_manager: Arc<TokenManager>,
+    pub circuit_breaker: Arc<CircuitBreakerManager>,
+    pub adaptive_limits: Arc<AdaptiveLimitManager>,
+}
+
+pub struct Cluster {
+    instance_id: String,
+    backend: Arc<dyn ClusterBackend>,
+    outbound: mpsc::Sender<ClusterEvent>,
+    outbound_rx: parking_lot::Mutex<Option<mpsc::Receiver<ClusterEvent>>>,
+    leader: AtomicBool,
+}
+
+impl Cluster {
+    pub fn new(instance_id: String, backend: Arc<dyn ClusterBackend>) -> Self {
+        let (outbound, outbound_rx) = mpsc::channel(OUTBOUND_QUEUE_SIZE);
+        Self {
+            instance_id,
+            backend,
+            outbound,
+            outbound_rx: parking_lot::Mutex::new(Some(outbound_rx)),
+            leader: AtomicBool::new(false),
+        }
+    }
+
+    pub fn instance_id(&self) -> &str {
+        &self.instance_id
+    }
+
+    pub fn is_leader(&self) -> bool {
+        self.leader.load(Ordering::Relaxed)
+    }
+
+    /// Queue `event` for the other instances. Never blocks; drops when the queue is full.
+    pub fn publish(&self, event: ClusterEvent) {
+        if self.outbound.try_send(event).is_err() {
+            tracing::warn!("[Cluster] Outbound queue full, dropping state update");
+        }
+    }
+
+    /// Hook `members` up to the cluster and start publishing, applying remote changes
+    /// and running leader election.
+    pub fn start(self: &Arc<Self>, members: ClusterMembers) {
+        members.token_manager.set_cluster(Arc::clone(self));
+        members.circuit_breaker.set_cluster(Arc::clone(self));
+        members.adaptive_limits.set_cluster(Arc::clone(self));
+
+        if let Some(mut outbound_rx) = self.outbound_rx.lock().take() {
+            let cluster = Arc::clone(self);
+            tokio::spawn(async move {
+                while let Some(event) = outbound_rx.recv().await {
+                    let message = ClusterMessage { instance: cluster.instance_id.clone(), event };
+                    if let Err(e) = cluster.backend.publish(&message).await {
+                        tracing::warn!("[Cluster] Failed to publish state update: {}", e);
+                    }
+                }
+            });
+        }
+
+        let mut inbound = self.backend.subscribe();
+        let instance_id = self.instance_id.clone();
+        tokio::spawn(async move {
+            while let Some(message) = inbound.recv().await {
+                if message.instance != instance_id {
+                    apply::apply(&members, message.event);
+                }
+            }
+        });
+
+        let cluster = Arc::clone(self);
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(LEADER_CHECK_INTERVAL);
+            loop {
+                interval.tick().await;
+                let leader = match cluster.backend.try_acquire_leadership().await {
+                    Ok(leader) => leader,
+                    Err(e) => {
+                        tracing::warn!("[Cluster] Leader election failed: {}", e);
+                        false
+                    },
+                };
+                if cluster.leader.swap(leader, Ordering::Relaxed) != leader {
+                    if leader {
+                        tracing::info!("[Cluster] {} is now the leader", cluster.instance_id);
+                    } else {
+                        tracing::warn!("[Cluster] {} lost leadership", cluster.instance_id);
+                    }
+                }
+            }
+        });
+    }
+}
+
+/// Cluster handle for components built before the cluster exists. Publishes nothing
+/// until [`ClusterSlot::set`]; a standalone instance is always the leader.
+#[derive(Default)]
+pub struct ClusterSlot(parking_lot::RwLock<Option<Arc<Cluster>>>);
+
+impl ClusterSlot {
+    pub fn set(&self, cluster: Arc<Cluster>) {
+        *self.0.write() = Some(cluster);
+    }
+
+    pub fn publish(&self, event: ClusterEvent) {
+        if let Some(cluster) = self.0.read().as_ref() {
+            cluster.publish(event);
+        }
+    }
+
+    pub fn is_leader(&self) -> bool {
+        self.0.read().as_ref().is_none_or(|cluster| cluster.is_leader())
+    }
+}
+
+impl std::fmt::Debug for ClusterSlot {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("ClusterSlot").field(&self.0.read().is_some()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,130 @@
+//! PostgreSQL cluster backend: `LISTEN`/`NOTIFY` for state updates and a
+//! session-level advisory lock for leader election.
+
+use super::{ClusterBackend, ClusterMessage};
+use async_trait::async_trait;
+use sha2::{Digest, Sha256};
+use sqlx::postgres::{PgConnection, PgListener, PgPool};
+use sqlx::Connection;
+use std::time::Duration;
+use tokio::sync::mpsc;
+
+const INBOUND_QUEUE_SIZE: usize = 1024;
+const RECONNECT_DELAY: Duration = Duration::from_secs(2);
+
+pub struct PostgresClusterBackend {
+    pool: PgPool,
+    channel: String,
+    /// Advisory lock key derived from the channel name
+    lock_key: i64,
+    /// Connection holding the advisory lock while this instance is leader
+    leader_conn: tokio::sync::Mutex<Option<PgConnection>>,
+}
+
+fn lock_key(channel: &str) -> i64 {
+    let digest = Sha256::digest(format!("antigravity-leader:{}", channel).as_bytes());
+    let mut bytes = [0_u8; 8];
+    bytes.copy_from_slice(digest.get(..8).unwrap_or(&[0; 8]));
+    i64::from_be_bytes(bytes)
+}
+
+impl PostgresClusterBackend {
+    pub fn new(pool: PgPool, channel: String) -> Self {
+        let lock_key = lock_key(&channel);
+        Self { pool, channel, lock_key, leader_conn: tokio::sync::Mutex::new(None) }
+    }
+}
+
+#[async_trait]
+impl ClusterBackend for PostgresClusterBackend {
+    async fn publish(&self, message: &ClusterMessage) -> Result<(), String> {
+        let payload = serde_json::to_string(message).map_err(|e| e.to_string())?;
+        sqlx::query("SELECT pg_notify($1, $2)")
+            .bind(&self.channel)
+            .bind(payload)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| e.to_string())?;
+        Ok(())
+    }
+
+    fn subscribe(&self) -> mpsc::Receiver<ClusterMessage> {
+        let (tx, rx) = mpsc::channel(INBOUND_QUEUE_SIZE);
+        let pool = self.pool.clone();
+        let channel = self.channel.clone();
+        tokio::spawn(async move {
+            while !tx.is_closed() {
+                let mut listener = match PgListener::connect_with(&pool).await {
+                    Ok(listener) => listener,
+                    Err(e) => {
+                        tracing::warn!("[Cluster] LISTEN connection failed: {}", e);
+                        tokio::time::sleep(RECONNECT_DELAY).await;
+                        continue;
+                    },
+                };
+                if let Err(e) = listener.listen(&channel).await {
+                    tracing::warn!("[Cluster] LISTEN {} failed: {}", channel, e);
+                    tokio::time::sleep(RECONNECT_DELAY).await;
+                    continue;
+                }
+                tracing::info!("[Cluster] Listening on channel '{}'", channel);
+
+                loop {
+                    // recv() reconnects transparently; notifications sent while
+                    // disconnected are lost, which lockout expiry makes tolerable
+                    let notification = match listener.recv().await {
+                        Ok(notification) => notification,
+                        Err(e) => {
+                            tracing::warn!("[Cluster] LISTEN connection lost: {}", e);
+                            tokio::time::sleep(RECONNECT_DELAY).await;
+                            break;
+                        },
+                    };
+                    match serde_json::from_str::<ClusterMessage>(notification.payload()) {
+                        Ok(message) => {
+                            if tx.send(message).await.is_err() {
+                                return;
+                            }
+                        },
+                        Err(e) => tracing::debug!("[Cluster] Ignoring malformed update: {}", e),
+                    }
+                }
+            }
+        });
+        rx
+    }
+
+    async fn try_acquire_leadership(&self) -> Result<bool, String> {
+        let mut leader_conn = self.leader_conn.lock().await;
+        if let Some(conn) = leader_conn.as_mut() {
+            if conn.ping().await.is_ok() {
+                return Ok(true);
+            }
+            // The lock died with the connection; try again on a fresh one
+            *leader_conn = None;
+        }
+
+        let mut conn = self.pool.acquire().await.map_err(|e| e.to_string())?;
+        let acquired: bool = sqlx::query_scalar("SELECT pg_try_advisory_lock($1)")
+            .bind(self.lock_key)
+            .fetch_one(&mut *conn)
+            .await
+            .map_err(|e| e.to_string())?;
+        if acquired {
+            // Detached from the pool so the lock never leaks to another user
+            *leader_conn = Some(conn.detach());
+        }
+        Ok(acquired)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lock_key_depends_on_channel() {
+        assert_eq!(lock_key("antigravity_cluster"), lock_key("antigravity_cluster"));
+        assert_ne!(lock_key("antigravity_cluster"), lock_key("staging"));
+    }
+}
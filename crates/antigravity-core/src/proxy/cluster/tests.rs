@@ -0,0 +1,201 @@
+use super::*;
+use crate::proxy::common::circuit_breaker::CircuitState;
+use std::sync::atomic::AtomicUsize;
+use std::time::SystemTime;
+use tokio::sync::broadcast;
+
+/// In-process backend: every instance sharing `bus` sees every message.
+struct LocalBackend {
+    bus: broadcast::Sender<ClusterMessage>,
+    published: AtomicUsize,
+    leader: bool,
+}
+
+#[async_trait]
+impl ClusterBackend for LocalBackend {
+    async fn publish(&self, message: &ClusterMessage) -> Result<(), String> {
+        self.published.fetch_add(1, Ordering::SeqCst);
+        let _ = self.bus.send(message.clone());
+        Ok(())
+    }
+
+    fn subscribe(&self) -> mpsc::Receiver<ClusterMessage> {
+        let (tx, rx) = mpsc::channel(64);
+        let mut bus = self.bus.subscribe();
+        tokio::spawn(async move {
+            while let Ok(message) = bus.recv().await {
+                if tx.send(message).await.is_err() {
+                    break;
+                }
+            }
+        });
+        rx
+    }
+
+    async fn try_acquire_leadership(&self) -> Result<bool, String> {
+        Ok(self.leader)
+    }
+}
+
+struct Instance {
+    cluster: Arc<Cluster>,
+    backend: Arc<LocalBackend>,
+    members: ClusterMembers,
+}
+
+fn instance(name: &str, bus: &broadcast::Sender<ClusterMessage>, leader: bool) -> Instance {
+    let backend =
+        Arc::new(LocalBackend { bus: bus.clone(), published: AtomicUsize::new(0), leader });
+    let cluster = Arc::new(Cluster::new(name.to_string(), backend.clone()));
+    let unique_id = uuid::Uuid::new_v4();
+    let members = ClusterMembers {
+        token_manager: Arc::new(TokenManager::new(
+            std::env::temp_dir().join(format!("antigravity_test_{unique_id}")),
+        )),
+        circuit_breaker: Arc::new(CircuitBreakerManager::new()),
+        adaptive_limits: Arc::new(AdaptiveLimitManager::default()),
+    };
+    cluster.start(ClusterMembers {
+        token_manager: Arc::clone(&members.token_manager),
+        circuit_breaker: Arc::clone(&members.circuit_breaker),
+        adaptive_limits: Arc::clone(&members.adaptive_limits),
+    });
+    Instance { cluster, backend, members }
+}
+
+/// Wait until `condition` holds (remote updates are applied asynchronously).
+async fn eventually(condition: impl Fn() -> bool) {
+    for _ in 0..100 {
+        if condition() {
+            return;
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    panic!("condition not reached");
+}
+
+#[test]
+fn message_is_tagged_by_type() {
+    let message = ClusterMessage {
+        instance: "gw-1".to_string(),
+        event: ClusterEvent::Lockout {
+            account: "a@example.com".to_string(),
+            model: Some("gemini-3-pro".to_string()),
+            reset_at_ms: 1_700_000_000_000,
+            retry_after_sec: 60,
+            reason: RateLimitReason::QuotaExhausted,
+        },
+    };
+    let json = serde_json::to_value(&message).unwrap();
+    assert_eq!(json["type"], "lockout");
+    assert_eq!(json["instance"], "gw-1");
+    assert_eq!(json["reason"], "quota_exhausted");
+    assert_eq!(serde_json::from_value::<ClusterMessage>(json).unwrap(), message);
+}
+
+#[tokio::test]
+async fn lockouts_propagate_without_echo() {
+    let (bus, _) = broadcast::channel(64);
+    let a = instance("a", &bus, true);
+    let b = instance("b", &bus, false);
+
+    a.members.token_manager.rate_limit_tracker().set_model_lockout(
+        "a@example.com",
+        "gemini-3-pro",
+        SystemTime::now() + Duration::from_secs(60),
+        RateLimitReason::RateLimitExceeded,
+    );
+    eventually(|| {
+        b.members.token_manager.is_rate_limited_for_model("a@example.com", "gemini-3-pro")
+    })
+    .await;
+    assert!(!b.members.token_manager.is_rate_limited_for_model("a@example.com", "other-model"));
+
+    b.members
+        .token_manager
+        .rate_limit_tracker()
+        .mark_model_success("a@example.com", "gemini-3-pro");
+    eventually(|| {
+        !a.members.token_manager.is_rate_limited_for_model("a@example.com", "gemini-3-pro")
+    })
+    .await;
+
+    // Applying a remote update never publishes it again
+    assert_eq!(a.backend.published.load(Ordering::SeqCst), 1);
+    assert_eq!(b.backend.published.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn sessions_circuits_and_limits_propagate() {
+    let (bus, _) = broadcast::channel(64);
+    let a = instance("a", &bus, true);
+    let b = instance("b", &bus, false);
+
+    a.members.token_manager.bind_session_to_account("sess-1", "a@example.com", true);
+    eventually(|| {
+        b.members.token_manager.get_session_account("sess-1").as_deref() == Some("a@example.com")
+    })
+    .await;
+
+    for _ in 0..5 {
+        a.members.circuit_breaker.record_failure("acc-1", "upstream 500");
+    }
+    eventually(|| b.members.circuit_breaker.get_state("acc-1") == CircuitState::Open).await;
+    assert!(b.members.circuit_breaker.should_allow("acc-1").is_err());
+
+    a.members.circuit_breaker.reset("acc-1");
+    eventually(|| b.members.circuit_breaker.get_state("acc-1") == CircuitState::Closed).await;
+
+    a.members.adaptive_limits.record_429("acc-1");
+    let limit = a.members.adaptive_limits.get("acc-1").unwrap().confirmed_limit();
+    eventually(|| {
+        b.members.adaptive_limits.get("acc-1").is_some_and(|t| t.confirmed_limit() == limit)
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn clearing_everything_propagates() {
+    let (bus, _) = broadcast::channel(64);
+    let a = instance("a", &bus, true);
+    let b = instance("b", &bus, false);
+
+    a.members.token_manager.rate_limit_tracker().set_model_lockout(
+        "a@example.com",
+        "gemini-3-pro",
+        SystemTime::now() + Duration::from_secs(60),
+        RateLimitReason::RateLimitExceeded,
+    );
+    a.members.token_manager.bind_session_to_account("sess-1", "a@example.com", true);
+    eventually(|| {
+        b.members.token_manager.is_rate_limited_for_model("a@example.com", "gemini-3-pro")
+            && b.members.token_manager.get_session_account("sess-1").is_some()
+    })
+    .await;
+
+    a.members.token_manager.clear_all_rate_limits();
+    eventually(|| {
+        !b.members.token_manager.is_rate_limited_for_model("a@example.com", "gemini-3-pro")
+    })
+    .await;
+    a.members.token_manager.clear_all_sessions();
+    eventually(|| b.members.token_manager.get_session_account("sess-1").is_none()).await;
+
+    // Lockout, binding and the two clears; b only applied them
+    assert_eq!(a.backend.published.load(Ordering::SeqCst), 4);
+    assert_eq!(b.backend.published.load(Ordering::SeqCst), 0);
+}
+
+#[tokio::test]
+async fn leadership_follows_backend() {
+    let (bus, _) = broadcast::channel(64);
+    let a = instance("a", &bus, true);
+    let b = instance("b", &bus, false);
+    eventually(|| a.cluster.is_leader()).await;
+    assert!(!b.cluster.is_leader());
+
+    let slot = ClusterSlot::default();
+    assert!(slot.is_leader(), "standalone instances always lead");
+    slot.set(Arc::clone(&b.cluster));
+    assert!(!slot.is_leader());
+}
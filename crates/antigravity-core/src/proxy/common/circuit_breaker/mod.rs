@@ -19,9 +19,12 @@ mod tests;
 use state::AccountCircuit;
 pub use state::{CircuitBreakerConfig, CircuitBreakerSummary, CircuitState};
 
+use crate::proxy::cluster::{Cluster, ClusterSlot};
+use crate::proxy::monitor::{EventBusSlot, ProxyEventBus};
 use parking_lot::RwLock;
 use std::collections::HashMap;
 use std::sync::atomic::AtomicU64;
+use std::sync::Arc;
 use std::time::Duration;
 use tracing::debug;
 
@@ -31,6 +34,8 @@ pub struct CircuitBreakerManager {
     pub(super) config: CircuitBreakerConfig,
     pub(super) circuits: RwLock<HashMap<String, AccountCircuit>>,
     pub(super) total_trips: AtomicU64,
+    pub(super) events: EventBusSlot,
+    pub(super) cluster: ClusterSlot,
 }
 
 impl Default for CircuitBreakerManager {
@@ -45,7 +50,23 @@ impl CircuitBreakerManager {
     }
 
     pub fn with_config(config: CircuitBreakerConfig) -> Self {
-        Self { config, circuits: RwLock::new(HashMap::new()), total_trips: AtomicU64::new(0) }
+        Self {
+            config,
+            circuits: RwLock::new(HashMap::new()),
+            total_trips: AtomicU64::new(0),
+            events: EventBusSlot::default(),
+            cluster: ClusterSlot::default(),
+        }
+    }
+
+    /// Report circuit open/close transitions to the live monitor.
+    pub fn set_event_bus(&self, bus: Arc<dyn ProxyEventBus>) {
+        self.events.set(bus);
+    }
+
+    /// Share circuit open/close transitions with the other instances.
+    pub fn set_cluster(&self, cluster: Arc<Cluster>) {
+        self.cluster.set(cluster);
     }
 
     /// Check if an account's circuit is open (should fail fast)
@@ -68,7 +89,7 @@ impl CircuitBreakerManager {
                         circuit.state = CircuitState::HalfOpen;
                         circuit.consecutive_successes = 0;
 
-                        Self::persist_state_change(
+                        self.persist_state_change(
                             account_id,
                             previous_state,
                             CircuitState::HalfOpen,
@@ -113,7 +134,7 @@ impl CircuitBreakerManager {
                         circuit.state = CircuitState::HalfOpen;
                         circuit.consecutive_successes = 0;
 
-                        Self::persist_state_change(
+                        self.persist_state_change(
                             account_id,
                             previous_state,
                             CircuitState::HalfOpen,
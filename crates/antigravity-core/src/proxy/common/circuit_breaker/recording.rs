@@ -1,6 +1,9 @@
 use super::state::{AccountCircuit, CircuitBreakerSummary, CircuitState};
 use super::CircuitBreakerManager;
+use crate::proxy::cluster::ClusterEvent;
+use antigravity_types::models::{AccountState, AccountStateChange};
 use std::sync::atomic::Ordering;
+use std::time::{Duration, Instant};
 use tracing::{debug, info, warn};
 
 impl CircuitBreakerManager {
@@ -27,7 +30,7 @@ impl CircuitBreakerManager {
                     circuit.opened_at = None;
                     circuit.last_failure_reason = None;
 
-                    Self::persist_state_change(
+                    self.persist_state_change(
                         account_id,
                         previous_state,
                         CircuitState::Closed,
@@ -67,7 +70,7 @@ impl CircuitBreakerManager {
                     circuit.opened_at = Some(std::time::Instant::now());
                     self.total_trips.fetch_add(1, Ordering::Relaxed);
 
-                    Self::persist_state_change(
+                    self.persist_state_change(
                         account_id,
                         previous_state,
                         CircuitState::Open,
@@ -87,7 +90,7 @@ impl CircuitBreakerManager {
                 circuit.opened_at = Some(std::time::Instant::now());
                 self.total_trips.fetch_add(1, Ordering::Relaxed);
 
-                Self::persist_state_change(
+                self.persist_state_change(
                     account_id,
                     previous_state,
                     CircuitState::Open,
@@ -100,6 +103,7 @@ impl CircuitBreakerManager {
     }
 
     pub(super) fn persist_state_change(
+        &self,
         account_id: &str,
         previous_state: CircuitState,
         new_state: CircuitState,
@@ -114,6 +118,51 @@ impl CircuitBreakerManager {
             new_state,
             reason
         );
+
+        let state = match new_state {
+            CircuitState::Open => AccountState::CircuitOpened,
+            CircuitState::Closed => AccountState::CircuitClosed,
+            CircuitState::HalfOpen => return,
+        };
+        self.cluster.publish(match new_state {
+            CircuitState::Open => ClusterEvent::CircuitOpened {
+                account_id: account_id.to_string(),
+                opened_at_ms: chrono::Utc::now().timestamp_millis(),
+                reason: reason.map(str::to_string),
+            },
+            _ => ClusterEvent::CircuitClosed { account_id: account_id.to_string() },
+        });
+        self.events.emit_account_state(AccountStateChange {
+            account_id: account_id.to_string(),
+            state,
+            model: None,
+            reason: reason.map(str::to_string),
+            retry_after_sec: (state == AccountState::CircuitOpened)
+                .then_some(self.config.open_duration.as_secs()),
+        });
+    }
+
+    /// Apply a circuit opened `age` ago by another instance.
+    pub fn apply_remote_open(&self, account_id: &str, age: Duration, reason: Option<String>) {
+        if age >= self.config.open_duration {
+            return;
+        }
+        let mut circuits = self.circuits.write();
+        let circuit = circuits.entry(account_id.to_string()).or_default();
+        circuit.state = CircuitState::Open;
+        circuit.opened_at = Some(Instant::now().checked_sub(age).unwrap_or_else(Instant::now));
+        circuit.consecutive_failures =
+            circuit.consecutive_failures.max(self.config.failure_threshold);
+        circuit.consecutive_successes = 0;
+        circuit.half_open_probe_active = false;
+        circuit.last_failure_reason = reason;
+    }
+
+    /// Apply a circuit closed by another instance.
+    pub fn apply_remote_close(&self, account_id: &str) {
+        if let Some(circuit) = self.circuits.write().get_mut(account_id) {
+            *circuit = AccountCircuit::default();
+        }
     }
 
     pub fn get_state(&self, account_id: &str) -> CircuitState {
@@ -136,7 +185,7 @@ impl CircuitBreakerManager {
             );
 
             if previous_state != CircuitState::Closed {
-                Self::persist_state_change(
+                self.persist_state_change(
                     account_id,
                     previous_state,
                     CircuitState::Closed,
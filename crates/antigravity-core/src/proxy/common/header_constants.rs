@@ -8,3 +8,13 @@ pub const X_MAPPED_MODEL: &str = "X-Mapped-Model";
 pub const X_MAPPING_REASON: &str = "X-Mapping-Reason";
 /// Header to force routing to a specific account by email.
 pub const X_FORCE_ACCOUNT: &str = "X-Force-Account";
+/// Header carrying the OpenTelemetry trace id of the request.
+pub const X_TRACE_ID: &str = "X-Trace-Id";
+/// Webhook header with the notification kind.
+pub const X_ANTIGRAVITY_EVENT: &str = "X-Antigravity-Event";
+/// Webhook header with the Unix time covered by the signature.
+pub const X_ANTIGRAVITY_TIMESTAMP: &str = "X-Antigravity-Timestamp";
+/// Webhook header with `sha256=<hex HMAC of "{timestamp}.{body}">`.
+pub const X_ANTIGRAVITY_SIGNATURE: &str = "X-Antigravity-Signature";
+/// Header selecting the admission queue class (`interactive` or `batch`).
+pub const X_ANTIGRAVITY_PRIORITY: &str = "X-Antigravity-Priority";
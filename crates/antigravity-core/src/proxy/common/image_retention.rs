@@ -1,13 +1,21 @@
 use serde_json::Value;
+use std::sync::atomic::{AtomicUsize, Ordering};
 
 const DEFAULT_IMAGE_RETENTION_TURNS: usize = 5;
 const IMAGE_PLACEHOLDER: &str = "[Image was provided in this message]";
 
+static CONFIGURED_RETENTION_TURNS: AtomicUsize = AtomicUsize::new(DEFAULT_IMAGE_RETENTION_TURNS);
+
+/// Apply `ProxyConfig::image_retention_turns` (called at startup and on hot-reload).
+pub fn set_retention_turns(turns: u32) {
+    CONFIGURED_RETENTION_TURNS.store(turns as usize, Ordering::Relaxed);
+}
+
 fn get_retention_turns() -> usize {
     std::env::var("ANTIGRAVITY_IMAGE_RETENTION_TURNS")
         .ok()
         .and_then(|v| v.parse::<usize>().ok())
-        .unwrap_or(DEFAULT_IMAGE_RETENTION_TURNS)
+        .unwrap_or_else(|| CONFIGURED_RETENTION_TURNS.load(Ordering::Relaxed))
 }
 
 pub fn strip_old_images(contents: &mut Value) {
@@ -50,6 +50,15 @@ static CLAUDE_TO_GEMINI: LazyLock<HashMap<&'static str, &'static str>> = LazyLoc
         ("gemini-3-flash-preview", "gemini-3-flash"),
         ("gemini-3-pro-image", "gemini-3-pro-image"),
         ("internal-background-task", "gemini-2.5-flash"),
+        // Embeddings
+        ("text-embedding-3-small", "gemini-embedding-001"),
+        ("text-embedding-3-large", "gemini-embedding-001"),
+        ("text-embedding-ada-002", "gemini-embedding-001"),
+        ("text-embedding-004", "gemini-embedding-001"),
+        // Text to speech
+        ("tts-1", "gemini-2.5-flash-preview-tts"),
+        ("tts-1-hd", "gemini-2.5-pro-preview-tts"),
+        ("gpt-4o-mini-tts", "gemini-2.5-flash-preview-tts"),
         // Deprecated Gemini 2.0 models (removed from Cloud Code API, return 404)
         ("gemini-2.0-flash", "gemini-2.5-flash"),
         ("gemini-2.0-flash-exp", "gemini-2.5-flash"),
@@ -11,6 +11,7 @@ use std::collections::HashMap;
 /// # Returns
 /// - Ok((mapped_model, routing_reason)) on success
 /// - Err(error_message) for unknown models
+#[tracing::instrument(skip(custom_mapping))]
 pub fn resolve_model_route(
     original_model: &str,
     custom_mapping: &HashMap<String, String>,
@@ -7,7 +7,11 @@ static GLOBAL_THINKING_BUDGET: OnceLock<RwLock<ThinkingBudgetConfig>> = OnceLock
 
 /// Get the current thinking budget configuration.
 /// Returns default (Adaptive mode) if not yet initialized.
+/// A routing rule's budget takes precedence for the request it fired on.
 pub fn get_thinking_budget_config() -> ThinkingBudgetConfig {
+    if let Some(budget) = crate::proxy::routing_rules::thinking_budget() {
+        return budget;
+    }
     GLOBAL_THINKING_BUDGET
         .get()
         .map(|lock| match lock.read() {
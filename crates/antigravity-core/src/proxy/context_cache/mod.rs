@@ -0,0 +1,280 @@
+//! Upstream context caching for Anthropic `cache_control` breakpoints.
+//!
+//! The prefix up to a breakpoint (system instruction, tools and leading contents) is hashed
+//! and registered as cached content on the account serving the request. Later turns with the
+//! same prefix on that account reference it instead of resending it. Cached content is scoped
+//! to one account's project, so entries are keyed by account and the session is kept bound to
+//! the account that holds its cache.
+
+mod prefix;
+
+#[cfg(test)]
+mod tests;
+
+pub use prefix::{find_breakpoints, Breakpoints, Prefix};
+
+use crate::proxy::retry::Leg;
+use crate::proxy::upstream::client::UpstreamClient;
+use parking_lot::Mutex;
+use reqwest::StatusCode;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+
+const CREATE_METHOD: &str = "createCachedContent";
+
+/// Entries are dropped this long before the upstream expiry so a request never races it.
+const EXPIRY_MARGIN: Duration = Duration::from_secs(30);
+/// How long a model stays skipped after the upstream refused cached content for it.
+const UNSUPPORTED_BACKOFF: Duration = Duration::from_secs(3600);
+/// How long a prefix is not retried after a transient creation failure.
+const FAILURE_BACKOFF: Duration = Duration::from_secs(300);
+/// Longest a request waits for its prefix to be registered before going out uncached.
+const CREATE_TIMEOUT: Duration = Duration::from_secs(10);
+const MAX_ENTRIES: usize = 4096;
+
+#[derive(Debug, Clone)]
+enum Entry {
+    Ready { name: String, expires: Instant },
+    Failed { until: Instant },
+}
+
+impl Entry {
+    fn is_live(&self, now: Instant) -> bool {
+        match self {
+            Entry::Ready { expires, .. } => *expires > now,
+            Entry::Failed { until } => *until > now,
+        }
+    }
+
+    fn ready_name(&self, now: Instant) -> Option<&str> {
+        match self {
+            Entry::Ready { name, expires } if *expires > now => Some(name),
+            _ => None,
+        }
+    }
+}
+
+/// Cached content used by one upstream call.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AppliedCache {
+    pub name: String,
+    /// Whether the call created it, so its prefix counts as cache creation
+    pub created: bool,
+}
+
+pub struct ContextCache {
+    /// Keyed by (account email, prefix hash)
+    entries: Mutex<HashMap<(String, String), Entry>>,
+    /// Models the upstream refused cached content for, until the given time
+    unsupported: Mutex<HashMap<String, Instant>>,
+}
+
+impl ContextCache {
+    pub(crate) fn new() -> Self {
+        Self { entries: Mutex::new(HashMap::new()), unsupported: Mutex::new(HashMap::new()) }
+    }
+
+    pub fn global() -> &'static ContextCache {
+        static INSTANCE: OnceLock<ContextCache> = OnceLock::new();
+        INSTANCE.get_or_init(ContextCache::new)
+    }
+
+    /// Rewrite the v1internal `body` for `leg` to use cached content where possible.
+    ///
+    /// Reuses the longest prefix already cached on the account. A longer prefix is registered
+    /// when it adds at least a cacheable amount beyond that. Returns `None` (and leaves the body
+    /// alone) when nothing is cached.
+    pub async fn apply(
+        &self,
+        upstream: &UpstreamClient,
+        leg: &Leg,
+        account_proxy: Option<&str>,
+        breakpoints: &Breakpoints,
+        body: &mut Value,
+    ) -> Option<AppliedCache> {
+        let model = body.get("model").and_then(Value::as_str)?.to_string();
+        if self.is_unsupported(&model) {
+            return None;
+        }
+        let prefixes = prefix::prefixes(&body["request"], &model, breakpoints);
+        let (cached, target) = self.plan(&leg.email, &prefixes);
+
+        if let Some(target) = target {
+            let content = prefix::cached_content(&body["request"], target, breakpoints.ttl_secs);
+            if let Some(name) = self.create(upstream, leg, account_proxy, &model, content).await {
+                let ttl = Duration::from_secs(breakpoints.ttl_secs);
+                self.insert(
+                    &leg.email,
+                    &target.hash,
+                    Entry::Ready {
+                        name: name.clone(),
+                        expires: Instant::now() + ttl.saturating_sub(EXPIRY_MARGIN),
+                    },
+                );
+                tracing::info!(
+                    "[ContextCache] Registered {} for {} (~{} tokens, {} contents)",
+                    name,
+                    leg.email,
+                    target.estimated_tokens,
+                    target.contents
+                );
+                prefix::use_cached_content(body, &name, target);
+                return Some(AppliedCache { name, created: true });
+            }
+            self.insert(
+                &leg.email,
+                &target.hash,
+                Entry::Failed { until: Instant::now() + FAILURE_BACKOFF },
+            );
+        }
+
+        let (prefix, name) = cached?;
+        tracing::debug!("[ContextCache] Reusing {} for {}", name, leg.email);
+        prefix::use_cached_content(body, &name, prefix);
+        Some(AppliedCache { name, created: false })
+    }
+
+    /// The longest prefix cached on `email`, and the prefix to register, if any.
+    fn plan<'a>(
+        &self,
+        email: &str,
+        prefixes: &'a [Prefix],
+    ) -> (Option<(&'a Prefix, String)>, Option<&'a Prefix>) {
+        let now = Instant::now();
+        let entries = self.entries.lock();
+        let entry = |prefix: &Prefix| entries.get(&(email.to_string(), prefix.hash.clone()));
+
+        let cached = prefixes.iter().rev().find_map(|prefix| {
+            entry(prefix).and_then(|e| e.ready_name(now)).map(|name| (prefix, name.to_string()))
+        });
+        let target = prefixes.last().filter(|longest| {
+            let known = entry(longest).is_some_and(|e| e.is_live(now));
+            let gain = longest.estimated_tokens
+                - cached.as_ref().map_or(0, |(prefix, _)| prefix.estimated_tokens);
+            !known && gain >= prefix::MIN_PREFIX_TOKENS
+        });
+        (cached, target)
+    }
+
+    async fn create(
+        &self,
+        upstream: &UpstreamClient,
+        leg: &Leg,
+        account_proxy: Option<&str>,
+        model: &str,
+        content: Value,
+    ) -> Option<String> {
+        let body = json!({
+            "project": leg.project_id,
+            "model": model,
+            "cachedContent": content,
+        });
+        let call = upstream.call_v1_internal_fingerprinted(
+            CREATE_METHOD,
+            &leg.access_token,
+            body,
+            None,
+            &leg.email,
+            account_proxy,
+        );
+        let response = match tokio::time::timeout(CREATE_TIMEOUT, call).await {
+            Ok(Ok(response)) => response,
+            Ok(Err(e)) => {
+                tracing::warn!("[ContextCache] Create failed for {}: {}", leg.email, e);
+                return None;
+            },
+            Err(_) => {
+                tracing::warn!(
+                    "[ContextCache] Create timed out after {}s for {}",
+                    CREATE_TIMEOUT.as_secs(),
+                    leg.email
+                );
+                return None;
+            },
+        };
+
+        let status = response.status();
+        if !status.is_success() {
+            let text = response.text().await.unwrap_or_default();
+            if is_unsupported_status(status) {
+                tracing::info!(
+                    "[ContextCache] Upstream refused cached content for {} ({}), skipping it for {}s",
+                    model,
+                    status,
+                    UNSUPPORTED_BACKOFF.as_secs()
+                );
+                self.unsupported
+                    .lock()
+                    .insert(model.to_string(), Instant::now() + UNSUPPORTED_BACKOFF);
+            } else {
+                tracing::warn!("[ContextCache] Create returned {}: {}", status, text);
+            }
+            return None;
+        }
+
+        let value: Value = response.json().await.ok()?;
+        let name = value
+            .get("name")
+            .or_else(|| value.pointer("/response/name"))
+            .and_then(Value::as_str)
+            .map(str::to_string);
+        if name.is_none() {
+            tracing::warn!("[ContextCache] Create response has no name: {}", value);
+        }
+        name
+    }
+
+    /// Forget cached content the upstream no longer accepts.
+    pub fn invalidate(&self, name: &str) {
+        self.entries
+            .lock()
+            .retain(|_, entry| !matches!(entry, Entry::Ready { name: n, .. } if n == name));
+    }
+
+    fn is_unsupported(&self, model: &str) -> bool {
+        let mut unsupported = self.unsupported.lock();
+        match unsupported.get(model) {
+            Some(until) if *until > Instant::now() => true,
+            Some(_) => {
+                unsupported.remove(model);
+                false
+            },
+            None => false,
+        }
+    }
+
+    fn insert(&self, email: &str, hash: &str, entry: Entry) {
+        let now = Instant::now();
+        let mut entries = self.entries.lock();
+        if entries.len() >= MAX_ENTRIES {
+            entries.retain(|_, entry| entry.is_live(now));
+        }
+        if entries.len() >= MAX_ENTRIES {
+            let oldest = entries
+                .iter()
+                .min_by_key(|(_, entry)| match entry {
+                    Entry::Ready { expires, .. } => *expires,
+                    Entry::Failed { until } => *until,
+                })
+                .map(|(key, _)| key.clone());
+            if let Some(key) = oldest {
+                entries.remove(&key);
+            }
+        }
+        entries.insert((email.to_string(), hash.to_string()), entry);
+    }
+}
+
+/// Statuses meaning the upstream does not offer cached content for the model at all.
+/// A 400 usually means this prefix was rejected (e.g. too short), not the model.
+pub(super) fn is_unsupported_status(status: StatusCode) -> bool {
+    matches!(
+        status,
+        StatusCode::FORBIDDEN
+            | StatusCode::NOT_FOUND
+            | StatusCode::METHOD_NOT_ALLOWED
+            | StatusCode::NOT_IMPLEMENTED
+    )
+}
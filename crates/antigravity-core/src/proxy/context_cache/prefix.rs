@@ -0,0 +1,165 @@
+//! `cache_control` breakpoints and the cacheable prefix of a v1internal request.
+
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+
+/// Upstream minimum for cached content, estimated at four bytes per token.
+pub(super) const MIN_PREFIX_TOKENS: usize = 4096;
+
+const DEFAULT_TTL_SECS: u64 = 300;
+const LONG_TTL_SECS: u64 = 3600;
+
+/// Where a client asked for caching, in units of upstream `contents` entries.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Breakpoints {
+    /// Number of leading contents cached at each breakpoint, ascending
+    pub contents: Vec<usize>,
+    pub ttl_secs: u64,
+}
+
+/// A cacheable prefix of one request.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Prefix {
+    /// Leading contents covered, after the system instruction and tools
+    pub contents: usize,
+    pub hash: String,
+    pub estimated_tokens: usize,
+}
+
+/// Breakpoints of a raw Anthropic `/v1/messages` body, or `None` when it has no `cache_control`.
+///
+/// Markers on tools or the system prompt cache those alone. A marker on a message caches the
+/// messages up to it, or up to the one before when it is not on the message's last block. The
+/// count is taken over runs of same-role messages, which the transformation merges into one
+/// upstream content.
+pub fn find_breakpoints(body: &Value) -> Option<Breakpoints> {
+    let mut markers: Vec<&Value> = Vec::new();
+    let mut contents = Vec::new();
+
+    for key in ["tools", "system"] {
+        if let Some(blocks) = body.get(key).and_then(Value::as_array) {
+            let found: Vec<&Value> = blocks.iter().filter_map(marker).collect();
+            if !found.is_empty() {
+                markers.extend(found);
+                contents.push(0);
+            }
+        }
+    }
+
+    let messages = body.get("messages").and_then(Value::as_array).map(Vec::as_slice).unwrap_or(&[]);
+    let roles: Vec<&str> =
+        messages.iter().map(|m| m.get("role").and_then(Value::as_str).unwrap_or("")).collect();
+    for (index, message) in messages.iter().enumerate() {
+        let Some(blocks) = message.get("content").and_then(Value::as_array) else {
+            continue;
+        };
+        for (position, block) in blocks.iter().enumerate() {
+            if let Some(found) = marker(block) {
+                markers.push(found);
+                let end = if position + 1 == blocks.len() { index + 1 } else { index };
+                contents.push(complete_turns(&roles, end));
+            }
+        }
+    }
+
+    if markers.is_empty() {
+        return None;
+    }
+    contents.sort_unstable();
+    contents.dedup();
+    let long = markers.iter().any(|m| m.get("ttl").and_then(Value::as_str) == Some("1h"));
+    Some(Breakpoints { contents, ttl_secs: if long { LONG_TTL_SECS } else { DEFAULT_TTL_SECS } })
+}
+
+fn marker(block: &Value) -> Option<&Value> {
+    block.get("cache_control").filter(|marker| !marker.is_null())
+}
+
+/// Number of same-role runs that lie entirely within `roles[..end]`.
+fn complete_turns(roles: &[&str], end: usize) -> usize {
+    if end == 0 {
+        return 0;
+    }
+    let runs = 1 + (1..end).filter(|&i| roles[i] != roles[i - 1]).count();
+    if end < roles.len() && roles[end] == roles[end - 1] {
+        runs - 1
+    } else {
+        runs
+    }
+}
+
+/// Cacheable prefixes of `request` (the inner v1internal request) at each breakpoint, ascending.
+///
+/// The last content always stays in the request. Prefixes below the upstream minimum size are
+/// dropped.
+pub fn prefixes(request: &Value, model: &str, breakpoints: &Breakpoints) -> Vec<Prefix> {
+    let contents =
+        request.get("contents").and_then(Value::as_array).map(Vec::as_slice).unwrap_or(&[]);
+    let Some(limit) = contents.len().checked_sub(1) else {
+        return Vec::new();
+    };
+
+    let mut hasher = Sha256::new();
+    let mut bytes = 0;
+    for part in [
+        json!(model),
+        request["systemInstruction"].clone(),
+        request["tools"].clone(),
+        request["toolConfig"].clone(),
+    ] {
+        let encoded = part.to_string();
+        bytes += encoded.len();
+        hasher.update(encoded.as_bytes());
+        hasher.update([0u8]);
+    }
+
+    let mut prefixes = Vec::new();
+    let mut hashed = 0;
+    for &count in &breakpoints.contents {
+        let count = count.min(limit);
+        if prefixes.last().is_some_and(|p: &Prefix| p.contents == count) {
+            continue;
+        }
+        for content in &contents[hashed..count] {
+            let encoded = content.to_string();
+            bytes += encoded.len();
+            hasher.update(encoded.as_bytes());
+            hasher.update([0u8]);
+        }
+        hashed = count;
+        let estimated_tokens = bytes / 4;
+        if estimated_tokens >= MIN_PREFIX_TOKENS {
+            let hash = format!("{:x}", hasher.clone().finalize());
+            prefixes.push(Prefix { contents: count, hash, estimated_tokens });
+        }
+    }
+    prefixes
+}
+
+/// Cached content body holding `prefix` of `request`.
+pub fn cached_content(request: &Value, prefix: &Prefix, ttl_secs: u64) -> Value {
+    let mut content = json!({
+        "contents": request["contents"].as_array().map(|c| &c[..prefix.contents]).unwrap_or(&[]),
+        "ttl": format!("{}s", ttl_secs),
+    });
+    for key in ["systemInstruction", "tools", "toolConfig"] {
+        if let Some(value) = request.get(key) {
+            content[key] = value.clone();
+        }
+    }
+    content
+}
+
+/// Point a v1internal `body` at cached content `name` and drop the parts it holds.
+pub fn use_cached_content(body: &mut Value, name: &str, prefix: &Prefix) {
+    let Some(request) = body.get_mut("request").and_then(Value::as_object_mut) else {
+        return;
+    };
+    for key in ["systemInstruction", "tools", "toolConfig"] {
+        request.remove(key);
+    }
+    if let Some(contents) = request.get_mut("contents").and_then(Value::as_array_mut) {
+        contents.drain(..prefix.contents.min(contents.len()));
+    }
+    request.insert("cachedContent".to_string(), Value::String(name.to_string()));
+}
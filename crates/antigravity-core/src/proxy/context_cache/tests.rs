@@ -0,0 +1,177 @@
+use super::prefix::{cached_content, prefixes, use_cached_content};
+use super::{find_breakpoints, is_unsupported_status, Breakpoints, ContextCache, Entry};
+use serde_json::{json, Value};
+use std::time::{Duration, Instant};
+
+fn marked(text: &str) -> Value {
+    json!({ "type": "text", "text": text, "cache_control": { "type": "ephemeral" } })
+}
+
+fn plain(text: &str) -> Value {
+    json!({ "type": "text", "text": text })
+}
+
+/// Inner v1internal request with a large system instruction and `turns` contents.
+fn request(turns: usize) -> Value {
+    let contents: Vec<Value> = (0..turns)
+        .map(|i| {
+            let role = if i % 2 == 0 { "user" } else { "model" };
+            json!({ "role": role, "parts": [{ "text": format!("turn {} {}", i, "x".repeat(8000)) }] })
+        })
+        .collect();
+    json!({
+        "contents": contents,
+        "systemInstruction": { "parts": [{ "text": "s".repeat(20_000) }] },
+        "tools": [{ "functionDeclarations": [] }],
+        "toolConfig": { "functionCallingConfig": { "mode": "VALIDATED" } },
+    })
+}
+
+#[test]
+fn test_no_markers_means_no_breakpoints() {
+    let body = json!({ "system": [plain("s")], "messages": [{ "role": "user", "content": [plain("hi")] }] });
+    assert_eq!(find_breakpoints(&body), None);
+
+    let null_marker = json!({ "messages": [{ "role": "user", "content": [{ "type": "text", "text": "a", "cache_control": null }] }] });
+    assert_eq!(find_breakpoints(&null_marker), None);
+}
+
+#[test]
+fn test_breakpoints_count_whole_turns() {
+    let body = json!({
+        "system": [marked("system")],
+        "messages": [
+            { "role": "user", "content": [plain("a")] },
+            { "role": "assistant", "content": [plain("b")] },
+            // Same-role run: merged with the next message upstream
+            { "role": "user", "content": [marked("c")] },
+            { "role": "user", "content": [plain("d"), marked("e"), plain("f")] },
+            { "role": "assistant", "content": "g" },
+        ],
+    });
+    let breakpoints = find_breakpoints(&body).unwrap();
+    // system -> 0; "c" ends inside the third run -> 2; "e" is not last in its message -> 2
+    assert_eq!(breakpoints.contents, vec![0, 2]);
+    assert_eq!(breakpoints.ttl_secs, 300);
+
+    let long = json!({ "messages": [
+        { "role": "user", "content": [{ "type": "text", "text": "a", "cache_control": { "type": "ephemeral", "ttl": "1h" } }] },
+        { "role": "assistant", "content": [plain("b")] },
+    ] });
+    let breakpoints = find_breakpoints(&long).unwrap();
+    assert_eq!(breakpoints.contents, vec![1]);
+    assert_eq!(breakpoints.ttl_secs, 3600);
+}
+
+#[test]
+fn test_prefixes_keep_last_content_and_are_stable_across_turns() {
+    let breakpoints = Breakpoints { contents: vec![0, 2, 9], ttl_secs: 300 };
+    let first = prefixes(&request(3), "gemini-3-pro", &breakpoints);
+    assert_eq!(first.iter().map(|p| p.contents).collect::<Vec<_>>(), vec![0, 2]);
+
+    // A later turn shares the earlier prefixes
+    let later = prefixes(&request(5), "gemini-3-pro", &breakpoints);
+    assert_eq!(later[0], first[0]);
+    assert_eq!(later[1], first[1]);
+    assert_eq!(later.last().unwrap().contents, 4);
+
+    // Another model never shares a cache
+    let other = prefixes(&request(3), "gemini-3-flash", &breakpoints);
+    assert_ne!(other[0].hash, first[0].hash);
+
+    // Too small to cache
+    let small =
+        json!({ "contents": [{ "role": "user", "parts": [] }, { "role": "user", "parts": [] }] });
+    assert!(prefixes(&small, "gemini-3-pro", &breakpoints).is_empty());
+}
+
+#[test]
+fn test_use_cached_content_splits_the_body() {
+    let inner = request(3);
+    let prefix =
+        prefixes(&inner, "m", &Breakpoints { contents: vec![2], ttl_secs: 300 }).pop().unwrap();
+
+    let content = cached_content(&inner, &prefix, 300);
+    assert_eq!(content["contents"].as_array().unwrap().len(), 2);
+    assert_eq!(content["ttl"], "300s");
+    assert!(content["systemInstruction"].is_object());
+
+    let mut body = json!({ "model": "m", "request": inner });
+    use_cached_content(&mut body, "cachedContents/abc", &prefix);
+    let request = &body["request"];
+    assert_eq!(request["cachedContent"], "cachedContents/abc");
+    assert_eq!(request["contents"].as_array().unwrap().len(), 1);
+    assert!(request["contents"][0]["parts"][0]["text"].as_str().unwrap().starts_with("turn 2"));
+    assert!(request.get("systemInstruction").is_none());
+    assert!(request.get("tools").is_none());
+}
+
+#[test]
+fn test_plan_reuses_longest_and_registers_only_worthwhile_growth() {
+    let cache = ContextCache::new();
+    let breakpoints = Breakpoints { contents: vec![0, 2, 3], ttl_secs: 300 };
+    let prefixes = prefixes(&request(4), "m", &breakpoints);
+    assert_eq!(prefixes.len(), 3);
+    let ready = |name: &str| Entry::Ready {
+        name: name.to_string(),
+        expires: Instant::now() + Duration::from_secs(60),
+    };
+
+    // Nothing cached yet: register the longest prefix
+    let (cached, target) = cache.plan("a@x", &prefixes);
+    assert!(cached.is_none());
+    assert_eq!(target.unwrap().contents, 3);
+
+    // System-only cache: the conversation adds enough to register a longer one
+    cache.insert("a@x", &prefixes[0].hash, ready("c0"));
+    let (cached, target) = cache.plan("a@x", &prefixes);
+    assert_eq!(cached.unwrap().1, "c0");
+    assert_eq!(target.unwrap().contents, 3);
+
+    // One content short of the longest: too little to be worth a new cache
+    cache.insert("a@x", &prefixes[1].hash, ready("c2"));
+    let (cached, target) = cache.plan("a@x", &prefixes);
+    assert_eq!(cached.unwrap().1, "c2");
+    assert!(target.is_none());
+
+    // Caches belong to one account
+    let (cached, _) = cache.plan("b@x", &prefixes);
+    assert!(cached.is_none());
+
+    cache.invalidate("c2");
+    let (cached, _) = cache.plan("a@x", &prefixes);
+    assert_eq!(cached.unwrap().1, "c0");
+}
+
+#[test]
+fn test_failed_prefix_is_not_retried_but_older_cache_is_used() {
+    let cache = ContextCache::new();
+    let prefixes = prefixes(&request(4), "m", &Breakpoints { contents: vec![0, 3], ttl_secs: 300 });
+    cache.insert(
+        "a@x",
+        &prefixes[0].hash,
+        Entry::Ready { name: "c0".to_string(), expires: Instant::now() + Duration::from_secs(60) },
+    );
+    cache.insert(
+        "a@x",
+        &prefixes[1].hash,
+        Entry::Failed { until: Instant::now() + Duration::from_secs(60) },
+    );
+    let (cached, target) = cache.plan("a@x", &prefixes);
+    assert_eq!(cached.unwrap().1, "c0");
+    assert!(target.is_none());
+
+    // Expired entries count as absent
+    cache.insert(
+        "a@x",
+        &prefixes[0].hash,
+        Entry::Ready { name: "c0".to_string(), expires: Instant::now() - Duration::from_secs(1) },
+    );
+    assert!(cache.plan("a@x", &prefixes).0.is_none());
+}
+
+#[test]
+fn test_bad_request_does_not_mark_model_unsupported() {
+    assert!(!is_unsupported_status(reqwest::StatusCode::BAD_REQUEST));
+    assert!(is_unsupported_status(reqwest::StatusCode::NOT_FOUND));
+}
@@ -1,25 +1,72 @@
 use axum::{
     extract::{Multipart, State},
-    http::StatusCode,
-    response::IntoResponse,
-    Json,
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
 };
+use futures::StreamExt;
+use parking_lot::Mutex;
 use serde_json::{json, Value};
-use tracing::{debug, info};
+use std::collections::HashSet;
+use tracing::{debug, info, warn};
 use uuid::Uuid;
 
+use crate::proxy::audio::{
+    split_on_silence, AudioProcessor, AudioTask, ChunkLimits, ResponseFormat, Transcript,
+};
 use crate::proxy::common::header_constants::X_ACCOUNT_EMAIL;
-use crate::proxy::{audio::AudioProcessor, server::AppState};
+use crate::proxy::server::AppState;
+
+/// Audio understanding model used for all transcriptions (client `model` is ignored).
+const AUDIO_MODEL: &str = "gemini-2.5-flash";
+
+/// Chunks of a long recording transcribed at once.
+const MAX_PARALLEL_CHUNKS: usize = 4;
+
+/// Accounts tried per chunk before the request fails.
+const MAX_CHUNK_ATTEMPTS: usize = 3;
+
+/// A Whisper API request (transcription or translation).
+struct AudioRequest {
+    task: AudioTask,
+    audio: Vec<u8>,
+    file_name: String,
+    prompt: Option<String>,
+    language: Option<String>,
+    temperature: Option<f64>,
+    response_format: ResponseFormat,
+    word_timestamps: bool,
+    segment_timestamps: bool,
+}
 
 /// handleaudiotranscriptionrequest (OpenAI Whisper API compatible)
 pub async fn handle_audio_transcription(
     State(state): State<AppState>,
+    multipart: Multipart,
+) -> Result<Response, (StatusCode, String)> {
+    let request = parse_form(multipart, AudioTask::Transcribe).await?;
+    handle_audio(&state, request).await
+}
+
+/// Translate speech into English text (OpenAI Whisper API compatible)
+pub async fn handle_audio_translation(
+    State(state): State<AppState>,
+    multipart: Multipart,
+) -> Result<Response, (StatusCode, String)> {
+    let request = parse_form(multipart, AudioTask::Translate).await?;
+    handle_audio(&state, request).await
+}
+
+async fn parse_form(
     mut multipart: Multipart,
-) -> Result<impl IntoResponse, (StatusCode, String)> {
+    task: AudioTask,
+) -> Result<AudioRequest, (StatusCode, String)> {
     let mut audio_data: Option<Vec<u8>> = None;
     let mut filename: Option<String> = None;
-    let model = "gemini-2.5-flash".to_string();
-    let mut prompt = "Generate a transcript of the speech.".to_string();
+    let mut prompt = None;
+    let mut language = None;
+    let mut temperature = None;
+    let mut response_format = ResponseFormat::Json;
+    let mut granularities: Vec<String> = Vec::new();
 
     // 1. parse multipart/form-data
     while let Some(field) = multipart
@@ -41,144 +88,344 @@ pub async fn handle_audio_transcription(
                 );
             },
             "model" => {
-                // Ignore client-provided model (whisper-1, etc.) — always use gemini-3-pro for audio
+                // Ignore client-provided model (whisper-1, etc.) — always use AUDIO_MODEL
                 let _ = field.text().await;
             },
             "prompt" => {
-                prompt = field.text().await.unwrap_or(prompt);
+                prompt = field.text().await.ok().filter(|p| !p.trim().is_empty());
+            },
+            "language" => {
+                language = field.text().await.ok().filter(|l| !l.trim().is_empty());
+            },
+            "temperature" => {
+                let value = field.text().await.unwrap_or_default();
+                temperature = Some(value.trim().parse::<f64>().map_err(|_| {
+                    (StatusCode::BAD_REQUEST, format!("Invalid temperature: {}", value))
+                })?);
+            },
+            "response_format" => {
+                let value = field.text().await.unwrap_or_default();
+                response_format = ResponseFormat::parse(&value).ok_or_else(|| {
+                    (
+                        StatusCode::BAD_REQUEST,
+                        format!(
+                            "Unsupported response_format '{}': expected json, text, srt, verbose_json or vtt",
+                            value
+                        ),
+                    )
+                })?;
+            },
+            "timestamp_granularities" | "timestamp_granularities[]" => {
+                granularities.push(field.text().await.unwrap_or_default().trim().to_string());
             },
-            // Intentionally ignored: unknown multipart fields (language, response_format, etc.)
             _ => {
                 tracing::trace!("Ignoring unknown multipart field: {}", name);
             },
         }
     }
 
-    let audio_bytes =
-        audio_data.ok_or((StatusCode::BAD_REQUEST, "Missing audiofile".to_string()))?;
+    if let Some(unknown) = granularities.iter().find(|g| *g != "word" && *g != "segment") {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            format!("Unsupported timestamp granularity '{}': expected word or segment", unknown),
+        ));
+    }
 
+    let audio = audio_data.ok_or((StatusCode::BAD_REQUEST, "Missing audiofile".to_string()))?;
     let file_name =
         filename.ok_or((StatusCode::BAD_REQUEST, "Failed to getfilename".to_string()))?;
 
-    info!(
-        "receivedaudiotranscriptionrequest: file={}, size={} bytes, model={}",
+    // Whisper returns segments by default and words only on request
+    let word_timestamps = granularities.iter().any(|g| g == "word");
+    let segment_timestamps =
+        granularities.is_empty() || granularities.iter().any(|g| g == "segment");
+    Ok(AudioRequest {
+        task,
+        audio,
         file_name,
-        audio_bytes.len(),
-        model
+        prompt,
+        // Translations always produce English; the source language is detected
+        language: language.filter(|_| task == AudioTask::Transcribe),
+        temperature,
+        response_format,
+        word_timestamps,
+        segment_timestamps,
+    })
+}
+
+async fn handle_audio(
+    state: &AppState,
+    request: AudioRequest,
+) -> Result<Response, (StatusCode, String)> {
+    info!(
+        "receivedaudio{}request: file={}, size={} bytes, format={:?}",
+        request.task.as_str(),
+        request.file_name,
+        request.audio.len(),
+        request.response_format
     );
 
     // 2. Detect MIME type from magic bytes (more secure than extension)
-    let mime_type = AudioProcessor::detect_mime_type(&file_name, &audio_bytes)
+    let mime_type = AudioProcessor::detect_mime_type(&request.file_name, &request.audio)
         .map_err(|e| (StatusCode::BAD_REQUEST, e))?;
 
-    // 3. verifyfilesize
-    if AudioProcessor::exceeds_size_limit(audio_bytes.len()) {
-        let size_mb = audio_bytes.len() as f64 / (1024.0 * 1024.0);
-        return Err((
-            StatusCode::PAYLOAD_TOO_LARGE,
-            format!(
-                "audiofiletoo large ({:.1} MB)。maxsupport 15 MB (approximately 16 minute MP3)。suggest: 1) compressaudioquality 2) segmentupload",
-                size_mb
-            ),
-        ));
+    // 3. Files over the inline limit are split on silence
+    let chunks = if AudioProcessor::exceeds_size_limit(request.audio.len()) {
+        let audio = request.audio.clone();
+        let extension = std::path::Path::new(&request.file_name)
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(str::to_lowercase);
+        let chunks = tokio::task::spawn_blocking(move || {
+            split_on_silence(audio, extension.as_deref(), ChunkLimits::default())
+        })
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, format!("Chunking failed: {}", e)))?
+        .map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+        info!(
+            "audio is {:.1} MB, split into {} chunks",
+            request.audio.len() as f64 / (1024.0 * 1024.0),
+            chunks.len()
+        );
+        chunks.into_iter().map(|c| (c.offset_secs, c.wav, "audio/wav".to_string())).collect()
+    } else {
+        vec![(0.0, request.audio.clone(), mime_type)]
+    };
+
+    // 4. Transcribe chunks in parallel, preferring a different account for each
+    let in_use = Mutex::new(HashSet::new());
+    let results: Vec<_> = futures::stream::iter(chunks.into_iter().map(|(offset, data, mime)| {
+        let in_use = &in_use;
+        let request = &request;
+        async move {
+            transcribe_chunk(state, request, &data, &mime, in_use)
+                .await
+                .map(|(email, transcript)| (offset, email, transcript))
+        }
+    }))
+    .buffered(MAX_PARALLEL_CHUNKS)
+    .collect()
+    .await;
+
+    let mut parts = Vec::with_capacity(results.len());
+    let mut emails = Vec::new();
+    for result in results {
+        let (offset, email, transcript) = result?;
+        if !emails.contains(&email) {
+            emails.push(email);
+        }
+        parts.push((offset, transcript));
     }
+    let transcript = Transcript::stitch(parts);
+    info!("audio{}complete，return {} character", request.task.as_str(), transcript.text().len());
 
-    // 4. use Inline Data method
-    debug!("use Inline Data methodhandle");
-    let base64_audio = AudioProcessor::encode_to_base64(&audio_bytes);
+    // 5. render in the requested format
+    let body = transcript.render(
+        request.response_format,
+        request.task,
+        request.word_timestamps,
+        request.segment_timestamps,
+    );
+    Ok((
+        StatusCode::OK,
+        [
+            (header::CONTENT_TYPE.as_str(), request.response_format.content_type().to_string()),
+            (X_ACCOUNT_EMAIL, emails.join(",")),
+        ],
+        body,
+    )
+        .into_response())
+}
 
-    // 5. build Gemini request
-    let gemini_request = json!({
-        "contents": [{
-            "role": "user",
-            "parts": [
-                {"text": prompt},
-                {
-                    "inlineData": {
-                        "mimeType": mime_type,
-                        "data": base64_audio
-                    }
-                }
-            ]
-        }]
-    });
+/// Transcribe one chunk, rotating to another account on rate limits.
+async fn transcribe_chunk(
+    state: &AppState,
+    request: &AudioRequest,
+    data: &[u8],
+    mime_type: &str,
+    in_use: &Mutex<HashSet<String>>,
+) -> Result<(String, Transcript), (StatusCode, String)> {
+    let token_manager = &state.token_manager;
+    let gemini_request = build_gemini_request(request, mime_type, data);
+    let mut failed: HashSet<String> = HashSet::new();
+    let mut last_error = String::new();
 
-    // 6. get Token  and upstreamclient
-    let token_manager = state.token_manager;
-    let (access_token, project_id, email, _guard) = token_manager
-        .get_token("text", false, None, &model)
-        .await
-        .map_err(|e| (StatusCode::SERVICE_UNAVAILABLE, e))?;
+    for attempt in 0..MAX_CHUNK_ATTEMPTS {
+        let mut exclude = failed.clone();
+        exclude.extend(in_use.lock().iter().cloned());
+        if exclude.len() >= token_manager.len() {
+            exclude = failed.clone();
+        }
+        let (access_token, project_id, email, _guard) = token_manager
+            .get_token_with_exclusions(
+                "text",
+                attempt > 0,
+                None,
+                AUDIO_MODEL,
+                (!exclude.is_empty()).then_some(&exclude),
+            )
+            .await
+            .map_err(|e| (StatusCode::SERVICE_UNAVAILABLE, e))?;
+        in_use.lock().insert(email.clone());
+        info!("useaccount: {}", email);
 
-    info!("useaccount: {}", email);
+        let result =
+            call_upstream(state, &gemini_request, &access_token, &project_id, &email).await;
+        in_use.lock().remove(&email);
+        match result {
+            Ok(text) => return Ok((email, Transcript::from_model_output(&text))),
+            Err((status, error_text)) => {
+                warn!("Audio upstream error {} on {}: {}", status, email, error_text);
+                if !crate::proxy::retry::should_rotate_account(status) {
+                    return Err((
+                        StatusCode::BAD_GATEWAY,
+                        crate::proxy::common::sanitize_upstream_error(status, &error_text),
+                    ));
+                }
+                if crate::proxy::retry::is_rate_limit_code(status) {
+                    token_manager.mark_rate_limited(&email, status, None, &error_text);
+                }
+                last_error = crate::proxy::common::sanitize_upstream_error(status, &error_text);
+                failed.insert(email);
+            },
+        }
+    }
+    Err((StatusCode::BAD_GATEWAY, last_error))
+}
 
-    // 7. wraprequestas v1internal format
-    // Use model as-is — Antigravity API expects "gemini-3-pro" directly
-    let mapped_model = model.clone();
+/// Send one request; returns the model's text, or the upstream status and error body.
+async fn call_upstream(
+    state: &AppState,
+    gemini_request: &Value,
+    access_token: &str,
+    project_id: &str,
+    email: &str,
+) -> Result<String, (u16, String)> {
+    // wraprequestas v1internal format
     let mut wrapped_body = json!({
         "project": project_id,
         "requestId": format!("audio-{}", Uuid::new_v4()),
         "request": gemini_request,
-        "model": mapped_model,
+        "model": AUDIO_MODEL,
         "userAgent": "antigravity",
         "requestType": "text"
     });
-    crate::proxy::upstream::device_fingerprint::inject_body_fingerprint(&mut wrapped_body, &email);
-
-    // 8. sendrequestto Gemini
-    let upstream = state.upstream.clone();
+    crate::proxy::upstream::device_fingerprint::inject_body_fingerprint(&mut wrapped_body, email);
 
-    let account_proxy = token_manager.get_account_proxy_url(&email);
-    let response = upstream
+    let account_proxy = state.token_manager.get_account_proxy_url(email);
+    let response = state
+        .upstream
         .call_v1_internal_fingerprinted_warp(
             "generateContent",
-            &access_token,
+            access_token,
             wrapped_body,
             None,
-            &email,
+            email,
             std::collections::HashMap::new(),
             None,
             account_proxy.as_deref(),
         )
         .await
-        .map_err(|e| (StatusCode::BAD_GATEWAY, format!("upstreamRequest failed: {}", e)))?;
+        .map_err(|e| (502, format!("upstreamRequest failed: {}", e)))?;
 
     if !response.status().is_success() {
         let status_code = response.status().as_u16();
         let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
-        tracing::warn!("Audio upstream error {}: {}", status_code, error_text);
-        return Err((
-            StatusCode::BAD_GATEWAY,
-            crate::proxy::common::sanitize_upstream_error(status_code, &error_text),
-        ));
+        return Err((status_code, error_text));
     }
 
-    let result: Value = response
-        .json()
-        .await
-        .map_err(|e| (StatusCode::BAD_GATEWAY, format!("parseResponse failed: {}", e)))?;
+    let result: Value =
+        response.json().await.map_err(|e| (502, format!("parseResponse failed: {}", e)))?;
 
-    // 9. extracttextresponse（unwrap v1internal response）
+    // extracttextresponse（unwrap v1internal response）
     let inner_response = result.get("response").unwrap_or(&result);
     let text = inner_response
         .get("candidates")
         .and_then(|c| c.get(0))
         .and_then(|c| c.get("content"))
         .and_then(|c| c.get("parts"))
-        .and_then(|p| p.get(0))
-        .and_then(|p| p.get("text"))
-        .and_then(|t| t.as_str())
-        .unwrap_or("");
+        .and_then(|p| p.as_array())
+        .map(|parts| {
+            parts.iter().filter_map(|p| p.get("text").and_then(|t| t.as_str())).collect::<String>()
+        })
+        .unwrap_or_default();
+    debug!("audio chunk returned {} characters", text.len());
+    Ok(text)
+}
+
+/// Gemini request asking for a timestamped transcript as JSON.
+fn build_gemini_request(request: &AudioRequest, mime_type: &str, data: &[u8]) -> Value {
+    let mut instruction = match request.task {
+        AudioTask::Transcribe => "Generate a verbatim transcript of the speech.".to_string(),
+        AudioTask::Translate => "Translate the speech into English.".to_string(),
+    };
+    if let Some(language) = &request.language {
+        instruction.push_str(&format!(" The audio is in language '{}'.", language));
+    }
+    instruction.push_str(
+        " Split it into segments at sentence or pause boundaries, with start and end times in \
+         seconds from the start of the audio. Set language to the spoken language's English \
+         name in lowercase (e.g. \"english\").",
+    );
+    if request.word_timestamps {
+        instruction.push_str(" Give each segment's words with their start and end times.");
+    }
+    if let Some(prompt) = &request.prompt {
+        instruction.push_str(&format!("\n\nContext and spelling hints: {}", prompt));
+    }
 
-    info!("audiotranscriptioncomplete，return {} character", text.len());
+    let mut segment = json!({
+        "type": "object",
+        "properties": {
+            "start": { "type": "number" },
+            "end": { "type": "number" },
+            "text": { "type": "string" }
+        },
+        "required": ["start", "end", "text"]
+    });
+    if request.word_timestamps {
+        segment["properties"]["words"] = json!({
+            "type": "array",
+            "items": {
+                "type": "object",
+                "properties": {
+                    "word": { "type": "string" },
+                    "start": { "type": "number" },
+                    "end": { "type": "number" }
+                },
+                "required": ["word", "start", "end"]
+            }
+        });
+    }
 
-    // 10. returnstandardformatresponse
-    Ok((
-        StatusCode::OK,
-        [(X_ACCOUNT_EMAIL, email.as_str())],
-        Json(json!({
-            "text": text
-        })),
-    )
-        .into_response())
+    let mut generation_config = json!({
+        "responseMimeType": "application/json",
+        "responseSchema": {
+            "type": "object",
+            "properties": {
+                "language": { "type": "string" },
+                "segments": { "type": "array", "items": segment }
+            },
+            "required": ["segments"]
+        }
+    });
+    if let Some(temperature) = request.temperature {
+        generation_config["temperature"] = json!(temperature);
+    }
+
+    json!({
+        "contents": [{
+            "role": "user",
+            "parts": [
+                { "text": instruction },
+                {
+                    "inlineData": {
+                        "mimeType": mime_type,
+                        "data": AudioProcessor::encode_to_base64(data)
+                    }
+                }
+            ]
+        }],
+        "generationConfig": generation_config
+    })
 }
@@ -0,0 +1,276 @@
+//! Anthropic Message Batches API (`/v1/messages/batches`).
+//!
+//! Requests are stored and executed in the background by [`BatchManager`](crate::proxy::BatchManager)
+//! through [`handle_messages`](super::handle_messages).
+
+use crate::proxy::api_keys::ClientKey;
+use crate::proxy::server::AppState;
+use antigravity_types::models::{
+    Batch, BatchApi, BatchItem, BatchItemStatus, BatchPage, BatchRequestCounts, BatchStatus,
+};
+use axum::{
+    extract::{Extension, Json, Path, Query, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::collections::HashSet;
+
+const DEFAULT_LIST_LIMIT: usize = 20;
+const MAX_LIST_LIMIT: usize = 1000;
+
+#[derive(Debug, Deserialize)]
+pub struct ListBatchesQuery {
+    #[serde(default)]
+    pub limit: Option<usize>,
+    #[serde(default)]
+    pub before_id: Option<String>,
+    #[serde(default)]
+    pub after_id: Option<String>,
+}
+
+pub async fn handle_create_batch(
+    State(state): State<AppState>,
+    client_key: Option<Extension<ClientKey>>,
+    Json(body): Json<Value>,
+) -> Result<Json<Value>, (StatusCode, String)> {
+    let key_id = client_key.map(|Extension(key)| key.id);
+    let api_key = key_id.as_deref().and_then(|id| state.api_keys.get(id));
+    let config = state.batches.config();
+
+    let requests = body
+        .get("requests")
+        .and_then(Value::as_array)
+        .filter(|requests| !requests.is_empty())
+        .ok_or((StatusCode::BAD_REQUEST, "'requests' must be a non-empty array".to_string()))?;
+    if requests.len() > config.max_requests {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            format!("A batch may contain at most {} requests", config.max_requests),
+        ));
+    }
+
+    let mut seen = HashSet::new();
+    let mut items = Vec::with_capacity(requests.len());
+    for (position, request) in requests.iter().enumerate() {
+        let invalid = |message: String| {
+            (StatusCode::BAD_REQUEST, format!("requests.{}: {}", position, message))
+        };
+        let custom_id = request.get("custom_id").and_then(Value::as_str).unwrap_or_default();
+        if !is_valid_custom_id(custom_id) {
+            return Err(invalid(
+                "'custom_id' must be 1-64 letters, digits, '_' or '-'".to_string(),
+            ));
+        }
+        if !seen.insert(custom_id) {
+            return Err(invalid(format!("duplicate custom_id '{}'", custom_id)));
+        }
+        let params = request.get("params").cloned().unwrap_or(Value::Null);
+        validate_params(&params).map_err(invalid)?;
+        let model = params["model"].as_str().unwrap_or_default();
+        if api_key.as_ref().is_some_and(|key| !key.allows_model(model)) {
+            return Err((
+                StatusCode::FORBIDDEN,
+                format!("requests.{}: model '{}' is not allowed for this API key", position, model),
+            ));
+        }
+        items.push(BatchItem::new(position as u32, custom_id.to_string(), params));
+    }
+
+    if let Some(message) = state.batches.budget_rejection(&state, key_id.as_deref(), &items) {
+        return Err((StatusCode::TOO_MANY_REQUESTS, message));
+    }
+
+    let now = chrono::Utc::now().timestamp();
+    let batch = Batch {
+        id: format!("msgbatch_{}", uuid::Uuid::new_v4().simple()),
+        api: BatchApi::Anthropic,
+        endpoint: "/v1/messages".to_string(),
+        api_key_id: key_id,
+        status: BatchStatus::InProgress,
+        metadata: serde_json::Map::new(),
+        input_file_id: None,
+        output_file_id: None,
+        error_file_id: None,
+        created_at: now,
+        expires_at: now + (config.expiry_hours * 3600) as i64,
+        cancel_initiated_at: None,
+        ended_at: None,
+        request_counts: BatchRequestCounts { processing: items.len() as u64, ..Default::default() },
+    };
+    state.batches.submit(&state, &batch, &items).await.map_err(internal_error)?;
+    Ok(Json(batch_json(&batch)))
+}
+
+pub async fn handle_list_batches(
+    State(state): State<AppState>,
+    client_key: Option<Extension<ClientKey>>,
+    Query(query): Query<ListBatchesQuery>,
+) -> Result<Json<Value>, (StatusCode, String)> {
+    let limit = query.limit.unwrap_or(DEFAULT_LIST_LIMIT);
+    if !(1..=MAX_LIST_LIMIT).contains(&limit) {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            format!("'limit' must be between 1 and {}", MAX_LIST_LIMIT),
+        ));
+    }
+    let backwards = query.before_id.is_some();
+    let page = BatchPage { after_id: query.after_id, before_id: query.before_id, limit };
+    let key_id = client_key.as_ref().map(|Extension(key)| key.id.as_str());
+
+    let mut batches =
+        state.batches.list(BatchApi::Anthropic, key_id, &page).await.map_err(internal_error)?;
+    let has_more = batches.len() > limit;
+    if has_more {
+        // The extra batch is on the far side of the page from the cursor
+        if backwards {
+            batches.remove(0);
+        } else {
+            batches.truncate(limit);
+        }
+    }
+
+    Ok(Json(json!({
+        "data": batches.iter().map(batch_json).collect::<Vec<_>>(),
+        "has_more": has_more,
+        "first_id": batches.first().map(|b| b.id.clone()),
+        "last_id": batches.last().map(|b| b.id.clone()),
+    })))
+}
+
+pub async fn handle_get_batch(
+    State(state): State<AppState>,
+    client_key: Option<Extension<ClientKey>>,
+    Path(id): Path<String>,
+) -> Result<Json<Value>, (StatusCode, String)> {
+    let batch = visible_batch(&state, &id, client_key.as_ref()).await?;
+    Ok(Json(batch_json(&batch)))
+}
+
+pub async fn handle_cancel_batch(
+    State(state): State<AppState>,
+    client_key: Option<Extension<ClientKey>>,
+    Path(id): Path<String>,
+) -> Result<Json<Value>, (StatusCode, String)> {
+    visible_batch(&state, &id, client_key.as_ref()).await?;
+    let batch = state
+        .batches
+        .cancel(&state, &id)
+        .await
+        .map_err(internal_error)?
+        .ok_or_else(|| not_found(&id))?;
+    Ok(Json(batch_json(&batch)))
+}
+
+/// Results as JSON Lines, in submission order. Only available once the batch has ended.
+pub async fn handle_batch_results(
+    State(state): State<AppState>,
+    client_key: Option<Extension<ClientKey>>,
+    Path(id): Path<String>,
+) -> Result<Response, (StatusCode, String)> {
+    let batch = visible_batch(&state, &id, client_key.as_ref()).await?;
+    if batch.status != BatchStatus::Ended {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            format!("Batch '{}' is still processing; results are available once it has ended", id),
+        ));
+    }
+
+    let items = state.batches.items(&id).await.map_err(internal_error)?;
+    let mut body = String::new();
+    for item in &items {
+        if let Some(result) = result_json(item) {
+            body.push_str(&json!({ "custom_id": item.custom_id, "result": result }).to_string());
+            body.push('\n');
+        }
+    }
+    Ok(([(header::CONTENT_TYPE, "application/x-jsonl")], body).into_response())
+}
+
+async fn visible_batch(
+    state: &AppState,
+    id: &str,
+    client_key: Option<&Extension<ClientKey>>,
+) -> Result<Batch, (StatusCode, String)> {
+    let key_id = client_key.map(|Extension(key)| key.id.as_str());
+    state
+        .batches
+        .get_visible(id, key_id)
+        .await
+        .map_err(internal_error)?
+        .filter(|batch| batch.api == BatchApi::Anthropic)
+        .ok_or_else(|| not_found(id))
+}
+
+fn is_valid_custom_id(id: &str) -> bool {
+    (1..=64).contains(&id.len())
+        && id.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+/// Minimal shape check of a `/v1/messages` body; the rest is validated when it runs.
+fn validate_params(params: &Value) -> Result<(), String> {
+    if !params.is_object() {
+        return Err("'params' must be an object".to_string());
+    }
+    if !params.get("model").is_some_and(Value::is_string) {
+        return Err("'params.model' is required".to_string());
+    }
+    if !params.get("messages").is_some_and(Value::is_array) {
+        return Err("'params.messages' must be an array".to_string());
+    }
+    if params.get("max_tokens").and_then(Value::as_u64).is_none_or(|n| n == 0) {
+        return Err("'params.max_tokens' must be a positive integer".to_string());
+    }
+    if params.get("stream").and_then(Value::as_bool) == Some(true) {
+        return Err("streaming is not supported in batches".to_string());
+    }
+    Ok(())
+}
+
+fn batch_json(batch: &Batch) -> Value {
+    let counts = &batch.request_counts;
+    let ended = batch.status == BatchStatus::Ended;
+    json!({
+        "id": batch.id,
+        "type": "message_batch",
+        "processing_status": batch.status.as_str(),
+        "request_counts": {
+            "processing": counts.processing,
+            "succeeded": counts.succeeded,
+            "errored": counts.errored,
+            "canceled": counts.canceled,
+            "expired": counts.expired,
+        },
+        "ended_at": batch.ended_at.and_then(rfc3339),
+        "created_at": rfc3339(batch.created_at),
+        "expires_at": rfc3339(batch.expires_at),
+        "archived_at": null,
+        "cancel_initiated_at": batch.cancel_initiated_at.and_then(rfc3339),
+        "results_url": ended.then(|| format!("/v1/messages/batches/{}/results", batch.id)),
+    })
+}
+
+fn result_json(item: &BatchItem) -> Option<Value> {
+    match item.status {
+        BatchItemStatus::Pending => None,
+        BatchItemStatus::Succeeded => Some(json!({ "type": "succeeded", "message": item.result })),
+        BatchItemStatus::Errored => Some(json!({ "type": "errored", "error": item.result })),
+        BatchItemStatus::Canceled => Some(json!({ "type": "canceled" })),
+        BatchItemStatus::Expired => Some(json!({ "type": "expired" })),
+    }
+}
+
+fn rfc3339(timestamp: i64) -> Option<String> {
+    chrono::DateTime::from_timestamp(timestamp, 0)
+        .map(|t| t.to_rfc3339_opts(chrono::SecondsFormat::Secs, true))
+}
+
+fn not_found(id: &str) -> (StatusCode, String) {
+    (StatusCode::NOT_FOUND, format!("Batch '{}' not found", id))
+}
+
+fn internal_error(e: String) -> (StatusCode, String) {
+    tracing::error!("[Batch] Store error: {}", e);
+    (StatusCode::INTERNAL_SERVER_ERROR, e)
+}
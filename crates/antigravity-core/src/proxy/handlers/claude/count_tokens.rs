@@ -0,0 +1,131 @@
+//! Native `/v1/messages/count_tokens` support (no z.ai required).
+//!
+//! The Claude request is transformed exactly like a real `/v1/messages` call and sent to
+//! the upstream `countTokens` method. If no account is available (or the upstream call
+//! fails) the count is estimated locally and corrected by the [`EstimationCalibrator`].
+//!
+//! [`EstimationCalibrator`]: crate::proxy::mappers::estimation_calibrator::EstimationCalibrator
+
+use crate::proxy::common::header_constants::{X_ACCOUNT_EMAIL, X_MAPPED_MODEL};
+use crate::proxy::mappers::claude::{transform_claude_request_in, ClaudeRequest};
+use crate::proxy::mappers::context_manager::ContextManager;
+use crate::proxy::mappers::estimation_calibrator::get_calibrator;
+use crate::proxy::server::AppState;
+use axum::{
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde_json::{json, Value};
+
+use super::request_validation::parse_request;
+
+/// Count input tokens for a Claude request, returning Anthropic's `{input_tokens}` shape.
+pub async fn count_tokens_native(state: &AppState, body: Value) -> Response {
+    let request = match parse_request(body) {
+        Ok(r) => r,
+        Err(resp) => return resp,
+    };
+
+    let mapped_model = match crate::proxy::common::resolve_model_route(
+        &request.model,
+        &*state.custom_mapping.read().await,
+    ) {
+        Ok((model, _reason)) => model,
+        Err(e) => {
+            tracing::debug!("[CountTokens] Unroutable model {}: {}", request.model, e);
+            request.model.clone()
+        },
+    };
+
+    // Thinking budget is output, not input
+    let estimate = {
+        let mut input_only = request.clone();
+        input_only.thinking = None;
+        ContextManager::estimate_token_usage(&input_only)
+    };
+
+    match count_upstream(state, &request, &mapped_model).await {
+        Ok((actual, email)) => {
+            get_calibrator().record(estimate, actual);
+            let mut response = Json(json!({ "input_tokens": actual })).into_response();
+            if let Ok(v) = email.parse() {
+                response.headers_mut().insert(X_ACCOUNT_EMAIL, v);
+            }
+            if let Ok(v) = mapped_model.parse() {
+                response.headers_mut().insert(X_MAPPED_MODEL, v);
+            }
+            response
+        },
+        Err(e) => {
+            let calibrated = get_calibrator().calibrate(estimate);
+            tracing::debug!(
+                "[CountTokens] Falling back to estimate for {} ({}): {} tokens",
+                mapped_model,
+                e,
+                calibrated
+            );
+            Json(json!({ "input_tokens": calibrated })).into_response()
+        },
+    }
+}
+
+/// Ask the upstream for the exact count. Returns `(total_tokens, account_email)`.
+async fn count_upstream(
+    state: &AppState,
+    request: &ClaudeRequest,
+    mapped_model: &str,
+) -> Result<(u32, String), String> {
+    let (access_token, project_id, email, _guard) =
+        state.token_manager.get_token("claude", false, None, mapped_model).await?;
+
+    let mut request_with_mapped = request.clone();
+    request_with_mapped.model = mapped_model.to_string();
+    let body = transform_claude_request_in(&request_with_mapped, &project_id, false)?;
+
+    let response = state
+        .upstream
+        .call_v1_internal(
+            &format!("models/{}:countTokens", mapped_model),
+            &access_token,
+            body,
+            None,
+        )
+        .await?;
+
+    let status = response.status();
+    if !status.is_success() {
+        let error_text = response.text().await.unwrap_or_default();
+        tracing::warn!(
+            "[CountTokens] Upstream {} for {}: {}",
+            status.as_u16(),
+            mapped_model,
+            error_text
+        );
+        return Err(format!("upstream returned {}", status.as_u16()));
+    }
+
+    let resp: Value = response.json().await.map_err(|e| format!("parse error: {}", e))?;
+    extract_total_tokens(&resp)
+        .map(|total| (total, email))
+        .ok_or_else(|| "upstream response has no totalTokens".to_string())
+}
+
+/// `totalTokens` from a countTokens response (bare or wrapped in `response`).
+fn extract_total_tokens(resp: &Value) -> Option<u32> {
+    resp.get("totalTokens")
+        .or_else(|| resp.get("response").and_then(|r| r.get("totalTokens")))
+        .and_then(Value::as_u64)
+        .and_then(|n| u32::try_from(n).ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extracts_bare_and_wrapped_total_tokens() {
+        assert_eq!(extract_total_tokens(&json!({"totalTokens": 42})), Some(42));
+        assert_eq!(extract_total_tokens(&json!({"response": {"totalTokens": 7}})), Some(7));
+        assert_eq!(extract_total_tokens(&json!({"response": {}})), None);
+    }
+}
@@ -1,20 +1,97 @@
 //! Dispatch mode decision logic for Claude messages handler
 
+use crate::proxy::common::header_constants::{X_MAPPED_MODEL, X_MAPPING_REASON};
 use crate::proxy::mappers::claude::ClaudeRequest;
 use crate::proxy::server::AppState;
-use axum::http::HeaderMap;
-use axum::response::Response;
+use crate::proxy::ZaiDispatchMode;
+use axum::http::{HeaderMap, StatusCode};
+use axum::response::{IntoResponse, Response};
+use std::sync::atomic::Ordering;
 
 pub struct DispatchDecision {
+    /// Forward this request to z.ai instead of the Google pool.
     pub use_zai: bool,
+    /// Forward to z.ai if the Google pool turns out to be exhausted.
+    pub fallback_to_zai: bool,
+    /// Dispatch tag recorded in the monitor log's `mapping_reason`, if z.ai is active.
+    pub reason: Option<&'static str>,
+}
+
+impl DispatchDecision {
+    const GOOGLE: Self = Self { use_zai: false, fallback_to_zai: false, reason: None };
+
+    /// Combine the model route reason with the dispatch tag for the Google path.
+    pub fn annotate(&self, route_reason: String) -> String {
+        match self.reason {
+            Some(tag) => format!("{}; {}", route_reason, tag),
+            None => route_reason,
+        }
+    }
 }
 
 pub async fn decide_dispatch_mode(
-    _state: &AppState,
-    _request: &ClaudeRequest,
-    _trace_id: &str,
+    state: &AppState,
+    request: &ClaudeRequest,
+    trace_id: &str,
 ) -> DispatchDecision {
-    DispatchDecision { use_zai: false }
+    let mode = {
+        let zai = state.zai.read().await;
+        if !zai.enabled || zai.api_key.trim().is_empty() {
+            return DispatchDecision::GOOGLE;
+        }
+        zai.dispatch_mode
+    };
+
+    let decision = match mode {
+        ZaiDispatchMode::Off => return DispatchDecision::GOOGLE,
+        ZaiDispatchMode::Exclusive => DispatchDecision {
+            use_zai: true,
+            fallback_to_zai: false,
+            reason: Some("zai:exclusive"),
+        },
+        ZaiDispatchMode::Pooled => {
+            // z.ai takes one slot in the rotation alongside every Google account
+            let slots = state.token_manager.len() + 1;
+            let turn = state.provider_rr.fetch_add(1, Ordering::Relaxed) % slots;
+            if turn == 0 {
+                DispatchDecision {
+                    use_zai: true,
+                    fallback_to_zai: false,
+                    reason: Some("zai:pooled"),
+                }
+            } else {
+                DispatchDecision {
+                    use_zai: false,
+                    fallback_to_zai: false,
+                    reason: Some("google:pooled"),
+                }
+            }
+        },
+        ZaiDispatchMode::Fallback => {
+            if state.token_manager.has_available_account("", "").await {
+                DispatchDecision {
+                    use_zai: false,
+                    fallback_to_zai: true,
+                    reason: Some("google:fallback"),
+                }
+            } else {
+                DispatchDecision {
+                    use_zai: true,
+                    fallback_to_zai: false,
+                    reason: Some("zai:fallback"),
+                }
+            }
+        },
+    };
+
+    tracing::debug!(
+        "[{}] Dispatch ({}): {} -> {}",
+        trace_id,
+        mode,
+        request.model,
+        if decision.use_zai { "z.ai" } else { "google" }
+    );
+    decision
 }
 
 /// Strip Gemini-specific dummy signatures from thinking blocks before sending to Anthropic API.
@@ -84,3 +161,25 @@ pub async fn forward_to_zai(
     )
     .await)
 }
+
+/// Forward to z.ai and tag the response for the monitor log.
+pub async fn dispatch_to_zai(
+    state: &AppState,
+    headers: &HeaderMap,
+    request: &ClaudeRequest,
+    reason: &str,
+) -> Response {
+    let response = match forward_to_zai(state, headers, request).await {
+        Ok(response) => response,
+        Err(e) => {
+            tracing::error!("{}", e);
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        },
+    };
+
+    let mapped = {
+        let zai = state.zai.read().await;
+        crate::proxy::providers::zai_anthropic::map_model_for_zai(&request.model, &zai)
+    };
+    ([(X_MAPPING_REASON, reason), (X_MAPPED_MODEL, mapped.as_str())], response).into_response()
+}
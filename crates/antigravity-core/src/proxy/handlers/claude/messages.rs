@@ -1,9 +1,12 @@
 use crate::proxy::common::header_constants::X_FORCE_ACCOUNT;
+use crate::proxy::context_cache::{find_breakpoints, AppliedCache, ContextCache};
 use crate::proxy::mappers::claude::{
     clean_cache_control_from_messages, close_tool_loop_for_thinking,
     filter_invalid_thinking_blocks_with_family, merge_consecutive_messages, ClaudeRequest,
 };
+use crate::proxy::providers::compatible::try_forward_to_provider;
 use crate::proxy::server::AppState;
+use antigravity_types::models::ProviderProtocol;
 use axum::http::{HeaderMap, StatusCode};
 use axum::{
     extract::{Json, State},
@@ -12,7 +15,7 @@ use axum::{
 use serde_json::{json, Value};
 use tracing::{debug, info};
 
-use super::dispatch::{decide_dispatch_mode, forward_to_zai};
+use super::dispatch::{decide_dispatch_mode, dispatch_to_zai};
 use super::error_handling::{handle_upstream_error, ClaudeErrorAction, ErrorContext};
 use super::preprocessing::{extract_meaningful_message, log_request_debug, log_request_info};
 use super::request_preparation::prepare_request;
@@ -22,8 +25,12 @@ use super::streaming::{handle_streaming_response, ClaudeStreamResult, StreamingC
 use super::token_selection::acquire_token;
 use super::upstream_call::prepare_upstream_call;
 use super::warmup::{create_warmup_response, is_warmup_request};
-use crate::proxy::retry::{extract_error_info, record_request_success, MAX_RETRY_ATTEMPTS};
+use crate::proxy::retry::{
+    extract_error_info, hedged_call, record_request_success, retarget_body, HedgeTarget, Leg,
+    MAX_RETRY_ATTEMPTS,
+};
 use crate::proxy::session_manager::SessionManager;
+use crate::proxy::telemetry::current_trace_id;
 
 pub async fn handle_messages(
     State(state): State<AppState>,
@@ -35,7 +42,26 @@ pub async fn handle_messages(
 
     tracing::debug!("handle_messages called. Body JSON len: {}", body.to_string().len());
 
-    let trace_id = generate_trace_id();
+    let trace_id = current_trace_id().unwrap_or_else(generate_trace_id);
+
+    if let Some(response) = try_forward_to_provider(
+        &state,
+        &headers,
+        &body,
+        ProviderProtocol::Anthropic,
+        "/v1/messages",
+    )
+    .await
+    {
+        return response;
+    }
+
+    // Breakpoints are read from the raw body: parsing drops most `cache_control` markers
+    let breakpoints = if state.experimental.read().await.enable_context_caching {
+        find_breakpoints(&body)
+    } else {
+        None
+    };
 
     let mut request: ClaudeRequest = match parse_request(body) {
         Ok(r) => r,
@@ -58,13 +84,8 @@ pub async fn handle_messages(
     }
 
     if dispatch.use_zai {
-        match forward_to_zai(&state, &headers, &request).await {
-            Ok(response) => return response,
-            Err(e) => {
-                tracing::error!("{}", e);
-                return StatusCode::INTERNAL_SERVER_ERROR.into_response();
-            },
-        }
+        let reason = dispatch.reason.unwrap_or("zai");
+        return dispatch_to_zai(&state, &headers, &request, reason).await;
     }
 
     let scaling_enabled = state.experimental.read().await.enable_usage_scaling;
@@ -96,7 +117,7 @@ pub async fn handle_messages(
             &request_for_body.model,
             &*state.custom_mapping.read().await,
         ) {
-            Ok(result) => result,
+            Ok((mapped, reason)) => (mapped, dispatch.annotate(reason)),
             Err(e) => {
                 return (
                     StatusCode::BAD_REQUEST,
@@ -132,6 +153,10 @@ pub async fn handle_messages(
         .await
         {
             Ok(r) => r,
+            Err(_) if dispatch.fallback_to_zai => {
+                info!("[{}] No Google account available, falling back to z.ai", trace_id);
+                return dispatch_to_zai(&state, &headers, &request, "zai:fallback").await;
+            },
             Err(response) => return response,
         };
         let access_token = token_result.access_token;
@@ -139,7 +164,6 @@ pub async fn handle_messages(
         let email = token_result.email;
         let _guard = token_result.guard;
 
-        last_email = Some(email.clone());
         info!("✓ Using account: {} (type: {})", email, config.request_type);
 
         let prepared = match prepare_request(
@@ -156,28 +180,65 @@ pub async fn handle_messages(
         };
         let mapped_model = prepared.mapped_model;
         let request_with_mapped = prepared.request_with_mapped;
-        let mut gemini_body = prepared.gemini_body;
-        crate::proxy::upstream::device_fingerprint::inject_body_fingerprint(
-            &mut gemini_body,
-            &email,
-        );
+        let gemini_body = prepared.gemini_body;
 
         let call_config = prepare_upstream_call(&request, &request_with_mapped, &trace_id);
-
-        let account_proxy = token_manager.get_account_proxy_url(&email);
-        let response = match upstream
-            .call_v1_internal_fingerprinted_warp(
-                call_config.method,
-                &access_token,
-                gemini_body,
-                call_config.query,
-                &email,
-                call_config.extra_headers.clone(),
-                None,
-                account_proxy.as_deref(),
-            )
-            .await
-        {
+        let applied_caches: parking_lot::Mutex<std::collections::HashMap<String, AppliedCache>> =
+            parking_lot::Mutex::default();
+
+        let hedged = hedged_call(
+            &token_manager,
+            &state.adaptive_limits,
+            HedgeTarget {
+                request_type: &config.request_type,
+                model: &config.final_model,
+                exclude: &attempted_accounts,
+                pinned: force_account.is_some(),
+            },
+            Leg { email: email.clone(), access_token, project_id },
+            |leg| {
+                let upstream = upstream.clone();
+                let mut body = retarget_body(&gemini_body, &leg);
+                let account_proxy = token_manager.get_account_proxy_url(&leg.email);
+                let (method, query) = (call_config.method, call_config.query);
+                let extra_headers = call_config.extra_headers.clone();
+                let (breakpoints, applied_caches) = (breakpoints.as_ref(), &applied_caches);
+                async move {
+                    if let Some(breakpoints) = breakpoints {
+                        if let Some(applied) = ContextCache::global()
+                            .apply(
+                                &upstream,
+                                &leg,
+                                account_proxy.as_deref(),
+                                breakpoints,
+                                &mut body,
+                            )
+                            .await
+                        {
+                            applied_caches.lock().insert(leg.email.clone(), applied);
+                        }
+                    }
+                    upstream
+                        .call_v1_internal_fingerprinted_warp(
+                            method,
+                            &leg.access_token,
+                            body,
+                            query,
+                            &leg.email,
+                            extra_headers,
+                            None,
+                            account_proxy.as_deref(),
+                        )
+                        .await
+                }
+            },
+        )
+        .await;
+        let email = hedged.email;
+        let _hedge_guard = hedged.guard;
+        let context_cache = applied_caches.into_inner().remove(&email);
+        last_email = Some(email.clone());
+        let response = match hedged.result {
             Ok(r) => r,
             Err(e) => {
                 last_error = crate::proxy::common::UpstreamError::ConnectionError(e.clone());
@@ -194,6 +255,10 @@ pub async fn handle_messages(
         // success
         if status.is_success() {
             record_request_success(&token_manager, &state, &email, &session_id_str);
+            if context_cache.is_some() {
+                token_manager.bind_session_to_cache(&session_id_str, &email).await;
+            }
+            let cache_created = context_cache.as_ref().is_some_and(|cache| cache.created);
 
             let context_limit =
                 crate::proxy::mappers::claude::token_scaling::get_context_limit_for_model(
@@ -217,6 +282,7 @@ pub async fn handle_messages(
                     context_limit,
                     estimated_tokens,
                     client_wants_stream: call_config.client_wants_stream,
+                    cache_created,
                 };
                 match handle_streaming_response(response, &ctx).await {
                     ClaudeStreamResult::Success(resp) => return resp,
@@ -237,6 +303,7 @@ pub async fn handle_messages(
                     reason: reason.clone(),
                     scaling_enabled,
                     context_limit,
+                    cache_created,
                 };
                 return handle_nonstreaming_success(response, &request_with_mapped, &ctx).await;
             }
@@ -244,6 +311,12 @@ pub async fn handle_messages(
 
         let (err_info, upstream_err) = extract_error_info(response).await;
         last_error = upstream_err;
+        if let Some(cache) =
+            context_cache.as_ref().filter(|_| matches!(status.as_u16(), 400 | 403 | 404))
+        {
+            // The retry registers a fresh cache rather than reusing one the upstream refused
+            ContextCache::global().invalidate(&cache.name);
+        }
         tracing::error!(
             "[{}] Upstream Error Response ({}): {}",
             trace_id,
@@ -288,5 +361,12 @@ pub async fn handle_messages(
         }
     }
 
+    if dispatch.fallback_to_zai {
+        info!(
+            "[{}] Google pool exhausted after {} attempts, falling back to z.ai",
+            trace_id, attempt
+        );
+        return dispatch_to_zai(&state, &headers, &request, "zai:fallback").await;
+    }
     all_retries_exhausted_error(max_attempts, &last_error, last_email.as_deref())
 }
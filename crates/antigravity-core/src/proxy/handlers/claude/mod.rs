@@ -4,6 +4,8 @@
 //! and handling streaming/non-streaming responses.
 
 mod background_detection;
+mod batches;
+mod count_tokens;
 mod dispatch;
 mod error_handling;
 mod error_recovery;
@@ -18,5 +20,9 @@ mod token_selection;
 mod upstream_call;
 mod warmup;
 
+pub use batches::{
+    handle_batch_results, handle_cancel_batch, handle_create_batch, handle_get_batch,
+    handle_list_batches,
+};
 pub use messages::handle_messages;
 pub use models::{handle_count_tokens, handle_list_models};
@@ -55,15 +55,5 @@ pub async fn handle_count_tokens(
         .await;
     }
 
-    // Non-ZAI fallback: Anthropic format cannot be forwarded to Gemini countTokens
-    (
-        axum::http::StatusCode::NOT_IMPLEMENTED,
-        Json(json!({
-            "error": {
-                "type": "not_implemented",
-                "message": "Token counting requires ZAI mode to be enabled"
-            }
-        })),
-    )
-        .into_response()
+    super::count_tokens::count_tokens_native(&state, body).await
 }
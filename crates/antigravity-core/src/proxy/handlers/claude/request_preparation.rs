@@ -46,7 +46,9 @@ pub async fn prepare_request(
     let _config =
         resolve_request_config(&request_for_body.model, &mapped_model, &tools_val, None, None);
 
-    let background_task_type = detect_background_task_type(request_for_body);
+    // A model chosen by a routing rule is not downgraded for background tasks
+    let background_task_type = detect_background_task_type(request_for_body)
+        .filter(|_| crate::proxy::routing_rules::rewritten_model().is_none());
     let mut request_with_mapped = request_for_body.clone();
 
     if let Some(task_type) = background_task_type {
@@ -16,6 +16,8 @@ pub struct ResponseContext {
     pub reason: String,
     pub scaling_enabled: bool,
     pub context_limit: u32,
+    /// Whether this request created its upstream context cache
+    pub cache_created: bool,
 }
 
 pub async fn handle_nonstreaming_success(
@@ -51,7 +53,7 @@ pub async fn handle_nonstreaming_success(
     };
 
     let s_id_owned = Some(ctx.session_id.clone());
-    let claude_response = match transform_response(
+    let mut claude_response = match transform_response(
         &gemini_response,
         ctx.scaling_enabled,
         ctx.context_limit,
@@ -65,6 +67,10 @@ pub async fn handle_nonstreaming_success(
         },
     };
 
+    if ctx.cache_created {
+        claude_response.usage.report_cache_creation();
+    }
+
     let cache_info = if let Some(cached) = claude_response.usage.cache_read_input_tokens {
         format!(", Cached: {}", cached)
     } else {
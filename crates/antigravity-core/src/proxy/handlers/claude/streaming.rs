@@ -23,6 +23,8 @@ pub struct StreamingContext {
     pub context_limit: u32,
     pub estimated_tokens: Option<u32>,
     pub client_wants_stream: bool,
+    /// Whether this request created its upstream context cache
+    pub cache_created: bool,
 }
 
 pub enum ClaudeStreamResult {
@@ -45,6 +47,7 @@ pub async fn handle_streaming_response(
         ctx.scaling_enabled,
         ctx.context_limit,
         ctx.estimated_tokens,
+        ctx.cache_created,
     );
 
     let peek_config = PeekConfig::default();
@@ -0,0 +1,117 @@
+//! Embedding handlers: OpenAI `/v1/embeddings` and Gemini `:embedContent` /
+//! `:batchEmbedContents`.
+//!
+//! Both go through [`call_with_rotation`], which rotates accounts on rate limits and
+//! auth failures like the generation handlers do.
+
+use axum::{
+    extract::State,
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde_json::{json, Value};
+use tracing::info;
+
+use crate::proxy::common::header_constants::{X_ACCOUNT_EMAIL, X_MAPPED_MODEL, X_MAPPING_REASON};
+use crate::proxy::common::resolve_model_route;
+use crate::proxy::mappers::openai::embeddings::{
+    build_batch_request, build_openai_response, extract_embeddings, EmbeddingRequest,
+};
+use crate::proxy::retry::{call_with_rotation, RotatedCall};
+use crate::proxy::server::AppState;
+
+/// OpenAI-compatible `/v1/embeddings`.
+pub async fn handle_embeddings(
+    State(state): State<AppState>,
+    Json(body): Json<Value>,
+) -> Result<Response, (StatusCode, String)> {
+    let request: EmbeddingRequest = serde_json::from_value(body)
+        .map_err(|e| (StatusCode::BAD_REQUEST, format!("Invalid request: {}", e)))?;
+    request.validate().map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+
+    let (mapped_model, reason) =
+        resolve_model_route(&request.model, &*state.custom_mapping.read().await)
+            .map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+    info!(
+        "[Embeddings] {} inputs: {} -> {}",
+        request.input.texts().len(),
+        request.model,
+        mapped_model
+    );
+
+    let upstream_body = build_batch_request(&request, &mapped_model);
+    let result = match call_embed(&state, &mapped_model, "batchEmbedContents", upstream_body).await
+    {
+        Ok(result) => result,
+        Err(response) => return Ok(response),
+    };
+
+    let embeddings = extract_embeddings(&result.body).map_err(|e| (StatusCode::BAD_GATEWAY, e))?;
+    Ok((
+        StatusCode::OK,
+        [
+            (X_ACCOUNT_EMAIL, result.email.as_str()),
+            (X_MAPPED_MODEL, mapped_model.as_str()),
+            (X_MAPPING_REASON, reason.as_str()),
+        ],
+        Json(build_openai_response(&request, embeddings)),
+    )
+        .into_response())
+}
+
+/// Native Gemini `models/{model}:embedContent` and `:batchEmbedContents`.
+pub async fn handle_gemini_embed(
+    state: &AppState,
+    model_name: &str,
+    method: &str,
+    mut body: Value,
+) -> Result<Response, (StatusCode, String)> {
+    let (mapped_model, reason) =
+        resolve_model_route(model_name, &*state.custom_mapping.read().await)
+            .map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+    info!("[Gemini] {}: {} -> {}", method, model_name, mapped_model);
+
+    // Each batch entry names its model; point them all at the routed one
+    if method == "batchEmbedContents" {
+        let Some(requests) = body.get_mut("requests").and_then(Value::as_array_mut) else {
+            return Err((StatusCode::BAD_REQUEST, "'requests' must be an array".to_string()));
+        };
+        for entry in requests {
+            entry["model"] = json!(format!("models/{}", mapped_model));
+        }
+    }
+
+    let result = match call_embed(state, &mapped_model, method, body).await {
+        Ok(result) => result,
+        Err(response) => return Ok(response),
+    };
+    Ok((
+        StatusCode::OK,
+        [
+            (X_ACCOUNT_EMAIL, result.email.as_str()),
+            (X_MAPPED_MODEL, mapped_model.as_str()),
+            (X_MAPPING_REASON, reason.as_str()),
+        ],
+        Json(result.body),
+    )
+        .into_response())
+}
+
+async fn call_embed(
+    state: &AppState,
+    mapped_model: &str,
+    method: &str,
+    body: Value,
+) -> Result<RotatedCall, Response> {
+    call_with_rotation(state, "Embeddings", "text", mapped_model, method, |project_id| {
+        json!({
+            "project": project_id,
+            "requestId": format!("embed-{}", uuid::Uuid::new_v4()),
+            "request": body,
+            "model": mapped_model,
+            "userAgent": "antigravity",
+        })
+    })
+    .await
+}
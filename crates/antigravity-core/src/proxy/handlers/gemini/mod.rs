@@ -8,7 +8,8 @@ pub use models::{handle_count_tokens, handle_get_model, handle_list_models};
 use crate::proxy::common::header_constants::{X_ACCOUNT_EMAIL, X_FORCE_ACCOUNT, X_MAPPED_MODEL};
 use crate::proxy::common::{sanitize_upstream_error, UpstreamError};
 use crate::proxy::retry::{
-    build_exhaustion_response, extract_error_info, record_request_success, MAX_RETRY_ATTEMPTS,
+    build_exhaustion_response, extract_error_info, hedged_call, record_request_success,
+    retarget_body, HedgeTarget, Leg, MAX_RETRY_ATTEMPTS,
 };
 use crate::proxy::{
     mappers::gemini::{unwrap_response, wrap_request},
@@ -39,9 +40,19 @@ pub async fn handle_generate(
         Some((m, action)) => (m.to_string(), action.to_string()),
         None => (model_action, "generateContent".to_string()),
     };
+    let model_name = crate::proxy::routing_rules::rewritten_model().unwrap_or(model_name);
 
     info!("[Gemini] Request: {}/{}", model_name, method);
 
+    if method == "embedContent" || method == "batchEmbedContents" {
+        return crate::proxy::handlers::embeddings::handle_gemini_embed(
+            &state,
+            &model_name,
+            &method,
+            body,
+        )
+        .await;
+    }
     if method != "generateContent" && method != "streamGenerateContent" {
         return Err((StatusCode::BAD_REQUEST, format!("Unsupported method: {}", method)));
     }
@@ -134,30 +145,45 @@ pub async fn handle_generate(
             }
         };
 
-        last_email = Some(email.clone());
         info!("[Gemini] Account: {} (type: {})", email, config.request_type);
 
-        let mut wrapped_body = wrap_request(&body, &project_id, &mapped_model, Some(&session_id));
-        crate::proxy::upstream::device_fingerprint::inject_body_fingerprint(
-            &mut wrapped_body,
-            &email,
-        );
+        let wrapped_body = wrap_request(&body, &project_id, &mapped_model, Some(&session_id));
         let query_string = if is_stream { Some("alt=sse") } else { None };
         let upstream_method = if is_stream { "streamGenerateContent" } else { "generateContent" };
 
-        let account_proxy = token_manager.get_account_proxy_url(&email);
-        let response = match state
-            .upstream
-            .call_v1_internal_fingerprinted(
-                upstream_method,
-                &access_token,
-                wrapped_body,
-                query_string,
-                &email,
-                account_proxy.as_deref(),
-            )
-            .await
-        {
+        let hedged = hedged_call(
+            &token_manager,
+            &state.adaptive_limits,
+            HedgeTarget {
+                request_type: &config.request_type,
+                model: &config.final_model,
+                exclude: &attempted_accounts,
+                pinned: force_account.is_some(),
+            },
+            Leg { email: email.clone(), access_token, project_id },
+            |leg| {
+                let upstream = state.upstream.clone();
+                let body = retarget_body(&wrapped_body, &leg);
+                let account_proxy = token_manager.get_account_proxy_url(&leg.email);
+                async move {
+                    upstream
+                        .call_v1_internal_fingerprinted(
+                            upstream_method,
+                            &leg.access_token,
+                            body,
+                            query_string,
+                            &leg.email,
+                            account_proxy.as_deref(),
+                        )
+                        .await
+                }
+            },
+        )
+        .await;
+        let email = hedged.email;
+        let _hedge_guard = hedged.guard;
+        last_email = Some(email.clone());
+        let response = match hedged.result {
             Ok(r) => r,
             Err(e) => {
                 last_error = UpstreamError::TokenAcquisition(e.clone());
@@ -14,6 +14,7 @@ use crate::proxy::mappers::gemini::unwrap_response;
 /// Peek first SSE chunk with retry logic (Issue #859)
 /// Returns the first meaningful data chunk, skipping heartbeats.
 /// On timeout/empty/error, returns Err for account rotation.
+#[tracing::instrument(name = "peek", skip_all)]
 pub async fn peek_first_chunk<S>(stream: &mut S) -> Result<Bytes, String>
 where
     S: futures::Stream<Item = Result<Bytes, reqwest::Error>> + Unpin,
@@ -3,12 +3,14 @@
 
 pub mod audio;
 pub mod claude;
+pub mod embeddings;
 pub mod gemini;
 pub mod mcp;
 pub mod mcp_forward;
 pub mod mcp_vision;
 pub mod model_detect;
 pub mod openai;
+pub mod speech;
 pub mod warmup;
 
 #[cfg(test)]
@@ -1,17 +1,64 @@
+use crate::proxy::api_keys::ClientKey;
+use crate::proxy::routing_rules::{self, RequestFacts};
 use crate::proxy::server::AppState;
-use axum::{extract::Json, extract::State, http::StatusCode, response::IntoResponse};
+use antigravity_types::models::{Protocol, RuleAction};
+use axum::{
+    extract::{Extension, Json, State},
+    http::{HeaderMap, StatusCode},
+    response::IntoResponse,
+};
 use serde_json::{json, Value};
 
+/// Resolves a model the way a real request would and explains the decision.
+///
+/// The body is a request as the client would send it (`model` plus optionally
+/// `messages`, `tools`, ...) and an optional `protocol` (`openai`, `anthropic`,
+/// `gemini`). The `routing` object lists each rule checked and why it matched or not;
+/// a rejecting rule is reported, not enforced.
 pub async fn handle_detect_model(
     State(state): State<AppState>,
+    headers: HeaderMap,
+    client_key: Option<Extension<ClientKey>>,
     Json(body): Json<Value>,
 ) -> impl IntoResponse {
-    let model_name = body.get("model").and_then(|v| v.as_str()).unwrap_or("");
+    let requested = body.get("model").and_then(|v| v.as_str()).unwrap_or("");
 
-    if model_name.is_empty() {
+    if requested.is_empty() {
         return (StatusCode::BAD_REQUEST, "Missing 'model' field").into_response();
     }
 
+    let protocol = match body.get("protocol").and_then(|v| v.as_str()) {
+        Some(p) if p.eq_ignore_ascii_case("anthropic") || p.eq_ignore_ascii_case("claude") => {
+            Protocol::Anthropic
+        },
+        Some(p) if p.eq_ignore_ascii_case("gemini") => Protocol::Gemini,
+        _ => Protocol::OpenAI,
+    };
+    let facts = RequestFacts::from_body(
+        requested.to_string(),
+        protocol,
+        &headers,
+        client_key.map(|Extension(key)| key),
+        &body,
+    );
+    let rules = state.routing_rules.read().await;
+    let evaluation = routing_rules::evaluate(&rules, &facts);
+    let model_name = match evaluation.fired.map(|rule| &rule.action) {
+        Some(RuleAction::RewriteModel { model }) => model.as_str(),
+        _ => requested,
+    };
+    let account_group = match evaluation.fired.map(|rule| &rule.action) {
+        Some(RuleAction::PinAccountGroup { group }) => Some(group.clone()),
+        _ => facts.api_key.as_ref().and_then(|key| key.account_group.clone()),
+    };
+    let routing = json!({
+        "rule": evaluation.fired.map(|rule| rule.name.as_str()),
+        "action": evaluation.fired.map(|rule| &rule.action),
+        "account_group": account_group,
+        "trace": evaluation.trace,
+        "facts": facts,
+    });
+
     let (mapped_model, reason) = match crate::proxy::common::resolve_model_route(
         model_name,
         &*state.custom_mapping.read().await,
@@ -22,8 +69,10 @@ pub async fn handle_detect_model(
                 StatusCode::BAD_REQUEST,
                 Json(json!({
                     "error": e,
-                    "model": model_name,
-                    "available": false
+                    "model": requested,
+                    "routed_model": model_name,
+                    "available": false,
+                    "routing": routing
                 })),
             )
                 .into_response();
@@ -39,9 +88,11 @@ pub async fn handle_detect_model(
     );
 
     let mut response = json!({
-        "model": model_name,
+        "model": requested,
+        "routed_model": model_name,
         "mapped_model": mapped_model,
         "mapping_reason": reason,
+        "routing": routing,
         "type": config.request_type,
         "features": {
             "has_web_search": config.inject_google_search,
@@ -0,0 +1,293 @@
+//! OpenAI Batch API (`/v1/batches`).
+//!
+//! The input is a JSONL file uploaded through `/v1/files`. Requests run in the background
+//! through [`handle_chat_completions`](super::handle_chat_completions); when the batch ends
+//! its results are written to an output file (successes) and an error file (the rest).
+
+use super::files::{file_content, internal_error, visible_file};
+use crate::proxy::api_keys::ClientKey;
+use crate::proxy::server::AppState;
+use antigravity_types::models::{
+    Batch, BatchApi, BatchItem, BatchPage, BatchRequestCounts, BatchStatus,
+};
+use axum::{
+    extract::{Extension, Json, Path, Query, State},
+    http::StatusCode,
+};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::collections::HashSet;
+
+const ENDPOINT: &str = "/v1/chat/completions";
+const COMPLETION_WINDOW: &str = "24h";
+const DEFAULT_LIST_LIMIT: usize = 20;
+const MAX_LIST_LIMIT: usize = 100;
+
+#[derive(Debug, Deserialize)]
+pub struct CreateBatchRequest {
+    pub input_file_id: String,
+    pub endpoint: String,
+    pub completion_window: String,
+    #[serde(default)]
+    pub metadata: Option<serde_json::Map<String, Value>>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ListBatchesQuery {
+    #[serde(default)]
+    pub limit: Option<usize>,
+    #[serde(default)]
+    pub after: Option<String>,
+}
+
+/// One line of a batch input file.
+#[derive(Debug, Deserialize)]
+struct InputLine {
+    custom_id: String,
+    method: String,
+    url: String,
+    body: Value,
+}
+
+pub async fn handle_create_batch(
+    State(state): State<AppState>,
+    client_key: Option<Extension<ClientKey>>,
+    Json(request): Json<CreateBatchRequest>,
+) -> Result<Json<Value>, (StatusCode, String)> {
+    if request.endpoint != ENDPOINT {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            format!("Only endpoint '{}' is supported", ENDPOINT),
+        ));
+    }
+    if request.completion_window != COMPLETION_WINDOW {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            format!("Only completion_window '{}' is supported", COMPLETION_WINDOW),
+        ));
+    }
+
+    let file = visible_file(&state, &request.input_file_id, client_key.as_ref()).await?;
+    if file.purpose != "batch" {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            format!("File '{}' was not uploaded with purpose 'batch'", file.id),
+        ));
+    }
+    let content = file_content(&state, &file.id).await?;
+    let key_id = client_key.map(|Extension(key)| key.id);
+    let api_key = key_id.as_deref().and_then(|id| state.api_keys.get(id));
+    let items =
+        parse_input(&content, |model| api_key.as_ref().is_none_or(|key| key.allows_model(model)))
+            .map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+
+    let config = state.batches.config();
+    if items.len() > config.max_requests {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            format!("A batch may contain at most {} requests", config.max_requests),
+        ));
+    }
+    if let Some(message) = state.batches.budget_rejection(&state, key_id.as_deref(), &items) {
+        return Err((StatusCode::TOO_MANY_REQUESTS, message));
+    }
+
+    let now = chrono::Utc::now().timestamp();
+    let batch = Batch {
+        id: format!("batch_{}", uuid::Uuid::new_v4().simple()),
+        api: BatchApi::Openai,
+        endpoint: ENDPOINT.to_string(),
+        api_key_id: key_id,
+        status: BatchStatus::InProgress,
+        metadata: request.metadata.unwrap_or_default(),
+        input_file_id: Some(file.id),
+        output_file_id: None,
+        error_file_id: None,
+        created_at: now,
+        expires_at: now + (config.expiry_hours * 3600) as i64,
+        cancel_initiated_at: None,
+        ended_at: None,
+        request_counts: BatchRequestCounts { processing: items.len() as u64, ..Default::default() },
+    };
+    state.batches.submit(&state, &batch, &items).await.map_err(internal_error)?;
+    Ok(Json(batch_json(&batch)))
+}
+
+pub async fn handle_list_batches(
+    State(state): State<AppState>,
+    client_key: Option<Extension<ClientKey>>,
+    Query(query): Query<ListBatchesQuery>,
+) -> Result<Json<Value>, (StatusCode, String)> {
+    let limit = query.limit.unwrap_or(DEFAULT_LIST_LIMIT);
+    if !(1..=MAX_LIST_LIMIT).contains(&limit) {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            format!("'limit' must be between 1 and {}", MAX_LIST_LIMIT),
+        ));
+    }
+    let page = BatchPage { after_id: query.after, before_id: None, limit };
+    let key_id = client_key.as_ref().map(|Extension(key)| key.id.as_str());
+
+    let mut batches =
+        state.batches.list(BatchApi::Openai, key_id, &page).await.map_err(internal_error)?;
+    let has_more = batches.len() > limit;
+    batches.truncate(limit);
+
+    Ok(Json(json!({
+        "object": "list",
+        "data": batches.iter().map(batch_json).collect::<Vec<_>>(),
+        "first_id": batches.first().map(|b| b.id.clone()),
+        "last_id": batches.last().map(|b| b.id.clone()),
+        "has_more": has_more,
+    })))
+}
+
+pub async fn handle_get_batch(
+    State(state): State<AppState>,
+    client_key: Option<Extension<ClientKey>>,
+    Path(id): Path<String>,
+) -> Result<Json<Value>, (StatusCode, String)> {
+    let batch = visible_batch(&state, &id, client_key.as_ref()).await?;
+    Ok(Json(batch_json(&batch)))
+}
+
+pub async fn handle_cancel_batch(
+    State(state): State<AppState>,
+    client_key: Option<Extension<ClientKey>>,
+    Path(id): Path<String>,
+) -> Result<Json<Value>, (StatusCode, String)> {
+    visible_batch(&state, &id, client_key.as_ref()).await?;
+    let batch = state
+        .batches
+        .cancel(&state, &id)
+        .await
+        .map_err(internal_error)?
+        .ok_or_else(|| not_found(&id))?;
+    Ok(Json(batch_json(&batch)))
+}
+
+async fn visible_batch(
+    state: &AppState,
+    id: &str,
+    client_key: Option<&Extension<ClientKey>>,
+) -> Result<Batch, (StatusCode, String)> {
+    let key_id = client_key.map(|Extension(key)| key.id.as_str());
+    state
+        .batches
+        .get_visible(id, key_id)
+        .await
+        .map_err(internal_error)?
+        .filter(|batch| batch.api == BatchApi::Openai)
+        .ok_or_else(|| not_found(id))
+}
+
+/// Parse and check a JSONL input file; `allows_model` applies the key's model allowlist.
+fn parse_input(
+    content: &[u8],
+    allows_model: impl Fn(&str) -> bool,
+) -> Result<Vec<BatchItem>, String> {
+    let text = std::str::from_utf8(content).map_err(|_| "Input file is not UTF-8".to_string())?;
+    let mut seen = HashSet::new();
+    let mut items = Vec::new();
+    for (index, line) in text.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let invalid = |message: String| format!("Line {}: {}", index + 1, message);
+        let line: InputLine = serde_json::from_str(line).map_err(|e| invalid(e.to_string()))?;
+        if line.custom_id.is_empty() || !seen.insert(line.custom_id.clone()) {
+            return Err(invalid(format!("custom_id '{}' is empty or duplicated", line.custom_id)));
+        }
+        if !line.method.eq_ignore_ascii_case("POST") || line.url != ENDPOINT {
+            return Err(invalid(format!("requests must be 'POST {}'", ENDPOINT)));
+        }
+        let Some(model) = line.body.get("model").and_then(Value::as_str) else {
+            return Err(invalid("'body.model' is required".to_string()));
+        };
+        if !allows_model(model) {
+            return Err(invalid(format!("model '{}' is not allowed for this API key", model)));
+        }
+        if line.body.get("stream").and_then(Value::as_bool) == Some(true) {
+            return Err(invalid("streaming is not supported in batches".to_string()));
+        }
+        items.push(BatchItem::new(items.len() as u32, line.custom_id, line.body));
+    }
+    if items.is_empty() {
+        return Err("Input file contains no requests".to_string());
+    }
+    Ok(items)
+}
+
+/// OpenAI batch status; an ended batch is cancelled, expired or completed.
+fn status(batch: &Batch) -> &'static str {
+    match batch.status {
+        BatchStatus::InProgress => "in_progress",
+        BatchStatus::Canceling => "cancelling",
+        BatchStatus::Ended if batch.cancel_initiated_at.is_some() => "cancelled",
+        BatchStatus::Ended if batch.request_counts.expired > 0 => "expired",
+        BatchStatus::Ended => "completed",
+    }
+}
+
+fn batch_json(batch: &Batch) -> Value {
+    let status = status(batch);
+    let ended_as = |name: &str| if status == name { batch.ended_at } else { None };
+    let counts = &batch.request_counts;
+    json!({
+        "id": batch.id,
+        "object": "batch",
+        "endpoint": batch.endpoint,
+        "errors": null,
+        "input_file_id": batch.input_file_id,
+        "completion_window": COMPLETION_WINDOW,
+        "status": status,
+        "output_file_id": batch.output_file_id,
+        "error_file_id": batch.error_file_id,
+        "created_at": batch.created_at,
+        "in_progress_at": batch.created_at,
+        "expires_at": batch.expires_at,
+        "finalizing_at": null,
+        "completed_at": ended_as("completed"),
+        "failed_at": null,
+        "expired_at": ended_as("expired"),
+        "cancelling_at": batch.cancel_initiated_at,
+        "cancelled_at": ended_as("cancelled"),
+        "request_counts": {
+            "total": counts.total(),
+            "completed": counts.succeeded,
+            "failed": counts.errored,
+        },
+        "metadata": batch.metadata,
+    })
+}
+
+fn not_found(id: &str) -> (StatusCode, String) {
+    (StatusCode::NOT_FOUND, format!("Batch '{}' not found", id))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LINE: &str = r#"{"custom_id":"a","method":"POST","url":"/v1/chat/completions","body":{"model":"gemini-3-flash","messages":[]}}"#;
+
+    #[test]
+    fn test_parse_input_reads_requests_in_order() {
+        let input = format!("{}\n\n{}\n", LINE, LINE.replace("\"a\"", "\"b\""));
+        let items = parse_input(input.as_bytes(), |_| true).unwrap();
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[1].position, 1);
+        assert_eq!(items[1].custom_id, "b");
+        assert_eq!(items[0].params["model"], "gemini-3-flash");
+    }
+
+    #[test]
+    fn test_parse_input_rejects_bad_lines() {
+        let duplicate = format!("{}\n{}", LINE, LINE);
+        assert!(parse_input(duplicate.as_bytes(), |_| true).unwrap_err().contains("Line 2"));
+        let wrong_url = LINE.replace("/v1/chat/completions", "/v1/embeddings");
+        assert!(parse_input(wrong_url.as_bytes(), |_| true).is_err());
+        assert!(parse_input(LINE.as_bytes(), |_| false).unwrap_err().contains("not allowed"));
+        assert!(parse_input(b"\n", |_| true).is_err());
+    }
+}
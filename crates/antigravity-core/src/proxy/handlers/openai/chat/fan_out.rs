@@ -0,0 +1,213 @@
+//! `n > 1` for models without `candidateCount`: parallel single-choice calls.
+//!
+//! Each call goes through the normal rotation loop, preferring accounts not already
+//! used by a sibling. Failed calls are retried once on other accounts; if choices are
+//! still missing the request fails. Non-streaming results are merged into one response;
+//! streams are interleaved with their choice indices shifted and a single summed usage
+//! chunk.
+
+use axum::{
+    body::Body,
+    extract::Json,
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+use bytes::Bytes;
+use futures::{Stream, StreamExt};
+use parking_lot::Mutex;
+use serde_json::{json, Value};
+use std::collections::HashSet;
+use std::pin::Pin;
+use tracing::{info, warn};
+
+use super::run_completion;
+use crate::proxy::common::header_constants::{X_ACCOUNT_EMAIL, X_MAPPED_MODEL, X_MAPPING_REASON};
+use crate::proxy::mappers::openai::choices::{merge_responses, reindex_chunk, sum_usage};
+use crate::proxy::mappers::openai::{OpenAIRequest, OpenAIResponse, OpenAIUsage};
+use crate::proxy::server::AppState;
+
+/// Piece of a sub-stream: passthrough bytes or a usage report held back for summing.
+enum Piece {
+    Data(Bytes),
+    Usage(OpenAIUsage),
+}
+
+type PieceStream = Pin<Box<dyn Stream<Item = Result<Piece, String>> + Send>>;
+
+/// Rounds of calls made for the missing choices before giving up.
+const MAX_ROUNDS: u32 = 2;
+
+fn header(response: &Response, name: &str) -> String {
+    response.headers().get(name).and_then(|v| v.to_str().ok()).unwrap_or_default().to_string()
+}
+
+pub(super) async fn fan_out_completions(
+    state: &AppState,
+    force_account: Option<&str>,
+    mut request: OpenAIRequest,
+    n: u32,
+) -> Result<Response, (StatusCode, String)> {
+    request.n = None;
+    let siblings = Mutex::new(HashSet::new());
+    info!("[OpenAI] Fanning out n={} for {}", n, request.model);
+
+    let wanted = n as usize;
+    let mut successes = Vec::with_capacity(wanted);
+    let mut first_error: Option<Result<Response, (StatusCode, String)>> = None;
+    for round in 1..=MAX_ROUNDS {
+        let missing = wanted - successes.len();
+        if missing == 0 {
+            break;
+        }
+        if round > 1 {
+            // Accounts that failed stay in `siblings`, so the retries go elsewhere
+            warn!("[OpenAI] Fan-out retrying {} of {} failed choices", missing, n);
+        }
+        let calls =
+            (0..missing).map(|_| run_completion(state, force_account, &request, Some(&siblings)));
+        for result in futures::future::join_all(calls).await {
+            match result {
+                Ok(response) if response.status().is_success() => successes.push(response),
+                other => {
+                    warn!("[OpenAI] Fan-out call failed");
+                    first_error.get_or_insert(other);
+                },
+            }
+        }
+    }
+    // A response with fewer than n choices would break clients indexing into them
+    if successes.len() < wanted {
+        warn!("[OpenAI] Fan-out returned {}/{} choices, failing the request", successes.len(), n);
+        return first_error.unwrap_or_else(|| {
+            Err((StatusCode::BAD_GATEWAY, "Upstream returned too few choices".to_string()))
+        });
+    }
+    let Some(first) = successes.first() else {
+        return Err((StatusCode::BAD_GATEWAY, "Upstream returned no choices".to_string()));
+    };
+
+    let email = header(first, X_ACCOUNT_EMAIL);
+    let mapped_model = header(first, X_MAPPED_MODEL);
+    let reason = format!("{}; fan-out", header(first, X_MAPPING_REASON));
+
+    if request.stream {
+        return Ok(merge_streams(successes, request.model, email, mapped_model, reason));
+    }
+
+    let mut parsed: Vec<OpenAIResponse> = Vec::with_capacity(successes.len());
+    for response in successes {
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .map_err(|e| (StatusCode::BAD_GATEWAY, format!("Failed to read choice: {}", e)))?;
+        let choice = serde_json::from_slice(&bytes)
+            .map_err(|e| (StatusCode::BAD_GATEWAY, format!("Failed to parse choice: {}", e)))?;
+        parsed.push(choice);
+    }
+    let merged = merge_responses(parsed)
+        .ok_or_else(|| (StatusCode::BAD_GATEWAY, "Upstream returned no choices".to_string()))?;
+
+    Ok((
+        StatusCode::OK,
+        [(X_ACCOUNT_EMAIL, email), (X_MAPPED_MODEL, mapped_model), (X_MAPPING_REASON, reason)],
+        Json(merged),
+    )
+        .into_response())
+}
+
+/// Re-index one completion's SSE body, holding back `[DONE]` and usage chunks.
+fn offset_stream(body: Body, offset: u32, stream_id: String) -> PieceStream {
+    let mut upstream = body.into_data_stream();
+    Box::pin(async_stream::stream! {
+        let mut buffer = String::new();
+        while let Some(chunk) = upstream.next().await {
+            let chunk = match chunk {
+                Ok(c) => c,
+                Err(e) => {
+                    yield Err(e.to_string());
+                    return;
+                },
+            };
+            buffer.push_str(&String::from_utf8_lossy(&chunk));
+            while let Some(pos) = buffer.find('\n') {
+                let line: String = buffer.drain(..=pos).collect();
+                let Some(data) = line.trim().strip_prefix("data:").map(str::trim) else {
+                    continue;
+                };
+                if data == "[DONE]" {
+                    continue;
+                }
+                let Ok(mut value) = serde_json::from_str::<Value>(data) else {
+                    continue;
+                };
+                let no_choices =
+                    value.get("choices").and_then(Value::as_array).is_none_or(|c| c.is_empty());
+                if no_choices {
+                    if let Some(usage) =
+                        value.get("usage").and_then(|u| serde_json::from_value(u.clone()).ok())
+                    {
+                        yield Ok(Piece::Usage(usage));
+                        continue;
+                    }
+                }
+                reindex_chunk(&mut value, offset, &stream_id);
+                yield Ok(Piece::Data(Bytes::from(format!("data: {}\n\n", value))));
+            }
+        }
+    })
+}
+
+fn merge_streams(
+    responses: Vec<Response>,
+    model: String,
+    email: String,
+    mapped_model: String,
+    reason: String,
+) -> Response {
+    let stream_id = format!("chatcmpl-{}", uuid::Uuid::new_v4());
+    let created = chrono::Utc::now().timestamp();
+    let streams: Vec<PieceStream> = responses
+        .into_iter()
+        .enumerate()
+        .map(|(offset, r)| offset_stream(r.into_body(), offset as u32, stream_id.clone()))
+        .collect();
+    let mut merged = futures::stream::select_all(streams);
+
+    let body = async_stream::stream! {
+        let mut usage: Option<OpenAIUsage> = None;
+        while let Some(piece) = merged.next().await {
+            match piece {
+                Ok(Piece::Data(bytes)) => yield Ok::<Bytes, std::io::Error>(bytes),
+                Ok(Piece::Usage(u)) => usage = sum_usage(usage.take(), Some(&u)),
+                Err(e) => {
+                    yield Err(std::io::Error::other(e));
+                    return;
+                },
+            }
+        }
+        if let Some(usage) = usage {
+            let chunk = json!({
+                "id": stream_id,
+                "object": "chat.completion.chunk",
+                "created": created,
+                "model": model,
+                "choices": [],
+                "usage": usage,
+            });
+            yield Ok(Bytes::from(format!("data: {}\n\n", chunk)));
+        }
+        yield Ok(Bytes::from("data: [DONE]\n\n"));
+    };
+
+    Response::builder()
+        .header("Content-Type", "text/event-stream")
+        .header("Cache-Control", "no-cache")
+        .header("Connection", "keep-alive")
+        .header(X_ACCOUNT_EMAIL, email)
+        .header(X_MAPPED_MODEL, mapped_model)
+        .header(X_MAPPING_REASON, reason)
+        .body(Body::from_stream(body))
+        .unwrap_or_else(|e| {
+            tracing::error!("Failed to build SSE response: {}", e);
+            (StatusCode::INTERNAL_SERVER_ERROR, "Internal streaming setup error").into_response()
+        })
+}
@@ -1,4 +1,5 @@
 mod error_handler;
+mod fan_out;
 mod signature_preload;
 mod stream_handler;
 mod token_acquisition;
@@ -9,21 +10,36 @@ use super::MAX_RETRY_ATTEMPTS;
 use crate::proxy::common::header_constants::{
     X_ACCOUNT_EMAIL, X_FORCE_ACCOUNT, X_MAPPED_MODEL, X_MAPPING_REASON,
 };
+use antigravity_types::models::ProviderProtocol;
 use axum::http::HeaderMap;
 use axum::{
     extract::{Json, State},
     http::StatusCode,
-    response::IntoResponse,
+    response::{IntoResponse, Response},
 };
+use parking_lot::Mutex;
 use serde_json::Value;
+use std::collections::HashSet;
 use tracing::{debug, error, info, warn};
 
 use crate::proxy::common::{sanitize_upstream_error, UpstreamError};
 use crate::proxy::handlers::openai::completions::request_parser::ensure_non_empty_messages;
-use crate::proxy::mappers::openai::{transform_openai_request, OpenAIRequest};
-use crate::proxy::retry::{build_exhaustion_response, extract_error_info, record_request_success};
+use crate::proxy::mappers::openai::choices::{supports_candidate_count, validate_choice_count};
+use crate::proxy::mappers::openai::structured_output::{
+    apply_response_format, strict_schema, validate_output, validate_response_format,
+};
+use crate::proxy::mappers::openai::{
+    transform_openai_request, JsonSchemaFormat, OpenAIContent, OpenAIContentBlock, OpenAIRequest,
+    OpenAIResponse,
+};
+use crate::proxy::providers::compatible::try_forward_to_provider;
+use crate::proxy::retry::{
+    build_exhaustion_response, extract_error_info, hedged_call, record_request_success,
+    retarget_body, HedgeTarget, Leg,
+};
 use crate::proxy::server::AppState;
 use crate::proxy::session_manager::SessionManager;
+use crate::proxy::telemetry::current_trace_id;
 
 use error_handler::{
     handle_auth_errors, handle_grace_retry, handle_rate_limit_errors, handle_service_disabled,
@@ -45,13 +61,63 @@ pub async fn handle_chat_completions(
         convert_responses_to_chat(&mut body);
     }
 
+    if let Some(response) = try_forward_to_provider(
+        &state,
+        &headers,
+        &body,
+        ProviderProtocol::Openai,
+        "/v1/chat/completions",
+    )
+    .await
+    {
+        return Ok(response);
+    }
+
     let mut openai_req: OpenAIRequest = serde_json::from_value(body)
         .map_err(|e| (StatusCode::BAD_REQUEST, format!("Invalid request: {}", e)))?;
 
     ensure_non_empty_messages(&mut openai_req);
 
+    if let Some(fmt) = &openai_req.response_format {
+        validate_response_format(fmt).map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+        // Streamed chunks reach the client before the output can be validated
+        if openai_req.stream && strict_schema(fmt).is_some() {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                "response_format json_schema with strict: true is not supported with stream: true"
+                    .to_string(),
+            ));
+        }
+    }
+
     debug!("Received OpenAI request for model: {}", openai_req.model);
 
+    let n = validate_choice_count(openai_req.n).map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+    if n > 1 {
+        let (mapped_model, _) = crate::proxy::common::resolve_model_route(
+            &openai_req.model,
+            &*state.custom_mapping.read().await,
+        )
+        .map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+        if !supports_candidate_count(&mapped_model) {
+            return fan_out::fan_out_completions(&state, force_account.as_deref(), openai_req, n)
+                .await;
+        }
+    }
+
+    run_completion(&state, force_account.as_deref(), &openai_req, None).await
+}
+
+/// Run one chat completion with account rotation.
+///
+/// `siblings` holds the accounts already serving other calls of a fan-out; they are
+/// avoided while the pool has others to offer.
+async fn run_completion(
+    state: &AppState,
+    force_account: Option<&str>,
+    openai_req: &OpenAIRequest,
+    siblings: Option<&Mutex<HashSet<String>>>,
+) -> Result<Response, (StatusCode, String)> {
     let upstream = state.upstream.clone();
     let token_manager = state.token_manager.clone();
     let pool_size = token_manager.len();
@@ -59,11 +125,11 @@ pub async fn handle_chat_completions(
 
     let mut last_error = UpstreamError::EmptyStream;
     let mut last_email: Option<String> = None;
-    let trace_id = format!("oai_{}", chrono::Utc::now().timestamp_micros());
+    let trace_id = current_trace_id()
+        .unwrap_or_else(|| format!("oai_{}", chrono::Utc::now().timestamp_micros()));
     let mut grace_retry_used = false;
     let mut attempt = 0usize;
-    let mut attempted_accounts: std::collections::HashSet<String> =
-        std::collections::HashSet::new();
+    let mut attempted_accounts: HashSet<String> = HashSet::new();
 
     while attempt < max_attempts {
         let (mapped_model, reason) = match crate::proxy::common::resolve_model_route(
@@ -82,33 +148,47 @@ pub async fn handle_chat_completions(
             None,
         );
 
-        let session_id = SessionManager::extract_openai_session_id(&openai_req);
+        let session_id = SessionManager::extract_openai_session_id(openai_req);
 
+        let exclusions = match siblings {
+            Some(siblings) => {
+                let mut merged = attempted_accounts.clone();
+                merged.extend(siblings.lock().iter().cloned());
+                if merged.len() < pool_size {
+                    merged
+                } else {
+                    attempted_accounts.clone()
+                }
+            },
+            None => attempted_accounts.clone(),
+        };
         let (access_token, project_id, email, _active_guard) = match acquire_token(
             token_manager.clone(),
-            force_account.as_deref(),
+            force_account,
             &config,
             &session_id,
             attempt > 0,
-            &attempted_accounts,
+            &exclusions,
         )
         .await
         {
             Ok(t) => t,
             Err(e) => return Err((StatusCode::SERVICE_UNAVAILABLE, e)),
         };
+        if let Some(siblings) = siblings {
+            siblings.lock().insert(email.clone());
+        }
 
-        last_email = Some(email.clone());
         info!("✓ Using account: {} (type: {})", email, config.request_type);
 
-        signature_preload::preload_signatures(&openai_req).await;
+        signature_preload::preload_signatures(openai_req).await;
 
         let is_claude_model = mapped_model.starts_with("claude-");
 
-        let mut gemini_body = if is_claude_model {
+        let gemini_body = if is_claude_model {
             let mut claude_req =
                 crate::proxy::mappers::openai::request::claude_bridge::openai_to_claude_request(
-                    &openai_req,
+                    openai_req,
                 );
             claude_req.model = mapped_model.clone();
 
@@ -120,21 +200,17 @@ pub async fn handle_chat_completions(
             .map_err(|e| (StatusCode::BAD_REQUEST, e))?;
 
             if let Some(fmt) = &openai_req.response_format {
-                if fmt.r#type == "json_object" {
-                    if let Some(gen_config) = body.get_mut("generationConfig") {
-                        gen_config["responseMimeType"] = serde_json::json!("application/json");
-                    }
+                if let Some(gen_config) =
+                    body.get_mut("request").and_then(|r| r.get_mut("generationConfig"))
+                {
+                    apply_response_format(gen_config, fmt);
                 }
             }
 
             body
         } else {
-            transform_openai_request(&openai_req, &project_id, &mapped_model)
+            transform_openai_request(openai_req, &project_id, &mapped_model)
         };
-        crate::proxy::upstream::device_fingerprint::inject_body_fingerprint(
-            &mut gemini_body,
-            &email,
-        );
 
         debug!("[OpenAI-Request] Transformed Gemini Body");
 
@@ -143,23 +219,51 @@ pub async fn handle_chat_completions(
             info!("[OpenAI] 🔄 Auto-converting non-stream request to stream for better quota");
         }
 
-        let account_proxy = token_manager.get_account_proxy_url(&email);
-        let response = match call_upstream_with_retry(
-            upstream.clone(),
-            "streamGenerateContent",
-            &access_token,
-            gemini_body,
-            Some("alt=sse"),
-            None,
-            &email,
-            account_proxy.as_deref(),
-            attempt,
-            max_attempts,
+        let hedged = hedged_call(
+            &token_manager,
+            &state.adaptive_limits,
+            HedgeTarget {
+                request_type: &config.request_type,
+                model: &config.final_model,
+                exclude: &exclusions,
+                pinned: force_account.is_some(),
+            },
+            Leg { email: email.clone(), access_token, project_id },
+            |leg| {
+                let upstream = upstream.clone();
+                let body = retarget_body(&gemini_body, &leg);
+                let account_proxy = token_manager.get_account_proxy_url(&leg.email);
+                async move {
+                    match call_upstream_with_retry(
+                        upstream,
+                        "streamGenerateContent",
+                        &leg.access_token,
+                        body,
+                        Some("alt=sse"),
+                        None,
+                        &leg.email,
+                        account_proxy.as_deref(),
+                        attempt,
+                        max_attempts,
+                    )
+                    .await
+                    {
+                        UpstreamResult::Success(r) => Ok(r),
+                        UpstreamResult::ConnectionError(e) => Err(e),
+                    }
+                }
+            },
         )
-        .await
-        {
-            UpstreamResult::Success(r) => r,
-            UpstreamResult::ConnectionError(e) => {
+        .await;
+        let email = hedged.email;
+        let _hedge_guard = hedged.guard;
+        if let Some(siblings) = siblings {
+            siblings.lock().insert(email.clone());
+        }
+        last_email = Some(email.clone());
+        let response = match hedged.result {
+            Ok(r) => r,
+            Err(e) => {
                 last_error = UpstreamError::ConnectionError(e);
                 attempted_accounts.insert(email.clone());
                 attempt += 1;
@@ -170,7 +274,7 @@ pub async fn handle_chat_completions(
 
         let status = response.status();
         if status.is_success() {
-            record_request_success(&token_manager, &state, &email, &session_id);
+            record_request_success(&token_manager, state, &email, &session_id);
 
             let gemini_stream = response.bytes_stream();
             match handle_stream_response(
@@ -187,6 +291,13 @@ pub async fn handle_chat_completions(
             {
                 OpenAIStreamResult::StreamingResponse(resp) => return Ok(resp.into_response()),
                 OpenAIStreamResult::JsonResponse(st, em, model, rsn, json) => {
+                    if let Some(spec) = openai_req.response_format.as_ref().and_then(strict_schema)
+                    {
+                        if let Err(e) = validate_choices(&json, spec) {
+                            warn!("[{}] Strict schema validation failed: {}", trace_id, e);
+                            return Ok(schema_mismatch_response(&e, &em, &model, &rsn));
+                        }
+                    }
                     return Ok((
                         st,
                         [
@@ -249,7 +360,7 @@ pub async fn handle_chat_completions(
             &error_text,
             retry_after.as_deref(),
             token_manager.clone(),
-            &state,
+            state,
             &email,
             &session_id,
             &config.final_model,
@@ -314,3 +425,43 @@ pub async fn handle_chat_completions(
 
     Ok(build_exhaustion_response(&last_error, last_email.as_deref()))
 }
+
+/// Check every text choice of a non-streaming response against a strict schema.
+fn validate_choices(response: &OpenAIResponse, spec: &JsonSchemaFormat) -> Result<(), String> {
+    for choice in &response.choices {
+        let text = match &choice.message.content {
+            Some(OpenAIContent::String(s)) => s.clone(),
+            Some(OpenAIContent::Array(blocks)) => blocks
+                .iter()
+                .filter_map(|b| match b {
+                    OpenAIContentBlock::Text { text } => Some(text.as_str()),
+                    _ => None,
+                })
+                .collect(),
+            // Tool calls carry no content to validate
+            None => continue,
+        };
+        validate_output(&text, spec)?;
+    }
+    Ok(())
+}
+
+fn schema_mismatch_response(
+    message: &str,
+    email: &str,
+    mapped_model: &str,
+    reason: &str,
+) -> Response {
+    (
+        StatusCode::BAD_GATEWAY,
+        [(X_ACCOUNT_EMAIL, email), (X_MAPPED_MODEL, mapped_model), (X_MAPPING_REASON, reason)],
+        Json(serde_json::json!({
+            "error": {
+                "message": message,
+                "type": "invalid_response_error",
+                "code": "response_schema_mismatch"
+            }
+        })),
+    )
+        .into_response()
+}
@@ -7,6 +7,7 @@ use super::*;
 use crate::proxy::common::header_constants::X_ACCOUNT_EMAIL;
 use crate::proxy::common::{sanitize_upstream_error, UpstreamError};
 use crate::proxy::retry::{build_exhaustion_response, extract_error_info, record_request_success};
+use crate::proxy::telemetry::current_trace_id;
 use crate::proxy::SignatureCache;
 use request_parser::{ensure_non_empty_messages, normalize_request_body};
 
@@ -28,7 +29,8 @@ pub async fn handle_completions(
     let mut last_error = UpstreamError::EmptyStream;
 
     let mut last_email: Option<String> = None;
-    let trace_id = format!("req_{}", chrono::Utc::now().timestamp_micros());
+    let trace_id = current_trace_id()
+        .unwrap_or_else(|| format!("req_{}", chrono::Utc::now().timestamp_micros()));
     let mut grace_retry_used = false;
 
     for attempt in 0..max_attempts {
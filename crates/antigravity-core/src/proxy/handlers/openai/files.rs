@@ -0,0 +1,166 @@
+//! OpenAI Files API (`/v1/files`), limited to batch input and output files.
+
+use crate::modules::batch_store;
+use crate::proxy::api_keys::ClientKey;
+use crate::proxy::server::AppState;
+use antigravity_types::models::BatchFile;
+use axum::{
+    extract::{Extension, Json, Multipart, Path, Query, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+};
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+#[derive(Debug, Deserialize)]
+pub struct ListFilesQuery {
+    #[serde(default)]
+    pub purpose: Option<String>,
+}
+
+/// Upload a JSONL batch input file (`file` and `purpose=batch` form fields).
+pub async fn handle_upload_file(
+    State(state): State<AppState>,
+    client_key: Option<Extension<ClientKey>>,
+    mut multipart: Multipart,
+) -> Result<Json<Value>, (StatusCode, String)> {
+    let mut upload: Option<(String, Vec<u8>)> = None;
+    let mut purpose = None;
+    while let Some(field) = multipart
+        .next_field()
+        .await
+        .map_err(|e| (StatusCode::BAD_REQUEST, format!("Invalid multipart body: {}", e)))?
+    {
+        match field.name().unwrap_or("") {
+            "file" => {
+                let filename = field.file_name().unwrap_or("upload.jsonl").to_string();
+                let bytes = field.bytes().await.map_err(|e| {
+                    (StatusCode::BAD_REQUEST, format!("Failed to read file: {}", e))
+                })?;
+                upload = Some((filename, bytes.to_vec()));
+            },
+            "purpose" => {
+                purpose = Some(field.text().await.map_err(|e| {
+                    (StatusCode::BAD_REQUEST, format!("Failed to read purpose: {}", e))
+                })?);
+            },
+            _ => {},
+        }
+    }
+
+    let (filename, content) =
+        upload.ok_or((StatusCode::BAD_REQUEST, "Missing 'file' field".to_string()))?;
+    if purpose.as_deref() != Some("batch") {
+        return Err((StatusCode::BAD_REQUEST, "Only purpose 'batch' is supported".to_string()));
+    }
+
+    let file = BatchFile {
+        id: format!("file-{}", uuid::Uuid::new_v4().simple()),
+        filename,
+        purpose: "batch".to_string(),
+        api_key_id: client_key.map(|Extension(key)| key.id),
+        bytes: content.len() as u64,
+        created_at: chrono::Utc::now().timestamp(),
+    };
+    state.batches.store().put_file(&file, content).await.map_err(internal_error)?;
+    Ok(Json(file_json(&file)))
+}
+
+pub async fn handle_list_files(
+    State(state): State<AppState>,
+    client_key: Option<Extension<ClientKey>>,
+    Query(query): Query<ListFilesQuery>,
+) -> Result<Json<Value>, (StatusCode, String)> {
+    let key_id = client_key.as_ref().map(|Extension(key)| key.id.as_str());
+    let files = state.batches.store().list_files(key_id).await.map_err(internal_error)?;
+    let data: Vec<Value> = files
+        .iter()
+        .filter(|file| query.purpose.as_ref().is_none_or(|purpose| &file.purpose == purpose))
+        .map(file_json)
+        .collect();
+    Ok(Json(json!({ "object": "list", "data": data, "has_more": false })))
+}
+
+pub async fn handle_get_file(
+    State(state): State<AppState>,
+    client_key: Option<Extension<ClientKey>>,
+    Path(id): Path<String>,
+) -> Result<Json<Value>, (StatusCode, String)> {
+    let file = visible_file(&state, &id, client_key.as_ref()).await?;
+    Ok(Json(file_json(&file)))
+}
+
+pub async fn handle_delete_file(
+    State(state): State<AppState>,
+    client_key: Option<Extension<ClientKey>>,
+    Path(id): Path<String>,
+) -> Result<Json<Value>, (StatusCode, String)> {
+    visible_file(&state, &id, client_key.as_ref()).await?;
+    let deleted = state.batches.store().delete_file(&id).await.map_err(internal_error)?;
+    Ok(Json(json!({ "id": id, "object": "file", "deleted": deleted })))
+}
+
+pub async fn handle_file_content(
+    State(state): State<AppState>,
+    client_key: Option<Extension<ClientKey>>,
+    Path(id): Path<String>,
+) -> Result<Response, (StatusCode, String)> {
+    visible_file(&state, &id, client_key.as_ref()).await?;
+    let content = file_content(&state, &id).await?;
+    Ok(([(header::CONTENT_TYPE, "application/octet-stream")], content).into_response())
+}
+
+/// File `id` if it exists and the caller's key may see it.
+pub(super) async fn visible_file(
+    state: &AppState,
+    id: &str,
+    client_key: Option<&Extension<ClientKey>>,
+) -> Result<BatchFile, (StatusCode, String)> {
+    let key_id = client_key.map(|Extension(key)| key.id.as_str());
+    if !batch_store::is_valid_id(id) {
+        return Err(not_found(id));
+    }
+    state
+        .batches
+        .store()
+        .get_file(id)
+        .await
+        .map_err(internal_error)?
+        .filter(|file| file.is_visible_to(key_id))
+        .ok_or_else(|| not_found(id))
+}
+
+pub(super) async fn file_content(
+    state: &AppState,
+    id: &str,
+) -> Result<Vec<u8>, (StatusCode, String)> {
+    state
+        .batches
+        .store()
+        .file_content(id)
+        .await
+        .map_err(internal_error)?
+        .ok_or_else(|| not_found(id))
+}
+
+fn file_json(file: &BatchFile) -> Value {
+    json!({
+        "id": file.id,
+        "object": "file",
+        "bytes": file.bytes,
+        "created_at": file.created_at,
+        "filename": file.filename,
+        "purpose": file.purpose,
+        "status": "processed",
+        "status_details": null,
+    })
+}
+
+fn not_found(id: &str) -> (StatusCode, String) {
+    (StatusCode::NOT_FOUND, format!("File '{}' not found", id))
+}
+
+pub(super) fn internal_error(e: String) -> (StatusCode, String) {
+    tracing::error!("[Batch] Store error: {}", e);
+    (StatusCode::INTERNAL_SERVER_ERROR, e)
+}
@@ -0,0 +1,230 @@
+//! OpenAI Images API handlers (`/v1/images/generations`, `/v1/images/edits`).
+//!
+//! Image options come from `resolve_request_config`, the same as image models reached
+//! through chat completions. `n` is served by parallel upstream calls since each call
+//! yields one image.
+
+use axum::{
+    extract::{Json, Multipart, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use serde_json::{json, Value};
+use tracing::{info, warn};
+
+use crate::proxy::common::header_constants::{X_ACCOUNT_EMAIL, X_MAPPED_MODEL, X_MAPPING_REASON};
+use crate::proxy::common::media_detect::detect_image_mime;
+use crate::proxy::common::resolve_model_route;
+use crate::proxy::mappers::openai::images::{
+    build_image_request, build_images_response, extract_images, is_openai_image_model,
+    validate_image_params, GeneratedImage, ImageGenerationRequest, InlineImage,
+    DEFAULT_IMAGE_MODEL,
+};
+use crate::proxy::mappers::request_config::resolve_request_config;
+use crate::proxy::retry::call_with_rotation;
+use crate::proxy::server::AppState;
+
+/// Parameters shared by generations and edits.
+struct ImageJob {
+    model: String,
+    prompt: String,
+    n: u32,
+    size: Option<String>,
+    quality: Option<String>,
+    style: Option<String>,
+    response_format: Option<String>,
+    images: Vec<InlineImage>,
+    mask: Option<InlineImage>,
+}
+
+pub async fn handle_images_generations(
+    State(state): State<AppState>,
+    Json(body): Json<Value>,
+) -> Result<Response, (StatusCode, String)> {
+    let request: ImageGenerationRequest = serde_json::from_value(body)
+        .map_err(|e| (StatusCode::BAD_REQUEST, format!("Invalid request: {}", e)))?;
+
+    let job = ImageJob {
+        model: request.model.unwrap_or_else(|| DEFAULT_IMAGE_MODEL.to_string()),
+        prompt: request.prompt,
+        n: request.n,
+        size: request.size,
+        quality: request.quality,
+        style: request.style,
+        response_format: request.response_format,
+        images: Vec::new(),
+        mask: None,
+    };
+    run_image_job(&state, job).await
+}
+
+pub async fn handle_images_edits(
+    State(state): State<AppState>,
+    mut multipart: Multipart,
+) -> Result<Response, (StatusCode, String)> {
+    let mut job = ImageJob {
+        model: DEFAULT_IMAGE_MODEL.to_string(),
+        prompt: String::new(),
+        n: 1,
+        size: None,
+        quality: None,
+        style: None,
+        response_format: None,
+        images: Vec::new(),
+        mask: None,
+    };
+
+    while let Some(field) = multipart
+        .next_field()
+        .await
+        .map_err(|e| (StatusCode::BAD_REQUEST, format!("Invalid multipart body: {}", e)))?
+    {
+        let name = field.name().unwrap_or("").to_string();
+        match name.as_str() {
+            "image" | "image[]" | "mask" => {
+                let declared = field.content_type().unwrap_or("image/png").to_string();
+                let bytes = field.bytes().await.map_err(|e| {
+                    (StatusCode::BAD_REQUEST, format!("Failed to read {}: {}", name, e))
+                })?;
+                let data = STANDARD.encode(&bytes);
+                let image = InlineImage { mime_type: detect_image_mime(&data, &declared), data };
+                if name == "mask" {
+                    job.mask = Some(image);
+                } else {
+                    job.images.push(image);
+                }
+            },
+            "prompt" | "model" | "n" | "size" | "quality" | "style" | "response_format" => {
+                let value = field.text().await.map_err(|e| {
+                    (StatusCode::BAD_REQUEST, format!("Failed to read {}: {}", name, e))
+                })?;
+                match name.as_str() {
+                    "prompt" => job.prompt = value,
+                    "model" => job.model = value,
+                    "n" => {
+                        job.n = value.trim().parse().map_err(|_| {
+                            (StatusCode::BAD_REQUEST, "'n' must be an integer".to_string())
+                        })?
+                    },
+                    "size" => job.size = Some(value),
+                    "quality" => job.quality = Some(value),
+                    "style" => job.style = Some(value),
+                    _ => job.response_format = Some(value),
+                }
+            },
+            // Intentionally ignored: user, background, output_format etc.
+            _ => {
+                tracing::trace!("Ignoring unknown multipart field: {}", name);
+            },
+        }
+    }
+
+    if job.images.is_empty() {
+        return Err((StatusCode::BAD_REQUEST, "Missing 'image' file".to_string()));
+    }
+    run_image_job(&state, job).await
+}
+
+async fn run_image_job(state: &AppState, job: ImageJob) -> Result<Response, (StatusCode, String)> {
+    validate_image_params(&job.prompt, job.n, job.response_format.as_deref())
+        .map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+
+    let (routed_model, mapped_model, reason) = {
+        let mapping = state.custom_mapping.read().await;
+        match resolve_model_route(&job.model, &mapping) {
+            Ok((mapped, reason)) => (job.model.as_str(), mapped, reason),
+            Err(_) if is_openai_image_model(&job.model) => {
+                let (mapped, reason) = resolve_model_route(DEFAULT_IMAGE_MODEL, &mapping)
+                    .map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+                (DEFAULT_IMAGE_MODEL, mapped, reason)
+            },
+            Err(e) => return Err((StatusCode::BAD_REQUEST, e)),
+        }
+    };
+    let config = resolve_request_config(
+        routed_model,
+        &mapped_model,
+        &None,
+        job.size.as_deref(),
+        job.quality.as_deref(),
+    );
+    let Some(image_config) = config.image_config.clone() else {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            format!("Model '{}' does not support image generation", job.model),
+        ));
+    };
+    info!(
+        "[Images] {} x{} ({} source images): {} -> {}",
+        job.model,
+        job.n,
+        job.images.len(),
+        mapped_model,
+        config.final_model
+    );
+
+    let request = build_image_request(
+        &job.prompt,
+        job.style.as_deref(),
+        &job.images,
+        job.mask.as_ref(),
+        image_config,
+    );
+    let calls = (0..job.n).map(|_| {
+        call_with_rotation(
+            state,
+            "Images",
+            &config.request_type,
+            &config.final_model,
+            "generateContent",
+            |project_id| {
+                json!({
+                    "project": project_id,
+                    "requestId": format!("image-{}", uuid::Uuid::new_v4()),
+                    "request": request,
+                    "model": config.final_model,
+                    "userAgent": "antigravity",
+                    "requestType": config.request_type,
+                })
+            },
+        )
+    });
+    let results = futures::future::join_all(calls).await;
+
+    let mut images: Vec<GeneratedImage> = Vec::new();
+    let mut email: Option<String> = None;
+    let mut first_error: Option<Response> = None;
+    for result in results {
+        match result {
+            Ok(call) => {
+                images.extend(extract_images(&call.body));
+                email.get_or_insert(call.email);
+            },
+            Err(response) => {
+                warn!("[Images] Generation failed with {}", response.status());
+                first_error.get_or_insert(response);
+            },
+        }
+    }
+
+    // Partial results are still useful; only fail when nothing came back
+    if images.is_empty() {
+        return match first_error {
+            Some(response) => Ok(response),
+            None => Err((StatusCode::BAD_GATEWAY, "Upstream returned no image".to_string())),
+        };
+    }
+    images.truncate(job.n as usize);
+
+    Ok((
+        StatusCode::OK,
+        [
+            (X_ACCOUNT_EMAIL, email.unwrap_or_default()),
+            (X_MAPPED_MODEL, config.final_model.clone()),
+            (X_MAPPING_REASON, reason),
+        ],
+        Json(build_images_response(&images, job.response_format.as_deref())),
+    )
+        .into_response())
+}
@@ -1,14 +1,26 @@
 // OpenAI-compatible API handlers
 // Split from monolithic openai.rs for maintainability
 
+mod batches;
 mod chat;
 mod completions;
+mod files;
+mod images;
 mod models;
+mod responses;
 mod responses_format;
 
+pub use batches::{
+    handle_cancel_batch, handle_create_batch, handle_get_batch, handle_list_batches,
+};
 pub use chat::handle_chat_completions;
 pub use completions::handle_completions;
+pub use files::{
+    handle_delete_file, handle_file_content, handle_get_file, handle_list_files, handle_upload_file,
+};
+pub use images::{handle_images_edits, handle_images_generations};
 pub use models::handle_list_models;
+pub use responses::{handle_create_response, handle_delete_response, handle_get_response};
 
 // Shared imports for submodules
 use crate::proxy::retry::{
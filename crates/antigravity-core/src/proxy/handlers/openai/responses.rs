@@ -0,0 +1,210 @@
+//! OpenAI Responses API handlers (`/v1/responses`).
+//!
+//! Requests are translated to Chat Completions and served by [`handle_chat_completions`]
+//! (account rotation, retries, structured outputs), then converted back to Responses
+//! output items or streaming events.
+
+use super::chat::handle_chat_completions;
+use crate::proxy::api_keys::ClientKey;
+use crate::proxy::mappers::openai::responses::{
+    complete_from_chat, create_responses_sse_stream, input_to_messages, output_to_messages,
+    ResponseObject, ResponsesRequest,
+};
+use crate::proxy::mappers::openai::{OpenAIMessage, OpenAIResponse};
+use crate::proxy::response_store::ResponseStore;
+use crate::proxy::server::AppState;
+use axum::{
+    body::Body,
+    extract::{Extension, Json, Path, State},
+    http::{header, HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+};
+use serde_json::{json, Value};
+use std::sync::Arc;
+
+pub async fn handle_create_response(
+    State(state): State<AppState>,
+    client_key: Option<Extension<ClientKey>>,
+    headers: HeaderMap,
+    Json(body): Json<Value>,
+) -> Result<Response, (StatusCode, String)> {
+    let request: ResponsesRequest = serde_json::from_value(body)
+        .map_err(|e| (StatusCode::BAD_REQUEST, format!("Invalid request: {}", e)))?;
+    let key_id = client_key.map(|Extension(key)| key.id);
+
+    let mut conversation = match &request.previous_response_id {
+        Some(id) => state.response_store.conversation(id, key_id.as_deref()).ok_or_else(|| {
+            (StatusCode::NOT_FOUND, format!("Previous response '{}' not found", id))
+        })?,
+        None => Vec::new(),
+    };
+    conversation.extend(input_to_messages(request.input.as_ref()));
+
+    let chat_body = build_chat_body(&request, &conversation);
+    let chat_response = handle_chat_completions(State(state.clone()), headers, Json(chat_body))
+        .await?
+        .into_response();
+    if !chat_response.status().is_success() {
+        return Ok(chat_response);
+    }
+
+    let mut response = ResponseObject::new(
+        format!("resp_{}", uuid::Uuid::new_v4().simple()),
+        request.model.clone(),
+        chrono::Utc::now().timestamp(),
+    );
+    response.previous_response_id.clone_from(&request.previous_response_id);
+    response.instructions.clone_from(&request.instructions);
+    if let Some(metadata) = &request.metadata {
+        response.metadata = metadata.clone();
+    }
+
+    let store = (request.store != Some(false)).then(|| Arc::clone(&state.response_store));
+    let (mut parts, body) = chat_response.into_parts();
+    parts.headers.remove(header::CONTENT_LENGTH);
+
+    if request.stream {
+        let stream = create_responses_sse_stream(body.into_data_stream(), response, move |done| {
+            if let Some(store) = store {
+                store_response(&store, done, conversation, key_id);
+            }
+        });
+        return Ok(Response::from_parts(parts, Body::from_stream(stream)));
+    }
+
+    let bytes = axum::body::to_bytes(body, usize::MAX)
+        .await
+        .map_err(|e| (StatusCode::BAD_GATEWAY, format!("Failed to read response: {}", e)))?;
+    let chat: OpenAIResponse = serde_json::from_slice(&bytes)
+        .map_err(|e| (StatusCode::BAD_GATEWAY, format!("Parse error: {}", e)))?;
+    complete_from_chat(&mut response, &chat);
+    if let Some(store) = store {
+        store_response(&store, &response, conversation, key_id);
+    }
+
+    let body = serde_json::to_vec(&response)
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, format!("Serialize error: {}", e)))?;
+    Ok(Response::from_parts(parts, Body::from(body)))
+}
+
+pub async fn handle_get_response(
+    State(state): State<AppState>,
+    client_key: Option<Extension<ClientKey>>,
+    Path(id): Path<String>,
+) -> Result<Json<ResponseObject>, (StatusCode, String)> {
+    let key_id = client_key.as_ref().map(|Extension(key)| key.id.as_str());
+    state
+        .response_store
+        .get(&id, key_id)
+        .map(Json)
+        .ok_or_else(|| (StatusCode::NOT_FOUND, format!("Response '{}' not found", id)))
+}
+
+pub async fn handle_delete_response(
+    State(state): State<AppState>,
+    client_key: Option<Extension<ClientKey>>,
+    Path(id): Path<String>,
+) -> Result<Json<Value>, (StatusCode, String)> {
+    let key_id = client_key.as_ref().map(|Extension(key)| key.id.as_str());
+    if !state.response_store.delete(&id, key_id) {
+        return Err((StatusCode::NOT_FOUND, format!("Response '{}' not found", id)));
+    }
+    Ok(Json(json!({ "id": id, "object": "response.deleted", "deleted": true })))
+}
+
+fn store_response(
+    store: &ResponseStore,
+    response: &ResponseObject,
+    mut conversation: Vec<OpenAIMessage>,
+    owner: Option<String>,
+) {
+    conversation.extend(output_to_messages(&response.output));
+    store.insert(response.clone(), conversation, owner);
+}
+
+/// Chat Completions request body for a Responses request.
+fn build_chat_body(request: &ResponsesRequest, conversation: &[OpenAIMessage]) -> Value {
+    let mut messages: Vec<Value> = Vec::with_capacity(conversation.len() + 1);
+    if let Some(instructions) = request.instructions.as_ref().filter(|i| !i.is_empty()) {
+        messages.push(json!({ "role": "system", "content": instructions }));
+    }
+    messages.extend(conversation.iter().filter_map(|m| serde_json::to_value(m).ok()));
+
+    let mut body = json!({
+        "model": request.model,
+        "messages": messages,
+        "stream": request.stream,
+    });
+    if let Some(v) = request.temperature {
+        body["temperature"] = json!(v);
+    }
+    if let Some(v) = request.top_p {
+        body["top_p"] = json!(v);
+    }
+    if let Some(v) = request.max_output_tokens {
+        body["max_tokens"] = json!(v);
+    }
+    if let Some(tools) = &request.tools {
+        body["tools"] = json!(tools);
+    }
+    if let Some(choice) = &request.tool_choice {
+        body["tool_choice"] = chat_tool_choice(choice);
+    }
+    if let Some(v) = request.parallel_tool_calls {
+        body["parallel_tool_calls"] = json!(v);
+    }
+    if let Some(format) = request.text.as_ref().and_then(|t| t.get("format")) {
+        body["response_format"] = chat_response_format(format);
+    }
+    body
+}
+
+/// `{"type":"function","name":..}` → `{"type":"function","function":{"name":..}}`.
+fn chat_tool_choice(choice: &Value) -> Value {
+    match choice.get("name") {
+        Some(name) if choice.get("function").is_none() => {
+            json!({ "type": "function", "function": { "name": name } })
+        },
+        _ => choice.clone(),
+    }
+}
+
+/// `text.format` → chat `response_format` (schema fields move under `json_schema`).
+fn chat_response_format(format: &Value) -> Value {
+    if format.get("type").and_then(Value::as_str) != Some("json_schema") {
+        return format.clone();
+    }
+    let mut spec = format.clone();
+    if let Some(obj) = spec.as_object_mut() {
+        obj.remove("type");
+    }
+    json!({ "type": "json_schema", "json_schema": spec })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builds_chat_body_with_converted_options() {
+        let request: ResponsesRequest = serde_json::from_value(json!({
+            "model": "gemini-3-flash",
+            "instructions": "be brief",
+            "input": "hi",
+            "max_output_tokens": 100,
+            "tool_choice": {"type": "function", "name": "lookup"},
+            "text": {"format": {"type": "json_schema", "name": "out", "schema": {"type": "object"}, "strict": true}}
+        }))
+        .unwrap();
+        let conversation = input_to_messages(request.input.as_ref());
+        let body = build_chat_body(&request, &conversation);
+
+        assert_eq!(body["messages"][0], json!({"role": "system", "content": "be brief"}));
+        assert_eq!(body["messages"][1]["content"], "hi");
+        assert_eq!(body["max_tokens"], 100);
+        assert_eq!(body["tool_choice"]["function"]["name"], "lookup");
+        assert_eq!(body["response_format"]["type"], "json_schema");
+        assert_eq!(body["response_format"]["json_schema"]["name"], "out");
+        assert_eq!(body["response_format"]["json_schema"]["strict"], true);
+    }
+}
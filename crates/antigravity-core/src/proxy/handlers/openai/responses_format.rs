@@ -1,4 +1,5 @@
 // Responses API format conversion to Chat Completions format
+use crate::proxy::mappers::openai::responses::{input_to_messages, ResponsesInput};
 use serde_json::{json, Value};
 use tracing::debug;
 
@@ -12,43 +13,35 @@ pub fn is_responses_format(body: &Value) -> bool {
 pub fn convert_responses_to_chat(body: &mut Value) {
     debug!("Detected Responses API format, converting to Chat Completions format");
 
+    let mut messages: Vec<Value> = Vec::new();
+
     // Convert instructions to system message
     if let Some(instructions) = body.get("instructions").and_then(|v| v.as_str()) {
         if !instructions.is_empty() {
-            let system_msg = json!({
+            messages.push(json!({
                 "role": "system",
                 "content": instructions
-            });
-
-            // Initialize messages array if needed
-            if body.get("messages").is_none() {
-                body["messages"] = json!([]);
-            }
-
-            // Insert system message at the beginning
-            if let Some(messages) = body.get_mut("messages").and_then(|v| v.as_array_mut()) {
-                messages.insert(0, system_msg);
-            }
+            }));
         }
     }
 
-    // Convert input to user message
+    // Convert typed input items (messages, images, function calls/outputs, reasoning)
     if let Some(input) = body.get("input") {
-        let user_msg = if input.is_string() {
-            json!({
-                "role": "user",
-                "content": input.as_str().unwrap_or("")
-            })
-        } else {
-            // input is array format, simplified handling
-            json!({
-                "role": "user",
-                "content": input.to_string()
-            })
-        };
-
-        if let Some(messages) = body.get_mut("messages").and_then(|v| v.as_array_mut()) {
-            messages.push(user_msg);
+        match serde_json::from_value::<ResponsesInput>(input.clone()) {
+            Ok(parsed) => messages.extend(
+                input_to_messages(Some(&parsed))
+                    .iter()
+                    .filter_map(|m| serde_json::to_value(m).ok()),
+            ),
+            Err(e) => {
+                debug!("Unrecognized Responses input ({}), forwarding as text", e);
+                messages.push(json!({
+                    "role": "user",
+                    "content": input.to_string()
+                }));
+            },
         }
     }
+
+    body["messages"] = json!(messages);
 }
@@ -0,0 +1,204 @@
+//! OpenAI-compatible `/v1/audio/speech` on Gemini TTS models.
+//!
+//! Without `stream_format` the audio is generated with `generateContent` and sent as
+//! one file. With `stream_format: "audio"` or `"sse"` it comes from
+//! `streamGenerateContent` and is forwarded chunk by chunk as it arrives.
+
+use axum::{
+    body::Body,
+    extract::State,
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use bytes::{Bytes, BytesMut};
+use futures::StreamExt;
+use serde_json::{json, Value};
+use tracing::{error, info};
+
+use crate::proxy::common::header_constants::{X_ACCOUNT_EMAIL, X_MAPPED_MODEL, X_MAPPING_REASON};
+use crate::proxy::common::resolve_model_route;
+use crate::proxy::mappers::gemini::unwrap_response;
+use crate::proxy::mappers::openai::speech::{
+    build_speech_request, delta_event, done_event, encode_audio, extract_audio, wav_header,
+    SpeechFormat, SpeechRequest, StreamFormat,
+};
+use crate::proxy::retry::{
+    call_with_rotation, open_with_rotation, record_request_success, OpenedCall,
+};
+use crate::proxy::server::AppState;
+
+/// OpenAI-compatible `/v1/audio/speech`.
+pub async fn handle_audio_speech(
+    State(state): State<AppState>,
+    Json(body): Json<Value>,
+) -> Result<Response, (StatusCode, String)> {
+    let request: SpeechRequest = serde_json::from_value(body)
+        .map_err(|e| (StatusCode::BAD_REQUEST, format!("Invalid request: {}", e)))?;
+    let (format, stream) = request.validate().map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+
+    let (mapped_model, reason) =
+        resolve_model_route(&request.model, &*state.custom_mapping.read().await)
+            .map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+    if !mapped_model.contains("-tts") {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            format!("Model {} ({}) does not support speech output", request.model, mapped_model),
+        ));
+    }
+    info!(
+        "[Speech] {} chars, voice {}, {:?}/{:?}: {} -> {}",
+        request.input.chars().count(),
+        request.voice,
+        format,
+        stream,
+        request.model,
+        mapped_model
+    );
+
+    let gemini_request =
+        build_speech_request(&request).map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+    let wrap = |project_id: &str| {
+        json!({
+            "project": project_id,
+            "requestId": format!("speech-{}", uuid::Uuid::new_v4()),
+            "request": gemini_request,
+            "model": mapped_model,
+            "userAgent": "antigravity",
+        })
+    };
+
+    if stream == StreamFormat::Buffered {
+        let result = match call_with_rotation(
+            &state,
+            "Speech",
+            "text",
+            &mapped_model,
+            "generateContent",
+            wrap,
+        )
+        .await
+        {
+            Ok(result) => result,
+            Err(response) => return Ok(response),
+        };
+        let (pcm, sample_rate) = extract_audio(&result.body)
+            .map_err(|e| (StatusCode::BAD_GATEWAY, e))?
+            .ok_or((StatusCode::BAD_GATEWAY, "Upstream returned no audio".to_string()))?;
+        return Ok((
+            StatusCode::OK,
+            [
+                (header::CONTENT_TYPE.as_str(), format.content_type()),
+                (X_ACCOUNT_EMAIL, result.email.as_str()),
+                (X_MAPPED_MODEL, mapped_model.as_str()),
+                (X_MAPPING_REASON, reason.as_str()),
+            ],
+            encode_audio(format, pcm, sample_rate),
+        )
+            .into_response());
+    }
+
+    let opened = match open_with_rotation(
+        &state,
+        "Speech",
+        "text",
+        &mapped_model,
+        "streamGenerateContent",
+        Some("alt=sse"),
+        wrap,
+    )
+    .await
+    {
+        Ok(opened) => opened,
+        Err(response) => return Ok(response),
+    };
+    let email = opened.email.clone();
+    let content_type =
+        if stream == StreamFormat::Sse { "text/event-stream" } else { format.content_type() };
+    Response::builder()
+        .status(StatusCode::OK)
+        .header(header::CONTENT_TYPE, content_type)
+        .header(header::CACHE_CONTROL, "no-cache")
+        .header(X_ACCOUNT_EMAIL, email)
+        .header(X_MAPPED_MODEL, mapped_model)
+        .header(X_MAPPING_REASON, reason)
+        .body(stream_audio(state, opened, format, stream))
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, format!("Response build error: {}", e)))
+}
+
+/// Forward the audio of an upstream SSE stream as raw audio or as speech events.
+fn stream_audio(
+    state: AppState,
+    opened: OpenedCall,
+    format: SpeechFormat,
+    stream_format: StreamFormat,
+) -> Body {
+    let OpenedCall { response, email, guard } = opened;
+    let mut upstream = response.bytes_stream();
+    let stream = async_stream::stream! {
+        const MAX_BUFFER_SIZE: usize = 10 * 1024 * 1024;
+        let _guard = guard;
+        let mut buffer = BytesMut::new();
+        let mut header_sent = false;
+        let mut usage: Option<Value> = None;
+
+        while let Some(item) = upstream.next().await {
+            let bytes = match item {
+                Ok(bytes) => bytes,
+                Err(e) => {
+                    error!("[Speech] Stream error on {}: {}", email, e);
+                    yield Err("Stream error".to_string());
+                    return;
+                },
+            };
+            buffer.extend_from_slice(&bytes);
+            if buffer.len() > MAX_BUFFER_SIZE {
+                error!("[Speech] Buffer overflow, dropping connection");
+                yield Err("Buffer overflow".to_string());
+                return;
+            }
+
+            while let Some(pos) = buffer.iter().position(|&b| b == b'\n') {
+                let line = buffer.split_to(pos + 1);
+                let Some(data) = std::str::from_utf8(&line)
+                    .ok()
+                    .and_then(|l| l.trim().strip_prefix("data:"))
+                    .map(str::trim)
+                else {
+                    continue;
+                };
+                let Ok(chunk) = serde_json::from_str::<Value>(data) else {
+                    continue;
+                };
+                let chunk = unwrap_response(&chunk);
+                if let Some(meta) = chunk.get("usageMetadata") {
+                    usage = Some(meta.clone());
+                }
+                let (pcm, sample_rate) = match extract_audio(&chunk) {
+                    Ok(Some(audio)) => audio,
+                    Ok(None) => continue,
+                    Err(e) => {
+                        error!("[Speech] {}", e);
+                        yield Err(e);
+                        return;
+                    },
+                };
+                if stream_format == StreamFormat::Sse {
+                    yield Ok::<Bytes, String>(Bytes::from(delta_event(&pcm)));
+                    continue;
+                }
+                if format == SpeechFormat::Wav && !header_sent {
+                    header_sent = true;
+                    yield Ok(Bytes::from(wav_header(sample_rate, None)));
+                }
+                yield Ok(Bytes::from(pcm));
+            }
+        }
+
+        record_request_success(&state.token_manager, &state, &email, "");
+        if stream_format == StreamFormat::Sse {
+            yield Ok(Bytes::from(done_event(usage.as_ref())));
+        }
+    };
+    Body::from_stream(stream)
+}
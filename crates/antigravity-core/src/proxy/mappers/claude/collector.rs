@@ -67,7 +67,7 @@ where
         role: "assistant".to_string(),
         model: String::new(),
         content: Vec::new(),
-        stop_reason: "end_turn".to_string(),
+        stop_reason: Some("end_turn".to_string()),
         stop_sequence: None,
         usage: Usage {
             input_tokens: 0,
@@ -206,7 +206,7 @@ where
             "message_delta" => {
                 if let Some(delta) = event.data.get("delta") {
                     if let Some(stop_reason) = delta.get("stop_reason").and_then(|v| v.as_str()) {
-                        response.stop_reason = stop_reason.to_string();
+                        response.stop_reason = Some(stop_reason.to_string());
                     }
                 }
                 if let Some(usage) = event.data.get("usage") {
@@ -14,6 +14,11 @@ use crate::proxy::SignatureCache;
 use serde_json::{json, Value};
 use std::collections::HashMap;
 
+#[tracing::instrument(
+    name = "transform_request",
+    skip_all,
+    fields(protocol = "claude", model = %claude_req.model, is_retry = is_retry)
+)]
 pub fn transform_claude_request_in(
     claude_req: &ClaudeRequest,
     project_id: &str,
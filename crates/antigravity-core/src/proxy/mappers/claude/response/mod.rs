@@ -192,7 +192,7 @@ impl NonStreamingProcessor {
             role: "assistant".to_string(),
             model: gemini_response.model_version.clone().unwrap_or_default(),
             content: self.content_blocks.clone(),
-            stop_reason: stop_reason.to_string(),
+            stop_reason: Some(stop_reason.to_string()),
             stop_sequence: None,
             usage,
         }
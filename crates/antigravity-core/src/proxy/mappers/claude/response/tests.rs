@@ -23,6 +23,7 @@ fn test_simple_text_response() {
         usage_metadata: Some(UsageMetadata {
             prompt_token_count: Some(10),
             candidates_token_count: Some(5),
+            thoughts_token_count: None,
             total_token_count: Some(15),
             cached_content_token_count: None,
         }),
@@ -36,7 +37,7 @@ fn test_simple_text_response() {
 
     let claude_resp = result.unwrap();
     assert_eq!(claude_resp.role, "assistant");
-    assert_eq!(claude_resp.stop_reason, "end_turn");
+    assert_eq!(claude_resp.stop_reason.as_deref(), Some("end_turn"));
     assert_eq!(claude_resp.content.len(), 1);
 
     match &claude_resp.content[0] {
@@ -25,6 +25,7 @@ pub fn create_claude_sse_stream(
     scaling_enabled: bool,
     context_limit: u32,
     estimated_tokens: Option<u32>,
+    cache_created: bool,
 ) -> Pin<Box<dyn Stream<Item = Result<Bytes, String>> + Send>> {
     use async_stream::stream;
     use bytes::BytesMut;
@@ -37,6 +38,7 @@ pub fn create_claude_sse_stream(
         state.scaling_enabled = scaling_enabled;
         state.context_limit = context_limit;
         state.estimated_tokens = estimated_tokens;
+        state.cache_created = cache_created;
         let mut buffer = BytesMut::new();
         const MAX_BUFFER_SIZE: usize = 10 * 1024 * 1024; // 10MB safety limit for SSE line buffering
 
@@ -57,6 +57,8 @@ pub struct StreamingState {
     pub in_mcp_xml: bool,
     /// Estimated token count for the response.
     pub estimated_tokens: Option<u32>,
+    /// Whether this request created its upstream context cache.
+    pub cache_created: bool,
     /// Accumulated thinking content for content-based signature caching.
     accumulated_thinking: String,
     /// Whether thinking was received in the response stream.
@@ -97,6 +99,7 @@ impl StreamingState {
             mcp_xml_buffer: String::new(),
             in_mcp_xml: false,
             estimated_tokens: None,
+            cache_created: false,
             accumulated_thinking: String::new(),
             thinking_received: false,
             thinking_requested: false,
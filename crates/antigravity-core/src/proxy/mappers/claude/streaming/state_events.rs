@@ -2,10 +2,19 @@ use super::state::StreamingState;
 use bytes::Bytes;
 use serde_json::json;
 
-use crate::proxy::mappers::claude::models::Usage;
+use crate::proxy::mappers::claude::models::{Usage, UsageMetadata};
 use crate::proxy::mappers::claude::token_scaling::to_claude_usage;
 
 impl StreamingState {
+    /// Claude usage for upstream `usage`, with cache creation reported when this request wrote it.
+    pub(super) fn claude_usage(&self, usage: &UsageMetadata) -> Usage {
+        let mut usage = to_claude_usage(usage, self.scaling_enabled, self.context_limit);
+        if self.cache_created {
+            usage.report_cache_creation();
+        }
+        usage
+    }
+
     pub fn emit_message_start(&mut self, raw_json: &serde_json::Value) -> Bytes {
         if self.message_start_sent {
             return Bytes::new();
@@ -13,10 +22,8 @@ impl StreamingState {
 
         let usage = raw_json
             .get("usageMetadata")
-            .and_then(|u| {
-                serde_json::from_value::<super::super::gemini_models::UsageMetadata>(u.clone()).ok()
-            })
-            .map(|u| to_claude_usage(&u, self.scaling_enabled, self.context_limit))
+            .and_then(|u| serde_json::from_value::<UsageMetadata>(u.clone()).ok())
+            .map(|u| self.claude_usage(&u))
             .unwrap_or(Usage {
                 input_tokens: 0,
                 output_tokens: 0,
@@ -3,7 +3,6 @@ use serde_json::json;
 
 use super::state::{BlockType, StreamingState};
 use crate::proxy::mappers::claude::models::*;
-use crate::proxy::mappers::claude::token_scaling::to_claude_usage;
 use crate::proxy::SignatureCache;
 
 impl StreamingState {
@@ -64,15 +63,13 @@ impl StreamingState {
 
         let stop_reason = self.determine_stop_reason(finish_reason, stream_truncated);
 
-        let usage = usage_metadata
-            .map(|u| to_claude_usage(u, self.scaling_enabled, self.context_limit))
-            .unwrap_or(Usage {
-                input_tokens: 0,
-                output_tokens: 0,
-                cache_read_input_tokens: None,
-                cache_creation_input_tokens: None,
-                server_tool_use: None,
-            });
+        let usage = usage_metadata.map(|u| self.claude_usage(u)).unwrap_or(Usage {
+            input_tokens: 0,
+            output_tokens: 0,
+            cache_read_input_tokens: None,
+            cache_creation_input_tokens: None,
+            server_tool_use: None,
+        });
 
         if let (Some(estimated), Some(um)) = (self.estimated_tokens, usage_metadata) {
             let actual =
@@ -109,6 +109,7 @@ mod tests {
         let usage = UsageMetadata {
             prompt_token_count: Some(100),
             candidates_token_count: Some(50),
+            thoughts_token_count: None,
             total_token_count: Some(150),
             cached_content_token_count: None,
         };
@@ -121,6 +122,7 @@ mod tests {
         let usage_70 = UsageMetadata {
             prompt_token_count: Some(700_000),
             candidates_token_count: Some(10),
+            thoughts_token_count: None,
             total_token_count: Some(700_010),
             cached_content_token_count: None,
         };
@@ -132,6 +134,7 @@ mod tests {
         let usage_100 = UsageMetadata {
             prompt_token_count: Some(1_000_000),
             candidates_token_count: Some(10),
+            thoughts_token_count: None,
             total_token_count: Some(1_000_010),
             cached_content_token_count: None,
         };
@@ -143,6 +146,7 @@ mod tests {
         let usage_90 = UsageMetadata {
             prompt_token_count: Some(900_000),
             candidates_token_count: Some(10),
+            thoughts_token_count: None,
             total_token_count: Some(900_010),
             cached_content_token_count: None,
         };
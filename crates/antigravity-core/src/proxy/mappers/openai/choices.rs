@@ -0,0 +1,137 @@
+//! Multiple choices (`n`) for chat completions.
+//!
+//! Gemini models return several candidates from one call via `candidateCount`.
+//! Other upstream models (Claude through the bridge, image models) only ever return
+//! one, so the handler fans out parallel calls and merges them with these helpers.
+
+use serde_json::Value;
+
+use super::models::{CompletionTokensDetails, OpenAIResponse, OpenAIUsage, PromptTokensDetails};
+
+/// Upper bound for `n`.
+pub const MAX_CHOICES: u32 = 8;
+
+/// Whether the upstream model can return several candidates from one call.
+pub fn supports_candidate_count(mapped_model: &str) -> bool {
+    !mapped_model.starts_with("claude-") && !mapped_model.contains("image")
+}
+
+pub fn validate_choice_count(n: Option<u32>) -> Result<u32, String> {
+    match n.unwrap_or(1) {
+        n @ 1..=MAX_CHOICES => Ok(n),
+        _ => Err(format!("'n' must be between 1 and {}", MAX_CHOICES)),
+    }
+}
+
+fn add_opt(a: Option<u32>, b: Option<u32>) -> Option<u32> {
+    match (a, b) {
+        (None, None) => None,
+        (a, b) => Some(a.unwrap_or(0) + b.unwrap_or(0)),
+    }
+}
+
+/// Sum two usage records; every call of a fan-out is billed for its prompt.
+pub fn sum_usage(a: Option<OpenAIUsage>, b: Option<&OpenAIUsage>) -> Option<OpenAIUsage> {
+    let (a, b) = match (a, b) {
+        (None, None) => return None,
+        (Some(a), None) => return Some(a),
+        (None, Some(b)) => return Some(b.clone()),
+        (Some(a), Some(b)) => (a, b),
+    };
+    let cached = add_opt(
+        a.prompt_tokens_details.and_then(|d| d.cached_tokens),
+        b.prompt_tokens_details.as_ref().and_then(|d| d.cached_tokens),
+    );
+    let reasoning = add_opt(
+        a.completion_tokens_details.and_then(|d| d.reasoning_tokens),
+        b.completion_tokens_details.as_ref().and_then(|d| d.reasoning_tokens),
+    );
+    Some(OpenAIUsage {
+        prompt_tokens: a.prompt_tokens + b.prompt_tokens,
+        completion_tokens: a.completion_tokens + b.completion_tokens,
+        total_tokens: a.total_tokens + b.total_tokens,
+        prompt_tokens_details: cached.map(|c| PromptTokensDetails { cached_tokens: Some(c) }),
+        completion_tokens_details: reasoning
+            .map(|r| CompletionTokensDetails { reasoning_tokens: Some(r) }),
+    })
+}
+
+/// Merge fan-out responses into one, numbering choices in response order.
+pub fn merge_responses(responses: Vec<OpenAIResponse>) -> Option<OpenAIResponse> {
+    let mut iter = responses.into_iter();
+    let mut merged = iter.next()?;
+    for response in iter {
+        merged.choices.extend(response.choices);
+        merged.usage = sum_usage(merged.usage.take(), response.usage.as_ref());
+    }
+    for (index, choice) in merged.choices.iter_mut().enumerate() {
+        choice.index = index as u32;
+    }
+    Some(merged)
+}
+
+/// Move a stream chunk's choices to `offset` and give it the merged stream's id.
+pub fn reindex_chunk(chunk: &mut Value, offset: u32, stream_id: &str) {
+    if let Some(obj) = chunk.as_object_mut() {
+        obj.insert("id".to_string(), Value::String(stream_id.to_string()));
+    }
+    for choice in chunk.get_mut("choices").and_then(Value::as_array_mut).into_iter().flatten() {
+        let index = choice.get("index").and_then(Value::as_u64).unwrap_or(0);
+        choice["index"] = Value::from(index + u64::from(offset));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn response(text: &str, completion_tokens: u32) -> OpenAIResponse {
+        serde_json::from_value(json!({
+            "id": "chatcmpl-1", "object": "chat.completion", "created": 1, "model": "m",
+            "choices": [{"index": 0, "message": {"role": "assistant", "content": text},
+                         "finish_reason": "stop"}],
+            "usage": {"prompt_tokens": 10, "completion_tokens": completion_tokens,
+                      "total_tokens": 10 + completion_tokens,
+                      "completion_tokens_details": {"reasoning_tokens": 2}}
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn validates_choice_count() {
+        assert_eq!(validate_choice_count(None), Ok(1));
+        assert_eq!(validate_choice_count(Some(4)), Ok(4));
+        assert!(validate_choice_count(Some(0)).is_err());
+        assert!(validate_choice_count(Some(MAX_CHOICES + 1)).is_err());
+    }
+
+    #[test]
+    fn candidate_count_support_by_model() {
+        assert!(supports_candidate_count("gemini-3-flash"));
+        assert!(!supports_candidate_count("claude-sonnet-4-5"));
+        assert!(!supports_candidate_count("gemini-3-pro-image"));
+    }
+
+    #[test]
+    fn merges_choices_and_sums_usage() {
+        let merged = merge_responses(vec![response("a", 3), response("b", 5)]).unwrap();
+        let indices: Vec<u32> = merged.choices.iter().map(|c| c.index).collect();
+        assert_eq!(indices, vec![0, 1]);
+
+        let usage = merged.usage.unwrap();
+        assert_eq!(usage.prompt_tokens, 20);
+        assert_eq!(usage.completion_tokens, 8);
+        assert_eq!(usage.total_tokens, 28);
+        assert_eq!(usage.completion_tokens_details.unwrap().reasoning_tokens, Some(4));
+        assert!(usage.prompt_tokens_details.is_none());
+    }
+
+    #[test]
+    fn reindexes_stream_chunks() {
+        let mut chunk = json!({"id": "chatcmpl-x", "choices": [{"index": 0, "delta": {}}]});
+        reindex_chunk(&mut chunk, 2, "chatcmpl-merged");
+        assert_eq!(chunk["id"], "chatcmpl-merged");
+        assert_eq!(chunk["choices"][0]["index"], 2);
+    }
+}
@@ -5,10 +5,51 @@ use super::models::*;
 use bytes::Bytes;
 use futures::StreamExt;
 use serde_json::Value;
+use std::collections::BTreeMap;
 use std::io;
 
 use crate::proxy::common::sse_parser::parse_sse_line;
 
+/// Per-choice state while collecting.
+#[derive(Default)]
+struct ChoiceAccumulator {
+    content: String,
+    reasoning_content: String,
+    tool_calls: Vec<ToolCall>,
+    finish_reason: Option<String>,
+}
+
+impl ChoiceAccumulator {
+    fn into_choice(self, index: u32) -> Choice {
+        let reasoning_content =
+            if self.reasoning_content.is_empty() { None } else { Some(self.reasoning_content) };
+        let message = if !self.tool_calls.is_empty() {
+            OpenAIMessage {
+                role: "assistant".to_string(),
+                content: if self.content.is_empty() {
+                    None
+                } else {
+                    Some(OpenAIContent::String(self.content))
+                },
+                tool_calls: Some(self.tool_calls),
+                reasoning_content,
+                tool_call_id: None,
+                name: None,
+            }
+        } else {
+            OpenAIMessage {
+                role: "assistant".to_string(),
+                content: Some(OpenAIContent::String(self.content)),
+                tool_calls: None,
+                reasoning_content,
+                tool_call_id: None,
+                name: None,
+            }
+        };
+        Choice { index, message, finish_reason: self.finish_reason }
+    }
+}
+
 /// SSE event type
 #[derive(Debug, Clone)]
 struct SseEvent {
@@ -64,10 +105,7 @@ where
         usage: None,
     };
 
-    let mut content = String::new();
-    let mut reasoning_content = String::new();
-    let mut tool_calls: Vec<ToolCall> = Vec::new();
-    let mut finish_reason: Option<String> = None;
+    let mut accumulated: BTreeMap<u32, ChoiceAccumulator> = BTreeMap::new();
 
     for event in chunks {
         // Extract basic info
@@ -81,19 +119,22 @@ where
             response.created = created;
         }
 
-        // handle choices
+        // handle choices, keyed by index so n > 1 keeps candidates apart
         if let Some(choices_arr) = event.data.get("choices").and_then(|v| v.as_array()) {
             for choice in choices_arr {
+                let choice_index = choice.get("index").and_then(|v| v.as_u64()).unwrap_or(0);
+                let acc = accumulated.entry(choice_index as u32).or_default();
+
                 if let Some(delta) = choice.get("delta") {
                     // Accumulate content
                     if let Some(text) = delta.get("content").and_then(|v| v.as_str()) {
-                        content.push_str(text);
+                        acc.content.push_str(text);
                     }
 
                     // Accumulate reasoning_content (thinking process)
                     if let Some(reasoning) = delta.get("reasoning_content").and_then(|v| v.as_str())
                     {
-                        reasoning_content.push_str(reasoning);
+                        acc.reasoning_content.push_str(reasoning);
                     }
 
                     // Accumulate tool_calls
@@ -101,6 +142,7 @@ where
                         for tc in tc_arr {
                             let index =
                                 tc.get("index").and_then(|v| v.as_u64()).unwrap_or(0) as usize;
+                            let tool_calls = &mut acc.tool_calls;
 
                             // Ensure tool_calls has enough space
                             while tool_calls.len() <= index {
@@ -131,7 +173,7 @@ where
 
                 // get finish_reason
                 if let Some(reason) = choice.get("finish_reason").and_then(|v| v.as_str()) {
-                    finish_reason = Some(reason.to_string());
+                    acc.finish_reason = Some(reason.to_string());
                 }
             }
         }
@@ -143,36 +185,11 @@ where
         }
     }
 
-    // 3. Build final choice
-    let message = if !tool_calls.is_empty() {
-        OpenAIMessage {
-            role: "assistant".to_string(),
-            content: if content.is_empty() { None } else { Some(OpenAIContent::String(content)) },
-            tool_calls: Some(tool_calls),
-            reasoning_content: if reasoning_content.is_empty() {
-                None
-            } else {
-                Some(reasoning_content)
-            },
-            tool_call_id: None,
-            name: None,
-        }
-    } else {
-        OpenAIMessage {
-            role: "assistant".to_string(),
-            content: Some(OpenAIContent::String(content)),
-            tool_calls: None,
-            reasoning_content: if reasoning_content.is_empty() {
-                None
-            } else {
-                Some(reasoning_content)
-            },
-            tool_call_id: None,
-            name: None,
-        }
-    };
-
-    response.choices.push(Choice { index: 0, message, finish_reason });
+    // 3. Build final choices (always at least one)
+    if accumulated.is_empty() {
+        accumulated.insert(0, ChoiceAccumulator::default());
+    }
+    response.choices = accumulated.into_iter().map(|(index, acc)| acc.into_choice(index)).collect();
 
     Ok(response)
 }
@@ -208,4 +225,26 @@ mod tests {
             panic!("Expected String content");
         }
     }
+
+    #[tokio::test]
+    async fn test_collect_keeps_choices_apart() {
+        let sse_data = vec![
+            "data: {\"id\":\"c\",\"choices\":[{\"index\":0,\"delta\":{\"content\":\"A\"}}]}\n\n",
+            "data: {\"id\":\"c\",\"choices\":[{\"index\":1,\"delta\":{\"content\":\"B\"},\"finish_reason\":\"length\"}]}\n\n",
+            "data: {\"id\":\"c\",\"choices\":[{\"index\":0,\"delta\":{\"content\":\"a\"},\"finish_reason\":\"stop\"}]}\n\n",
+            "data: [DONE]\n\n",
+        ];
+        let byte_stream =
+            stream::iter(sse_data.into_iter().map(|s| Ok::<Bytes, io::Error>(Bytes::from(s))));
+
+        let response = collect_openai_stream_to_json(byte_stream).await.unwrap();
+        assert_eq!(response.choices.len(), 2);
+        assert_eq!(response.choices[0].finish_reason.as_deref(), Some("stop"));
+        assert_eq!(response.choices[1].index, 1);
+        assert_eq!(response.choices[1].finish_reason.as_deref(), Some("length"));
+        match &response.choices[0].message.content {
+            Some(OpenAIContent::String(text)) => assert_eq!(text, "Aa"),
+            other => panic!("Expected String content, got {:?}", other),
+        }
+    }
 }
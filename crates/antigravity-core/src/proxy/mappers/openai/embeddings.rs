@@ -0,0 +1,213 @@
+//! OpenAI embeddings ↔ Gemini `batchEmbedContents` conversion.
+
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use crate::proxy::mappers::context_manager::estimate_tokens_from_str;
+
+/// Gemini accepts at most this many requests per `batchEmbedContents` call.
+pub const MAX_BATCH_INPUTS: usize = 100;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct EmbeddingRequest {
+    pub model: String,
+    pub input: EmbeddingInput,
+    #[serde(default)]
+    pub dimensions: Option<u32>,
+    #[serde(default)]
+    pub encoding_format: Option<String>,
+    #[serde(default)]
+    pub user: Option<String>,
+}
+
+/// A single string or a batch of strings. Token-id arrays are not supported upstream.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum EmbeddingInput {
+    Text(String),
+    Batch(Vec<String>),
+}
+
+impl EmbeddingInput {
+    pub fn texts(&self) -> Vec<&str> {
+        match self {
+            Self::Text(text) => vec![text.as_str()],
+            Self::Batch(texts) => texts.iter().map(String::as_str).collect(),
+        }
+    }
+}
+
+impl EmbeddingRequest {
+    /// Reject requests the upstream would fail on anyway.
+    pub fn validate(&self) -> Result<(), String> {
+        let texts = self.input.texts();
+        if texts.is_empty() {
+            return Err("'input' must not be empty".to_string());
+        }
+        if texts.len() > MAX_BATCH_INPUTS {
+            return Err(format!(
+                "'input' has {} items, at most {} are supported",
+                texts.len(),
+                MAX_BATCH_INPUTS
+            ));
+        }
+        if texts.iter().any(|t| t.is_empty()) {
+            return Err("'input' must not contain empty strings".to_string());
+        }
+        if self.dimensions == Some(0) {
+            return Err("'dimensions' must be greater than 0".to_string());
+        }
+        match self.encoding_format.as_deref() {
+            None | Some("float") | Some("base64") => Ok(()),
+            Some(other) => Err(format!("Unsupported encoding_format: {}", other)),
+        }
+    }
+}
+
+/// Gemini `batchEmbedContents` body for an OpenAI request.
+pub fn build_batch_request(request: &EmbeddingRequest, mapped_model: &str) -> Value {
+    let model = format!("models/{}", mapped_model);
+    let requests: Vec<Value> = request
+        .input
+        .texts()
+        .into_iter()
+        .map(|text| {
+            let mut entry = json!({
+                "model": model,
+                "content": { "parts": [{ "text": text }] },
+            });
+            if let Some(dimensions) = request.dimensions {
+                entry["outputDimensionality"] = json!(dimensions);
+            }
+            entry
+        })
+        .collect();
+    json!({ "requests": requests })
+}
+
+/// Vectors from an `embedContent` (`embedding`) or `batchEmbedContents` (`embeddings`) response.
+pub fn extract_embeddings(response: &Value) -> Result<Vec<Vec<f32>>, String> {
+    let values = |embedding: &Value| -> Result<Vec<f32>, String> {
+        embedding
+            .get("values")
+            .and_then(Value::as_array)
+            .ok_or_else(|| "Embedding without values".to_string())?
+            .iter()
+            .map(|v| v.as_f64().map(|f| f as f32).ok_or_else(|| "Non-numeric value".to_string()))
+            .collect()
+    };
+
+    if let Some(list) = response.get("embeddings").and_then(Value::as_array) {
+        return list.iter().map(values).collect();
+    }
+    if let Some(single) = response.get("embedding") {
+        return Ok(vec![values(single)?]);
+    }
+    Err("Upstream response contains no embeddings".to_string())
+}
+
+/// OpenAI `list` response for the request's inputs and the upstream vectors.
+pub fn build_openai_response(request: &EmbeddingRequest, embeddings: Vec<Vec<f32>>) -> Value {
+    let base64 = request.encoding_format.as_deref() == Some("base64");
+    let data: Vec<Value> = embeddings
+        .into_iter()
+        .enumerate()
+        .map(|(index, mut vector)| {
+            // Gemini only normalizes full-size vectors; truncated ones need it here
+            if request.dimensions.is_some() {
+                normalize(&mut vector);
+            }
+            let embedding = if base64 { json!(encode_base64(&vector)) } else { json!(vector) };
+            json!({ "object": "embedding", "index": index, "embedding": embedding })
+        })
+        .collect();
+
+    let prompt_tokens: u32 = request.input.texts().into_iter().map(estimate_tokens_from_str).sum();
+    json!({
+        "object": "list",
+        "data": data,
+        "model": request.model,
+        "usage": { "prompt_tokens": prompt_tokens, "total_tokens": prompt_tokens },
+    })
+}
+
+fn normalize(vector: &mut [f32]) {
+    let norm = vector.iter().map(|v| v * v).sum::<f32>().sqrt();
+    if norm > 0.0 {
+        vector.iter_mut().for_each(|v| *v /= norm);
+    }
+}
+
+/// Little-endian f32 bytes, as the OpenAI SDKs decode `encoding_format: base64`.
+fn encode_base64(vector: &[f32]) -> String {
+    let bytes: Vec<u8> = vector.iter().flat_map(|v| v.to_le_bytes()).collect();
+    STANDARD.encode(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(body: Value) -> EmbeddingRequest {
+        serde_json::from_value(body).unwrap()
+    }
+
+    #[test]
+    fn builds_batch_request_with_dimensions() {
+        let req = request(json!({
+            "model": "text-embedding-3-small",
+            "input": ["a", "b"],
+            "dimensions": 256
+        }));
+        assert!(req.validate().is_ok());
+        let body = build_batch_request(&req, "gemini-embedding-001");
+        let requests = body["requests"].as_array().unwrap();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(requests[0]["model"], "models/gemini-embedding-001");
+        assert_eq!(requests[1]["content"]["parts"][0]["text"], "b");
+        assert_eq!(requests[0]["outputDimensionality"], 256);
+    }
+
+    #[test]
+    fn rejects_invalid_input() {
+        assert!(request(json!({"model": "m", "input": []})).validate().is_err());
+        assert!(request(json!({"model": "m", "input": ""})).validate().is_err());
+        assert!(request(json!({"model": "m", "input": "x", "encoding_format": "int8"}))
+            .validate()
+            .is_err());
+        assert!(serde_json::from_value::<EmbeddingRequest>(json!({"model": "m", "input": [1, 2]}))
+            .is_err());
+    }
+
+    #[test]
+    fn converts_batch_response_to_openai_list() {
+        let req = request(json!({"model": "text-embedding-3-small", "input": ["hello", "world"]}));
+        let upstream = json!({"embeddings": [{"values": [0.1, 0.2]}, {"values": [0.3, 0.4]}]});
+        let out = build_openai_response(&req, extract_embeddings(&upstream).unwrap());
+
+        assert_eq!(out["object"], "list");
+        assert_eq!(out["model"], "text-embedding-3-small");
+        assert_eq!(out["data"][1]["index"], 1);
+        assert_eq!(out["data"][1]["embedding"].as_array().unwrap().len(), 2);
+        assert!(out["usage"]["prompt_tokens"].as_u64().unwrap() > 0);
+    }
+
+    #[test]
+    fn truncated_vectors_are_normalized_and_base64_encoded() {
+        let req = request(json!({
+            "model": "m",
+            "input": "x",
+            "dimensions": 2,
+            "encoding_format": "base64"
+        }));
+        let out = build_openai_response(
+            &req,
+            extract_embeddings(&json!({"embedding": {"values": [3.0, 4.0]}})).unwrap(),
+        );
+        let bytes = STANDARD.decode(out["data"][0]["embedding"].as_str().unwrap()).unwrap();
+        let floats: Vec<f32> =
+            bytes.chunks(4).map(|c| f32::from_le_bytes([c[0], c[1], c[2], c[3]])).collect();
+        assert_eq!(floats, vec![0.6, 0.8]);
+    }
+}
@@ -0,0 +1,224 @@
+//! OpenAI Images API (`/v1/images/*`) ↔ Gemini image generation.
+
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+/// Default model when the client omits one.
+pub const DEFAULT_IMAGE_MODEL: &str = "gemini-3-pro-image";
+/// Upper bound for `n`, matching the OpenAI API.
+pub const MAX_IMAGES: u32 = 10;
+
+fn default_n() -> u32 {
+    1
+}
+
+/// `POST /v1/images/generations` request.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ImageGenerationRequest {
+    pub prompt: String,
+    #[serde(default)]
+    pub model: Option<String>,
+    #[serde(default = "default_n")]
+    pub n: u32,
+    #[serde(default)]
+    pub size: Option<String>,
+    #[serde(default)]
+    pub quality: Option<String>,
+    #[serde(default)]
+    pub response_format: Option<String>,
+    #[serde(default)]
+    pub style: Option<String>,
+    #[serde(default)]
+    pub user: Option<String>,
+}
+
+/// Image sent inline to the upstream model.
+#[derive(Debug, Clone, PartialEq)]
+pub struct InlineImage {
+    pub mime_type: String,
+    /// Base64-encoded bytes.
+    pub data: String,
+}
+
+/// Generated image plus any text the model returned alongside it.
+#[derive(Debug, Clone)]
+pub struct GeneratedImage {
+    pub image: InlineImage,
+    pub revised_prompt: Option<String>,
+}
+
+/// OpenAI image model names, served by [`DEFAULT_IMAGE_MODEL`] unless a custom mapping
+/// says otherwise.
+pub fn is_openai_image_model(model: &str) -> bool {
+    model.starts_with("dall-e") || model.starts_with("gpt-image")
+}
+
+/// Shared checks for generation and edit parameters.
+pub fn validate_image_params(
+    prompt: &str,
+    n: u32,
+    response_format: Option<&str>,
+) -> Result<(), String> {
+    if prompt.trim().is_empty() {
+        return Err("'prompt' must not be empty".to_string());
+    }
+    if n == 0 || n > MAX_IMAGES {
+        return Err(format!("'n' must be between 1 and {}", MAX_IMAGES));
+    }
+    match response_format {
+        None | Some("b64_json") | Some("url") => Ok(()),
+        Some(other) => Err(format!("Unsupported response_format: {}", other)),
+    }
+}
+
+/// Gemini request body (before wrapping) for a prompt and optional source images.
+///
+/// `image_config` comes from `resolve_request_config`. A mask follows the source
+/// images; the prompt tells the model how to read it since Gemini has no native mask
+/// input.
+pub fn build_image_request(
+    prompt: &str,
+    style: Option<&str>,
+    images: &[InlineImage],
+    mask: Option<&InlineImage>,
+    image_config: Value,
+) -> Value {
+    let mut text = prompt.to_string();
+    if let Some(style) = style {
+        text.push_str(&format!("\n\nStyle: {}.", style));
+    }
+    if mask.is_some() {
+        text.push_str(
+            "\n\nThe last image is a mask. Only change the regions that are transparent in \
+             the mask and keep everything else in the first image unchanged.",
+        );
+    }
+
+    let mut parts = vec![json!({ "text": text })];
+    for image in images.iter().chain(mask) {
+        parts.push(json!({
+            "inlineData": { "mimeType": image.mime_type, "data": image.data }
+        }));
+    }
+
+    json!({
+        "contents": [{ "role": "user", "parts": parts }],
+        "generationConfig": { "imageConfig": image_config },
+    })
+}
+
+/// Images from a Gemini `generateContent` response.
+pub fn extract_images(response: &Value) -> Vec<GeneratedImage> {
+    let mut images = Vec::new();
+    for candidate in response.get("candidates").and_then(Value::as_array).into_iter().flatten() {
+        let parts = candidate.pointer("/content/parts").and_then(Value::as_array);
+        let mut text = String::new();
+        let mut found = Vec::new();
+        for part in parts.into_iter().flatten() {
+            if let Some(t) = part.get("text").and_then(Value::as_str) {
+                if part.get("thought").and_then(Value::as_bool) != Some(true) {
+                    text.push_str(t);
+                }
+            }
+            let Some(inline) = part.get("inlineData") else { continue };
+            if let Some(data) = inline.get("data").and_then(Value::as_str) {
+                let mime_type =
+                    inline.get("mimeType").and_then(Value::as_str).unwrap_or("image/png");
+                found
+                    .push(InlineImage { mime_type: mime_type.to_string(), data: data.to_string() });
+            }
+        }
+        let text = text.trim();
+        let revised_prompt = (!text.is_empty()).then(|| text.to_string());
+        images.extend(
+            found
+                .into_iter()
+                .map(|image| GeneratedImage { image, revised_prompt: revised_prompt.clone() }),
+        );
+    }
+    images
+}
+
+/// OpenAI images response. `url` results are data URIs since nothing is hosted.
+pub fn build_images_response(images: &[GeneratedImage], response_format: Option<&str>) -> Value {
+    let data: Vec<Value> = images
+        .iter()
+        .map(|generated| {
+            let mut entry = if response_format == Some("url") {
+                json!({
+                    "url": format!(
+                        "data:{};base64,{}",
+                        generated.image.mime_type, generated.image.data
+                    )
+                })
+            } else {
+                json!({ "b64_json": generated.image.data })
+            };
+            if let Some(revised) = &generated.revised_prompt {
+                entry["revised_prompt"] = json!(revised);
+            }
+            entry
+        })
+        .collect();
+    json!({ "created": chrono::Utc::now().timestamp(), "data": data })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn png(data: &str) -> InlineImage {
+        InlineImage { mime_type: "image/png".to_string(), data: data.to_string() }
+    }
+
+    #[test]
+    fn validates_params() {
+        assert!(validate_image_params("a cat", 1, None).is_ok());
+        assert!(validate_image_params(" ", 1, None).is_err());
+        assert!(validate_image_params("a cat", 0, None).is_err());
+        assert!(validate_image_params("a cat", 11, None).is_err());
+        assert!(validate_image_params("a cat", 2, Some("png")).is_err());
+    }
+
+    #[test]
+    fn recognizes_openai_image_models() {
+        assert!(is_openai_image_model("dall-e-3"));
+        assert!(is_openai_image_model("gpt-image-1"));
+        assert!(!is_openai_image_model("gemini-3-pro-image"));
+    }
+
+    #[test]
+    fn edit_request_puts_mask_after_source_image() {
+        let body = build_image_request(
+            "add a hat",
+            None,
+            &[png("SRC")],
+            Some(&png("MASK")),
+            json!({"aspectRatio": "1:1"}),
+        );
+        let parts = body["contents"][0]["parts"].as_array().unwrap();
+        assert_eq!(parts.len(), 3);
+        assert!(parts[0]["text"].as_str().unwrap().contains("mask"));
+        assert_eq!(parts[1]["inlineData"]["data"], "SRC");
+        assert_eq!(parts[2]["inlineData"]["data"], "MASK");
+        assert_eq!(body["generationConfig"]["imageConfig"]["aspectRatio"], "1:1");
+    }
+
+    #[test]
+    fn converts_inline_images_to_openai_shape() {
+        let response = json!({"candidates": [{"content": {"parts": [
+            {"text": "thinking", "thought": true},
+            {"text": "A cat in a hat"},
+            {"inlineData": {"mimeType": "image/jpeg", "data": "AAAA"}}
+        ]}}]});
+        let images = extract_images(&response);
+        assert_eq!(images.len(), 1);
+
+        let b64 = build_images_response(&images, None);
+        assert_eq!(b64["data"][0]["b64_json"], "AAAA");
+        assert_eq!(b64["data"][0]["revised_prompt"], "A cat in a hat");
+
+        let url = build_images_response(&images, Some("url"));
+        assert_eq!(url["data"][0]["url"], "data:image/jpeg;base64,AAAA");
+    }
+}
@@ -1,11 +1,17 @@
 // OpenAI mapper module
 // Handles OpenAI ↔ Gemini protocol conversion
 
+pub mod choices;
 pub mod collector;
+pub mod embeddings;
+pub mod images;
 pub mod models;
 pub mod request;
 pub mod response;
+pub mod responses;
+pub mod speech;
 pub mod streaming;
+pub mod structured_output;
 
 pub use collector::collect_openai_stream_to_json;
 pub use models::*;
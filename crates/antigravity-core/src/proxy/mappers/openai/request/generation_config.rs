@@ -7,6 +7,7 @@
 )]
 
 use super::super::models::OpenAIRequest;
+use super::super::structured_output::apply_response_format;
 use crate::proxy::common::thinking_config::get_thinking_budget_config;
 use crate::proxy::common::thinking_constants::{
     THINKING_BUDGET, THINKING_MIN_OVERHEAD, THINKING_OVERHEAD,
@@ -102,9 +103,7 @@ pub fn build_generation_config(
     }
 
     if let Some(fmt) = &request.response_format {
-        if fmt.r#type == "json_object" {
-            gen_config["responseMimeType"] = json!("application/json");
-        }
+        apply_response_format(&mut gen_config, fmt);
     }
 
     gen_config
@@ -14,6 +14,11 @@ use generation_config::build_generation_config;
 use message_transform::{merge_consecutive_roles, transform_message, MessageTransformContext};
 use serde_json::{json, Value};
 
+#[tracing::instrument(
+    name = "transform_request",
+    skip_all,
+    fields(protocol = "openai", model = %request.model, mapped_model = %mapped_model)
+)]
 pub fn transform_openai_request(
     request: &OpenAIRequest,
     project_id: &str,
@@ -163,7 +163,10 @@ fn test_gemini_3_pro_high_json_mode_with_thinking() {
         temperature: Some(1.0),
         top_p: Some(0.0), // Client sent top_p=0.0
         stop: None,
-        response_format: Some(ResponseFormat { r#type: "json_object".to_string() }),
+        response_format: Some(ResponseFormat {
+            r#type: "json_object".to_string(),
+            json_schema: None,
+        }),
         tools: None,
         tool_choice: None,
         parallel_tool_calls: None,
@@ -0,0 +1,300 @@
+//! Responses API input items → Chat Completions messages.
+
+use super::super::models::{
+    OpenAIContent, OpenAIContentBlock, OpenAIImageUrl, OpenAIMessage, ToolCall, ToolFunction,
+};
+use super::models::{
+    InputContentPart, InputItem, InputMessage, InputMessageContent, ReasoningSummary,
+    ResponsesInput, TypedInputItem,
+};
+use serde_json::{json, Map, Value};
+use std::collections::HashMap;
+
+/// Convert Responses input into chat messages.
+///
+/// Consecutive function calls are grouped into a single assistant message and reasoning
+/// summaries are attached to the assistant turn that follows them.
+pub fn input_to_messages(input: Option<&ResponsesInput>) -> Vec<OpenAIMessage> {
+    let items = match input {
+        None => return Vec::new(),
+        Some(ResponsesInput::Text(text)) => {
+            return vec![message("user", Some(OpenAIContent::String(text.clone())))];
+        },
+        Some(ResponsesInput::Items(items)) => items,
+    };
+
+    let mut messages: Vec<OpenAIMessage> = Vec::new();
+    let mut call_names: HashMap<String, String> = HashMap::new();
+    let mut pending_reasoning: Option<String> = None;
+
+    for item in items {
+        let typed = match item {
+            InputItem::Message(msg) | InputItem::Typed(TypedInputItem::Message(msg)) => {
+                let mut converted = convert_message(msg);
+                if converted.role == "assistant" {
+                    converted.reasoning_content = pending_reasoning.take();
+                }
+                messages.push(converted);
+                continue;
+            },
+            InputItem::Typed(typed) => typed,
+        };
+
+        match typed {
+            TypedInputItem::FunctionCall { call_id, name, arguments } => {
+                call_names.insert(call_id.clone(), name.clone());
+                push_tool_call(
+                    &mut messages,
+                    &mut pending_reasoning,
+                    call_id,
+                    name.clone(),
+                    arguments.clone(),
+                );
+            },
+            TypedInputItem::LocalShellCall { call_id, id, action } => {
+                let call_id =
+                    call_id.clone().or_else(|| id.clone()).unwrap_or_else(|| "unknown".to_string());
+                call_names.insert(call_id.clone(), "shell".to_string());
+                let arguments = shell_arguments(action).to_string();
+                push_tool_call(
+                    &mut messages,
+                    &mut pending_reasoning,
+                    &call_id,
+                    "shell".to_string(),
+                    arguments,
+                );
+            },
+            TypedInputItem::FunctionCallOutput { call_id, output } => {
+                let name = call_names.get(call_id).cloned();
+                messages.push(OpenAIMessage {
+                    role: "tool".to_string(),
+                    content: Some(OpenAIContent::String(output_text(output))),
+                    reasoning_content: None,
+                    tool_calls: None,
+                    tool_call_id: Some(call_id.clone()),
+                    name,
+                });
+            },
+            TypedInputItem::Reasoning { summary } => {
+                let text = summary
+                    .iter()
+                    .map(|part| match part {
+                        ReasoningSummary::SummaryText { text } => text.as_str(),
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\n");
+                if !text.is_empty() {
+                    pending_reasoning = Some(text);
+                }
+            },
+            TypedInputItem::Message(_) => {},
+            TypedInputItem::Unknown => {
+                tracing::trace!("[Responses] Skipping unsupported input item");
+            },
+        }
+    }
+
+    messages
+}
+
+fn message(role: &str, content: Option<OpenAIContent>) -> OpenAIMessage {
+    OpenAIMessage {
+        role: role.to_string(),
+        content,
+        reasoning_content: None,
+        tool_calls: None,
+        tool_call_id: None,
+        name: None,
+    }
+}
+
+fn convert_message(msg: &InputMessage) -> OpenAIMessage {
+    // Gemini has no developer role; treat it as system
+    let role = if msg.role == "developer" { "system" } else { msg.role.as_str() };
+    let parts = match &msg.content {
+        InputMessageContent::Text(text) => {
+            return message(role, Some(OpenAIContent::String(text.clone())));
+        },
+        InputMessageContent::Parts(parts) => parts,
+    };
+
+    let mut blocks = Vec::new();
+    for part in parts {
+        match part {
+            InputContentPart::InputText { text } | InputContentPart::OutputText { text } => {
+                blocks.push(OpenAIContentBlock::Text { text: text.clone() });
+            },
+            InputContentPart::Refusal { refusal } => {
+                blocks.push(OpenAIContentBlock::Text { text: refusal.clone() });
+            },
+            InputContentPart::InputImage { image_url: Some(url), detail, .. } => {
+                blocks.push(OpenAIContentBlock::ImageUrl {
+                    image_url: OpenAIImageUrl { url: url.clone(), detail: detail.clone() },
+                });
+            },
+            InputContentPart::InputFile { file_data: Some(data), .. }
+                if data.starts_with("data:") =>
+            {
+                // Data URIs are forwarded as inline data with their own MIME type
+                blocks.push(OpenAIContentBlock::ImageUrl {
+                    image_url: OpenAIImageUrl { url: data.clone(), detail: None },
+                });
+            },
+            InputContentPart::InputImage { .. } | InputContentPart::InputFile { .. } => {
+                tracing::warn!("[Responses] Skipping file reference without inline data");
+            },
+            InputContentPart::Unknown => {},
+        }
+    }
+
+    let only_text = blocks.iter().all(|b| matches!(b, OpenAIContentBlock::Text { .. }));
+    let content = if only_text {
+        let text = blocks
+            .into_iter()
+            .filter_map(|b| match b {
+                OpenAIContentBlock::Text { text } => Some(text),
+                _ => None,
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        OpenAIContent::String(text)
+    } else {
+        OpenAIContent::Array(blocks)
+    };
+    message(role, Some(content))
+}
+
+fn push_tool_call(
+    messages: &mut Vec<OpenAIMessage>,
+    pending_reasoning: &mut Option<String>,
+    call_id: &str,
+    name: String,
+    arguments: String,
+) {
+    let call = ToolCall {
+        id: call_id.to_string(),
+        r#type: "function".to_string(),
+        function: ToolFunction { name, arguments },
+    };
+
+    // Parallel calls arrive as consecutive items but belong to one assistant turn
+    if let Some(last) = messages.last_mut() {
+        if last.role == "assistant" && pending_reasoning.is_none() {
+            if let Some(calls) = last.tool_calls.as_mut() {
+                calls.push(call);
+                return;
+            }
+            if last.content.is_some() {
+                last.tool_calls = Some(vec![call]);
+                return;
+            }
+        }
+    }
+
+    let mut msg = message("assistant", None);
+    msg.reasoning_content = pending_reasoning.take();
+    msg.tool_calls = Some(vec![call]);
+    messages.push(msg);
+}
+
+/// `local_shell_call` action → `shell` tool arguments (`command` is always an array).
+fn shell_arguments(action: &Value) -> Value {
+    let mut args = Map::new();
+    if let Some(exec) = action.get("exec") {
+        if let Some(cmd) = exec.get("command") {
+            let cmd = if cmd.is_string() { json!([cmd]) } else { cmd.clone() };
+            args.insert("command".to_string(), cmd);
+        }
+        if let Some(wd) = exec.get("working_directory").or_else(|| exec.get("workdir")) {
+            args.insert("workdir".to_string(), wd.clone());
+        }
+    }
+    Value::Object(args)
+}
+
+/// Function output as text (string, `{content}` or a list of text parts).
+fn output_text(output: &Value) -> String {
+    match output {
+        Value::String(s) => s.clone(),
+        Value::Null => String::new(),
+        Value::Array(parts) => parts
+            .iter()
+            .filter_map(|p| p.get("text").and_then(Value::as_str))
+            .collect::<Vec<_>>()
+            .join("\n"),
+        other => match other.get("content").and_then(Value::as_str) {
+            Some(content) => content.to_string(),
+            None => other.to_string(),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(input: Value) -> Vec<OpenAIMessage> {
+        let input: ResponsesInput = serde_json::from_value(input).unwrap();
+        input_to_messages(Some(&input))
+    }
+
+    #[test]
+    fn text_input_is_user_message() {
+        let messages = parse(json!("hello"));
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0].role, "user");
+        assert_eq!(messages[0].content, Some(OpenAIContent::String("hello".to_string())));
+    }
+
+    #[test]
+    fn keeps_images_and_untyped_messages() {
+        let messages = parse(json!([
+            {"role": "developer", "content": "be brief"},
+            {"type": "message", "role": "user", "content": [
+                {"type": "input_text", "text": "what is this?"},
+                {"type": "input_image", "image_url": "data:image/png;base64,AAAA"}
+            ]}
+        ]));
+        assert_eq!(messages[0].role, "system");
+        let Some(OpenAIContent::Array(blocks)) = &messages[1].content else {
+            panic!("expected content blocks");
+        };
+        assert_eq!(blocks.len(), 2);
+        assert!(matches!(&blocks[1], OpenAIContentBlock::ImageUrl { image_url }
+            if image_url.url.starts_with("data:image/png")));
+    }
+
+    #[test]
+    fn groups_function_calls_and_outputs() {
+        let messages = parse(json!([
+            {"role": "user", "content": "weather in A and B?"},
+            {"type": "reasoning", "id": "rs_1", "summary": [
+                {"type": "summary_text", "text": "need two lookups"}
+            ]},
+            {"type": "function_call", "call_id": "c1", "name": "weather", "arguments": "{\"city\":\"A\"}"},
+            {"type": "function_call", "call_id": "c2", "name": "weather", "arguments": "{\"city\":\"B\"}"},
+            {"type": "function_call_output", "call_id": "c1", "output": "sunny"},
+            {"type": "function_call_output", "call_id": "c2", "output": [{"type": "input_text", "text": "rain"}]},
+            {"type": "web_search_call", "id": "ws_1"}
+        ]));
+        assert_eq!(messages.len(), 4);
+        let assistant = &messages[1];
+        assert_eq!(assistant.reasoning_content.as_deref(), Some("need two lookups"));
+        assert_eq!(assistant.tool_calls.as_ref().map(Vec::len), Some(2));
+        assert_eq!(messages[2].tool_call_id.as_deref(), Some("c1"));
+        assert_eq!(messages[2].name.as_deref(), Some("weather"));
+        assert_eq!(messages[3].content, Some(OpenAIContent::String("rain".to_string())));
+    }
+
+    #[test]
+    fn local_shell_call_becomes_shell_tool_call() {
+        let messages = parse(json!([
+            {"type": "local_shell_call", "call_id": "s1", "action": {"exec": {"command": "ls"}}},
+            {"type": "function_call_output", "call_id": "s1", "output": "a.txt"}
+        ]));
+        let call = &messages[0].tool_calls.as_ref().unwrap()[0];
+        assert_eq!(call.function.name, "shell");
+        assert_eq!(call.function.arguments, r#"{"command":["ls"]}"#);
+        assert_eq!(messages[1].name.as_deref(), Some("shell"));
+    }
+}
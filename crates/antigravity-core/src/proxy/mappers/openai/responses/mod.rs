@@ -0,0 +1,14 @@
+//! OpenAI Responses API (`/v1/responses`) mapping.
+//!
+//! Typed input items are converted to Chat Completions messages so the request can take
+//! the regular chat path; the chat result is converted back into Responses output items.
+
+pub mod input;
+pub mod models;
+pub mod output;
+pub mod stream;
+
+pub use input::input_to_messages;
+pub use models::*;
+pub use output::{complete_from_chat, output_to_messages};
+pub use stream::create_responses_sse_stream;
@@ -0,0 +1,355 @@
+//! OpenAI Responses API data models.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// `POST /v1/responses` request.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct ResponsesRequest {
+    /// Model identifier.
+    pub model: String,
+    /// Text or typed input items.
+    #[serde(default)]
+    pub input: Option<ResponsesInput>,
+    /// System instructions (not carried over by `previous_response_id`).
+    #[serde(default)]
+    pub instructions: Option<String>,
+    /// Continue the conversation of a stored response.
+    #[serde(default)]
+    pub previous_response_id: Option<String>,
+    /// Stream Responses events.
+    #[serde(default)]
+    pub stream: bool,
+    /// Store the response for later retrieval (default true).
+    #[serde(default)]
+    pub store: Option<bool>,
+    /// Tool definitions (flat `{type, name, parameters}` format).
+    #[serde(default)]
+    pub tools: Option<Vec<Value>>,
+    /// Tool choice strategy.
+    #[serde(default)]
+    pub tool_choice: Option<Value>,
+    /// Allow parallel tool calls.
+    #[serde(default)]
+    pub parallel_tool_calls: Option<bool>,
+    /// Sampling temperature.
+    #[serde(default)]
+    pub temperature: Option<f32>,
+    /// Nucleus sampling parameter.
+    #[serde(default)]
+    pub top_p: Option<f32>,
+    /// Maximum output tokens.
+    #[serde(default)]
+    pub max_output_tokens: Option<u32>,
+    /// Text output configuration (structured outputs).
+    #[serde(default)]
+    pub text: Option<Value>,
+    /// Arbitrary client metadata, echoed back.
+    #[serde(default)]
+    pub metadata: Option<Value>,
+}
+
+/// Request input: plain text or a list of items.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+#[non_exhaustive]
+pub enum ResponsesInput {
+    /// Single user message.
+    Text(String),
+    /// Typed conversation items.
+    Items(Vec<InputItem>),
+}
+
+/// An input item; messages may omit the `type` field.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+#[non_exhaustive]
+pub enum InputItem {
+    /// Item with an explicit `type`.
+    Typed(TypedInputItem),
+    /// `{role, content}` message without a `type`.
+    Message(InputMessage),
+}
+
+/// Input items with an explicit `type`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum TypedInputItem {
+    /// Conversation message.
+    Message(InputMessage),
+    /// Function call previously emitted by the model.
+    FunctionCall {
+        /// Call identifier matched by `function_call_output`.
+        call_id: String,
+        /// Function name.
+        name: String,
+        /// JSON-encoded arguments.
+        #[serde(default)]
+        arguments: String,
+    },
+    /// Result of a function call.
+    #[serde(alias = "custom_tool_call_output")]
+    FunctionCallOutput {
+        /// Call identifier.
+        call_id: String,
+        /// Output (string or content list).
+        #[serde(default)]
+        output: Value,
+    },
+    /// Codex native shell call.
+    LocalShellCall {
+        /// Call identifier.
+        #[serde(default)]
+        call_id: Option<String>,
+        /// Item identifier (fallback call identifier).
+        #[serde(default)]
+        id: Option<String>,
+        /// `{exec: {command, working_directory}}`.
+        #[serde(default)]
+        action: Value,
+    },
+    /// Reasoning emitted by the model.
+    Reasoning {
+        /// Reasoning summary parts.
+        #[serde(default)]
+        summary: Vec<ReasoningSummary>,
+    },
+    /// Unsupported item types are skipped.
+    #[serde(other)]
+    Unknown,
+}
+
+/// Conversation message item.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct InputMessage {
+    /// Role (user, assistant, system, developer).
+    pub role: String,
+    /// Text or content parts.
+    pub content: InputMessageContent,
+}
+
+/// Message content: plain text or typed parts.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+#[non_exhaustive]
+pub enum InputMessageContent {
+    /// Plain text.
+    Text(String),
+    /// Typed content parts.
+    Parts(Vec<InputContentPart>),
+}
+
+/// Message content part.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum InputContentPart {
+    /// User text.
+    InputText {
+        /// Text content.
+        text: String,
+    },
+    /// Assistant text from an earlier turn.
+    OutputText {
+        /// Text content.
+        text: String,
+    },
+    /// Image by URL or data URI.
+    InputImage {
+        /// Image URL or data URI.
+        #[serde(default)]
+        image_url: Option<String>,
+        /// Uploaded file identifier.
+        #[serde(default)]
+        file_id: Option<String>,
+        /// Detail level.
+        #[serde(default)]
+        detail: Option<String>,
+    },
+    /// Inline file as a data URI.
+    InputFile {
+        /// Data URI with the file content.
+        #[serde(default)]
+        file_data: Option<String>,
+        /// Uploaded file identifier.
+        #[serde(default)]
+        file_id: Option<String>,
+        /// Original filename.
+        #[serde(default)]
+        filename: Option<String>,
+    },
+    /// Assistant refusal from an earlier turn.
+    Refusal {
+        /// Refusal text.
+        refusal: String,
+    },
+    /// Unsupported part types are skipped.
+    #[serde(other)]
+    Unknown,
+}
+
+/// Reasoning summary part.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum ReasoningSummary {
+    /// Summary text.
+    SummaryText {
+        /// Text content.
+        text: String,
+    },
+}
+
+/// Response object returned by `/v1/responses`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct ResponseObject {
+    /// Response identifier (`resp_...`).
+    pub id: String,
+    /// Object type ("response").
+    pub object: String,
+    /// Unix timestamp of creation.
+    pub created_at: i64,
+    /// in_progress, completed, incomplete or failed.
+    pub status: String,
+    /// Model requested by the client.
+    pub model: String,
+    /// Output items.
+    pub output: Vec<OutputItem>,
+    /// Token usage.
+    pub usage: Option<ResponseUsage>,
+    /// Response this one continues.
+    pub previous_response_id: Option<String>,
+    /// Instructions used for this response.
+    pub instructions: Option<String>,
+    /// Why the response is incomplete.
+    pub incomplete_details: Option<Value>,
+    /// Error details for failed responses.
+    pub error: Option<Value>,
+    /// Client metadata.
+    #[serde(default)]
+    pub metadata: Value,
+}
+
+impl ResponseObject {
+    /// Empty in-progress response.
+    pub fn new(id: String, model: String, created_at: i64) -> Self {
+        Self {
+            id,
+            object: "response".to_string(),
+            created_at,
+            status: "in_progress".to_string(),
+            model,
+            output: Vec::new(),
+            usage: None,
+            previous_response_id: None,
+            instructions: None,
+            incomplete_details: None,
+            error: None,
+            metadata: Value::Object(Default::default()),
+        }
+    }
+
+    /// Concatenated text of all output messages.
+    pub fn output_text(&self) -> String {
+        self.output
+            .iter()
+            .filter_map(|item| match item {
+                OutputItem::Message { content, .. } => Some(content),
+                _ => None,
+            })
+            .flatten()
+            .map(|part| match part {
+                OutputContent::OutputText { text, .. } => text.as_str(),
+            })
+            .collect()
+    }
+}
+
+/// Output item.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum OutputItem {
+    /// Assistant message.
+    Message {
+        /// Item identifier (`msg_...`).
+        id: String,
+        /// Always "assistant".
+        role: String,
+        /// in_progress or completed.
+        status: String,
+        /// Content parts.
+        content: Vec<OutputContent>,
+    },
+    /// Function call to be executed by the client.
+    FunctionCall {
+        /// Item identifier (`fc_...`).
+        id: String,
+        /// Call identifier for the `function_call_output`.
+        call_id: String,
+        /// Function name.
+        name: String,
+        /// JSON-encoded arguments.
+        arguments: String,
+        /// in_progress or completed.
+        status: String,
+    },
+    /// Model reasoning summary.
+    Reasoning {
+        /// Item identifier (`rs_...`).
+        id: String,
+        /// Summary parts.
+        summary: Vec<ReasoningSummary>,
+    },
+}
+
+/// Output message content part.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum OutputContent {
+    /// Generated text.
+    OutputText {
+        /// Text content.
+        text: String,
+        /// Citations (always empty).
+        #[serde(default)]
+        annotations: Vec<Value>,
+    },
+}
+
+/// Token usage in Responses format.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ResponseUsage {
+    /// Prompt tokens.
+    pub input_tokens: u32,
+    /// Prompt token breakdown.
+    pub input_tokens_details: InputTokensDetails,
+    /// Generated tokens.
+    pub output_tokens: u32,
+    /// Generated token breakdown.
+    pub output_tokens_details: OutputTokensDetails,
+    /// Total tokens.
+    pub total_tokens: u32,
+}
+
+/// Prompt token breakdown.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct InputTokensDetails {
+    /// Tokens served from cache.
+    pub cached_tokens: u32,
+}
+
+/// Generated token breakdown.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct OutputTokensDetails {
+    /// Tokens spent on reasoning.
+    pub reasoning_tokens: u32,
+}
@@ -0,0 +1,200 @@
+//! Chat Completions results → Responses API output items.
+
+use super::super::models::{
+    OpenAIContent, OpenAIMessage, OpenAIResponse, OpenAIUsage, ToolCall, ToolFunction,
+};
+use super::models::{
+    InputTokensDetails, OutputContent, OutputItem, OutputTokensDetails, ReasoningSummary,
+    ResponseObject, ResponseUsage,
+};
+use serde_json::json;
+
+/// Item identifier with the given prefix (`msg`, `fc`, `rs`).
+pub fn item_id(prefix: &str) -> String {
+    format!("{}_{}", prefix, uuid::Uuid::new_v4().simple())
+}
+
+/// Output items for an assistant message: reasoning, text, then function calls.
+pub fn message_to_output(message: &OpenAIMessage) -> Vec<OutputItem> {
+    let mut output = Vec::new();
+
+    if let Some(reasoning) = message.reasoning_content.as_ref().filter(|r| !r.is_empty()) {
+        output.push(OutputItem::Reasoning {
+            id: item_id("rs"),
+            summary: vec![ReasoningSummary::SummaryText { text: reasoning.clone() }],
+        });
+    }
+
+    let text = match &message.content {
+        Some(OpenAIContent::String(s)) => s.clone(),
+        Some(OpenAIContent::Array(_)) | None => String::new(),
+    };
+    if !text.is_empty() {
+        output.push(OutputItem::Message {
+            id: item_id("msg"),
+            role: "assistant".to_string(),
+            status: "completed".to_string(),
+            content: vec![OutputContent::OutputText { text, annotations: Vec::new() }],
+        });
+    }
+
+    for call in message.tool_calls.iter().flatten() {
+        output.push(OutputItem::FunctionCall {
+            id: item_id("fc"),
+            call_id: call.id.clone(),
+            name: call.function.name.clone(),
+            arguments: call.function.arguments.clone(),
+            status: "completed".to_string(),
+        });
+    }
+
+    output
+}
+
+/// Fill a response from a non-streaming chat completion.
+pub fn complete_from_chat(response: &mut ResponseObject, chat: &OpenAIResponse) {
+    let choice = chat.choices.first();
+    if let Some(choice) = choice {
+        response.output = message_to_output(&choice.message);
+    }
+    response.usage = chat.usage.as_ref().map(usage_from_chat);
+    finish(response, choice.and_then(|c| c.finish_reason.as_deref()));
+}
+
+/// Set the final status from the chat finish reason.
+pub fn finish(response: &mut ResponseObject, finish_reason: Option<&str>) {
+    if finish_reason == Some("length") {
+        response.status = "incomplete".to_string();
+        response.incomplete_details = Some(json!({ "reason": "max_output_tokens" }));
+    } else {
+        response.status = "completed".to_string();
+    }
+}
+
+pub fn usage_from_chat(usage: &OpenAIUsage) -> ResponseUsage {
+    ResponseUsage {
+        input_tokens: usage.prompt_tokens,
+        input_tokens_details: InputTokensDetails {
+            cached_tokens: usage
+                .prompt_tokens_details
+                .as_ref()
+                .and_then(|d| d.cached_tokens)
+                .unwrap_or(0),
+        },
+        output_tokens: usage.completion_tokens,
+        output_tokens_details: OutputTokensDetails {
+            reasoning_tokens: usage
+                .completion_tokens_details
+                .as_ref()
+                .and_then(|d| d.reasoning_tokens)
+                .unwrap_or(0),
+        },
+        total_tokens: usage.total_tokens,
+    }
+}
+
+/// Output items as chat messages, for replaying a stored conversation.
+pub fn output_to_messages(output: &[OutputItem]) -> Vec<OpenAIMessage> {
+    let mut message = OpenAIMessage {
+        role: "assistant".to_string(),
+        content: None,
+        reasoning_content: None,
+        tool_calls: None,
+        tool_call_id: None,
+        name: None,
+    };
+
+    for item in output {
+        match item {
+            OutputItem::Reasoning { summary, .. } => {
+                let text = summary
+                    .iter()
+                    .map(|part| match part {
+                        ReasoningSummary::SummaryText { text } => text.as_str(),
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\n");
+                message.reasoning_content = Some(text);
+            },
+            OutputItem::Message { content, .. } => {
+                let text: String = content
+                    .iter()
+                    .map(|part| match part {
+                        OutputContent::OutputText { text, .. } => text.as_str(),
+                    })
+                    .collect();
+                message.content = Some(OpenAIContent::String(text));
+            },
+            OutputItem::FunctionCall { call_id, name, arguments, .. } => {
+                message.tool_calls.get_or_insert_with(Vec::new).push(ToolCall {
+                    id: call_id.clone(),
+                    r#type: "function".to_string(),
+                    function: ToolFunction { name: name.clone(), arguments: arguments.clone() },
+                });
+            },
+        }
+    }
+
+    if message.content.is_none() && message.tool_calls.is_none() {
+        return Vec::new();
+    }
+    vec![message]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::Value;
+
+    #[test]
+    fn chat_message_round_trips_through_output_items() {
+        let chat: OpenAIResponse = serde_json::from_value(json!({
+            "id": "chatcmpl-1",
+            "object": "chat.completion",
+            "created": 1,
+            "model": "gemini-3-flash",
+            "choices": [{
+                "index": 0,
+                "message": {
+                    "role": "assistant",
+                    "content": "Let me check.",
+                    "reasoning_content": "thinking",
+                    "tool_calls": [{
+                        "id": "call_1",
+                        "type": "function",
+                        "function": {"name": "weather", "arguments": "{}"}
+                    }]
+                },
+                "finish_reason": "tool_calls"
+            }],
+            "usage": {"prompt_tokens": 10, "completion_tokens": 5, "total_tokens": 15}
+        }))
+        .unwrap();
+
+        let mut response = ResponseObject::new("resp_1".into(), "gemini-3-flash".into(), 1);
+        complete_from_chat(&mut response, &chat);
+
+        assert_eq!(response.status, "completed");
+        assert_eq!(response.output_text(), "Let me check.");
+        assert_eq!(response.usage.map(|u| u.total_tokens), Some(15));
+        let types: Vec<Value> = response
+            .output
+            .iter()
+            .map(|item| serde_json::to_value(item).unwrap()["type"].clone())
+            .collect();
+        assert_eq!(types, vec![json!("reasoning"), json!("message"), json!("function_call")]);
+
+        let replay = output_to_messages(&response.output);
+        assert_eq!(replay.len(), 1);
+        assert_eq!(replay[0].reasoning_content.as_deref(), Some("thinking"));
+        assert_eq!(replay[0].tool_calls.as_ref().unwrap()[0].id, "call_1");
+    }
+
+    #[test]
+    fn length_finish_marks_incomplete() {
+        let mut response = ResponseObject::new("resp_1".into(), "m".into(), 1);
+        finish(&mut response, Some("length"));
+        assert_eq!(response.status, "incomplete");
+        assert_eq!(response.incomplete_details, Some(json!({"reason": "max_output_tokens"})));
+    }
+}
@@ -0,0 +1,480 @@
+//! Chat Completions SSE → Responses API streaming events.
+//!
+//! The chat stream already handles Gemini specifics (thought signatures, function call
+//! de-duplication, usage), so Responses events are derived from its chunks rather than
+//! from the raw upstream stream.
+
+use super::models::{OutputContent, OutputItem, ReasoningSummary, ResponseObject};
+use super::output::{finish, item_id, usage_from_chat};
+use crate::proxy::mappers::openai::models::OpenAIUsage;
+use bytes::{Bytes, BytesMut};
+use futures::{Stream, StreamExt};
+use serde_json::{json, Value};
+use std::pin::Pin;
+
+/// Item currently receiving deltas.
+enum OpenItem {
+    Reasoning { id: String, text: String },
+    Message { id: String, text: String },
+}
+
+/// Builds Responses events from chat completion chunks.
+pub struct ResponsesEventBuilder {
+    response: ResponseObject,
+    sequence: u64,
+    open: Option<OpenItem>,
+    finish_reason: Option<String>,
+}
+
+impl ResponsesEventBuilder {
+    pub fn new(response: ResponseObject) -> Self {
+        Self { response, sequence: 0, open: None, finish_reason: None }
+    }
+
+    /// The response as built so far.
+    pub fn response(&self) -> &ResponseObject {
+        &self.response
+    }
+
+    /// `response.created` and `response.in_progress`.
+    pub fn start(&mut self) -> Vec<Value> {
+        let snapshot = json!(self.response);
+        vec![
+            self.event("response.created", json!({ "response": snapshot })),
+            self.event("response.in_progress", json!({ "response": snapshot })),
+        ]
+    }
+
+    /// Events for one `chat.completion.chunk`.
+    pub fn on_chunk(&mut self, chunk: &Value) -> Vec<Value> {
+        let mut events = Vec::new();
+
+        if let Some(error) = chunk.get("error") {
+            let message = error.get("message").and_then(Value::as_str).unwrap_or("stream error");
+            return self.fail(message);
+        }
+
+        if let Some(usage) =
+            chunk.get("usage").and_then(|u| serde_json::from_value::<OpenAIUsage>(u.clone()).ok())
+        {
+            self.response.usage = Some(usage_from_chat(&usage));
+        }
+
+        let Some(choice) = chunk
+            .get("choices")
+            .and_then(Value::as_array)
+            .and_then(|c| c.iter().find(|c| c.get("index").and_then(Value::as_u64) == Some(0)))
+        else {
+            return events;
+        };
+
+        if let Some(reason) = choice.get("finish_reason").and_then(Value::as_str) {
+            self.finish_reason = Some(reason.to_string());
+        }
+
+        let delta = choice.get("delta").cloned().unwrap_or(Value::Null);
+
+        if let Some(text) = delta.get("reasoning_content").and_then(Value::as_str) {
+            if !text.is_empty() {
+                events.extend(self.reasoning_delta(text));
+            }
+        }
+
+        if let Some(text) = delta.get("content").and_then(Value::as_str) {
+            if !text.is_empty() {
+                events.extend(self.text_delta(text));
+            }
+        }
+
+        for call in delta.get("tool_calls").and_then(Value::as_array).into_iter().flatten() {
+            events.extend(self.function_call(call));
+        }
+
+        events
+    }
+
+    /// Close open items and emit the terminal event.
+    pub fn finish(&mut self) -> Vec<Value> {
+        let mut events = self.close_open();
+        finish(&mut self.response, self.finish_reason.as_deref());
+        let event_type = if self.response.status == "incomplete" {
+            "response.incomplete"
+        } else {
+            "response.completed"
+        };
+        let snapshot = json!(self.response);
+        events.push(self.event(event_type, json!({ "response": snapshot })));
+        events
+    }
+
+    /// Mark the response failed and emit `response.failed`.
+    pub fn fail(&mut self, message: &str) -> Vec<Value> {
+        let mut events = self.close_open();
+        self.response.status = "failed".to_string();
+        self.response.error = Some(json!({ "code": "server_error", "message": message }));
+        let snapshot = json!(self.response);
+        events.push(self.event("response.failed", json!({ "response": snapshot })));
+        events
+    }
+
+    fn reasoning_delta(&mut self, text: &str) -> Vec<Value> {
+        let mut events = Vec::new();
+        if !matches!(self.open, Some(OpenItem::Reasoning { .. })) {
+            events.extend(self.close_open());
+            let id = item_id("rs");
+            let index = self.response.output.len();
+            let item = json!({ "type": "reasoning", "id": id, "summary": [] });
+            events.push(self.event(
+                "response.output_item.added",
+                json!({ "output_index": index, "item": item }),
+            ));
+            events.push(self.event(
+                "response.reasoning_summary_part.added",
+                json!({
+                    "item_id": id,
+                    "output_index": index,
+                    "summary_index": 0,
+                    "part": { "type": "summary_text", "text": "" }
+                }),
+            ));
+            self.open = Some(OpenItem::Reasoning { id, text: String::new() });
+        }
+        let index = self.response.output.len();
+        if let Some(OpenItem::Reasoning { id, text: acc }) = &mut self.open {
+            acc.push_str(text);
+            let id = id.clone();
+            events.push(self.event(
+                "response.reasoning_summary_text.delta",
+                json!({ "item_id": id, "output_index": index, "summary_index": 0, "delta": text }),
+            ));
+        }
+        events
+    }
+
+    fn text_delta(&mut self, text: &str) -> Vec<Value> {
+        let mut events = Vec::new();
+        if !matches!(self.open, Some(OpenItem::Message { .. })) {
+            events.extend(self.close_open());
+            let id = item_id("msg");
+            let index = self.response.output.len();
+            let item = json!({
+                "type": "message",
+                "id": id,
+                "role": "assistant",
+                "status": "in_progress",
+                "content": []
+            });
+            events.push(self.event(
+                "response.output_item.added",
+                json!({ "output_index": index, "item": item }),
+            ));
+            events.push(self.event(
+                "response.content_part.added",
+                json!({
+                    "item_id": id,
+                    "output_index": index,
+                    "content_index": 0,
+                    "part": { "type": "output_text", "text": "", "annotations": [] }
+                }),
+            ));
+            self.open = Some(OpenItem::Message { id, text: String::new() });
+        }
+        let index = self.response.output.len();
+        if let Some(OpenItem::Message { id, text: acc }) = &mut self.open {
+            acc.push_str(text);
+            let id = id.clone();
+            events.push(self.event(
+                "response.output_text.delta",
+                json!({ "item_id": id, "output_index": index, "content_index": 0, "delta": text }),
+            ));
+        }
+        events
+    }
+
+    fn function_call(&mut self, call: &Value) -> Vec<Value> {
+        let mut events = self.close_open();
+        let function = call.get("function").cloned().unwrap_or(Value::Null);
+        let field = |v: Option<&Value>| v.and_then(Value::as_str).unwrap_or_default().to_string();
+        let id = item_id("fc");
+        let call_id = field(call.get("id"));
+        let name = field(function.get("name"));
+        let arguments = field(function.get("arguments"));
+        let index = self.response.output.len();
+
+        let added = json!({
+            "type": "function_call",
+            "id": id,
+            "call_id": call_id,
+            "name": name,
+            "arguments": "",
+            "status": "in_progress"
+        });
+        events.push(
+            self.event(
+                "response.output_item.added",
+                json!({ "output_index": index, "item": added }),
+            ),
+        );
+        events.push(self.event(
+            "response.function_call_arguments.delta",
+            json!({ "item_id": id, "output_index": index, "delta": arguments }),
+        ));
+        events.push(self.event(
+            "response.function_call_arguments.done",
+            json!({ "item_id": id, "output_index": index, "arguments": arguments }),
+        ));
+
+        let item = OutputItem::FunctionCall {
+            id,
+            call_id,
+            name,
+            arguments,
+            status: "completed".to_string(),
+        };
+        let done = json!(item);
+        events.push(
+            self.event("response.output_item.done", json!({ "output_index": index, "item": done })),
+        );
+        self.response.output.push(item);
+        events
+    }
+
+    fn close_open(&mut self) -> Vec<Value> {
+        let Some(open) = self.open.take() else {
+            return Vec::new();
+        };
+        let index = self.response.output.len();
+        let mut events = Vec::new();
+        let item = match open {
+            OpenItem::Reasoning { id, text } => {
+                let part = json!({ "type": "summary_text", "text": text });
+                events.push(self.event(
+                    "response.reasoning_summary_text.done",
+                    json!({ "item_id": id, "output_index": index, "summary_index": 0, "text": text }),
+                ));
+                events.push(self.event(
+                    "response.reasoning_summary_part.done",
+                    json!({ "item_id": id, "output_index": index, "summary_index": 0, "part": part }),
+                ));
+                OutputItem::Reasoning { id, summary: vec![ReasoningSummary::SummaryText { text }] }
+            },
+            OpenItem::Message { id, text } => {
+                let part = json!({ "type": "output_text", "text": text, "annotations": [] });
+                events.push(self.event(
+                    "response.output_text.done",
+                    json!({ "item_id": id, "output_index": index, "content_index": 0, "text": text }),
+                ));
+                events.push(self.event(
+                    "response.content_part.done",
+                    json!({ "item_id": id, "output_index": index, "content_index": 0, "part": part }),
+                ));
+                OutputItem::Message {
+                    id,
+                    role: "assistant".to_string(),
+                    status: "completed".to_string(),
+                    content: vec![OutputContent::OutputText { text, annotations: Vec::new() }],
+                }
+            },
+        };
+        let done = json!(item);
+        events.push(
+            self.event("response.output_item.done", json!({ "output_index": index, "item": done })),
+        );
+        self.response.output.push(item);
+        events
+    }
+
+    fn event(&mut self, event_type: &str, mut payload: Value) -> Value {
+        payload["type"] = json!(event_type);
+        payload["sequence_number"] = json!(self.sequence);
+        self.sequence += 1;
+        payload
+    }
+}
+
+/// Format a Responses event as an SSE frame.
+pub fn event_frame(event: &Value) -> Bytes {
+    let event_type = event.get("type").and_then(Value::as_str).unwrap_or("message");
+    Bytes::from(format!(
+        "event: {}\ndata: {}\n\n",
+        event_type,
+        serde_json::to_string(event).unwrap_or_default()
+    ))
+}
+
+/// Convert a chat completion SSE stream into a Responses event stream.
+///
+/// `on_complete` receives the final response once the stream finished successfully.
+pub fn create_responses_sse_stream<S, E, F>(
+    mut chat_stream: S,
+    response: ResponseObject,
+    on_complete: F,
+) -> Pin<Box<dyn Stream<Item = Result<Bytes, String>> + Send>>
+where
+    S: Stream<Item = Result<Bytes, E>> + Send + Unpin + 'static,
+    E: std::fmt::Display + Send,
+    F: FnOnce(&ResponseObject) + Send + 'static,
+{
+    let stream = async_stream::stream! {
+        let mut builder = ResponsesEventBuilder::new(response);
+        let mut buffer = BytesMut::new();
+        let mut failed = false;
+
+        for event in builder.start() {
+            yield Ok(event_frame(&event));
+        }
+
+        'outer: while let Some(item) = chat_stream.next().await {
+            let bytes = match item {
+                Ok(bytes) => bytes,
+                Err(e) => {
+                    tracing::warn!("[Responses-SSE] Stream error: {}", e);
+                    for event in builder.fail(&e.to_string()) {
+                        yield Ok(event_frame(&event));
+                    }
+                    failed = true;
+                    break;
+                },
+            };
+            buffer.extend_from_slice(&bytes);
+
+            while let Some(pos) = buffer.iter().position(|&b| b == b'\n') {
+                let line_raw = buffer.split_to(pos + 1);
+                let Ok(line) = std::str::from_utf8(&line_raw) else { continue };
+                let Some(data) = line.trim().strip_prefix("data:") else { continue };
+                let data = data.trim();
+                if data == "[DONE]" {
+                    break 'outer;
+                }
+                let Ok(chunk) = serde_json::from_str::<Value>(data) else { continue };
+                for event in builder.on_chunk(&chunk) {
+                    yield Ok(event_frame(&event));
+                }
+                if builder.response().status == "failed" {
+                    failed = true;
+                    break 'outer;
+                }
+            }
+        }
+
+        if !failed {
+            for event in builder.finish() {
+                yield Ok(event_frame(&event));
+            }
+            on_complete(builder.response());
+        }
+    };
+
+    Box::pin(stream)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chunk(delta: Value, finish_reason: Option<&str>) -> Value {
+        json!({
+            "object": "chat.completion.chunk",
+            "choices": [{ "index": 0, "delta": delta, "finish_reason": finish_reason }]
+        })
+    }
+
+    fn types(events: &[Value]) -> Vec<&str> {
+        events.iter().filter_map(|e| e["type"].as_str()).collect()
+    }
+
+    #[test]
+    fn emits_item_lifecycle_for_reasoning_text_and_calls() {
+        let mut builder = ResponsesEventBuilder::new(ResponseObject::new(
+            "resp_1".into(),
+            "gemini-3-flash".into(),
+            1,
+        ));
+        let mut events = builder.start();
+        events.extend(builder.on_chunk(&chunk(json!({"reasoning_content": "hmm"}), None)));
+        events.extend(builder.on_chunk(&chunk(json!({"content": "Hel"}), None)));
+        events.extend(builder.on_chunk(&chunk(json!({"content": "lo"}), None)));
+        events.extend(builder.on_chunk(&chunk(
+            json!({"tool_calls": [{"index": 0, "id": "call_1", "type": "function",
+                "function": {"name": "f", "arguments": "{}"}}]}),
+            Some("tool_calls"),
+        )));
+        events.extend(builder.on_chunk(&json!({
+            "choices": [],
+            "usage": {"prompt_tokens": 3, "completion_tokens": 2, "total_tokens": 5}
+        })));
+        events.extend(builder.finish());
+
+        assert_eq!(
+            types(&events),
+            vec![
+                "response.created",
+                "response.in_progress",
+                "response.output_item.added",
+                "response.reasoning_summary_part.added",
+                "response.reasoning_summary_text.delta",
+                "response.reasoning_summary_text.done",
+                "response.reasoning_summary_part.done",
+                "response.output_item.done",
+                "response.output_item.added",
+                "response.content_part.added",
+                "response.output_text.delta",
+                "response.output_text.delta",
+                "response.output_text.done",
+                "response.content_part.done",
+                "response.output_item.done",
+                "response.output_item.added",
+                "response.function_call_arguments.delta",
+                "response.function_call_arguments.done",
+                "response.output_item.done",
+                "response.completed",
+            ]
+        );
+        let sequence: Vec<u64> =
+            events.iter().filter_map(|e| e["sequence_number"].as_u64()).collect();
+        assert_eq!(sequence, (0..events.len() as u64).collect::<Vec<_>>());
+
+        let done = builder.response();
+        assert_eq!(done.status, "completed");
+        assert_eq!(done.output.len(), 3);
+        assert_eq!(done.output_text(), "Hello");
+        assert_eq!(done.usage.map(|u| u.total_tokens), Some(5));
+    }
+
+    #[tokio::test]
+    async fn stream_stores_completed_response() {
+        let chat = [
+            "data: {\"choices\":[{\"index\":0,\"delta\":{\"content\":\"hi\"},\"finish_reason\":null}]}\n\n",
+            "data: {\"choices\":[{\"index\":0,\"delta\":{},\"finish_reason\":\"stop\"}]}\n\ndata: [DONE]\n\n",
+        ];
+        let input = futures::stream::iter(
+            chat.into_iter().map(|s| Ok::<Bytes, String>(Bytes::from_static(s.as_bytes()))),
+        );
+        let stored = std::sync::Arc::new(std::sync::Mutex::new(None));
+        let sink = stored.clone();
+        let stream = create_responses_sse_stream(
+            input,
+            ResponseObject::new("resp_1".into(), "m".into(), 1),
+            move |r| *sink.lock().unwrap() = Some(r.output_text()),
+        );
+        let frames: Vec<Bytes> = stream.map(|r| r.unwrap()).collect().await;
+        let body =
+            frames.iter().map(|b| String::from_utf8_lossy(b).into_owned()).collect::<String>();
+
+        assert!(body.contains("event: response.output_text.delta\ndata: "));
+        assert!(body.trim_end().ends_with('}'));
+        assert!(body.contains("\"type\":\"response.completed\""));
+        assert_eq!(stored.lock().unwrap().as_deref(), Some("hi"));
+    }
+
+    #[test]
+    fn error_chunk_fails_response() {
+        let mut builder =
+            ResponsesEventBuilder::new(ResponseObject::new("r".into(), "m".into(), 1));
+        builder.on_chunk(&chunk(json!({"content": "partial"}), None));
+        let events = builder.on_chunk(&json!({"choices": [], "error": {"message": "boom"}}));
+        assert_eq!(types(&events).last(), Some(&"response.failed"));
+        assert_eq!(builder.response().status, "failed");
+        assert_eq!(builder.response().output_text(), "partial");
+    }
+}
@@ -0,0 +1,338 @@
+//! OpenAI `/v1/audio/speech` ↔ Gemini TTS (`responseModalities: ["AUDIO"]`) conversion.
+//!
+//! Gemini returns raw 16-bit little-endian mono PCM (`audio/L16;codec=pcm;rate=24000`),
+//! which is also what OpenAI's `pcm` format is. `wav` wraps it in a RIFF header.
+//! There are no encoders for the compressed formats, so those are rejected.
+
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+/// OpenAI's limit on `input`.
+pub const MAX_INPUT_CHARS: usize = 4096;
+
+/// Gemini TTS output rate, used when the upstream mime type does not name one.
+pub const DEFAULT_SAMPLE_RATE: u32 = 24_000;
+
+/// Gemini prebuilt voices; also accepted directly as `voice`.
+const GEMINI_VOICES: &[&str] = &[
+    "Achernar",
+    "Achird",
+    "Algenib",
+    "Algieba",
+    "Alnilam",
+    "Aoede",
+    "Autonoe",
+    "Callirrhoe",
+    "Charon",
+    "Despina",
+    "Enceladus",
+    "Erinome",
+    "Fenrir",
+    "Gacrux",
+    "Iapetus",
+    "Kore",
+    "Laomedeia",
+    "Leda",
+    "Orus",
+    "Puck",
+    "Pulcherrima",
+    "Rasalgethi",
+    "Sadachbia",
+    "Sadaltager",
+    "Schedar",
+    "Sulafat",
+    "Umbriel",
+    "Vindemiatrix",
+    "Zephyr",
+    "Zubenelgenubi",
+];
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct SpeechRequest {
+    pub model: String,
+    pub input: String,
+    pub voice: String,
+    #[serde(default)]
+    pub instructions: Option<String>,
+    #[serde(default)]
+    pub response_format: Option<String>,
+    #[serde(default)]
+    pub speed: Option<f64>,
+    #[serde(default)]
+    pub stream_format: Option<String>,
+}
+
+/// Container of the returned audio.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpeechFormat {
+    Wav,
+    Pcm,
+}
+
+impl SpeechFormat {
+    pub fn content_type(self) -> &'static str {
+        match self {
+            Self::Wav => "audio/wav",
+            Self::Pcm => "audio/pcm",
+        }
+    }
+}
+
+/// How the audio is delivered. Without `stream_format` the whole file is sent at once.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StreamFormat {
+    Buffered,
+    /// Audio bytes as they are generated (chunked transfer encoding)
+    Audio,
+    /// `speech.audio.delta` / `speech.audio.done` server-sent events
+    Sse,
+}
+
+impl SpeechRequest {
+    /// Reject requests the upstream cannot serve, and resolve the output options.
+    pub fn validate(&self) -> Result<(SpeechFormat, StreamFormat), String> {
+        if self.input.trim().is_empty() {
+            return Err("'input' must not be empty".to_string());
+        }
+        if self.input.chars().count() > MAX_INPUT_CHARS {
+            return Err(format!("'input' is longer than {} characters", MAX_INPUT_CHARS));
+        }
+        if let Some(speed) = self.speed {
+            if !(0.25..=4.0).contains(&speed) {
+                return Err("'speed' must be between 0.25 and 4.0".to_string());
+            }
+        }
+        map_voice(&self.voice)?;
+        let format = match self.response_format.as_deref() {
+            None | Some("wav") => SpeechFormat::Wav,
+            Some("pcm") => SpeechFormat::Pcm,
+            Some(other @ ("mp3" | "opus" | "aac" | "flac")) => {
+                return Err(format!(
+                    "response_format '{}' is not supported, use 'wav' or 'pcm'",
+                    other
+                ))
+            },
+            Some(other) => return Err(format!("Unknown response_format: {}", other)),
+        };
+        let stream = match self.stream_format.as_deref() {
+            None => StreamFormat::Buffered,
+            Some("audio") => StreamFormat::Audio,
+            Some("sse") => StreamFormat::Sse,
+            Some(other) => return Err(format!("Unknown stream_format: {}", other)),
+        };
+        Ok((format, stream))
+    }
+}
+
+/// Gemini voice for an OpenAI voice name or a Gemini prebuilt voice (any case).
+pub fn map_voice(voice: &str) -> Result<&'static str, String> {
+    let mapped = match voice.to_ascii_lowercase().as_str() {
+        "alloy" => "Kore",
+        "ash" => "Charon",
+        "ballad" => "Algieba",
+        "coral" => "Aoede",
+        "echo" => "Puck",
+        "fable" => "Fenrir",
+        "nova" => "Leda",
+        "onyx" => "Orus",
+        "sage" => "Despina",
+        "shimmer" => "Callirrhoe",
+        "verse" => "Enceladus",
+        other => GEMINI_VOICES
+            .iter()
+            .find(|v| v.eq_ignore_ascii_case(other))
+            .copied()
+            .ok_or_else(|| format!("Unknown voice: {}", voice))?,
+    };
+    Ok(mapped)
+}
+
+/// Gemini `generateContent` body for an OpenAI speech request.
+///
+/// Gemini TTS has no rate control, so `speed` is passed as a delivery direction
+/// alongside `instructions`.
+pub fn build_speech_request(request: &SpeechRequest) -> Result<Value, String> {
+    let voice = map_voice(&request.voice)?;
+    let mut directions: Vec<String> = Vec::new();
+    if let Some(instructions) = request.instructions.as_deref().map(str::trim) {
+        if !instructions.is_empty() {
+            directions.push(instructions.to_string());
+        }
+    }
+    if let Some(speed) = request.speed.filter(|s| (s - 1.0).abs() > 0.05) {
+        directions.push(format!("Speak at about {:.2}x the normal speaking rate.", speed));
+    }
+    let text = if directions.is_empty() {
+        request.input.clone()
+    } else {
+        format!("{}\nRead the following text aloud:\n{}", directions.join("\n"), request.input)
+    };
+
+    Ok(json!({
+        "contents": [{ "role": "user", "parts": [{ "text": text }] }],
+        "generationConfig": {
+            "responseModalities": ["AUDIO"],
+            "speechConfig": {
+                "voiceConfig": { "prebuiltVoiceConfig": { "voiceName": voice } }
+            }
+        }
+    }))
+}
+
+/// PCM audio in one (unwrapped) Gemini response or stream chunk, with its sample rate.
+pub fn extract_audio(response: &Value) -> Result<Option<(Vec<u8>, u32)>, String> {
+    let Some(parts) = response
+        .get("candidates")
+        .and_then(|c| c.get(0))
+        .and_then(|c| c.get("content"))
+        .and_then(|c| c.get("parts"))
+        .and_then(Value::as_array)
+    else {
+        return Ok(None);
+    };
+
+    let mut pcm = Vec::new();
+    let mut rate = None;
+    for inline in parts.iter().filter_map(|p| p.get("inlineData")) {
+        let mime = inline.get("mimeType").and_then(Value::as_str).unwrap_or_default();
+        rate = rate.or_else(|| sample_rate(mime));
+        let data = inline.get("data").and_then(Value::as_str).unwrap_or_default();
+        pcm.extend(STANDARD.decode(data).map_err(|e| format!("Invalid audio data: {}", e))?);
+    }
+    if pcm.is_empty() {
+        return Ok(None);
+    }
+    Ok(Some((pcm, rate.unwrap_or(DEFAULT_SAMPLE_RATE))))
+}
+
+/// `rate=` parameter of an `audio/L16` mime type.
+fn sample_rate(mime: &str) -> Option<u32> {
+    mime.split(';').find_map(|p| p.trim().strip_prefix("rate=")).and_then(|r| r.parse().ok())
+}
+
+/// RIFF header for 16-bit mono PCM; `data_len` is `None` when streaming.
+pub fn wav_header(sample_rate: u32, data_len: Option<u32>) -> Vec<u8> {
+    // Streaming players read to EOF when the sizes are left at their maximum
+    let data_len = data_len.unwrap_or(u32::MAX - 36);
+    let mut header = Vec::with_capacity(44);
+    header.extend_from_slice(b"RIFF");
+    header.extend_from_slice(&(36 + data_len).to_le_bytes());
+    header.extend_from_slice(b"WAVEfmt ");
+    header.extend_from_slice(&16u32.to_le_bytes());
+    header.extend_from_slice(&1u16.to_le_bytes()); // PCM
+    header.extend_from_slice(&1u16.to_le_bytes()); // mono
+    header.extend_from_slice(&sample_rate.to_le_bytes());
+    header.extend_from_slice(&(sample_rate * 2).to_le_bytes()); // byte rate
+    header.extend_from_slice(&2u16.to_le_bytes()); // block align
+    header.extend_from_slice(&16u16.to_le_bytes()); // bits per sample
+    header.extend_from_slice(b"data");
+    header.extend_from_slice(&data_len.to_le_bytes());
+    header
+}
+
+/// Audio file in `format` from the complete PCM.
+pub fn encode_audio(format: SpeechFormat, pcm: Vec<u8>, sample_rate: u32) -> Vec<u8> {
+    match format {
+        SpeechFormat::Pcm => pcm,
+        SpeechFormat::Wav => {
+            let mut wav = wav_header(sample_rate, Some(pcm.len() as u32));
+            wav.extend(pcm);
+            wav
+        },
+    }
+}
+
+/// `speech.audio.delta` event carrying one chunk of audio.
+pub fn delta_event(audio: &[u8]) -> String {
+    let event = json!({ "type": "speech.audio.delta", "audio": STANDARD.encode(audio) });
+    format!("data: {}\n\n", event)
+}
+
+/// Final `speech.audio.done` event, with usage when the upstream reported it.
+pub fn done_event(usage: Option<&Value>) -> String {
+    let count = |key: &str| usage.and_then(|u| u.get(key)).and_then(Value::as_u64).unwrap_or(0);
+    let (input, output) = (count("promptTokenCount"), count("candidatesTokenCount"));
+    let event = json!({
+        "type": "speech.audio.done",
+        "usage": {
+            "input_tokens": input,
+            "output_tokens": output,
+            "total_tokens": input + output,
+        }
+    });
+    format!("data: {}\n\n", event)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(body: Value) -> SpeechRequest {
+        serde_json::from_value(body).unwrap()
+    }
+
+    #[test]
+    fn validates_formats_voices_and_speed() {
+        let ok = request(json!({ "model": "tts-1", "input": "Hi", "voice": "alloy" }));
+        assert_eq!(ok.validate(), Ok((SpeechFormat::Wav, StreamFormat::Buffered)));
+
+        let pcm = request(json!({
+            "model": "tts-1", "input": "Hi", "voice": "KORE",
+            "response_format": "pcm", "stream_format": "sse"
+        }));
+        assert_eq!(pcm.validate(), Ok((SpeechFormat::Pcm, StreamFormat::Sse)));
+
+        for bad in [
+            json!({ "model": "tts-1", "input": "Hi", "voice": "alloy", "response_format": "mp3" }),
+            json!({ "model": "tts-1", "input": "Hi", "voice": "nobody" }),
+            json!({ "model": "tts-1", "input": "Hi", "voice": "alloy", "speed": 5.0 }),
+            json!({ "model": "tts-1", "input": " ", "voice": "alloy" }),
+        ] {
+            assert!(request(bad).validate().is_err());
+        }
+    }
+
+    #[test]
+    fn builds_gemini_request_with_directions() {
+        let body = build_speech_request(&request(json!({
+            "model": "gpt-4o-mini-tts", "input": "Hello there", "voice": "Echo",
+            "instructions": "Cheerful.", "speed": 1.5
+        })))
+        .unwrap();
+        assert_eq!(body["generationConfig"]["responseModalities"][0], "AUDIO");
+        assert_eq!(
+            body["generationConfig"]["speechConfig"]["voiceConfig"]["prebuiltVoiceConfig"]
+                ["voiceName"],
+            "Puck"
+        );
+        let text = body["contents"][0]["parts"][0]["text"].as_str().unwrap();
+        assert!(text.starts_with("Cheerful.\nSpeak at about 1.50x"), "{text}");
+        assert!(text.ends_with("\nHello there"));
+
+        let plain = build_speech_request(&request(json!({
+            "model": "tts-1", "input": "Hello there", "voice": "alloy"
+        })))
+        .unwrap();
+        assert_eq!(plain["contents"][0]["parts"][0]["text"], "Hello there");
+    }
+
+    #[test]
+    fn extracts_pcm_and_wraps_wav() {
+        let response = json!({
+            "candidates": [{ "content": { "parts": [{ "inlineData": {
+                "mimeType": "audio/L16;codec=pcm;rate=16000",
+                "data": STANDARD.encode([1u8, 0, 2, 0])
+            }}]}}]
+        });
+        let (pcm, rate) = extract_audio(&response).unwrap().unwrap();
+        assert_eq!((pcm.as_slice(), rate), ([1u8, 0, 2, 0].as_slice(), 16_000));
+        assert!(extract_audio(&json!({ "candidates": [] })).unwrap().is_none());
+
+        let wav = encode_audio(SpeechFormat::Wav, pcm, rate);
+        assert_eq!(&wav[..4], b"RIFF");
+        assert_eq!(u32::from_le_bytes(wav[24..28].try_into().unwrap()), 16_000);
+        assert_eq!(u32::from_le_bytes(wav[40..44].try_into().unwrap()), 4);
+        assert_eq!(wav.len(), 48);
+    }
+}
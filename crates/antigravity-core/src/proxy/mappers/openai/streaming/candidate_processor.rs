@@ -10,6 +10,7 @@
 
 use bytes::Bytes;
 use serde_json::{json, Value};
+use std::collections::{HashMap, HashSet};
 
 use super::stream_formatters::{
     content_chunk, format_grounding_metadata, generate_call_id, map_finish_reason, reasoning_chunk,
@@ -22,8 +23,9 @@ pub(super) struct CandidateContext<'a> {
     pub created_ts: i64,
     pub model: &'a str,
     pub session_id: &'a Option<String>,
-    pub accumulated_thinking: &'a mut String,
-    pub emitted_tool_calls: &'a mut std::collections::HashSet<String>,
+    /// Thinking text so far, per candidate index, for caching its signature
+    pub accumulated_thinking: &'a mut HashMap<usize, String>,
+    pub emitted_tool_calls: &'a mut HashSet<String>,
 }
 
 pub(super) fn process_candidate(
@@ -84,11 +86,12 @@ fn process_part(
         if is_thought_part {
             thought_out.push_str(text);
             const MAX_THINKING_SIZE: usize = 10 * 1024 * 1024; // 10MB
-            if ctx.accumulated_thinking.len() < MAX_THINKING_SIZE {
-                let remaining = MAX_THINKING_SIZE.saturating_sub(ctx.accumulated_thinking.len());
+            let thinking = ctx.accumulated_thinking.entry(idx).or_default();
+            if thinking.len() < MAX_THINKING_SIZE {
+                let remaining = MAX_THINKING_SIZE.saturating_sub(thinking.len());
                 let safe_len = utf8_safe_prefix_len(text, remaining);
                 if safe_len > 0 {
-                    ctx.accumulated_thinking.push_str(&text[..safe_len]);
+                    thinking.push_str(&text[..safe_len]);
                 }
             }
         } else {
@@ -99,7 +102,7 @@ fn process_part(
     if let Some(sig) =
         part.get("thoughtSignature").or(part.get("thought_signature")).and_then(|s| s.as_str())
     {
-        cache_signature(sig, ctx);
+        cache_signature(sig, ctx, idx);
     }
 
     if let Some(img) = part.get("inlineData") {
@@ -113,13 +116,13 @@ fn process_part(
     }
 }
 
-fn cache_signature(sig: &str, ctx: &mut CandidateContext<'_>) {
-    if ctx.accumulated_thinking.is_empty() {
+fn cache_signature(sig: &str, ctx: &CandidateContext<'_>, idx: usize) {
+    let Some(thinking) = ctx.accumulated_thinking.get(&idx).filter(|t| !t.is_empty()) else {
         return;
-    }
+    };
     let model_family = antigravity_types::ModelFamily::from_model_name(ctx.model);
     SignatureCache::global().cache_content_signature(
-        ctx.accumulated_thinking,
+        thinking,
         sig.to_string(),
         model_family.as_str().to_string(),
     );
@@ -134,8 +137,9 @@ fn cache_signature(sig: &str, ctx: &mut CandidateContext<'_>) {
         );
     }
     tracing::debug!(
-        "[OpenAI-SSE] Cached content signature (thinking_len={}, sig_len={})",
-        ctx.accumulated_thinking.len(),
+        "[OpenAI-SSE] Cached content signature (candidate={}, thinking_len={}, sig_len={})",
+        idx,
+        thinking.len(),
         sig.len()
     );
 }
@@ -171,7 +175,8 @@ fn emit_function_call(
     ctx: &mut CandidateContext<'_>,
     idx: usize,
 ) {
-    let call_key = serde_json::to_string(func_call).unwrap_or_default();
+    // Keyed per candidate so identical calls from different choices are all emitted
+    let call_key = format!("{}:{}", idx, serde_json::to_string(func_call).unwrap_or_default());
     if !ctx.emitted_tool_calls.insert(call_key) {
         return;
     }
@@ -280,6 +285,7 @@ fn chunk_text(content: &str, max_chunk_size: usize) -> Vec<&str> {
 mod tests {
     use super::{chunk_text, process_candidate, utf8_safe_prefix_len, CandidateContext};
     use serde_json::json;
+    use std::collections::{HashMap, HashSet};
 
     #[test]
     fn utf8_safe_prefix_len_respects_boundary() {
@@ -307,8 +313,8 @@ mod tests {
                 ]
             }
         });
-        let mut accumulated_thinking = String::new();
-        let mut emitted_tool_calls = std::collections::HashSet::new();
+        let mut accumulated_thinking = HashMap::new();
+        let mut emitted_tool_calls = HashSet::new();
         let mut ctx = CandidateContext {
             stream_id: "test",
             created_ts: 0,
@@ -328,4 +334,53 @@ mod tests {
         assert!(hello_pos < image_pos);
         assert!(image_pos < world_pos);
     }
+
+    #[test]
+    fn identical_tool_calls_are_emitted_per_candidate() {
+        let candidate = json!({
+            "content": {"parts": [{"functionCall": {"name": "lookup", "args": {"q": "x"}}}]}
+        });
+        let mut accumulated_thinking = HashMap::new();
+        let mut emitted_tool_calls = HashSet::new();
+        let mut ctx = CandidateContext {
+            stream_id: "test",
+            created_ts: 0,
+            model: "gemini-3-flash",
+            session_id: &None,
+            accumulated_thinking: &mut accumulated_thinking,
+            emitted_tool_calls: &mut emitted_tool_calls,
+        };
+
+        assert!(!process_candidate(&candidate, 0, &mut ctx).is_empty());
+        assert!(!process_candidate(&candidate, 1, &mut ctx).is_empty());
+        assert!(process_candidate(&candidate, 1, &mut ctx).is_empty());
+    }
+
+    #[test]
+    fn thinking_signature_is_cached_for_its_own_candidate() {
+        let thought = |text: &str| json!({"content": {"parts": [{"text": text, "thought": true}]}});
+        let first = "candidate zero reasons about the interleaved stream";
+        let second = "candidate one reasons about something else entirely";
+        let sig = "sig-".repeat(40);
+        let mut accumulated_thinking = HashMap::new();
+        let mut emitted_tool_calls = HashSet::new();
+        let mut ctx = CandidateContext {
+            stream_id: "test",
+            created_ts: 0,
+            model: "gemini-3-pro",
+            session_id: &None,
+            accumulated_thinking: &mut accumulated_thinking,
+            emitted_tool_calls: &mut emitted_tool_calls,
+        };
+
+        process_candidate(&thought(first), 0, &mut ctx);
+        process_candidate(&thought(second), 1, &mut ctx);
+        let signed = json!({"content": {"parts": [{"text": "", "thoughtSignature": sig}]}});
+        process_candidate(&signed, 1, &mut ctx);
+
+        let cache = crate::proxy::SignatureCache::global();
+        assert_eq!(cache.get_content_signature(second).map(|(s, _)| s), Some(sig));
+        assert!(cache.get_content_signature(&format!("{first}{second}")).is_none());
+        assert!(cache.get_content_signature(first).is_none());
+    }
 }
@@ -37,7 +37,7 @@ pub fn create_openai_sse_stream(
     let stream = async_stream::stream! {
         let mut emitted_tool_calls = std::collections::HashSet::new();
         let mut final_usage: Option<OpenAIUsage> = None;
-        let mut accumulated_thinking = String::new();
+        let mut accumulated_thinking = std::collections::HashMap::new();
         let mut done_emitted = false;
         while let Some(item) = gemini_stream.next().await {
             match item {
@@ -78,7 +78,9 @@ pub fn create_openai_sse_stream(
                                     }
 
                                     if let Some(candidates) = actual_data.get("candidates").and_then(|c| c.as_array()) {
-                                        for (idx, candidate) in candidates.iter().enumerate() {
+                                        for (pos, candidate) in candidates.iter().enumerate() {
+                                            // With candidateCount > 1 a chunk may carry any subset of candidates
+                                            let idx = candidate.get("index").and_then(|i| i.as_u64()).map_or(pos, |i| i as usize);
                                             let mut ctx = CandidateContext {
                                                 stream_id: &stream_id,
                                                 created_ts,
@@ -0,0 +1,283 @@
+//! OpenAI structured outputs (`response_format`).
+//!
+//! `json_object` and `json_schema` formats are mapped to Gemini's `responseMimeType` /
+//! `responseSchema`. When a `json_schema` format is marked `strict`, the generated text is
+//! validated against the original (uncleaned) schema before it is returned to the client.
+
+use super::models::{JsonSchemaFormat, ResponseFormat};
+use crate::proxy::common::json_schema::clean_json_schema;
+use serde_json::{json, Value};
+
+/// Check that a `response_format` is well formed before anything is sent upstream.
+pub fn validate_response_format(format: &ResponseFormat) -> Result<(), String> {
+    match format.r#type.as_str() {
+        "text" | "json_object" => Ok(()),
+        "json_schema" => {
+            let spec = format.json_schema.as_ref().ok_or_else(|| {
+                "response_format.json_schema is required when type is 'json_schema'".to_string()
+            })?;
+            match &spec.schema {
+                Some(Value::Object(_)) => Ok(()),
+                Some(_) => Err(format!(
+                    "response_format.json_schema.schema for '{}' must be a JSON object",
+                    spec.name
+                )),
+                None => Err(format!(
+                    "response_format.json_schema.schema is required for '{}'",
+                    spec.name
+                )),
+            }
+        },
+        other => Err(format!(
+            "Unsupported response_format type '{}': expected 'text', 'json_object' or 'json_schema'",
+            other
+        )),
+    }
+}
+
+/// Set `responseMimeType` / `responseSchema` on a Gemini `generationConfig`.
+pub fn apply_response_format(gen_config: &mut Value, format: &ResponseFormat) {
+    match format.r#type.as_str() {
+        "json_object" => {
+            gen_config["responseMimeType"] = json!("application/json");
+        },
+        "json_schema" => {
+            gen_config["responseMimeType"] = json!("application/json");
+            if let Some(schema) = format.json_schema.as_ref().and_then(|s| s.schema.as_ref()) {
+                let mut cleaned = schema.clone();
+                clean_json_schema(&mut cleaned);
+                gen_config["responseSchema"] = cleaned;
+            }
+        },
+        _ => {},
+    }
+}
+
+/// The schema to enforce on the response, if the client asked for strict validation.
+pub fn strict_schema(format: &ResponseFormat) -> Option<&JsonSchemaFormat> {
+    if format.r#type != "json_schema" {
+        return None;
+    }
+    format.json_schema.as_ref().filter(|s| s.strict == Some(true) && s.schema.is_some())
+}
+
+/// Validate generated text against a strict schema.
+///
+/// The error names the schema and the JSON path of the first mismatch.
+pub fn validate_output(text: &str, spec: &JsonSchemaFormat) -> Result<(), String> {
+    let Some(schema) = &spec.schema else {
+        return Ok(());
+    };
+    let value: Value = serde_json::from_str(text.trim()).map_err(|e| {
+        format!("Response does not match schema '{}': output is not valid JSON ({})", spec.name, e)
+    })?;
+    validate_value(&value, schema, schema, "$")
+        .map_err(|e| format!("Response does not match schema '{}': {}", spec.name, e))
+}
+
+fn validate_value(value: &Value, schema: &Value, root: &Value, path: &str) -> Result<(), String> {
+    let Some(obj) = schema.as_object() else {
+        // `true` / `{}` accept anything
+        return Ok(());
+    };
+
+    if let Some(reference) = obj.get("$ref").and_then(Value::as_str) {
+        let target = resolve_ref(root, reference)
+            .ok_or_else(|| format!("{}: unresolvable $ref '{}'", path, reference))?;
+        return validate_value(value, target, root, path);
+    }
+
+    if let Some(types) = obj.get("type") {
+        let allowed: Vec<&str> = match types {
+            Value::String(t) => vec![t.as_str()],
+            Value::Array(list) => list.iter().filter_map(Value::as_str).collect(),
+            _ => Vec::new(),
+        };
+        if !allowed.is_empty() && !allowed.iter().any(|t| matches_type(value, t)) {
+            return Err(format!(
+                "{}: expected {}, got {}",
+                path,
+                allowed.join(" or "),
+                type_name(value)
+            ));
+        }
+    }
+
+    if let Some(expected) = obj.get("const") {
+        if value != expected {
+            return Err(format!("{}: expected constant {}", path, expected));
+        }
+    }
+
+    if let Some(options) = obj.get("enum").and_then(Value::as_array) {
+        if !options.contains(value) {
+            return Err(format!(
+                "{}: {} is not one of {}",
+                path,
+                value,
+                Value::Array(options.clone())
+            ));
+        }
+    }
+
+    for keyword in ["anyOf", "oneOf"] {
+        if let Some(variants) = obj.get(keyword).and_then(Value::as_array) {
+            if !variants.iter().any(|v| validate_value(value, v, root, path).is_ok()) {
+                return Err(format!("{}: does not match any allowed variant", path));
+            }
+        }
+    }
+
+    if let Some(all) = obj.get("allOf").and_then(Value::as_array) {
+        for sub in all {
+            validate_value(value, sub, root, path)?;
+        }
+    }
+
+    if let Value::Object(map) = value {
+        let properties = obj.get("properties").and_then(Value::as_object);
+        if let Some(required) = obj.get("required").and_then(Value::as_array) {
+            for name in required.iter().filter_map(Value::as_str) {
+                if !map.contains_key(name) {
+                    return Err(format!("{}: missing required property '{}'", path, name));
+                }
+            }
+        }
+        for (key, item) in map {
+            let child = format!("{}.{}", path, key);
+            match properties.and_then(|p| p.get(key)) {
+                Some(sub) => validate_value(item, sub, root, &child)?,
+                None => match obj.get("additionalProperties") {
+                    Some(Value::Bool(false)) => {
+                        return Err(format!("{}: unexpected property '{}'", path, key));
+                    },
+                    Some(sub @ Value::Object(_)) => validate_value(item, sub, root, &child)?,
+                    _ => {},
+                },
+            }
+        }
+    }
+
+    if let Value::Array(list) = value {
+        if let Some(items) = obj.get("items") {
+            for (i, item) in list.iter().enumerate() {
+                validate_value(item, items, root, &format!("{}[{}]", path, i))?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Resolve a local `#/...` JSON pointer reference.
+fn resolve_ref<'a>(root: &'a Value, reference: &str) -> Option<&'a Value> {
+    let pointer = reference.strip_prefix('#')?;
+    root.pointer(pointer)
+}
+
+fn matches_type(value: &Value, ty: &str) -> bool {
+    match ty {
+        "object" => value.is_object(),
+        "array" => value.is_array(),
+        "string" => value.is_string(),
+        "boolean" => value.is_boolean(),
+        "null" => value.is_null(),
+        "number" => value.is_number(),
+        "integer" => {
+            value.is_i64() || value.is_u64() || value.as_f64().is_some_and(|f| f.fract() == 0.0)
+        },
+        _ => true,
+    }
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn schema_format(schema: Value, strict: bool) -> ResponseFormat {
+        ResponseFormat {
+            r#type: "json_schema".to_string(),
+            json_schema: Some(JsonSchemaFormat {
+                name: "person".to_string(),
+                description: None,
+                schema: Some(schema),
+                strict: Some(strict),
+            }),
+        }
+    }
+
+    fn person_schema() -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "name": {"type": "string"},
+                "age": {"type": "integer"},
+                "tags": {"type": "array", "items": {"$ref": "#/$defs/tag"}}
+            },
+            "required": ["name", "age"],
+            "additionalProperties": false,
+            "$defs": {"tag": {"type": "string", "enum": ["a", "b"]}}
+        })
+    }
+
+    #[test]
+    fn json_schema_requires_schema_object() {
+        let mut format = schema_format(json!({}), true);
+        assert!(validate_response_format(&format).is_ok());
+        format.json_schema.as_mut().unwrap().schema = None;
+        assert!(validate_response_format(&format).unwrap_err().contains("person"));
+        format.json_schema = None;
+        assert!(validate_response_format(&format).is_err());
+        format.r#type = "xml".to_string();
+        assert!(validate_response_format(&format).unwrap_err().contains("xml"));
+    }
+
+    #[test]
+    fn applies_cleaned_response_schema() {
+        let mut gen_config = json!({});
+        apply_response_format(&mut gen_config, &schema_format(person_schema(), false));
+        assert_eq!(gen_config["responseMimeType"], "application/json");
+        let schema = &gen_config["responseSchema"];
+        assert!(schema.get("$defs").is_none());
+        assert!(schema.get("additionalProperties").is_none());
+        assert_eq!(schema["properties"]["tags"]["items"]["type"], "string");
+    }
+
+    #[test]
+    fn strict_only_when_requested() {
+        assert!(strict_schema(&schema_format(person_schema(), true)).is_some());
+        assert!(strict_schema(&schema_format(person_schema(), false)).is_none());
+    }
+
+    #[test]
+    fn validates_output_with_paths() {
+        let format = schema_format(person_schema(), true);
+        let spec = strict_schema(&format).unwrap();
+        assert!(validate_output(r#"{"name":"Ann","age":3,"tags":["a"]}"#, spec).is_ok());
+
+        let err = validate_output(r#"{"name":"Ann"}"#, spec).unwrap_err();
+        assert!(err.contains("'person'") && err.contains("missing required property 'age'"));
+
+        let err = validate_output(r#"{"name":"Ann","age":"3"}"#, spec).unwrap_err();
+        assert!(err.contains("$.age: expected integer, got string"));
+
+        let err = validate_output(r#"{"name":"Ann","age":3,"tags":["c"]}"#, spec).unwrap_err();
+        assert!(err.contains("$.tags[0]"));
+
+        let err = validate_output(r#"{"name":"Ann","age":3,"x":1}"#, spec).unwrap_err();
+        assert!(err.contains("unexpected property 'x'"));
+
+        assert!(validate_output("not json", spec).unwrap_err().contains("not valid JSON"));
+    }
+}
@@ -0,0 +1,276 @@
+// Admission: runs each request inside an admission ticket carrying its priority class
+// (batch keys are always batch; otherwise the X-Antigravity-Priority header decides).
+//
+// SSE streaming requests that end up queued get their response started right away, with
+// comment pings until an account is found; the handler's stream is then forwarded as is
+// (its response headers are lost). Other streams (Gemini without `alt=sse`) just wait.
+// Only JSON bodies are inspected, so uploads pass through unbuffered. Queue rejections
+// become protocol-shaped 429s.
+//
+// Runs inside routing so account group pins apply to the queue's pool check.
+
+use axum::{
+    body::{Body, Bytes},
+    extract::{Request, State},
+    http::{header, HeaderValue, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+    Json,
+};
+use futures::StreamExt;
+use serde_json::{json, Value};
+use std::future::Future;
+use std::time::Duration;
+
+use super::MAX_REQUEST_BODY;
+use crate::proxy::admission::{self, Rejection, Ticket};
+use crate::proxy::api_keys::ClientKey;
+use crate::proxy::common::header_constants::X_ANTIGRAVITY_PRIORITY;
+use crate::proxy::prometheus;
+use crate::proxy::server::AppState;
+use antigravity_types::models::PriorityClass;
+
+const MAX_ERROR_BODY: usize = 64 * 1024;
+
+pub async fn admission_middleware(
+    State(state): State<AppState>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let config = state.token_manager.admission().config();
+    if !config.enabled || request.method() != axum::http::Method::POST {
+        return next.run(request).await;
+    }
+
+    let ticket = Ticket::new(request_priority(&request));
+    let path = request.uri().path().to_string();
+    let (sse, request) = match streams_sse(request).await {
+        Ok(inspected) => inspected,
+        Err(response) => return response,
+    };
+
+    let mut handler = Box::pin(admission::scope(ticket.clone(), next.run(request)));
+    if !sse {
+        return finish(handler.await, &ticket, &path);
+    }
+    tokio::select! {
+        response = &mut handler => finish(response, &ticket, &path),
+        () = ticket.queued() => {
+            keepalive_response(handler, ticket, path, Duration::from_secs(config.keepalive_secs))
+        },
+    }
+}
+
+fn request_priority(request: &Request) -> PriorityClass {
+    let key = request.extensions().get::<ClientKey>().map(|key| key.priority);
+    let header = request
+        .headers()
+        .get(X_ANTIGRAVITY_PRIORITY)
+        .and_then(|v| v.to_str().ok())
+        .and_then(PriorityClass::parse);
+    // A batch key cannot promote itself with the header
+    match (key, header) {
+        (Some(PriorityClass::Batch), _) => PriorityClass::Batch,
+        (_, Some(priority)) => priority,
+        _ => PriorityClass::Interactive,
+    }
+}
+
+/// Whether the response will be an SSE stream: Gemini streams by path and sends SSE only
+/// with `alt=sse`, the other protocols stream with `"stream": true` in a JSON body.
+async fn streams_sse(request: Request) -> Result<(bool, Request), Response> {
+    if request.uri().path().contains(":streamGenerateContent") {
+        let sse = request.uri().query().is_some_and(|q| q.split('&').any(|p| p == "alt=sse"));
+        return Ok((sse, request));
+    }
+    let is_json = request
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|ct| ct.contains("json"));
+    if !is_json {
+        return Ok((false, request));
+    }
+    let (parts, body) = request.into_parts();
+    let bytes = match axum::body::to_bytes(body, MAX_REQUEST_BODY).await {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            tracing::warn!("[Admission] Failed to buffer request body: {}", e);
+            return Err((StatusCode::PAYLOAD_TOO_LARGE, "Request body too large").into_response());
+        },
+    };
+    let streaming = serde_json::from_slice::<Value>(&bytes)
+        .ok()
+        .and_then(|body| body.get("stream").and_then(Value::as_bool))
+        .unwrap_or(false);
+    Ok((streaming, Request::from_parts(parts, Body::from(bytes))))
+}
+
+/// Turn the handler's 503 into a 429 when the queue turned the request away.
+fn finish(response: Response, ticket: &Ticket, path: &str) -> Response {
+    match ticket.rejection() {
+        Some(rejection) if response.status() == StatusCode::SERVICE_UNAVAILABLE => {
+            rejected_response(path, &rejection)
+        },
+        _ => response,
+    }
+}
+
+fn keepalive_response<F>(handler: F, ticket: Ticket, path: String, interval: Duration) -> Response
+where
+    F: Future<Output = Response> + Send + Unpin + 'static,
+{
+    let stream = async_stream::stream! {
+        let mut handler = handler;
+        let mut pings = tokio::time::interval(interval);
+        let response = loop {
+            tokio::select! {
+                response = &mut handler => break finish(response, &ticket, &path),
+                _ = pings.tick() => {
+                    prometheus::record_admission_keepalive();
+                    yield Ok::<Bytes, axum::Error>(Bytes::from_static(b": queued\n\n"));
+                },
+            }
+        };
+
+        if response.status().is_success() {
+            let mut body = response.into_body().into_data_stream();
+            while let Some(chunk) = body.next().await {
+                yield chunk;
+            }
+        } else {
+            let status = response.status();
+            let bytes =
+                axum::body::to_bytes(response.into_body(), MAX_ERROR_BODY).await.unwrap_or_default();
+            tracing::warn!("[Admission] Queued stream failed with {}", status);
+            yield Ok(error_event(&path, &bytes));
+        }
+    };
+
+    let mut response = Body::from_stream(stream).into_response();
+    let headers = response.headers_mut();
+    headers.insert(header::CONTENT_TYPE, HeaderValue::from_static("text/event-stream"));
+    headers.insert(header::CACHE_CONTROL, HeaderValue::from_static("no-cache"));
+    response
+}
+
+/// The handler's JSON error body as a final SSE event.
+fn error_event(path: &str, body: &[u8]) -> Bytes {
+    let data = String::from_utf8_lossy(body).replace('\n', "");
+    if path.starts_with("/v1/messages") {
+        Bytes::from(format!("event: error\ndata: {}\n\n", data))
+    } else {
+        Bytes::from(format!("data: {}\n\n", data))
+    }
+}
+
+/// 429 in the error format of the protocol the client speaks.
+fn rejected_response(path: &str, rejection: &Rejection) -> Response {
+    let message = &rejection.message;
+    let body = if path.starts_with("/v1/messages") {
+        json!({ "type": "error", "error": { "type": "rate_limit_error", "message": message } })
+    } else if path.starts_with("/v1beta") {
+        json!({ "error": { "code": 429, "message": message, "status": "RESOURCE_EXHAUSTED" } })
+    } else {
+        json!({
+            "error": { "message": message, "type": "rate_limit_error", "code": "queue_rejected" }
+        })
+    };
+    let mut response = (StatusCode::TOO_MANY_REQUESTS, Json(body)).into_response();
+    if let Some(secs) = rejection.retry_after {
+        response.headers_mut().insert(header::RETRY_AFTER, HeaderValue::from(secs));
+    }
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(key: Option<PriorityClass>, header: Option<&str>) -> Request {
+        let mut builder = Request::builder().uri("/v1/messages");
+        if let Some(header) = header {
+            builder = builder.header(X_ANTIGRAVITY_PRIORITY, header);
+        }
+        let mut request = builder.body(Body::empty()).unwrap();
+        if let Some(priority) = key {
+            request.extensions_mut().insert(ClientKey {
+                id: "k".into(),
+                label: "k".into(),
+                account_group: None,
+                priority,
+            });
+        }
+        request
+    }
+
+    #[test]
+    fn batch_keys_cannot_promote_themselves() {
+        assert_eq!(request_priority(&request(None, None)), PriorityClass::Interactive);
+        assert_eq!(request_priority(&request(None, Some("batch"))), PriorityClass::Batch);
+        assert_eq!(
+            request_priority(&request(Some(PriorityClass::Batch), Some("interactive"))),
+            PriorityClass::Batch
+        );
+        assert_eq!(
+            request_priority(&request(Some(PriorityClass::Interactive), Some("batch"))),
+            PriorityClass::Batch
+        );
+    }
+
+    #[tokio::test]
+    async fn queued_stream_pings_then_forwards_handler_body() {
+        let handler = Box::pin(async {
+            tokio::time::sleep(Duration::from_millis(30)).await;
+            Response::new(Body::from("data: {\"ok\":true}\n\n"))
+        });
+        let ticket = Ticket::new(PriorityClass::Interactive);
+        let response = keepalive_response(
+            handler,
+            ticket,
+            "/v1/chat/completions".into(),
+            Duration::from_millis(10),
+        );
+        assert_eq!(response.headers()[header::CONTENT_TYPE], "text/event-stream");
+
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let text = String::from_utf8(bytes.to_vec()).unwrap();
+        assert!(text.starts_with(": queued\n\n"), "{text}");
+        assert!(text.ends_with("data: {\"ok\":true}\n\n"), "{text}");
+    }
+
+    #[tokio::test]
+    async fn only_sse_streams_are_detected() {
+        let sse = |uri: &str, content_type: &str, body: &'static str| {
+            let request = Request::builder()
+                .method("POST")
+                .uri(uri)
+                .header(header::CONTENT_TYPE, content_type)
+                .body(Body::from(body))
+                .unwrap();
+            async { streams_sse(request).await.unwrap().0 }
+        };
+        let gemini = "/v1beta/models/gemini-3-pro:streamGenerateContent";
+        assert!(sse(&format!("{gemini}?alt=sse"), "application/json", "{}").await);
+        assert!(!sse(gemini, "application/json", "{}").await);
+        assert!(sse("/v1/messages", "application/json", r#"{"stream":true}"#).await);
+        assert!(!sse("/v1/messages", "application/json", r#"{"stream":false}"#).await);
+        // Uploads are not buffered, whatever they contain
+        assert!(
+            !sse(
+                "/v1/audio/transcriptions",
+                "multipart/form-data; boundary=x",
+                r#"{"stream":true}"#
+            )
+            .await
+        );
+    }
+
+    #[test]
+    fn rejection_sets_retry_after() {
+        let rejection = Rejection { message: "full".into(), retry_after: Some(12) };
+        let response = rejected_response("/v1beta/models/gemini-3-pro:generateContent", &rejection);
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(response.headers()[header::RETRY_AFTER], "12");
+    }
+}
@@ -1,32 +1,38 @@
 use axum::{
-    extract::Request,
-    extract::State,
+    body::Bytes,
+    extract::{FromRequest, Multipart, Request, State},
     http::{header, StatusCode},
     middleware::Next,
-    response::Response,
+    response::{IntoResponse, Response},
+    Json,
 };
 use std::net::IpAddr;
 use std::sync::Arc;
 use subtle::ConstantTimeEq;
 use tokio::sync::RwLock;
 
-use super::rate_limiter;
+use super::{body_limit, rate_limiter};
+use crate::proxy::api_keys::{ApiKeyRegistry, ApiKeyRejection, ClientKey};
+use crate::proxy::server::AppState;
 use crate::proxy::{ProxyAuthMode, ProxySecurityConfig};
 
+/// Proxy endpoints: accepts the master key or any valid client API key.
 pub async fn auth_middleware(
-    state: State<Arc<RwLock<ProxySecurityConfig>>>,
+    State(state): State<AppState>,
     request: Request,
     next: Next,
 ) -> Result<Response, StatusCode> {
-    auth_middleware_internal(state, request, next, false).await
+    auth_middleware_internal(&state.security_config, Some(&state.api_keys), request, next, false)
+        .await
 }
 
+/// Admin endpoints: master key only.
 pub async fn admin_auth_middleware(
-    state: State<Arc<RwLock<ProxySecurityConfig>>>,
+    State(security): State<Arc<RwLock<ProxySecurityConfig>>>,
     request: Request,
     next: Next,
 ) -> Result<Response, StatusCode> {
-    auth_middleware_internal(state, request, next, true).await
+    auth_middleware_internal(&security, None, request, next, true).await
 }
 
 fn constant_time_compare(a: &str, b: &str) -> bool {
@@ -40,12 +46,41 @@ fn is_health_check(path: &str) -> bool {
     path == "/healthz" || path == "/api/health" || path == "/health"
 }
 
+/// Outcome of checking a request's credentials.
+enum AuthDecision {
+    Allow(Request),
+    /// Authenticated with a client API key, recorded on the response for usage tracking
+    AllowClient(ClientKey, Request),
+    Reject(Response),
+    Deny(StatusCode),
+}
+
 async fn auth_middleware_internal(
-    State(security): State<Arc<RwLock<ProxySecurityConfig>>>,
+    security: &RwLock<ProxySecurityConfig>,
+    api_keys: Option<&Arc<ApiKeyRegistry>>,
     request: Request,
     next: Next,
     force_strict: bool,
 ) -> Result<Response, StatusCode> {
+    match authorize(security, api_keys, request, force_strict).await {
+        AuthDecision::Allow(request) => Ok(next.run(request).await),
+        AuthDecision::AllowClient(client, request) => {
+            let mut response = next.run(request).await;
+            response.extensions_mut().insert(client);
+            Ok(response)
+        },
+        AuthDecision::Reject(response) => Ok(response),
+        AuthDecision::Deny(status) => Err(status),
+    }
+}
+
+#[tracing::instrument(name = "auth", skip_all, fields(strict = force_strict))]
+async fn authorize(
+    security: &RwLock<ProxySecurityConfig>,
+    api_keys: Option<&Arc<ApiKeyRegistry>>,
+    request: Request,
+    force_strict: bool,
+) -> AuthDecision {
     let method = request.method().clone();
     let path = request.uri().path();
 
@@ -59,7 +94,7 @@ async fn auth_middleware_internal(
     }
 
     if method == axum::http::Method::OPTIONS {
-        return Ok(next.run(request).await);
+        return AuthDecision::Allow(request);
     }
 
     let client_ip = extract_client_ip(&request);
@@ -68,7 +103,7 @@ async fn auth_middleware_internal(
         if let Some(ip) = client_ip {
             if rate_limiter::is_blocked(ip) {
                 tracing::warn!("Blocked IP {} attempted access", ip);
-                return Err(StatusCode::TOO_MANY_REQUESTS);
+                return AuthDecision::Deny(StatusCode::TOO_MANY_REQUESTS);
             }
         }
     }
@@ -81,17 +116,17 @@ async fn auth_middleware_internal(
     if force_strict {
         // Admin endpoints: only skip auth for health checks
         if health {
-            return Ok(next.run(request).await);
+            return AuthDecision::Allow(request);
         }
         // Otherwise, ALWAYS require auth regardless of auth_mode
     } else {
         // Proxy endpoints: respect auth_mode
         if matches!(effective_mode, ProxyAuthMode::Off) {
-            return Ok(next.run(request).await);
+            return AuthDecision::Allow(request);
         }
 
         if matches!(effective_mode, ProxyAuthMode::AllExceptHealth) && health {
-            return Ok(next.run(request).await);
+            return AuthDecision::Allow(request);
         }
     }
 
@@ -101,32 +136,169 @@ async fn auth_middleware_internal(
         .and_then(|h| h.to_str().ok())
         .and_then(|s| s.strip_prefix("Bearer ").or(Some(s)))
         .or_else(|| request.headers().get("x-api-key").and_then(|h| h.to_str().ok()))
-        .or_else(|| request.headers().get("x-goog-api-key").and_then(|h| h.to_str().ok()));
+        .or_else(|| request.headers().get("x-goog-api-key").and_then(|h| h.to_str().ok()))
+        .map(str::to_string);
+
+    if let (Some(registry), Some(key)) = (api_keys, api_key.as_deref()) {
+        let is_master =
+            !security.api_key.is_empty() && constant_time_compare(key, &security.api_key);
+        if !is_master && !registry.is_empty() {
+            return match authorize_client_key(registry, key, request).await {
+                Ok((client, request)) => AuthDecision::AllowClient(client, request),
+                Err(response) => AuthDecision::Reject(response),
+            };
+        }
+    }
 
     if security.api_key.is_empty() {
         if force_strict {
             tracing::error!("Admin auth is required but api_key is empty");
-            return Err(StatusCode::UNAUTHORIZED);
+            return AuthDecision::Deny(StatusCode::UNAUTHORIZED);
         }
         tracing::error!("Proxy auth is enabled but api_key is empty; denying request");
-        return Err(StatusCode::UNAUTHORIZED);
+        return AuthDecision::Deny(StatusCode::UNAUTHORIZED);
     }
 
-    let authorized = api_key.is_some_and(|k| constant_time_compare(k, &security.api_key));
+    let authorized = api_key.is_some_and(|k| constant_time_compare(&k, &security.api_key));
 
     if authorized {
         if let Some(ip) = client_ip {
             rate_limiter::clear_failed_attempts(ip);
         }
-        Ok(next.run(request).await)
+        AuthDecision::Allow(request)
     } else {
         if force_strict {
             if let Some(ip) = client_ip {
                 rate_limiter::record_failed_attempt(ip);
             }
         }
-        Err(StatusCode::UNAUTHORIZED)
+        AuthDecision::Deny(StatusCode::UNAUTHORIZED)
+    }
+}
+
+/// Validate a client key and, if it has a model allowlist, the requested model.
+///
+/// A key with an allowlist is denied when the model cannot be determined, except on endpoints
+/// that take no model (their items are checked by the handlers).
+async fn authorize_client_key(
+    registry: &ApiKeyRegistry,
+    secret: &str,
+    request: Request,
+) -> Result<(ClientKey, Request), Response> {
+    let key = registry.authenticate(secret).map_err(|r| rejection_response(&r))?;
+    let client = ClientKey {
+        id: key.id.clone(),
+        label: key.label.clone(),
+        account_group: key.account_group.clone(),
+        priority: key.priority,
+    };
+
+    let mut request = if key.allowed_models.is_empty() || is_model_free(request.uri().path()) {
+        request
+    } else {
+        let (model, request) = extract_model(request).await?;
+        match model {
+            Some(model) if !key.allows_model(&model) => {
+                tracing::warn!("[ApiKeys] Key '{}' denied model {}", key.label, model);
+                return Err(rejection_response(&ApiKeyRejection::ModelNotAllowed(model)));
+            },
+            None if request.method() == axum::http::Method::POST => {
+                tracing::warn!("[ApiKeys] Key '{}' denied request without a model", key.label);
+                return Err(rejection_response(&ApiKeyRejection::ModelRequired));
+            },
+            _ => {},
+        }
+        request
+    };
+    request.extensions_mut().insert(client.clone());
+    Ok((client, request))
+}
+
+/// POST endpoints that name no model of their own.
+fn is_model_free(path: &str) -> bool {
+    ["/v1/files", "/v1/batches", "/v1/messages/batches", "/v1/api/event_logging"]
+        .iter()
+        .any(|prefix| path.starts_with(prefix))
+}
+
+/// Requested model from a Gemini-style path, the JSON body's `model` field, or the `model`
+/// field of a multipart form.
+///
+/// Fails with 413 when the body exceeds the route's [`body_limit`] and 400 when it cannot
+/// be read.
+pub(super) async fn extract_model(request: Request) -> Result<(Option<String>, Request), Response> {
+    let path = request.uri().path();
+    if let Some(rest) = path.split("/v1beta/models/").nth(1) {
+        let model = rest.split([':', '/']).next().map(str::to_string);
+        return Ok((model, request));
     }
+    if request.method() != axum::http::Method::POST {
+        return Ok((None, request));
+    }
+
+    let limit = body_limit(request.uri().path());
+    let declared = request
+        .headers()
+        .get(header::CONTENT_LENGTH)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse::<usize>().ok());
+    if declared.is_some_and(|len| len > limit) {
+        return Err((StatusCode::PAYLOAD_TOO_LARGE, "Request body too large").into_response());
+    }
+
+    let (parts, body) = request.into_parts();
+    let bytes = match axum::body::to_bytes(body, limit).await {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            tracing::warn!("[ApiKeys] Failed to buffer body for model check: {}", e);
+            return Err((StatusCode::BAD_REQUEST, "Failed to read request body").into_response());
+        },
+    };
+    let model = if is_multipart(&parts.headers) {
+        multipart_model(&parts, bytes.clone()).await
+    } else {
+        serde_json::from_slice::<serde_json::Value>(&bytes)
+            .ok()
+            .and_then(|v| v.get("model").and_then(|m| m.as_str()).map(str::to_string))
+    };
+    Ok((model, Request::from_parts(parts, axum::body::Body::from(bytes))))
+}
+
+fn is_multipart(headers: &axum::http::HeaderMap) -> bool {
+    headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v.starts_with("multipart/form-data"))
+}
+
+/// The `model` field of a buffered multipart form, read from a copy of the request.
+async fn multipart_model(parts: &axum::http::request::Parts, bytes: Bytes) -> Option<String> {
+    let mut probe = Request::new(axum::body::Body::from(bytes));
+    *probe.headers_mut() = parts.headers.clone();
+    *probe.extensions_mut() = parts.extensions.clone();
+    let mut multipart = Multipart::from_request(probe, &()).await.ok()?;
+    while let Ok(Some(field)) = multipart.next_field().await {
+        if field.name() == Some("model") {
+            return field.text().await.ok();
+        }
+    }
+    None
+}
+
+fn rejection_response(rejection: &ApiKeyRejection) -> Response {
+    let (status, error_type) = match rejection {
+        ApiKeyRejection::Unknown | ApiKeyRejection::Disabled | ApiKeyRejection::Expired => {
+            (StatusCode::UNAUTHORIZED, "authentication_error")
+        },
+        ApiKeyRejection::ModelNotAllowed(_) | ApiKeyRejection::ModelRequired => {
+            (StatusCode::FORBIDDEN, "permission_error")
+        },
+        ApiKeyRejection::BudgetExhausted => (StatusCode::TOO_MANY_REQUESTS, "rate_limit_error"),
+    };
+    let body = serde_json::json!({
+        "error": { "message": rejection.message(), "type": error_type }
+    });
+    (status, Json(body)).into_response()
 }
 
 fn extract_client_ip(request: &Request) -> Option<IpAddr> {
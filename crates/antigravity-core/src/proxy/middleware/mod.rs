@@ -1,13 +1,49 @@
 // Middleware module - Axum middleware
 
+pub mod admission;
 pub mod auth;
 pub mod cors;
 pub mod logging;
 pub mod monitor;
-mod monitor_usage;
+pub(crate) mod monitor_usage;
 pub mod rate_limiter;
+pub mod routing;
 pub mod service_status;
+pub mod spend;
+pub mod trace;
 
+pub use admission::admission_middleware;
 pub use auth::{admin_auth_middleware, auth_middleware};
 pub use cors::cors_layer;
+pub use routing::routing_middleware;
 pub use service_status::service_status_middleware;
+pub use spend::spend_middleware;
+pub use trace::trace_middleware;
+
+/// Largest request body accepted outside the audio upload routes. Middleware that buffers
+/// bodies uses the same limit, so it never rejects a request the handler would take.
+pub const MAX_REQUEST_BODY: usize = 100 * 1024 * 1024;
+
+/// Largest body accepted on `path`.
+pub(crate) fn body_limit(path: &str) -> usize {
+    match path {
+        "/v1/audio/transcriptions" | "/v1/audio/translations" => {
+            crate::proxy::audio::AudioProcessor::max_upload_bytes()
+        },
+        _ => MAX_REQUEST_BODY,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn audio_uploads_get_their_own_limit() {
+        let audio = crate::proxy::audio::AudioProcessor::max_upload_bytes();
+        assert_eq!(body_limit("/v1/audio/transcriptions"), audio);
+        assert_eq!(body_limit("/v1/audio/translations"), audio);
+        assert_eq!(body_limit("/v1/audio/speech"), MAX_REQUEST_BODY);
+        assert_eq!(body_limit("/v1/chat/completions"), MAX_REQUEST_BODY);
+    }
+}
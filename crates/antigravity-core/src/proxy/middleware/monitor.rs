@@ -9,8 +9,9 @@
 )]
 
 use super::monitor_usage::extract_usage_from_json;
+use crate::proxy::api_keys::ClientKey;
 use crate::proxy::common::header_constants::{X_ACCOUNT_EMAIL, X_MAPPED_MODEL, X_MAPPING_REASON};
-use crate::proxy::monitor::ProxyRequestLog;
+use crate::proxy::monitor::{truncate_body, ProxyRequestLog};
 use crate::proxy::server::AppState;
 use axum::{
     body::Body,
@@ -19,10 +20,12 @@ use axum::{
     middleware::Next,
     response::Response,
 };
+use bytes::Bytes;
 use futures::StreamExt;
 use serde_json::Value;
 use std::sync::Arc;
 use std::time::Instant;
+use tracing::Instrument;
 
 const MAX_BODY_LOG_SIZE: usize = 512 * 1024;
 
@@ -31,10 +34,6 @@ pub async fn monitor_middleware(
     request: Request,
     next: Next,
 ) -> Response {
-    if !state.monitor.is_enabled() {
-        return next.run(request).await;
-    }
-
     let start = Instant::now();
     let method = request.method().to_string();
     let uri = request.uri().to_string();
@@ -50,23 +49,21 @@ pub async fn monitor_middleware(
     };
 
     let monitor = state.monitor.clone();
-    let content_length = request
-        .headers()
-        .get(axum::http::header::CONTENT_LENGTH)
-        .and_then(|v| v.to_str().ok())
-        .and_then(|s| s.parse::<usize>().ok());
+    // With monitoring off, requests are still parsed for usage to charge keys and budgets
+    let mut capture = monitor.capture_config();
+    capture.enabled &= monitor.is_enabled();
+    let mut request_body = None;
 
-    let request = if method == "POST" && content_length.is_some_and(|l| l <= MAX_BODY_LOG_SIZE) {
+    // Bodies without Content-Length are buffered too; only the prefix is held back
+    let request = if method == "POST" {
+        let limit = if capture.enabled {
+            capture.max_body_bytes.max(MAX_BODY_LOG_SIZE)
+        } else {
+            MAX_BODY_LOG_SIZE
+        };
         let (parts, body) = request.into_parts();
-        match axum::body::to_bytes(body, MAX_BODY_LOG_SIZE).await {
-            Ok(bytes) => {
-                if model.is_none() {
-                    model = serde_json::from_slice::<Value>(&bytes).ok().and_then(|v| {
-                        v.get("model").and_then(|m| m.as_str()).map(|s| s.to_string())
-                    });
-                }
-                Request::from_parts(parts, Body::from(bytes))
-            },
+        let (prefix, complete, body) = match buffer_prefix(body, limit).await {
+            Ok(buffered) => buffered,
             Err(e) => {
                 tracing::error!("Failed to buffer request body: {}", e);
                 return Response::builder()
@@ -74,12 +71,25 @@ pub async fn monitor_middleware(
                     .body(Body::from("Failed to buffer request body"))
                     .unwrap_or_else(|_| Response::new(Body::empty()));
             },
+        };
+        if complete && model.is_none() {
+            model = serde_json::from_slice::<Value>(&prefix)
+                .ok()
+                .and_then(|v| v.get("model").and_then(|m| m.as_str()).map(|s| s.to_string()));
         }
+        if capture.enabled {
+            request_body = Some(captured_body(&prefix, capture.max_body_bytes));
+        }
+        Request::from_parts(parts, body)
     } else {
         request
     };
 
-    let response = next.run(request).await;
+    let (response, upstream_request_body) = if capture.enabled {
+        crate::proxy::capture::scope(next.run(request)).await
+    } else {
+        (next.run(request).await, None)
+    };
     let duration = start.elapsed().as_millis() as u64;
     let status = response.status().as_u16();
 
@@ -103,6 +113,8 @@ pub async fn monitor_middleware(
         .and_then(|v| v.to_str().ok())
         .map(|s| s.to_string());
 
+    let client_key = response.extensions().get::<ClientKey>().cloned();
+
     let log = ProxyRequestLog {
         id: uuid::Uuid::new_v4().to_string(),
         timestamp: chrono::Utc::now().timestamp_millis(),
@@ -115,38 +127,85 @@ pub async fn monitor_middleware(
         mapping_reason,
         account_email,
         error: None,
-        request_body: None,
+        request_body,
+        upstream_request_body: upstream_request_body
+            .map(|body| truncate_body(&body, capture.max_body_bytes)),
         response_body: None,
         input_tokens: None,
         output_tokens: None,
         cached_tokens: None,
+        api_key_id: client_key.as_ref().map(|k| k.id.clone()),
+        api_key_label: client_key.map(|k| k.label),
     };
 
+    let capture_limit = capture.enabled.then_some(capture.max_body_bytes);
     if content_type.contains("text/event-stream") {
-        handle_sse_response(response, log, monitor, start).await
+        handle_sse_response(response, log, monitor, start, capture_limit).await
     } else if content_type.contains("application/json") {
-        handle_json_response(response, log, monitor, start).await
+        handle_json_response(response, log, monitor, start, capture_limit).await
     } else {
         monitor.log_request(log).await;
         response
     }
 }
 
+/// Read `body` until more than `limit` bytes are buffered or it ends.
+///
+/// Returns the buffered prefix, whether it holds the whole body, and a body that
+/// replays the prefix followed by whatever was not read yet.
+async fn buffer_prefix(body: Body, limit: usize) -> Result<(Bytes, bool, Body), axum::Error> {
+    let mut stream = body.into_data_stream();
+    let mut prefix = Vec::new();
+    while let Some(chunk) = stream.next().await {
+        prefix.extend_from_slice(&chunk?);
+        if prefix.len() > limit {
+            let prefix = Bytes::from(prefix);
+            let head = futures::stream::once(futures::future::ready(Ok(prefix.clone())));
+            return Ok((prefix, false, Body::from_stream(head.chain(stream))));
+        }
+    }
+    let prefix = Bytes::from(prefix);
+    Ok((prefix.clone(), true, Body::from(prefix)))
+}
+
+/// Request body as stored in the capture, cut at `max` bytes.
+fn captured_body(prefix: &[u8], max: usize) -> String {
+    // One byte past the limit is enough for `truncate_body` to mark the cut
+    let head = &prefix[..prefix.len().min(max + 1)];
+    truncate_body(&String::from_utf8_lossy(head), max)
+}
+
 async fn handle_sse_response(
     response: Response,
     mut log: ProxyRequestLog,
     monitor: Arc<crate::proxy::monitor::ProxyMonitor>,
     start: Instant,
+    capture_limit: Option<usize>,
 ) -> Response {
     let (parts, body) = response.into_parts();
     let mut stream = body.into_data_stream();
     let (tx, rx) = tokio::sync::mpsc::channel(64);
+    // Outlives the request span, which closes once the headers are sent
+    let stream_span = tracing::info_span!(
+        "stream",
+        bytes = tracing::field::Empty,
+        input_tokens = tracing::field::Empty,
+        output_tokens = tracing::field::Empty,
+        error = tracing::field::Empty,
+    );
 
-    tokio::spawn(async move {
+    let forward = async move {
         let mut last_few_bytes = Vec::new();
+        let mut captured = Vec::new();
+        let mut total_bytes = 0usize;
 
         while let Some(chunk_res) = stream.next().await {
             if let Ok(chunk) = chunk_res {
+                total_bytes += chunk.len();
+                if let Some(limit) = capture_limit {
+                    let room = limit.saturating_sub(captured.len()).min(chunk.len());
+                    captured.extend_from_slice(&chunk[..room]);
+                }
                 if chunk.len() > 8192 {
                     last_few_bytes = chunk.slice(chunk.len() - 8192..).to_vec();
                 } else {
@@ -198,9 +257,25 @@ async fn handle_sse_response(
                 }
             }
         }
+        if capture_limit.is_some() {
+            log.response_body = Some(String::from_utf8_lossy(&captured).into_owned());
+        }
         log.duration = start.elapsed().as_millis() as u64;
+
+        let span = tracing::Span::current();
+        span.record("bytes", total_bytes);
+        if let Some(tokens) = log.input_tokens {
+            span.record("input_tokens", tokens);
+        }
+        if let Some(tokens) = log.output_tokens {
+            span.record("output_tokens", tokens);
+        }
+        if let Some(error) = &log.error {
+            span.record("error", truncate_body(error, 1024).as_str());
+        }
         monitor.log_request(log).await;
-    });
+    };
+    tokio::spawn(forward.instrument(stream_span));
 
     Response::from_parts(parts, Body::from_stream(tokio_stream::wrappers::ReceiverStream::new(rx)))
 }
@@ -210,6 +285,7 @@ async fn handle_json_response(
     mut log: ProxyRequestLog,
     monitor: Arc<crate::proxy::monitor::ProxyMonitor>,
     _start: Instant,
+    capture_limit: Option<usize>,
 ) -> Response {
     let content_length = response
         .headers()
@@ -282,8 +358,42 @@ async fn handle_json_response(
         } else {
             log.response_body = None;
         }
+        if let Some(limit) = capture_limit.filter(|_| !failed_to_buffer) {
+            log.response_body = Some(truncate_body(&String::from_utf8_lossy(&buffer), limit));
+        }
         monitor.log_request(log).await;
     });
 
     Response::from_parts(parts, Body::from_stream(tokio_stream::wrappers::ReceiverStream::new(rx)))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chunked(parts: Vec<&'static str>) -> Body {
+        Body::from_stream(futures::stream::iter(
+            parts.into_iter().map(|p| Ok::<_, std::io::Error>(Bytes::from(p))),
+        ))
+    }
+
+    #[tokio::test]
+    async fn buffers_whole_body_without_content_length() {
+        let (prefix, complete, body) =
+            buffer_prefix(chunked(vec!["{\"model\":", "\"m\"}"]), 64).await.unwrap();
+        assert!(complete);
+        assert_eq!(prefix, "{\"model\":\"m\"}");
+        assert_eq!(axum::body::to_bytes(body, usize::MAX).await.unwrap(), prefix);
+    }
+
+    #[tokio::test]
+    async fn replays_bodies_larger_than_the_limit() {
+        let (prefix, complete, body) =
+            buffer_prefix(chunked(vec!["aaaa", "bbbb", "cccc"]), 6).await.unwrap();
+        assert!(!complete);
+        assert_eq!(prefix, "aaaabbbb");
+        assert_eq!(axum::body::to_bytes(body, usize::MAX).await.unwrap(), "aaaabbbbcccc");
+        assert_eq!(captured_body(&prefix, 6), "aaaabb...[truncated]");
+        assert_eq!(captured_body(b"aaaa", 6), "aaaa");
+    }
+}
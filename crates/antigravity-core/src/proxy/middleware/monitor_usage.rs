@@ -11,7 +11,7 @@
 use crate::proxy::monitor::ProxyRequestLog;
 use serde_json::Value;
 
-pub(super) fn extract_usage_from_json(usage: &Value, log: &mut ProxyRequestLog) {
+pub(crate) fn extract_usage_from_json(usage: &Value, log: &mut ProxyRequestLog) {
     log.input_tokens = usage
         .get("prompt_tokens")
         .and_then(|v| v.as_u64())
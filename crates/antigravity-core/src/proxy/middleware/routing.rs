@@ -0,0 +1,177 @@
+// Routing rules: evaluates `routing_rules` against each JSON request and applies the
+// first rule that fires before the handler runs. A client key's account group applies
+// to every request made with it; a rule pinning a group only narrows it further.
+//
+// Runs inside auth and spend so the client key is already in the request extensions.
+
+use axum::{
+    body::Body,
+    extract::{Request, State},
+    http::{header, HeaderMap, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde_json::{json, Value};
+
+use super::MAX_REQUEST_BODY;
+use crate::proxy::api_keys::{ApiKeyRejection, ClientKey};
+use crate::proxy::routing_rules::{self, RequestFacts, RouteDecision};
+use crate::proxy::server::AppState;
+use antigravity_types::models::RuleAction;
+
+pub async fn routing_middleware(
+    State(state): State<AppState>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let key_group =
+        request.extensions().get::<ClientKey>().and_then(|key| key.account_group.clone());
+    let key_decision = || {
+        key_group
+            .clone()
+            .map(|group| RouteDecision { key_group: Some(group), ..Default::default() })
+    };
+    let path = request.uri().path().to_string();
+    let is_json = request
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|ct| ct.contains("json"));
+    if request.method() != axum::http::Method::POST
+        || !is_json
+        || path == "/v1/models/detect"
+        || path.contains("event_logging")
+        || state.routing_rules.read().await.is_empty()
+    {
+        return run(next, request, key_decision()).await;
+    }
+
+    let (mut parts, body) = request.into_parts();
+    let bytes = match axum::body::to_bytes(body, MAX_REQUEST_BODY).await {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            tracing::warn!("[Routing] Failed to buffer request body: {}", e);
+            return (StatusCode::PAYLOAD_TOO_LARGE, "Request body too large").into_response();
+        },
+    };
+    let Ok(mut body) = serde_json::from_slice::<Value>(&bytes) else {
+        return run(next, Request::from_parts(parts, Body::from(bytes)), key_decision()).await;
+    };
+
+    let api_key = parts.extensions.get::<ClientKey>().cloned();
+    let model = body.get("model").cloned();
+    let decision = match route(&state, &path, &parts.headers, api_key, &mut body).await {
+        Ok(decision) => decision,
+        Err(response) => return response,
+    };
+    let bytes = if body.get("model") != model.as_ref() {
+        parts.headers.remove(header::CONTENT_LENGTH);
+        serde_json::to_vec(&body).map(Into::into).unwrap_or(bytes)
+    } else {
+        bytes
+    };
+
+    run(next, Request::from_parts(parts, Body::from(bytes)), decision).await
+}
+
+/// Evaluate the routing rules for a JSON request to `path`, rewriting the body's model when
+/// a `rewrite_model` rule fires. Returns the decision to run the request under, or the
+/// rejection to send instead.
+///
+/// A rewritten model is checked against the client key's allowlist again, since auth only
+/// saw the model the client asked for.
+pub(crate) async fn route(
+    state: &AppState,
+    path: &str,
+    headers: &HeaderMap,
+    api_key: Option<ClientKey>,
+    body: &mut Value,
+) -> Result<Option<RouteDecision>, Response> {
+    let key_group = api_key.as_ref().and_then(|key| key.account_group.clone());
+    let key_id = api_key.as_ref().map(|key| key.id.clone());
+    let facts = RequestFacts::from_request(path, headers, api_key, body);
+    let fired = {
+        let rules = state.routing_rules.read().await;
+        routing_rules::evaluate(&rules, &facts).fired.cloned()
+    };
+    let Some(rule) = fired else {
+        return Ok(
+            key_group.map(|group| RouteDecision { key_group: Some(group), ..Default::default() })
+        );
+    };
+
+    tracing::info!("[Routing] Rule '{}' fired for model '{}'", rule.name, facts.model);
+    let mut decision = RouteDecision::from_rule(&rule);
+    decision.key_group = key_group;
+    match &rule.action {
+        RuleAction::Reject { message } => {
+            let message = message
+                .clone()
+                .unwrap_or_else(|| format!("Request rejected by routing rule '{}'", rule.name));
+            return Err(rejected_response(path, &message));
+        },
+        RuleAction::RewriteModel { model } => {
+            let key = key_id.as_deref().and_then(|id| state.api_keys.get(id));
+            if key.is_some_and(|key| !key.allows_model(model)) {
+                tracing::warn!(
+                    "[Routing] Rule '{}' rewrote to disallowed model {}",
+                    rule.name,
+                    model
+                );
+                let rejection = ApiKeyRejection::ModelNotAllowed(model.clone());
+                return Err(rejected_response(path, &rejection.message()));
+            }
+            if body.get("model").is_some() {
+                body["model"] = Value::String(model.clone());
+            }
+        },
+        _ => {},
+    }
+    Ok(Some(decision))
+}
+
+async fn run(next: Next, request: Request, decision: Option<RouteDecision>) -> Response {
+    match decision {
+        Some(decision) => routing_rules::scope(decision, next.run(request)).await,
+        None => next.run(request).await,
+    }
+}
+
+/// 403 in the error format of the protocol the client speaks.
+fn rejected_response(path: &str, message: &str) -> Response {
+    let body = if path.starts_with("/v1/messages") {
+        json!({ "type": "error", "error": { "type": "permission_error", "message": message } })
+    } else if path.starts_with("/v1beta") {
+        json!({ "error": { "code": 403, "message": message, "status": "PERMISSION_DENIED" } })
+    } else {
+        json!({
+            "error": { "message": message, "type": "invalid_request_error", "code": "request_rejected" }
+        })
+    };
+    (StatusCode::FORBIDDEN, Json(body)).into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn body(response: Response) -> Value {
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        serde_json::from_slice(&bytes).unwrap()
+    }
+
+    #[tokio::test]
+    async fn rejection_matches_client_protocol() {
+        let claude = body(rejected_response("/v1/messages", "no")).await;
+        assert_eq!(claude["error"]["type"], "permission_error");
+
+        let gemini =
+            body(rejected_response("/v1beta/models/gemini-3-pro:generateContent", "no")).await;
+        assert_eq!(gemini["error"]["status"], "PERMISSION_DENIED");
+
+        let openai = body(rejected_response("/v1/chat/completions", "no")).await;
+        assert_eq!(openai["error"]["code"], "request_rejected");
+    }
+}
@@ -0,0 +1,85 @@
+// Spend budget enforcement: rejects requests once a blocking budget is exhausted.
+//
+// Runs inside auth so the client key is already in the request extensions.
+
+use axum::{
+    extract::{Request, State},
+    http::StatusCode,
+    middleware::Next,
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde_json::json;
+
+use super::auth::extract_model;
+use crate::proxy::api_keys::ClientKey;
+use crate::proxy::server::AppState;
+
+pub async fn spend_middleware(
+    State(state): State<AppState>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let spend = state.monitor.spend();
+    if request.method() != axum::http::Method::POST || !spend.has_blocking_budgets() {
+        return next.run(request).await;
+    }
+
+    let api_key_id = request.extensions().get::<ClientKey>().map(|c| c.id.clone());
+    let (model, request) = if spend.blocks_any_model() {
+        match extract_model(request).await {
+            Ok(extracted) => extracted,
+            Err(response) => return response,
+        }
+    } else {
+        (None, request)
+    };
+
+    match spend.blocking_budget(api_key_id.as_deref(), model.as_deref()) {
+        Some(budget) => {
+            tracing::warn!("[Spend] Request rejected by budget '{}'", budget.name);
+            budget_exceeded_response(request.uri().path(), &budget.name)
+        },
+        None => next.run(request).await,
+    }
+}
+
+/// 429 in the error format of the protocol the client speaks.
+pub(crate) fn budget_exceeded_response(path: &str, budget: &str) -> Response {
+    let message = format!("Spend budget '{}' exhausted for the current period", budget);
+    let body = if path.starts_with("/v1/messages") {
+        json!({ "type": "error", "error": { "type": "rate_limit_error", "message": message } })
+    } else if path.starts_with("/v1beta") {
+        json!({ "error": { "code": 429, "message": message, "status": "RESOURCE_EXHAUSTED" } })
+    } else {
+        json!({
+            "error": { "message": message, "type": "insufficient_quota", "code": "budget_exceeded" }
+        })
+    };
+    (StatusCode::TOO_MANY_REQUESTS, Json(body)).into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn body(response: Response) -> serde_json::Value {
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        serde_json::from_slice(&bytes).unwrap()
+    }
+
+    #[tokio::test]
+    async fn rejection_matches_client_protocol() {
+        let claude = body(budget_exceeded_response("/v1/messages", "b")).await;
+        assert_eq!(claude["error"]["type"], "rate_limit_error");
+
+        let gemini =
+            body(budget_exceeded_response("/v1beta/models/gemini-3-pro:generateContent", "b"))
+                .await;
+        assert_eq!(gemini["error"]["status"], "RESOURCE_EXHAUSTED");
+
+        let openai = body(budget_exceeded_response("/v1/chat/completions", "b")).await;
+        assert_eq!(openai["error"]["code"], "budget_exceeded");
+    }
+}
@@ -0,0 +1,81 @@
+//! Request span and W3C `traceparent` propagation.
+
+use axum::{extract::Request, http::HeaderValue, middleware::Next, response::Response};
+use opentelemetry::propagation::TextMapPropagator;
+use opentelemetry::trace::TraceContextExt;
+use opentelemetry_http::HeaderExtractor;
+use opentelemetry_sdk::propagation::TraceContextPropagator;
+use tracing::Instrument;
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+use crate::proxy::common::header_constants::X_TRACE_ID;
+use crate::proxy::telemetry::{self, format_trace_id};
+
+/// Open the root span of a request, continuing the caller's trace when a valid
+/// `traceparent` is present, and return its trace id in `X-Trace-Id`.
+pub async fn trace_middleware(request: Request, next: Next) -> Response {
+    let span = tracing::info_span!(
+        "http.request",
+        otel.name = %format!("{} {}", request.method(), request.uri().path()),
+        http.request.method = %request.method(),
+        url.path = %request.uri().path(),
+        http.response.status_code = tracing::field::Empty,
+    );
+
+    let parent = TraceContextPropagator::new().extract(&HeaderExtractor(request.headers()));
+    let remote_trace_id = format_trace_id(parent.span().span_context());
+    // Fails only when no OpenTelemetry layer is installed; the remote id is still echoed
+    let _ = span.set_parent(parent);
+    let trace_id = format_trace_id(span.context().span().span_context()).or(remote_trace_id);
+
+    let mut response = match trace_id.clone() {
+        Some(id) => telemetry::scope(id, next.run(request).instrument(span.clone())).await,
+        None => next.run(request).instrument(span.clone()).await,
+    };
+    span.record("http.response.status_code", response.status().as_u16());
+
+    if let Some(value) = trace_id.and_then(|id| HeaderValue::from_str(&id).ok()) {
+        response.headers_mut().insert(X_TRACE_ID, value);
+    }
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{routing::get, Router};
+    use axum_test::TestServer;
+
+    fn server() -> TestServer {
+        let app = Router::new()
+            .route("/", get(|| async { telemetry::current_trace_id().unwrap_or_default() }))
+            .layer(axum::middleware::from_fn(trace_middleware));
+        TestServer::new(app).unwrap()
+    }
+
+    #[tokio::test]
+    async fn echoes_incoming_trace_id() {
+        let response = server()
+            .get("/")
+            .add_header(
+                axum::http::HeaderName::from_static("traceparent"),
+                HeaderValue::from_static("00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01"),
+            )
+            .await;
+        assert_eq!(response.header("x-trace-id"), "4bf92f3577b34da6a3ce929d0e0e4736");
+        // Handlers see the same id for their log lines
+        assert_eq!(response.text(), "4bf92f3577b34da6a3ce929d0e0e4736");
+    }
+
+    #[tokio::test]
+    async fn ignores_malformed_traceparent() {
+        let response = server()
+            .get("/")
+            .add_header(
+                axum::http::HeaderName::from_static("traceparent"),
+                HeaderValue::from_static("not-a-trace"),
+            )
+            .await;
+        assert!(response.maybe_header("x-trace-id").is_none());
+    }
+}
@@ -18,15 +18,26 @@
 // Our custom modules
 pub mod active_request_guard;
 pub mod adaptive_limit;
+pub mod admission;
+pub mod api_keys;
+pub mod batch;
+pub mod capture;
+pub mod cluster;
+pub mod context_cache;
 pub mod health;
 pub mod monitor;
+pub mod notifier;
 pub mod prometheus;
 pub mod proxy_pool;
+pub mod response_store;
 pub mod routing_config;
+pub mod routing_rules;
 pub mod security;
 pub mod server;
 pub mod signature_metrics;
+pub mod spend;
 pub mod sticky_config;
+pub mod telemetry;
 pub mod token_manager;
 
 // Cleaned upstream modules (Phase 3c complete)
@@ -56,8 +67,12 @@ pub use antigravity_types::models::config;
 pub use antigravity_types::models::{ProxyAuthMode, ZaiConfig, ZaiDispatchMode};
 
 // Core types
+pub use api_keys::{ApiKeyRegistry, ClientKey};
+pub use batch::BatchManager;
 pub use monitor::{ProxyEventBus, ProxyMonitor};
+pub use notifier::Notifier;
 pub use proxy_pool::ProxyPool;
+pub use response_store::ResponseStore;
 pub use routing_config::SmartRoutingConfig;
 pub use security::ProxySecurityConfig;
 pub use server::{
@@ -68,7 +83,8 @@ pub use token_manager::TokenManager;
 
 // AIMD rate limiting types
 pub use adaptive_limit::{
-    AIMDController, AdaptiveLimitManager, AdaptiveLimitTracker, AimdAccountStats, ProbeStrategy,
+    AIMDController, AdaptiveLimitManager, AdaptiveLimitTracker, AimdAccountStats, HedgeOutcome,
+    HedgePlan, HedgeReason, ProbeStrategy,
 };
 pub use common::circuit_breaker::{CircuitBreakerManager, CircuitState};
 pub use health::HealthMonitor;
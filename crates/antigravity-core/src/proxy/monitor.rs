@@ -6,6 +6,11 @@
 
 // Re-export ProxyRequestLog for upstream middleware compatibility
 use crate::modules::repository::{AccountRepository, RequestLog};
+use crate::proxy::api_keys::ApiKeyRegistry;
+use crate::proxy::spend::SpendTracker;
+use antigravity_types::models::{
+    AccountStateChange, BudgetAlert, CaptureConfig, QuotaData, SpendConfig,
+};
 pub use antigravity_types::models::{ProxyRequestLog, ProxyStats};
 use dashmap::DashMap;
 use std::collections::VecDeque;
@@ -17,6 +22,41 @@ use tokio::sync::RwLock;
 /// Different frontends (Tauri, WebSocket, etc.) can implement this.
 pub trait ProxyEventBus: Send + Sync {
     fn emit_request_log(&self, log: &ProxyRequestLog);
+
+    fn emit_account_state(&self, _change: &AccountStateChange) {}
+
+    fn emit_quota_update(&self, _account_id: &str, _quota: &QuotaData) {}
+
+    fn emit_budget_alert(&self, _alert: &BudgetAlert) {}
+}
+
+/// Event bus handle for components built before the bus exists (token manager,
+/// rate limiter, circuit breaker). Emits nothing until [`EventBusSlot::set`].
+#[derive(Default)]
+pub struct EventBusSlot(parking_lot::RwLock<Option<Arc<dyn ProxyEventBus>>>);
+
+impl EventBusSlot {
+    pub fn set(&self, bus: Arc<dyn ProxyEventBus>) {
+        *self.0.write() = Some(bus);
+    }
+
+    pub fn emit_account_state(&self, change: AccountStateChange) {
+        if let Some(bus) = self.0.read().as_ref() {
+            bus.emit_account_state(&change);
+        }
+    }
+
+    pub fn emit_quota_update(&self, account_id: &str, quota: &QuotaData) {
+        if let Some(bus) = self.0.read().as_ref() {
+            bus.emit_quota_update(account_id, quota);
+        }
+    }
+}
+
+impl std::fmt::Debug for EventBusSlot {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("EventBusSlot").field(&self.0.read().is_some()).finish()
+    }
 }
 
 /// A no-op event bus for headless mode
@@ -37,6 +77,24 @@ pub struct ProxyMonitor {
     max_logs: usize,
     repository: Option<Arc<dyn AccountRepository>>,
     tokens: Option<Arc<DashMap<String, crate::proxy::token_manager::ProxyToken>>>,
+    api_keys: Option<Arc<ApiKeyRegistry>>,
+    capture: parking_lot::RwLock<CaptureConfig>,
+    spend: Arc<SpendTracker>,
+}
+
+/// Response bodies kept in the in-memory buffer (errors only).
+const MAX_MEMORY_BODY_SIZE: usize = 16_384;
+
+/// Truncate `body` to at most `max` bytes on a char boundary.
+pub(crate) fn truncate_body(body: &str, max: usize) -> String {
+    if body.len() <= max {
+        return body.to_string();
+    }
+    let mut end = max;
+    while !body.is_char_boundary(end) {
+        end -= 1;
+    }
+    format!("{}...[truncated]", &body[..end])
 }
 
 impl ProxyMonitor {
@@ -53,6 +111,9 @@ impl ProxyMonitor {
             max_logs: 1000,
             repository: None,
             tokens: None,
+            api_keys: None,
+            capture: parking_lot::RwLock::new(CaptureConfig::default()),
+            spend: Arc::new(SpendTracker::default()),
         }
     }
 
@@ -69,9 +130,19 @@ impl ProxyMonitor {
             max_logs: 1000,
             repository: Some(repository),
             tokens: Some(tokens),
+            api_keys: None,
+            capture: parking_lot::RwLock::new(CaptureConfig::default()),
+            spend: Arc::new(SpendTracker::default()),
         }
     }
 
+    /// Count logged requests against client API key budgets.
+    #[must_use]
+    pub fn with_api_keys(mut self, api_keys: Arc<ApiKeyRegistry>) -> Self {
+        self.api_keys = Some(api_keys);
+        self
+    }
+
     pub fn is_enabled(&self) -> bool {
         self.enabled.load(Ordering::Relaxed)
     }
@@ -80,7 +151,37 @@ impl ProxyMonitor {
         self.enabled.store(enabled, Ordering::Relaxed);
     }
 
-    pub async fn log_request(&self, log: ProxyRequestLog) {
+    /// Current body capture settings.
+    pub fn capture_config(&self) -> CaptureConfig {
+        self.capture.read().clone()
+    }
+
+    pub fn set_capture_config(&self, config: CaptureConfig) {
+        *self.capture.write() = config;
+    }
+
+    /// Spend accounting fed by every logged request.
+    pub fn spend(&self) -> &Arc<SpendTracker> {
+        &self.spend
+    }
+
+    pub fn set_spend_config(&self, config: SpendConfig) {
+        self.spend.set_config(config);
+    }
+
+    /// Charge a finished request to its client key and the spend budgets, then record it
+    /// in the stats, logs and database if monitoring is enabled.
+    pub async fn log_request(&self, mut log: ProxyRequestLog) {
+        if let (Some(registry), Some(key_id)) = (&self.api_keys, &log.api_key_id) {
+            let tokens = u64::from(log.input_tokens.unwrap_or(0))
+                + u64::from(log.output_tokens.unwrap_or(0));
+            registry.record_usage(key_id, tokens);
+        }
+        self.spend.record(&log, self.event_bus.as_ref());
+        if !self.is_enabled() {
+            return;
+        }
+
         // Extract DB fields before log is moved into the in-memory buffer
         let db_context = self.extract_db_context(&log);
 
@@ -101,6 +202,26 @@ impl ProxyMonitor {
             }
         }
 
+        // Captured bodies go to SQLite; the in-memory buffer keeps only error bodies
+        let capture = self.capture_config();
+        if capture.enabled {
+            let full = log.clone();
+            tokio::spawn(async move {
+                if let Err(e) =
+                    crate::modules::proxy_db::save_log(full, capture.retention_days).await
+                {
+                    tracing::warn!("Failed to persist captured request: {}", e);
+                }
+            });
+        }
+        log.request_body = None;
+        log.upstream_request_body = None;
+        log.response_body = if log.status >= 400 {
+            log.response_body.as_deref().map(|b| truncate_body(b, MAX_MEMORY_BODY_SIZE))
+        } else {
+            None
+        };
+
         // Emit to event bus
         self.event_bus.emit_request_log(&log);
 
@@ -148,6 +269,7 @@ impl ProxyMonitor {
             latency_ms: Some(i32::try_from(log.duration).unwrap_or(i32::MAX)),
             status_code: i32::from(log.status),
             error_type: log.error.clone(),
+            api_key_id: log.api_key_id.clone(),
         };
         Some((Arc::clone(repo), request_log))
     }
@@ -0,0 +1,162 @@
+//! Webhook delivery with retries.
+
+use super::format::{render, sign};
+use crate::proxy::common::header_constants::{
+    X_ANTIGRAVITY_EVENT, X_ANTIGRAVITY_SIGNATURE, X_ANTIGRAVITY_TIMESTAMP,
+};
+use antigravity_types::models::{DeliveryStatus, Notification, WebhookTarget};
+use std::time::Duration;
+
+const MAX_BACKOFF: Duration = Duration::from_secs(60);
+
+/// Result of delivering one notification to one target (before it gets a log ID).
+pub(super) struct Outcome {
+    pub status: DeliveryStatus,
+    pub attempts: u32,
+    pub http_status: Option<u16>,
+    pub error: Option<String>,
+}
+
+/// Retry network errors, 429 and 5xx; other statuses are final.
+fn is_retryable(status: reqwest::StatusCode) -> bool {
+    status == reqwest::StatusCode::TOO_MANY_REQUESTS || status.is_server_error()
+}
+
+/// POST `notification` to `target`, retrying with exponential backoff from `base_delay`.
+pub(super) async fn deliver(
+    http: &reqwest::Client,
+    target: &WebhookTarget,
+    notification: &Notification,
+    max_attempts: u32,
+    base_delay: Duration,
+) -> Outcome {
+    let body = serde_json::to_vec(&render(target, notification)).unwrap_or_default();
+    let mut outcome =
+        Outcome { status: DeliveryStatus::Failed, attempts: 0, http_status: None, error: None };
+    let mut delay = base_delay;
+
+    while outcome.attempts < max_attempts.max(1) {
+        if outcome.attempts > 0 {
+            tokio::time::sleep(delay).await;
+            delay = (delay * 2).min(MAX_BACKOFF);
+        }
+        outcome.attempts += 1;
+
+        let mut request = http
+            .post(&target.url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .header(X_ANTIGRAVITY_EVENT, &notification.kind);
+        if let Some(secret) = target.secret.as_deref().filter(|s| !s.is_empty()) {
+            // Signed per attempt so receivers can reject stale timestamps
+            let timestamp = chrono::Utc::now().timestamp();
+            request = request
+                .header(X_ANTIGRAVITY_TIMESTAMP, timestamp.to_string())
+                .header(X_ANTIGRAVITY_SIGNATURE, sign(secret, timestamp, &body));
+        }
+
+        match request.body(body.clone()).send().await {
+            Ok(response) => {
+                let status = response.status();
+                outcome.http_status = Some(status.as_u16());
+                if status.is_success() {
+                    outcome.status = DeliveryStatus::Delivered;
+                    outcome.error = None;
+                    return outcome;
+                }
+                outcome.error = Some(format!("HTTP {}", status));
+                if !is_retryable(status) {
+                    return outcome;
+                }
+            },
+            Err(e) => outcome.error = Some(e.to_string()),
+        }
+        tracing::debug!(
+            "[Notifier] Delivery to '{}' failed (attempt {}): {:?}",
+            target.name,
+            outcome.attempts,
+            outcome.error
+        );
+    }
+    outcome
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use antigravity_types::models::{NotificationSeverity, WebhookFormat};
+    use axum::http::{HeaderMap, StatusCode};
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::sync::Arc;
+
+    fn notification() -> Notification {
+        Notification {
+            kind: "pool_low".to_string(),
+            severity: NotificationSeverity::Warning,
+            title: "Pool low".to_string(),
+            account_id: None,
+            account_email: None,
+            model: Some("gemini-3-pro".to_string()),
+            data: serde_json::Value::Null,
+            timestamp: 1,
+        }
+    }
+
+    /// Receiver that fails with `fail_status` until the `succeed_on`-th call.
+    async fn receiver(fail_status: StatusCode, succeed_on: u32) -> (String, Arc<AtomicU32>) {
+        let calls = Arc::new(AtomicU32::new(0));
+        let counter = Arc::clone(&calls);
+        let app = axum::Router::new().route(
+            "/hook",
+            axum::routing::post(move |headers: HeaderMap| {
+                let counter = Arc::clone(&counter);
+                async move {
+                    assert!(headers.get(X_ANTIGRAVITY_SIGNATURE).is_some());
+                    let call = counter.fetch_add(1, Ordering::SeqCst) + 1;
+                    if call >= succeed_on {
+                        StatusCode::OK
+                    } else {
+                        fail_status
+                    }
+                }
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        (format!("http://{}/hook", addr), calls)
+    }
+
+    fn target(url: String) -> WebhookTarget {
+        WebhookTarget {
+            name: "test".to_string(),
+            url,
+            format: WebhookFormat::Generic,
+            enabled: true,
+            events: Vec::new(),
+            secret: Some("s3cret".to_string()),
+            chat_id: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn retries_server_errors_until_delivered() {
+        let (url, calls) = receiver(StatusCode::SERVICE_UNAVAILABLE, 3).await;
+        let http = reqwest::Client::new();
+        let outcome =
+            deliver(&http, &target(url), &notification(), 5, Duration::from_millis(5)).await;
+        assert_eq!(outcome.status, DeliveryStatus::Delivered);
+        assert_eq!(outcome.attempts, 3);
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn client_errors_are_not_retried() {
+        let (url, calls) = receiver(StatusCode::BAD_REQUEST, 10).await;
+        let http = reqwest::Client::new();
+        let outcome =
+            deliver(&http, &target(url), &notification(), 5, Duration::from_millis(5)).await;
+        assert_eq!(outcome.status, DeliveryStatus::Failed);
+        assert_eq!(outcome.http_status, Some(400));
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+}
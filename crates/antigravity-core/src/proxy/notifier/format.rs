@@ -0,0 +1,110 @@
+//! Webhook payload formats and request signing.
+
+use antigravity_types::models::{Notification, NotificationSeverity, WebhookFormat, WebhookTarget};
+use hmac::{Hmac, Mac};
+use serde_json::{json, Value};
+use sha2::Sha256;
+
+fn severity_icon(severity: NotificationSeverity) -> &'static str {
+    match severity {
+        NotificationSeverity::Info => "ℹ️",
+        NotificationSeverity::Warning => "⚠️",
+        NotificationSeverity::Critical => "🚨",
+    }
+}
+
+/// Plain-text message for chat formats: title plus account and model lines.
+fn message_text(notification: &Notification, bold: impl Fn(&str) -> String) -> String {
+    let mut text =
+        format!("{} {}", severity_icon(notification.severity), bold(&notification.title));
+    if let Some(account) = notification.account_email.as_ref().or(notification.account_id.as_ref())
+    {
+        text.push_str(&format!("\nAccount: {}", account));
+    }
+    if let Some(model) = &notification.model {
+        text.push_str(&format!("\nModel: {}", model));
+    }
+    text.push_str(&format!("\nEvent: {}", notification.kind));
+    text
+}
+
+/// Request body for `target`.
+pub(super) fn render(target: &WebhookTarget, notification: &Notification) -> Value {
+    match target.format {
+        WebhookFormat::Generic => json!(notification),
+        WebhookFormat::Slack => {
+            json!({ "text": message_text(notification, |t| format!("*{}*", t)) })
+        },
+        WebhookFormat::Telegram => json!({
+            "chat_id": target.chat_id,
+            "text": message_text(notification, str::to_string),
+            "disable_web_page_preview": true,
+        }),
+    }
+}
+
+/// `sha256=<hex>` HMAC of `"{timestamp}.{body}"`.
+pub(super) fn sign(secret: &str, timestamp: i64, body: &[u8]) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes())
+        .unwrap_or_else(|_| unreachable!("HMAC accepts any key length"));
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(body);
+    let digest = mac.finalize().into_bytes();
+    let hex: String = digest.iter().map(|b| format!("{:02x}", b)).collect();
+    format!("sha256={}", hex)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn target(format: WebhookFormat) -> WebhookTarget {
+        WebhookTarget {
+            name: "t".to_string(),
+            url: "https://example.com/hook".to_string(),
+            format,
+            enabled: true,
+            events: Vec::new(),
+            secret: None,
+            chat_id: Some("42".to_string()),
+        }
+    }
+
+    fn notification() -> Notification {
+        Notification {
+            kind: "account_disabled".to_string(),
+            severity: NotificationSeverity::Critical,
+            title: "Account disabled".to_string(),
+            account_id: Some("acc-1".to_string()),
+            account_email: Some("a@example.com".to_string()),
+            model: None,
+            data: json!({ "reason": "invalid_grant" }),
+            timestamp: 1,
+        }
+    }
+
+    #[test]
+    fn renders_each_format() {
+        let generic = render(&target(WebhookFormat::Generic), &notification());
+        assert_eq!(generic["kind"], "account_disabled");
+        assert_eq!(generic["data"]["reason"], "invalid_grant");
+
+        let slack = render(&target(WebhookFormat::Slack), &notification());
+        let text = slack["text"].as_str().unwrap();
+        assert!(text.contains("*Account disabled*"));
+        assert!(text.contains("Account: a@example.com"));
+
+        let telegram = render(&target(WebhookFormat::Telegram), &notification());
+        assert_eq!(telegram["chat_id"], "42");
+        assert!(telegram["text"].as_str().unwrap().contains("Event: account_disabled"));
+    }
+
+    #[test]
+    fn signature_is_hmac_sha256_of_timestamp_and_body() {
+        assert_eq!(
+            sign("secret", 1_700_000_000, br#"{"a":1}"#),
+            "sha256=49f24e537407743fa4a0242bb63b94b9a47ee99cbbe071ccd8a22550ae411686"
+        );
+    }
+}
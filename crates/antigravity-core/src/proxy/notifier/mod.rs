@@ -0,0 +1,171 @@
+//! Webhook notifier for account lifecycle and pool-level events.
+//!
+//! Notifications come from three sources (see `sources`): the live monitor event bus,
+//! the `account_events` table and a periodic pool availability check. Each one is
+//! rendered per target format, optionally HMAC-signed, delivered with retries, and
+//! recorded in a bounded in-memory delivery log.
+
+mod delivery;
+mod format;
+mod sources;
+
+use crate::proxy::cluster::{Cluster, ClusterSlot};
+use crate::proxy::providers::registry::glob_match;
+use antigravity_types::models::{Notification, NotifierConfig, WebhookDelivery, WebhookTarget};
+use dashmap::DashSet;
+use parking_lot::{Mutex, RwLock};
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+const DELIVERY_LOG_SIZE: usize = 200;
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(10);
+const BASE_BACKOFF: Duration = Duration::from_secs(1);
+
+pub struct Notifier {
+    config: RwLock<NotifierConfig>,
+    http: reqwest::Client,
+    deliveries: Mutex<VecDeque<WebhookDelivery>>,
+    next_id: AtomicU64,
+    /// Models currently below their pool alert threshold
+    pool_low: DashSet<String>,
+    /// Set once `account_events` are being polled; lifecycle events then come from there
+    watching_account_events: AtomicBool,
+    /// In a cluster only the leader sends account and pool notifications
+    cluster: ClusterSlot,
+}
+
+/// Whether `target` subscribes to `kind` (exact name or glob; no filters = everything).
+fn target_accepts(target: &WebhookTarget, kind: &str) -> bool {
+    target.enabled
+        && (target.events.is_empty()
+            || target.events.iter().any(|pattern| pattern == kind || glob_match(pattern, kind)))
+}
+
+impl Notifier {
+    pub fn new(config: NotifierConfig) -> Self {
+        let http = reqwest::Client::builder()
+            .timeout(WEBHOOK_TIMEOUT)
+            .build()
+            .unwrap_or_else(|_| reqwest::Client::new());
+        Self {
+            config: RwLock::new(config),
+            http,
+            deliveries: Mutex::new(VecDeque::new()),
+            next_id: AtomicU64::new(1),
+            pool_low: DashSet::new(),
+            watching_account_events: AtomicBool::new(false),
+            cluster: ClusterSlot::default(),
+        }
+    }
+
+    pub fn config(&self) -> NotifierConfig {
+        self.config.read().clone()
+    }
+
+    pub fn set_config(&self, config: NotifierConfig) {
+        // Rules may have been removed; forget their state so re-adding them alerts again
+        self.pool_low.retain(|model| config.pool_alerts.iter().any(|r| &r.model == model));
+        *self.config.write() = config;
+    }
+
+    pub fn set_cluster(&self, cluster: Arc<Cluster>) {
+        self.cluster.set(cluster);
+    }
+
+    /// Deliver `notification` in the background to every target that accepts it.
+    pub fn notify(self: &Arc<Self>, notification: Notification) {
+        let targets: Vec<WebhookTarget> = self
+            .config
+            .read()
+            .targets
+            .iter()
+            .filter(|t| target_accepts(t, &notification.kind))
+            .cloned()
+            .collect();
+        if targets.is_empty() {
+            return;
+        }
+        let notification = Arc::new(notification);
+        for target in targets {
+            let notifier = Arc::clone(self);
+            let notification = Arc::clone(&notification);
+            tokio::spawn(async move {
+                notifier.deliver_to(&target, &notification).await;
+            });
+        }
+    }
+
+    /// Deliver `notification` to `target` (ignoring its filters), returning the log entry.
+    pub async fn deliver_to(
+        &self,
+        target: &WebhookTarget,
+        notification: &Notification,
+    ) -> WebhookDelivery {
+        let max_attempts = self.config.read().max_attempts;
+        let outcome =
+            delivery::deliver(&self.http, target, notification, max_attempts, BASE_BACKOFF).await;
+        let entry = WebhookDelivery {
+            id: self.next_id.fetch_add(1, Ordering::Relaxed),
+            target: target.name.clone(),
+            kind: notification.kind.clone(),
+            title: notification.title.clone(),
+            status: outcome.status,
+            attempts: outcome.attempts,
+            http_status: outcome.http_status,
+            error: outcome.error,
+            timestamp: chrono::Utc::now().timestamp_millis(),
+        };
+        if entry.error.is_some() {
+            tracing::warn!(
+                "[Notifier] '{}' not delivered to '{}' after {} attempt(s): {}",
+                entry.kind,
+                entry.target,
+                entry.attempts,
+                entry.error.as_deref().unwrap_or_default()
+            );
+        }
+
+        let mut log = self.deliveries.lock();
+        if log.len() >= DELIVERY_LOG_SIZE {
+            log.pop_front();
+        }
+        log.push_back(entry.clone());
+        entry
+    }
+
+    /// Most recent deliveries, newest first.
+    pub fn deliveries(&self, limit: usize) -> Vec<WebhookDelivery> {
+        self.deliveries.lock().iter().rev().take(limit).cloned().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn target(events: &[&str]) -> WebhookTarget {
+        WebhookTarget {
+            name: "t".to_string(),
+            url: "http://127.0.0.1:9/hook".to_string(),
+            format: Default::default(),
+            enabled: true,
+            events: events.iter().map(|e| e.to_string()).collect(),
+            secret: None,
+            chat_id: None,
+        }
+    }
+
+    #[test]
+    fn event_filters() {
+        assert!(target_accepts(&target(&[]), "account_disabled"));
+        assert!(target_accepts(&target(&["account_disabled"]), "account_disabled"));
+        assert!(target_accepts(&target(&["pool_*"]), "pool_low"));
+        assert!(!target_accepts(&target(&["pool_*"]), "rate_limited"));
+
+        let mut disabled = target(&[]);
+        disabled.enabled = false;
+        assert!(!target_accepts(&disabled, "pool_low"));
+    }
+}
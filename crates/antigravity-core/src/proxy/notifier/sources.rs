@@ -0,0 +1,282 @@
+//! Event sources feeding the notifier.
+
+use super::Notifier;
+use crate::modules::repository::{AccountEvent, AccountEventType, AccountRepository};
+use crate::proxy::token_manager::TokenManager;
+use antigravity_types::models::{
+    AccountState, AccountStateChange, BudgetAction, BudgetAlert, MonitorEvent, MonitorEventPayload,
+    Notification, NotificationSeverity,
+};
+use serde_json::json;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::broadcast;
+
+const ACCOUNT_EVENT_POLL_INTERVAL: Duration = Duration::from_secs(5);
+const ACCOUNT_EVENT_BATCH: i64 = 100;
+const POOL_CHECK_INTERVAL: Duration = Duration::from_secs(30);
+
+fn now_ms() -> i64 {
+    chrono::Utc::now().timestamp_millis()
+}
+
+fn account_state_notification(change: &AccountStateChange) -> Notification {
+    let (kind, severity, title) = match change.state {
+        AccountState::RateLimited => {
+            ("rate_limited", NotificationSeverity::Warning, "Account rate limited")
+        },
+        AccountState::CircuitOpened => {
+            ("circuit_opened", NotificationSeverity::Critical, "Circuit breaker tripped")
+        },
+        AccountState::CircuitClosed => {
+            ("circuit_closed", NotificationSeverity::Info, "Circuit breaker closed")
+        },
+        AccountState::Disabled => {
+            ("account_disabled", NotificationSeverity::Critical, "Account disabled")
+        },
+    };
+    Notification {
+        kind: kind.to_string(),
+        severity,
+        title: title.to_string(),
+        account_id: Some(change.account_id.clone()),
+        account_email: None,
+        model: change.model.clone(),
+        data: json!({ "reason": change.reason, "retry_after_sec": change.retry_after_sec }),
+        timestamp: now_ms(),
+    }
+}
+
+fn budget_notification(alert: &BudgetAlert) -> Notification {
+    let severity = match alert.action {
+        BudgetAction::Block => NotificationSeverity::Critical,
+        BudgetAction::Warn => NotificationSeverity::Warning,
+    };
+    Notification {
+        kind: "budget_alert".to_string(),
+        severity,
+        title: format!("Budget '{}' reached its limit", alert.budget),
+        account_id: None,
+        account_email: None,
+        model: None,
+        data: json!(alert),
+        timestamp: now_ms(),
+    }
+}
+
+fn account_event_notification(event: &AccountEvent, email: Option<String>) -> Notification {
+    let (severity, title) = match event.event_type {
+        AccountEventType::Disabled => (NotificationSeverity::Critical, "Account disabled"),
+        AccountEventType::PhoneVerificationRequired => {
+            (NotificationSeverity::Critical, "Phone verification required")
+        },
+        AccountEventType::RateLimited => (NotificationSeverity::Warning, "Account rate limited"),
+        AccountEventType::Created => (NotificationSeverity::Info, "Account added"),
+        AccountEventType::Updated => (NotificationSeverity::Info, "Account updated"),
+        AccountEventType::Deleted => (NotificationSeverity::Info, "Account deleted"),
+        AccountEventType::Enabled => (NotificationSeverity::Info, "Account enabled"),
+        AccountEventType::TokenRefreshed => (NotificationSeverity::Info, "Token refreshed"),
+        AccountEventType::QuotaUpdated => (NotificationSeverity::Info, "Quota updated"),
+        AccountEventType::ModelProtected => (NotificationSeverity::Info, "Model protected"),
+        AccountEventType::ModelUnprotected => (NotificationSeverity::Info, "Model unprotected"),
+    };
+    Notification {
+        kind: event.event_type.as_str().to_string(),
+        severity,
+        title: title.to_string(),
+        account_id: Some(event.account_id.clone()),
+        account_email: email,
+        model: None,
+        data: event.metadata.clone(),
+        timestamp: event.created_at.timestamp_millis(),
+    }
+}
+
+impl Notifier {
+    fn account_email(token_manager: &TokenManager, account_id: &str) -> Option<String> {
+        token_manager.tokens_ref().get(account_id).map(|t| t.email.clone())
+    }
+
+    /// Forward account state changes and budget alerts from the live monitor bus.
+    pub fn start_event_forwarding(
+        self: &Arc<Self>,
+        mut receiver: broadcast::Receiver<MonitorEvent>,
+        token_manager: Arc<TokenManager>,
+    ) {
+        let notifier = Arc::clone(self);
+        tokio::spawn(async move {
+            loop {
+                let event = match receiver.recv().await {
+                    Ok(event) => event,
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        tracing::warn!("[Notifier] Skipped {} monitor events", skipped);
+                        continue;
+                    },
+                    Err(broadcast::error::RecvError::Closed) => break,
+                };
+                let notification = match &event.payload {
+                    MonitorEventPayload::AccountState(change) => {
+                        // Disabling is also logged to account_events; don't notify twice
+                        if change.state == AccountState::Disabled
+                            && notifier.watching_account_events.load(Ordering::Relaxed)
+                        {
+                            continue;
+                        }
+                        let mut notification = account_state_notification(change);
+                        notification.account_email =
+                            Self::account_email(&token_manager, &change.account_id);
+                        notification
+                    },
+                    MonitorEventPayload::BudgetAlert(alert) => budget_notification(alert),
+                    MonitorEventPayload::RequestLog(_)
+                    | MonitorEventPayload::QuotaUpdated { .. } => {
+                        continue;
+                    },
+                };
+                notifier.notify(notification);
+            }
+        });
+    }
+
+    /// Poll `account_events` for new lifecycle events.
+    pub fn start_account_event_watch(
+        self: &Arc<Self>,
+        repository: Arc<dyn AccountRepository>,
+        token_manager: Arc<TokenManager>,
+    ) {
+        self.watching_account_events.store(true, Ordering::Relaxed);
+        let notifier = Arc::clone(self);
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(ACCOUNT_EVENT_POLL_INTERVAL);
+            // Start from the current head; history is not replayed on restart
+            let mut cursor = loop {
+                interval.tick().await;
+                match repository.latest_event_id().await {
+                    Ok(id) => break id,
+                    Err(e) => {
+                        tracing::warn!("[Notifier] Failed to read account event cursor: {}", e)
+                    },
+                }
+            };
+            loop {
+                interval.tick().await;
+                let events = match repository.list_events_after(cursor, ACCOUNT_EVENT_BATCH).await {
+                    Ok(events) => events,
+                    Err(e) => {
+                        tracing::debug!("[Notifier] Failed to poll account events: {}", e);
+                        continue;
+                    },
+                };
+                // Followers keep the cursor current so a new leader doesn't replay history
+                let leader = notifier.cluster.is_leader();
+                for (id, event) in events {
+                    cursor = id;
+                    if !leader {
+                        continue;
+                    }
+                    let email = Self::account_email(&token_manager, &event.account_id);
+                    notifier.notify(account_event_notification(&event, email));
+                }
+            }
+        });
+    }
+
+    /// Compare pool availability against the alert rules, returning edge-triggered
+    /// `pool_low` / `pool_recovered` notifications.
+    fn check_pools(&self, available: impl Fn(&str) -> usize) -> Vec<Notification> {
+        let rules = self.config.read().pool_alerts.clone();
+        let mut notifications = Vec::new();
+        for rule in rules {
+            let count = available(&rule.model);
+            let low = count < rule.min_available as usize;
+            let (kind, severity, title) = if low && self.pool_low.insert(rule.model.clone()) {
+                (
+                    "pool_low",
+                    NotificationSeverity::Warning,
+                    format!(
+                        "Only {} account(s) available for {} (minimum {})",
+                        count, rule.model, rule.min_available
+                    ),
+                )
+            } else if !low && self.pool_low.remove(&rule.model).is_some() {
+                (
+                    "pool_recovered",
+                    NotificationSeverity::Info,
+                    format!("{} account(s) available for {} again", count, rule.model),
+                )
+            } else {
+                continue;
+            };
+            notifications.push(Notification {
+                kind: kind.to_string(),
+                severity,
+                title,
+                account_id: None,
+                account_email: None,
+                model: Some(rule.model.clone()),
+                data: json!({ "available": count, "min_available": rule.min_available }),
+                timestamp: now_ms(),
+            });
+        }
+        notifications
+    }
+
+    /// Periodically check pool availability against the configured alert rules.
+    pub fn start_pool_watch(self: &Arc<Self>, token_manager: Arc<TokenManager>) {
+        let notifier = Arc::clone(self);
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(POOL_CHECK_INTERVAL);
+            loop {
+                interval.tick().await;
+                let notifications =
+                    notifier.check_pools(|model| token_manager.available_account_count(model));
+                if notifier.cluster.is_leader() {
+                    for notification in notifications {
+                        notifier.notify(notification);
+                    }
+                }
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use antigravity_types::models::{NotifierConfig, PoolAlertRule};
+
+    #[test]
+    fn pool_alerts_fire_on_transitions_only() {
+        let notifier = Notifier::new(NotifierConfig {
+            pool_alerts: vec![PoolAlertRule {
+                model: "gemini-3-pro".to_string(),
+                min_available: 2,
+            }],
+            ..Default::default()
+        });
+
+        let kinds = |available: usize| -> Vec<String> {
+            notifier.check_pools(|_| available).into_iter().map(|n| n.kind).collect()
+        };
+        assert!(kinds(3).is_empty());
+        assert_eq!(kinds(1), ["pool_low"]);
+        assert!(kinds(0).is_empty());
+        assert_eq!(kinds(2), ["pool_recovered"]);
+        assert!(kinds(5).is_empty());
+    }
+
+    #[test]
+    fn circuit_open_is_critical() {
+        let notification = account_state_notification(&AccountStateChange {
+            account_id: "acc-1".to_string(),
+            state: AccountState::CircuitOpened,
+            model: None,
+            reason: Some("5 failures".to_string()),
+            retry_after_sec: Some(60),
+        });
+        assert_eq!(notification.kind, "circuit_opened");
+        assert_eq!(notification.severity, NotificationSeverity::Critical);
+        assert_eq!(notification.data["retry_after_sec"], 60);
+    }
+}
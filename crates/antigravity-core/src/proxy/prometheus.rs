@@ -14,6 +14,13 @@
 //! - `antigravity_peek_retries_total{reason}` - Counter of peek phase retries
 //! - `antigravity_peek_heartbeats_total` - Counter of heartbeats during peek
 //! - `antigravity_stream_graceful_finish_total{path}` - Counter of stream errors converted to graceful completions
+//! - `antigravity_admission_queue_depth{priority}` - Gauge of requests waiting for an account
+//! - `antigravity_admission_queued_total{priority}` - Counter of requests that entered the queue
+//! - `antigravity_admission_outcomes_total{priority,outcome}` - Counter of how queued requests left
+//! - `antigravity_admission_wait_seconds{priority}` - Histogram of queue wait before admission
+//! - `antigravity_admission_keepalives_total` - Counter of SSE pings sent to queued streaming clients
+//! - `antigravity_hedges_total{reason}` - Counter of second attempts fired on another account
+//! - `antigravity_hedge_outcomes_total{reason,outcome}` - Counter of how hedged requests ended
 
 // Prometheus metrics: counter/gauge operations and file size calculations.
 // All values are bounded by system limits (file sizes, counters).
@@ -123,6 +130,36 @@ pub fn init_metrics() -> PrometheusHandle {
             "Stream errors that aborted the connection instead of graceful finish"
         );
 
+        describe_gauge!(
+            "antigravity_admission_queue_depth",
+            "Requests waiting in the admission queue by priority class"
+        );
+        describe_counter!(
+            "antigravity_admission_queued_total",
+            "Requests that entered the admission queue by priority class"
+        );
+        describe_counter!(
+            "antigravity_admission_outcomes_total",
+            "Queued requests by outcome (admitted, rejected_full, deadline, timeout, failed, cancelled)"
+        );
+        describe_histogram!(
+            "antigravity_admission_wait_seconds",
+            "Time queued requests waited before getting an account"
+        );
+        describe_counter!(
+            "antigravity_admission_keepalives_total",
+            "SSE keepalive comments sent to queued streaming clients"
+        );
+
+        describe_counter!(
+            "antigravity_hedges_total",
+            "Second attempts fired on another account by reason (usage, slow_first_byte)"
+        );
+        describe_counter!(
+            "antigravity_hedge_outcomes_total",
+            "Hedged requests by outcome (primary_won, secondary_won, both_failed, no_account)"
+        );
+
         crate::proxy::signature_metrics::init_signature_metrics();
 
         handle
@@ -240,6 +277,40 @@ pub(crate) fn record_stream_abort(path: &str) {
     counter!("antigravity_stream_abort_total", &labels).increment(1);
 }
 
+pub(crate) fn update_admission_depth(priority: &str, depth: usize) {
+    let labels = [("priority", priority.to_string())];
+    gauge!("antigravity_admission_queue_depth", &labels).set(depth as f64);
+}
+
+pub(crate) fn record_admission_queued(priority: &str) {
+    let labels = [("priority", priority.to_string())];
+    counter!("antigravity_admission_queued_total", &labels).increment(1);
+}
+
+pub(crate) fn record_admission_outcome(priority: &str, outcome: &str) {
+    let labels = [("priority", priority.to_string()), ("outcome", outcome.to_string())];
+    counter!("antigravity_admission_outcomes_total", &labels).increment(1);
+}
+
+pub(crate) fn record_admission_wait(priority: &str, wait: std::time::Duration) {
+    let labels = [("priority", priority.to_string())];
+    histogram!("antigravity_admission_wait_seconds", &labels).record(wait.as_secs_f64());
+}
+
+pub(crate) fn record_admission_keepalive() {
+    counter!("antigravity_admission_keepalives_total").increment(1);
+}
+
+pub(crate) fn record_hedge_fired(reason: &str) {
+    let labels = [("reason", reason.to_string())];
+    counter!("antigravity_hedges_total", &labels).increment(1);
+}
+
+pub(crate) fn record_hedge_outcome(reason: &str, outcome: &str) {
+    let labels = [("reason", reason.to_string()), ("outcome", outcome.to_string())];
+    counter!("antigravity_hedge_outcomes_total", &labels).increment(1);
+}
+
 /// Render all metrics in Prometheus text format.
 pub fn render_metrics() -> String {
     update_uptime_gauge();
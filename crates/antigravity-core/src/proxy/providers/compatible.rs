@@ -0,0 +1,250 @@
+//! Forwarding to OpenAI/Anthropic-compatible providers.
+//!
+//! The request body is passed through with only `model` rewritten. Routes are tried
+//! in order; connection errors, 429 and 5xx fall through to the next one and count
+//! against that provider's circuit breaker.
+
+use axum::{
+    body::Body,
+    http::{header, HeaderMap, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+};
+use bytes::Bytes;
+use futures::StreamExt;
+use serde_json::{json, Value};
+use tracing::{debug, info, warn};
+
+use super::registry::{resolve_provider_route, routes_for_protocol, ProviderRoute};
+use super::zai_anthropic::{copy_passthrough_headers, join_base_url};
+use crate::proxy::common::client_builder::build_http_client;
+use crate::proxy::common::header_constants::{X_ACCOUNT_EMAIL, X_MAPPED_MODEL, X_MAPPING_REASON};
+use crate::proxy::server::AppState;
+use antigravity_types::models::ProviderProtocol;
+
+const DEFAULT_ANTHROPIC_VERSION: &str = "2023-06-01";
+
+/// Circuit breaker key for a provider; shares the breaker with pool accounts.
+pub fn breaker_id(name: &str) -> String {
+    format!("provider:{}", name)
+}
+
+/// Local servers (vLLM, Ollama) are reached directly, bypassing the upstream proxy.
+fn is_loopback(base_url: &str) -> bool {
+    url::Url::parse(base_url)
+        .ok()
+        .and_then(|u| u.host_str().map(str::to_string))
+        .is_some_and(|h| h == "localhost" || h == "[::1]" || h.starts_with("127."))
+}
+
+fn build_request_headers(route: &ProviderRoute, incoming: &HeaderMap) -> HeaderMap {
+    let mut headers = copy_passthrough_headers(incoming);
+    let api_key = route.provider.api_key.trim();
+    match route.provider.protocol {
+        ProviderProtocol::Openai => {
+            headers.remove("anthropic-version");
+            if !api_key.is_empty() {
+                if let Ok(v) = HeaderValue::from_str(&format!("Bearer {}", api_key)) {
+                    headers.insert(header::AUTHORIZATION, v);
+                }
+            }
+        },
+        ProviderProtocol::Anthropic => {
+            if !api_key.is_empty() {
+                if let Ok(v) = HeaderValue::from_str(api_key) {
+                    headers.insert("x-api-key", v);
+                }
+            }
+            headers
+                .entry("anthropic-version")
+                .or_insert(HeaderValue::from_static(DEFAULT_ANTHROPIC_VERSION));
+        },
+    }
+    headers.insert(header::CONTENT_TYPE, HeaderValue::from_static("application/json"));
+    headers
+}
+
+/// Path appended to the provider's versioned `base_url`.
+fn endpoint_path(protocol: ProviderProtocol) -> &'static str {
+    match protocol {
+        ProviderProtocol::Openai => "/chat/completions",
+        ProviderProtocol::Anthropic => "/messages",
+    }
+}
+
+/// Statuses that say the provider cannot serve requests right now (bad credentials,
+/// rate limits, outages); they count against its circuit breaker.
+fn is_provider_failure(status: u16) -> bool {
+    matches!(status, 401 | 403 | 429) || status >= 500
+}
+
+/// Serve the request from a configured provider when its model routes to one.
+///
+/// Returns `None` for models the Google pool should handle. Models that only route
+/// to providers speaking another protocol are rejected, since the body is forwarded
+/// unconverted.
+pub async fn try_forward_to_provider(
+    state: &AppState,
+    headers: &HeaderMap,
+    body: &Value,
+    protocol: ProviderProtocol,
+    endpoint: &str,
+) -> Option<Response> {
+    let model = body.get("model").and_then(Value::as_str)?;
+    let (routes, reason) = {
+        let providers = state.providers.read().await;
+        let mapping = state.custom_mapping.read().await;
+        resolve_provider_route(&providers, &mapping, model)?
+    };
+
+    let routes = routes_for_protocol(routes, protocol);
+    if routes.is_empty() {
+        return Some(
+            (
+                StatusCode::BAD_REQUEST,
+                format!(
+                    "Model '{}' is served by a provider that does not speak the {} protocol \
+                     used by {}",
+                    model, protocol, endpoint
+                ),
+            )
+                .into_response(),
+        );
+    }
+    Some(forward_to_provider(state, &routes, headers, body.clone(), &reason).await)
+}
+
+/// Forward `body` to the first healthy route; `reason` goes into `X-Mapping-Reason`.
+pub async fn forward_to_provider(
+    state: &AppState,
+    routes: &[ProviderRoute],
+    incoming_headers: &HeaderMap,
+    mut body: Value,
+    reason: &str,
+) -> Response {
+    let upstream_proxy = state.upstream_proxy.read().await.clone();
+    let mut last_error = String::from("No provider available");
+
+    for route in routes {
+        let name = &route.provider.name;
+        let breaker = breaker_id(name);
+        if let Some(open) = state.circuit_breaker.check(&breaker) {
+            debug!("[Provider] Skipping {}: {}", name, open);
+            last_error = format!("Provider '{}' unavailable: {}", name, open);
+            continue;
+        }
+
+        let url =
+            match join_base_url(&route.provider.base_url, endpoint_path(route.provider.protocol)) {
+                Ok(u) => u,
+                Err(e) => return (StatusCode::BAD_REQUEST, e).into_response(),
+            };
+        body["model"] = Value::String(route.model.clone());
+        crate::proxy::capture::record_upstream_request(&body);
+        let body_bytes = match serde_json::to_vec(&body) {
+            Ok(bytes) => bytes,
+            Err(e) => {
+                return (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    format!("Request serialization failed: {}", e),
+                )
+                    .into_response()
+            },
+        };
+
+        let proxy = (!is_loopback(&route.provider.base_url)).then_some(&upstream_proxy);
+        let client = match build_http_client(proxy, state.request_timeout) {
+            Ok(c) => c,
+            Err(e) => {
+                return (StatusCode::BAD_GATEWAY, format!("Proxy client error: {}", e))
+                    .into_response()
+            },
+        };
+
+        info!("[Provider] {} -> {} ({})", route.qualified_model(), url, reason);
+        let resp = match client
+            .post(&url)
+            .headers(build_request_headers(route, incoming_headers))
+            .body(body_bytes)
+            .send()
+            .await
+        {
+            Ok(r) => r,
+            Err(e) => {
+                warn!("[Provider] {} request failed: {}", name, e);
+                state.circuit_breaker.record_failure(&breaker, "connection error");
+                last_error = format!("Provider '{}' request failed: {}", name, e);
+                continue;
+            },
+        };
+
+        let status = resp.status().as_u16();
+        if is_provider_failure(status) {
+            warn!("[Provider] {} returned {}, trying next route", name, status);
+            state.circuit_breaker.record_failure(&breaker, &format!("HTTP {}", status));
+            last_error = format!("Provider '{}' returned {}", name, status);
+            continue;
+        }
+        state.circuit_breaker.record_success(&breaker);
+
+        let mut out = Response::builder()
+            .status(StatusCode::from_u16(status).unwrap_or(StatusCode::BAD_GATEWAY))
+            .header(X_ACCOUNT_EMAIL, breaker.as_str())
+            .header(X_MAPPED_MODEL, route.qualified_model())
+            .header(X_MAPPING_REASON, reason);
+        if let Some(ct) = resp.headers().get(header::CONTENT_TYPE) {
+            out = out.header(header::CONTENT_TYPE, ct.clone());
+        }
+        let stream = resp.bytes_stream().map(|chunk| match chunk {
+            Ok(b) => Ok::<Bytes, std::io::Error>(b),
+            Err(e) => Err(std::io::Error::other(e)),
+        });
+        return out.body(Body::from_stream(stream)).unwrap_or_else(|_| {
+            (StatusCode::INTERNAL_SERVER_ERROR, "Failed to build response").into_response()
+        });
+    }
+
+    let mapped = routes.first().map(ProviderRoute::qualified_model).unwrap_or_default();
+    (
+        StatusCode::BAD_GATEWAY,
+        [(X_MAPPED_MODEL, mapped), (X_MAPPING_REASON, reason.to_string())],
+        axum::Json(json!({
+            "error": { "message": last_error, "type": "upstream_error", "code": 502 }
+        })),
+    )
+        .into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn loopback_detection() {
+        assert!(is_loopback("http://localhost:11434/v1"));
+        assert!(is_loopback("http://127.0.0.1:8000/v1"));
+        assert!(!is_loopback("https://api.together.xyz/v1"));
+    }
+
+    #[test]
+    fn endpoint_paths_follow_versioned_base_url() {
+        let url = |base: &str, protocol| join_base_url(base, endpoint_path(protocol)).unwrap();
+        assert_eq!(
+            url("http://localhost:11434/v1", ProviderProtocol::Openai),
+            "http://localhost:11434/v1/chat/completions"
+        );
+        assert_eq!(
+            url("https://api.anthropic.com/v1/", ProviderProtocol::Anthropic),
+            "https://api.anthropic.com/v1/messages"
+        );
+    }
+
+    #[test]
+    fn auth_rate_limit_and_server_errors_trip_the_breaker() {
+        for status in [401, 403, 429, 500, 503] {
+            assert!(is_provider_failure(status), "{status}");
+        }
+        for status in [200, 400, 404, 422] {
+            assert!(!is_provider_failure(status), "{status}");
+        }
+    }
+}
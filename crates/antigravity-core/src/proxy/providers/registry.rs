@@ -0,0 +1,165 @@
+//! Provider selection for OpenAI/Anthropic-compatible upstreams.
+//!
+//! A request targets a provider explicitly as `name:model` (directly or through a
+//! custom mapping resolved by `resolve_model_route`), or implicitly when the model
+//! matches one of the provider's globs.
+
+use antigravity_types::models::{ProviderConfig, ProviderProtocol};
+use std::collections::HashMap;
+
+use crate::proxy::common::resolve_model_route;
+
+/// A provider and the model name to send it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProviderRoute {
+    pub provider: ProviderConfig,
+    pub model: String,
+}
+
+impl ProviderRoute {
+    /// `name:model`, as reported in the mapped-model header.
+    pub fn qualified_model(&self) -> String {
+        format!("{}:{}", self.provider.name, self.model)
+    }
+}
+
+/// Providers able to serve `model`, best first.
+///
+/// An explicit `name:` prefix selects exactly that provider. Otherwise every enabled
+/// provider with a matching glob is returned, by descending priority.
+pub fn select_providers(providers: &[ProviderConfig], model: &str) -> Vec<ProviderRoute> {
+    if let Some((name, rest)) = model.split_once(':') {
+        if let Some(provider) = providers.iter().find(|p| p.enabled && p.name == name) {
+            return vec![ProviderRoute { provider: provider.clone(), model: rest.to_string() }];
+        }
+    }
+
+    let mut matches: Vec<&ProviderConfig> = providers
+        .iter()
+        .filter(|p| p.enabled && p.models.iter().any(|glob| glob_match(glob, model)))
+        .collect();
+    // Stable sort keeps config order among equal priorities
+    matches.sort_by_key(|p| std::cmp::Reverse(p.priority));
+    matches
+        .into_iter()
+        .map(|p| ProviderRoute { provider: p.clone(), model: model.to_string() })
+        .collect()
+}
+
+/// Provider routes for a requested model, with the reason recorded in the monitor log.
+///
+/// Custom mappings are applied first so `alias -> name:model` works; the original
+/// name is checked when the mapping does not lead to a provider.
+pub fn resolve_provider_route(
+    providers: &[ProviderConfig],
+    custom_mapping: &HashMap<String, String>,
+    model: &str,
+) -> Option<(Vec<ProviderRoute>, String)> {
+    if providers.iter().all(|p| !p.enabled) {
+        return None;
+    }
+
+    if let Ok((mapped, reason)) = resolve_model_route(model, custom_mapping) {
+        if mapped != model {
+            let routes = select_providers(providers, &mapped);
+            if !routes.is_empty() {
+                return Some((routes, format!("{}; provider", reason)));
+            }
+        }
+    }
+
+    let routes = select_providers(providers, model);
+    (!routes.is_empty()).then(|| (routes, "provider".to_string()))
+}
+
+/// Keep only routes speaking `protocol`.
+pub fn routes_for_protocol(
+    routes: Vec<ProviderRoute>,
+    protocol: ProviderProtocol,
+) -> Vec<ProviderRoute> {
+    routes.into_iter().filter(|r| r.provider.protocol == protocol).collect()
+}
+
+/// Glob match supporting `*` (any run) and `?` (one character).
+pub(crate) fn glob_match(pattern: &str, text: &str) -> bool {
+    let p: Vec<char> = pattern.chars().collect();
+    let t: Vec<char> = text.chars().collect();
+    let (mut pi, mut ti) = (0, 0);
+    let mut star: Option<(usize, usize)> = None;
+
+    while ti < t.len() {
+        if pi < p.len() && (p[pi] == '?' || p[pi] == t[ti]) {
+            pi += 1;
+            ti += 1;
+        } else if pi < p.len() && p[pi] == '*' {
+            star = Some((pi, ti));
+            pi += 1;
+        } else if let Some((sp, st)) = star {
+            pi = sp + 1;
+            ti = st + 1;
+            star = Some((sp, st + 1));
+        } else {
+            return false;
+        }
+    }
+    p[pi..].iter().all(|&c| c == '*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn provider(name: &str, models: &[&str], priority: i32) -> ProviderConfig {
+        ProviderConfig {
+            name: name.to_string(),
+            enabled: true,
+            base_url: "http://localhost:8000/v1".to_string(),
+            api_key: String::new(),
+            protocol: ProviderProtocol::Openai,
+            models: models.iter().map(|m| m.to_string()).collect(),
+            priority,
+        }
+    }
+
+    #[test]
+    fn glob_matching() {
+        assert!(glob_match("llama-*", "llama-3.1-8b"));
+        assert!(glob_match("*-instruct", "qwen-7b-instruct"));
+        assert!(glob_match("qwen?", "qwen2"));
+        assert!(!glob_match("llama-*", "gemini-3-flash"));
+        assert!(glob_match("*", "anything"));
+    }
+
+    #[test]
+    fn explicit_prefix_wins_over_globs() {
+        let providers = vec![provider("vllm", &["qwen*"], 0), provider("ollama", &[], 0)];
+        let routes = select_providers(&providers, "ollama:qwen2");
+        assert_eq!(routes.len(), 1);
+        assert_eq!(routes[0].provider.name, "ollama");
+        assert_eq!(routes[0].model, "qwen2");
+        assert_eq!(routes[0].qualified_model(), "ollama:qwen2");
+    }
+
+    #[test]
+    fn globs_are_ordered_by_priority() {
+        let mut disabled = provider("off", &["llama*"], 100);
+        disabled.enabled = false;
+        let providers =
+            vec![provider("low", &["llama*"], 1), provider("high", &["llama*"], 5), disabled];
+        let names: Vec<String> =
+            select_providers(&providers, "llama3").into_iter().map(|r| r.provider.name).collect();
+        assert_eq!(names, vec!["high", "low"]);
+        assert!(select_providers(&providers, "gemini-3-flash").is_empty());
+    }
+
+    #[test]
+    fn custom_mapping_can_target_a_provider() {
+        let providers = vec![provider("vllm", &[], 0)];
+        let mapping = HashMap::from([("gpt-4o".to_string(), "vllm:qwen2.5-72b".to_string())]);
+
+        let (routes, reason) = resolve_provider_route(&providers, &mapping, "gpt-4o").unwrap();
+        assert_eq!(routes[0].model, "qwen2.5-72b");
+        assert_eq!(reason, "exact; provider");
+        assert!(resolve_provider_route(&providers, &mapping, "gemini-3-flash").is_none());
+    }
+}
@@ -10,7 +10,7 @@ use serde_json::Value;
 use crate::proxy::common::client_builder::build_http_client;
 use crate::proxy::server::AppState;
 
-fn map_model_for_zai(original: &str, state: &crate::proxy::ZaiConfig) -> String {
+pub(crate) fn map_model_for_zai(original: &str, state: &crate::proxy::ZaiConfig) -> String {
     let m = original.to_lowercase();
     if let Some(mapped) = state.model_mapping.get(original) {
         return mapped.clone();
@@ -36,13 +36,13 @@ fn map_model_for_zai(original: &str, state: &crate::proxy::ZaiConfig) -> String
     state.models.sonnet.clone()
 }
 
-fn join_base_url(base: &str, path: &str) -> Result<String, String> {
+pub(crate) fn join_base_url(base: &str, path: &str) -> Result<String, String> {
     let base = base.trim_end_matches('/');
     let path = if path.starts_with('/') { path.to_string() } else { format!("/{}", path) };
     Ok(format!("{}{}", base, path))
 }
 
-fn copy_passthrough_headers(incoming: &HeaderMap) -> HeaderMap {
+pub(crate) fn copy_passthrough_headers(incoming: &HeaderMap) -> HeaderMap {
     // Only forward a conservative set of headers to avoid leaking the local proxy key or cookies.
     // Note: `HeaderMap` already stores names as lowercase, so `k.as_str()` is lowercase.
     let mut out = HeaderMap::new();
@@ -127,6 +127,7 @@ pub async fn forward_anthropic_json(
     // [FIX #290] Clean cache_control before sending to Anthropic API
     // This prevents "Extra inputs are not permitted" errors
     deep_remove_cache_control(&mut body);
+    crate::proxy::capture::record_upstream_request(&body);
 
     // [FIX #307] Explicitly serialize body to Vec<u8> to ensure Content-Length is set correctly.
     // This avoids "Transfer-Encoding: chunked" for small bodies which caused connection errors.
@@ -145,7 +145,7 @@ impl RateLimitTracker {
         };
 
         let key = RateLimitKey::from_optional_model(account_id, model.as_deref());
-        self.limits.insert(key, info.clone());
+        self.insert_limit(key, info.clone());
 
         tracing::warn!(
             "account {} [{}] rate limit type: {:?}, reset delay: {} seconds",
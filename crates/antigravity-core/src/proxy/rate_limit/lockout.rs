@@ -47,7 +47,7 @@ impl RateLimitTracker {
             model: None,
         };
 
-        self.limits.insert(key, info);
+        self.insert_limit(key, info);
 
         tracing::debug!(
             "⚡ Account {} adaptive lockout: {}s (attempt #{})",
@@ -79,7 +79,7 @@ impl RateLimitTracker {
         };
 
         let key = RateLimitKey::from_optional_model(account_id, model.as_deref());
-        self.limits.insert(key, info);
+        self.insert_limit(key, info);
 
         if let Some(m) = &model {
             tracing::info!(
@@ -147,7 +147,7 @@ impl RateLimitTracker {
             model: Some(model.to_string()),
         };
 
-        self.limits.insert(key, info);
+        self.insert_limit(key, info);
         tracing::info!(
             "🔒 Account {}:{} locked for {}s ({:?})",
             account_id,
@@ -199,7 +199,7 @@ impl RateLimitTracker {
             model: Some(model.to_string()),
         };
 
-        self.limits.insert(key, info);
+        self.insert_limit(key, info);
 
         tracing::debug!(
             "⚡ {}:{} adaptive lockout: {}s (attempt #{})",
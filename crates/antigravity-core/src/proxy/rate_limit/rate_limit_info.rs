@@ -3,10 +3,12 @@
 //! This module provides types for tracking rate limits per account
 //! and per model, with support for different rate limit reasons.
 
+use serde::{Deserialize, Serialize};
 use std::time::SystemTime;
 
 /// Reason for a rate limit being applied.
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum RateLimitReason {
     /// Daily or monthly quota exhausted.
     QuotaExhausted,
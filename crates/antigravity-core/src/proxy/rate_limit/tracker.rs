@@ -1,17 +1,90 @@
+use antigravity_types::models::{AccountState, AccountStateChange};
 use dashmap::DashMap;
+use std::sync::Arc;
 use std::time::{Duration, SystemTime};
 
 use super::duration_to_secs_ceil;
 use super::rate_limit_info::{RateLimitInfo, RateLimitKey};
+use crate::proxy::cluster::{Cluster, ClusterEvent, ClusterSlot};
+use crate::proxy::monitor::{EventBusSlot, ProxyEventBus};
 
 pub struct RateLimitTracker {
     pub(super) limits: DashMap<RateLimitKey, RateLimitInfo>,
     pub(super) failure_counts: DashMap<RateLimitKey, (u32, SystemTime)>,
+    events: EventBusSlot,
+    cluster: ClusterSlot,
+}
+
+fn unix_ms(time: SystemTime) -> i64 {
+    time.duration_since(SystemTime::UNIX_EPOCH)
+        .map_or(0, |d| i64::try_from(d.as_millis()).unwrap_or(i64::MAX))
 }
 
 impl RateLimitTracker {
     pub fn new() -> Self {
-        Self { limits: DashMap::new(), failure_counts: DashMap::new() }
+        Self {
+            limits: DashMap::new(),
+            failure_counts: DashMap::new(),
+            events: EventBusSlot::default(),
+            cluster: ClusterSlot::default(),
+        }
+    }
+
+    /// Report new lockouts to the live monitor.
+    pub fn set_event_bus(&self, bus: Arc<dyn ProxyEventBus>) {
+        self.events.set(bus);
+    }
+
+    /// Share lockouts with the other instances.
+    pub fn set_cluster(&self, cluster: Arc<Cluster>) {
+        self.cluster.set(cluster);
+    }
+
+    pub(super) fn insert_limit(&self, key: RateLimitKey, info: RateLimitInfo) {
+        self.events.emit_account_state(AccountStateChange {
+            account_id: key.account_id().to_string(),
+            state: AccountState::RateLimited,
+            model: info.model.clone(),
+            reason: Some(format!("{:?}", info.reason)),
+            retry_after_sec: Some(info.retry_after_sec),
+        });
+        self.cluster.publish(ClusterEvent::Lockout {
+            account: key.account_id().to_string(),
+            model: key.model_name().map(str::to_string),
+            reset_at_ms: unix_ms(info.reset_time),
+            retry_after_sec: info.retry_after_sec,
+            reason: info.reason,
+        });
+        self.limits.insert(key, info);
+    }
+
+    /// Remove a lockout, telling the other instances if there was one.
+    fn remove_limit(&self, key: &RateLimitKey) -> bool {
+        let removed = self.limits.remove(key).is_some();
+        if removed {
+            self.cluster.publish(ClusterEvent::LockoutCleared {
+                account: key.account_id().to_string(),
+                model: key.model_name().map(str::to_string),
+            });
+        }
+        removed
+    }
+
+    /// Apply a lockout observed by another instance.
+    pub fn apply_remote_lockout(&self, key: RateLimitKey, info: RateLimitInfo) {
+        if info.reset_time > SystemTime::now() {
+            self.limits.insert(key, info);
+        }
+    }
+
+    /// Apply a lockout cleared by another instance.
+    pub fn apply_remote_clear(&self, key: &RateLimitKey) {
+        self.limits.remove(key);
+    }
+
+    /// Apply a reset of all lockouts made by another instance.
+    pub fn apply_remote_clear_all(&self) {
+        self.limits.clear();
     }
 
     /// Get remaining wait time in seconds for account
@@ -34,7 +107,7 @@ impl RateLimitTracker {
         if self.failure_counts.remove(&key).is_some() {
             tracing::debug!("account {} request success, reset failure count", account_id);
         }
-        self.limits.remove(&key);
+        self.remove_limit(&key);
     }
 
     pub fn get(&self, account_id: &str) -> Option<RateLimitInfo> {
@@ -109,7 +182,7 @@ impl RateLimitTracker {
         if self.failure_counts.remove(&key).is_some() {
             tracing::debug!("{}:{} success, reset failure count", account_id, model);
         }
-        self.limits.remove(&key);
+        self.remove_limit(&key);
     }
 
     /// Get seconds until rate limit reset
@@ -145,13 +218,14 @@ impl RateLimitTracker {
     /// Clear rate limit record for account
     pub fn clear(&self, account_id: &str) -> bool {
         let key = RateLimitKey::account(account_id);
-        self.limits.remove(&key).is_some()
+        self.remove_limit(&key)
     }
 
-    /// Clear all rate limit records (optimistic reset)
+    /// Clear all rate limit records (optimistic reset), on every instance
     pub fn clear_all(&self) {
         let count = self.limits.len();
         self.limits.clear();
+        self.cluster.publish(ClusterEvent::LockoutsCleared);
         tracing::warn!("🔄 Optimistic reset: Cleared all {} rate limit record(s)", count);
     }
 }
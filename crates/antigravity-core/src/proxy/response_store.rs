@@ -0,0 +1,153 @@
+//! Server-side store for `/v1/responses` results.
+//!
+//! Keeps each response together with the conversation that produced it so a follow-up
+//! request can pass `previous_response_id` instead of resending the history.
+//!
+//! A response created with a client key is only visible to that key; the global key sees
+//! every response.
+
+use crate::proxy::mappers::openai::responses::ResponseObject;
+use crate::proxy::mappers::openai::OpenAIMessage;
+use dashmap::mapref::one::Ref;
+use dashmap::DashMap;
+use std::time::{Duration, Instant};
+
+const DEFAULT_CAPACITY: usize = 2000;
+const DEFAULT_TTL: Duration = Duration::from_secs(6 * 3600);
+
+struct StoredResponse {
+    response: ResponseObject,
+    /// Conversation including this response's output (system instructions excluded).
+    conversation: Vec<OpenAIMessage>,
+    /// Client key that created the response; None for the global key
+    owner: Option<String>,
+    stored_at: Instant,
+}
+
+/// Bounded in-memory response store with expiry.
+pub struct ResponseStore {
+    entries: DashMap<String, StoredResponse>,
+    capacity: usize,
+    ttl: Duration,
+}
+
+impl ResponseStore {
+    pub fn new() -> Self {
+        Self::with_limits(DEFAULT_CAPACITY, DEFAULT_TTL)
+    }
+
+    pub fn with_limits(capacity: usize, ttl: Duration) -> Self {
+        Self { entries: DashMap::new(), capacity: capacity.max(1), ttl }
+    }
+
+    /// Store a finished response of client key `owner` and the full conversation it ends.
+    pub fn insert(
+        &self,
+        response: ResponseObject,
+        conversation: Vec<OpenAIMessage>,
+        owner: Option<String>,
+    ) {
+        self.evict();
+        self.entries.insert(
+            response.id.clone(),
+            StoredResponse { response, conversation, owner, stored_at: Instant::now() },
+        );
+    }
+
+    pub fn get(&self, id: &str, key_id: Option<&str>) -> Option<ResponseObject> {
+        self.visible(id, key_id).map(|e| e.response.clone())
+    }
+
+    /// Conversation to replay for `previous_response_id`.
+    pub fn conversation(&self, id: &str, key_id: Option<&str>) -> Option<Vec<OpenAIMessage>> {
+        self.visible(id, key_id).map(|e| e.conversation.clone())
+    }
+
+    /// Remove a response. Returns false if it did not exist or is not visible to `key_id`.
+    pub fn delete(&self, id: &str, key_id: Option<&str>) -> bool {
+        if self.visible(id, key_id).is_none() {
+            return false;
+        }
+        self.entries.remove(id).is_some()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Live entry `id` if client key `key_id` may see it.
+    fn visible(&self, id: &str, key_id: Option<&str>) -> Option<Ref<'_, String, StoredResponse>> {
+        self.entries.get(id).filter(|e| {
+            e.stored_at.elapsed() < self.ttl
+                && key_id.is_none_or(|key_id| e.owner.as_deref() == Some(key_id))
+        })
+    }
+
+    /// Drop expired entries, then the oldest ones until there is room for one more.
+    fn evict(&self) {
+        self.entries.retain(|_, e| e.stored_at.elapsed() < self.ttl);
+        while self.entries.len() >= self.capacity {
+            let oldest =
+                self.entries.iter().min_by_key(|e| e.value().stored_at).map(|e| e.key().clone());
+            match oldest {
+                Some(id) => {
+                    self.entries.remove(&id);
+                },
+                None => break,
+            }
+        }
+    }
+}
+
+impl Default for ResponseStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn response(id: &str) -> ResponseObject {
+        ResponseObject::new(id.to_string(), "m".to_string(), 0)
+    }
+
+    #[test]
+    fn evicts_oldest_when_full() {
+        let store = ResponseStore::with_limits(2, DEFAULT_TTL);
+        store.insert(response("a"), Vec::new(), None);
+        store.insert(response("b"), Vec::new(), None);
+        store.insert(response("c"), Vec::new(), None);
+        assert_eq!(store.len(), 2);
+        assert!(store.get("a", None).is_none());
+        assert!(store.get("c", None).is_some());
+        assert!(store.delete("b", None));
+        assert!(!store.delete("b", None));
+    }
+
+    #[test]
+    fn responses_are_scoped_to_their_key() {
+        let store = ResponseStore::new();
+        store.insert(response("a"), Vec::new(), Some("k1".to_string()));
+        assert!(store.get("a", Some("k1")).is_some());
+        assert!(store.get("a", Some("k2")).is_none());
+        assert!(store.conversation("a", Some("k2")).is_none());
+        assert!(!store.delete("a", Some("k2")));
+        // The global key sees everything
+        assert!(store.get("a", None).is_some());
+        assert!(store.delete("a", Some("k1")));
+    }
+
+    #[test]
+    fn expired_entries_are_hidden() {
+        let store = ResponseStore::with_limits(10, Duration::ZERO);
+        store.insert(response("a"), Vec::new(), None);
+        assert!(store.get("a", None).is_none());
+        assert!(store.conversation("a", None).is_none());
+    }
+}
@@ -0,0 +1,327 @@
+//! Hedged upstream calls.
+//!
+//! A request whose account is close to its AIMD limit, or whose first byte is slower
+//! than the model's recent p95, gets a second attempt on a different account. Both
+//! attempts race; the first successful response wins and the other one is dropped,
+//! which cancels its upstream connection. A failed attempt does not end the race while
+//! the other one is still running.
+
+use crate::proxy::active_request_guard::ActiveRequestGuard;
+use crate::proxy::adaptive_limit::{AdaptiveLimitManager, HedgeOutcome, HedgePlan};
+use crate::proxy::prometheus;
+use crate::proxy::token_manager::{PoolState, TokenManager};
+use parking_lot::Mutex;
+use serde_json::Value;
+use std::collections::HashSet;
+use std::future::Future;
+use std::time::Instant;
+
+type CallResult = Result<reqwest::Response, String>;
+
+/// Account credentials for one attempt.
+pub struct Leg {
+    pub email: String,
+    pub access_token: String,
+    pub project_id: String,
+}
+
+/// What a hedge needs to pick a second account.
+pub struct HedgeTarget<'a> {
+    pub request_type: &'a str,
+    pub model: &'a str,
+    /// Accounts the hedge must avoid besides the primary one
+    pub exclude: &'a HashSet<String>,
+    /// Requests forced onto one account are never hedged
+    pub pinned: bool,
+}
+
+/// The attempt that answers the request.
+pub struct HedgedResponse {
+    pub email: String,
+    /// Active-request guard of the hedge account, when the hedge won
+    pub guard: Option<ActiveRequestGuard>,
+    pub result: CallResult,
+}
+
+/// Re-address a v1internal body to `leg`'s project and device fingerprint.
+pub fn retarget_body(body: &Value, leg: &Leg) -> Value {
+    let mut body = body.clone();
+    if let Some(obj) = body.as_object_mut() {
+        obj.insert("project".to_string(), Value::String(leg.project_id.clone()));
+    }
+    crate::proxy::upstream::device_fingerprint::inject_body_fingerprint(&mut body, &leg.email);
+    body
+}
+
+/// Run `launch` for the primary account, hedging onto a second account when the
+/// adaptive limits call for it.
+///
+/// On failure of both attempts the primary's result is returned, so the caller's
+/// error handling applies to the account it chose.
+pub async fn hedged_call<F, Fut>(
+    token_manager: &TokenManager,
+    adaptive_limits: &AdaptiveLimitManager,
+    target: HedgeTarget<'_>,
+    primary: Leg,
+    launch: F,
+) -> HedgedResponse
+where
+    F: Fn(Leg) -> Fut,
+    Fut: Future<Output = CallResult>,
+{
+    let primary_email = primary.email.clone();
+    let plan =
+        if target.pinned { None } else { adaptive_limits.hedge_plan(&primary_email, target.model) };
+    let started = Instant::now();
+    let first = launch(primary);
+    let Some(plan) = plan else {
+        let result = first.await;
+        record_first_byte(adaptive_limits, target.model, &result, started);
+        return HedgedResponse { email: primary_email, guard: None, result };
+    };
+
+    let hedge_email: Mutex<Option<String>> = Mutex::new(None);
+    let second = async {
+        tokio::time::sleep(plan.delay).await;
+        let (leg, guard) = acquire_hedge(token_manager, &target, &primary_email).await?;
+        *hedge_email.lock() = Some(leg.email.clone());
+        prometheus::record_hedge_fired(plan.reason.as_str());
+        tracing::info!(
+            "[Hedge] {} on {} after {}ms, hedging on {}",
+            plan.reason.as_str(),
+            primary_email,
+            started.elapsed().as_millis(),
+            leg.email
+        );
+        let email = leg.email.clone();
+        let hedge_started = Instant::now();
+        let result = launch(leg).await;
+        Some((email, guard, result, hedge_started))
+    };
+    tokio::pin!(first, second);
+
+    let mut primary_failure: Option<CallResult> = None;
+    let mut second_pending = true;
+    loop {
+        tokio::select! {
+            result = &mut first, if primary_failure.is_none() => {
+                record_first_byte(adaptive_limits, target.model, &result, started);
+                let Some(secondary) = hedge_email.lock().clone() else {
+                    return HedgedResponse { email: primary_email.clone(), guard: None, result };
+                };
+                let won = is_success(&result);
+                if won || !second_pending {
+                    let outcome =
+                        if won { HedgeOutcome::PrimaryWon } else { HedgeOutcome::BothFailed };
+                    finish(adaptive_limits, plan, &primary_email, Some(&secondary), outcome);
+                    return HedgedResponse { email: primary_email.clone(), guard: None, result };
+                }
+                primary_failure = Some(result);
+            },
+            hedge = &mut second, if second_pending => {
+                second_pending = false;
+                let Some((email, guard, result, hedge_started)) = hedge else {
+                    finish(adaptive_limits, plan, &primary_email, None, HedgeOutcome::NoAccount);
+                    continue;
+                };
+                if is_success(&result) {
+                    record_first_byte(adaptive_limits, target.model, &result, hedge_started);
+                    let outcome = HedgeOutcome::SecondaryWon;
+                    finish(adaptive_limits, plan, &primary_email, Some(&email), outcome);
+                    return HedgedResponse { email, guard: Some(guard), result };
+                }
+                if let Ok(response) = &result {
+                    adaptive_limits.record_error(&email, response.status().as_u16());
+                }
+                tracing::debug!("[Hedge] Hedge on {} failed, waiting for {}", email, primary_email);
+                if let Some(result) = primary_failure.take() {
+                    let outcome = HedgeOutcome::BothFailed;
+                    finish(adaptive_limits, plan, &primary_email, Some(&email), outcome);
+                    return HedgedResponse { email: primary_email.clone(), guard: None, result };
+                }
+            },
+        }
+    }
+}
+
+/// Pick a second account without queueing for one: the primary is still running.
+async fn acquire_hedge(
+    token_manager: &TokenManager,
+    target: &HedgeTarget<'_>,
+    primary: &str,
+) -> Option<(Leg, ActiveRequestGuard)> {
+    let mut exclude = target.exclude.clone();
+    exclude.insert(primary.to_string());
+    if token_manager.pool_state(target.model, Some(&exclude)).await != PoolState::Available {
+        return None;
+    }
+    let (access_token, project_id, email, guard) = token_manager
+        .get_token_with_exclusions(target.request_type, false, None, target.model, Some(&exclude))
+        .await
+        .ok()?;
+    (email != primary).then_some((Leg { email, access_token, project_id }, guard))
+}
+
+fn is_success(result: &CallResult) -> bool {
+    result.as_ref().is_ok_and(|response| response.status().is_success())
+}
+
+fn record_first_byte(
+    adaptive_limits: &AdaptiveLimitManager,
+    model: &str,
+    result: &CallResult,
+    started: Instant,
+) {
+    if is_success(result) {
+        adaptive_limits.record_first_byte(model, started.elapsed());
+    }
+}
+
+fn finish(
+    adaptive_limits: &AdaptiveLimitManager,
+    plan: HedgePlan,
+    primary: &str,
+    secondary: Option<&str>,
+    outcome: HedgeOutcome,
+) {
+    prometheus::record_hedge_outcome(plan.reason.as_str(), outcome.as_str());
+    adaptive_limits.record_hedge(primary, secondary, outcome);
+    tracing::debug!("[Hedge] {} hedge for {}: {}", plan.reason.as_str(), primary, outcome.as_str());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::proxy::token_manager::ProxyToken;
+    use antigravity_types::models::HedgingConfig;
+    use std::path::PathBuf;
+    use std::time::Duration;
+
+    const MODEL: &str = "gemini-3-pro";
+
+    fn manager_with(accounts: &[&str]) -> TokenManager {
+        let dir = std::env::temp_dir().join(format!("antigravity_test_{}", uuid::Uuid::new_v4()));
+        let manager = TokenManager::new(dir);
+        for email in accounts {
+            let token = ProxyToken::new(
+                email.to_string(),
+                "token".to_string(),
+                "refresh".to_string(),
+                3600,
+                chrono::Utc::now().timestamp() + 3600,
+                email.to_string(),
+                PathBuf::from("/tmp"),
+                Some(format!("project-{email}")),
+                Some("g1-pro-tier".to_string()),
+                Some(100),
+                HashSet::new(),
+                1.0,
+                HashSet::new(),
+                None,
+            );
+            manager.tokens.insert(token.account_id.clone(), token);
+        }
+        manager
+    }
+
+    /// Hedges any request whose first byte takes longer than 50ms.
+    fn slow_first_byte_limits() -> AdaptiveLimitManager {
+        let limits = AdaptiveLimitManager::default();
+        limits.set_hedging_config(HedgingConfig {
+            enabled: true,
+            min_samples: 5,
+            min_delay_ms: 50,
+        });
+        for _ in 0..5 {
+            limits.record_first_byte(MODEL, Duration::from_millis(10));
+        }
+        limits
+    }
+
+    fn primary() -> Leg {
+        Leg {
+            email: "slow@test.com".into(),
+            access_token: "token".into(),
+            project_id: "project-slow@test.com".into(),
+        }
+    }
+
+    fn target(exclude: &HashSet<String>) -> HedgeTarget<'_> {
+        HedgeTarget { request_type: "default", model: MODEL, exclude, pinned: false }
+    }
+
+    fn response(status: u16) -> CallResult {
+        let response = axum::http::Response::builder().status(status).body(Vec::new()).unwrap();
+        Ok(reqwest::Response::from(response))
+    }
+
+    /// `slow@test.com` answers with `primary_status` after 2s, the others right away.
+    async fn call(leg: Leg, primary_status: u16, hedge_status: u16) -> CallResult {
+        if leg.email == "slow@test.com" {
+            tokio::time::sleep(Duration::from_secs(2)).await;
+            return response(primary_status);
+        }
+        response(hedge_status)
+    }
+
+    #[tokio::test]
+    async fn hedge_wins_over_slow_primary() {
+        let manager = manager_with(&["slow@test.com", "fast@test.com"]);
+        let limits = slow_first_byte_limits();
+        let exclude = HashSet::new();
+
+        let started = Instant::now();
+        let hedged =
+            hedged_call(&manager, &limits, target(&exclude), primary(), |leg| call(leg, 200, 200))
+                .await;
+
+        assert!(started.elapsed() < Duration::from_secs(1));
+        assert_eq!(hedged.email, "fast@test.com");
+        assert!(hedged.guard.is_some());
+        assert!(hedged.result.unwrap().status().is_success());
+        let slow = limits.get("slow@test.com").unwrap();
+        assert_eq!((slow.hedges_fired(), slow.hedges_won()), (1, 1));
+    }
+
+    #[tokio::test]
+    async fn failed_hedge_falls_back_to_primary() {
+        let manager = manager_with(&["slow@test.com", "fast@test.com"]);
+        let limits = slow_first_byte_limits();
+        let exclude = HashSet::new();
+
+        let hedged =
+            hedged_call(&manager, &limits, target(&exclude), primary(), |leg| call(leg, 503, 429))
+                .await;
+
+        assert_eq!(hedged.email, "slow@test.com");
+        assert!(hedged.guard.is_none());
+        assert_eq!(hedged.result.unwrap().status().as_u16(), 503);
+        let slow = limits.get("slow@test.com").unwrap();
+        assert_eq!((slow.hedges_fired(), slow.hedges_won()), (1, 0));
+    }
+
+    #[tokio::test]
+    async fn no_hedge_without_another_account() {
+        let manager = manager_with(&["slow@test.com", "fast@test.com"]);
+        let limits = slow_first_byte_limits();
+        let exclude = HashSet::from(["fast@test.com".to_string()]);
+
+        let hedged =
+            hedged_call(&manager, &limits, target(&exclude), primary(), |leg| call(leg, 200, 200))
+                .await;
+
+        assert_eq!(hedged.email, "slow@test.com");
+        assert!(limits.get("slow@test.com").is_none_or(|t| t.hedges_fired() == 0));
+    }
+
+    #[test]
+    fn retarget_body_switches_project() {
+        let body = serde_json::json!({ "project": "project-slow@test.com", "request": {} });
+        let leg = Leg {
+            email: "fast@test.com".into(),
+            access_token: "token".into(),
+            project_id: "project-fast".into(),
+        };
+        assert_eq!(retarget_body(&body, &leg)["project"], "project-fast");
+    }
+}
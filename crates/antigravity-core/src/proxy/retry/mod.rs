@@ -5,14 +5,18 @@
 
 mod error_extraction;
 mod exhaustion_response;
+mod hedge;
 mod peek;
 mod profile;
+mod rotating_call;
 mod success_bookkeeping;
 
 pub use error_extraction::{extract_error_info, ErrorInfo};
 pub use exhaustion_response::build_exhaustion_response;
+pub use hedge::{hedged_call, retarget_body, HedgeTarget, HedgedResponse, Leg};
 pub use peek::{peek_first_data_chunk, PeekConfig, PeekResult};
 pub use profile::RetryProfile;
+pub use rotating_call::{call_with_rotation, open_with_rotation, OpenedCall, RotatedCall};
 pub use success_bookkeeping::record_request_success;
 
 use std::time::Duration;
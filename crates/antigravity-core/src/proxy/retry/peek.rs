@@ -34,6 +34,7 @@ pub enum PeekResult<S> {
     Retry(String),
 }
 
+#[tracing::instrument(name = "peek", skip_all, fields(trace_id = %trace_id))]
 pub async fn peek_first_data_chunk<S, E>(
     mut stream: Pin<Box<S>>,
     config: &PeekConfig,
@@ -0,0 +1,163 @@
+//! Single-shot upstream call with account rotation.
+//!
+//! Shared by the endpoints (embeddings, images, speech) that need the pool's
+//! failover but none of the peeking or signature handling of the chat handlers.
+
+use std::collections::HashSet;
+
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+use serde_json::Value;
+use tracing::{debug, warn};
+
+use super::{
+    build_exhaustion_response, extract_error_info, is_rate_limit_code, record_request_success,
+    should_rotate_account, MAX_RETRY_ATTEMPTS,
+};
+use crate::proxy::active_request_guard::ActiveRequestGuard;
+use crate::proxy::common::header_constants::X_ACCOUNT_EMAIL;
+use crate::proxy::common::{sanitize_upstream_error, UpstreamError};
+use crate::proxy::mappers::gemini::unwrap_response;
+use crate::proxy::server::AppState;
+
+/// Unwrapped upstream body and the account that served it.
+pub struct RotatedCall {
+    pub body: Value,
+    pub email: String,
+}
+
+/// Successful upstream response, not yet read.
+pub struct OpenedCall {
+    pub response: reqwest::Response,
+    pub email: String,
+    /// Keeps the account counted as busy until the body has been streamed
+    pub guard: ActiveRequestGuard,
+}
+
+/// Call v1internal `method`, rotating accounts on rate limits, auth failures and 404s.
+///
+/// `wrap` builds the v1internal body for the selected account's project. Failures are
+/// returned as ready-to-send responses.
+pub async fn call_with_rotation<F>(
+    state: &AppState,
+    label: &str,
+    quota_group: &str,
+    target_model: &str,
+    method: &str,
+    wrap: F,
+) -> Result<RotatedCall, Response>
+where
+    F: Fn(&str) -> Value,
+{
+    let opened =
+        open_with_rotation(state, label, quota_group, target_model, method, None, wrap).await?;
+    let resp: Value =
+        opened.response.json().await.map_err(|e| {
+            (StatusCode::BAD_GATEWAY, format!("Parse error: {}", e)).into_response()
+        })?;
+    record_request_success(&state.token_manager, state, &opened.email, "");
+    Ok(RotatedCall { body: unwrap_response(&resp), email: opened.email })
+}
+
+/// Like [`call_with_rotation`], but hands back the successful response unread, for
+/// callers that stream it (`query` is e.g. `alt=sse`).
+///
+/// Success is not recorded; the caller does that once the body has been consumed.
+pub async fn open_with_rotation<F>(
+    state: &AppState,
+    label: &str,
+    quota_group: &str,
+    target_model: &str,
+    method: &str,
+    query: Option<&str>,
+    wrap: F,
+) -> Result<OpenedCall, Response>
+where
+    F: Fn(&str) -> Value,
+{
+    let token_manager = state.token_manager.clone();
+    let max_attempts = MAX_RETRY_ATTEMPTS.min(token_manager.len()).max(1);
+    let mut last_error = UpstreamError::EmptyStream;
+    let mut last_email: Option<String> = None;
+    let mut attempted: HashSet<String> = HashSet::new();
+
+    for attempt in 0..max_attempts {
+        let (access_token, project_id, email, guard) = match token_manager
+            .get_token_with_exclusions(
+                quota_group,
+                attempt > 0,
+                None,
+                target_model,
+                (!attempted.is_empty()).then_some(&attempted),
+            )
+            .await
+        {
+            Ok(t) => t,
+            Err(e) => {
+                return Err((StatusCode::SERVICE_UNAVAILABLE, format!("Token error: {}", e))
+                    .into_response())
+            },
+        };
+        attempted.insert(email.clone());
+        last_email = Some(email.clone());
+
+        let mut wrapped_body = wrap(&project_id);
+        crate::proxy::upstream::device_fingerprint::inject_body_fingerprint(
+            &mut wrapped_body,
+            &email,
+        );
+
+        let account_proxy = token_manager.get_account_proxy_url(&email);
+        let response = match state
+            .upstream
+            .call_v1_internal_fingerprinted(
+                method,
+                &access_token,
+                wrapped_body,
+                query,
+                &email,
+                account_proxy.as_deref(),
+            )
+            .await
+        {
+            Ok(r) => r,
+            Err(e) => {
+                debug!("[{}] Attempt {}/{} failed: {}", label, attempt + 1, max_attempts, e);
+                last_error = UpstreamError::TokenAcquisition(e);
+                continue;
+            },
+        };
+
+        if response.status().is_success() {
+            return Ok(OpenedCall { response, email, guard });
+        }
+
+        let (err_info, upstream_err) = extract_error_info(response).await;
+        let code = err_info.status_code;
+        last_error = upstream_err;
+
+        if is_rate_limit_code(code) || code == 401 || code == 403 {
+            token_manager.mark_rate_limited(
+                &email,
+                code,
+                err_info.retry_after.as_deref(),
+                &err_info.error_text,
+            );
+        }
+        if should_rotate_account(code) {
+            warn!("[{}] {} on {}, rotating", label, code, email);
+            continue;
+        }
+
+        return Err((
+            StatusCode::from_u16(code).unwrap_or(StatusCode::BAD_GATEWAY),
+            [(X_ACCOUNT_EMAIL, email.as_str())],
+            sanitize_upstream_error(code, &err_info.error_text),
+        )
+            .into_response());
+    }
+
+    Err(build_exhaustion_response(&last_error, last_email.as_deref()))
+}
@@ -0,0 +1,116 @@
+//! Facts about a request that routing rules match on.
+
+use axum::http::HeaderMap;
+use chrono::NaiveTime;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::proxy::api_keys::ClientKey;
+use crate::proxy::mappers::context_manager::estimate_tokens_from_str;
+use antigravity_types::models::Protocol;
+
+/// Everything a rule condition can look at.
+#[derive(Debug, Clone, Serialize)]
+pub struct RequestFacts {
+    pub model: String,
+    #[serde(skip)]
+    pub api_key: Option<ClientKey>,
+    pub protocol: Protocol,
+    #[serde(skip)]
+    pub headers: HeaderMap,
+    /// Rough estimate over all text in the body
+    pub prompt_tokens: u64,
+    pub has_tools: bool,
+    pub has_images: bool,
+    /// Current UTC time of day
+    pub time: NaiveTime,
+}
+
+impl RequestFacts {
+    /// Facts for a proxied request, with the model taken from a Gemini-style path
+    /// or the body's `model` field.
+    pub fn from_request(
+        path: &str,
+        headers: &HeaderMap,
+        api_key: Option<ClientKey>,
+        body: &Value,
+    ) -> Self {
+        let model = model_from_path(path)
+            .or_else(|| body.get("model").and_then(Value::as_str).map(str::to_string))
+            .unwrap_or_default();
+        Self::from_body(model, protocol_for_path(path), headers, api_key, body)
+    }
+
+    pub fn from_body(
+        model: String,
+        protocol: Protocol,
+        headers: &HeaderMap,
+        api_key: Option<ClientKey>,
+        body: &Value,
+    ) -> Self {
+        Self {
+            model,
+            api_key,
+            protocol,
+            headers: headers.clone(),
+            prompt_tokens: estimate_prompt_tokens(body),
+            has_tools: ["tools", "functions"]
+                .iter()
+                .any(|key| body.get(key).and_then(Value::as_array).is_some_and(|t| !t.is_empty())),
+            has_images: contains_image(body),
+            time: chrono::Utc::now().time(),
+        }
+    }
+}
+
+/// Client protocol implied by the endpoint path.
+pub fn protocol_for_path(path: &str) -> Protocol {
+    if path.starts_with("/v1/messages") {
+        Protocol::Anthropic
+    } else if path.starts_with("/v1beta") {
+        Protocol::Gemini
+    } else {
+        Protocol::OpenAI
+    }
+}
+
+fn model_from_path(path: &str) -> Option<String> {
+    let rest = path.split("/v1beta/models/").nth(1)?;
+    rest.split([':', '/']).next().filter(|m| !m.is_empty()).map(str::to_string)
+}
+
+/// Sum of token estimates for every string in the body, skipping inline binary data.
+fn estimate_prompt_tokens(value: &Value) -> u64 {
+    match value {
+        Value::String(s) if s.starts_with("data:") => 0,
+        Value::String(s) => u64::from(estimate_tokens_from_str(s)),
+        Value::Array(items) => items.iter().map(estimate_prompt_tokens).sum(),
+        Value::Object(map) => map
+            .iter()
+            .filter(|(key, _)| key.as_str() != "data")
+            .map(|(_, v)| estimate_prompt_tokens(v))
+            .sum(),
+        _ => 0,
+    }
+}
+
+/// Image parts in any of the three protocols' content formats.
+fn contains_image(value: &Value) -> bool {
+    match value {
+        Value::Array(items) => items.iter().any(contains_image),
+        Value::Object(map) => {
+            let typed_image = map
+                .get("type")
+                .and_then(Value::as_str)
+                .is_some_and(|t| matches!(t, "image" | "image_url" | "input_image"));
+            let inline_image = ["inlineData", "inline_data"].iter().any(|key| {
+                map.get(*key)
+                    .and_then(|d| d.get("mimeType").or_else(|| d.get("mime_type")))
+                    .and_then(Value::as_str)
+                    .is_some_and(|mime| mime.starts_with("image/"))
+            });
+            typed_image || inline_image || map.values().any(contains_image)
+        },
+        _ => false,
+    }
+}
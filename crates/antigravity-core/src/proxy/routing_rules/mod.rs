@@ -0,0 +1,222 @@
+//! Evaluation of the ordered `routing_rules` list.
+//!
+//! The routing middleware evaluates the rules once per request and runs the handler
+//! inside [`scope`] with the fired rule's [`RouteDecision`]. Model mapping, thinking
+//! configuration and account selection read the decision back through the accessors
+//! below; outside a scope they all return `None`.
+
+mod facts;
+#[cfg(test)]
+mod tests;
+
+use serde::Serialize;
+use std::future::Future;
+
+use crate::proxy::providers::registry::glob_match;
+use antigravity_types::models::{RoutingRule, RuleAction, RuleMatch, ThinkingBudgetConfig};
+
+pub use facts::{protocol_for_path, RequestFacts};
+
+/// Outcome of checking one rule, as reported by the explain endpoint.
+#[derive(Debug, Clone, Serialize)]
+pub struct RuleTrace {
+    pub rule: String,
+    pub matched: bool,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Evaluation<'a> {
+    /// First enabled rule whose conditions all hold
+    pub fired: Option<&'a RoutingRule>,
+    /// Every rule checked up to and including the fired one
+    pub trace: Vec<RuleTrace>,
+}
+
+/// Check rules in order and stop at the first match.
+pub fn evaluate<'a>(rules: &'a [RoutingRule], facts: &RequestFacts) -> Evaluation<'a> {
+    let mut trace = Vec::new();
+    for rule in rules {
+        if !rule.enabled {
+            trace.push(RuleTrace {
+                rule: rule.name.clone(),
+                matched: false,
+                reason: "disabled".to_string(),
+            });
+            continue;
+        }
+        match check(&rule.conditions, facts) {
+            Ok(reason) => {
+                trace.push(RuleTrace { rule: rule.name.clone(), matched: true, reason });
+                return Evaluation { fired: Some(rule), trace };
+            },
+            Err(reason) => {
+                trace.push(RuleTrace { rule: rule.name.clone(), matched: false, reason });
+            },
+        }
+    }
+    Evaluation { fired: None, trace }
+}
+
+/// `Ok` with the matched conditions, or `Err` with the first one that failed.
+fn check(conditions: &RuleMatch, facts: &RequestFacts) -> Result<String, String> {
+    let mut matched = Vec::new();
+
+    if !conditions.models.is_empty() {
+        match conditions.models.iter().find(|p| glob_match(p, &facts.model)) {
+            Some(pattern) => matched.push(format!("model '{}' matches '{}'", facts.model, pattern)),
+            None => {
+                return Err(format!(
+                    "model '{}' matches none of [{}]",
+                    facts.model,
+                    conditions.models.join(", ")
+                ))
+            },
+        }
+    }
+
+    if !conditions.api_keys.is_empty() {
+        let key = facts
+            .api_key
+            .as_ref()
+            .and_then(|key| conditions.api_keys.iter().find(|k| **k == key.id || **k == key.label));
+        match (key, &facts.api_key) {
+            (Some(k), _) => matched.push(format!("api key '{}'", k)),
+            (None, Some(key)) => {
+                return Err(format!("api key '{}' is not one of the rule's keys", key.label))
+            },
+            (None, None) => return Err("request has no client api key".to_string()),
+        }
+    }
+
+    if !conditions.protocols.is_empty() {
+        if !conditions.protocols.contains(&facts.protocol) {
+            return Err(format!("protocol {:?} not in {:?}", facts.protocol, conditions.protocols));
+        }
+        matched.push(format!("protocol {:?}", facts.protocol));
+    }
+
+    for (name, pattern) in &conditions.headers {
+        match facts.headers.get(name.as_str()).and_then(|v| v.to_str().ok()) {
+            Some(value) if glob_match(pattern, value) => {
+                matched.push(format!("header {} '{}' matches '{}'", name, value, pattern));
+            },
+            Some(value) => {
+                return Err(format!("header {} '{}' does not match '{}'", name, value, pattern))
+            },
+            None => return Err(format!("header {} is missing", name)),
+        }
+    }
+
+    if let Some(min) = conditions.min_prompt_tokens {
+        if facts.prompt_tokens < min {
+            return Err(format!("~{} prompt tokens < {}", facts.prompt_tokens, min));
+        }
+        matched.push(format!("~{} prompt tokens >= {}", facts.prompt_tokens, min));
+    }
+    if let Some(max) = conditions.max_prompt_tokens {
+        if facts.prompt_tokens > max {
+            return Err(format!("~{} prompt tokens > {}", facts.prompt_tokens, max));
+        }
+        matched.push(format!("~{} prompt tokens <= {}", facts.prompt_tokens, max));
+    }
+
+    for (label, wanted, actual) in [
+        ("tools", conditions.has_tools, facts.has_tools),
+        ("images", conditions.has_images, facts.has_images),
+    ] {
+        match wanted {
+            Some(wanted) if wanted != actual => {
+                return Err(format!("request {} {}", if actual { "has" } else { "has no" }, label))
+            },
+            Some(_) => matched.push(format!("{} {}", if actual { "has" } else { "no" }, label)),
+            None => {},
+        }
+    }
+
+    if let Some(window) = &conditions.time_of_day {
+        let Some((start, end)) = conditions.time_window() else {
+            return Err(format!("invalid time_of_day '{}'", window));
+        };
+        let inside = if start <= end {
+            facts.time >= start && facts.time < end
+        } else {
+            facts.time >= start || facts.time < end
+        };
+        let now = facts.time.format("%H:%M");
+        if !inside {
+            return Err(format!("{} UTC outside {}", now, window));
+        }
+        matched.push(format!("{} UTC within {}", now, window));
+    }
+
+    if matched.is_empty() {
+        Ok("matches every request".to_string())
+    } else {
+        Ok(matched.join(", "))
+    }
+}
+
+/// What the fired rule changes for the rest of the request.
+#[derive(Debug, Clone, Default)]
+pub struct RouteDecision {
+    /// None when only the client key's account group applies
+    pub rule: Option<String>,
+    pub model: Option<String>,
+    /// Group pinned by the rule; narrows `key_group`, never widens it
+    pub account_group: Option<String>,
+    /// The client key's account group, a hard limit on account selection
+    pub key_group: Option<String>,
+    pub thinking_budget: Option<ThinkingBudgetConfig>,
+}
+
+impl RouteDecision {
+    pub fn from_rule(rule: &RoutingRule) -> Self {
+        let mut decision = Self { rule: Some(rule.name.clone()), ..Default::default() };
+        match &rule.action {
+            RuleAction::RewriteModel { model } => decision.model = Some(model.clone()),
+            RuleAction::PinAccountGroup { group } => decision.account_group = Some(group.clone()),
+            RuleAction::ThinkingBudget { budget } => {
+                decision.thinking_budget = Some(budget.clone())
+            },
+            RuleAction::Reject { .. } => {},
+        }
+        decision
+    }
+}
+
+tokio::task_local! {
+    static ROUTE_DECISION: RouteDecision;
+}
+
+/// Run `future` with `decision` applied.
+pub async fn scope<F: Future>(decision: RouteDecision, future: F) -> F::Output {
+    ROUTE_DECISION.scope(decision, future).await
+}
+
+/// Model set by a `rewrite_model` rule for the current request.
+pub fn rewritten_model() -> Option<String> {
+    ROUTE_DECISION.try_with(|d| d.model.clone()).ok().flatten()
+}
+
+/// Account group a rule pinned the current request to.
+pub fn account_group() -> Option<String> {
+    ROUTE_DECISION.try_with(|d| d.account_group.clone()).ok().flatten()
+}
+
+/// Account groups the current request is limited to; an account must belong to all of them.
+pub fn account_groups() -> Vec<String> {
+    ROUTE_DECISION
+        .try_with(|d| {
+            let mut groups: Vec<String> =
+                d.key_group.iter().chain(d.account_group.iter()).cloned().collect();
+            groups.dedup();
+            groups
+        })
+        .unwrap_or_default()
+}
+
+/// Thinking budget override for the current request.
+pub fn thinking_budget() -> Option<ThinkingBudgetConfig> {
+    ROUTE_DECISION.try_with(|d| d.thinking_budget.clone()).ok().flatten()
+}
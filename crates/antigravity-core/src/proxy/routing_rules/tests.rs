@@ -0,0 +1,162 @@
+use super::*;
+use antigravity_types::models::{PriorityClass, Protocol};
+use axum::http::HeaderMap;
+use chrono::NaiveTime;
+use serde_json::json;
+
+use crate::proxy::api_keys::ClientKey;
+
+fn rule(name: &str, conditions: RuleMatch, action: RuleAction) -> RoutingRule {
+    RoutingRule { name: name.to_string(), enabled: true, conditions, action }
+}
+
+fn reject() -> RuleAction {
+    RuleAction::Reject { message: None }
+}
+
+fn facts(body: serde_json::Value) -> RequestFacts {
+    let mut facts = RequestFacts::from_request("/v1/messages", &HeaderMap::new(), None, &body);
+    facts.time = NaiveTime::from_hms_opt(12, 0, 0).unwrap();
+    facts
+}
+
+#[test]
+fn extracts_facts_from_each_protocol() {
+    let claude = facts(json!({
+        "model": "claude-opus-4",
+        "tools": [{"name": "bash"}],
+        "messages": [{"role": "user", "content": [
+            {"type": "text", "text": "describe"},
+            {"type": "image", "source": {"type": "base64", "data": "AAAA".repeat(1000)}}
+        ]}]
+    }));
+    assert_eq!(claude.model, "claude-opus-4");
+    assert_eq!(claude.protocol, Protocol::Anthropic);
+    assert!(claude.has_tools && claude.has_images);
+    assert!(claude.prompt_tokens < 50, "base64 data must not count: {}", claude.prompt_tokens);
+
+    let gemini = RequestFacts::from_request(
+        "/v1beta/models/gemini-3-pro:generateContent",
+        &HeaderMap::new(),
+        None,
+        &json!({"contents": [{"parts": [{"inlineData": {"mimeType": "image/png", "data": "x"}}]}]}),
+    );
+    assert_eq!(gemini.model, "gemini-3-pro");
+    assert_eq!(gemini.protocol, Protocol::Gemini);
+    assert!(gemini.has_images && !gemini.has_tools);
+
+    let openai = RequestFacts::from_request(
+        "/v1/chat/completions",
+        &HeaderMap::new(),
+        None,
+        &json!({"model": "gpt-4o", "messages": [{"role": "user", "content": "hi"}], "tools": []}),
+    );
+    assert_eq!(openai.protocol, Protocol::OpenAI);
+    assert!(!openai.has_tools && !openai.has_images);
+}
+
+#[test]
+fn first_enabled_match_wins() {
+    let mut disabled = rule("off", RuleMatch::default(), reject());
+    disabled.enabled = false;
+    let rules = vec![
+        disabled,
+        rule(
+            "gemini-only",
+            RuleMatch { models: vec!["gemini-*".into()], ..Default::default() },
+            reject(),
+        ),
+        rule(
+            "claude",
+            RuleMatch { models: vec!["claude-*".into()], ..Default::default() },
+            RuleAction::RewriteModel { model: "claude-sonnet-4-5".into() },
+        ),
+        rule("catch-all", RuleMatch::default(), reject()),
+    ];
+
+    let evaluation = evaluate(&rules, &facts(json!({"model": "claude-opus-4"})));
+    assert_eq!(evaluation.fired.map(|r| r.name.as_str()), Some("claude"));
+    let reasons: Vec<(&str, bool, &str)> =
+        evaluation.trace.iter().map(|t| (t.rule.as_str(), t.matched, t.reason.as_str())).collect();
+    assert_eq!(
+        reasons,
+        vec![
+            ("off", false, "disabled"),
+            ("gemini-only", false, "model 'claude-opus-4' matches none of [gemini-*]"),
+            ("claude", true, "model 'claude-opus-4' matches 'claude-*'"),
+        ]
+    );
+
+    let decision = RouteDecision::from_rule(evaluation.fired.unwrap());
+    assert_eq!(decision.model.as_deref(), Some("claude-sonnet-4-5"));
+
+    let none = evaluate(&rules[..3], &facts(json!({"model": "gpt-4o"})));
+    assert!(none.fired.is_none());
+    assert_eq!(none.trace.len(), 3);
+}
+
+#[test]
+fn matches_keys_headers_and_size() {
+    let conditions = RuleMatch {
+        api_keys: vec!["batch".into()],
+        headers: [("x-priority".to_string(), "low*".to_string())].into(),
+        min_prompt_tokens: Some(100),
+        has_tools: Some(false),
+        ..Default::default()
+    };
+    let rules = vec![rule("batch", conditions, RuleAction::PinAccountGroup { group: "b".into() })];
+
+    let mut request = facts(json!({"model": "m", "messages": [{"content": "word ".repeat(200)}]}));
+    request.api_key = Some(ClientKey {
+        id: "k1".into(),
+        label: "batch".into(),
+        account_group: None,
+        priority: PriorityClass::Batch,
+    });
+    request.headers.insert("x-priority", "lowest".parse().unwrap());
+    let evaluation = evaluate(&rules, &request);
+    assert!(evaluation.fired.is_some(), "{:?}", evaluation.trace);
+    assert!(evaluation.trace[0].reason.contains("header x-priority 'lowest' matches 'low*'"));
+
+    request.headers.remove("x-priority");
+    assert_eq!(evaluate(&rules, &request).trace[0].reason, "header x-priority is missing");
+
+    request.api_key = None;
+    assert_eq!(evaluate(&rules, &request).trace[0].reason, "request has no client api key");
+
+    let small = RequestFacts { prompt_tokens: 10, ..request.clone() };
+    let small_rules = vec![rule(
+        "small",
+        RuleMatch { max_prompt_tokens: Some(50), ..Default::default() },
+        reject(),
+    )];
+    assert!(evaluate(&small_rules, &small).fired.is_some());
+    assert!(evaluate(&small_rules, &request).fired.is_none());
+}
+
+#[test]
+fn time_window_wraps_past_midnight() {
+    let night = vec![rule(
+        "night",
+        RuleMatch { time_of_day: Some("22:00-06:00".into()), ..Default::default() },
+        reject(),
+    )];
+    let mut request = facts(json!({"model": "m"}));
+
+    for (hour, inside) in [(23, true), (3, true), (6, false), (12, false), (22, true)] {
+        request.time = NaiveTime::from_hms_opt(hour, 0, 0).unwrap();
+        assert_eq!(evaluate(&night, &request).fired.is_some(), inside, "hour {}", hour);
+    }
+}
+
+#[tokio::test]
+async fn decision_is_visible_only_inside_scope() {
+    let decision = RouteDecision::from_rule(&rule(
+        "pin",
+        RuleMatch::default(),
+        RuleAction::PinAccountGroup { group: "batch".into() },
+    ));
+    assert_eq!(account_group(), None);
+    let inside = scope(decision, async { (account_group(), rewritten_model()) }).await;
+    assert_eq!(inside, (Some("batch".to_string()), None));
+}
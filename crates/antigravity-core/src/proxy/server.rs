@@ -19,6 +19,7 @@ pub struct AppState {
     pub upstream_proxy: Arc<RwLock<antigravity_types::models::UpstreamProxyConfig>>,
     pub security_config: Arc<RwLock<crate::proxy::ProxySecurityConfig>>,
     pub zai: Arc<RwLock<antigravity_types::models::ZaiConfig>>,
+    pub providers: Arc<RwLock<Vec<antigravity_types::models::ProviderConfig>>>,
     pub monitor: Arc<crate::proxy::monitor::ProxyMonitor>,
     pub experimental: Arc<RwLock<antigravity_types::models::ExperimentalConfig>>,
     pub adaptive_limits: Arc<crate::proxy::AdaptiveLimitManager>,
@@ -29,6 +30,10 @@ pub struct AppState {
     pub upstream: Arc<crate::proxy::upstream::client::UpstreamClient>,
     pub provider_rr: Arc<AtomicUsize>,
     pub zai_vision_mcp: Arc<crate::proxy::zai_vision_mcp::ZaiVisionMcpState>,
+    pub api_keys: Arc<crate::proxy::ApiKeyRegistry>,
+    pub response_store: Arc<crate::proxy::ResponseStore>,
+    pub batches: Arc<crate::proxy::BatchManager>,
+    pub routing_rules: Arc<RwLock<Vec<antigravity_types::models::RoutingRule>>>,
 }
 
 /// Configuration for building the proxy router with shared state references.
@@ -41,6 +46,7 @@ pub struct ProxyRouterConfig {
     pub upstream_proxy: Arc<RwLock<antigravity_types::models::UpstreamProxyConfig>>,
     pub security_config: Arc<RwLock<crate::proxy::ProxySecurityConfig>>,
     pub zai: Arc<RwLock<antigravity_types::models::ZaiConfig>>,
+    pub providers: Arc<RwLock<Vec<antigravity_types::models::ProviderConfig>>>,
     pub monitor: Arc<crate::proxy::monitor::ProxyMonitor>,
     pub experimental: Arc<RwLock<antigravity_types::models::ExperimentalConfig>>,
     pub adaptive_limits: Arc<crate::proxy::AdaptiveLimitManager>,
@@ -50,6 +56,10 @@ pub struct ProxyRouterConfig {
     pub provider_rr: Arc<AtomicUsize>,
     pub zai_vision_mcp: Arc<crate::proxy::zai_vision_mcp::ZaiVisionMcpState>,
     pub upstream_client: Arc<crate::proxy::upstream::client::UpstreamClient>,
+    pub api_keys: Arc<crate::proxy::ApiKeyRegistry>,
+    pub response_store: Arc<crate::proxy::ResponseStore>,
+    pub batches: Arc<crate::proxy::BatchManager>,
+    pub routing_rules: Arc<RwLock<Vec<antigravity_types::models::RoutingRule>>>,
 }
 
 /// Build proxy router with shared state references for hot-reload support.
@@ -63,6 +73,7 @@ pub fn build_proxy_router_with_shared_state(config: ProxyRouterConfig) -> Router
         upstream_proxy,
         security_config,
         zai,
+        providers,
         monitor,
         experimental,
         adaptive_limits,
@@ -72,6 +83,10 @@ pub fn build_proxy_router_with_shared_state(config: ProxyRouterConfig) -> Router
         provider_rr,
         zai_vision_mcp,
         upstream_client,
+        api_keys,
+        response_store,
+        batches,
+        routing_rules,
     } = config;
     let state = AppState {
         token_manager,
@@ -81,6 +96,7 @@ pub fn build_proxy_router_with_shared_state(config: ProxyRouterConfig) -> Router
         upstream_proxy: Arc::clone(&upstream_proxy),
         upstream: upstream_client,
         zai,
+        providers,
         provider_rr,
         zai_vision_mcp,
         monitor,
@@ -88,8 +104,13 @@ pub fn build_proxy_router_with_shared_state(config: ProxyRouterConfig) -> Router
         adaptive_limits,
         health_monitor,
         circuit_breaker,
-        security_config: Arc::clone(&security_config),
+        security_config,
+        api_keys,
+        response_store,
+        batches,
+        routing_rules,
     };
+    state.batches.resume(&state);
 
     use crate::proxy::handlers;
 
@@ -104,13 +125,61 @@ pub fn build_proxy_router_with_shared_state(config: ProxyRouterConfig) -> Router
             "/v1/completions",
             post(handlers::openai::handle_completions),
         )
-        .route("/v1/responses", post(handlers::openai::handle_completions))
+        .route(
+            "/v1/responses",
+            post(handlers::openai::handle_create_response),
+        )
+        .route(
+            "/v1/responses/:id",
+            get(handlers::openai::handle_get_response)
+                .delete(handlers::openai::handle_delete_response),
+        )
+        .route(
+            "/v1/images/generations",
+            post(handlers::openai::handle_images_generations),
+        )
+        .route(
+            "/v1/images/edits",
+            post(handlers::openai::handle_images_edits),
+        )
+        .route(
+            "/v1/batches",
+            get(handlers::openai::handle_list_batches).post(handlers::openai::handle_create_batch),
+        )
+        .route("/v1/batches/:id", get(handlers::openai::handle_get_batch))
+        .route(
+            "/v1/batches/:id/cancel",
+            post(handlers::openai::handle_cancel_batch),
+        )
+        .route(
+            "/v1/files",
+            get(handlers::openai::handle_list_files).post(handlers::openai::handle_upload_file),
+        )
+        .route(
+            "/v1/files/:id",
+            get(handlers::openai::handle_get_file).delete(handlers::openai::handle_delete_file),
+        )
+        .route(
+            "/v1/files/:id/content",
+            get(handlers::openai::handle_file_content),
+        )
+        .route(
+            "/v1/embeddings",
+            post(handlers::embeddings::handle_embeddings),
+        )
         .route(
             "/v1/audio/transcriptions",
             post(handlers::audio::handle_audio_transcription).layer(DefaultBodyLimit::max(
-                crate::proxy::audio::AudioProcessor::max_size_bytes(),
+                crate::proxy::audio::AudioProcessor::max_upload_bytes(),
             )),
         )
+        .route(
+            "/v1/audio/translations",
+            post(handlers::audio::handle_audio_translation).layer(DefaultBodyLimit::max(
+                crate::proxy::audio::AudioProcessor::max_upload_bytes(),
+            )),
+        )
+        .route("/v1/audio/speech", post(handlers::speech::handle_audio_speech))
         // Claude Protocol
         .route("/v1/messages", post(handlers::claude::handle_messages))
         .route(
@@ -121,6 +190,22 @@ pub fn build_proxy_router_with_shared_state(config: ProxyRouterConfig) -> Router
             "/v1/models/claude",
             get(handlers::claude::handle_list_models),
         )
+        .route(
+            "/v1/messages/batches",
+            get(handlers::claude::handle_list_batches).post(handlers::claude::handle_create_batch),
+        )
+        .route(
+            "/v1/messages/batches/:id",
+            get(handlers::claude::handle_get_batch),
+        )
+        .route(
+            "/v1/messages/batches/:id/cancel",
+            post(handlers::claude::handle_cancel_batch),
+        )
+        .route(
+            "/v1/messages/batches/:id/results",
+            get(handlers::claude::handle_batch_results),
+        )
         // z.ai MCP
         .route(
             "/mcp/web_search_prime/mcp",
@@ -152,10 +237,22 @@ pub fn build_proxy_router_with_shared_state(config: ProxyRouterConfig) -> Router
         )
         .route("/v1/api/event_logging", post(|| async { StatusCode::OK }))
         .layer(axum::middleware::from_fn_with_state(
-            security_config,
+            state.clone(),
+            crate::proxy::middleware::admission_middleware,
+        ))
+        .layer(axum::middleware::from_fn_with_state(
+            state.clone(),
+            crate::proxy::middleware::routing_middleware,
+        ))
+        .layer(axum::middleware::from_fn_with_state(
+            state.clone(),
+            crate::proxy::middleware::spend_middleware,
+        ))
+        .layer(axum::middleware::from_fn_with_state(
+            state.clone(),
             crate::proxy::middleware::auth_middleware,
         ))
-        .layer(DefaultBodyLimit::max(100 * 1024 * 1024))
+        .layer(DefaultBodyLimit::max(crate::proxy::middleware::MAX_REQUEST_BODY))
         .layer(axum::middleware::from_fn_with_state(
             state.clone(),
             crate::proxy::middleware::monitor::monitor_middleware,
@@ -174,11 +271,14 @@ pub struct ServerStartConfig {
     pub upstream_proxy: antigravity_types::models::UpstreamProxyConfig,
     pub security_config: crate::proxy::ProxySecurityConfig,
     pub zai: antigravity_types::models::ZaiConfig,
+    pub providers: Vec<antigravity_types::models::ProviderConfig>,
     pub monitor: Arc<crate::proxy::monitor::ProxyMonitor>,
     pub experimental: antigravity_types::models::ExperimentalConfig,
     pub adaptive_limits: Arc<crate::proxy::AdaptiveLimitManager>,
     pub health_monitor: Arc<crate::proxy::HealthMonitor>,
     pub circuit_breaker: Arc<crate::proxy::CircuitBreakerManager>,
+    pub api_keys: Arc<crate::proxy::ApiKeyRegistry>,
+    pub batches: Arc<crate::proxy::BatchManager>,
 }
 
 /// Axum server instance
@@ -198,6 +298,7 @@ impl AxumServer {
         let custom_mapping = Arc::new(RwLock::new(self.config.custom_mapping));
         let security_config = Arc::new(RwLock::new(self.config.security_config));
         let zai = Arc::new(RwLock::new(self.config.zai));
+        let providers = Arc::new(RwLock::new(self.config.providers));
         let experimental = Arc::new(RwLock::new(self.config.experimental));
         let upstream_proxy = Arc::new(RwLock::new(self.config.upstream_proxy.clone()));
 
@@ -212,6 +313,7 @@ impl AxumServer {
 
         let provider_rr = Arc::new(AtomicUsize::new(0));
         let zai_vision_mcp = Arc::new(crate::proxy::zai_vision_mcp::ZaiVisionMcpState::new());
+        let response_store = Arc::new(crate::proxy::ResponseStore::new());
         let upstream_client = Arc::new(crate::proxy::upstream::client::UpstreamClient::new(
             http_client.clone(),
             Arc::clone(&upstream_proxy),
@@ -224,6 +326,7 @@ impl AxumServer {
             upstream_proxy,
             security_config,
             zai,
+            providers,
             monitor: self.config.monitor,
             experimental,
             adaptive_limits: self.config.adaptive_limits,
@@ -233,6 +336,10 @@ impl AxumServer {
             provider_rr,
             zai_vision_mcp,
             upstream_client,
+            api_keys: self.config.api_keys,
+            response_store,
+            batches: self.config.batches,
+            routing_rules: Arc::new(RwLock::new(Vec::new())),
         });
 
         let listener = tokio::net::TcpListener::bind(&addr).await?;
@@ -0,0 +1,459 @@
+//! Spend accounting: prices every logged request and enforces spend budgets.
+//!
+//! Usage is rolled up per API key, model and account for the current day and month.
+//! Totals live in memory for budget checks and are flushed to SQLite periodically;
+//! on startup the current periods are hydrated back from the database.
+
+use crate::modules::spend_db;
+use crate::proxy::monitor::{ProxyEventBus, ProxyRequestLog};
+use crate::proxy::providers::registry::glob_match;
+use antigravity_types::models::{
+    BudgetAction, BudgetAlert, BudgetScope, BudgetStatus, ModelPrice, SpendBudget, SpendConfig,
+    SpendPeriod, UsageDimension, UsageQuery, UsageReport, UsageRollup, UsageTotals,
+};
+use dashmap::{DashMap, DashSet};
+use parking_lot::RwLock;
+use std::sync::Arc;
+use std::time::Duration;
+
+const FLUSH_INTERVAL: Duration = Duration::from_secs(30);
+const PERIODS: [SpendPeriod; 2] = [SpendPeriod::Daily, SpendPeriod::Monthly];
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct BucketKey {
+    period: SpendPeriod,
+    period_start: i64,
+    dimension: UsageDimension,
+    key: String,
+}
+
+#[derive(Debug, Default)]
+struct Bucket {
+    total: UsageTotals,
+    /// Part of `total` not yet written to SQLite
+    unflushed: UsageTotals,
+}
+
+pub struct SpendTracker {
+    config: RwLock<SpendConfig>,
+    buckets: DashMap<BucketKey, Bucket>,
+    /// (budget name, period start) pairs already alerted
+    alerted: DashSet<(String, i64)>,
+}
+
+/// Cost of a request in microdollars (`input` excludes cached tokens).
+fn request_cost(price: &ModelPrice, input: u64, output: u64, cached: u64) -> u64 {
+    let micro = u128::from(input) * u128::from(price.input)
+        + u128::from(output) * u128::from(price.output)
+        + u128::from(cached) * u128::from(price.cached);
+    u64::try_from(micro / 1_000_000).unwrap_or(u64::MAX)
+}
+
+/// Price for `model`: an exact entry wins over the first matching glob.
+fn find_price<'a>(prices: &'a [ModelPrice], model: &str) -> Option<&'a ModelPrice> {
+    prices.iter().find(|p| p.model == model).or_else(|| {
+        prices.iter().find(|p| p.model.contains(['*', '?']) && glob_match(&p.model, model))
+    })
+}
+
+fn target_matches(pattern: &str, model: &str) -> bool {
+    pattern == model || glob_match(pattern, model)
+}
+
+impl SpendTracker {
+    pub fn new(config: SpendConfig) -> Self {
+        Self { config: RwLock::new(config), buckets: DashMap::new(), alerted: DashSet::new() }
+    }
+
+    pub fn config(&self) -> SpendConfig {
+        self.config.read().clone()
+    }
+
+    pub fn set_config(&self, config: SpendConfig) {
+        *self.config.write() = config;
+    }
+
+    /// Price a finished request, add it to the rollups and raise any budget alerts.
+    pub fn record(&self, log: &ProxyRequestLog, events: &dyn ProxyEventBus) {
+        self.record_at(log, events, chrono::Utc::now().timestamp());
+    }
+
+    fn record_at(&self, log: &ProxyRequestLog, events: &dyn ProxyEventBus, now: i64) {
+        let model =
+            log.model.as_deref().or(log.mapped_model.as_deref()).unwrap_or("unknown").to_string();
+        let input = u64::from(log.input_tokens.unwrap_or(0));
+        let output = u64::from(log.output_tokens.unwrap_or(0));
+        let cached = u64::from(log.cached_tokens.unwrap_or(0));
+        let cost = find_price(&self.config.read().prices, &model)
+            .map_or(0, |price| request_cost(price, input, output, cached));
+        let delta = UsageTotals {
+            requests: 1,
+            input_tokens: input,
+            output_tokens: output,
+            cached_tokens: cached,
+            cost,
+        };
+
+        let keys = [
+            (UsageDimension::ApiKey, log.api_key_id.clone()),
+            (UsageDimension::Model, Some(model)),
+            (UsageDimension::Account, log.account_email.clone()),
+        ];
+        for period in PERIODS {
+            let period_start = period.period_start(now);
+            for (dimension, key) in &keys {
+                let Some(key) = key else { continue };
+                let mut bucket = self
+                    .buckets
+                    .entry(BucketKey {
+                        period,
+                        period_start,
+                        dimension: *dimension,
+                        key: key.clone(),
+                    })
+                    .or_default();
+                bucket.total.add(&delta);
+                bucket.unflushed.add(&delta);
+            }
+        }
+
+        if cost > 0 {
+            self.check_budgets(events, now);
+        }
+    }
+
+    /// Spend of `budget` in the period containing `now`.
+    fn spent(&self, budget: &SpendBudget, now: i64) -> u64 {
+        let period_start = budget.period.period_start(now);
+        let target = budget.target.as_deref().unwrap_or_default();
+        let dimension = match budget.scope {
+            BudgetScope::Global => return self.sum_models(budget.period, period_start, |_| true),
+            BudgetScope::Model => {
+                return self.sum_models(budget.period, period_start, |m| target_matches(target, m))
+            },
+            BudgetScope::ApiKey => UsageDimension::ApiKey,
+            BudgetScope::Account => UsageDimension::Account,
+        };
+        let key =
+            BucketKey { period: budget.period, period_start, dimension, key: target.to_string() };
+        self.buckets.get(&key).map_or(0, |b| b.total.cost)
+    }
+
+    fn sum_models(
+        &self,
+        period: SpendPeriod,
+        period_start: i64,
+        matches: impl Fn(&str) -> bool,
+    ) -> u64 {
+        self.buckets
+            .iter()
+            .filter(|e| {
+                let k = e.key();
+                k.period == period
+                    && k.period_start == period_start
+                    && k.dimension == UsageDimension::Model
+                    && matches(&k.key)
+            })
+            .fold(0u64, |sum, e| sum.saturating_add(e.value().total.cost))
+    }
+
+    fn check_budgets(&self, events: &dyn ProxyEventBus, now: i64) {
+        let config = self.config.read().clone();
+        for budget in &config.budgets {
+            let spent = self.spent(budget, now);
+            if spent < budget.limit {
+                continue;
+            }
+            let period_start = budget.period.period_start(now);
+            if !self.alerted.insert((budget.name.clone(), period_start)) {
+                continue;
+            }
+            let alert = BudgetAlert {
+                budget: budget.name.clone(),
+                scope: budget.scope,
+                target: budget.target.clone(),
+                period: budget.period,
+                period_start,
+                spent,
+                limit: budget.limit,
+                action: budget.action,
+            };
+            tracing::warn!(
+                "[Spend] Budget '{}' reached: {} of {} microdollars",
+                alert.budget,
+                spent,
+                budget.limit
+            );
+            // Webhook delivery is up to the notifier, which subscribes to these
+            events.emit_budget_alert(&alert);
+        }
+    }
+
+    /// First blocking budget that is exhausted for a request by `api_key_id` for `model`.
+    /// Model-scoped budgets are only considered when the model is known.
+    pub fn blocking_budget(
+        &self,
+        api_key_id: Option<&str>,
+        model: Option<&str>,
+    ) -> Option<SpendBudget> {
+        self.blocking_budget_at(api_key_id, model, chrono::Utc::now().timestamp())
+    }
+
+    fn blocking_budget_at(
+        &self,
+        api_key_id: Option<&str>,
+        model: Option<&str>,
+        now: i64,
+    ) -> Option<SpendBudget> {
+        let config = self.config.read();
+        config
+            .budgets
+            .iter()
+            .filter(|b| b.action == BudgetAction::Block)
+            .filter(|b| {
+                let target = b.target.as_deref();
+                match b.scope {
+                    BudgetScope::Global => true,
+                    BudgetScope::ApiKey => api_key_id.is_some() && api_key_id == target,
+                    BudgetScope::Model => model
+                        .zip(target)
+                        .is_some_and(|(model, pattern)| target_matches(pattern, model)),
+                    BudgetScope::Account => false,
+                }
+            })
+            .find(|b| self.spent(b, now) >= b.limit)
+            .cloned()
+    }
+
+    /// Whether any blocking budget is configured at all.
+    pub fn has_blocking_budgets(&self) -> bool {
+        self.config.read().budgets.iter().any(|b| b.action == BudgetAction::Block)
+    }
+
+    /// Whether a blocking budget is scoped to a model, so the request model is needed.
+    pub fn blocks_any_model(&self) -> bool {
+        self.config
+            .read()
+            .budgets
+            .iter()
+            .any(|b| b.action == BudgetAction::Block && b.scope == BudgetScope::Model)
+    }
+
+    /// Load the current day and month from SQLite so budgets survive restarts.
+    pub async fn hydrate(&self) -> Result<(), String> {
+        let now = chrono::Utc::now().timestamp();
+        for period in PERIODS {
+            let period_start = period.period_start(now);
+            let query = UsageQuery {
+                period,
+                from: Some(period_start),
+                to: Some(period_start),
+                ..Default::default()
+            };
+            for rollup in spend_db::query_rollups(query).await? {
+                let key = BucketKey {
+                    period,
+                    period_start,
+                    dimension: rollup.dimension,
+                    key: rollup.key,
+                };
+                self.buckets.entry(key).or_default().total.add(&rollup.totals);
+            }
+        }
+        Ok(())
+    }
+
+    /// Write unflushed deltas to SQLite and drop buckets of past periods.
+    pub async fn flush(&self) -> Result<(), String> {
+        let mut rows = Vec::new();
+        for mut entry in self.buckets.iter_mut() {
+            if entry.unflushed.requests == 0 {
+                continue;
+            }
+            let totals = std::mem::take(&mut entry.unflushed);
+            let key = entry.key();
+            rows.push(UsageRollup {
+                period: key.period,
+                period_start: key.period_start,
+                dimension: key.dimension,
+                key: key.key.clone(),
+                totals,
+            });
+        }
+
+        if !rows.is_empty() {
+            if let Err(e) = spend_db::add_rollups(rows.clone()).await {
+                // Keep the deltas for the next attempt
+                for row in rows {
+                    let key = BucketKey {
+                        period: row.period,
+                        period_start: row.period_start,
+                        dimension: row.dimension,
+                        key: row.key,
+                    };
+                    self.buckets.entry(key).or_default().unflushed.add(&row.totals);
+                }
+                return Err(e);
+            }
+        }
+
+        let now = chrono::Utc::now().timestamp();
+        self.buckets.retain(|key, bucket| {
+            key.period_start >= key.period.period_start(now) || bucket.unflushed.requests > 0
+        });
+        let oldest =
+            SpendPeriod::Monthly.period_start(now).min(SpendPeriod::Daily.period_start(now));
+        self.alerted.retain(|(_, start)| *start >= oldest);
+        Ok(())
+    }
+
+    /// Flush in the background every 30 seconds.
+    pub fn start_flush(self: &Arc<Self>) -> tokio::task::JoinHandle<()> {
+        let tracker = Arc::clone(self);
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(FLUSH_INTERVAL);
+            interval.tick().await;
+            loop {
+                interval.tick().await;
+                if let Err(e) = tracker.flush().await {
+                    tracing::warn!("[Spend] Failed to flush usage rollups: {}", e);
+                }
+            }
+        })
+    }
+
+    /// Stored rollups matching `query` plus the current state of every budget.
+    pub async fn report(&self, query: UsageQuery) -> Result<UsageReport, String> {
+        self.flush().await?;
+        let rollups = spend_db::query_rollups(query).await?;
+        Ok(UsageReport { rollups, budgets: self.budget_statuses() })
+    }
+
+    pub fn budget_statuses(&self) -> Vec<BudgetStatus> {
+        let now = chrono::Utc::now().timestamp();
+        let config = self.config.read().clone();
+        config
+            .budgets
+            .into_iter()
+            .map(|budget| {
+                let spent = self.spent(&budget, now);
+                BudgetStatus {
+                    period_start: budget.period.period_start(now),
+                    exceeded: spent >= budget.limit,
+                    spent,
+                    budget,
+                }
+            })
+            .collect()
+    }
+}
+
+impl Default for SpendTracker {
+    fn default() -> Self {
+        Self::new(SpendConfig::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct Alerts(Mutex<Vec<BudgetAlert>>);
+
+    impl ProxyEventBus for Alerts {
+        fn emit_request_log(&self, _log: &ProxyRequestLog) {}
+
+        fn emit_budget_alert(&self, alert: &BudgetAlert) {
+            self.0.lock().unwrap().push(alert.clone());
+        }
+    }
+
+    const NOW: i64 = 1_760_000_000;
+
+    fn price(model: &str, input: u64, output: u64, cached: u64) -> ModelPrice {
+        ModelPrice { model: model.to_string(), input, output, cached }
+    }
+
+    fn budget(name: &str, scope: BudgetScope, target: Option<&str>, limit: u64) -> SpendBudget {
+        SpendBudget {
+            name: name.to_string(),
+            scope,
+            target: target.map(str::to_string),
+            period: SpendPeriod::Daily,
+            limit,
+            action: BudgetAction::Block,
+        }
+    }
+
+    fn log(model: &str, key: Option<&str>, input: u32, output: u32) -> ProxyRequestLog {
+        ProxyRequestLog {
+            id: "req".to_string(),
+            timestamp: NOW * 1000,
+            method: "POST".to_string(),
+            url: "/v1/chat/completions".to_string(),
+            status: 200,
+            duration: 10,
+            model: Some(model.to_string()),
+            mapped_model: None,
+            mapping_reason: None,
+            account_email: Some("a@example.com".to_string()),
+            error: None,
+            request_body: None,
+            upstream_request_body: None,
+            response_body: None,
+            input_tokens: Some(input),
+            output_tokens: Some(output),
+            cached_tokens: None,
+            api_key_id: key.map(str::to_string),
+            api_key_label: None,
+        }
+    }
+
+    #[test]
+    fn prices_exact_before_glob_and_bills_cached_tokens() {
+        let prices =
+            vec![price("gemini-*", 1, 1, 1), price("gemini-3-pro", 2_000_000, 12_000_000, 500_000)];
+        assert_eq!(find_price(&prices, "gemini-3-pro").unwrap().input, 2_000_000);
+        assert_eq!(find_price(&prices, "gemini-3-flash").unwrap().input, 1);
+        assert!(find_price(&prices, "claude-opus-4").is_none());
+
+        // 600k uncached input, 100k output, 400k cached
+        let cost = request_cost(&prices[1], 600_000, 100_000, 400_000);
+        assert_eq!(cost, 1_200_000 + 1_200_000 + 200_000);
+    }
+
+    #[test]
+    fn budgets_block_and_alert_once_per_period() {
+        let tracker = SpendTracker::new(SpendConfig {
+            prices: vec![price("gemini-*", 1_000_000, 0, 0)],
+            budgets: vec![
+                budget("key", BudgetScope::ApiKey, Some("k1"), 2000),
+                budget("pro", BudgetScope::Model, Some("gemini-3-pro*"), 1500),
+                budget("all", BudgetScope::Global, None, 10_000),
+            ],
+        });
+        let alerts = Alerts::default();
+
+        tracker.record_at(&log("gemini-3-pro", Some("k1"), 1000, 0), &alerts, NOW);
+        tracker.record_at(&log("gemini-3-flash", Some("k2"), 1000, 0), &alerts, NOW);
+        assert_eq!(tracker.blocking_budget_at(Some("k1"), Some("gemini-3-flash"), NOW), None);
+
+        tracker.record_at(&log("gemini-3-pro", Some("k1"), 1000, 0), &alerts, NOW);
+        assert_eq!(
+            tracker.blocking_budget_at(Some("k2"), Some("gemini-3-pro-high"), NOW).unwrap().name,
+            "pro"
+        );
+        assert_eq!(tracker.blocking_budget_at(Some("k1"), None, NOW).unwrap().name, "key");
+        assert_eq!(tracker.blocking_budget_at(Some("k2"), Some("gemini-3-flash"), NOW), None);
+
+        tracker.record_at(&log("gemini-3-pro", Some("k1"), 1000, 0), &alerts, NOW);
+        let names: Vec<_> = alerts.0.lock().unwrap().iter().map(|a| a.budget.clone()).collect();
+        assert_eq!(names, ["key", "pro"]);
+
+        // Next day starts from zero
+        let tomorrow = NOW + 86_400;
+        assert_eq!(tracker.blocking_budget_at(Some("k1"), Some("gemini-3-pro"), tomorrow), None);
+    }
+}
@@ -0,0 +1,87 @@
+//! OpenTelemetry span export and W3C trace context propagation.
+//!
+//! Pipeline spans are ordinary `tracing` spans. The server installs a
+//! `tracing-opentelemetry` layer with the tracer from [`Telemetry::init`], which
+//! exports them over OTLP/HTTP. [`crate::proxy::middleware::trace_middleware`]
+//! continues an incoming `traceparent` and runs the request inside [`scope`], so
+//! handlers can tag their logs with [`current_trace_id`].
+
+use std::future::Future;
+
+use antigravity_types::models::TelemetryConfig;
+use opentelemetry::trace::{SpanContext, TraceContextExt, TracerProvider as _};
+use opentelemetry_otlp::WithExportConfig;
+use opentelemetry_sdk::trace::{SdkTracerProvider, Tracer};
+use opentelemetry_sdk::Resource;
+
+tokio::task_local! {
+    static TRACE_ID: String;
+}
+
+/// Installed OTLP exporter. Call [`Telemetry::shutdown`] before exit to flush spans.
+pub struct Telemetry {
+    provider: SdkTracerProvider,
+}
+
+impl Telemetry {
+    /// Build the OTLP/HTTP exporter; `None` when export is disabled.
+    pub fn init(config: &TelemetryConfig) -> Result<Option<Self>, String> {
+        if !config.enabled {
+            return Ok(None);
+        }
+        let mut exporter = opentelemetry_otlp::SpanExporter::builder().with_http();
+        if let Some(endpoint) = &config.otlp_endpoint {
+            exporter = exporter.with_endpoint(endpoint.clone());
+        }
+        let exporter = exporter.build().map_err(|e| format!("OTLP exporter: {}", e))?;
+        let provider = SdkTracerProvider::builder()
+            .with_batch_exporter(exporter)
+            .with_resource(
+                Resource::builder().with_service_name(config.service_name.clone()).build(),
+            )
+            .build();
+        Ok(Some(Self { provider }))
+    }
+
+    pub fn tracer(&self) -> Tracer {
+        self.provider.tracer("antigravity")
+    }
+
+    /// Flush pending spans. Blocks; run it off the async runtime.
+    pub fn shutdown(self) {
+        if let Err(e) = self.provider.shutdown() {
+            tracing::warn!("OTLP exporter shutdown failed: {}", e);
+        }
+    }
+}
+
+/// Trace id of a span context as 32 lowercase hex digits, if valid.
+pub(crate) fn format_trace_id(context: &SpanContext) -> Option<String> {
+    context.is_valid().then(|| context.trace_id().to_string())
+}
+
+/// Trace id of the current span, falling back to the one of the enclosing request.
+pub fn current_trace_id() -> Option<String> {
+    use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+    let context = tracing::Span::current().context();
+    format_trace_id(context.span().span_context()).or_else(|| TRACE_ID.try_with(Clone::clone).ok())
+}
+
+/// Run `future` with `trace_id` as the request's trace id.
+pub async fn scope<F: Future>(trace_id: String, future: F) -> F::Output {
+    TRACE_ID.scope(trace_id, future).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn trace_id_available_inside_scope_only() {
+        assert_eq!(current_trace_id(), None);
+        let id = "4bf92f3577b34da6a3ce929d0e0e4736".to_string();
+        let inner = scope(id.clone(), async { current_trace_id() }).await;
+        assert_eq!(inner, Some(id));
+    }
+}
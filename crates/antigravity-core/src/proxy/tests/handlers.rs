@@ -29,6 +29,12 @@ mod integration_tests {
     fn create_test_app_state() -> AppState {
         let temp_dir =
             std::env::temp_dir().join(format!("antigravity-test-{}", uuid::Uuid::new_v4()));
+        let api_keys = Arc::new(crate::proxy::ApiKeyRegistry::new(
+            crate::modules::api_key_store::ApiKeyStore::Json(temp_dir.join("api_keys.json")),
+        ));
+        let batches = Arc::new(crate::proxy::BatchManager::new(
+            crate::modules::batch_store::BatchStore::Json(temp_dir.join("batches")),
+        ));
         let token_manager = Arc::new(TokenManager::new(temp_dir));
         let custom_mapping = Arc::new(RwLock::new(HashMap::new()));
         let upstream_proxy =
@@ -57,6 +63,7 @@ mod integration_tests {
             upstream_proxy,
             security_config,
             zai,
+            providers: Arc::new(RwLock::new(Vec::new())),
             monitor,
             experimental,
             adaptive_limits,
@@ -67,6 +74,10 @@ mod integration_tests {
             http_client: reqwest::Client::new(),
             provider_rr: Arc::new(AtomicUsize::new(0)),
             zai_vision_mcp: Arc::new(crate::proxy::zai_vision_mcp::ZaiVisionMcpState::new()),
+            api_keys,
+            response_store: Arc::new(crate::proxy::ResponseStore::new()),
+            batches,
+            routing_rules: Arc::new(RwLock::new(Vec::new())),
         }
     }
 
@@ -253,11 +264,10 @@ mod integration_tests {
     }
 
     fn build_models_router_with_auth(state: AppState) -> Router {
-        let security_config = state.security_config.clone();
         Router::new()
             .route("/v1/models", get(crate::proxy::handlers::openai::handle_list_models))
             .layer(axum::middleware::from_fn_with_state(
-                security_config,
+                state.clone(),
                 crate::proxy::middleware::auth_middleware,
             ))
             .with_state(state)
@@ -315,4 +325,1094 @@ mod integration_tests {
 
         response.assert_status_ok();
     }
+
+    #[tokio::test]
+    async fn test_auth_middleware_accepts_client_key_and_enforces_allowlist() {
+        let state = create_test_app_state_with_auth(
+            antigravity_types::models::ProxyAuthMode::AllExceptHealth,
+            "secret-api-key",
+        );
+        let created = state
+            .api_keys
+            .create(antigravity_types::models::CreateApiKeyRequest {
+                label: "ci".to_string(),
+                allowed_models: vec!["gemini-*".to_string()],
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        let bearer =
+            axum::http::HeaderValue::from_str(&format!("Bearer {}", created.secret)).unwrap();
+        let app = Router::new()
+            .route(
+                "/v1/chat/completions",
+                axum::routing::post(|| async { axum::http::StatusCode::OK }),
+            )
+            .route(
+                "/v1/audio/transcriptions",
+                axum::routing::post(|| async { axum::http::StatusCode::OK }),
+            )
+            .layer(axum::middleware::from_fn_with_state(
+                state.clone(),
+                crate::proxy::middleware::auth_middleware,
+            ))
+            .with_state(state);
+        let server = axum_test::TestServer::new(app).unwrap();
+
+        let allowed = server
+            .post("/v1/chat/completions")
+            .add_header(axum::http::header::AUTHORIZATION, bearer.clone())
+            .json(&serde_json::json!({"model": "gemini-3-flash", "messages": []}))
+            .await;
+        allowed.assert_status_ok();
+
+        let denied = server
+            .post("/v1/chat/completions")
+            .add_header(axum::http::header::AUTHORIZATION, bearer.clone())
+            .json(&serde_json::json!({"model": "claude-opus-4-5", "messages": []}))
+            .await;
+        denied.assert_status(axum::http::StatusCode::FORBIDDEN);
+
+        // No model to check against the allowlist
+        let missing = server
+            .post("/v1/chat/completions")
+            .add_header(axum::http::header::AUTHORIZATION, bearer.clone())
+            .json(&serde_json::json!({"messages": []}))
+            .await;
+        missing.assert_status(axum::http::StatusCode::FORBIDDEN);
+
+        // Multipart forms are checked by their `model` field
+        let form = |model: &str| {
+            axum_test::multipart::MultipartForm::new()
+                .add_text("model", model.to_string())
+                .add_part(
+                    "file",
+                    axum_test::multipart::Part::bytes(b"RIFF".to_vec()).file_name("a.wav"),
+                )
+        };
+        server
+            .post("/v1/audio/transcriptions")
+            .add_header(axum::http::header::AUTHORIZATION, bearer.clone())
+            .multipart(form("gemini-3-flash"))
+            .await
+            .assert_status_ok();
+        server
+            .post("/v1/audio/transcriptions")
+            .add_header(axum::http::header::AUTHORIZATION, bearer)
+            .multipart(form("whisper-1"))
+            .await
+            .assert_status(axum::http::StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn test_rewritten_model_is_checked_against_allowlist() {
+        use antigravity_types::models::{RoutingRule, RuleAction, RuleMatch};
+
+        let state = create_test_app_state();
+        let created = state
+            .api_keys
+            .create(antigravity_types::models::CreateApiKeyRequest {
+                label: "ci".to_string(),
+                allowed_models: vec!["gemini-*".to_string()],
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        *state.routing_rules.write().await = vec![RoutingRule {
+            name: "upgrade".to_string(),
+            enabled: true,
+            conditions: RuleMatch::default(),
+            action: RuleAction::RewriteModel { model: "claude-opus-4-5".to_string() },
+        }];
+        let key = crate::proxy::api_keys::ClientKey {
+            id: created.key.id.clone(),
+            label: "ci".to_string(),
+            account_group: None,
+            priority: Default::default(),
+        };
+
+        let mut body = serde_json::json!({"model": "gemini-3-flash", "messages": []});
+        let result = crate::proxy::middleware::routing::route(
+            &state,
+            "/v1/chat/completions",
+            &axum::http::HeaderMap::new(),
+            Some(key),
+            &mut body,
+        )
+        .await;
+        let rejection = result.expect_err("rewrite to a disallowed model must be rejected");
+        assert_eq!(rejection.status(), axum::http::StatusCode::FORBIDDEN);
+        assert_eq!(body["model"], "gemini-3-flash");
+
+        // Without a client key the rewrite applies
+        let result = crate::proxy::middleware::routing::route(
+            &state,
+            "/v1/chat/completions",
+            &axum::http::HeaderMap::new(),
+            None,
+            &mut body,
+        )
+        .await;
+        assert!(result.is_ok());
+        assert_eq!(body["model"], "claude-opus-4-5");
+    }
+
+    #[tokio::test]
+    async fn test_claude_count_tokens_estimates_without_accounts() {
+        let state = create_test_app_state();
+        let app = Router::new()
+            .route(
+                "/v1/messages/count_tokens",
+                axum::routing::post(crate::proxy::handlers::claude::handle_count_tokens),
+            )
+            .with_state(state);
+
+        let response = axum_test::TestServer::new(app)
+            .unwrap()
+            .post("/v1/messages/count_tokens")
+            .json(&serde_json::json!({
+                "model": "claude-sonnet-4-5",
+                "messages": [{"role": "user", "content": "Hello, how are you today?"}]
+            }))
+            .await;
+
+        response.assert_status_ok();
+        let body: serde_json::Value = response.json();
+        assert!(body["input_tokens"].as_u64().is_some_and(|n| n > 0));
+    }
+
+    fn build_responses_router(state: AppState) -> Router {
+        Router::new()
+            .route(
+                "/v1/responses",
+                axum::routing::post(crate::proxy::handlers::openai::handle_create_response),
+            )
+            .route(
+                "/v1/responses/:id",
+                get(crate::proxy::handlers::openai::handle_get_response)
+                    .delete(crate::proxy::handlers::openai::handle_delete_response),
+            )
+            .with_state(state)
+    }
+
+    #[tokio::test]
+    async fn test_responses_unknown_previous_response_returns_404() {
+        let server =
+            axum_test::TestServer::new(build_responses_router(create_test_app_state())).unwrap();
+
+        let response = server
+            .post("/v1/responses")
+            .json(&serde_json::json!({
+                "model": "gemini-3-flash",
+                "input": "Hello",
+                "previous_response_id": "resp_missing"
+            }))
+            .await;
+        response.assert_status_not_found();
+
+        server.get("/v1/responses/resp_missing").await.assert_status_not_found();
+        server.delete("/v1/responses/resp_missing").await.assert_status_not_found();
+    }
+
+    #[tokio::test]
+    async fn test_responses_get_and_delete_stored_response() {
+        use crate::proxy::mappers::openai::responses::ResponseObject;
+
+        let state = create_test_app_state();
+        state.response_store.insert(
+            ResponseObject::new("resp_1".to_string(), "gemini-3-flash".to_string(), 0),
+            Vec::new(),
+            None,
+        );
+        let server = axum_test::TestServer::new(build_responses_router(state)).unwrap();
+
+        let response = server.get("/v1/responses/resp_1").await;
+        response.assert_status_ok();
+        let body: serde_json::Value = response.json();
+        assert_eq!(body["object"], "response");
+
+        let response = server.delete("/v1/responses/resp_1").await;
+        response.assert_status_ok();
+        let body: serde_json::Value = response.json();
+        assert_eq!(body["deleted"], true);
+        server.get("/v1/responses/resp_1").await.assert_status_not_found();
+    }
+
+    #[tokio::test]
+    async fn test_responses_are_scoped_to_client_key() {
+        use crate::proxy::api_keys::ClientKey;
+        use crate::proxy::mappers::openai::responses::ResponseObject;
+
+        let state = create_test_app_state();
+        state.response_store.insert(
+            ResponseObject::new("resp_1".to_string(), "gemini-3-flash".to_string(), 0),
+            Vec::new(),
+            Some("k1".to_string()),
+        );
+        let as_key = |id: &str| {
+            let key = ClientKey {
+                id: id.to_string(),
+                label: id.to_string(),
+                account_group: None,
+                priority: Default::default(),
+            };
+            axum_test::TestServer::new(
+                build_responses_router(state.clone()).layer(axum::Extension(key)),
+            )
+            .unwrap()
+        };
+
+        let other = as_key("k2");
+        other.get("/v1/responses/resp_1").await.assert_status_not_found();
+        other.delete("/v1/responses/resp_1").await.assert_status_not_found();
+        other
+            .post("/v1/responses")
+            .json(&serde_json::json!({
+                "model": "gemini-3-flash",
+                "input": "Hi",
+                "previous_response_id": "resp_1"
+            }))
+            .await
+            .assert_status_not_found();
+
+        as_key("k1").get("/v1/responses/resp_1").await.assert_status_ok();
+    }
+
+    #[tokio::test]
+    async fn test_responses_no_accounts_returns_503() {
+        let server =
+            axum_test::TestServer::new(build_responses_router(create_test_app_state())).unwrap();
+
+        let response = server
+            .post("/v1/responses")
+            .json(&serde_json::json!({
+                "model": "gemini-3-pro",
+                "input": [{"role": "user", "content": [{"type": "input_text", "text": "Hello"}]}]
+            }))
+            .await;
+        response.assert_status(axum::http::StatusCode::SERVICE_UNAVAILABLE);
+    }
+
+    fn build_embeddings_router(state: AppState) -> Router {
+        Router::new()
+            .route(
+                "/v1/embeddings",
+                axum::routing::post(crate::proxy::handlers::embeddings::handle_embeddings),
+            )
+            .route(
+                "/v1beta/models/:model",
+                axum::routing::post(crate::proxy::handlers::gemini::handle_generate),
+            )
+            .with_state(state)
+    }
+
+    #[tokio::test]
+    async fn test_embeddings_rejects_invalid_input() {
+        let server =
+            axum_test::TestServer::new(build_embeddings_router(create_test_app_state())).unwrap();
+
+        let response = server
+            .post("/v1/embeddings")
+            .json(&serde_json::json!({"model": "text-embedding-3-small", "input": []}))
+            .await;
+        response.assert_status_bad_request();
+    }
+
+    #[tokio::test]
+    async fn test_embeddings_no_accounts_returns_503() {
+        let server =
+            axum_test::TestServer::new(build_embeddings_router(create_test_app_state())).unwrap();
+
+        let response = server
+            .post("/v1/embeddings")
+            .json(&serde_json::json!({
+                "model": "text-embedding-3-small",
+                "input": ["a", "b"],
+                "dimensions": 256
+            }))
+            .await;
+        response.assert_status(axum::http::StatusCode::SERVICE_UNAVAILABLE);
+
+        let response = server
+            .post("/v1beta/models/gemini-embedding-001:batchEmbedContents")
+            .json(&serde_json::json!({
+                "requests": [{"model": "models/gemini-embedding-001", "content": {"parts": [{"text": "a"}]}}]
+            }))
+            .await;
+        response.assert_status(axum::http::StatusCode::SERVICE_UNAVAILABLE);
+    }
+
+    fn build_speech_router(state: AppState) -> Router {
+        Router::new()
+            .route(
+                "/v1/audio/speech",
+                axum::routing::post(crate::proxy::handlers::speech::handle_audio_speech),
+            )
+            .with_state(state)
+    }
+
+    #[tokio::test]
+    async fn test_speech_rejects_invalid_requests() {
+        let server =
+            axum_test::TestServer::new(build_speech_router(create_test_app_state())).unwrap();
+
+        for body in [
+            serde_json::json!({"model": "tts-1", "input": "Hi", "voice": "alloy", "response_format": "mp3"}),
+            serde_json::json!({"model": "tts-1", "input": "Hi", "voice": "nobody"}),
+            serde_json::json!({"model": "gpt-4o", "input": "Hi", "voice": "alloy"}),
+        ] {
+            server.post("/v1/audio/speech").json(&body).await.assert_status_bad_request();
+        }
+    }
+
+    #[tokio::test]
+    async fn test_speech_no_accounts_returns_503() {
+        let server =
+            axum_test::TestServer::new(build_speech_router(create_test_app_state())).unwrap();
+
+        for stream_format in [None, Some("audio")] {
+            let mut body = serde_json::json!({"model": "tts-1", "input": "Hi", "voice": "alloy"});
+            if let Some(stream_format) = stream_format {
+                body["stream_format"] = serde_json::json!(stream_format);
+            }
+            let response = server.post("/v1/audio/speech").json(&body).await;
+            response.assert_status(axum::http::StatusCode::SERVICE_UNAVAILABLE);
+        }
+    }
+
+    fn build_images_router(state: AppState) -> Router {
+        Router::new()
+            .route(
+                "/v1/images/generations",
+                axum::routing::post(crate::proxy::handlers::openai::handle_images_generations),
+            )
+            .route(
+                "/v1/images/edits",
+                axum::routing::post(crate::proxy::handlers::openai::handle_images_edits),
+            )
+            .with_state(state)
+    }
+
+    #[tokio::test]
+    async fn test_images_rejects_invalid_requests() {
+        let server =
+            axum_test::TestServer::new(build_images_router(create_test_app_state())).unwrap();
+
+        let response = server
+            .post("/v1/images/generations")
+            .json(&serde_json::json!({"prompt": "a cat", "n": 11}))
+            .await;
+        response.assert_status_bad_request();
+
+        let response = server
+            .post("/v1/images/generations")
+            .json(&serde_json::json!({"prompt": "a cat", "model": "gemini-3-flash"}))
+            .await;
+        response.assert_status_bad_request();
+
+        let form = axum_test::multipart::MultipartForm::new().add_text("prompt", "add a hat");
+        server.post("/v1/images/edits").multipart(form).await.assert_status_bad_request();
+    }
+
+    #[tokio::test]
+    async fn test_images_no_accounts_returns_503() {
+        let server =
+            axum_test::TestServer::new(build_images_router(create_test_app_state())).unwrap();
+
+        let response = server
+            .post("/v1/images/generations")
+            .json(&serde_json::json!({"prompt": "a cat", "model": "dall-e-3", "n": 2, "size": "1792x1024"}))
+            .await;
+        response.assert_status(axum::http::StatusCode::SERVICE_UNAVAILABLE);
+
+        let image = axum_test::multipart::Part::bytes(vec![0x89, 0x50, 0x4E, 0x47, 0, 0, 0, 0])
+            .file_name("cat.png")
+            .mime_type("image/png");
+        let form = axum_test::multipart::MultipartForm::new()
+            .add_text("prompt", "add a hat")
+            .add_part("image", image);
+        let response = server.post("/v1/images/edits").multipart(form).await;
+        response.assert_status(axum::http::StatusCode::SERVICE_UNAVAILABLE);
+    }
+
+    fn build_messages_router(state: AppState) -> Router {
+        Router::new()
+            .route(
+                "/v1/messages",
+                axum::routing::post(crate::proxy::handlers::claude::handle_messages),
+            )
+            .with_state(state)
+    }
+
+    async fn set_zai_mode(state: &AppState, mode: crate::proxy::ZaiDispatchMode) {
+        let mut zai = state.zai.write().await;
+        zai.enabled = true;
+        zai.api_key = "test-key".to_string();
+        // Nothing listens here; forwarding fails fast with 502
+        zai.base_url = "http://127.0.0.1:9".to_string();
+        zai.dispatch_mode = mode;
+    }
+
+    async fn post_message(state: AppState) -> axum_test::TestResponse {
+        axum_test::TestServer::new(build_messages_router(state))
+            .unwrap()
+            .post("/v1/messages")
+            .json(&serde_json::json!({
+                "model": "claude-sonnet-4-5",
+                "max_tokens": 16,
+                "messages": [{"role": "user", "content": "Hello there"}]
+            }))
+            .await
+    }
+
+    #[tokio::test]
+    async fn test_zai_dispatch_modes_record_mapping_reason() {
+        use crate::proxy::ZaiDispatchMode;
+
+        for (mode, reason) in [
+            (ZaiDispatchMode::Exclusive, "zai:exclusive"),
+            (ZaiDispatchMode::Pooled, "zai:pooled"),
+            (ZaiDispatchMode::Fallback, "zai:fallback"),
+        ] {
+            let state = create_test_app_state();
+            set_zai_mode(&state, mode).await;
+            let response = post_message(state).await;
+            response.assert_status(axum::http::StatusCode::BAD_GATEWAY);
+            assert_eq!(response.header("x-mapping-reason"), reason, "mode {}", mode);
+            assert_eq!(response.header("x-mapped-model"), "glm-4.7");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_zai_off_uses_google_pool() {
+        let state = create_test_app_state();
+        set_zai_mode(&state, crate::proxy::ZaiDispatchMode::Off).await;
+        let response = post_message(state).await;
+        response.assert_status(axum::http::StatusCode::SERVICE_UNAVAILABLE);
+        assert!(response.maybe_header("x-mapping-reason").is_none());
+    }
+
+    async fn set_providers(
+        state: &AppState,
+        protocol: antigravity_types::models::ProviderProtocol,
+    ) {
+        *state.providers.write().await = vec![antigravity_types::models::ProviderConfig {
+            name: "local".to_string(),
+            enabled: true,
+            base_url: "http://127.0.0.1:9/v1".to_string(),
+            api_key: String::new(),
+            protocol,
+            models: vec!["llama-*".to_string()],
+            priority: 0,
+        }];
+    }
+
+    #[tokio::test]
+    async fn test_chat_completions_routes_to_provider() {
+        let state = create_test_app_state();
+        set_providers(&state, antigravity_types::models::ProviderProtocol::Openai).await;
+        let app = Router::new()
+            .route(
+                "/v1/chat/completions",
+                axum::routing::post(crate::proxy::handlers::openai::handle_chat_completions),
+            )
+            .with_state(state);
+
+        let response = axum_test::TestServer::new(app)
+            .unwrap()
+            .post("/v1/chat/completions")
+            .json(&serde_json::json!({
+                "model": "llama-3.1-8b",
+                "messages": [{"role": "user", "content": "Hello"}]
+            }))
+            .await;
+
+        // Unreachable provider: the request never falls back to the Google pool
+        response.assert_status(axum::http::StatusCode::BAD_GATEWAY);
+        assert_eq!(response.header("x-mapped-model"), "local:llama-3.1-8b");
+        assert_eq!(response.header("x-mapping-reason"), "provider");
+    }
+
+    #[tokio::test]
+    async fn test_messages_rejects_provider_with_other_protocol() {
+        let state = create_test_app_state();
+        set_providers(&state, antigravity_types::models::ProviderProtocol::Openai).await;
+        let app = Router::new()
+            .route(
+                "/v1/messages",
+                axum::routing::post(crate::proxy::handlers::claude::handle_messages),
+            )
+            .with_state(state);
+
+        let response = axum_test::TestServer::new(app)
+            .unwrap()
+            .post("/v1/messages")
+            .json(&serde_json::json!({
+                "model": "local:llama-3.1-8b",
+                "max_tokens": 16,
+                "messages": [{"role": "user", "content": "Hello"}]
+            }))
+            .await;
+
+        response.assert_status(axum::http::StatusCode::BAD_REQUEST);
+        assert!(response.text().contains("anthropic protocol"));
+    }
+
+    async fn post_chat_with_n(model: &str, n: u32) -> axum_test::TestResponse {
+        let app = Router::new()
+            .route(
+                "/v1/chat/completions",
+                axum::routing::post(crate::proxy::handlers::openai::handle_chat_completions),
+            )
+            .with_state(create_test_app_state());
+        axum_test::TestServer::new(app)
+            .unwrap()
+            .post("/v1/chat/completions")
+            .json(&serde_json::json!({
+                "model": model,
+                "n": n,
+                "messages": [{"role": "user", "content": "Hello"}]
+            }))
+            .await
+    }
+
+    #[tokio::test]
+    async fn test_chat_completions_rejects_out_of_range_n() {
+        post_chat_with_n("gemini-3-flash", 0).await.assert_status_bad_request();
+        post_chat_with_n("gemini-3-flash", 9).await.assert_status_bad_request();
+    }
+
+    #[tokio::test]
+    async fn test_chat_completions_fan_out_surfaces_pool_error() {
+        // Claude models fan out; with an empty pool every call fails the same way
+        let response = post_chat_with_n("claude-sonnet-4-5", 2).await;
+        response.assert_status(axum::http::StatusCode::SERVICE_UNAVAILABLE);
+    }
+
+    #[tokio::test]
+    async fn test_chat_completions_rejects_streaming_strict_schema() {
+        let app = Router::new()
+            .route(
+                "/v1/chat/completions",
+                axum::routing::post(crate::proxy::handlers::openai::handle_chat_completions),
+            )
+            .with_state(create_test_app_state());
+        let response = axum_test::TestServer::new(app)
+            .unwrap()
+            .post("/v1/chat/completions")
+            .json(&serde_json::json!({
+                "model": "gemini-3-flash",
+                "stream": true,
+                "messages": [{"role": "user", "content": "Hello"}],
+                "response_format": {
+                    "type": "json_schema",
+                    "json_schema": {"name": "out", "strict": true, "schema": {"type": "object"}}
+                }
+            }))
+            .await;
+        response.assert_status_bad_request();
+        assert!(response.text().contains("stream"));
+    }
+
+    fn build_batches_router(state: AppState) -> Router {
+        use crate::proxy::handlers::{claude, openai};
+        Router::new()
+            .route(
+                "/v1/messages/batches",
+                get(claude::handle_list_batches).post(claude::handle_create_batch),
+            )
+            .route("/v1/messages/batches/:id", get(claude::handle_get_batch))
+            .route(
+                "/v1/messages/batches/:id/cancel",
+                axum::routing::post(claude::handle_cancel_batch),
+            )
+            .route("/v1/messages/batches/:id/results", get(claude::handle_batch_results))
+            .route("/v1/batches", axum::routing::post(openai::handle_create_batch))
+            .route("/v1/batches/:id", get(openai::handle_get_batch))
+            .route("/v1/batches/:id/cancel", axum::routing::post(openai::handle_cancel_batch))
+            .route("/v1/files", axum::routing::post(openai::handle_upload_file))
+            .route("/v1/files/:id/content", get(openai::handle_file_content))
+            .with_state(state)
+    }
+
+    /// Poll `path` until the batch `field` reads `expected`.
+    async fn wait_for_batch(
+        server: &axum_test::TestServer,
+        path: &str,
+        field: &str,
+        expected: &str,
+    ) -> serde_json::Value {
+        for _ in 0..100 {
+            let body: serde_json::Value = server.get(path).await.json();
+            if body[field] == expected {
+                return body;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+        }
+        panic!("batch at {} never reached {} = {}", path, field, expected);
+    }
+
+    #[tokio::test]
+    async fn test_message_batch_validation() {
+        let state = create_test_app_state();
+        let server = axum_test::TestServer::new(build_batches_router(state.clone())).unwrap();
+        let params = serde_json::json!({
+            "model": "claude-sonnet-4-5",
+            "max_tokens": 16,
+            "messages": [{"role": "user", "content": "Hi"}]
+        });
+
+        let empty =
+            server.post("/v1/messages/batches").json(&serde_json::json!({"requests": []})).await;
+        empty.assert_status_bad_request();
+
+        let duplicate = server
+            .post("/v1/messages/batches")
+            .json(&serde_json::json!({"requests": [
+                {"custom_id": "a", "params": params},
+                {"custom_id": "a", "params": params}
+            ]}))
+            .await;
+        duplicate.assert_status_bad_request();
+        assert!(duplicate.text().contains("duplicate custom_id"));
+
+        let mut streaming = params.clone();
+        streaming["stream"] = serde_json::json!(true);
+        server
+            .post("/v1/messages/batches")
+            .json(&serde_json::json!({"requests": [{"custom_id": "a", "params": streaming}]}))
+            .await
+            .assert_status_bad_request();
+
+        server.get("/v1/messages/batches/msgbatch_missing").await.assert_status_not_found();
+
+        // Requests whose model is blocked by an exhausted budget are refused up front
+        use antigravity_types::models::{
+            BudgetAction, BudgetScope, ModelPrice, SpendBudget, SpendConfig, SpendPeriod,
+        };
+        state.monitor.set_spend_config(SpendConfig {
+            prices: vec![ModelPrice {
+                model: "claude-*".to_string(),
+                input: 1_000_000,
+                output: 0,
+                cached: 0,
+            }],
+            budgets: vec![SpendBudget {
+                name: "claude".to_string(),
+                scope: BudgetScope::Model,
+                target: Some("claude-*".to_string()),
+                period: SpendPeriod::Daily,
+                limit: 1,
+                action: BudgetAction::Block,
+            }],
+        });
+        state
+            .monitor
+            .log_request(crate::proxy::monitor::ProxyRequestLog {
+                id: "req".to_string(),
+                timestamp: chrono::Utc::now().timestamp_millis(),
+                method: "POST".to_string(),
+                url: "/v1/messages".to_string(),
+                status: 200,
+                duration: 10,
+                model: Some("claude-sonnet-4-5".to_string()),
+                mapped_model: None,
+                mapping_reason: None,
+                account_email: None,
+                error: None,
+                request_body: None,
+                upstream_request_body: None,
+                response_body: None,
+                input_tokens: Some(1000),
+                output_tokens: Some(0),
+                cached_tokens: None,
+                api_key_id: None,
+                api_key_label: None,
+            })
+            .await;
+        let blocked = server
+            .post("/v1/messages/batches")
+            .json(&serde_json::json!({"requests": [{"custom_id": "a", "params": params}]}))
+            .await;
+        blocked.assert_status(axum::http::StatusCode::TOO_MANY_REQUESTS);
+        assert!(blocked.text().contains("'claude'"));
+    }
+
+    #[tokio::test]
+    async fn test_usage_is_charged_with_monitor_disabled() {
+        use antigravity_types::models::{
+            BudgetAction, BudgetScope, ModelPrice, SpendBudget, SpendConfig, SpendPeriod,
+        };
+        let mut state = create_test_app_state();
+        let monitor = ProxyMonitor::new().with_api_keys(Arc::clone(&state.api_keys));
+        monitor.set_enabled(false);
+        monitor.set_spend_config(SpendConfig {
+            prices: vec![ModelPrice {
+                model: "claude-*".to_string(),
+                input: 1_000_000,
+                output: 0,
+                cached: 0,
+            }],
+            budgets: vec![SpendBudget {
+                name: "claude".to_string(),
+                scope: BudgetScope::Model,
+                target: Some("claude-*".to_string()),
+                period: SpendPeriod::Daily,
+                limit: 1_000_000_000,
+                action: BudgetAction::Warn,
+            }],
+        });
+        state.monitor = Arc::new(monitor);
+        let key = state
+            .api_keys
+            .create(antigravity_types::models::CreateApiKeyRequest {
+                label: "ci".to_string(),
+                ..Default::default()
+            })
+            .await
+            .unwrap()
+            .key;
+        let client_key = crate::proxy::api_keys::ClientKey {
+            id: key.id.clone(),
+            label: key.label,
+            account_group: None,
+            priority: antigravity_types::models::PriorityClass::Interactive,
+        };
+        let app = Router::new()
+            .route(
+                "/v1/messages",
+                axum::routing::post(move || {
+                    let client_key = client_key.clone();
+                    async move {
+                        let mut response = axum::response::IntoResponse::into_response(axum::Json(
+                            serde_json::json!({
+                                "usage": {"input_tokens": 1000, "output_tokens": 24}
+                            }),
+                        ));
+                        response.extensions_mut().insert(client_key);
+                        response
+                    }
+                }),
+            )
+            .layer(axum::middleware::from_fn_with_state(
+                state.clone(),
+                crate::proxy::middleware::monitor::monitor_middleware,
+            ))
+            .with_state(state.clone());
+
+        axum_test::TestServer::new(app)
+            .unwrap()
+            .post("/v1/messages")
+            .json(&serde_json::json!({"model": "claude-sonnet-4-5", "messages": []}))
+            .await
+            .assert_status_ok();
+
+        // The response is inspected after it has been sent
+        for _ in 0..100 {
+            if state.api_keys.get(&key.id).unwrap().usage.requests > 0 {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+        let usage = state.api_keys.get(&key.id).unwrap().usage;
+        assert_eq!((usage.requests, usage.tokens), (1, 1024));
+        let spent = state.monitor.spend().budget_statuses()[0].spent;
+        assert_eq!(spent, 1000);
+        assert!(state.monitor.get_logs(Some(10)).await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_large_request_body_is_forwarded_without_content_length() {
+        let state = create_test_app_state();
+        let app = Router::new()
+            .route(
+                "/v1/messages",
+                axum::routing::post(|body: bytes::Bytes| async move {
+                    axum::Json(serde_json::json!({"received": body.len()}))
+                }),
+            )
+            .layer(axum::middleware::from_fn_with_state(
+                state.clone(),
+                crate::proxy::middleware::monitor::monitor_middleware,
+            ))
+            .with_state(state.clone());
+
+        // Larger than what the monitor buffers, so the rest is streamed after the prefix
+        let body = serde_json::json!({
+            "model": "claude-sonnet-4-5",
+            "messages": [{"role": "user", "content": "x".repeat(600 * 1024)}]
+        })
+        .to_string();
+        let response = axum_test::TestServer::new(app)
+            .unwrap()
+            .post("/v1/messages")
+            .bytes(body.clone().into())
+            .await;
+        response.assert_status_ok();
+        assert_eq!(response.json::<serde_json::Value>()["received"], body.len());
+    }
+
+    #[tokio::test]
+    async fn test_message_batch_cancel_and_results() {
+        // No accounts, so the batch waits for spare quota until it is canceled
+        let server =
+            axum_test::TestServer::new(build_batches_router(create_test_app_state())).unwrap();
+        let request = |custom_id: &str| {
+            serde_json::json!({
+                "custom_id": custom_id,
+                "params": {
+                    "model": "claude-sonnet-4-5",
+                    "max_tokens": 16,
+                    "messages": [{"role": "user", "content": "Hi"}]
+                }
+            })
+        };
+
+        let created = server
+            .post("/v1/messages/batches")
+            .json(&serde_json::json!({"requests": [request("first"), request("second")]}))
+            .await;
+        created.assert_status_ok();
+        let batch: serde_json::Value = created.json();
+        assert_eq!(batch["type"], "message_batch");
+        assert_eq!(batch["processing_status"], "in_progress");
+        assert_eq!(batch["request_counts"]["processing"], 2);
+        let id = batch["id"].as_str().unwrap().to_string();
+
+        server
+            .get(&format!("/v1/messages/batches/{}/results", id))
+            .await
+            .assert_status_bad_request();
+
+        let canceled = server.post(&format!("/v1/messages/batches/{}/cancel", id)).await;
+        canceled.assert_status_ok();
+        assert!(canceled.json::<serde_json::Value>()["cancel_initiated_at"].is_string());
+
+        let path = format!("/v1/messages/batches/{}", id);
+        let ended = wait_for_batch(&server, &path, "processing_status", "ended").await;
+        assert_eq!(ended["request_counts"]["canceled"], 2);
+        assert_eq!(ended["results_url"], format!("/v1/messages/batches/{}/results", id));
+
+        let results = server.get(&format!("/v1/messages/batches/{}/results", id)).await;
+        results.assert_status_ok();
+        let lines: Vec<serde_json::Value> =
+            results.text().lines().map(|line| serde_json::from_str(line).unwrap()).collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0]["custom_id"], "first");
+        assert_eq!(lines[1]["result"]["type"], "canceled");
+
+        let listed: serde_json::Value = server.get("/v1/messages/batches?limit=1").await.json();
+        assert_eq!(listed["data"][0]["id"], id.as_str());
+        assert_eq!(listed["has_more"], false);
+    }
+
+    #[tokio::test]
+    async fn test_openai_batch_from_uploaded_file() {
+        use axum_test::multipart::{MultipartForm, Part};
+
+        let server =
+            axum_test::TestServer::new(build_batches_router(create_test_app_state())).unwrap();
+        let line = serde_json::json!({
+            "custom_id": "req-1",
+            "method": "POST",
+            "url": "/v1/chat/completions",
+            "body": {"model": "gemini-3-flash", "messages": [{"role": "user", "content": "Hi"}]}
+        });
+        let form = MultipartForm::new().add_text("purpose", "batch").add_part(
+            "file",
+            Part::bytes(format!("{}\n", line).into_bytes()).file_name("in.jsonl"),
+        );
+        let uploaded = server.post("/v1/files").multipart(form).await;
+        uploaded.assert_status_ok();
+        let file: serde_json::Value = uploaded.json();
+        assert_eq!(file["object"], "file");
+
+        let unsupported = server
+            .post("/v1/batches")
+            .json(&serde_json::json!({
+                "input_file_id": file["id"],
+                "endpoint": "/v1/embeddings",
+                "completion_window": "24h"
+            }))
+            .await;
+        unsupported.assert_status_bad_request();
+
+        let created = server
+            .post("/v1/batches")
+            .json(&serde_json::json!({
+                "input_file_id": file["id"],
+                "endpoint": "/v1/chat/completions",
+                "completion_window": "24h",
+                "metadata": {"run": "nightly"}
+            }))
+            .await;
+        created.assert_status_ok();
+        let batch: serde_json::Value = created.json();
+        assert_eq!(batch["object"], "batch");
+        assert_eq!(batch["metadata"]["run"], "nightly");
+        let id = batch["id"].as_str().unwrap().to_string();
+
+        server.post(&format!("/v1/batches/{}/cancel", id)).await.assert_status_ok();
+        let ended =
+            wait_for_batch(&server, &format!("/v1/batches/{}", id), "status", "cancelled").await;
+        assert_eq!(ended["request_counts"]["total"], 1);
+        assert!(ended["output_file_id"].is_null());
+
+        let error_file = ended["error_file_id"].as_str().unwrap();
+        let content = server.get(&format!("/v1/files/{}/content", error_file)).await.text();
+        let error: serde_json::Value = serde_json::from_str(content.trim()).unwrap();
+        assert_eq!(error["custom_id"], "req-1");
+        assert_eq!(error["error"]["code"], "batch_cancelled");
+    }
+
+    /// Adds ready-to-use accounts whose access token is `token_<email>`.
+    fn add_accounts(state: &AppState, emails: &[&str]) {
+        for email in emails {
+            let token = crate::proxy::token_manager::ProxyToken::new(
+                email.to_string(),
+                format!("token_{email}"),
+                "refresh".to_string(),
+                3600,
+                chrono::Utc::now().timestamp() + 3600,
+                email.to_string(),
+                std::path::PathBuf::from("/tmp"),
+                Some(format!("project-{email}")),
+                Some("g1-pro-tier".to_string()),
+                Some(100),
+                std::collections::HashSet::new(),
+                1.0,
+                std::collections::HashSet::new(),
+                None,
+            );
+            state.token_manager.tokens.insert(token.account_id.clone(), token);
+        }
+    }
+
+    /// Points `state` at a local v1internal stand-in that answers every call with
+    /// `respond(access_token)` and returns the access tokens it saw, in order.
+    async fn mock_upstream<F>(
+        state: &mut AppState,
+        respond: F,
+    ) -> Arc<std::sync::Mutex<Vec<String>>>
+    where
+        F: Fn(&str) -> (axum::http::StatusCode, String) + Clone + Send + Sync + 'static,
+    {
+        let seen = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let log = Arc::clone(&seen);
+        let app = Router::new().fallback(move |headers: axum::http::HeaderMap| {
+            let log = Arc::clone(&log);
+            let respond = respond.clone();
+            async move {
+                let token = headers
+                    .get(axum::http::header::AUTHORIZATION)
+                    .and_then(|v| v.to_str().ok())
+                    .and_then(|v| v.strip_prefix("Bearer "))
+                    .unwrap_or_default()
+                    .to_string();
+                log.lock().unwrap().push(token.clone());
+                respond(&token)
+            }
+        });
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        state.upstream = Arc::new(crate::proxy::upstream::client::UpstreamClient::new(
+            reqwest::Client::new(),
+            Arc::clone(&state.upstream_proxy),
+            Some(vec![format!("http://{}/v1internal", addr)]),
+        ));
+        seen
+    }
+
+    #[tokio::test]
+    async fn test_single_shot_rotation_tries_each_account_once() {
+        let mut state = create_test_app_state();
+        add_accounts(&state, &["a@test.com", "b@test.com", "c@test.com"]);
+        let seen = mock_upstream(&mut state, |_| {
+            let body = serde_json::json!({"error": {"message": "gone"}});
+            (axum::http::StatusCode::NOT_FOUND, body.to_string())
+        })
+        .await;
+        let app = Router::new()
+            .route(
+                "/v1/embeddings",
+                axum::routing::post(crate::proxy::handlers::embeddings::handle_embeddings),
+            )
+            .with_state(state);
+
+        let response = axum_test::TestServer::new(app)
+            .unwrap()
+            .post("/v1/embeddings")
+            .json(&serde_json::json!({"model": "text-embedding-004", "input": "Hello"}))
+            .await;
+
+        assert!(!response.status_code().is_success());
+        let mut seen = seen.lock().unwrap().clone();
+        assert_eq!(seen.len(), 3);
+        seen.sort();
+        seen.dedup();
+        assert_eq!(seen.len(), 3, "an account was retried before the others were tried");
+    }
+
+    /// Upstream whose `call`-th response (from 1) is a success when `succeeds(call)`, and a
+    /// non-retryable 400 otherwise.
+    fn numbered_responses(
+        succeeds: fn(usize) -> bool,
+    ) -> impl Fn(&str) -> (axum::http::StatusCode, String) + Clone + Send + Sync + 'static {
+        let calls = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        move |_| {
+            let call = calls.fetch_add(1, std::sync::atomic::Ordering::SeqCst) + 1;
+            if !succeeds(call) {
+                let body = serde_json::json!({"error": {"code": 400, "message": "bad request"}});
+                return (axum::http::StatusCode::BAD_REQUEST, body.to_string());
+            }
+            let chunk = serde_json::json!({"response": {
+                "candidates": [{
+                    "content": {"role": "model", "parts": [{"text": format!("choice {call}")}]},
+                    "finishReason": "STOP",
+                }],
+                "usageMetadata": {
+                    "promptTokenCount": 5,
+                    "candidatesTokenCount": 2,
+                    "totalTokenCount": 7,
+                },
+            }});
+            (axum::http::StatusCode::OK, format!("data: {}\n\n", chunk))
+        }
+    }
+
+    async fn fan_out_with(succeeds: fn(usize) -> bool) -> axum_test::TestResponse {
+        let mut state = create_test_app_state();
+        add_accounts(&state, &["a@test.com", "b@test.com", "c@test.com"]);
+        mock_upstream(&mut state, numbered_responses(succeeds)).await;
+        let app = Router::new()
+            .route(
+                "/v1/chat/completions",
+                axum::routing::post(crate::proxy::handlers::openai::handle_chat_completions),
+            )
+            .with_state(state);
+        axum_test::TestServer::new(app)
+            .unwrap()
+            .post("/v1/chat/completions")
+            .json(&serde_json::json!({
+                "model": "claude-sonnet-4-5",
+                "n": 2,
+                "messages": [{"role": "user", "content": "Hello"}]
+            }))
+            .await
+    }
+
+    #[tokio::test]
+    async fn test_chat_completions_fan_out_retries_failed_choices() {
+        let response = fan_out_with(|call| call != 1).await;
+        response.assert_status_ok();
+        let json: serde_json::Value = response.json();
+        assert_eq!(json["choices"].as_array().unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_chat_completions_fan_out_fails_when_choices_stay_missing() {
+        let response = fan_out_with(|call| call == 1).await;
+        response.assert_status(axum::http::StatusCode::BAD_REQUEST);
+    }
 }
@@ -0,0 +1,79 @@
+use super::TokenManager;
+use crate::modules::config;
+use antigravity_types::models::PriorityClass;
+use std::collections::HashSet;
+use std::time::Duration;
+
+/// Whether the pool could serve a request right now.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum PoolState {
+    /// At least one account could take the request
+    Available,
+    /// No account outside the exclusions (or the pinned group) exists
+    Empty,
+    /// Every account is busy; `reset_in` is when the first rate limit lifts, if all
+    /// of them are rate-limited
+    Saturated { reset_in: Option<Duration> },
+}
+
+impl TokenManager {
+    /// Check the pool with the same eligibility rules selection uses.
+    pub(crate) async fn pool_state(
+        &self,
+        target_model: &str,
+        exclude_accounts: Option<&HashSet<String>>,
+    ) -> PoolState {
+        let groups = crate::proxy::routing_rules::account_groups();
+        let excluded = exclude_accounts.cloned().unwrap_or_default();
+        let spare_floor = (crate::proxy::admission::current_priority() == PriorityClass::Batch)
+            .then(super::routing::spare_quota_floor);
+        let candidates: Vec<_> = self
+            .tokens
+            .iter()
+            .map(|e| e.value().clone())
+            .filter(|t| !excluded.contains(&t.email))
+            .filter(|t| groups.iter().all(|g| t.groups.contains(g)))
+            .filter(|t| spare_floor.is_none_or(|floor| super::routing::has_spare_quota(t, floor)))
+            .collect();
+        if candidates.is_empty() {
+            return PoolState::Empty;
+        }
+
+        let model = crate::proxy::common::model_mapping::normalize_to_standard_id(target_model)
+            .unwrap_or_else(|| target_model.to_string());
+        let quota_protection_enabled =
+            config::load_config_cached().map(|cfg| cfg.quota_protection.enabled).unwrap_or(false);
+        let aimd = self.adaptive_limits.read().await.clone();
+        let max_concurrent = self.routing_config.read().await.max_concurrent_per_account;
+
+        let mut reset_in: Option<u64> = None;
+        let mut all_rate_limited = true;
+        for candidate in &candidates {
+            let eligible = self.is_candidate_eligible(
+                candidate,
+                &model,
+                &excluded,
+                quota_protection_enabled,
+                &aimd,
+                true,
+                true,
+            );
+            if eligible && self.get_active_requests(&candidate.email) < max_concurrent {
+                return PoolState::Available;
+            }
+
+            let wait = self.get_rate_limit_reset_seconds(&candidate.email).unwrap_or_else(|| {
+                self.rate_limit_tracker.get_remaining_wait_for_model(&candidate.email, &model)
+            });
+            if wait == 0 {
+                all_rate_limited = false;
+            } else {
+                reset_in = Some(reset_in.map_or(wait, |r| r.min(wait)));
+            }
+        }
+
+        PoolState::Saturated {
+            reset_in: reset_in.filter(|_| all_rate_limited).map(Duration::from_secs),
+        }
+    }
+}
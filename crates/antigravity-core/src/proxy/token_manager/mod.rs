@@ -1,14 +1,19 @@
 use crate::modules::repository::AccountRepository;
+use crate::proxy::admission::AdmissionQueue;
+use crate::proxy::cluster::{Cluster, ClusterSlot};
+use crate::proxy::monitor::{EventBusSlot, ProxyEventBus};
 use crate::proxy::rate_limit::RateLimitTracker;
 use crate::proxy::routing_config::SmartRoutingConfig;
 use crate::proxy::AdaptiveLimitManager;
 use crate::proxy::HealthMonitor;
+use antigravity_types::models::AdmissionConfig;
 use dashmap::DashMap;
 use std::path::PathBuf;
 use std::sync::atomic::{AtomicU32, Ordering};
 use std::sync::Arc;
 use std::time::Instant;
 
+mod admission;
 mod candidate_filter;
 mod file_utils;
 mod health;
@@ -24,6 +29,7 @@ mod session;
 mod store;
 mod token_refresh;
 
+pub(crate) use admission::PoolState;
 pub use proxy_token::{AccountTier, ProxyToken};
 
 pub struct TokenManager {
@@ -43,6 +49,9 @@ pub struct TokenManager {
     /// When true, side-channel requests (OAuth refresh, project resolution, quota fetch)
     /// are blocked if the account has no per-account proxy_url.
     pub(crate) enforce_proxy: std::sync::atomic::AtomicBool,
+    pub(crate) events: EventBusSlot,
+    pub(crate) cluster: ClusterSlot,
+    pub(crate) admission: Arc<AdmissionQueue>,
 }
 
 impl TokenManager {
@@ -62,6 +71,9 @@ impl TokenManager {
             refresh_locks: Arc::new(DashMap::new()),
             repository: Arc::new(tokio::sync::RwLock::new(None)),
             enforce_proxy: std::sync::atomic::AtomicBool::new(false),
+            events: EventBusSlot::default(),
+            cluster: ClusterSlot::default(),
+            admission: Arc::new(AdmissionQueue::default()),
         }
     }
 
@@ -104,6 +116,26 @@ impl TokenManager {
         *guard = Some(repo);
     }
 
+    /// Report rate limits and disabled accounts to the live monitor.
+    pub fn set_event_bus(&self, bus: Arc<dyn ProxyEventBus>) {
+        self.rate_limit_tracker.set_event_bus(Arc::clone(&bus));
+        self.events.set(bus);
+    }
+
+    /// Share rate limits and sticky session bindings with the other instances.
+    pub fn set_cluster(&self, cluster: Arc<Cluster>) {
+        self.rate_limit_tracker.set_cluster(Arc::clone(&cluster));
+        self.cluster.set(cluster);
+    }
+
+    pub fn set_admission_config(&self, config: AdmissionConfig) {
+        self.admission.set_config(config);
+    }
+
+    pub fn admission(&self) -> &AdmissionQueue {
+        &self.admission
+    }
+
     pub fn set_enforce_proxy(&self, enforce: bool) {
         self.enforce_proxy.store(enforce, Ordering::Release);
     }
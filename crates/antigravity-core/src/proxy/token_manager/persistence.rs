@@ -13,6 +13,7 @@ use super::file_utils::atomic_write_json;
 use super::TokenManager;
 use crate::modules::oauth;
 use crate::modules::repository::AccountRepository;
+use antigravity_types::models::{AccountState, AccountStateChange};
 use std::sync::Arc;
 
 impl TokenManager {
@@ -192,6 +193,13 @@ impl TokenManager {
         account_id: &str,
         reason: &str,
     ) -> Result<(), String> {
+        self.events.emit_account_state(AccountStateChange {
+            account_id: account_id.to_string(),
+            state: AccountState::Disabled,
+            model: None,
+            reason: Some(reason.to_string()),
+            retry_after_sec: None,
+        });
         let repo = self.get_repo().await;
         let file_result = self.disable_account_in_file(account_id, reason).await;
 
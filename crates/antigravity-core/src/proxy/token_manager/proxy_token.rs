@@ -52,6 +52,8 @@ pub struct ProxyToken {
     pub available_models: HashSet<String>,
     /// Per-account proxy URL. When set, all requests use this proxy.
     pub proxy_url: Option<String>,
+    /// Named account groups this account belongs to.
+    pub groups: HashSet<String>,
     /// Pre-computed account tier (cached from subscription_tier string).
     cached_tier: AccountTier,
 }
@@ -105,6 +107,7 @@ impl ProxyToken {
             health_score,
             available_models,
             proxy_url,
+            groups: HashSet::new(),
             cached_tier,
         }
     }
@@ -43,7 +43,6 @@ impl TokenManager {
         &self.rate_limit_tracker
     }
 
-    #[allow(dead_code, reason = "public API used in tests")]
     pub fn get_rate_limit_reset_seconds(&self, account_id: &str) -> Option<u64> {
         self.rate_limit_tracker.get_reset_seconds(account_id)
     }
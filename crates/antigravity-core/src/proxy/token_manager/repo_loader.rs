@@ -73,7 +73,7 @@ impl TokenManager {
 
         let health_score = self.get_health_score(&account.id);
 
-        Ok(ProxyToken::new(
+        let mut proxy_token = ProxyToken::new(
             account.id.clone(),
             token.access_token.clone(),
             token.refresh_token.clone(),
@@ -88,7 +88,9 @@ impl TokenManager {
             health_score,
             available_models,
             account.proxy_url.clone(),
-        ))
+        );
+        proxy_token.groups = account.groups.iter().cloned().collect();
+        Ok(proxy_token)
     }
 
     /// Merge new tokens into the token map, removing stale entries.
@@ -1,4 +1,6 @@
+use super::proxy_token::ProxyToken;
 use super::TokenManager;
+use crate::modules::config;
 use crate::proxy::routing_config::SmartRoutingConfig;
 
 impl TokenManager {
@@ -24,6 +26,26 @@ impl TokenManager {
         false
     }
 
+    /// Accounts that could serve `model` right now: offering it (or not reporting models
+    /// yet), not rate-limited for it and not quota-protected for it.
+    pub fn available_account_count(&self, model: &str) -> usize {
+        self.tokens
+            .iter()
+            .filter(|entry| {
+                let token = entry.value();
+                (token.available_models.is_empty() || token.available_models.contains(model))
+                    && !token.protected_models.contains(model)
+                    && !self.is_rate_limited_for_model(&token.email, model)
+            })
+            .count()
+    }
+
+    /// Whether any account has quota to spare for batch requests.
+    pub fn has_spare_quota(&self) -> bool {
+        let floor = spare_quota_floor();
+        self.tokens.iter().any(|entry| has_spare_quota(entry.value(), floor))
+    }
+
     pub async fn set_preferred_account(&self, account_id: Option<String>) {
         let mut preferred = self.preferred_account_id.write().await;
         if let Some(ref id) = account_id {
@@ -38,3 +60,17 @@ impl TokenManager {
         self.preferred_account_id.read().await.clone()
     }
 }
+
+/// Lowest remaining quota (percent) an account may have to serve batch requests: the
+/// quota protection threshold when protection is enabled, anything above zero otherwise.
+pub(super) fn spare_quota_floor() -> i32 {
+    config::load_config_cached()
+        .ok()
+        .filter(|cfg| cfg.quota_protection.enabled)
+        .map_or(0, |cfg| i32::from(cfg.quota_protection.threshold_percentage))
+}
+
+/// Accounts whose quota has not been fetched yet are given the benefit of the doubt.
+pub(super) fn has_spare_quota(token: &ProxyToken, floor: i32) -> bool {
+    token.remaining_quota.is_none_or(|quota| quota > floor)
+}
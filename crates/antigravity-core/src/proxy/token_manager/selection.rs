@@ -1,7 +1,10 @@
+use super::admission::PoolState;
 use super::proxy_token::ProxyToken;
 use super::TokenManager;
 use crate::modules::config;
 use crate::proxy::active_request_guard::ActiveRequestGuard;
+use crate::proxy::admission::QueueSlot;
+use antigravity_types::models::PriorityClass;
 use std::collections::HashSet;
 use std::sync::Arc;
 
@@ -17,6 +20,8 @@ impl TokenManager {
             .await
     }
 
+    /// Select an account, waiting in the admission queue (when enabled) while every
+    /// account is busy.
     pub async fn get_token_with_exclusions(
         &self,
         quota_group: &str,
@@ -24,6 +29,53 @@ impl TokenManager {
         session_id: Option<&str>,
         target_model: &str,
         exclude_accounts: Option<&HashSet<String>>,
+    ) -> Result<(String, String, String, ActiveRequestGuard), String> {
+        let mut slot: Option<QueueSlot<'_>> = None;
+        loop {
+            let result = self
+                .select_with_timeout(
+                    quota_group,
+                    force_rotate,
+                    session_id,
+                    target_model,
+                    exclude_accounts,
+                )
+                .await;
+            let error = match result {
+                Ok(selected) => {
+                    if let Some(slot) = slot.take() {
+                        slot.admitted();
+                    }
+                    return Ok(selected);
+                },
+                Err(e) => e,
+            };
+            if !self.admission.is_enabled() {
+                return Err(error);
+            }
+            let PoolState::Saturated { reset_in } =
+                self.pool_state(target_model, exclude_accounts).await
+            else {
+                if let Some(slot) = slot.take() {
+                    slot.abandon();
+                }
+                return Err(error);
+            };
+            let queued = match slot.as_mut() {
+                Some(queued) => queued,
+                None => slot.insert(self.admission.enter(reset_in.map(|d| d.as_secs()))?),
+            };
+            queued.wait(reset_in).await?;
+        }
+    }
+
+    async fn select_with_timeout(
+        &self,
+        quota_group: &str,
+        force_rotate: bool,
+        session_id: Option<&str>,
+        target_model: &str,
+        exclude_accounts: Option<&HashSet<String>>,
     ) -> Result<(String, String, String, ActiveRequestGuard), String> {
         let timeout_duration = std::time::Duration::from_secs(5);
         match tokio::time::timeout(
@@ -62,6 +114,16 @@ impl TokenManager {
             .map(|entry| entry.value().clone())
             .ok_or_else(|| format!("Forced account not found: {}", forced_email))?;
 
+        // Forcing an account never escapes the request's account group
+        let groups = crate::proxy::routing_rules::account_groups();
+        if !groups.iter().all(|g| token.groups.contains(g)) {
+            return Err(format!(
+                "Forced account {} is not in account group '{}'",
+                forced_email,
+                groups.join("' and '")
+            ));
+        }
+
         if self.is_rate_limited(&token.account_id) {
             return Err(format!("Forced account {} is rate limited", forced_email));
         }
@@ -100,6 +162,11 @@ impl TokenManager {
         Ok((token.access_token, project_id, token.email, guard))
     }
 
+    #[tracing::instrument(
+        name = "get_token",
+        skip(self, _quota_group, session_id, exclude_accounts),
+        fields(has_session = session_id.is_some())
+    )]
     async fn get_token_internal(
         &self,
         _quota_group: &str,
@@ -110,12 +177,19 @@ impl TokenManager {
     ) -> Result<(String, String, String, ActiveRequestGuard), String> {
         let mut tokens_snapshot: Vec<ProxyToken> =
             self.tokens.iter().map(|e| e.value().clone()).collect();
-        let total = tokens_snapshot.len();
-
-        if total == 0 {
+        if tokens_snapshot.is_empty() {
             return Err("Token pool is empty".to_string());
         }
 
+        let groups = crate::proxy::routing_rules::account_groups();
+        if !groups.is_empty() {
+            tokens_snapshot.retain(|t| groups.iter().all(|g| t.groups.contains(g)));
+            if tokens_snapshot.is_empty() {
+                return Err(format!("No accounts in account group '{}'", groups.join("' and '")));
+            }
+        }
+        let total = tokens_snapshot.len();
+
         tokens_snapshot.sort_by(super::selection_helpers::compare_tokens_by_priority);
 
         let routing = self.routing_config.read().await.clone();
@@ -140,6 +214,15 @@ impl TokenManager {
             }
         }
 
+        // Batch requests only use quota that interactive traffic can spare
+        if crate::proxy::admission::current_priority() == PriorityClass::Batch {
+            let floor = super::routing::spare_quota_floor();
+            tokens_snapshot.retain(|t| super::routing::has_spare_quota(t, floor));
+            if tokens_snapshot.is_empty() {
+                return Err("No accounts with spare quota for batch requests".to_string());
+            }
+        }
+
         let preferred_id = self.preferred_account_id.read().await.clone();
         if let Some(ref pref_id) = preferred_id {
             if let Some((token, guard)) = self
@@ -162,7 +162,7 @@ impl TokenManager {
                 bound_id,
                 session_id
             );
-            self.session_accounts.remove(session_id);
+            self.remove_session_binding(session_id);
             return None;
         }
 
@@ -3,6 +3,7 @@
 //! Handles session-to-account bindings and failure tracking for sticky sessions.
 
 use super::TokenManager;
+use crate::proxy::cluster::ClusterEvent;
 use std::sync::atomic::Ordering;
 use std::time::Instant;
 
@@ -33,12 +34,37 @@ impl TokenManager {
     /// Clear binding for a specific session.
     #[allow(dead_code, reason = "public API used in tests")]
     pub fn clear_session_binding(&self, session_id: &str) {
-        self.session_accounts.remove(session_id);
+        self.remove_session_binding(session_id);
     }
 
-    /// Clear all session bindings.
+    /// Remove a binding, telling the other instances if there was one.
+    pub(crate) fn remove_session_binding(&self, session_id: &str) -> Option<String> {
+        let (_, (bound_id, _ts)) = self.session_accounts.remove(session_id)?;
+        self.cluster.publish(ClusterEvent::SessionUnbound { session_id: session_id.to_string() });
+        Some(bound_id)
+    }
+
+    /// Apply a binding made (`Some`) or dropped (`None`) by another instance.
+    pub fn apply_remote_session_binding(&self, session_id: &str, email: Option<String>) {
+        match email {
+            Some(email) => {
+                self.session_accounts.insert(session_id.to_string(), (email, Instant::now()));
+            },
+            None => {
+                self.session_accounts.remove(session_id);
+            },
+        }
+    }
+
+    /// Clear all session bindings, on every instance.
     pub fn clear_all_sessions(&self) {
         self.session_accounts.clear();
+        self.cluster.publish(ClusterEvent::SessionsCleared);
+    }
+
+    /// Apply a clear of all session bindings made by another instance.
+    pub fn apply_remote_sessions_cleared(&self) {
+        self.session_accounts.clear();
     }
 
     /// Check if session has exceeded failure threshold and should be unbound.
@@ -48,7 +74,7 @@ impl TokenManager {
 
     /// Unbind session from its current account due to failures.
     pub(crate) fn unbind_session_on_failures(&self, session_id: &str) -> Option<String> {
-        if let Some((_, (bound_id, _ts))) = self.session_accounts.remove(session_id) {
+        if let Some(bound_id) = self.remove_session_binding(session_id) {
             let failures = self.get_session_failures(session_id);
             self.clear_session_failures(session_id);
             tracing::warn!(
@@ -75,6 +101,10 @@ impl TokenManager {
             if current_binding.as_ref() != Some(&email.to_string()) {
                 self.session_accounts
                     .insert(session_id.to_string(), (email.to_string(), Instant::now()));
+                self.cluster.publish(ClusterEvent::SessionBound {
+                    session_id: session_id.to_string(),
+                    email: email.to_string(),
+                });
                 if let Some(ref old) = current_binding {
                     tracing::info!(
                         "Sticky Session: Rebound session {} from {} to {} (cache continuity)",
@@ -91,6 +121,12 @@ impl TokenManager {
         }
     }
 
+    /// Keep a session on the account holding its upstream context cache.
+    pub(crate) async fn bind_session_to_cache(&self, session_id: &str, email: &str) {
+        let enabled = self.routing_config.read().await.enable_session_affinity;
+        self.bind_session_to_account(session_id, email, enabled);
+    }
+
     /// Get account bound to session.
     pub(crate) fn get_session_account(&self, session_id: &str) -> Option<String> {
         self.session_accounts.get(session_id).map(|v| v.0.clone())
@@ -177,8 +177,13 @@ impl TokenManager {
         }
 
         let proxy_url = account.get("proxy_url").and_then(|v| v.as_str()).map(|s| s.to_string());
+        let groups: HashSet<String> = account
+            .get("groups")
+            .and_then(|v| v.as_array())
+            .map(|arr| arr.iter().filter_map(|v| v.as_str()).map(|s| s.to_string()).collect())
+            .unwrap_or_default();
 
-        Ok(Some(ProxyToken::new(
+        let mut token = ProxyToken::new(
             account_id,
             access_token,
             refresh_token,
@@ -193,7 +198,9 @@ impl TokenManager {
             health_score,
             available_models,
             proxy_url,
-        )))
+        );
+        token.groups = groups;
+        Ok(Some(token))
     }
 
     pub async fn has_available_account(&self, quota_group: &str, target_model: &str) -> bool {
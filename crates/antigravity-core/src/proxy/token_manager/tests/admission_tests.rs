@@ -0,0 +1,132 @@
+use super::super::proxy_token::ProxyToken;
+use super::super::TokenManager;
+use crate::proxy::admission::{self, Ticket};
+use crate::proxy::rate_limit::RateLimitReason;
+use crate::proxy::routing_config::SmartRoutingConfig;
+use antigravity_types::models::{AdmissionConfig, PriorityClass, QueueClassConfig};
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+async fn create_test_manager(max_wait_secs: u64) -> Arc<TokenManager> {
+    let unique_id = uuid::Uuid::new_v4();
+    let manager =
+        TokenManager::new(std::env::temp_dir().join(format!("antigravity_test_{unique_id}")));
+    let class = QueueClassConfig { max_depth: 1, max_wait_secs };
+    manager.set_admission_config(AdmissionConfig {
+        enabled: true,
+        interactive: class.clone(),
+        batch: class,
+        keepalive_secs: 1,
+    });
+    manager
+        .update_routing_config(SmartRoutingConfig {
+            max_concurrent_per_account: 1,
+            ..Default::default()
+        })
+        .await;
+
+    let token = ProxyToken::new(
+        "only@test.com".to_string(),
+        "token".to_string(),
+        "refresh".to_string(),
+        3600,
+        chrono::Utc::now().timestamp() + 3600,
+        "only@test.com".to_string(),
+        PathBuf::from("/tmp"),
+        Some("test-project".to_string()),
+        Some("g1-pro-tier".to_string()),
+        Some(100),
+        HashSet::new(),
+        1.0,
+        HashSet::new(),
+        None,
+    );
+    manager.tokens.insert(token.account_id.clone(), token);
+    Arc::new(manager)
+}
+
+#[tokio::test]
+async fn test_queued_request_admitted_when_account_frees_up() {
+    let manager = create_test_manager(5).await;
+    let (_, _, _, busy) = manager.get_token("default", false, None, "gemini-3-pro").await.unwrap();
+
+    let ticket = Ticket::new(PriorityClass::Interactive);
+    let waiter = tokio::spawn({
+        let manager = Arc::clone(&manager);
+        admission::scope(ticket.clone(), async move {
+            manager.get_token("default", false, None, "gemini-3-pro").await.map(|t| t.2)
+        })
+    });
+
+    tokio::time::timeout(Duration::from_secs(2), ticket.queued()).await.unwrap();
+    assert_eq!(manager.admission().depth(PriorityClass::Interactive), 1);
+    drop(busy);
+
+    assert_eq!(waiter.await.unwrap().unwrap(), "only@test.com");
+    assert_eq!(manager.admission().depth(PriorityClass::Interactive), 0);
+    assert!(ticket.rejection().is_none());
+}
+
+#[tokio::test]
+async fn test_queue_full_rejects_with_ticket_rejection() {
+    let manager = create_test_manager(5).await;
+    let (_, _, _, _busy) = manager.get_token("default", false, None, "gemini-3-pro").await.unwrap();
+
+    let first = Ticket::new(PriorityClass::Batch);
+    let _waiter = tokio::spawn({
+        let manager = Arc::clone(&manager);
+        admission::scope(first.clone(), async move {
+            manager.get_token("default", false, None, "gemini-3-pro").await.map(|t| t.2)
+        })
+    });
+    tokio::time::timeout(Duration::from_secs(2), first.queued()).await.unwrap();
+
+    let second = Ticket::new(PriorityClass::Batch);
+    let result =
+        admission::scope(second.clone(), manager.get_token("default", false, None, "gemini-3-pro"))
+            .await;
+    assert!(result.is_err());
+    assert!(second.rejection().unwrap().message.contains("queue full"));
+}
+
+#[tokio::test]
+async fn test_predicted_wait_beyond_deadline_rejects_immediately() {
+    let manager = create_test_manager(5).await;
+    let reset = SystemTime::now() + Duration::from_secs(300);
+    manager.rate_limit_tracker().set_lockout_until(
+        "only@test.com",
+        reset,
+        RateLimitReason::QuotaExhausted,
+        None,
+    );
+
+    let ticket = Ticket::new(PriorityClass::Interactive);
+    let started = std::time::Instant::now();
+    let result =
+        admission::scope(ticket.clone(), manager.get_token("default", false, None, "gemini-3-pro"))
+            .await;
+
+    assert!(result.is_err());
+    assert!(started.elapsed() < Duration::from_secs(2));
+    let rejection = ticket.rejection().unwrap();
+    assert!(rejection.message.contains("queue deadline"), "{}", rejection.message);
+    assert!(rejection.retry_after.is_some_and(|secs| secs > 290));
+}
+
+#[tokio::test]
+async fn test_batch_requests_skip_accounts_without_spare_quota() {
+    let manager = create_test_manager(5).await;
+    manager.tokens.get_mut("only@test.com").unwrap().remaining_quota = Some(0);
+    assert!(!manager.has_spare_quota());
+
+    let batch = admission::scope(Ticket::new(PriorityClass::Batch), async {
+        manager.get_token("default", false, None, "gemini-3-pro").await.map(|t| t.2)
+    })
+    .await;
+    assert!(batch.unwrap_err().contains("spare quota"));
+
+    let (_, _, email, _) = manager.get_token("default", false, None, "gemini-3-pro").await.unwrap();
+    assert_eq!(email, "only@test.com");
+}
@@ -1,3 +1,4 @@
+mod admission_tests;
 mod rate_limiter_tests;
 mod recovery_tests;
 mod selection_tests;
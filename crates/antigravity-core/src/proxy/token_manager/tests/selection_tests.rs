@@ -219,3 +219,62 @@ async fn test_get_token_forced_case_insensitive_email() {
         Err(e) => panic!("Expected success for case-insensitive match: {e}"),
     }
 }
+
+#[tokio::test]
+async fn test_get_token_restricted_to_account_group() {
+    use crate::proxy::routing_rules::{self, RouteDecision};
+
+    let manager = create_test_manager();
+    let mut interactive = make_token("ultra@test.com", Some("g1-ultra-tier"), Some(100), 1.0);
+    interactive.groups.insert("team-a".to_string());
+    let mut batch = make_token("batch@test.com", Some("free-tier"), Some(10), 1.0);
+    batch.groups.insert("batch".to_string());
+    manager.tokens.insert(interactive.account_id.clone(), interactive);
+    manager.tokens.insert(batch.account_id.clone(), batch);
+
+    let pinned =
+        |group: &str| RouteDecision { account_group: Some(group.into()), ..Default::default() };
+
+    let result = routing_rules::scope(
+        pinned("batch"),
+        manager.get_token_with_exclusions("default", false, None, "gemini-3-pro", None),
+    )
+    .await;
+    match result {
+        Ok((_token, _project, email, _guard)) => assert_eq!(email, "batch@test.com"),
+        Err(e) => panic!("Expected batch account but got error: {e}"),
+    }
+
+    let result = routing_rules::scope(
+        pinned("ultra-only"),
+        manager.get_token_with_exclusions("default", false, None, "gemini-3-pro", None),
+    )
+    .await;
+    match result {
+        Ok(_) => panic!("Expected no account outside the group"),
+        Err(e) => assert!(e.contains("ultra-only"), "{e}"),
+    }
+
+    // A rule cannot move a request out of its client key's group
+    let narrowed = RouteDecision {
+        key_group: Some("team-a".into()),
+        account_group: Some("batch".into()),
+        ..Default::default()
+    };
+    let result = routing_rules::scope(
+        narrowed,
+        manager.get_token_with_exclusions("default", false, None, "gemini-3-pro", None),
+    )
+    .await;
+    assert!(result.is_err(), "account outside the key group was selected");
+
+    // Nor can forcing an account
+    let key_only = RouteDecision { key_group: Some("team-a".into()), ..Default::default() };
+    let result =
+        routing_rules::scope(key_only, manager.get_token_forced("batch@test.com", "gemini-3-pro"))
+            .await;
+    match result {
+        Ok(_) => panic!("Expected forced account outside the key group to be rejected"),
+        Err(e) => assert!(e.contains("team-a"), "{e}"),
+    }
+}
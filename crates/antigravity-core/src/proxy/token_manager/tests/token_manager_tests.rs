@@ -91,6 +91,29 @@ fn test_rate_limit_with_model() {
     assert_eq!(info.unwrap().model, Some("gemini-pro".to_string()));
 }
 
+#[test]
+fn test_available_account_count() {
+    let manager = create_test_manager();
+    let mut token = create_test_token(Some("pro"));
+    token.available_models = HashSet::from(["gemini-3-pro".to_string()]);
+    manager.tokens.insert("a".to_string(), token);
+    let mut other = create_test_token(Some("pro"));
+    other.email = "other@example.com".to_string();
+    manager.tokens.insert("b".to_string(), other);
+
+    assert_eq!(manager.available_account_count("gemini-3-pro"), 2);
+    assert_eq!(manager.available_account_count("claude-opus-4"), 1);
+
+    manager.mark_rate_limited_with_model(
+        "other@example.com",
+        429,
+        Some("30"),
+        "",
+        Some("gemini-3-pro".to_string()),
+    );
+    assert_eq!(manager.available_account_count("gemini-3-pro"), 1);
+}
+
 #[tokio::test]
 async fn test_preferred_account_mode() {
     let manager = create_test_manager();
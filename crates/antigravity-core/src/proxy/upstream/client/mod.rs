@@ -25,10 +25,8 @@ fn default_upstream_urls() -> Vec<String> {
     ]
 }
 
-fn resolve_upstream_urls(explicit: Option<Vec<String>>) -> Vec<String> {
-    if let Some(urls) = explicit {
-        return urls;
-    }
+/// Endpoint list: `ANTIGRAVITY_UPSTREAM_URL` > configured `upstream_urls` > defaults.
+fn resolve_upstream_urls(configured: &[String]) -> Vec<String> {
     if let Ok(raw) = std::env::var("ANTIGRAVITY_UPSTREAM_URL") {
         let url = raw.trim().trim_end_matches('/').to_string();
         if url.is_empty() {
@@ -39,17 +37,19 @@ fn resolve_upstream_urls(explicit: Option<Vec<String>>) -> Vec<String> {
             tracing::warn!("ANTIGRAVITY_UPSTREAM_URL is not a valid URL, using defaults");
             return default_upstream_urls();
         }
-        tracing::info!("Using custom upstream URL");
-        vec![url]
-    } else {
+        return vec![url];
+    }
+    if configured.is_empty() {
         default_upstream_urls()
+    } else {
+        configured.iter().map(|url| url.trim().trim_end_matches('/').to_string()).collect()
     }
 }
 
 pub struct UpstreamClient {
     http_client: Client,
     proxy_pool: Arc<ProxyPool>,
-    base_urls: Vec<String>,
+    base_urls: parking_lot::RwLock<Arc<Vec<String>>>,
     proxy_config: Arc<tokio::sync::RwLock<crate::proxy::config::UpstreamProxyConfig>>,
 }
 
@@ -74,9 +74,28 @@ impl UpstreamClient {
             }
         };
 
-        let base_urls = resolve_upstream_urls(base_urls);
+        let base_urls = base_urls.unwrap_or_else(|| resolve_upstream_urls(&[]));
+
+        Self {
+            http_client,
+            proxy_pool: Arc::new(pool),
+            base_urls: parking_lot::RwLock::new(Arc::new(base_urls)),
+            proxy_config,
+        }
+    }
+
+    /// Apply `ProxyConfig::upstream_urls` (called at startup and on hot-reload).
+    pub fn set_upstream_urls(&self, configured: &[String]) {
+        let urls = resolve_upstream_urls(configured);
+        let mut base_urls = self.base_urls.write();
+        if **base_urls != urls {
+            tracing::info!("Upstream endpoints: {}", urls.join(", "));
+            *base_urls = Arc::new(urls);
+        }
+    }
 
-        Self { http_client, proxy_pool: Arc::new(pool), base_urls, proxy_config }
+    fn base_urls(&self) -> Arc<Vec<String>> {
+        Arc::clone(&self.base_urls.read())
     }
 
     /// Get the proxy pool reference for external use.
@@ -183,7 +202,7 @@ impl UpstreamClient {
             &body,
             query_string,
             proxy_url.as_deref(),
-            &self.base_urls,
+            &self.base_urls(),
         )
         .await
     }
@@ -226,7 +245,7 @@ impl UpstreamClient {
             &body,
             query_string,
             effective_proxy.as_deref(),
-            &self.base_urls,
+            &self.base_urls(),
         )
         .await
     }
@@ -257,7 +276,7 @@ impl UpstreamClient {
             &body,
             query_string,
             effective_proxy.as_deref(),
-            &self.base_urls,
+            &self.base_urls(),
         )
         .await
     }
@@ -279,7 +298,7 @@ impl UpstreamClient {
             &body,
             query_string,
             None,
-            &self.base_urls,
+            &self.base_urls(),
         )
         .await
     }
@@ -2,6 +2,7 @@ use reqwest::{header, Client, Response, StatusCode};
 use serde_json::Value;
 use std::collections::HashMap;
 use tokio::time::Duration;
+use tracing::Instrument;
 
 use super::super::device_fingerprint;
 use super::super::endpoint_health::{
@@ -104,6 +105,7 @@ pub async fn execute_with_fallback(
     warp_proxy_url: Option<&str>,
     base_urls: &[String],
 ) -> Result<Response, String> {
+    crate::proxy::capture::record_upstream_request(body);
     let mut last_err: Option<String> = None;
 
     for (idx, base_url) in base_urls.iter().enumerate() {
@@ -117,7 +119,30 @@ pub async fn execute_with_fallback(
         let mut transport_retries: u32 = 0;
 
         loop {
-            let response = client.post(&url).headers(headers.clone()).json(body).send().await;
+            let attempt_span = tracing::info_span!(
+                "upstream.attempt",
+                endpoint = %base_url,
+                method,
+                endpoint_index = idx,
+                retry = transport_retries,
+                http.response.status_code = tracing::field::Empty,
+                error = tracing::field::Empty,
+            );
+            let response = client
+                .post(&url)
+                .headers(headers.clone())
+                .json(body)
+                .send()
+                .instrument(attempt_span.clone())
+                .await;
+            match &response {
+                Ok(resp) => {
+                    attempt_span.record("http.response.status_code", resp.status().as_u16());
+                },
+                Err(e) => {
+                    attempt_span.record("error", tracing::field::display(e));
+                },
+            }
 
             match response {
                 Ok(resp) => {
@@ -16,6 +16,23 @@ fn test_build_url() {
     );
 }
 
+#[test]
+fn test_configured_upstream_urls() {
+    if std::env::var("ANTIGRAVITY_UPSTREAM_URL").is_ok() {
+        return;
+    }
+    let proxy_config = Arc::new(RwLock::new(Default::default()));
+    let client = super::UpstreamClient::new(reqwest::Client::new(), proxy_config, None);
+    let defaults = client.base_urls();
+    assert_eq!(defaults.len(), 3);
+
+    client.set_upstream_urls(&["https://gateway.internal/v1internal/".to_string()]);
+    assert_eq!(*client.base_urls(), vec!["https://gateway.internal/v1internal".to_string()]);
+
+    client.set_upstream_urls(&[]);
+    assert_eq!(client.base_urls(), defaults);
+}
+
 #[tokio::test]
 async fn test_direct_mode_returns_ok() {
     let config = antigravity_types::models::config::UpstreamProxyConfig::default();
@@ -42,6 +42,9 @@ pub struct Account {
     /// When set, ALL requests for this account go through this proxy.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub proxy_url: Option<String>,
+    /// Named account groups (e.g. "batch") that routing can restrict selection to
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub groups: Vec<String>,
     /// Timestamp when account was created
     pub created_at: i64,
     /// Timestamp when account was last used
@@ -66,6 +69,7 @@ impl Account {
             proxy_disabled_at: None,
             protected_models: HashSet::new(),
             proxy_url: None,
+            groups: Vec::new(),
             created_at: now,
             last_used: now,
         }
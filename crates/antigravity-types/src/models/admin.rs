@@ -0,0 +1,85 @@
+//! Request and response bodies of the admin REST API (`/api/...`).
+
+use serde::{Deserialize, Serialize};
+
+use super::quota::QuotaData;
+
+/// Server status (`GET /api/status`).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub struct ServerStatus {
+    /// Server version
+    pub version: String,
+    /// Whether the proxy is serving requests
+    pub proxy_running: bool,
+    /// Number of stored accounts
+    pub accounts_count: usize,
+    /// Email of the current account, if any
+    pub current_account: Option<String>,
+}
+
+/// Account as listed by `GET /api/accounts`, without credentials.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct AccountInfo {
+    /// Unique identifier
+    pub id: String,
+    /// Email address
+    pub email: String,
+    /// Optional display name
+    pub name: Option<String>,
+    /// Account is disabled entirely
+    pub disabled: bool,
+    /// Account is excluded from the proxy pool
+    pub proxy_disabled: bool,
+    /// Account is the current one
+    pub is_current: bool,
+    /// Remaining Gemini quota percentage
+    pub gemini_quota: Option<i32>,
+    /// Remaining Claude quota percentage
+    pub claude_quota: Option<i32>,
+    /// Subscription tier reported with the quota
+    pub subscription_tier: Option<String>,
+    /// Full quota data from the last refresh
+    pub quota: Option<QuotaData>,
+    /// Account groups the account belongs to
+    #[serde(default)]
+    pub groups: Vec<String>,
+}
+
+/// Include or exclude an account from the proxy pool (`POST /api/accounts/toggle-proxy`).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ToggleProxyRequest {
+    /// Account to change
+    pub account_id: String,
+    /// `true` to serve requests from the account again
+    pub enable: bool,
+    /// Why the account was disabled
+    #[serde(default)]
+    pub reason: Option<String>,
+}
+
+/// Result of a proxy toggle.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ToggleProxyResponse {
+    /// Whether the change was applied
+    pub success: bool,
+    /// Account that was changed
+    pub account_id: String,
+    /// New proxy-disabled state
+    pub proxy_disabled: bool,
+}
+
+/// Refresh one account's quota (`POST /api/accounts/refresh-quota`).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RefreshQuotaRequest {
+    /// Account to refresh
+    pub account_id: String,
+}
+
+/// Freshly fetched quota of an account.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct QuotaResponse {
+    /// Account that was refreshed
+    pub account_id: String,
+    /// Quota data, if the upstream returned any
+    pub quota: Option<QuotaData>,
+}
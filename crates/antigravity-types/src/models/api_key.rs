@@ -0,0 +1,343 @@
+//! Client API key models for multi-tenant proxy access.
+//!
+//! Each key carries its own label, expiry, model allowlist and usage budget,
+//! so individual team members or CI jobs can be revoked independently.
+
+use chrono::{Datelike, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::models::config::PriorityClass;
+
+/// Window over which an API key budget is measured.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum BudgetPeriod {
+    /// Budget never resets (lifetime of the key)
+    #[default]
+    Total,
+    /// Budget resets at 00:00 UTC every day
+    Daily,
+    /// Budget resets on the first day of every month (UTC)
+    Monthly,
+}
+
+impl BudgetPeriod {
+    /// Start of the period containing `now` (unix seconds).
+    #[allow(
+        clippy::arithmetic_side_effects,
+        clippy::integer_division,
+        reason = "Day arithmetic on unix timestamps is bounded"
+    )]
+    pub fn period_start(self, now: i64) -> i64 {
+        match self {
+            Self::Total => 0,
+            Self::Daily => now - now.rem_euclid(86_400),
+            Self::Monthly => {
+                let dt = Utc.timestamp_opt(now, 0).single().unwrap_or_else(Utc::now);
+                Utc.with_ymd_and_hms(dt.year(), dt.month(), 1, 0, 0, 0)
+                    .single()
+                    .map_or(0, |start| start.timestamp())
+            },
+        }
+    }
+}
+
+/// Request and token limits for a single API key.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub struct ApiKeyBudget {
+    /// Window the limits apply to
+    #[serde(default)]
+    pub period: BudgetPeriod,
+    /// Maximum number of requests per period (None = unlimited)
+    #[serde(default)]
+    pub max_requests: Option<u64>,
+    /// Maximum number of tokens (input + output) per period (None = unlimited)
+    #[serde(default)]
+    pub max_tokens: Option<u64>,
+}
+
+/// Usage accumulated by an API key in the current budget period.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub struct ApiKeyUsage {
+    /// Requests served in the current period
+    #[serde(default)]
+    pub requests: u64,
+    /// Tokens (input + output) consumed in the current period
+    #[serde(default)]
+    pub tokens: u64,
+    /// Unix timestamp (seconds) at which the current period began
+    #[serde(default)]
+    pub period_start: i64,
+}
+
+impl ApiKeyUsage {
+    /// Reset counters if `now` falls into a later period. Returns true if reset.
+    pub fn roll_period(&mut self, period: BudgetPeriod, now: i64) -> bool {
+        let start = period.period_start(now);
+        if start > self.period_start {
+            *self = Self { requests: 0, tokens: 0, period_start: start };
+            return true;
+        }
+        false
+    }
+
+    /// Add usage recorded elsewhere since the last sync. Counts from an older period are
+    /// dropped; counts from a newer one replace ours.
+    pub fn add(&mut self, delta: &ApiKeyUsage) {
+        if delta.period_start > self.period_start {
+            *self = *delta;
+        } else if delta.period_start == self.period_start {
+            self.requests = self.requests.saturating_add(delta.requests);
+            self.tokens = self.tokens.saturating_add(delta.tokens);
+        }
+    }
+}
+
+/// A client API key accepted by the proxy in addition to the master key.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ApiKey {
+    /// Unique identifier (UUID)
+    pub id: String,
+    /// Human-readable label (owner, CI job, ...)
+    pub label: String,
+    /// First characters of the secret, for display only
+    pub key_prefix: String,
+    /// SHA-256 hex digest of the secret (never returned by the API)
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub key_hash: String,
+    /// Whether the key is accepted
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    /// Creation timestamp (unix seconds)
+    pub created_at: i64,
+    /// Expiry timestamp (unix seconds); None = never expires
+    #[serde(default)]
+    pub expires_at: Option<i64>,
+    /// Model allowlist (supports `*` wildcards); empty = all models
+    #[serde(default)]
+    pub allowed_models: Vec<String>,
+    /// Request/token budget
+    #[serde(default)]
+    pub budget: ApiKeyBudget,
+    /// Account group this key's requests are served from; None = any account
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub account_group: Option<String>,
+    /// Admission queue class of this key's requests
+    #[serde(default)]
+    pub priority: PriorityClass,
+    /// Usage in the current budget period
+    #[serde(default)]
+    pub usage: ApiKeyUsage,
+    /// Last time the key authenticated a request (unix seconds)
+    #[serde(default)]
+    pub last_used_at: Option<i64>,
+}
+
+impl ApiKey {
+    /// Whether the key has expired at `now` (unix seconds).
+    pub fn is_expired(&self, now: i64) -> bool {
+        self.expires_at.is_some_and(|exp| exp <= now)
+    }
+
+    /// Whether the key may use `model`.
+    pub fn allows_model(&self, model: &str) -> bool {
+        self.allowed_models.is_empty()
+            || self.allowed_models.iter().any(|pattern| glob_match(pattern, model))
+    }
+
+    /// Whether the current period's request or token budget is used up.
+    pub fn budget_exhausted(&self) -> bool {
+        self.budget.max_requests.is_some_and(|max| self.usage.requests >= max)
+            || self.budget.max_tokens.is_some_and(|max| self.usage.tokens >= max)
+    }
+
+    /// Copy of the key without the secret hash, safe to return from the API.
+    #[must_use]
+    pub fn redacted(&self) -> Self {
+        Self { key_hash: String::new(), ..self.clone() }
+    }
+}
+
+/// Request body for creating an API key.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CreateApiKeyRequest {
+    /// Human-readable label
+    pub label: String,
+    /// Expiry timestamp (unix seconds)
+    #[serde(default)]
+    pub expires_at: Option<i64>,
+    /// Model allowlist (supports `*` wildcards); empty = all models
+    #[serde(default)]
+    pub allowed_models: Vec<String>,
+    /// Request/token budget
+    #[serde(default)]
+    pub budget: ApiKeyBudget,
+    /// Account group this key's requests are served from
+    #[serde(default)]
+    pub account_group: Option<String>,
+    /// Admission queue class of this key's requests
+    #[serde(default)]
+    pub priority: PriorityClass,
+}
+
+/// Partial update of an API key. Absent fields are left unchanged.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UpdateApiKeyRequest {
+    #[serde(default)]
+    pub label: Option<String>,
+    #[serde(default)]
+    pub enabled: Option<bool>,
+    /// `Some(None)` clears the expiry
+    #[serde(default, with = "double_option")]
+    pub expires_at: Option<Option<i64>>,
+    #[serde(default)]
+    pub allowed_models: Option<Vec<String>>,
+    #[serde(default)]
+    pub budget: Option<ApiKeyBudget>,
+    /// `Some(None)` removes the group restriction
+    #[serde(default, with = "double_option")]
+    pub account_group: Option<Option<String>>,
+    #[serde(default)]
+    pub priority: Option<PriorityClass>,
+}
+
+/// Response to key creation. The secret is only ever returned here.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreatedApiKey {
+    pub key: ApiKey,
+    pub secret: String,
+}
+
+/// Distinguishes an explicit `null` from a missing field.
+mod double_option {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    #[allow(clippy::ref_option, reason = "serde `with` signature")]
+    pub fn serialize<T: Serialize, S: Serializer>(
+        value: &Option<Option<T>>,
+        s: S,
+    ) -> Result<S::Ok, S::Error> {
+        value.serialize(s)
+    }
+
+    pub fn deserialize<'de, T: Deserialize<'de>, D: Deserializer<'de>>(
+        d: D,
+    ) -> Result<Option<Option<T>>, D::Error> {
+        Option::<T>::deserialize(d).map(Some)
+    }
+}
+
+const fn default_enabled() -> bool {
+    true
+}
+
+/// Single-`*` wildcard match, consistent with custom model mapping rules.
+#[allow(
+    clippy::arithmetic_side_effects,
+    clippy::string_slice,
+    reason = "Slicing at the byte offset returned by find() is char-aligned"
+)]
+fn glob_match(pattern: &str, text: &str) -> bool {
+    match pattern.find('*') {
+        Some(pos) => {
+            let (prefix, suffix) = (&pattern[..pos], &pattern[pos + 1..]);
+            text.len() >= prefix.len() + suffix.len()
+                && text.starts_with(prefix)
+                && text.ends_with(suffix)
+        },
+        None => pattern == text,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key() -> ApiKey {
+        ApiKey {
+            id: "k1".to_string(),
+            label: "ci".to_string(),
+            key_prefix: "sk-ag-ab".to_string(),
+            key_hash: String::new(),
+            enabled: true,
+            created_at: 0,
+            expires_at: None,
+            allowed_models: Vec::new(),
+            budget: ApiKeyBudget::default(),
+            account_group: None,
+            priority: PriorityClass::Interactive,
+            usage: ApiKeyUsage::default(),
+            last_used_at: None,
+        }
+    }
+
+    #[test]
+    fn empty_allowlist_allows_everything() {
+        assert!(key().allows_model("gemini-3-pro"));
+    }
+
+    #[test]
+    fn allowlist_supports_wildcards() {
+        let mut k = key();
+        k.allowed_models = vec!["gemini-*".to_string(), "claude-sonnet-4-5".to_string()];
+        assert!(k.allows_model("gemini-3-flash"));
+        assert!(k.allows_model("claude-sonnet-4-5"));
+        assert!(!k.allows_model("claude-opus-4-5-thinking"));
+    }
+
+    #[test]
+    fn budget_exhaustion_checks_both_limits() {
+        let mut k = key();
+        k.budget.max_requests = Some(2);
+        k.usage.requests = 1;
+        assert!(!k.budget_exhausted());
+        k.usage.requests = 2;
+        assert!(k.budget_exhausted());
+
+        let mut k = key();
+        k.budget.max_tokens = Some(100);
+        k.usage.tokens = 150;
+        assert!(k.budget_exhausted());
+    }
+
+    #[test]
+    fn daily_period_rolls_over() {
+        let mut usage = ApiKeyUsage { requests: 5, tokens: 500, period_start: 86_400 };
+        assert!(!usage.roll_period(BudgetPeriod::Daily, 86_400 + 3600));
+        assert_eq!(usage.requests, 5);
+        assert!(usage.roll_period(BudgetPeriod::Daily, 2 * 86_400 + 10));
+        assert_eq!(usage, ApiKeyUsage { requests: 0, tokens: 0, period_start: 2 * 86_400 });
+    }
+
+    #[test]
+    fn adding_usage_respects_periods() {
+        let mut usage = ApiKeyUsage { requests: 5, tokens: 500, period_start: 86_400 };
+        usage.add(&ApiKeyUsage { requests: 1, tokens: 10, period_start: 86_400 });
+        assert_eq!(usage, ApiKeyUsage { requests: 6, tokens: 510, period_start: 86_400 });
+        usage.add(&ApiKeyUsage { requests: 9, tokens: 90, period_start: 0 });
+        assert_eq!(usage.requests, 6);
+        usage.add(&ApiKeyUsage { requests: 2, tokens: 20, period_start: 2 * 86_400 });
+        assert_eq!(usage, ApiKeyUsage { requests: 2, tokens: 20, period_start: 2 * 86_400 });
+    }
+
+    #[test]
+    fn total_period_never_rolls() {
+        let mut usage = ApiKeyUsage { requests: 5, tokens: 500, period_start: 0 };
+        assert!(!usage.roll_period(BudgetPeriod::Total, 10_000_000));
+        assert_eq!(usage.requests, 5);
+    }
+
+    #[test]
+    fn update_request_distinguishes_null_expiry() {
+        let clear: UpdateApiKeyRequest = serde_json::from_str(r#"{"expires_at":null}"#).unwrap();
+        assert_eq!(clear.expires_at, Some(None));
+        let keep: UpdateApiKeyRequest = serde_json::from_str("{}").unwrap();
+        assert_eq!(keep.expires_at, None);
+    }
+
+    #[test]
+    fn monthly_period_starts_on_first_day() {
+        // 2026-03-15T12:00:00Z -> 2026-03-01T00:00:00Z
+        assert_eq!(BudgetPeriod::Monthly.period_start(1_773_576_000), 1_772_323_200);
+    }
+}
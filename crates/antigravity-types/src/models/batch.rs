@@ -0,0 +1,258 @@
+//! Message batch models, shared by the Anthropic and OpenAI batch APIs.
+//!
+//! A batch is a list of independent requests executed in the background. Each
+//! request keeps its position, the caller's `custom_id` and, once finished, the
+//! response (or error) body it produced.
+
+use serde::{Deserialize, Serialize};
+
+/// API a batch was created through; decides the request and result formats.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum BatchApi {
+    /// `/v1/messages/batches`
+    Anthropic,
+    /// `/v1/batches` with a `/v1/files` input file
+    Openai,
+}
+
+impl BatchApi {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Anthropic => "anthropic",
+            Self::Openai => "openai",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        [Self::Anthropic, Self::Openai].into_iter().find(|api| api.as_str() == value)
+    }
+}
+
+/// Processing state of a batch.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum BatchStatus {
+    /// Requests are being executed
+    InProgress,
+    /// Cancel requested; running requests finish, pending ones are canceled
+    Canceling,
+    /// Every request has a result
+    Ended,
+}
+
+impl BatchStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::InProgress => "in_progress",
+            Self::Canceling => "canceling",
+            Self::Ended => "ended",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        [Self::InProgress, Self::Canceling, Self::Ended]
+            .into_iter()
+            .find(|status| status.as_str() == value)
+    }
+}
+
+/// Outcome of a single request in a batch.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum BatchItemStatus {
+    /// Not executed yet
+    #[default]
+    Pending,
+    /// Upstream returned a successful response
+    Succeeded,
+    /// Request failed; the result holds the error body
+    Errored,
+    /// Batch was canceled before the request ran
+    Canceled,
+    /// Batch expired before the request ran
+    Expired,
+}
+
+impl BatchItemStatus {
+    pub const ALL: [Self; 5] =
+        [Self::Pending, Self::Succeeded, Self::Errored, Self::Canceled, Self::Expired];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Pending => "pending",
+            Self::Succeeded => "succeeded",
+            Self::Errored => "errored",
+            Self::Canceled => "canceled",
+            Self::Expired => "expired",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|status| status.as_str() == value)
+    }
+}
+
+/// Number of requests in each state.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub struct BatchRequestCounts {
+    pub processing: u64,
+    pub succeeded: u64,
+    pub errored: u64,
+    pub canceled: u64,
+    pub expired: u64,
+}
+
+impl BatchRequestCounts {
+    pub fn add(&mut self, status: BatchItemStatus, count: u64) {
+        let field = match status {
+            BatchItemStatus::Pending => &mut self.processing,
+            BatchItemStatus::Succeeded => &mut self.succeeded,
+            BatchItemStatus::Errored => &mut self.errored,
+            BatchItemStatus::Canceled => &mut self.canceled,
+            BatchItemStatus::Expired => &mut self.expired,
+        };
+        *field = field.saturating_add(count);
+    }
+
+    pub fn total(&self) -> u64 {
+        [self.processing, self.succeeded, self.errored, self.canceled, self.expired]
+            .into_iter()
+            .fold(0, u64::saturating_add)
+    }
+}
+
+/// A batch and the aggregate state of its requests. Timestamps are unix seconds.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Batch {
+    pub id: String,
+    pub api: BatchApi,
+    /// Endpoint every request is sent to (`/v1/messages`, `/v1/chat/completions`)
+    pub endpoint: String,
+    /// Client key that created the batch; None for the global key
+    #[serde(default)]
+    pub api_key_id: Option<String>,
+    pub status: BatchStatus,
+    #[serde(default)]
+    pub metadata: serde_json::Map<String, serde_json::Value>,
+    /// OpenAI input file
+    #[serde(default)]
+    pub input_file_id: Option<String>,
+    /// OpenAI file with the successful responses, written when the batch ends
+    #[serde(default)]
+    pub output_file_id: Option<String>,
+    /// OpenAI file with the failed requests, written when the batch ends
+    #[serde(default)]
+    pub error_file_id: Option<String>,
+    pub created_at: i64,
+    pub expires_at: i64,
+    #[serde(default)]
+    pub cancel_initiated_at: Option<i64>,
+    #[serde(default)]
+    pub ended_at: Option<i64>,
+    /// Derived from the requests; not stored with the batch
+    #[serde(default)]
+    pub request_counts: BatchRequestCounts,
+}
+
+impl Batch {
+    /// Whether the batch may be seen and changed by the client key `key_id`.
+    /// Batches of the global key (and the global key itself) see everything.
+    pub fn is_visible_to(&self, key_id: Option<&str>) -> bool {
+        match key_id {
+            None => true,
+            Some(key_id) => self.api_key_id.as_deref() == Some(key_id),
+        }
+    }
+}
+
+/// One request of a batch.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct BatchItem {
+    /// Position in the submitted list, starting at 0
+    pub position: u32,
+    pub custom_id: String,
+    /// Request body sent to the batch endpoint
+    pub params: serde_json::Value,
+    #[serde(default)]
+    pub status: BatchItemStatus,
+    /// HTTP status of the response, once executed
+    #[serde(default)]
+    pub status_code: Option<u16>,
+    /// Response body for succeeded requests, error body otherwise
+    #[serde(default)]
+    pub result: Option<serde_json::Value>,
+    #[serde(default)]
+    pub completed_at: Option<i64>,
+}
+
+impl BatchItem {
+    pub fn new(position: u32, custom_id: String, params: serde_json::Value) -> Self {
+        Self {
+            position,
+            custom_id,
+            params,
+            status: BatchItemStatus::Pending,
+            status_code: None,
+            result: None,
+            completed_at: None,
+        }
+    }
+}
+
+/// Page of a batch listing, newest first.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BatchPage {
+    /// Return batches older than this one
+    pub after_id: Option<String>,
+    /// Return batches newer than this one
+    pub before_id: Option<String>,
+    pub limit: usize,
+}
+
+/// Uploaded or generated file of the OpenAI files API (content stored separately).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct BatchFile {
+    pub id: String,
+    pub filename: String,
+    /// `batch` for uploads, `batch_output` for generated results
+    pub purpose: String,
+    #[serde(default)]
+    pub api_key_id: Option<String>,
+    pub bytes: u64,
+    pub created_at: i64,
+}
+
+impl BatchFile {
+    /// Same visibility rule as [`Batch::is_visible_to`].
+    pub fn is_visible_to(&self, key_id: Option<&str>) -> bool {
+        match key_id {
+            None => true,
+            Some(key_id) => self.api_key_id.as_deref() == Some(key_id),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_by_status() {
+        let mut counts = BatchRequestCounts::default();
+        counts.add(BatchItemStatus::Pending, 3);
+        counts.add(BatchItemStatus::Succeeded, 2);
+        counts.add(BatchItemStatus::Expired, 1);
+        assert_eq!(counts.processing, 3);
+        assert_eq!(counts.total(), 6);
+    }
+
+    #[test]
+    fn statuses_round_trip_through_strings() {
+        for status in BatchItemStatus::ALL {
+            assert_eq!(BatchItemStatus::parse(status.as_str()), Some(status));
+        }
+        assert_eq!(BatchStatus::parse("canceling"), Some(BatchStatus::Canceling));
+        assert_eq!(BatchApi::parse("openai"), Some(BatchApi::Openai));
+    }
+}
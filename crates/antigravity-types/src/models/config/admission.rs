@@ -0,0 +1,105 @@
+//! Admission queue configuration.
+
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+/// Priority class of a request waiting for an account.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum PriorityClass {
+    /// Served first whenever an account frees up
+    #[default]
+    Interactive,
+    /// Only admitted while no interactive request is waiting
+    Batch,
+}
+
+impl PriorityClass {
+    pub const ALL: [Self; 2] = [Self::Interactive, Self::Batch];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Interactive => "interactive",
+            Self::Batch => "batch",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|p| p.as_str().eq_ignore_ascii_case(value.trim()))
+    }
+}
+
+/// Limits for one priority class.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Validate)]
+pub struct QueueClassConfig {
+    /// Requests that may wait at once; further requests are rejected with 429
+    #[validate(range(min = 1_usize, max = 100_000_usize))]
+    pub max_depth: usize,
+    /// Longest a request waits for an account before giving up
+    #[validate(range(min = 1_u64, max = 3600_u64))]
+    pub max_wait_secs: u64,
+}
+
+/// Admission queue in front of account selection.
+///
+/// When every account is rate-limited, quota-protected or over its AIMD limit,
+/// requests wait here instead of failing immediately.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Validate)]
+pub struct AdmissionConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default = "default_interactive")]
+    #[validate(nested)]
+    pub interactive: QueueClassConfig,
+    #[serde(default = "default_batch")]
+    #[validate(nested)]
+    pub batch: QueueClassConfig,
+    /// Interval of SSE comment pings sent to queued streaming clients
+    #[serde(default = "default_keepalive_secs")]
+    #[validate(range(min = 1_u64, max = 300_u64))]
+    pub keepalive_secs: u64,
+}
+
+impl AdmissionConfig {
+    pub fn class(&self, priority: PriorityClass) -> &QueueClassConfig {
+        match priority {
+            PriorityClass::Interactive => &self.interactive,
+            PriorityClass::Batch => &self.batch,
+        }
+    }
+}
+
+impl Default for AdmissionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            interactive: default_interactive(),
+            batch: default_batch(),
+            keepalive_secs: default_keepalive_secs(),
+        }
+    }
+}
+
+fn default_interactive() -> QueueClassConfig {
+    QueueClassConfig { max_depth: 64, max_wait_secs: 30 }
+}
+
+fn default_batch() -> QueueClassConfig {
+    QueueClassConfig { max_depth: 1024, max_wait_secs: 600 }
+}
+
+fn default_keepalive_secs() -> u64 {
+    10
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn priority_parses_case_insensitively() {
+        assert_eq!(PriorityClass::parse(" Batch"), Some(PriorityClass::Batch));
+        assert_eq!(PriorityClass::parse("interactive"), Some(PriorityClass::Interactive));
+        assert_eq!(PriorityClass::parse("urgent"), None);
+    }
+}
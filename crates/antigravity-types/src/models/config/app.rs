@@ -1,12 +1,13 @@
 //! Application-level configuration.
 
 use serde::{Deserialize, Serialize};
+use validator::Validate;
 
 use super::proxy::ProxyConfig;
 use super::session::{QuotaProtectionConfig, SmartWarmupConfig};
 
 /// Full application configuration.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
 pub struct AppConfig {
     /// UI language
     pub language: String,
@@ -24,6 +25,7 @@ pub struct AppConfig {
     pub default_export_path: Option<String>,
     /// Proxy configuration
     #[serde(default)]
+    #[validate(nested)]
     pub proxy: ProxyConfig,
     /// Custom Antigravity executable path
     pub antigravity_executable: Option<String>,
@@ -34,9 +36,11 @@ pub struct AppConfig {
     pub auto_launch: bool,
     /// Quota protection configuration
     #[serde(default)]
+    #[validate(nested)]
     pub quota_protection: QuotaProtectionConfig,
     /// Smart warmup configuration
     #[serde(default)]
+    #[validate(nested)]
     pub smart_warmup: SmartWarmupConfig,
 }
 
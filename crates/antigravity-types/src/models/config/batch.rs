@@ -0,0 +1,46 @@
+//! Message batch execution configuration.
+
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+/// Background execution of `/v1/messages/batches` and `/v1/batches`.
+///
+/// Batch requests run at the admission queue's batch priority and only on accounts
+/// whose remaining quota is above the quota protection threshold.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Validate)]
+pub struct BatchConfig {
+    /// Requests executed at once across all batches
+    #[serde(default = "default_concurrency")]
+    #[validate(range(min = 1_usize, max = 256_usize))]
+    pub concurrency: usize,
+    /// Requests accepted in a single batch
+    #[serde(default = "default_max_requests")]
+    #[validate(range(min = 1_usize, max = 100_000_usize))]
+    pub max_requests: usize,
+    /// Hours a batch may run before its remaining requests expire
+    #[serde(default = "default_expiry_hours")]
+    #[validate(range(min = 1_u64, max = 168_u64))]
+    pub expiry_hours: u64,
+}
+
+impl Default for BatchConfig {
+    fn default() -> Self {
+        Self {
+            concurrency: default_concurrency(),
+            max_requests: default_max_requests(),
+            expiry_hours: default_expiry_hours(),
+        }
+    }
+}
+
+fn default_concurrency() -> usize {
+    4
+}
+
+fn default_max_requests() -> usize {
+    10_000
+}
+
+fn default_expiry_hours() -> u64 {
+    24
+}
@@ -0,0 +1,38 @@
+//! Request/response capture configuration.
+
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+/// Persistent capture of request and response bodies for later search.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Validate)]
+pub struct CaptureConfig {
+    /// Store bodies for every request, not only errors
+    #[serde(default)]
+    pub enabled: bool,
+    /// Days to keep captured requests
+    #[validate(range(min = 1_u32, max = 365_u32))]
+    #[serde(default = "default_retention_days")]
+    pub retention_days: u32,
+    /// Per-body size limit; larger bodies are truncated
+    #[validate(range(min = 1024_usize, max = 4_194_304_usize))]
+    #[serde(default = "default_max_body_bytes")]
+    pub max_body_bytes: usize,
+}
+
+const fn default_retention_days() -> u32 {
+    7
+}
+
+const fn default_max_body_bytes() -> usize {
+    256 * 1024
+}
+
+impl Default for CaptureConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            retention_days: default_retention_days(),
+            max_body_bytes: default_max_body_bytes(),
+        }
+    }
+}
@@ -0,0 +1,37 @@
+//! Multi-instance shared state configuration.
+
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+/// Transport used to share state between gateway instances.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ClusterBackendKind {
+    /// PostgreSQL `LISTEN`/`NOTIFY` plus advisory-lock leader election (needs `DATABASE_URL`)
+    #[default]
+    Postgres,
+}
+
+/// Share rate-limit lockouts, sticky sessions, circuit breaker state and AIMD limits
+/// between instances behind a load balancer. Read once at server startup.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Validate)]
+pub struct ClusterConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default)]
+    pub backend: ClusterBackendKind,
+    /// Notification channel; instances sharing a database but not state use different names
+    #[validate(length(min = 1_u64, max = 63_u64))]
+    #[serde(default = "default_channel")]
+    pub channel: String,
+}
+
+fn default_channel() -> String {
+    "antigravity_cluster".to_string()
+}
+
+impl Default for ClusterConfig {
+    fn default() -> Self {
+        Self { enabled: false, backend: ClusterBackendKind::default(), channel: default_channel() }
+    }
+}
@@ -0,0 +1,41 @@
+//! Hedged upstream request configuration.
+
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+/// Second attempts on another account when the first one looks slow or near its limit.
+///
+/// An account at 95%+ of its AIMD limit is hedged immediately, one at 85%+ once the
+/// model's median time to first byte has passed, and any other request once the
+/// model's p95 time to first byte has passed. The first successful response wins.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Validate)]
+pub struct HedgingConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// First-byte samples a model needs before its latency percentiles are trusted
+    #[serde(default = "default_min_samples")]
+    #[validate(range(min = 5_usize, max = 10_000_usize))]
+    pub min_samples: usize,
+    /// Shortest delay before a latency-based hedge fires
+    #[serde(default = "default_min_delay_ms")]
+    #[validate(range(min = 50_u64, max = 60_000_u64))]
+    pub min_delay_ms: u64,
+}
+
+impl Default for HedgingConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            min_samples: default_min_samples(),
+            min_delay_ms: default_min_delay_ms(),
+        }
+    }
+}
+
+fn default_min_samples() -> usize {
+    20
+}
+
+fn default_min_delay_ms() -> u64 {
+    1000
+}
@@ -1,21 +1,41 @@
 //! Application and proxy configuration models.
 
+mod admission;
 mod app;
+mod batch;
+mod capture;
+mod cluster;
 mod enums;
+mod hedging;
+mod notifier;
+mod providers;
 mod proxy;
+mod routing;
 mod session;
+mod spend;
+mod telemetry;
 mod thinking;
 mod zai;
 
+pub use admission::{AdmissionConfig, PriorityClass, QueueClassConfig};
 pub use app::AppConfig;
+pub use batch::BatchConfig;
+pub use capture::CaptureConfig;
+pub use cluster::{ClusterBackendKind, ClusterConfig};
 pub use enums::{
     Protocol, ProxyAuthMode, ProxyRotationStrategy, SchedulingMode, UpstreamProxyMode,
     ZaiDispatchMode,
 };
+pub use hedging::HedgingConfig;
+pub use notifier::{NotifierConfig, PoolAlertRule, WebhookFormat, WebhookTarget};
+pub use providers::{ProviderConfig, ProviderProtocol};
 pub use proxy::ProxyConfig;
+pub use routing::{RoutingRule, RuleAction, RuleMatch};
 pub use session::{
     AccountProxyPoolConfig, ExperimentalConfig, ProxyAssignmentStrategy, QuotaProtectionConfig,
     SmartWarmupConfig, StickySessionConfig, UpstreamProxyConfig,
 };
+pub use spend::{BudgetAction, BudgetScope, ModelPrice, SpendBudget, SpendConfig, SpendPeriod};
+pub use telemetry::TelemetryConfig;
 pub use thinking::{ThinkingBudgetConfig, ThinkingBudgetMode};
 pub use zai::{ZaiConfig, ZaiMcpConfig, ZaiModelDefaults};
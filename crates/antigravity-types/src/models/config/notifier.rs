@@ -0,0 +1,114 @@
+//! Outbound webhook notification configuration.
+
+use serde::{Deserialize, Serialize};
+use validator::{Validate, ValidationError};
+
+/// Payload shape sent to a webhook target.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum WebhookFormat {
+    /// The notification as JSON
+    #[default]
+    Generic,
+    /// Slack incoming webhook (`{"text": ...}`)
+    Slack,
+    /// Telegram Bot API `sendMessage` (`url` = `https://api.telegram.org/bot<token>/sendMessage`)
+    Telegram,
+}
+
+/// One webhook receiver.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Validate)]
+#[validate(schema(function = "validate_target"))]
+pub struct WebhookTarget {
+    /// Unique name shown in the delivery log
+    #[validate(length(min = 1_u64))]
+    pub name: String,
+    #[validate(url)]
+    pub url: String,
+    #[serde(default)]
+    pub format: WebhookFormat,
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// Event kinds to deliver (e.g. `account_disabled`, `pool_*`); empty delivers all
+    #[serde(default)]
+    pub events: Vec<String>,
+    /// HMAC-SHA256 key; when set, requests carry `X-Antigravity-Signature`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub secret: Option<String>,
+    /// Telegram chat ID (required for the Telegram format)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub chat_id: Option<String>,
+}
+
+fn validate_target(target: &WebhookTarget) -> Result<(), ValidationError> {
+    if target.format == WebhookFormat::Telegram
+        && target.chat_id.as_deref().is_none_or(str::is_empty)
+    {
+        return Err(ValidationError::new("telegram_chat_id_required"));
+    }
+    Ok(())
+}
+
+/// Alert when fewer than `min_available` accounts can serve `model`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Validate)]
+pub struct PoolAlertRule {
+    #[validate(length(min = 1_u64))]
+    pub model: String,
+    #[validate(range(min = 1_u32))]
+    pub min_available: u32,
+}
+
+/// Webhook notifications for account and pool events.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Validate)]
+pub struct NotifierConfig {
+    #[serde(default)]
+    #[validate(nested)]
+    pub targets: Vec<WebhookTarget>,
+    #[serde(default)]
+    #[validate(nested)]
+    pub pool_alerts: Vec<PoolAlertRule>,
+    /// Delivery attempts per notification before giving up
+    #[validate(range(min = 1_u32, max = 10_u32))]
+    #[serde(default = "default_max_attempts")]
+    pub max_attempts: u32,
+}
+
+const fn default_true() -> bool {
+    true
+}
+
+const fn default_max_attempts() -> u32 {
+    5
+}
+
+impl Default for NotifierConfig {
+    fn default() -> Self {
+        Self { targets: Vec::new(), pool_alerts: Vec::new(), max_attempts: default_max_attempts() }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn telegram_target_requires_chat_id() {
+        let mut target = WebhookTarget {
+            name: "ops".to_string(),
+            url: "https://api.telegram.org/bot123:abc/sendMessage".to_string(),
+            format: WebhookFormat::Telegram,
+            enabled: true,
+            events: Vec::new(),
+            secret: None,
+            chat_id: None,
+        };
+        assert!(target.validate().is_err());
+        target.chat_id = Some("-100123".to_string());
+        assert!(target.validate().is_ok());
+
+        let parsed: WebhookTarget =
+            serde_json::from_str(r#"{"name":"hook","url":"https://example.com/hook"}"#).unwrap();
+        assert!(parsed.enabled);
+        assert_eq!(parsed.format, WebhookFormat::Generic);
+    }
+}
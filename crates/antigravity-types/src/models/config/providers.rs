@@ -0,0 +1,82 @@
+//! Third-party upstream provider configuration.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use validator::Validate;
+
+/// Wire protocol spoken by a provider.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ProviderProtocol {
+    /// OpenAI Chat Completions (`{base_url}/chat/completions`)
+    #[default]
+    Openai,
+    /// Anthropic Messages (`{base_url}/messages`)
+    Anthropic,
+}
+
+impl fmt::Display for ProviderProtocol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::Openai => write!(f, "openai"),
+            Self::Anthropic => write!(f, "anthropic"),
+        }
+    }
+}
+
+/// An OpenAI- or Anthropic-compatible upstream (vLLM, Ollama, hosted APIs).
+///
+/// Requests reach a provider either as `name:model` or by matching one of its
+/// model globs.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Validate)]
+pub struct ProviderConfig {
+    /// Unique name, used as the `name:` routing prefix
+    #[validate(length(min = 1_u64))]
+    pub name: String,
+    /// Disabled providers are never selected
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// Versioned API root that endpoint paths are appended to, for both protocols
+    /// (e.g. `http://localhost:11434/v1`, `https://api.anthropic.com/v1`)
+    #[validate(url)]
+    pub base_url: String,
+    /// API key (may be empty for local servers)
+    #[serde(default)]
+    pub api_key: String,
+    /// Wire protocol
+    #[serde(default)]
+    pub protocol: ProviderProtocol,
+    /// Model globs served by this provider (e.g. `llama-*`)
+    #[serde(default)]
+    pub models: Vec<String>,
+    /// Higher priority providers are tried first when several match
+    #[serde(default)]
+    pub priority: i32,
+}
+
+const fn default_true() -> bool {
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_defaults_from_minimal_json() {
+        let json = r#"{"name":"ollama","base_url":"http://localhost:11434/v1"}"#;
+        let config: ProviderConfig = serde_json::from_str(json).expect("deserialize");
+        assert!(config.enabled);
+        assert_eq!(config.protocol, ProviderProtocol::Openai);
+        assert_eq!(config.priority, 0);
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_rejects_invalid_url() {
+        let json = r#"{"name":"x","base_url":"not a url","protocol":"anthropic"}"#;
+        let config: ProviderConfig = serde_json::from_str(json).expect("deserialize");
+        assert_eq!(config.protocol, ProviderProtocol::Anthropic);
+        assert!(config.validate().is_err());
+    }
+}
@@ -2,12 +2,22 @@
 
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use validator::Validate;
+use validator::{Validate, ValidateUrl, ValidationError};
 
+use super::admission::AdmissionConfig;
+use super::batch::BatchConfig;
+use super::capture::CaptureConfig;
+use super::cluster::ClusterConfig;
 use super::enums::ProxyAuthMode;
+use super::hedging::HedgingConfig;
+use super::notifier::NotifierConfig;
+use super::providers::ProviderConfig;
+use super::routing::RoutingRule;
 use super::session::{
     AccountProxyPoolConfig, ExperimentalConfig, StickySessionConfig, UpstreamProxyConfig,
 };
+use super::spend::SpendConfig;
+use super::telemetry::TelemetryConfig;
 use super::thinking::ThinkingBudgetConfig;
 use super::zai::ZaiConfig;
 
@@ -44,6 +54,38 @@ pub struct ProxyConfig {
     /// Enable request logging
     #[serde(default)]
     pub enable_logging: bool,
+    /// Queueing of requests while every account is busy
+    #[serde(default)]
+    #[validate(nested)]
+    pub admission: AdmissionConfig,
+    /// Background execution of message batches
+    #[serde(default)]
+    #[validate(nested)]
+    pub batch: BatchConfig,
+    /// Persistent request/response capture
+    #[serde(default)]
+    #[validate(nested)]
+    pub capture: CaptureConfig,
+    /// Shared state between gateway instances (applied on restart)
+    #[serde(default)]
+    #[validate(nested)]
+    pub cluster: ClusterConfig,
+    /// Second attempts on another account for slow or near-limit requests
+    #[serde(default)]
+    #[validate(nested)]
+    pub hedging: HedgingConfig,
+    /// Webhook notifications for account and pool events
+    #[serde(default)]
+    #[validate(nested)]
+    pub notifier: NotifierConfig,
+    /// Model prices and spend budgets
+    #[serde(default)]
+    #[validate(nested)]
+    pub spend: SpendConfig,
+    /// OpenTelemetry span export (applied on restart)
+    #[serde(default)]
+    #[validate(nested)]
+    pub telemetry: TelemetryConfig,
     /// Upstream proxy configuration
     #[serde(default)]
     #[validate(nested)]
@@ -52,6 +94,14 @@ pub struct ProxyConfig {
     #[serde(default)]
     #[validate(nested)]
     pub zai: ZaiConfig,
+    /// OpenAI/Anthropic-compatible upstream providers
+    #[serde(default)]
+    #[validate(nested)]
+    pub providers: Vec<ProviderConfig>,
+    /// Ordered routing rules, checked before model mapping
+    #[serde(default)]
+    #[validate(nested)]
+    pub routing_rules: Vec<RoutingRule>,
     /// Sticky session configuration
     #[serde(default)]
     #[validate(nested)]
@@ -71,6 +121,21 @@ pub struct ProxyConfig {
     #[serde(default)]
     #[validate(nested)]
     pub account_proxy_pool: AccountProxyPoolConfig,
+    /// Cloud Code endpoints tried in order; empty = built-in endpoints.
+    /// `ANTIGRAVITY_UPSTREAM_URL` overrides this when set.
+    #[serde(default)]
+    #[validate(custom(function = "validate_urls"))]
+    pub upstream_urls: Vec<String>,
+    /// Keep images from the last N user turns, replace older ones with a placeholder.
+    /// `ANTIGRAVITY_IMAGE_RETENTION_TURNS` overrides this when set.
+    #[validate(range(max = 1000_u32))]
+    #[serde(default = "default_image_retention_turns")]
+    pub image_retention_turns: u32,
+    /// Exit-IP endpoints for per-account proxy health checks; empty = built-in endpoints.
+    /// `ANTIGRAVITY_HEALTH_CHECK_URL` overrides this when set.
+    #[serde(default)]
+    #[validate(custom(function = "validate_urls"))]
+    pub health_check_urls: Vec<String>,
 }
 
 impl Default for ProxyConfig {
@@ -85,13 +150,26 @@ impl Default for ProxyConfig {
             custom_mapping: HashMap::new(),
             request_timeout: 120,
             enable_logging: false,
+            admission: AdmissionConfig::default(),
+            batch: BatchConfig::default(),
+            capture: CaptureConfig::default(),
+            cluster: ClusterConfig::default(),
+            hedging: HedgingConfig::default(),
+            notifier: NotifierConfig::default(),
+            spend: SpendConfig::default(),
+            telemetry: TelemetryConfig::default(),
             upstream_proxy: UpstreamProxyConfig::default(),
             zai: ZaiConfig::default(),
+            providers: Vec::new(),
+            routing_rules: Vec::new(),
             scheduling: StickySessionConfig::default(),
             experimental: ExperimentalConfig::default(),
             thinking_budget: ThinkingBudgetConfig::default(),
             preferred_account_id: None,
             account_proxy_pool: AccountProxyPoolConfig::default(),
+            upstream_urls: Vec::new(),
+            image_retention_turns: default_image_retention_turns(),
+            health_check_urls: Vec::new(),
         }
     }
 }
@@ -115,3 +193,15 @@ impl ProxyConfig {
 pub const fn default_request_timeout() -> u64 {
     120
 }
+
+pub const fn default_image_retention_turns() -> u32 {
+    5
+}
+
+fn validate_urls(urls: &[String]) -> Result<(), ValidationError> {
+    if urls.iter().all(|url| url.validate_url()) {
+        Ok(())
+    } else {
+        Err(ValidationError::new("url"))
+    }
+}
@@ -0,0 +1,161 @@
+//! Ordered request routing rules.
+//!
+//! Rules are checked top to bottom before model mapping; the first enabled rule whose
+//! conditions all hold decides the request. Requests no rule matches are routed by
+//! `custom_mapping` and the built-in model table as before.
+
+use chrono::NaiveTime;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use validator::{Validate, ValidationError};
+
+use super::enums::Protocol;
+use super::thinking::ThinkingBudgetConfig;
+
+/// Conditions of a rule. Unset conditions match everything; set ones must all hold.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default, Validate)]
+#[validate(schema(function = "validate_match"))]
+pub struct RuleMatch {
+    /// Requested model names or globs (`claude-*`)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub models: Vec<String>,
+    /// Client API key IDs or labels
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub api_keys: Vec<String>,
+    /// Client protocols (`OpenAI`, `Anthropic`, `Gemini`)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub protocols: Vec<Protocol>,
+    /// Header name to value glob; a missing header doesn't match
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub headers: BTreeMap<String, String>,
+    /// Estimated prompt size, inclusive
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_prompt_tokens: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_prompt_tokens: Option<u64>,
+    /// `true`: only requests declaring tools; `false`: only requests without
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub has_tools: Option<bool>,
+    /// `true`: only requests with image input; `false`: only requests without
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub has_images: Option<bool>,
+    /// UTC window `HH:MM-HH:MM`, end exclusive; `22:00-06:00` wraps past midnight
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub time_of_day: Option<String>,
+}
+
+impl RuleMatch {
+    /// Parsed `time_of_day`; `None` when unset or malformed.
+    pub fn time_window(&self) -> Option<(NaiveTime, NaiveTime)> {
+        let (start, end) = self.time_of_day.as_deref()?.split_once('-')?;
+        Some((
+            NaiveTime::parse_from_str(start.trim(), "%H:%M").ok()?,
+            NaiveTime::parse_from_str(end.trim(), "%H:%M").ok()?,
+        ))
+    }
+}
+
+fn validate_match(conditions: &RuleMatch) -> Result<(), ValidationError> {
+    if conditions.time_of_day.is_some() && conditions.time_window().is_none() {
+        return Err(ValidationError::new("time_of_day_format"));
+    }
+    if let (Some(min), Some(max)) = (conditions.min_prompt_tokens, conditions.max_prompt_tokens) {
+        if min > max {
+            return Err(ValidationError::new("prompt_tokens_range"));
+        }
+    }
+    Ok(())
+}
+
+/// What a rule does to the requests it matches.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum RuleAction {
+    /// Replace the requested model; custom mappings still apply to the new name
+    RewriteModel { model: String },
+    /// Serve the request only from accounts in this group
+    PinAccountGroup { group: String },
+    /// Use this thinking budget instead of the global one
+    ThinkingBudget { budget: ThinkingBudgetConfig },
+    /// Refuse the request with 403
+    Reject {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        message: Option<String>,
+    },
+}
+
+/// One routing rule.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Validate)]
+#[validate(schema(function = "validate_rule"))]
+pub struct RoutingRule {
+    /// Unique name reported by the explain endpoint and in logs
+    #[validate(length(min = 1_u64))]
+    pub name: String,
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    #[serde(default, rename = "match")]
+    #[validate(nested)]
+    pub conditions: RuleMatch,
+    pub action: RuleAction,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn validate_rule(rule: &RoutingRule) -> Result<(), ValidationError> {
+    match &rule.action {
+        RuleAction::RewriteModel { model } if model.trim().is_empty() => {
+            Err(ValidationError::new("rewrite_model_required"))
+        },
+        RuleAction::PinAccountGroup { group } if group.trim().is_empty() => {
+            Err(ValidationError::new("account_group_required"))
+        },
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(conditions: RuleMatch, action: RuleAction) -> RoutingRule {
+        RoutingRule { name: "r".to_string(), enabled: true, conditions, action }
+    }
+
+    #[test]
+    fn parses_rule_with_match_table() {
+        let json = serde_json::json!({
+            "name": "night-batch",
+            "match": { "api_keys": ["batch"], "time_of_day": "22:00-06:00" },
+            "action": { "type": "pin_account_group", "group": "batch" }
+        });
+        let parsed: RoutingRule = serde_json::from_value(json).unwrap();
+        assert!(parsed.enabled);
+        assert_eq!(
+            parsed.conditions.time_window(),
+            Some((
+                NaiveTime::from_hms_opt(22, 0, 0).unwrap(),
+                NaiveTime::from_hms_opt(6, 0, 0).unwrap()
+            ))
+        );
+        assert!(parsed.validate().is_ok());
+    }
+
+    #[test]
+    fn validates_conditions_and_actions() {
+        let reject = RuleAction::Reject { message: None };
+        let bad_time = RuleMatch { time_of_day: Some("9am-5pm".to_string()), ..Default::default() };
+        assert!(rule(bad_time, reject.clone()).validate().is_err());
+
+        let bad_range = RuleMatch {
+            min_prompt_tokens: Some(10),
+            max_prompt_tokens: Some(5),
+            ..Default::default()
+        };
+        assert!(rule(bad_range, reject).validate().is_err());
+
+        let empty_model = RuleAction::RewriteModel { model: " ".to_string() };
+        assert!(rule(RuleMatch::default(), empty_model).validate().is_err());
+    }
+}
@@ -24,6 +24,9 @@ pub struct ExperimentalConfig {
     /// Enable usage scaling for context window optimization
     #[serde(default)]
     pub enable_usage_scaling: bool,
+    /// Register `cache_control` prefixes as upstream cached content
+    #[serde(default)]
+    pub enable_context_caching: bool,
 }
 
 /// Sticky session configuration.
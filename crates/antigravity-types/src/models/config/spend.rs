@@ -0,0 +1,160 @@
+//! Spend accounting configuration: model prices and budgets.
+//!
+//! Amounts are integer microdollars (1 USD = 1_000_000) so totals add up exactly.
+
+use crate::models::api_key::BudgetPeriod;
+use serde::{Deserialize, Serialize};
+use validator::{Validate, ValidationError};
+
+/// Price of a model per million tokens, in microdollars.
+///
+/// `$3.00 / MTok` is written as `3000000`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Validate)]
+pub struct ModelPrice {
+    /// Model name or glob (e.g. `claude-sonnet-*`); exact names win over globs
+    #[validate(length(min = 1_u64))]
+    pub model: String,
+    /// Uncached input tokens
+    #[serde(default)]
+    pub input: u64,
+    /// Output tokens (including thinking)
+    #[serde(default)]
+    pub output: u64,
+    /// Input tokens served from the prompt cache
+    #[serde(default)]
+    pub cached: u64,
+}
+
+/// Window a budget or usage rollup covers (UTC).
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum SpendPeriod {
+    /// Calendar day
+    Daily,
+    /// Calendar month
+    #[default]
+    Monthly,
+}
+
+impl SpendPeriod {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Daily => "daily",
+            Self::Monthly => "monthly",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        [Self::Daily, Self::Monthly].into_iter().find(|p| p.as_str() == value)
+    }
+
+    /// Start of the period containing `now` (unix seconds).
+    pub fn period_start(self, now: i64) -> i64 {
+        match self {
+            Self::Daily => BudgetPeriod::Daily.period_start(now),
+            Self::Monthly => BudgetPeriod::Monthly.period_start(now),
+        }
+    }
+}
+
+/// What a budget's spend is measured over.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum BudgetScope {
+    /// All traffic through the proxy
+    #[default]
+    Global,
+    /// One client API key (`target` = key ID)
+    ApiKey,
+    /// One model or model glob (`target` = model pattern)
+    Model,
+    /// One upstream account (`target` = account email)
+    Account,
+}
+
+/// What happens once a budget's limit is reached.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum BudgetAction {
+    /// Emit a budget alert, keep serving
+    #[default]
+    Warn,
+    /// Emit a budget alert and reject further requests with 429 until the period ends
+    Block,
+}
+
+/// Spend limit for one scope and period.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Validate)]
+#[validate(schema(function = "validate_budget"))]
+pub struct SpendBudget {
+    /// Unique name shown in alerts
+    #[validate(length(min = 1_u64))]
+    pub name: String,
+    #[serde(default)]
+    pub scope: BudgetScope,
+    /// Key ID, model pattern or account email; unused for global budgets
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target: Option<String>,
+    #[serde(default)]
+    pub period: SpendPeriod,
+    /// Limit in microdollars
+    #[validate(range(min = 1_u64))]
+    pub limit: u64,
+    #[serde(default)]
+    pub action: BudgetAction,
+}
+
+fn validate_budget(budget: &SpendBudget) -> Result<(), ValidationError> {
+    let has_target = budget.target.as_deref().is_some_and(|t| !t.is_empty());
+    if budget.scope != BudgetScope::Global && !has_target {
+        return Err(ValidationError::new("budget_target_required"));
+    }
+    // Clients cannot choose the upstream account, so rejecting them would not help
+    if budget.scope == BudgetScope::Account && budget.action == BudgetAction::Block {
+        return Err(ValidationError::new("account_budget_cannot_block"));
+    }
+    Ok(())
+}
+
+/// Spend accounting settings.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Validate, Default)]
+pub struct SpendConfig {
+    /// Per-model prices; unpriced models are counted with zero cost
+    #[serde(default)]
+    #[validate(nested)]
+    pub prices: Vec<ModelPrice>,
+    #[serde(default)]
+    #[validate(nested)]
+    pub budgets: Vec<SpendBudget>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn budget(scope: BudgetScope, target: Option<&str>, action: BudgetAction) -> SpendBudget {
+        SpendBudget {
+            name: "b".to_string(),
+            scope,
+            target: target.map(str::to_string),
+            period: SpendPeriod::Daily,
+            limit: 1_000_000,
+            action,
+        }
+    }
+
+    #[test]
+    fn budget_validation() {
+        assert!(budget(BudgetScope::Global, None, BudgetAction::Block).validate().is_ok());
+        assert!(budget(BudgetScope::ApiKey, None, BudgetAction::Warn).validate().is_err());
+        assert!(budget(BudgetScope::Model, Some("gemini-*"), BudgetAction::Block)
+            .validate()
+            .is_ok());
+        assert!(budget(BudgetScope::Account, Some("a@x.com"), BudgetAction::Block)
+            .validate()
+            .is_err());
+        assert!(budget(BudgetScope::Account, Some("a@x.com"), BudgetAction::Warn)
+            .validate()
+            .is_ok());
+    }
+}
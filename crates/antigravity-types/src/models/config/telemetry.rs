@@ -0,0 +1,32 @@
+//! OpenTelemetry trace export configuration.
+
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+/// OTLP span export. Read once at server startup.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Validate)]
+pub struct TelemetryConfig {
+    /// Export spans over OTLP/HTTP
+    #[serde(default)]
+    pub enabled: bool,
+    /// Collector traces endpoint, e.g. `http://localhost:4318/v1/traces`.
+    /// None = `OTEL_EXPORTER_OTLP_TRACES_ENDPOINT` / `OTEL_EXPORTER_OTLP_ENDPOINT`,
+    /// falling back to the local collector default
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(url)]
+    pub otlp_endpoint: Option<String>,
+    /// `service.name` resource attribute
+    #[validate(length(min = 1_u64))]
+    #[serde(default = "default_service_name")]
+    pub service_name: String,
+}
+
+fn default_service_name() -> String {
+    "antigravity-server".to_string()
+}
+
+impl Default for TelemetryConfig {
+    fn default() -> Self {
+        Self { enabled: false, otlp_endpoint: None, service_name: default_service_name() }
+    }
+}
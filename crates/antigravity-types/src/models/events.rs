@@ -0,0 +1,108 @@
+//! Live monitor events.
+
+use super::quota::QuotaData;
+use super::spend::BudgetAlert;
+use super::stats::ProxyRequestLog;
+use serde::{Deserialize, Serialize};
+
+/// Account state transition reported to the live monitor.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum AccountState {
+    /// Account (or one of its models) was locked out by the rate limiter
+    RateLimited,
+    /// Circuit breaker opened after repeated failures
+    CircuitOpened,
+    /// Circuit breaker closed again
+    CircuitClosed,
+    /// Account was disabled (e.g. revoked refresh token)
+    Disabled,
+}
+
+/// Account state change.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct AccountStateChange {
+    /// Account ID
+    pub account_id: String,
+    /// New state
+    pub state: AccountState,
+    /// Model the change is scoped to, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    /// Human-readable reason
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    /// Seconds until the account is usable again
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retry_after_sec: Option<u64>,
+}
+
+/// Payload of a live monitor event.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(tag = "type", content = "data", rename_all = "snake_case")]
+pub enum MonitorEventPayload {
+    /// A proxied request finished
+    RequestLog(Box<ProxyRequestLog>),
+    /// An account changed state
+    AccountState(AccountStateChange),
+    /// An account's quota was refreshed
+    QuotaUpdated {
+        /// Account ID
+        account_id: String,
+        /// New quota
+        quota: QuotaData,
+    },
+    /// A spend budget reached its limit
+    BudgetAlert(BudgetAlert),
+}
+
+impl MonitorEventPayload {
+    /// Event name used on the SSE stream.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::RequestLog(_) => "request_log",
+            Self::AccountState(_) => "account_state",
+            Self::QuotaUpdated { .. } => "quota_updated",
+            Self::BudgetAlert(_) => "budget_alert",
+        }
+    }
+}
+
+/// Live monitor event with a monotonically increasing ID for resuming.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct MonitorEvent {
+    /// Event ID (increasing within one server process)
+    pub id: u64,
+    /// Emission time (Unix milliseconds)
+    pub timestamp: i64,
+    /// Event payload
+    #[serde(flatten)]
+    pub payload: MonitorEventPayload,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn event_is_tagged_by_kind() {
+        let event = MonitorEvent {
+            id: 7,
+            timestamp: 1,
+            payload: MonitorEventPayload::AccountState(AccountStateChange {
+                account_id: "acc-1".to_string(),
+                state: AccountState::CircuitOpened,
+                model: None,
+                reason: Some("5 failures".to_string()),
+                retry_after_sec: None,
+            }),
+        };
+        let json = serde_json::to_value(&event).unwrap();
+        assert_eq!(json["type"], "account_state");
+        assert_eq!(json["data"]["state"], "circuit_opened");
+        assert_eq!(event.payload.kind(), "account_state");
+
+        let back: MonitorEvent = serde_json::from_value(json).unwrap();
+        assert_eq!(back, event);
+    }
+}
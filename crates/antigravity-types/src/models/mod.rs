@@ -3,28 +3,55 @@
 //! This module contains all shared data structures used across the Antigravity ecosystem.
 
 pub mod account;
+pub mod admin;
+pub mod api_key;
+pub mod batch;
 pub mod config;
 pub mod device;
+pub mod events;
 pub mod model_family;
+pub mod notification;
 pub mod quota;
+pub mod spend;
 pub mod stats;
 pub mod sync;
 pub mod token;
 
 // Re-export all models
 pub use account::{Account, AccountIndex, AccountSummary};
+pub use admin::{
+    AccountInfo, QuotaResponse, RefreshQuotaRequest, ServerStatus, ToggleProxyRequest,
+    ToggleProxyResponse,
+};
+pub use api_key::{
+    ApiKey, ApiKeyBudget, ApiKeyUsage, BudgetPeriod, CreateApiKeyRequest, CreatedApiKey,
+    UpdateApiKeyRequest,
+};
+pub use batch::{
+    Batch, BatchApi, BatchFile, BatchItem, BatchItemStatus, BatchPage, BatchRequestCounts,
+    BatchStatus,
+};
 pub use config::{
-    AppConfig, ExperimentalConfig, Protocol, ProxyAuthMode, ProxyConfig, ProxyRotationStrategy,
-    QuotaProtectionConfig, SchedulingMode, SmartWarmupConfig, StickySessionConfig,
-    ThinkingBudgetConfig, ThinkingBudgetMode, UpstreamProxyConfig, UpstreamProxyMode, ZaiConfig,
-    ZaiDispatchMode, ZaiMcpConfig, ZaiModelDefaults,
+    AdmissionConfig, AppConfig, BatchConfig, BudgetAction, BudgetScope, CaptureConfig,
+    ClusterBackendKind, ClusterConfig, ExperimentalConfig, HedgingConfig, ModelPrice,
+    NotifierConfig, PoolAlertRule, PriorityClass, Protocol, ProviderConfig, ProviderProtocol,
+    ProxyAuthMode, ProxyConfig, ProxyRotationStrategy, QueueClassConfig, QuotaProtectionConfig,
+    RoutingRule, RuleAction, RuleMatch, SchedulingMode, SmartWarmupConfig, SpendBudget,
+    SpendConfig, SpendPeriod, StickySessionConfig, TelemetryConfig, ThinkingBudgetConfig,
+    ThinkingBudgetMode, UpstreamProxyConfig, UpstreamProxyMode, WebhookFormat, WebhookTarget,
+    ZaiConfig, ZaiDispatchMode, ZaiMcpConfig, ZaiModelDefaults,
 };
 pub use device::{DeviceProfile, DeviceProfileVersion, DeviceProfiles};
+pub use events::{AccountState, AccountStateChange, MonitorEvent, MonitorEventPayload};
 pub use model_family::ModelFamily;
+pub use notification::{DeliveryStatus, Notification, NotificationSeverity, WebhookDelivery};
 pub use quota::{ModelQuota, QuotaData};
+pub use spend::{
+    BudgetAlert, BudgetStatus, UsageDimension, UsageQuery, UsageReport, UsageRollup, UsageTotals,
+};
 pub use stats::{
-    DashboardStats, ProxyRequestLog, ProxyStats, ProxyStatus, RefreshStats, TokenUsageStats,
-    UpdateInfo,
+    DashboardStats, ProxyLogQuery, ProxyRequestLog, ProxyStats, ProxyStatus, RefreshStats,
+    TokenUsageStats, UpdateInfo,
 };
 pub use sync::{MappingEntry, ProxyAssignment, SyncableMapping, SyncableProxyAssignments};
 pub use token::TokenData;
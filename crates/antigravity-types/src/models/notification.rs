@@ -0,0 +1,64 @@
+//! Outbound notifications and their delivery log.
+
+use serde::{Deserialize, Serialize};
+
+/// How urgent a notification is.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationSeverity {
+    #[default]
+    Info,
+    Warning,
+    Critical,
+}
+
+/// Account or pool event sent to webhook targets.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Notification {
+    /// Event kind used by target filters (e.g. `account_disabled`, `pool_low`)
+    pub kind: String,
+    pub severity: NotificationSeverity,
+    /// One-line human-readable summary
+    pub title: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub account_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub account_email: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    /// Event-specific details
+    #[serde(default)]
+    pub data: serde_json::Value,
+    /// Emission time (Unix milliseconds)
+    pub timestamp: i64,
+}
+
+/// Final outcome of delivering a notification to one target.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DeliveryStatus {
+    Delivered,
+    Failed,
+}
+
+/// Delivery log entry.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct WebhookDelivery {
+    /// Increasing within one server process
+    pub id: u64,
+    /// Target name
+    pub target: String,
+    /// Notification kind
+    pub kind: String,
+    pub title: String,
+    pub status: DeliveryStatus,
+    pub attempts: u32,
+    /// Last HTTP status received
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub http_status: Option<u16>,
+    /// Last error, for failed deliveries
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Completion time (Unix milliseconds)
+    pub timestamp: i64,
+}
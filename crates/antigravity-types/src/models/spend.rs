@@ -0,0 +1,145 @@
+//! Spend accounting: usage rollups, budget status and alerts.
+//!
+//! Costs are integer microdollars, priced by [`super::config::ModelPrice`].
+
+use super::config::{BudgetAction, BudgetScope, SpendBudget, SpendPeriod};
+use serde::{Deserialize, Serialize};
+
+/// What a usage rollup is grouped by.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum UsageDimension {
+    /// Client API key ID
+    ApiKey,
+    /// Requested model
+    Model,
+    /// Upstream account email
+    Account,
+}
+
+impl UsageDimension {
+    pub const ALL: [Self; 3] = [Self::ApiKey, Self::Model, Self::Account];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::ApiKey => "api_key",
+            Self::Model => "model",
+            Self::Account => "account",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|d| d.as_str() == value)
+    }
+}
+
+/// Request, token and cost counters.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub struct UsageTotals {
+    pub requests: u64,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub cached_tokens: u64,
+    /// Cost in microdollars
+    pub cost: u64,
+}
+
+impl UsageTotals {
+    pub fn add(&mut self, other: &Self) {
+        self.requests = self.requests.saturating_add(other.requests);
+        self.input_tokens = self.input_tokens.saturating_add(other.input_tokens);
+        self.output_tokens = self.output_tokens.saturating_add(other.output_tokens);
+        self.cached_tokens = self.cached_tokens.saturating_add(other.cached_tokens);
+        self.cost = self.cost.saturating_add(other.cost);
+    }
+}
+
+/// Usage of one key, model or account over one day or month.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct UsageRollup {
+    pub period: SpendPeriod,
+    /// Period start (unix seconds, UTC)
+    pub period_start: i64,
+    pub dimension: UsageDimension,
+    /// Key ID, model name or account email
+    pub key: String,
+    #[serde(flatten)]
+    pub totals: UsageTotals,
+}
+
+/// Filters for `GET /api/usage`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub struct UsageQuery {
+    /// Daily or monthly rollups (default monthly)
+    #[serde(default)]
+    pub period: SpendPeriod,
+    /// Only this dimension
+    #[serde(default)]
+    pub dimension: Option<UsageDimension>,
+    /// Only this key ID, model or account
+    #[serde(default)]
+    pub key: Option<String>,
+    /// Earliest period start (unix seconds, inclusive)
+    #[serde(default)]
+    pub from: Option<i64>,
+    /// Latest period start (unix seconds, inclusive)
+    #[serde(default)]
+    pub to: Option<i64>,
+}
+
+/// A budget and its spend in the current period.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct BudgetStatus {
+    #[serde(flatten)]
+    pub budget: SpendBudget,
+    /// Start of the current period (unix seconds)
+    pub period_start: i64,
+    /// Spend in the current period, in microdollars
+    pub spent: u64,
+    pub exceeded: bool,
+}
+
+/// Usage rollups plus the state of every configured budget.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub struct UsageReport {
+    pub rollups: Vec<UsageRollup>,
+    pub budgets: Vec<BudgetStatus>,
+}
+
+/// Raised once per period when a budget's spend reaches its limit.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct BudgetAlert {
+    /// Budget name
+    pub budget: String,
+    pub scope: BudgetScope,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target: Option<String>,
+    pub period: SpendPeriod,
+    pub period_start: i64,
+    /// Spend in microdollars
+    pub spent: u64,
+    /// Limit in microdollars
+    pub limit: u64,
+    pub action: BudgetAction,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rollup_flattens_totals() {
+        let rollup = UsageRollup {
+            period: SpendPeriod::Daily,
+            period_start: 86_400,
+            dimension: UsageDimension::ApiKey,
+            key: "k1".to_string(),
+            totals: UsageTotals { requests: 2, cost: 1500, ..Default::default() },
+        };
+        let json = serde_json::to_value(&rollup).unwrap();
+        assert_eq!(json["dimension"], "api_key");
+        assert_eq!(json["requests"], 2);
+        assert_eq!(json["cost"], 1500);
+        assert_eq!(UsageDimension::parse("account"), Some(UsageDimension::Account));
+    }
+}
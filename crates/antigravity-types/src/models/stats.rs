@@ -194,6 +194,41 @@ impl ProxyStats {
     }
 }
 
+/// Filters for searching captured request logs.
+///
+/// Time bounds are Unix milliseconds. `q` is matched as full text against request
+/// and response bodies.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub struct ProxyLogQuery {
+    /// Requested or mapped model
+    #[serde(default)]
+    pub model: Option<String>,
+    /// Account email
+    #[serde(default)]
+    pub account: Option<String>,
+    /// Exact HTTP status
+    #[serde(default)]
+    pub status: Option<u16>,
+    /// Only failed requests (status >= 400)
+    #[serde(default)]
+    pub errors_only: bool,
+    /// Client API key ID
+    #[serde(default)]
+    pub api_key_id: Option<String>,
+    /// Earliest timestamp (inclusive)
+    #[serde(default)]
+    pub from: Option<i64>,
+    /// Latest timestamp (inclusive)
+    #[serde(default)]
+    pub to: Option<i64>,
+    /// Full-text search over bodies
+    #[serde(default)]
+    pub q: Option<String>,
+    /// Maximum results (default 100)
+    #[serde(default)]
+    pub limit: Option<usize>,
+}
+
 /// Individual proxy request log entry.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct ProxyRequestLog {
@@ -226,6 +261,9 @@ pub struct ProxyRequestLog {
     /// Request body (truncated)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub request_body: Option<String>,
+    /// Upstream request body after protocol transformation (truncated)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub upstream_request_body: Option<String>,
     /// Response body (truncated)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub response_body: Option<String>,
@@ -235,6 +273,12 @@ pub struct ProxyRequestLog {
     pub output_tokens: Option<u32>,
     /// Cached input tokens (from prompt cache)
     pub cached_tokens: Option<u32>,
+    /// Client API key that authenticated the request (None = master key / auth off)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub api_key_id: Option<String>,
+    /// Label of the client API key, for display
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub api_key_label: Option<String>,
 }
 
 /// Token usage statistics over a time period.
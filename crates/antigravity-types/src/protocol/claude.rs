@@ -1,6 +1,7 @@
 //! Anthropic Claude Messages API types.
 
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 
 /// Claude message role.
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
@@ -12,65 +13,512 @@ pub enum ClaudeRole {
     Assistant,
 }
 
-/// Claude message content block.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Request body for the Messages API (`/v1/messages`).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct ClaudeRequest {
+    /// Model identifier to use for completion.
+    pub model: String,
+    /// List of messages in the conversation.
+    pub messages: Vec<Message>,
+    /// Optional system prompt.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub system: Option<SystemPrompt>,
+    /// Optional list of tools available to the model.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tools: Option<Vec<Tool>>,
+    /// Whether to stream the response.
+    #[serde(default)]
+    pub stream: bool,
+    /// Maximum tokens to generate.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_tokens: Option<u32>,
+    /// Sampling temperature (0.0 to 1.0).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub temperature: Option<f32>,
+    /// Top-p nucleus sampling parameter.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub top_p: Option<f32>,
+    /// Top-k sampling parameter.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub top_k: Option<u32>,
+    /// Extended thinking configuration.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub thinking: Option<ThinkingConfig>,
+    /// Client-provided stop sequences.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stop_sequences: Option<Vec<String>>,
+    /// Request metadata.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<Metadata>,
+    /// Output configuration options.
+    /// Claude API sends this as "output", not "output_config".
+    #[serde(alias = "output", skip_serializing_if = "Option::is_none")]
+    pub output_config: Option<OutputConfig>,
+}
+
+impl ClaudeRequest {
+    /// Request for `model` with the given messages and output limit.
+    pub fn new(model: impl Into<String>, messages: Vec<Message>, max_tokens: u32) -> Self {
+        Self { model: model.into(), messages, max_tokens: Some(max_tokens), ..Self::default() }
+    }
+}
+
+/// Configuration for extended thinking feature.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ThinkingConfig {
+    /// Type of thinking (e.g., "enabled").
+    #[serde(rename = "type")]
+    pub type_: String,
+    /// Token budget for thinking.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub budget_tokens: Option<u32>,
+}
+
+impl ThinkingConfig {
+    /// Think with up to `budget_tokens` tokens before answering.
+    pub fn enabled(budget_tokens: u32) -> Self {
+        Self { type_: "enabled".to_string(), budget_tokens: Some(budget_tokens) }
+    }
+}
+
+/// System prompt that can be a string or structured blocks.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(untagged)]
+pub enum SystemPrompt {
+    /// Simple string system prompt.
+    String(String),
+    /// Array of structured system blocks.
+    Array(Vec<SystemBlock>),
+}
+
+/// A structured block within a system prompt.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SystemBlock {
+    /// Block type (e.g., "text").
+    #[serde(rename = "type")]
+    pub block_type: String,
+    /// Text content of the block.
+    pub text: String,
+}
+
+/// A message in the conversation.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Message {
+    /// Role of the message author (e.g., "user", "assistant").
+    pub role: String,
+    /// Content of the message.
+    pub content: MessageContent,
+}
+
+impl Message {
+    /// User message.
+    pub fn user(content: impl Into<MessageContent>) -> Self {
+        Self { role: "user".to_string(), content: content.into() }
+    }
+
+    /// Assistant message, e.g. a previous turn including its thinking blocks.
+    pub fn assistant(content: impl Into<MessageContent>) -> Self {
+        Self { role: "assistant".to_string(), content: content.into() }
+    }
+}
+
+/// Message content that can be a string or array of content blocks.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(untagged)]
+pub enum MessageContent {
+    /// Simple string content.
+    String(String),
+    /// Array of content blocks.
+    Array(Vec<ContentBlock>),
+}
+
+impl From<&str> for MessageContent {
+    fn from(text: &str) -> Self {
+        Self::String(text.to_string())
+    }
+}
+
+impl From<String> for MessageContent {
+    fn from(text: String) -> Self {
+        Self::String(text)
+    }
+}
+
+impl From<Vec<ContentBlock>> for MessageContent {
+    fn from(blocks: Vec<ContentBlock>) -> Self {
+        Self::Array(blocks)
+    }
+}
+
+/// Content block types for Claude API messages.
+///
+/// Represents different types of content that can appear in a message,
+/// including text, images, documents, tool calls, and thinking blocks.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(tag = "type")]
-pub enum ClaudeContentBlock {
-    /// Plain text content.
+pub enum ContentBlock {
+    /// Plain text content block.
     #[serde(rename = "text")]
     Text {
         /// The text content.
         text: String,
     },
-    /// Image content with base64 source.
+
+    /// Thinking/reasoning content block (extended thinking feature).
+    #[serde(rename = "thinking")]
+    Thinking {
+        /// The thinking/reasoning text.
+        thinking: String,
+        /// Optional cryptographic signature for verification.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        signature: Option<String>,
+        /// Optional cache control settings.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        cache_control: Option<Value>,
+    },
+
+    /// Image content block with base64-encoded data.
     #[serde(rename = "image")]
     Image {
-        /// Image source data.
-        source: ClaudeImageSource,
+        /// The image source containing type, media type, and data.
+        source: ImageSource,
+        /// Optional cache control settings.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        cache_control: Option<Value>,
+    },
+
+    /// Document content block (PDF, etc.).
+    #[serde(rename = "document")]
+    Document {
+        /// The document source containing type, media type, and data.
+        source: DocumentSource,
+        /// Optional cache control settings.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        cache_control: Option<Value>,
     },
+
+    /// Redacted thinking block (content hidden for safety).
+    #[serde(rename = "redacted_thinking")]
+    RedactedThinking {
+        /// Opaque data representing redacted content.
+        data: String,
+    },
+
     /// Tool use request from the model.
     #[serde(rename = "tool_use")]
     ToolUse {
-        /// Unique tool use identifier.
+        /// Unique identifier for this tool use.
         id: String,
-        /// Tool name to invoke.
+        /// Name of the tool being called.
         name: String,
-        /// Tool input parameters as JSON.
-        input: serde_json::Value,
+        /// Input arguments for the tool.
+        input: Value,
+        /// Optional signature for verification.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        signature: Option<String>,
+        /// Optional cache control settings.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        cache_control: Option<Value>,
     },
-    /// Tool execution result.
+
+    /// Result from a tool execution.
     #[serde(rename = "tool_result")]
     ToolResult {
         /// ID of the tool use this result corresponds to.
         tool_use_id: String,
-        /// Tool execution output.
-        content: String,
+        /// The result content from the tool.
+        content: Value,
+        /// Whether the tool execution resulted in an error.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        is_error: Option<bool>,
+    },
+
+    /// Server-side tool use (internal tools).
+    #[serde(rename = "server_tool_use")]
+    ServerToolUse {
+        /// Unique identifier for this tool use.
+        id: String,
+        /// Name of the server tool.
+        name: String,
+        /// Input arguments for the tool.
+        input: Value,
+    },
+
+    /// Web search tool result.
+    #[serde(rename = "web_search_tool_result")]
+    WebSearchToolResult {
+        /// ID of the tool use this result corresponds to.
+        tool_use_id: String,
+        /// The search result content.
+        content: Value,
     },
 }
 
-/// Claude image source.
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct ClaudeImageSource {
-    /// Source type (e.g., "base64").
+/// Source information for image content.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ImageSource {
+    /// The source type (e.g., "base64").
     #[serde(rename = "type")]
     pub source_type: String,
-    /// MIME type (e.g., "image/png").
+    /// MIME type of the image (e.g., "image/png").
     pub media_type: String,
     /// Base64-encoded image data.
     pub data: String,
 }
 
-/// Claude usage statistics.
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default)]
-pub struct ClaudeUsage {
-    /// Number of input tokens consumed.
+/// Source information for document content.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DocumentSource {
+    /// The source type (e.g., "base64").
+    #[serde(rename = "type")]
+    pub source_type: String,
+    /// MIME type of the document (e.g., "application/pdf").
+    pub media_type: String,
+    /// Base64-encoded document data.
+    pub data: String,
+}
+
+/// Tool definition for Claude API.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Tool {
+    /// Tool type (e.g., "function", "web_search").
+    #[serde(rename = "type")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub type_: Option<String>,
+    /// Tool name.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Tool description.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// JSON Schema for tool input.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub input_schema: Option<Value>,
+}
+
+impl Tool {
+    /// Client tool with a JSON Schema for its input.
+    pub fn new(
+        name: impl Into<String>,
+        description: impl Into<String>,
+        input_schema: Value,
+    ) -> Self {
+        Self {
+            type_: None,
+            name: Some(name.into()),
+            description: Some(description.into()),
+            input_schema: Some(input_schema),
+        }
+    }
+
+    /// Checks if this tool is a web search tool.
+    pub fn is_web_search(&self) -> bool {
+        if let Some(ref t) = self.type_ {
+            if t.starts_with("web_search") {
+                return true;
+            }
+        }
+        if let Some(ref n) = self.name {
+            if n == "web_search" {
+                return true;
+            }
+        }
+        false
+    }
+}
+
+/// Request metadata.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Metadata {
+    /// User identifier for tracking.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_id: Option<String>,
+}
+
+/// Output configuration options.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct OutputConfig {
+    /// Effort level for generation.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub effort: Option<String>,
+}
+
+/// Token usage statistics.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct Usage {
+    /// Number of input tokens.
     pub input_tokens: u32,
-    /// Number of output tokens generated.
+    /// Number of output tokens.
     pub output_tokens: u32,
-    /// Tokens used to create prompt cache.
+    /// Tokens read from cache.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cache_read_input_tokens: Option<u32>,
+    /// Tokens written to cache.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cache_creation_input_tokens: Option<u32>,
+    /// Server tool usage data.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub server_tool_use: Option<Value>,
+}
+
+impl Usage {
+    /// Report cached tokens as written rather than read, for the turn that created the cache.
+    pub fn report_cache_creation(&mut self) {
+        if let Some(tokens) = self.cache_read_input_tokens.take() {
+            self.cache_creation_input_tokens = Some(tokens);
+            self.cache_read_input_tokens = Some(0);
+        }
+    }
+}
+
+/// Response from Claude Messages API.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ClaudeResponse {
+    /// Unique response identifier.
+    pub id: String,
+    /// Response type (always "message").
+    #[serde(rename = "type")]
+    pub type_: String,
+    /// Role (always "assistant").
+    pub role: String,
+    /// Model that generated the response.
+    pub model: String,
+    /// Content blocks in the response.
+    pub content: Vec<ContentBlock>,
+    /// Reason generation stopped; unset in a stream's `message_start`.
+    pub stop_reason: Option<String>,
+    /// Stop sequence that triggered stop, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stop_sequence: Option<String>,
+    /// Token usage statistics.
+    pub usage: Usage,
+}
+
+impl ClaudeResponse {
+    /// Concatenated text blocks.
+    pub fn text(&self) -> String {
+        self.content
+            .iter()
+            .filter_map(|b| match b {
+                ContentBlock::Text { text } => Some(text.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Concatenated thinking blocks.
+    pub fn thinking(&self) -> String {
+        self.content
+            .iter()
+            .filter_map(|b| match b {
+                ContentBlock::Thinking { thinking, .. } => Some(thinking.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+/// Server-sent event of a streaming Messages response.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ClaudeStreamEvent {
+    /// Start of the message, with empty content.
+    MessageStart {
+        /// The message so far.
+        message: ClaudeResponse,
+    },
+    /// Start of a content block.
+    ContentBlockStart {
+        /// Index of the block.
+        index: u32,
+        /// The block with empty content.
+        content_block: ContentBlock,
+    },
+    /// Content added to a block.
+    ContentBlockDelta {
+        /// Index of the block.
+        index: u32,
+        /// The added content.
+        delta: ClaudeDelta,
+    },
+    /// End of a content block.
+    ContentBlockStop {
+        /// Index of the block.
+        index: u32,
+    },
+    /// Top-level message changes, such as the stop reason.
+    MessageDelta {
+        /// Changed message fields.
+        delta: ClaudeMessageDelta,
+        /// Cumulative usage.
+        #[serde(default)]
+        usage: Option<MessageDeltaUsage>,
+    },
+    /// End of the message.
+    MessageStop,
+    /// Keep-alive.
+    Ping,
+    /// Error during streaming.
+    Error {
+        /// Error details.
+        error: ClaudeApiError,
+    },
+}
+
+/// Content added to a block by a `content_block_delta` event.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ClaudeDelta {
+    /// Text appended to a text block.
+    TextDelta {
+        /// The added text.
+        text: String,
+    },
+    /// Reasoning appended to a thinking block.
+    ThinkingDelta {
+        /// The added reasoning.
+        thinking: String,
+    },
+    /// Signature of a thinking block, sent before it stops.
+    SignatureDelta {
+        /// The block signature.
+        signature: String,
+    },
+    /// Fragment of a tool use's JSON input.
+    InputJsonDelta {
+        /// The JSON fragment.
+        partial_json: String,
+    },
+}
+
+/// Message fields changed by a `message_delta` event.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub struct ClaudeMessageDelta {
+    /// Reason generation stopped.
     #[serde(default)]
-    pub cache_creation_input_tokens: u32,
-    /// Tokens read from prompt cache.
+    pub stop_reason: Option<String>,
+    /// Stop sequence that ended generation.
     #[serde(default)]
-    pub cache_read_input_tokens: u32,
+    pub stop_sequence: Option<String>,
+}
+
+/// Usage reported by a `message_delta` event.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub struct MessageDeltaUsage {
+    /// Output tokens generated so far.
+    #[serde(default)]
+    pub output_tokens: u32,
+    /// Input tokens, when the provider repeats them.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub input_tokens: Option<u32>,
+}
+
+/// Error object of the Messages API.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ClaudeApiError {
+    /// Error type (e.g., "overloaded_error").
+    #[serde(rename = "type")]
+    pub error_type: String,
+    /// Human-readable message.
+    pub message: String,
 }
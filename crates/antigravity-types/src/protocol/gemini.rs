@@ -1,6 +1,7 @@
 //! Google Gemini GenerateContent API types.
 
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 
 /// Gemini content role.
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
@@ -12,44 +13,392 @@ pub enum GeminiRole {
     Model,
 }
 
-/// Gemini content part.
-#[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(untagged)]
-pub enum GeminiPart {
-    /// Text content part.
-    Text {
-        /// The text content.
-        text: String,
-    },
-    /// Binary data content part (images, audio, etc).
-    InlineData {
-        /// The inline data payload.
-        inline_data: GeminiInlineData,
-    },
-}
-
-/// Gemini inline data (for images, etc).
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct GeminiInlineData {
-    /// MIME type of the data (e.g., "image/png", "audio/wav").
+/// Gemini content structure containing role and parts.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct GeminiContent {
+    /// The role of the content author (e.g., "user", "model"); empty for system instructions.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub role: String,
+    /// The parts that make up this content.
+    #[serde(default)]
+    pub parts: Vec<GeminiPart>,
+}
+
+impl GeminiContent {
+    /// User turn with one text part.
+    pub fn user(text: impl Into<String>) -> Self {
+        Self { role: "user".to_string(), parts: vec![GeminiPart::text(text)] }
+    }
+
+    /// Model turn with one text part.
+    pub fn model(text: impl Into<String>) -> Self {
+        Self { role: "model".to_string(), parts: vec![GeminiPart::text(text)] }
+    }
+
+    /// Role-less content, as used for `systemInstruction`.
+    pub fn system(text: impl Into<String>) -> Self {
+        Self { role: String::new(), parts: vec![GeminiPart::text(text)] }
+    }
+}
+
+/// A single part within Gemini content.
+///
+/// Parts can contain text, function calls, function responses, or inline data.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct GeminiPart {
+    /// Optional text content.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+    /// Whether this is a thought/reasoning part.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub thought: Option<bool>,
+    /// Signature for thought verification.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "thoughtSignature")]
+    pub thought_signature: Option<String>,
+    /// Function call request from the model.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "functionCall")]
+    pub function_call: Option<FunctionCall>,
+    /// Response to a function call.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "functionResponse")]
+    pub function_response: Option<FunctionResponse>,
+    /// Inline binary data (images, audio, etc.).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "inlineData", alias = "inline_data")]
+    pub inline_data: Option<InlineData>,
+}
+
+impl GeminiPart {
+    /// Text part.
+    pub fn text(text: impl Into<String>) -> Self {
+        Self { text: Some(text.into()), ..Self::default() }
+    }
+
+    /// Inline data part from base64 data.
+    pub fn inline_data(mime_type: impl Into<String>, data: impl Into<String>) -> Self {
+        Self {
+            inline_data: Some(InlineData { mime_type: mime_type.into(), data: data.into() }),
+            ..Self::default()
+        }
+    }
+
+    /// Function result part.
+    pub fn function_response(name: impl Into<String>, response: Value) -> Self {
+        Self {
+            function_response: Some(FunctionResponse { name: name.into(), response, id: None }),
+            ..Self::default()
+        }
+    }
+
+    /// Whether this part is reasoning rather than answer text.
+    pub fn is_thought(&self) -> bool {
+        self.thought == Some(true)
+    }
+}
+
+/// Function call request from the Gemini model.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct FunctionCall {
+    /// Name of the function to call.
+    pub name: String,
+    /// Optional unique identifier for this call.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    /// Optional arguments to pass to the function.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub args: Option<Value>,
+}
+
+/// Response to a function call.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct FunctionResponse {
+    /// Name of the function that was called.
+    pub name: String,
+    /// The response data from the function.
+    pub response: Value,
+    /// Optional identifier matching the original call.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+}
+
+/// Inline binary data with MIME type.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct InlineData {
+    /// MIME type of the data (e.g., "image/png").
+    #[serde(rename = "mimeType", alias = "mime_type")]
     pub mime_type: String,
     /// Base64-encoded binary data.
     pub data: String,
 }
 
-/// Gemini usage metadata.
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default)]
-pub struct GeminiUsageMetadata {
-    /// Number of tokens in the input prompt.
-    #[serde(default)]
-    pub prompt_token_count: u32,
-    /// Number of tokens in the generated candidates.
-    #[serde(default)]
-    pub candidates_token_count: u32,
+/// Function the model may call.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct GeminiFunctionDeclaration {
+    /// Function name.
+    pub name: String,
+    /// What the function does.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Schema of the arguments.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parameters: Option<Value>,
+}
+
+/// Tool available to the model.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct GeminiTool {
+    /// Callable functions.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub function_declarations: Option<Vec<GeminiFunctionDeclaration>>,
+    /// Grounding with Google Search (`{}` to enable).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub google_search: Option<Value>,
+}
+
+/// Thinking configuration.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct GeminiThinkingConfig {
+    /// Maximum thinking tokens (-1 for dynamic).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub thinking_budget: Option<i32>,
+    /// Return thought summaries as `thought` parts.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub include_thoughts: Option<bool>,
+}
+
+/// Generation parameters.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct GeminiGenerationConfig {
+    /// Sampling temperature.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub temperature: Option<f32>,
+    /// Nucleus sampling probability.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub top_p: Option<f32>,
+    /// Top-k sampling.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub top_k: Option<u32>,
+    /// Maximum tokens to generate.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_output_tokens: Option<u32>,
+    /// Number of candidates.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub candidate_count: Option<u32>,
+    /// Stop sequences.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stop_sequences: Option<Vec<String>>,
+    /// Output MIME type, e.g. "application/json" for structured output.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub response_mime_type: Option<String>,
+    /// Schema the JSON output must match.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub response_schema: Option<Value>,
+    /// Thinking configuration.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub thinking_config: Option<GeminiThinkingConfig>,
+}
+
+/// Request body for `models/{model}:generateContent` and `:streamGenerateContent`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct GenerateContentRequest {
+    /// Conversation turns.
+    pub contents: Vec<GeminiContent>,
+    /// System instruction.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub system_instruction: Option<GeminiContent>,
+    /// Generation parameters.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub generation_config: Option<GeminiGenerationConfig>,
+    /// Tools available to the model.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tools: Option<Vec<GeminiTool>>,
+    /// Safety settings, passed through as-is.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub safety_settings: Option<Value>,
+}
+
+impl GenerateContentRequest {
+    /// Request with the given turns and default parameters.
+    pub fn new(contents: Vec<GeminiContent>) -> Self {
+        Self { contents, ..Self::default() }
+    }
+}
+
+/// Response from the Gemini API, or one chunk of a stream.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct GeminiResponse {
+    /// List of response candidates.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub candidates: Option<Vec<Candidate>>,
+    /// Token usage metadata.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "usageMetadata")]
+    pub usage_metadata: Option<UsageMetadata>,
+    /// Version of the model that generated this response.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "modelVersion")]
+    pub model_version: Option<String>,
+    /// Unique identifier for this response.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "responseId")]
+    pub response_id: Option<String>,
+}
+
+impl GeminiResponse {
+    /// Answer text of the first candidate, without thoughts.
+    pub fn text(&self) -> String {
+        self.candidate_parts()
+            .filter(|p| !p.is_thought())
+            .filter_map(|p| p.text.as_deref())
+            .collect()
+    }
+
+    /// Thought summaries of the first candidate.
+    pub fn thoughts(&self) -> String {
+        self.candidate_parts()
+            .filter(|p| p.is_thought())
+            .filter_map(|p| p.text.as_deref())
+            .collect()
+    }
+
+    fn candidate_parts(&self) -> impl Iterator<Item = &GeminiPart> {
+        self.candidates
+            .iter()
+            .flatten()
+            .next()
+            .and_then(|c| c.content.as_ref())
+            .into_iter()
+            .flat_map(|c| &c.parts)
+    }
+}
+
+/// A single candidate response from Gemini.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Candidate {
+    /// The content of this candidate.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content: Option<GeminiContent>,
+    /// Reason why generation finished.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "finishReason")]
+    pub finish_reason: Option<String>,
+    /// Index of this candidate in the list.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub index: Option<u32>,
+    /// Grounding metadata for search-augmented responses.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "groundingMetadata")]
+    pub grounding_metadata: Option<GroundingMetadata>,
+}
+
+/// Token usage metadata from Gemini API.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub struct UsageMetadata {
+    /// Number of tokens in the prompt.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "promptTokenCount")]
+    pub prompt_token_count: Option<u32>,
+    /// Number of tokens in the response candidates.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "candidatesTokenCount")]
+    pub candidates_token_count: Option<u32>,
+    /// Number of tokens spent on thinking.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "thoughtsTokenCount")]
+    pub thoughts_token_count: Option<u32>,
     /// Total token count (prompt + candidates).
-    #[serde(default)]
-    pub total_token_count: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "totalTokenCount")]
+    pub total_token_count: Option<u32>,
     /// Number of tokens served from cache.
-    #[serde(default)]
-    pub cached_content_token_count: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "cachedContentTokenCount")]
+    pub cached_content_token_count: Option<u32>,
+}
+
+/// Metadata about grounding sources used in a response.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct GroundingMetadata {
+    /// Web search queries that were executed.
+    #[serde(rename = "webSearchQueries")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub web_search_queries: Option<Vec<String>>,
+    /// Chunks of grounding information from web sources.
+    #[serde(rename = "groundingChunks")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub grounding_chunks: Option<Vec<GroundingChunk>>,
+    /// Support information linking response to sources.
+    #[serde(rename = "groundingSupports")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub grounding_supports: Option<Vec<GroundingSupport>>,
+    /// Entry point for search results.
+    #[serde(rename = "searchEntryPoint")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub search_entry_point: Option<SearchEntryPoint>,
+}
+
+/// A chunk of grounding information.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct GroundingChunk {
+    /// Web source for this chunk.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub web: Option<WebSource>,
+}
+
+/// A web source used for grounding.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct WebSource {
+    /// URI of the web source.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub uri: Option<String>,
+    /// Title of the web page.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+}
+
+/// Support information linking response segments to sources.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct GroundingSupport {
+    /// Text segment in the response.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub segment: Option<TextSegment>,
+    /// Indices of grounding chunks that support this segment.
+    #[serde(rename = "groundingChunkIndices")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub grounding_chunk_indices: Option<Vec<i32>>,
+    /// Confidence scores for each supporting chunk.
+    #[serde(rename = "confidenceScores")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub confidence_scores: Option<Vec<f64>>,
+}
+
+/// A segment of text in the response.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct TextSegment {
+    /// Start index of the segment.
+    #[serde(rename = "startIndex")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_index: Option<i32>,
+    /// End index of the segment.
+    #[serde(rename = "endIndex")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end_index: Option<i32>,
+    /// Text content of the segment.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+}
+
+/// Entry point for search results display.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SearchEntryPoint {
+    /// Rendered HTML content for display.
+    #[serde(rename = "renderedContent")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rendered_content: Option<String>,
 }
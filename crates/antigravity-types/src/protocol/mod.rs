@@ -5,7 +5,8 @@
 //! - Anthropic (Claude Messages API)
 //! - Google Gemini (GenerateContent API)
 //!
-//! These are placeholder modules - full protocol types will be added as needed.
+//! They describe the wire format the proxy parses and emits, and are shared by its
+//! mappers and `antigravity-client`.
 
 pub mod claude;
 pub mod gemini;
@@ -13,4 +14,132 @@ pub mod openai;
 
 // Re-export common protocol enums
 pub use claude::ClaudeRole;
+pub use gemini::GeminiRole;
 pub use openai::OpenAIRole;
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, reason = "test assertions")]
+mod tests {
+    use serde_json::json;
+
+    use super::claude::{ClaudeDelta, ClaudeResponse, ClaudeStreamEvent, ContentBlock};
+    use super::gemini::{GeminiContent, GeminiResponse, GenerateContentRequest};
+    use super::openai::{ChatCompletionChunk, OpenAIMessage, OpenAIRequest, ResponseFormat};
+
+    #[test]
+    fn test_openai_request_omits_unset_fields() {
+        let mut req = OpenAIRequest::new("gpt-4o", vec![OpenAIMessage::user("hi")]);
+        req.response_format =
+            Some(ResponseFormat::json_schema("answer", json!({"type": "object"}), true));
+
+        let value = serde_json::to_value(&req).unwrap();
+        assert_eq!(value["messages"][0], json!({"role": "user", "content": "hi"}));
+        assert_eq!(value["response_format"]["type"], "json_schema");
+        assert_eq!(value["response_format"]["json_schema"]["name"], "answer");
+        assert!(value.get("temperature").is_none());
+        assert!(value.get("tools").is_none());
+    }
+
+    #[test]
+    fn test_openai_chunk_with_tool_call_delta() {
+        let chunk: ChatCompletionChunk = serde_json::from_value(json!({
+            "id": "chatcmpl-1",
+            "object": "chat.completion.chunk",
+            "created": 1,
+            "model": "gpt-4o",
+            "choices": [{
+                "index": 0,
+                "delta": {"tool_calls": [{"index": 0, "id": "call_1", "type": "function",
+                    "function": {"name": "lookup", "arguments": "{\"q\""}}]},
+                "finish_reason": null
+            }]
+        }))
+        .unwrap();
+
+        let calls = chunk.choices[0].delta.tool_calls.as_ref().unwrap();
+        assert_eq!(calls[0].id.as_deref(), Some("call_1"));
+        assert_eq!(calls[0].function.name, "lookup");
+    }
+
+    #[test]
+    fn test_claude_response_with_thinking() {
+        let resp: ClaudeResponse = serde_json::from_value(json!({
+            "id": "msg_1",
+            "type": "message",
+            "role": "assistant",
+            "model": "claude-sonnet-4-5",
+            "content": [
+                {"type": "thinking", "thinking": "Let me see.", "signature": "sig"},
+                {"type": "text", "text": "4"}
+            ],
+            "stop_reason": "end_turn",
+            "usage": {"input_tokens": 10, "output_tokens": 5}
+        }))
+        .unwrap();
+
+        assert_eq!(resp.text(), "4");
+        assert_eq!(resp.thinking(), "Let me see.");
+        assert_eq!(resp.usage.output_tokens, 5);
+        assert!(matches!(
+            &resp.content[0],
+            ContentBlock::Thinking { signature: Some(s), .. } if s == "sig"
+        ));
+    }
+
+    #[test]
+    fn test_claude_stream_events() {
+        let event: ClaudeStreamEvent = serde_json::from_value(json!({
+            "type": "content_block_delta",
+            "index": 0,
+            "delta": {"type": "thinking_delta", "thinking": "hmm"}
+        }))
+        .unwrap();
+        assert_eq!(
+            event,
+            ClaudeStreamEvent::ContentBlockDelta {
+                index: 0,
+                delta: ClaudeDelta::ThinkingDelta { thinking: "hmm".to_string() },
+            }
+        );
+
+        let start: ClaudeStreamEvent = serde_json::from_value(json!({
+            "type": "message_start",
+            "message": {"id": "msg_1", "type": "message", "role": "assistant", "model": "m",
+                "content": [], "stop_reason": null, "usage": {"input_tokens": 3, "output_tokens": 0}}
+        }))
+        .unwrap();
+        assert!(matches!(
+            start,
+            ClaudeStreamEvent::MessageStart { message } if message.stop_reason.is_none()
+        ));
+
+        let stop: ClaudeStreamEvent = serde_json::from_str(r#"{"type":"message_stop"}"#).unwrap();
+        assert_eq!(stop, ClaudeStreamEvent::MessageStop);
+    }
+
+    #[test]
+    fn test_gemini_round_trip_uses_camel_case() {
+        let mut req = GenerateContentRequest::new(vec![GeminiContent::user("hi")]);
+        req.system_instruction = Some(GeminiContent::system("be brief"));
+
+        let value = serde_json::to_value(&req).unwrap();
+        assert_eq!(value["contents"][0], json!({"role": "user", "parts": [{"text": "hi"}]}));
+        assert_eq!(value["systemInstruction"], json!({"parts": [{"text": "be brief"}]}));
+
+        let resp: GeminiResponse = serde_json::from_value(json!({
+            "candidates": [{
+                "content": {"role": "model", "parts": [
+                    {"text": "thinking...", "thought": true},
+                    {"text": "Hello", "thoughtSignature": "abc"}
+                ]},
+                "finishReason": "STOP"
+            }],
+            "usageMetadata": {"promptTokenCount": 3, "candidatesTokenCount": 1,
+                "thoughtsTokenCount": 2, "totalTokenCount": 6}
+        }))
+        .unwrap();
+        assert_eq!(resp.text(), "Hello");
+        assert_eq!(resp.thoughts(), "thinking...");
+        assert_eq!(resp.usage_metadata.unwrap().thoughts_token_count, Some(2));
+    }
+}
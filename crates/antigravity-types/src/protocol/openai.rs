@@ -1,6 +1,7 @@
-//! OpenAI ChatCompletions API types.
+//! OpenAI ChatCompletions and Images API types.
 
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 
 /// OpenAI message role.
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
@@ -8,6 +9,8 @@ use serde::{Deserialize, Serialize};
 pub enum OpenAIRole {
     /// System message providing context or instructions.
     System,
+    /// Developer message (newer name for system).
+    Developer,
     /// User message from the human.
     User,
     /// Assistant message from the model.
@@ -18,28 +21,559 @@ pub enum OpenAIRole {
     Function,
 }
 
-/// OpenAI chat message.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// OpenAI chat completion request.
+///
+/// This is the shape the proxy parses, so it also accepts the legacy `prompt`, the Codex
+/// `instructions`/`input` fields and image generation options.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct OpenAIRequest {
+    /// Model identifier (e.g., "gpt-4", "gemini-3-pro").
+    pub model: String,
+    /// Conversation messages.
+    #[serde(default)]
+    pub messages: Vec<OpenAIMessage>,
+    /// Legacy prompt field for completions API.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prompt: Option<String>,
+    /// Enable streaming response.
+    #[serde(default)]
+    pub stream: bool,
+    /// Number of completions to generate.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub n: Option<u32>,
+    /// Maximum tokens in response.
+    #[serde(rename = "max_tokens", skip_serializing_if = "Option::is_none")]
+    pub max_tokens: Option<u32>,
+    /// Sampling temperature (0.0-2.0).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub temperature: Option<f32>,
+    /// Nucleus sampling parameter.
+    #[serde(rename = "top_p", skip_serializing_if = "Option::is_none")]
+    pub top_p: Option<f32>,
+    /// Stop sequences.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stop: Option<Value>,
+    /// Response format specification.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub response_format: Option<ResponseFormat>,
+    /// Tool definitions for function calling.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tools: Option<Vec<Value>>,
+    /// Tool choice strategy.
+    #[serde(rename = "tool_choice", skip_serializing_if = "Option::is_none")]
+    pub tool_choice: Option<Value>,
+    /// Allow parallel tool calls.
+    #[serde(rename = "parallel_tool_calls", skip_serializing_if = "Option::is_none")]
+    pub parallel_tool_calls: Option<bool>,
+    /// Codex instructions field.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub instructions: Option<String>,
+    /// Codex input field.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub input: Option<Value>,
+    /// Image size for generation.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub size: Option<String>,
+    /// Image quality setting.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quality: Option<String>,
+    /// Person generation mode.
+    #[serde(default, rename = "personGeneration", skip_serializing_if = "Option::is_none")]
+    pub person_generation: Option<String>,
+}
+
+impl OpenAIRequest {
+    /// Request for `model` with the given messages and default parameters.
+    pub fn new(model: impl Into<String>, messages: Vec<OpenAIMessage>) -> Self {
+        Self { model: model.into(), messages, ..Self::default() }
+    }
+}
+
+/// Response format specification.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ResponseFormat {
+    /// Format type (e.g., "json_object", "json_schema", "text").
+    pub r#type: String,
+    /// Schema definition when `type` is "json_schema".
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub json_schema: Option<JsonSchemaFormat>,
+}
+
+impl ResponseFormat {
+    /// Structured output matching `schema`, validated when `strict` is set.
+    pub fn json_schema(name: impl Into<String>, schema: Value, strict: bool) -> Self {
+        Self {
+            r#type: "json_schema".to_string(),
+            json_schema: Some(JsonSchemaFormat {
+                name: name.into(),
+                description: None,
+                schema: Some(schema),
+                strict: Some(strict),
+            }),
+        }
+    }
+}
+
+/// Structured output schema (`response_format.json_schema`).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct JsonSchemaFormat {
+    /// Schema name, used in error messages.
+    #[serde(default)]
+    pub name: String,
+    /// Optional description of the expected output.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// JSON Schema the output must conform to.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schema: Option<Value>,
+    /// Validate the response against the schema.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub strict: Option<bool>,
+}
+
+/// Content in OpenAI message (string or array of blocks).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(untagged)]
+pub enum OpenAIContent {
+    /// Plain text content.
+    String(String),
+    /// Array of content blocks (text, images, audio).
+    Array(Vec<OpenAIContentBlock>),
+}
+
+/// Content block types in OpenAI messages.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(tag = "type")]
+pub enum OpenAIContentBlock {
+    /// Text content block.
+    #[serde(rename = "text")]
+    Text {
+        /// Text content.
+        text: String,
+    },
+    /// Image URL content block.
+    #[serde(rename = "image_url")]
+    ImageUrl {
+        /// Image URL data.
+        image_url: OpenAIImageUrl,
+    },
+    /// Audio input content block (nested or flat format).
+    #[serde(rename = "input_audio")]
+    InputAudio {
+        /// Nested format audio data.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        input_audio: Option<InputAudioContent>,
+        /// Flat format: base64 audio data.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        data: Option<String>,
+        /// Flat format: audio format (wav, mp3, etc.).
+        #[serde(skip_serializing_if = "Option::is_none")]
+        format: Option<String>,
+    },
+    /// Video URL content block.
+    #[serde(rename = "video_url")]
+    VideoUrl {
+        /// Video URL data.
+        video_url: VideoUrlContent,
+    },
+}
+
+impl OpenAIContentBlock {
+    /// Extract audio content from either nested or flat format.
+    #[must_use]
+    pub fn extract_audio(&self) -> Option<InputAudioContent> {
+        match self {
+            OpenAIContentBlock::InputAudio { input_audio, data, format } => {
+                if let Some(nested) = input_audio {
+                    return Some(nested.clone());
+                }
+                if let (Some(audio_data), Some(audio_format)) = (data, format) {
+                    return Some(InputAudioContent {
+                        data: audio_data.clone(),
+                        format: audio_format.clone(),
+                    });
+                }
+                None
+            },
+            OpenAIContentBlock::Text { text: _ }
+            | OpenAIContentBlock::ImageUrl { image_url: _ }
+            | OpenAIContentBlock::VideoUrl { video_url: _ } => None,
+        }
+    }
+}
+
+/// Image URL with optional detail level.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct OpenAIImageUrl {
+    /// Image URL (data URI or HTTP URL).
+    pub url: String,
+    /// Detail level ("low", "high", "auto").
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+}
+
+/// Audio content with base64 data and format.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct InputAudioContent {
+    /// Base64-encoded audio data.
+    pub data: String,
+    /// Audio format (wav, mp3, ogg, flac, m4a, aac).
+    pub format: String,
+}
+
+/// Video URL content.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct VideoUrlContent {
+    /// Video URL (data URI or HTTP URL).
+    pub url: String,
+}
+
+/// Message in OpenAI conversation.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct OpenAIMessage {
-    /// Role of the message author.
-    pub role: OpenAIRole,
-    /// Text content of the message.
-    pub content: Option<String>,
-    /// Optional name for the participant.
+    /// Role (system, user, assistant, tool).
+    pub role: String,
+    /// Message content.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub name: Option<String>,
-    /// ID of the tool call this message responds to.
+    pub content: Option<OpenAIContent>,
+    /// Reasoning content for o1 models.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reasoning_content: Option<String>,
+    /// Tool calls made by assistant.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tool_calls: Option<Vec<ToolCall>>,
+    /// Tool call ID for tool responses.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub tool_call_id: Option<String>,
+    /// Function name for tool messages.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+}
+
+impl OpenAIMessage {
+    fn with_content(role: &str, content: OpenAIContent) -> Self {
+        Self {
+            role: role.to_string(),
+            content: Some(content),
+            reasoning_content: None,
+            tool_calls: None,
+            tool_call_id: None,
+            name: None,
+        }
+    }
+
+    /// System message with text content.
+    pub fn system(text: impl Into<String>) -> Self {
+        Self::with_content("system", OpenAIContent::String(text.into()))
+    }
+
+    /// User message with text content.
+    pub fn user(text: impl Into<String>) -> Self {
+        Self::with_content("user", OpenAIContent::String(text.into()))
+    }
+
+    /// User message with multimodal blocks.
+    pub fn user_blocks(blocks: Vec<OpenAIContentBlock>) -> Self {
+        Self::with_content("user", OpenAIContent::Array(blocks))
+    }
+
+    /// Assistant message with text content.
+    pub fn assistant(text: impl Into<String>) -> Self {
+        Self::with_content("assistant", OpenAIContent::String(text.into()))
+    }
+
+    /// Result of the tool call `tool_call_id`.
+    pub fn tool(tool_call_id: impl Into<String>, text: impl Into<String>) -> Self {
+        Self {
+            tool_call_id: Some(tool_call_id.into()),
+            ..Self::with_content("tool", OpenAIContent::String(text.into()))
+        }
+    }
+
+    /// Concatenated text of the content (text blocks only).
+    pub fn text(&self) -> String {
+        match &self.content {
+            Some(OpenAIContent::String(text)) => text.clone(),
+            Some(OpenAIContent::Array(blocks)) => blocks
+                .iter()
+                .filter_map(|b| match b {
+                    OpenAIContentBlock::Text { text } => Some(text.as_str()),
+                    _ => None,
+                })
+                .collect(),
+            None => String::new(),
+        }
+    }
 }
 
-/// OpenAI usage statistics.
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default)]
+/// Tool call made by assistant.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ToolCall {
+    /// Unique tool call identifier.
+    pub id: String,
+    /// Tool type (always "function").
+    pub r#type: String,
+    /// Function call details.
+    pub function: ToolFunction,
+}
+
+/// Function call details in tool call.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub struct ToolFunction {
+    /// Function name.
+    #[serde(default)]
+    pub name: String,
+    /// JSON-encoded function arguments.
+    #[serde(default)]
+    pub arguments: String,
+}
+
+/// Function tool for [`OpenAIRequest::tools`].
+///
+/// The request keeps tools as JSON so the proxy can pass provider-specific tool types
+/// through; convert with `Value::from`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct OpenAITool {
+    /// Tool type, always "function".
+    #[serde(rename = "type")]
+    pub tool_type: String,
+    /// Function definition.
+    pub function: OpenAIFunction,
+}
+
+impl OpenAITool {
+    /// Function tool with a JSON Schema for its parameters.
+    pub fn function(
+        name: impl Into<String>,
+        description: impl Into<String>,
+        parameters: Value,
+    ) -> Self {
+        Self {
+            tool_type: "function".to_string(),
+            function: OpenAIFunction {
+                name: name.into(),
+                description: Some(description.into()),
+                parameters: Some(parameters),
+                strict: None,
+            },
+        }
+    }
+}
+
+impl From<OpenAITool> for Value {
+    fn from(tool: OpenAITool) -> Self {
+        let mut function = serde_json::Map::new();
+        function.insert("name".to_string(), tool.function.name.into());
+        if let Some(description) = tool.function.description {
+            function.insert("description".to_string(), description.into());
+        }
+        if let Some(parameters) = tool.function.parameters {
+            function.insert("parameters".to_string(), parameters);
+        }
+        if let Some(strict) = tool.function.strict {
+            function.insert("strict".to_string(), strict.into());
+        }
+        serde_json::json!({ "type": tool.tool_type, "function": function })
+    }
+}
+
+/// Function definition of a tool.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct OpenAIFunction {
+    /// Function name.
+    pub name: String,
+    /// What the function does.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// JSON Schema of the arguments.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parameters: Option<Value>,
+    /// Require arguments to match the schema exactly.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub strict: Option<bool>,
+}
+
+/// OpenAI chat completion response.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct OpenAIResponse {
+    /// Response identifier.
+    pub id: String,
+    /// Object type ("chat.completion").
+    pub object: String,
+    /// Unix timestamp of creation.
+    pub created: u64,
+    /// Model used for completion.
+    pub model: String,
+    /// Completion choices.
+    pub choices: Vec<Choice>,
+    /// Token usage statistics.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub usage: Option<OpenAIUsage>,
+}
+
+impl OpenAIResponse {
+    /// Text of the first choice.
+    pub fn text(&self) -> String {
+        self.choices.first().map(|c| c.message.text()).unwrap_or_default()
+    }
+}
+
+/// Single completion choice.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Choice {
+    /// Choice index.
+    pub index: u32,
+    /// Generated message.
+    pub message: OpenAIMessage,
+    /// Reason for completion (stop, length, tool_calls).
+    pub finish_reason: Option<String>,
+}
+
+/// Token usage statistics.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
 pub struct OpenAIUsage {
-    /// Number of tokens in the prompt.
+    /// Tokens in prompt.
+    #[serde(default)]
     pub prompt_tokens: u32,
-    /// Number of tokens in the completion.
+    /// Tokens in completion.
+    #[serde(default)]
     pub completion_tokens: u32,
-    /// Total tokens used (prompt + completion).
+    /// Total tokens used.
+    #[serde(default)]
     pub total_tokens: u32,
+    /// Prompt token breakdown.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prompt_tokens_details: Option<PromptTokensDetails>,
+    /// Completion token breakdown.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub completion_tokens_details: Option<CompletionTokensDetails>,
+}
+
+/// Prompt token details.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub struct PromptTokensDetails {
+    /// Tokens from cache.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cached_tokens: Option<u32>,
+}
+
+/// Completion token details.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub struct CompletionTokensDetails {
+    /// Tokens used for reasoning.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reasoning_tokens: Option<u32>,
+}
+
+/// One SSE chunk of a streaming chat completion.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ChatCompletionChunk {
+    /// Unique response identifier.
+    #[serde(default)]
+    pub id: String,
+    /// Model that generated the response.
+    #[serde(default)]
+    pub model: String,
+    /// Creation time (Unix seconds).
+    #[serde(default)]
+    pub created: i64,
+    /// Incremental choices.
+    #[serde(default)]
+    pub choices: Vec<ChunkChoice>,
+    /// Token usage, on the final chunk.
+    #[serde(default)]
+    pub usage: Option<OpenAIUsage>,
+}
+
+/// Incremental choice in a streaming chunk.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ChunkChoice {
+    /// Index of this choice in the list.
+    #[serde(default)]
+    pub index: u32,
+    /// Content added by this chunk.
+    #[serde(default)]
+    pub delta: ChatDelta,
+    /// Reason generation stopped, on the last chunk of the choice.
+    #[serde(default)]
+    pub finish_reason: Option<String>,
+}
+
+/// Incremental content delta.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct ChatDelta {
+    /// Role of the message author (first chunk only).
+    #[serde(default)]
+    pub role: Option<OpenAIRole>,
+    /// Incremental text content.
+    #[serde(default)]
+    pub content: Option<String>,
+    /// Incremental reasoning text.
+    #[serde(default)]
+    pub reasoning_content: Option<String>,
+    /// Incremental tool calls.
+    #[serde(default)]
+    pub tool_calls: Option<Vec<ToolCallDelta>>,
+}
+
+/// Incremental tool call; `arguments` arrive in pieces.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ToolCallDelta {
+    /// Index of the tool call within the message.
+    #[serde(default)]
+    pub index: u32,
+    /// Tool call identifier (first piece only).
+    #[serde(default)]
+    pub id: Option<String>,
+    /// Function name and argument fragment.
+    #[serde(default)]
+    pub function: ToolFunction,
+}
+
+/// Request body for `/v1/images/generations`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct ImageGenerationRequest {
+    /// Image model (e.g., "gemini-3-pro-image").
+    pub model: String,
+    /// Description of the image.
+    pub prompt: String,
+    /// Number of images.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub n: Option<u32>,
+    /// Size such as "1024x1024".
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub size: Option<String>,
+    /// Quality ("standard", "hd").
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quality: Option<String>,
+    /// Style ("vivid", "natural").
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub style: Option<String>,
+    /// "url" or "b64_json".
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub response_format: Option<String>,
+}
+
+/// Response from the Images API.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ImageResponse {
+    /// Creation time (Unix seconds).
+    #[serde(default)]
+    pub created: i64,
+    /// Generated images.
+    pub data: Vec<ImageData>,
+}
+
+/// One generated image.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ImageData {
+    /// Image URL or `data:` URI.
+    #[serde(default)]
+    pub url: Option<String>,
+    /// Base64-encoded image.
+    #[serde(default)]
+    pub b64_json: Option<String>,
+    /// Prompt after rewriting by the model.
+    #[serde(default)]
+    pub revised_prompt: Option<String>,
 }
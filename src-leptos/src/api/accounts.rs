@@ -45,6 +45,7 @@ impl ApiAccount {
             proxy_disabled_at: None,
             protected_models: HashSet::new(),
             proxy_url: None,
+            groups: Vec::new(),
             created_at: 0,
             last_used: 0,
         }
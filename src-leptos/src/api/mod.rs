@@ -5,6 +5,7 @@
 
 mod accounts;
 mod config;
+pub(crate) mod monitor_stream;
 mod proxy;
 mod system;
 
@@ -0,0 +1,164 @@
+//! Live monitor event stream (`/api/monitor/stream`).
+//!
+//! `EventSource` cannot send the `Authorization` header, so the SSE body is read
+//! through `fetch`. After a disconnect the stream reconnects with `Last-Event-ID`
+//! and the server replays what was missed.
+
+use super::{get_stored_api_key, API_BASE};
+use crate::api_models::MonitorEvent;
+use leptos::task::spawn_local;
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{AbortController, ReadableStreamDefaultReader, Request, RequestInit, Response};
+
+const RECONNECT_DELAY_MS: u32 = 2000;
+
+pub(crate) enum StreamMessage {
+    Event(MonitorEvent),
+    /// Events were missed and cannot be replayed; reload state over REST.
+    Resync,
+}
+
+/// Running subscription. Dropping it closes the connection and stops reconnecting.
+pub(crate) struct MonitorStream {
+    stopped: Rc<Cell<bool>>,
+    abort: Rc<RefCell<Option<AbortController>>>,
+}
+
+impl Drop for MonitorStream {
+    fn drop(&mut self) {
+        self.stopped.set(true);
+        if let Some(controller) = self.abort.borrow_mut().take() {
+            controller.abort();
+        }
+    }
+}
+
+pub(crate) fn connect_monitor_stream(
+    on_message: impl Fn(StreamMessage) + 'static,
+) -> MonitorStream {
+    let stopped = Rc::new(Cell::new(false));
+    let abort = Rc::new(RefCell::new(None));
+    let handle = MonitorStream { stopped: stopped.clone(), abort: abort.clone() };
+
+    spawn_local(async move {
+        let mut last_id = None;
+        while !stopped.get() {
+            if let Err(e) = read_stream(&mut last_id, &abort, &on_message).await {
+                if stopped.get() {
+                    break;
+                }
+                log::warn!("Monitor stream disconnected: {}", e);
+            }
+            gloo_timers::future::TimeoutFuture::new(RECONNECT_DELAY_MS).await;
+        }
+    });
+
+    handle
+}
+
+fn js_err(e: JsValue) -> String {
+    format!("{:?}", e)
+}
+
+async fn read_stream(
+    last_id: &mut Option<u64>,
+    abort: &RefCell<Option<AbortController>>,
+    on_message: &dyn Fn(StreamMessage),
+) -> Result<(), String> {
+    let controller = AbortController::new().map_err(js_err)?;
+    let opts = RequestInit::new();
+    opts.set_method("GET");
+    opts.set_signal(Some(&controller.signal()));
+
+    let url = format!("{}/monitor/stream", API_BASE);
+    let request = Request::new_with_str_and_init(&url, &opts).map_err(js_err)?;
+    let headers = request.headers();
+    headers.set("Accept", "text/event-stream").map_err(js_err)?;
+    if let Some(api_key) = get_stored_api_key() {
+        headers.set("Authorization", &format!("Bearer {}", api_key)).map_err(js_err)?;
+    }
+    if let Some(id) = last_id {
+        headers.set("Last-Event-ID", &id.to_string()).map_err(js_err)?;
+    }
+    *abort.borrow_mut() = Some(controller);
+
+    let window = web_sys::window().ok_or("No window")?;
+    let resp: Response = JsFuture::from(window.fetch_with_request(&request))
+        .await
+        .map_err(js_err)?
+        .dyn_into()
+        .map_err(js_err)?;
+    if !resp.ok() {
+        return Err(format!("HTTP error: {}", resp.status()));
+    }
+    // getReader() without options always returns a default reader
+    let reader: ReadableStreamDefaultReader =
+        resp.body().ok_or("Empty stream body")?.get_reader().unchecked_into();
+
+    let mut buffer: Vec<u8> = Vec::new();
+    loop {
+        let chunk = JsFuture::from(reader.read()).await.map_err(js_err)?;
+        let done = js_sys::Reflect::get(&chunk, &JsValue::from_str("done")).map_err(js_err)?;
+        if done.as_bool().unwrap_or(true) {
+            return Err("Stream closed".to_string());
+        }
+        let value = js_sys::Reflect::get(&chunk, &JsValue::from_str("value")).map_err(js_err)?;
+        buffer.extend(js_sys::Uint8Array::new(&value).to_vec());
+
+        while let Some(end) = buffer.windows(2).position(|w| w == b"\n\n") {
+            let frame: Vec<u8> = buffer.drain(..end + 2).collect();
+            if let Some(message) = parse_frame(&String::from_utf8_lossy(&frame), last_id) {
+                on_message(message);
+            }
+        }
+    }
+}
+
+/// Parse one SSE frame, advancing `last_id` past it. Keepalive comments yield `None`.
+fn parse_frame(frame: &str, last_id: &mut Option<u64>) -> Option<StreamMessage> {
+    let mut event = None;
+    let mut data = String::new();
+    for line in frame.lines() {
+        if let Some(value) = line.strip_prefix("event:") {
+            event = Some(value.trim());
+        } else if let Some(value) = line.strip_prefix("data:") {
+            data.push_str(value.trim_start());
+        } else if let Some(id) = line.strip_prefix("id:").and_then(|v| v.trim().parse().ok()) {
+            *last_id = Some(id);
+        }
+    }
+
+    match event? {
+        "resync" => Some(StreamMessage::Resync),
+        _ => serde_json::from_str(&data).ok().map(StreamMessage::Event),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api_models::MonitorEventPayload;
+
+    #[test]
+    fn parses_event_frames_and_tracks_id() {
+        let mut last_id = None;
+        let frame = "id: 42\nevent: quota_updated\ndata: {\"id\":42,\"timestamp\":1,\
+                     \"type\":\"quota_updated\",\"data\":{\"account_id\":\"a\",\
+                     \"quota\":{\"models\":[],\"last_updated\":0}}}\n\n";
+        let Some(StreamMessage::Event(event)) = parse_frame(frame, &mut last_id) else {
+            panic!("expected event");
+        };
+        assert_eq!(event.id, 42);
+        assert!(matches!(event.payload, MonitorEventPayload::QuotaUpdated { .. }));
+        assert_eq!(last_id, Some(42));
+
+        assert!(parse_frame(":\n\n", &mut last_id).is_none());
+        assert!(matches!(
+            parse_frame("event: resync\ndata: {}\n\n", &mut last_id),
+            Some(StreamMessage::Resync)
+        ));
+    }
+}
@@ -37,6 +37,10 @@ pub(crate) async fn get_proxy_logs(limit: Option<usize>) -> Result<Vec<ProxyRequ
     api_get(&endpoint).await
 }
 
+pub(crate) async fn get_usage(period: SpendPeriod) -> Result<UsageReport, String> {
+    api_get(&format!("/usage?period={}", period.as_str())).await
+}
+
 pub(crate) async fn set_proxy_monitor_enabled(_enabled: bool) -> Result<(), String> {
     Ok(())
 }
@@ -4,4 +4,9 @@ pub(crate) use antigravity_types::models::{
     Account, AppConfig, DashboardStats, ProxyRequestLog, ProxyStats, ProxyStatus, RefreshStats,
     UpdateInfo, UpstreamProxyMode,
 };
-pub(crate) use antigravity_types::models::{Protocol, ProxyAuthMode, ZaiDispatchMode};
+pub(crate) use antigravity_types::models::{
+    AccountState, MonitorEvent, MonitorEventPayload, Protocol, ProxyAuthMode, ZaiDispatchMode,
+};
+pub(crate) use antigravity_types::models::{
+    BudgetAction, BudgetStatus, SpendPeriod, UsageDimension, UsageReport, UsageRollup,
+};
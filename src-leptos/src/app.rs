@@ -2,7 +2,7 @@
 
 use crate::api::auth::is_authenticated;
 use crate::components::Sidebar;
-use crate::pages::{Accounts, ApiProxy, Dashboard, Login, Monitor, Settings};
+use crate::pages::{Accounts, ApiProxy, Dashboard, Login, Monitor, Settings, Usage};
 use leptos::prelude::*;
 use leptos::task::spawn_local;
 use leptos_router::{
@@ -58,6 +58,7 @@ pub fn App() -> impl IntoView {
                 <Route path=path!("/proxy") view=AuthenticatedApp />
                 <Route path=path!("/settings") view=AuthenticatedApp />
                 <Route path=path!("/monitor") view=AuthenticatedApp />
+                <Route path=path!("/usage") view=AuthenticatedApp />
             </Routes>
         </Router>
     }
@@ -92,6 +93,7 @@ fn AuthenticatedApp() -> impl IntoView {
                     <Route path=path!("/proxy") view=ApiProxy />
                     <Route path=path!("/settings") view=Settings />
                     <Route path=path!("/monitor") view=Monitor />
+                    <Route path=path!("/usage") view=Usage />
                 </Routes>
             </main>
         </div>
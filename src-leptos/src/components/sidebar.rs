@@ -19,6 +19,8 @@ const ICON_SETTINGS: &str = r#"<svg xmlns="http://www.w3.org/2000/svg" width="20
 
 const ICON_MONITOR: &str = r#"<svg xmlns="http://www.w3.org/2000/svg" width="20" height="20" viewBox="0 0 24 24" fill="none" stroke="currentColor" stroke-width="1.5" stroke-linecap="round" stroke-linejoin="round"><path d="M22 12h-4l-3 9L9 3l-3 9H2"/></svg>"#;
 
+const ICON_USAGE: &str = r#"<svg xmlns="http://www.w3.org/2000/svg" width="20" height="20" viewBox="0 0 24 24" fill="none" stroke="currentColor" stroke-width="1.5" stroke-linecap="round" stroke-linejoin="round"><line x1="12" y1="2" x2="12" y2="22"/><path d="M17 5H9.5a3.5 3.5 0 0 0 0 7h5a3.5 3.5 0 0 1 0 7H6"/></svg>"#;
+
 const ICON_LOGO: &str = r#"<svg xmlns="http://www.w3.org/2000/svg" width="24" height="24" viewBox="0 0 24 24" fill="none" stroke="currentColor" stroke-width="1.5" stroke-linecap="round" stroke-linejoin="round"><path d="M4.5 16.5c-1.5 1.26-2 5-2 5s3.74-.5 5-2c.71-.84.7-2.13-.09-2.91a2.18 2.18 0 0 0-2.91-.09z"/><path d="m12 15-3-3a22 22 0 0 1 2-3.95A12.88 12.88 0 0 1 22 2c0 2.72-.78 7.5-6 11a22.35 22.35 0 0 1-4 2z"/><path d="M9 12H4s.55-3.03 2-4c1.62-1.08 5 0 5 0"/><path d="M12 15v5s3.03-.55 4-2c1.08-1.62 0-5 0-5"/></svg>"#;
 
 fn is_path_active(current: &str, path: &str) -> bool {
@@ -33,10 +35,11 @@ fn is_path_active(current: &str, path: &str) -> bool {
 pub(crate) fn Sidebar() -> impl IntoView {
     let location = use_location();
 
-    let nav_items: [(&str, &str, &str); 4] = [
+    let nav_items: [(&str, &str, &str); 5] = [
         ("Dashboard", "/", ICON_DASHBOARD),
         ("Accounts", "/accounts", ICON_ACCOUNTS),
         ("API Proxy", "/proxy", ICON_PROXY),
+        ("Usage", "/usage", ICON_USAGE),
         ("Settings", "/settings", ICON_SETTINGS),
     ];
 
@@ -9,7 +9,8 @@ use current_account::CurrentAccountSection;
 use tiers::{QuickActionsSection, TierSection};
 
 use crate::api::commands;
-use crate::api_models::DashboardStats;
+use crate::api::monitor_stream::{connect_monitor_stream, StreamMessage};
+use crate::api_models::{AccountState, DashboardStats, MonitorEventPayload};
 use crate::app::AppState;
 use crate::components::{Button, ButtonVariant, StatsCard};
 use leptos::prelude::*;
@@ -32,6 +33,35 @@ pub(crate) fn Dashboard() -> impl IntoView {
         });
     };
 
+    // Keep quotas and disabled flags current while the dashboard is open
+    let live_stream = connect_monitor_stream(move |message| match message {
+        StreamMessage::Event(event) => match event.payload {
+            MonitorEventPayload::QuotaUpdated { account_id, quota } => {
+                state.accounts.update(|accounts| {
+                    if let Some(account) = accounts.iter_mut().find(|a| a.id == account_id) {
+                        account.update_quota(quota);
+                    }
+                });
+            },
+            MonitorEventPayload::AccountState(change) if change.state == AccountState::Disabled => {
+                state.accounts.update(|accounts| {
+                    if let Some(account) = accounts.iter_mut().find(|a| a.id == change.account_id) {
+                        account.disabled = true;
+                        account.disabled_reason = change.reason;
+                    }
+                });
+            },
+            _ => {},
+        },
+        StreamMessage::Resync => spawn_local(async move {
+            if let Ok(accounts) = commands::list_accounts().await {
+                state.accounts.set(accounts);
+            }
+        }),
+    });
+    // Dropped (closing the connection) when the page is unmounted
+    StoredValue::new_local(live_stream);
+
     let stats = Memo::new(move |_| DashboardStats::from_accounts(&state.accounts.get()));
 
     let current_account = Memo::new(move |_| {
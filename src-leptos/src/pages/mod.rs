@@ -6,6 +6,7 @@ pub(crate) mod login;
 pub(crate) mod monitor;
 pub(crate) mod proxy;
 pub(crate) mod settings;
+pub(crate) mod usage;
 
 pub(crate) use accounts::Accounts;
 pub(crate) use dashboard::Dashboard;
@@ -13,3 +14,4 @@ pub(crate) use login::Login;
 pub(crate) use monitor::Monitor;
 pub(crate) use proxy::ApiProxy;
 pub(crate) use settings::Settings;
+pub(crate) use usage::Usage;
@@ -4,17 +4,68 @@ pub(crate) mod formatters;
 pub(crate) mod log_detail;
 
 use crate::api::commands;
-use crate::api_models::{ProxyRequestLog, ProxyStats};
+use crate::api::monitor_stream::{connect_monitor_stream, MonitorStream, StreamMessage};
+use crate::api_models::{
+    AccountState, MonitorEvent, MonitorEventPayload, ProxyRequestLog, ProxyStats,
+};
 use crate::app::AppState;
 use crate::components::{Button, ButtonVariant};
 use formatters::{format_timestamp, format_tokens};
-use gloo_timers::callback::Interval;
 use leptos::prelude::*;
 use leptos::task::spawn_local;
 use log_detail::LogDetailModal;
 use std::cell::RefCell;
 use std::rc::Rc;
 
+const MAX_LOGS: usize = 100;
+const MAX_ACCOUNT_EVENTS: usize = 5;
+
+/// Add a streamed log to the top of the list and count it in the stats.
+fn apply_request_log(
+    logs: RwSignal<Vec<ProxyRequestLog>>,
+    stats: RwSignal<ProxyStats>,
+    log: ProxyRequestLog,
+) {
+    // The initial REST load may already contain logs replayed by the stream
+    if logs.with_untracked(|l| l.iter().any(|existing| existing.id == log.id)) {
+        return;
+    }
+    stats.update(|s| {
+        s.total_requests += 1;
+        if log.status >= 400 {
+            s.error_count += 1;
+        } else {
+            s.success_count += 1;
+        }
+        s.total_input_tokens += u64::from(log.input_tokens.unwrap_or(0));
+        s.total_output_tokens += u64::from(log.output_tokens.unwrap_or(0));
+    });
+    logs.update(|l| {
+        l.insert(0, log);
+        l.truncate(MAX_LOGS);
+    });
+}
+
+fn describe_account_event(event: &MonitorEvent) -> Option<String> {
+    let MonitorEventPayload::AccountState(change) = &event.payload else {
+        return None;
+    };
+    let state = match change.state {
+        AccountState::RateLimited => "rate-limited",
+        AccountState::CircuitOpened => "circuit opened",
+        AccountState::CircuitClosed => "circuit closed",
+        AccountState::Disabled => "disabled",
+    };
+    let mut text = format!("{} {}", change.account_id, state);
+    if let Some(model) = &change.model {
+        text.push_str(&format!(" ({})", model));
+    }
+    if let Some(secs) = change.retry_after_sec {
+        text.push_str(&format!(" for {}s", secs));
+    }
+    Some(text)
+}
+
 /// Monitor page for real-time request logging.
 #[component]
 pub(crate) fn Monitor() -> impl IntoView {
@@ -32,7 +83,7 @@ pub(crate) fn Monitor() -> impl IntoView {
             loading.set(true);
         }
         spawn_local(async move {
-            if let Ok(new_logs) = commands::get_proxy_logs(Some(100)).await {
+            if let Ok(new_logs) = commands::get_proxy_logs(Some(MAX_LOGS)).await {
                 logs.set(new_logs);
             }
             if let Ok(new_stats) = commands::get_proxy_stats().await {
@@ -48,22 +99,31 @@ pub(crate) fn Monitor() -> impl IntoView {
         load_data(true);
     });
 
-    let poller = Rc::new(RefCell::new(None::<Interval>));
+    let account_events = RwSignal::new(Vec::<(u64, String)>::new());
+
+    let live_stream = Rc::new(RefCell::new(None::<MonitorStream>));
     Effect::new(move |_| {
-        let should_poll = logging_enabled.get() && state.proxy_status.get().running;
-        if should_poll {
-            if poller.borrow().is_none() {
-                load_data(false);
-                let poller_ref = poller.clone();
-                let interval = Interval::new(2000, move || {
-                    if logging_enabled.get() && state.proxy_status.get().running {
-                        load_data(false);
-                    }
+        let should_stream = logging_enabled.get() && state.proxy_status.get().running;
+        if should_stream {
+            if live_stream.borrow().is_none() {
+                let stream = connect_monitor_stream(move |message| match message {
+                    StreamMessage::Event(event) => {
+                        if let Some(text) = describe_account_event(&event) {
+                            account_events.update(|events| {
+                                events.insert(0, (event.id, text));
+                                events.truncate(MAX_ACCOUNT_EVENTS);
+                            });
+                        } else if let MonitorEventPayload::RequestLog(log) = event.payload {
+                            apply_request_log(logs, stats, *log);
+                        }
+                    },
+                    StreamMessage::Resync => load_data(false),
                 });
-                *poller_ref.borrow_mut() = Some(interval);
+                *live_stream.borrow_mut() = Some(stream);
+                load_data(false);
             }
         } else {
-            poller.borrow_mut().take();
+            live_stream.borrow_mut().take();
         }
     });
 
@@ -201,6 +261,16 @@ pub(crate) fn Monitor() -> impl IntoView {
                 </div>
             </div>
 
+            <Show when=move || !account_events.get().is_empty()>
+                <ul class="account-events">
+                    <For
+                        each=move || account_events.get()
+                        key=|(id, _)| *id
+                        children=move |(_, text)| view! { <li>{text}</li> }
+                    />
+                </ul>
+            </Show>
+
             <div class="logs-table-container">
                 <table class="logs-table">
                     <thead>
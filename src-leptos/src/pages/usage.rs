@@ -0,0 +1,208 @@
+//! Usage page - spend by API key, model and account, plus budget status
+
+use crate::api::commands;
+use crate::api_models::{
+    BudgetAction, BudgetStatus, SpendPeriod, UsageDimension, UsageReport, UsageRollup,
+};
+use crate::pages::monitor::formatters::format_tokens;
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+
+/// Format microdollars as dollars.
+#[allow(
+    clippy::as_conversions,
+    clippy::cast_precision_loss,
+    clippy::float_arithmetic,
+    reason = "display only"
+)]
+fn format_usd(micros: u64) -> String {
+    format!("${:.2}", micros as f64 / 1_000_000.0)
+}
+
+#[allow(
+    clippy::as_conversions,
+    clippy::cast_precision_loss,
+    clippy::cast_possible_truncation,
+    clippy::float_arithmetic,
+    reason = "bar width percentage"
+)]
+fn budget_percent(status: &BudgetStatus) -> u32 {
+    ((status.spent as f64 / status.budget.limit.max(1) as f64) * 100.0).min(100.0) as u32
+}
+
+fn dimension_title(dimension: UsageDimension) -> &'static str {
+    match dimension {
+        UsageDimension::ApiKey => "By API Key",
+        UsageDimension::Model => "By Model",
+        UsageDimension::Account => "By Account",
+    }
+}
+
+/// Rollups of the latest period only, most expensive first.
+fn latest_rollups(report: &UsageReport, dimension: UsageDimension) -> Vec<UsageRollup> {
+    let latest = report.rollups.iter().map(|r| r.period_start).max();
+    let mut rows: Vec<_> = report
+        .rollups
+        .iter()
+        .filter(|r| r.dimension == dimension && Some(r.period_start) == latest)
+        .cloned()
+        .collect();
+    rows.sort_by_key(|r| std::cmp::Reverse(r.totals.cost));
+    rows
+}
+
+#[component]
+fn BudgetRow(status: BudgetStatus) -> impl IntoView {
+    let percent = budget_percent(&status);
+    let fill_class = if status.exceeded {
+        "quota-fill quota-fill--critical"
+    } else if percent >= 80 {
+        "quota-fill quota-fill--warning"
+    } else {
+        "quota-fill quota-fill--good"
+    };
+    let target = status.budget.target.clone().unwrap_or_else(|| "all traffic".to_string());
+    let period = status.budget.period.as_str();
+    let action = match status.budget.action {
+        BudgetAction::Warn => "warn",
+        BudgetAction::Block => "block",
+    };
+
+    view! {
+        <tr>
+            <td>{status.budget.name.clone()}</td>
+            <td>{target}</td>
+            <td>{period}</td>
+            <td>
+                <span class="quota-bar">
+                    <span class=fill_class style=format!("width: {}%", percent)></span>
+                </span>
+                <span class="quota-text">
+                    {format!("{} / {}", format_usd(status.spent), format_usd(status.budget.limit))}
+                </span>
+            </td>
+            <td>{action}</td>
+        </tr>
+    }
+}
+
+#[component]
+fn RollupTable(report: ReadSignal<UsageReport>, dimension: UsageDimension) -> impl IntoView {
+    let rows = Memo::new(move |_| latest_rollups(&report.get(), dimension));
+
+    view! {
+        <section class="usage-section">
+            <h2>{dimension_title(dimension)}</h2>
+            <Show
+                when=move || !rows.get().is_empty()
+                fallback=|| view! { <p class="empty-state">"No usage recorded"</p> }
+            >
+                <table class="logs-table">
+                    <thead>
+                        <tr>
+                            <th>"Key"</th>
+                            <th>"Requests"</th>
+                            <th>"Input"</th>
+                            <th>"Output"</th>
+                            <th>"Cached"</th>
+                            <th>"Cost"</th>
+                        </tr>
+                    </thead>
+                    <tbody>
+                        <For
+                            each=move || rows.get()
+                            key=|row| row.key.clone()
+                            children=move |row| view! {
+                                <tr>
+                                    <td>{row.key.clone()}</td>
+                                    <td>{row.totals.requests}</td>
+                                    <td>{format_tokens(row.totals.input_tokens)}</td>
+                                    <td>{format_tokens(row.totals.output_tokens)}</td>
+                                    <td>{format_tokens(row.totals.cached_tokens)}</td>
+                                    <td>{format_usd(row.totals.cost)}</td>
+                                </tr>
+                            }
+                        />
+                    </tbody>
+                </table>
+            </Show>
+        </section>
+    }
+}
+
+/// Usage page for spend accounting.
+#[component]
+pub(crate) fn Usage() -> impl IntoView {
+    let (report, set_report) = signal(UsageReport::default());
+    let period = RwSignal::new(SpendPeriod::Monthly);
+    let error = RwSignal::new(Option::<String>::None);
+
+    Effect::new(move |_| {
+        let period = period.get();
+        spawn_local(async move {
+            match commands::get_usage(period).await {
+                Ok(new_report) => {
+                    set_report.set(new_report);
+                    error.set(None);
+                },
+                Err(e) => error.set(Some(e)),
+            }
+        });
+    });
+
+    let budgets = Memo::new(move |_| report.get().budgets);
+
+    view! {
+        <div class="page usage">
+            <header class="page-header">
+                <div>
+                    <h1>"Usage"</h1>
+                    <p class="subtitle">"Spend by API key, model and account"</p>
+                </div>
+                <div class="quick-filters">
+                    <button
+                        class=move || if period.get() == SpendPeriod::Daily { "active" } else { "" }
+                        on:click=move |_| period.set(SpendPeriod::Daily)
+                    >"Today"</button>
+                    <button
+                        class=move || if period.get() == SpendPeriod::Monthly { "active" } else { "" }
+                        on:click=move |_| period.set(SpendPeriod::Monthly)
+                    >"This Month"</button>
+                </div>
+            </header>
+
+            <Show when=move || error.get().is_some()>
+                <div class="alert alert--error">{move || error.get().unwrap_or_default()}</div>
+            </Show>
+
+            <Show when=move || !budgets.get().is_empty()>
+                <section class="usage-section">
+                    <h2>"Budgets"</h2>
+                    <table class="logs-table">
+                        <thead>
+                            <tr>
+                                <th>"Budget"</th>
+                                <th>"Target"</th>
+                                <th>"Period"</th>
+                                <th>"Spent"</th>
+                                <th>"Action"</th>
+                            </tr>
+                        </thead>
+                        <tbody>
+                            <For
+                                each=move || budgets.get()
+                                key=|status| (status.budget.name.clone(), status.spent)
+                                children=move |status| view! { <BudgetRow status=status /> }
+                            />
+                        </tbody>
+                    </table>
+                </section>
+            </Show>
+
+            {UsageDimension::ALL
+                .into_iter()
+                .map(|dimension| view! { <RollupTable report=report dimension=dimension /> })
+                .collect_view()}
+        </div>
+    }
+}